@@ -53,6 +53,7 @@ exclude_crates=(
   reth-rpc-engine-api
   reth-rpc-eth-api
   reth-rpc-eth-types
+  reth-rpc-graphql
   reth-rpc-layer
   reth-stages
   reth-engine-local
@@ -5,9 +5,15 @@ static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::ne
 
 use clap::Parser;
 use reth::{args::RessArgs, cli::Cli, ress::install_ress_subprotocol};
+use reth_chainspec::ChainSpec;
+use reth_db::{database_metrics::DatabaseMetrics, Database, DatabaseEnv, MemoryDatabase};
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
-use reth_node_builder::NodeHandle;
-use reth_node_ethereum::EthereumNode;
+use reth_node_builder::{
+    components::BasicPayloadServiceBuilder, NodeBuilder, NodeHandle, WithLaunchContext,
+};
+use reth_node_ethereum::{EthereumAddOns, EthereumNode, EthereumPayloadBuilder};
+use reth_payload_util::BundlePool;
+use std::sync::Arc;
 use tracing::info;
 
 fn main() {
@@ -18,28 +24,52 @@ fn main() {
         unsafe { std::env::set_var("RUST_BACKTRACE", "1") };
     }
 
-    if let Err(err) =
-        Cli::<EthereumChainSpecParser, RessArgs>::parse().run(async move |builder, ress_args| {
-            info!(target: "reth::cli", "Launching node");
-            let NodeHandle { node, node_exit_future } =
-                builder.node(EthereumNode::default()).launch_with_debug_capabilities().await?;
-
-            // Install ress subprotocol.
-            if ress_args.enabled {
-                install_ress_subprotocol(
-                    ress_args,
-                    node.provider,
-                    node.evm_config,
-                    node.network,
-                    node.task_executor,
-                    node.add_ons_handle.engine_events.new_listener(),
-                )?;
-            }
-
-            node_exit_future.await
-        })
+    // Nodes with an in-memory database (`--datadir.memory`) are launched the same way.
+    if let Err(err) = Cli::<EthereumChainSpecParser, RessArgs>::parse()
+        .run_with_memory(launch_node::<Arc<DatabaseEnv>>, launch_node::<Arc<MemoryDatabase>>)
     {
         eprintln!("Error: {err:?}");
         std::process::exit(1);
     }
 }
+
+/// Launches the node with the database of the `builder` and waits for it to exit.
+async fn launch_node<DB>(
+    builder: WithLaunchContext<NodeBuilder<DB, ChainSpec>>,
+    ress_args: RessArgs,
+) -> eyre::Result<()>
+where
+    DB: Database + DatabaseMetrics + Clone + Unpin + 'static,
+{
+    info!(target: "reth::cli", "Launching node");
+    // The payload builder and `mev_sendBundle` share the pool of submitted bundles.
+    let mut payload_builder = EthereumPayloadBuilder::default();
+    let mut add_ons = EthereumAddOns::default();
+    if builder.config().builder.bundles {
+        let bundle_pool = BundlePool::default();
+        payload_builder = payload_builder.with_bundle_pool(bundle_pool.clone());
+        add_ons = add_ons.with_bundle_pool(bundle_pool);
+    }
+    let NodeHandle { node, node_exit_future } = builder
+        .with_types::<EthereumNode>()
+        .with_components(
+            EthereumNode::components().payload(BasicPayloadServiceBuilder::new(payload_builder)),
+        )
+        .with_add_ons(add_ons)
+        .launch_with_debug_capabilities()
+        .await?;
+
+    // Install ress subprotocol.
+    if ress_args.enabled {
+        install_ress_subprotocol(
+            ress_args,
+            node.provider,
+            node.evm_config,
+            node.network,
+            node.task_executor,
+            node.add_ons_handle.engine_events.new_listener(),
+        )?;
+    }
+
+    node_exit_future.await
+}
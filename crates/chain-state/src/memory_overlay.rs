@@ -200,6 +200,10 @@ impl<N: NodePrimitives> StateProofProvider for MemoryOverlayStateProviderRef<'_,
         input.prepend_self(self.trie_input().clone());
         self.historical.witness(input, target)
     }
+
+    fn has_trie_changesets(&self) -> ProviderResult<bool> {
+        self.historical.has_trie_changesets()
+    }
 }
 
 impl<N: NodePrimitives> HashedPostStateProvider for MemoryOverlayStateProviderRef<'_, N> {
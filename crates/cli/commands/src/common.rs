@@ -66,6 +66,10 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
     where
         C: ChainSpecParser<ChainSpec = N::ChainSpec>,
     {
+        if self.datadir.memory {
+            eyre::bail!("--datadir.memory is only supported by the node command")
+        }
+
         let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
         let db_path = data_dir.db();
         let sf_path = data_dir.static_files();
@@ -92,7 +96,7 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         }
 
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
-        let (db, sfp) = match access {
+        let (db, mut sfp) = match access {
             AccessRights::RW => (
                 Arc::new(init_db(db_path, self.db.database_args())?),
                 StaticFileProvider::read_write(sf_path)?,
@@ -102,6 +106,9 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
                 StaticFileProvider::read_only(sf_path, false)?,
             ),
         };
+        if self.datadir.verify_static_file_checksums {
+            sfp = sfp.with_checksum_verification();
+        }
 
         let provider_factory = self.create_provider_factory(&config, db, sfp)?;
         if access.is_read_write() {
@@ -1,6 +1,6 @@
 use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
 use clap::{Parser, Subcommand};
-use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_db::version::{get_db_version, DatabaseVersionError, DB_VERSION};
 use reth_db_common::DbTool;
@@ -13,6 +13,7 @@ mod clear;
 mod diff;
 mod get;
 mod list;
+mod static_file;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -48,6 +49,8 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Static file utilities
+    StaticFile(static_file::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -64,7 +67,7 @@ macro_rules! db_ro_exec {
     };
 }
 
-impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
     /// Execute `db` command
     pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
         let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
@@ -135,6 +138,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::StaticFile(command) => {
+                command.execute::<N, C>(self.env).await?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -184,4 +190,47 @@ mod tests {
         .unwrap();
         assert_eq!(cmd.env.datadir.resolve_datadir(cmd.env.chain.chain).as_ref(), Path::new(&path));
     }
+
+    #[test]
+    fn parse_static_file_verify() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "static-file",
+            "verify",
+            "--segment",
+            "headers",
+            "--repair",
+            "--era.path",
+            "era",
+        ])
+        .unwrap();
+        assert!(matches!(cmd.command, Subcommands::StaticFile(_)));
+
+        // ERA files are only read when repairing.
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "static-file",
+            "verify",
+            "--era.path",
+            "era",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_static_file_recompress() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "static-file",
+            "recompress",
+            "--segment",
+            "transactions",
+            "--train-files",
+            "4",
+            "--max-dict-size",
+            "65536",
+        ])
+        .unwrap();
+        assert!(matches!(cmd.command, Subcommands::StaticFile(_)));
+    }
 }
@@ -0,0 +1,66 @@
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_provider::{
+    writer::UnifiedStorageWriter, DatabaseProviderFactory, StageCheckpointReader,
+    StaticFileProviderFactory,
+};
+use reth_stages::StageId;
+use reth_static_file_types::StaticFileSegment;
+use tracing::info;
+
+/// The arguments for the `reth db static-file migrate-changesets` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Number of blocks to move before committing.
+    #[arg(long, default_value_t = 10_000, value_parser = clap::value_parser!(u64).range(1..))]
+    blocks_per_commit: u64,
+}
+
+impl Command {
+    /// Execute `db static-file migrate-changesets` command
+    pub async fn execute<N, C>(self, env: EnvironmentArgs<C>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>,
+    {
+        let Environment { provider_factory, .. } = env.init::<N>(AccessRights::RW)?;
+
+        // Changesets are only written up to the block the state was executed to.
+        let tip = provider_factory
+            .provider()?
+            .get_stage_checkpoint(StageId::Execution)?
+            .unwrap_or_default()
+            .block_number;
+
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            loop {
+                let from_block = provider_factory
+                    .static_file_provider()
+                    .get_highest_static_file_block(segment)
+                    .map_or(0, |highest| highest + 1);
+                let to_block = from_block.saturating_add(self.blocks_per_commit - 1).min(tip);
+
+                let provider_rw = provider_factory.database_provider_rw()?;
+                let Some(highest) =
+                    provider_rw.move_changesets_to_static_files(segment, to_block)?
+                else {
+                    info!(target: "reth::cli", %segment, "History is pruned, keeping changesets in the database");
+                    break
+                };
+                UnifiedStorageWriter::commit(provider_rw)?;
+
+                if from_block <= to_block {
+                    info!(target: "reth::cli", %segment, from_block, to_block, tip, "Moved changesets to static files");
+                }
+                if highest >= tip {
+                    break
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::common::{CliNodeTypes, EnvironmentArgs};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+
+mod migrate_changesets;
+mod recompress;
+mod verify;
+
+/// The arguments for the `reth db static-file` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    #[command(subcommand)]
+    subcommand: Subcommands,
+}
+
+impl Command {
+    /// Execute `db static-file` command
+    pub async fn execute<N, C>(self, env: EnvironmentArgs<C>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>,
+    {
+        match self.subcommand {
+            Subcommands::Verify(command) => command.execute::<N, C>(env).await,
+            Subcommands::Recompress(command) => command.execute::<N, C>(env).await,
+            Subcommands::MigrateChangesets(command) => command.execute::<N, C>(env).await,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Subcommands {
+    /// Verifies the integrity checksums of the static files and optionally repairs corrupted
+    /// rows
+    Verify(verify::Command),
+    /// Trains new zstd dictionaries from the most recent static files and recompresses the
+    /// complete static files with them
+    Recompress(recompress::Command),
+    /// Moves the account and storage changesets of an existing database to static files
+    MigrateChangesets(migrate_changesets::Command),
+}
@@ -0,0 +1,194 @@
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::static_file::iter_static_files;
+use reth_db_api::table::Value;
+use reth_nippy_jar::{
+    compression::{Compression, Compressors, Zstd},
+    NippyJar, NippyJarCursor, NippyJarError,
+};
+use reth_node_api::{ReceiptTy, TxTy};
+use reth_provider::StaticFileProviderFactory;
+use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
+use tracing::{info, warn};
+
+/// The arguments for the `reth db static-file recompress` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only recompress the static files of this segment.
+    #[arg(long, value_enum)]
+    segment: Option<StaticFileSegment>,
+
+    /// Number of the most recent complete static files of a segment to train its dictionaries
+    /// from.
+    #[arg(long, default_value_t = 2)]
+    train_files: usize,
+
+    /// Maximum number of rows to train the dictionaries of a segment from.
+    #[arg(long, default_value_t = 100_000)]
+    max_samples: usize,
+
+    /// Maximum size in bytes of each trained dictionary.
+    #[arg(long, default_value_t = 112_640)]
+    max_dict_size: usize,
+
+    /// The zstd compression level. A level of `0` uses zstd's default.
+    #[arg(long, default_value_t = 0)]
+    level: i32,
+}
+
+impl Command {
+    /// Execute `db static-file recompress` command
+    pub async fn execute<N, C>(self, env: EnvironmentArgs<C>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>,
+    {
+        let Environment { provider_factory, .. } = env.init::<N>(AccessRights::RW)?;
+        let static_file_provider = provider_factory.static_file_provider();
+
+        let static_files = iter_static_files(static_file_provider.directory())?;
+
+        for segment in StaticFileSegment::iter() {
+            if self.segment.is_some_and(|only| only != segment) {
+                continue
+            }
+            let Some(ranges) = static_files.get(&segment) else { continue };
+
+            // Static files that aren't complete may still be appended to.
+            let mut jars = Vec::new();
+            for (block_range, _) in ranges {
+                let fixed_range = static_file_provider.find_fixed_range(block_range.start());
+                if block_range.end() != fixed_range.end() {
+                    continue
+                }
+                let path = static_file_provider.directory().join(segment.filename(&fixed_range));
+                jars.push((fixed_range, NippyJar::<SegmentHeader>::load(&path)?));
+            }
+            if jars.is_empty() {
+                info!(target: "reth::cli", %segment, "No complete static files to recompress");
+                continue
+            }
+
+            let zstd = match self.train::<N>(segment, &jars) {
+                Ok(zstd) => zstd,
+                Err(err) => {
+                    warn!(target: "reth::cli", %segment, %err, "Failed to train dictionaries, skipping segment");
+                    continue
+                }
+            };
+            let version = jars
+                .iter()
+                .filter_map(|(_, jar)| jar.dictionary_version())
+                .max()
+                .map_or(1, |version| version + 1);
+            info!(target: "reth::cli", %segment, version, "Trained dictionaries");
+
+            let (mut total_before, mut total_after) = (0, 0);
+            for (fixed_range, jar) in jars {
+                let before = static_file_size(&jar)?;
+                let jar =
+                    jar.recompress(Compressors::Zstd(zstd.clone()), Some(version), |row| {
+                        uncompressed_row::<N>(segment, row)
+                    })?;
+                // The provider may still have the old file mapped.
+                static_file_provider.remove_cached_provider(segment, fixed_range.end());
+
+                let after = static_file_size(&jar)?;
+                info!(
+                    target: "reth::cli",
+                    path = ?jar.data_path(),
+                    before = %human_bytes(before as f64),
+                    after = %human_bytes(after as f64),
+                    "Recompressed static file"
+                );
+                total_before += before;
+                total_after += after;
+            }
+
+            info!(
+                target: "reth::cli",
+                %segment,
+                version,
+                before = %human_bytes(total_before as f64),
+                after = %human_bytes(total_after as f64),
+                "Recompressed segment"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Trains the dictionaries of a segment from the rows of its most recent static files.
+    fn train<N: CliNodeTypes>(
+        &self,
+        segment: StaticFileSegment,
+        jars: &[(SegmentRangeInclusive, NippyJar<SegmentHeader>)],
+    ) -> eyre::Result<Zstd> {
+        let mut samples = vec![Vec::new(); segment.columns()];
+        let mut rows = 0;
+
+        'jars: for (_, jar) in jars.iter().rev().take(self.train_files) {
+            let mut cursor = NippyJarCursor::new(jar)?;
+            while let Some(row) = cursor.next_row()? {
+                let values = uncompressed_row::<N>(segment, row)?;
+                for (column, value) in values.into_iter().enumerate() {
+                    // Empty values don't train anything.
+                    if !value.is_empty() {
+                        samples[column].push(value);
+                    }
+                }
+
+                rows += 1;
+                if rows >= self.max_samples {
+                    break 'jars
+                }
+            }
+        }
+
+        info!(target: "reth::cli", %segment, rows, "Training dictionaries");
+
+        let mut zstd =
+            Zstd::new(true, self.max_dict_size, segment.columns()).with_level(self.level);
+        zstd.prepare_compression(samples)?;
+        Ok(zstd)
+    }
+}
+
+/// Returns the column values of a static file row to compress with the trained dictionaries.
+///
+/// Transactions and receipts compress themselves with the fixed dictionaries of
+/// `reth-zstd-compressors`, which leaves little to compress for the trained ones. They're encoded
+/// again without it, which they can still be decoded from.
+fn uncompressed_row<N: CliNodeTypes>(
+    segment: StaticFileSegment,
+    row: Vec<&[u8]>,
+) -> Result<Vec<Vec<u8>>, NippyJarError> {
+    match segment {
+        StaticFileSegment::Transactions => encode_uncompressed::<TxTy<N>>(row),
+        StaticFileSegment::Receipts => encode_uncompressed::<ReceiptTy<N>>(row),
+        _ => Ok(row.into_iter().map(<[u8]>::to_vec).collect()),
+    }
+}
+
+/// Decodes the values of a row as `T` and encodes them again without compressing them.
+fn encode_uncompressed<T: Value>(row: Vec<&[u8]>) -> Result<Vec<Vec<u8>>, NippyJarError> {
+    row.into_iter()
+        .map(|value| {
+            let value =
+                T::decompress(value).map_err(|err| NippyJarError::Custom(err.to_string()))?;
+            Ok(reth_zstd_compressors::without_compression(|| value.compress().into()))
+        })
+        .collect()
+}
+
+/// Returns the size of the data, offsets and configuration files of a static file.
+fn static_file_size(jar: &NippyJar<SegmentHeader>) -> eyre::Result<u64> {
+    let mut size = 0;
+    for path in [jar.data_path().to_path_buf(), jar.offsets_path(), jar.config_path()] {
+        size += reth_fs_util::metadata(path)?.len();
+    }
+    Ok(size)
+}
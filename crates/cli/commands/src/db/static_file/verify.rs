@@ -0,0 +1,540 @@
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockHash, BlockNumber, TxNumber, U256};
+use clap::Parser;
+use eyre::{eyre, OptionExt};
+use futures::StreamExt;
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_util::get_secret_key;
+use reth_config::Config;
+use reth_db::{static_file::iter_static_files, DatabaseEnv};
+use reth_db_api::{models::CompactU256, table::Compress, tables, transaction::DbTx};
+use reth_era_downloader::read_dir;
+use reth_era_utils as era;
+use reth_network::{BlockDownloaderProvider, FetchClient};
+use reth_network_p2p::{
+    bodies::client::BodiesClient,
+    download::DownloadClient,
+    headers::client::{HeadersClient, HeadersRequest},
+};
+use reth_nippy_jar::{NippyJar, NippyJarError};
+use reth_node_api::{BodyTy, HeaderTy, NodeTypesWithDBAdapter};
+use reth_node_core::{
+    args::NetworkArgs,
+    dirs::{ChainPath, DataDirPath},
+};
+use reth_primitives_traits::{BlockBody, SealedHeader};
+use reth_provider::{
+    BlockBodyIndicesProvider, ChainSpecProvider, DBProvider, HeaderProvider, ProviderFactory,
+    StaticFileProviderFactory, TransactionsProvider,
+};
+use reth_static_file_types::{SegmentHeader, StaticFileSegment};
+use std::{
+    collections::HashMap,
+    ops::{Range, RangeInclusive},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{error, info, warn};
+
+/// The arguments for the `reth db static-file verify` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Only verify the static files of this segment.
+    #[arg(long, value_enum)]
+    segment: Option<StaticFileSegment>,
+
+    /// Re-fetch corrupted rows and rewrite the static files that contain them.
+    ///
+    /// Headers and transactions are re-fetched from the ERA1 files in `--era.path` if set, and
+    /// from peers otherwise. Rows of other segments can't be re-fetched, the blocks they belong
+    /// to have to be unwound with `reth stage unwind` and synced again.
+    #[arg(long, verbatim_doc_comment)]
+    repair: bool,
+
+    /// The path to a directory with ERA1 files to repair headers and transactions from.
+    #[arg(long = "era.path", value_name = "ERA_PATH", requires = "repair")]
+    era_path: Option<PathBuf>,
+
+    /// The path to a file with the known accumulator roots of all epochs.
+    ///
+    /// The ERA1 files in `--era.path` are verified against the root of their epoch before
+    /// they're used. Defaults to the roots that ship with reth for mainnet and sepolia.
+    #[arg(
+        long = "era.roots",
+        value_name = "ROOTS_FILE",
+        requires = "era_path",
+        verbatim_doc_comment
+    )]
+    era_roots: Option<PathBuf>,
+
+    #[command(flatten)]
+    network: NetworkArgs,
+}
+
+impl Command {
+    /// Execute `db static-file verify` command
+    pub async fn execute<N, C>(self, env: EnvironmentArgs<C>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>,
+    {
+        let access = if self.repair { AccessRights::RW } else { AccessRights::RO };
+        let Environment { provider_factory, config, data_dir } = env.init::<N>(access)?;
+        let static_file_provider = provider_factory.static_file_provider();
+        let provider = provider_factory.provider()?;
+
+        let static_files = iter_static_files(static_file_provider.directory())?;
+        let mut source = None;
+        let mut unrepaired = 0;
+
+        // Segments are verified in order, so that headers are repaired before the transactions
+        // that are validated against them.
+        for segment in StaticFileSegment::iter() {
+            if self.segment.is_some_and(|only| only != segment) {
+                continue
+            }
+            let Some(ranges) = static_files.get(&segment) else { continue };
+
+            for (block_range, _) in ranges {
+                let fixed_range = static_file_provider.find_fixed_range(block_range.start());
+                let path = static_file_provider.directory().join(segment.filename(&fixed_range));
+                let jar = NippyJar::<SegmentHeader>::load(&path)?;
+
+                let corrupted = match jar.verify_checksums() {
+                    Ok(corrupted) => corrupted,
+                    Err(NippyJarError::MissingChecksums) => {
+                        warn!(target: "reth::cli", ?path, "Static file has no checksums, skipping");
+                        continue
+                    }
+                    Err(err) => return Err(err.into()),
+                };
+                if corrupted.is_empty() {
+                    info!(target: "reth::cli", ?path, rows = jar.rows(), "Static file verified");
+                    continue
+                }
+
+                for rows in &corrupted {
+                    let blocks = corrupted_blocks(&provider, &jar, rows)?;
+                    error!(target: "reth::cli", ?path, ?rows, ?blocks, "Corrupted static file rows");
+                }
+
+                if !self.repair {
+                    unrepaired += corrupted.len();
+                    continue
+                }
+                if !segment.is_headers() && segment != StaticFileSegment::Transactions {
+                    error!(target: "reth::cli", ?path, %segment, "Static file segment can't be repaired, unwind the corrupted blocks and sync them again");
+                    unrepaired += corrupted.len();
+                    continue
+                }
+
+                if source.is_none() {
+                    source = Some(self.block_source(&provider_factory, &config, &data_dir).await?);
+                }
+                let Some(source) = &source else { unreachable!("block source was just created") };
+
+                let mut replacements = HashMap::new();
+                for rows in &corrupted {
+                    let fetched = match segment {
+                        StaticFileSegment::Headers => {
+                            fetch_header_rows::<N, _>(&provider, source, &jar, rows).await?
+                        }
+                        _ => fetch_transaction_rows::<N, _>(&provider, source, &jar, rows).await?,
+                    };
+                    replacements.extend(fetched);
+                }
+
+                jar.rewrite_rows(&corrupted, |row| {
+                    replacements
+                        .remove(&row)
+                        .ok_or_else(|| NippyJarError::Custom(format!("row {row} wasn't fetched")))
+                })?;
+                // The provider may still have the old file mapped.
+                static_file_provider.remove_cached_provider(segment, fixed_range.end());
+
+                info!(target: "reth::cli", ?path, ranges = corrupted.len(), "Repaired static file");
+            }
+        }
+
+        if unrepaired > 0 {
+            return Err(eyre!("Found {unrepaired} corrupted row ranges in static files"))
+        }
+
+        Ok(())
+    }
+
+    /// Returns the source to re-fetch corrupted rows from, starting the network if needed.
+    async fn block_source<N: CliNodeTypes>(
+        &self,
+        provider_factory: &ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+        config: &Config,
+        data_dir: &ChainPath<DataDirPath>,
+    ) -> eyre::Result<BlockSource<N>> {
+        if let Some(path) = &self.era_path {
+            let known_roots = match &self.era_roots {
+                Some(roots) => Some(era::read_known_roots(roots)?),
+                None => era::known_roots(provider_factory.chain_spec().chain()).map(<[_]>::to_vec),
+            };
+            return Ok(BlockSource::Era { path: path.clone(), known_roots })
+        }
+
+        let mut config = config.clone();
+        config.peers.trusted_nodes_only = self.network.trusted_only;
+        config.peers.trusted_nodes.extend(self.network.trusted_peers.clone());
+
+        let p2p_secret_key = get_secret_key(
+            &self.network.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret()),
+        )?;
+        let network = self
+            .network
+            .network_config::<N::NetworkPrimitives>(
+                &config,
+                provider_factory.chain_spec(),
+                p2p_secret_key,
+                data_dir.known_peers(),
+            )
+            .build(provider_factory.clone())
+            .start_network()
+            .await?;
+
+        Ok(BlockSource::Peers(network.fetch_client().await?))
+    }
+}
+
+/// Returns the blocks that the rows of a static file belong to.
+fn corrupted_blocks<P: TransactionsProvider>(
+    provider: &P,
+    jar: &NippyJar<SegmentHeader>,
+    rows: &Range<usize>,
+) -> eyre::Result<Option<RangeInclusive<BlockNumber>>> {
+    let header = jar.user_header();
+    if header.segment().is_tx_based() {
+        let Some(tx_start) = header.tx_start() else { return Ok(None) };
+        let first = provider.transaction_block(tx_start + rows.start as TxNumber)?;
+        let last = provider.transaction_block(tx_start + rows.end as TxNumber - 1)?;
+        Ok(first.zip(last).map(|(first, last)| first..=last))
+    } else if header.segment().is_headers() || header.segment().is_block_meta() {
+        // One row per block.
+        Ok(header
+            .block_start()
+            .map(|start| start + rows.start as BlockNumber..=start + rows.end as BlockNumber - 1))
+    } else {
+        // Changesets have a variable number of rows per block.
+        Ok(None)
+    }
+}
+
+/// Fetches the rows of the headers static file, validating that the fetched headers are the
+/// canonical ones in the database and link to the headers around them.
+async fn fetch_header_rows<N, P>(
+    provider: &P,
+    source: &BlockSource<N>,
+    jar: &NippyJar<SegmentHeader>,
+    rows: &Range<usize>,
+) -> eyre::Result<HashMap<usize, Vec<Vec<u8>>>>
+where
+    N: CliNodeTypes,
+    P: DBProvider + HeaderProvider<Header = HeaderTy<N>>,
+{
+    let block_start = jar.user_header().block_start().ok_or_eyre("static file has no blocks")?;
+    let first = block_start + rows.start as BlockNumber;
+    let last = block_start + rows.end as BlockNumber - 1;
+    let headers = source.headers(first..=last).await?;
+
+    let (mut total_difficulty, mut parent_hash) = if first == 0 {
+        (U256::ZERO, None)
+    } else {
+        let parent = provider
+            .sealed_header(first - 1)?
+            .ok_or_eyre("missing header before corrupted rows")?;
+        let td = provider
+            .header_td_by_number(first - 1)?
+            .ok_or_eyre("missing total difficulty before corrupted rows")?;
+        (td, Some(parent.hash()))
+    };
+
+    let mut replacements = HashMap::with_capacity(headers.len());
+    for (row, header) in rows.clone().zip(headers) {
+        let header = SealedHeader::seal_slow(header);
+        if parent_hash.is_some_and(|parent_hash| parent_hash != header.parent_hash()) {
+            return Err(eyre!("Fetched header {} doesn't link to its parent", header.number()))
+        }
+        let hash = header.hash();
+        check_canonical_hash(provider, header.number(), hash)?;
+        parent_hash = Some(hash);
+        total_difficulty += header.difficulty();
+
+        replacements.insert(
+            row,
+            vec![
+                header.unseal().compress().into(),
+                CompactU256::from(total_difficulty).compress().into(),
+                hash.compress().into(),
+            ],
+        );
+    }
+
+    if let Some(child) = provider.header_by_number(last + 1)? {
+        if parent_hash != Some(child.parent_hash()) {
+            return Err(eyre!("Header {} doesn't link to the fetched headers", last + 1))
+        }
+    }
+
+    Ok(replacements)
+}
+
+/// Checks that `hash` is the canonical hash of block `number` in the database.
+///
+/// The database keeps the hashes of all canonical blocks in [`tables::HeaderNumbers`], even for
+/// blocks whose headers are in static files, so they don't depend on the corrupted static file.
+fn check_canonical_hash<P: DBProvider>(
+    provider: &P,
+    number: BlockNumber,
+    hash: BlockHash,
+) -> eyre::Result<()> {
+    let tx = provider.tx_ref();
+    let is_canonical = tx.get::<tables::HeaderNumbers>(hash)? == Some(number) &&
+        tx.get::<tables::CanonicalHeaders>(number)?.is_none_or(|canonical| canonical == hash);
+    if !is_canonical {
+        return Err(eyre!(
+            "Fetched header {number} with hash {hash} isn't canonical in the database"
+        ))
+    }
+    Ok(())
+}
+
+/// Fetches the rows of a transactions static file, validating the fetched bodies against the
+/// transaction roots of their headers.
+async fn fetch_transaction_rows<N, P>(
+    provider: &P,
+    source: &BlockSource<N>,
+    jar: &NippyJar<SegmentHeader>,
+    rows: &Range<usize>,
+) -> eyre::Result<HashMap<usize, Vec<Vec<u8>>>>
+where
+    N: CliNodeTypes,
+    P: HeaderProvider<Header = HeaderTy<N>> + BlockBodyIndicesProvider + TransactionsProvider,
+{
+    let tx_start = jar.user_header().tx_start().ok_or_eyre("static file has no transactions")?;
+    let txs = tx_start + rows.start as TxNumber..tx_start + rows.end as TxNumber;
+    let blocks = corrupted_blocks(provider, jar, rows)?
+        .ok_or_eyre("missing blocks of corrupted transactions")?;
+
+    let mut headers = Vec::new();
+    for number in blocks.clone() {
+        headers.push(provider.sealed_header(number)?.ok_or_eyre("missing block header")?);
+    }
+    let bodies = source.bodies(&headers).await?;
+
+    let mut replacements = HashMap::with_capacity(rows.len());
+    for (header, body) in headers.iter().zip(bodies) {
+        if body.calculate_tx_root() != header.transactions_root() {
+            return Err(eyre!(
+                "Fetched body of block {} has a wrong transactions root",
+                header.number()
+            ))
+        }
+
+        let indices = provider
+            .block_body_indices(header.number())?
+            .ok_or_eyre("missing block body indices")?;
+        for (tx_num, tx) in indices.tx_num_range().zip(body.transactions()) {
+            if txs.contains(&tx_num) {
+                replacements
+                    .insert((tx_num - tx_start) as usize, vec![tx.clone().compress().into()]);
+            }
+        }
+    }
+
+    Ok(replacements)
+}
+
+/// Maximum number of consecutive requests to peers that may fail or return no new data.
+const MAX_PEER_RETRIES: u32 = 8;
+
+/// Delay before the first retry of a request to peers, doubled on every further retry.
+const PEER_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Upper bound for the delay between retries of a request to peers.
+const MAX_PEER_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Source of the blocks that corrupted static file rows are re-fetched from.
+enum BlockSource<N: CliNodeTypes> {
+    /// Directory with ERA1 files.
+    Era {
+        /// The directory.
+        path: PathBuf,
+        /// The known accumulator roots to verify the files against.
+        known_roots: Option<Vec<era::KnownRoot>>,
+    },
+    /// Peers of the network.
+    Peers(FetchClient<N::NetworkPrimitives>),
+}
+
+impl<N: CliNodeTypes> BlockSource<N> {
+    /// Fetches the headers of a range of blocks.
+    async fn headers(&self, blocks: RangeInclusive<BlockNumber>) -> eyre::Result<Vec<HeaderTy<N>>> {
+        let client = match self {
+            Self::Era { path, known_roots } => {
+                let blocks = Self::era_blocks(path, known_roots.as_deref(), blocks).await?;
+                return Ok(blocks.into_iter().map(|(header, _)| header).collect())
+            }
+            Self::Peers(client) => client,
+        };
+
+        let len = (blocks.end() - blocks.start() + 1) as usize;
+        let mut headers = Vec::with_capacity(len);
+        let mut retries = PeerRetries::default();
+        while headers.len() < len {
+            let next = *blocks.start() + headers.len() as BlockNumber;
+            let request = HeadersRequest::rising(next.into(), blocks.end() - next + 1);
+            let (peer_id, response) = match client.get_headers(request).await {
+                Ok(response) => response.split(),
+                Err(error) if error.is_retryable() => {
+                    warn!(target: "reth::cli", %error, "Error requesting headers, retrying");
+                    retries.failed().await?;
+                    continue
+                }
+                Err(error) => return Err(error.into()),
+            };
+
+            // Peers may respond with fewer headers than requested.
+            let fetched = headers.len();
+            for header in response {
+                if header.number() != *blocks.start() + headers.len() as BlockNumber {
+                    break
+                }
+                headers.push(header);
+            }
+
+            if headers.len() == fetched {
+                // Penalize the peer, so the next request is sent to another one.
+                warn!(
+                    target: "reth::cli",
+                    %peer_id,
+                    block = next,
+                    "Peer responded without the requested headers, retrying"
+                );
+                client.report_bad_message(peer_id);
+                retries.failed().await?;
+            } else {
+                retries.succeeded();
+            }
+        }
+
+        Ok(headers)
+    }
+
+    /// Fetches the bodies of blocks.
+    async fn bodies(&self, headers: &[SealedHeader<HeaderTy<N>>]) -> eyre::Result<Vec<BodyTy<N>>> {
+        let (Some(first), Some(last)) = (headers.first(), headers.last()) else {
+            return Ok(Vec::new())
+        };
+        let client = match self {
+            Self::Era { path, known_roots } => {
+                let blocks =
+                    Self::era_blocks(path, known_roots.as_deref(), first.number()..=last.number())
+                        .await?;
+                return Ok(blocks.into_iter().map(|(_, body)| body).collect())
+            }
+            Self::Peers(client) => client,
+        };
+
+        let mut bodies = Vec::new();
+        let mut retries = PeerRetries::default();
+        while bodies.len() < headers.len() {
+            let hashes = headers[bodies.len()..].iter().map(|header| header.hash()).collect();
+            let (peer_id, response) = match client.get_block_bodies(hashes).await {
+                Ok(response) => response.split(),
+                Err(error) if error.is_retryable() => {
+                    warn!(target: "reth::cli", %error, "Error requesting bodies, retrying");
+                    retries.failed().await?;
+                    continue
+                }
+                Err(error) => return Err(error.into()),
+            };
+
+            if response.is_empty() {
+                // Penalize the peer, so the next request is sent to another one.
+                let block = headers[bodies.len()].number();
+                warn!(
+                    target: "reth::cli",
+                    %peer_id,
+                    block,
+                    "Peer responded without the requested bodies, retrying"
+                );
+                client.report_bad_message(peer_id);
+                retries.failed().await?;
+            } else {
+                bodies.extend(response);
+                retries.succeeded();
+            }
+        }
+        bodies.truncate(headers.len());
+
+        Ok(bodies)
+    }
+
+    /// Reads a range of blocks from the ERA1 files in `path`, verifying each file before it's
+    /// read.
+    async fn era_blocks(
+        path: &Path,
+        known_roots: Option<&[era::KnownRoot]>,
+        blocks: RangeInclusive<BlockNumber>,
+    ) -> eyre::Result<Vec<(HeaderTy<N>, BodyTy<N>)>> {
+        let mut files = read_dir(path.to_path_buf(), *blocks.start())?;
+        let mut result = Vec::new();
+
+        'files: while let Some(meta) = files.next().await {
+            let meta = meta?;
+            era::verify(&meta, known_roots)?;
+            for block in era::open(&meta)?.iter() {
+                let (header, body) = era::decode::<HeaderTy<N>, BodyTy<N>, _>(block)?;
+                if header.number() < *blocks.start() {
+                    continue
+                }
+                if header.number() > *blocks.end() {
+                    break 'files
+                }
+                result.push((header, body));
+            }
+        }
+
+        if result.len() as u64 != blocks.end() - blocks.start() + 1 {
+            return Err(eyre!("ERA1 files don't contain blocks {blocks:?}"))
+        }
+
+        Ok(result)
+    }
+}
+
+/// Bounds the consecutive requests to peers that fail or return no new data.
+#[derive(Debug, Default)]
+struct PeerRetries {
+    /// Number of consecutive requests without progress.
+    failed: u32,
+}
+
+impl PeerRetries {
+    /// Records a request without progress and waits before the next one.
+    ///
+    /// Returns an error once [`MAX_PEER_RETRIES`] consecutive requests made no progress.
+    async fn failed(&mut self) -> eyre::Result<()> {
+        self.failed += 1;
+        if self.failed > MAX_PEER_RETRIES {
+            return Err(eyre!("no progress after {MAX_PEER_RETRIES} retries of requests to peers"))
+        }
+
+        let backoff = PEER_RETRY_BACKOFF.saturating_mul(1 << (self.failed - 1));
+        tokio::time::sleep(backoff.min(MAX_PEER_RETRY_BACKOFF)).await;
+        Ok(())
+    }
+
+    /// Records a request that made progress.
+    const fn succeeded(&mut self) {
+        self.failed = 0;
+    }
+}
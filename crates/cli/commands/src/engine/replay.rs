@@ -0,0 +1,56 @@
+//! `reth engine replay` command
+
+use crate::{launcher::Launcher, node::NodeCommand};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use reth_node_core::args::EngineApiReplayMode;
+use std::{fmt, path::PathBuf, sync::Arc};
+
+/// Launches the node from the configured datadir and feeds the engine API messages stored in the
+/// given directory into its consensus engine.
+///
+/// The latency of every replayed message is logged, followed by a summary once the journal was
+/// replayed. Discovery is disabled, so that the node doesn't advance past the recorded messages.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
+    /// The directory with the engine API messages recorded with `--debug.engine-api-store`.
+    #[arg(value_name = "DIR")]
+    path: PathBuf,
+
+    /// The pacing of the replayed messages.
+    #[arg(long, value_enum, default_value_t = EngineApiReplayMode::Original)]
+    mode: EngineApiReplayMode,
+
+    #[command(flatten)]
+    node: NodeCommand<C, Ext>,
+}
+
+impl<C, Ext> Command<C, Ext>
+where
+    C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>,
+    Ext: clap::Args + fmt::Debug,
+{
+    /// Execute `engine replay` command
+    pub async fn execute<L>(self, ctx: CliContext, launcher: L) -> eyre::Result<()>
+    where
+        L: Launcher<C, Ext>,
+    {
+        eyre::ensure!(self.path.is_dir(), "Engine API journal does not exist: {:?}", self.path);
+
+        let mut node = self.node;
+        node.debug.engine_api_replay = Some(self.path);
+        node.debug.engine_api_replay_mode = self.mode;
+        node.network.discovery.disable_discovery = true;
+
+        node.execute(ctx, launcher).await
+    }
+}
+
+impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Command<C, Ext> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        self.node.chain_spec()
+    }
+}
@@ -0,0 +1,145 @@
+//! `reth exex wal` command
+
+use crate::common::{CliNodeTypes, EnvironmentArgs};
+use alloy_primitives::BlockNumber;
+use clap::{Parser, Subcommand};
+use human_bytes::human_bytes;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::lockfile::StorageLock;
+use reth_exex::{serde_bincode_compat, ExExNotification, Wal};
+use reth_node_api::PrimitivesTy;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::info;
+
+/// `reth exex wal` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth exex wal` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Prints the segments and notifications stored in the WAL.
+    Inspect,
+    /// Removes all notifications with the highest block less than or equal to the given block.
+    ///
+    /// The node must be stopped, the command fails if it holds the database lock.
+    Truncate {
+        /// The block number to truncate the WAL to, inclusive.
+        #[arg(long, value_name = "BLOCK_NUMBER")]
+        to_block: BlockNumber,
+    },
+    /// Exports all notifications in the WAL as newline-delimited JSON.
+    Export {
+        /// The file to write the notifications to. If not set, notifications are written to
+        /// stdout.
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `exex wal` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
+        let wal_path = data_dir.exex_wal();
+        eyre::ensure!(wal_path.is_dir(), "ExEx WAL does not exist: {:?}", wal_path);
+
+        // Only truncation modifies the WAL, the rest must not touch a WAL that the node might be
+        // using at the same time
+        let (wal, _lock) = match self.command {
+            Subcommands::Truncate { .. } => {
+                // The node holds the database lock while it's running and writing to the WAL
+                let lock = StorageLock::try_acquire(&data_dir.db()).map_err(|err| {
+                    eyre::eyre!("Can't truncate the ExEx WAL while the node is running: {err}")
+                })?;
+                (Wal::<PrimitivesTy<N>>::new(&wal_path)?, Some(lock))
+            }
+            Subcommands::Inspect | Subcommands::Export { .. } => {
+                (Wal::<PrimitivesTy<N>>::open_read_only(&wal_path)?, None)
+            }
+        };
+
+        match self.command {
+            Subcommands::Inspect => {
+                let segments = wal.segments();
+                let total_size = segments.iter().map(|segment| segment.size).sum::<u64>();
+                println!(
+                    "WAL at {wal_path:?}: {} segments, {}, {} blocks",
+                    segments.len(),
+                    human_bytes(total_size as f64),
+                    wal.num_blocks()
+                );
+                for segment in segments {
+                    println!(
+                        "segment {}: {} notifications, {}",
+                        segment.id,
+                        segment.notifications,
+                        human_bytes(segment.size as f64)
+                    );
+                }
+
+                for notification in wal.iter_notifications()? {
+                    let notification = notification?;
+                    let kind = match &notification {
+                        ExExNotification::ChainCommitted { .. } => "commit",
+                        ExExNotification::ChainReorged { .. } => "reorg",
+                        ExExNotification::ChainReverted { .. } => "revert",
+                    };
+                    println!(
+                        "{kind}: reverted {:?}, committed {:?}",
+                        notification.reverted_chain().map(|chain| chain.range()),
+                        notification.committed_chain().map(|chain| chain.range()),
+                    );
+                }
+            }
+            Subcommands::Truncate { to_block } => {
+                info!(target: "reth::cli", ?wal_path, to_block, "Truncating ExEx WAL");
+                wal.truncate(to_block)?;
+                info!(target: "reth::cli", blocks = wal.num_blocks(), "Truncated ExEx WAL");
+            }
+            Subcommands::Export { output } => {
+                let mut writer: Box<dyn Write> = match &output {
+                    Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+                    None => Box::new(BufWriter::new(io::stdout().lock())),
+                };
+
+                let mut exported = 0;
+                for notification in wal.iter_notifications()? {
+                    let notification = notification?;
+                    serde_json::to_writer(
+                        &mut writer,
+                        &serde_bincode_compat::ExExNotification::from(&notification),
+                    )?;
+                    writeln!(writer)?;
+                    exported += 1;
+                }
+                writer.flush()?;
+
+                if let Some(path) = output {
+                    info!(target: "reth::cli", ?path, exported, "Exported ExEx WAL notifications");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
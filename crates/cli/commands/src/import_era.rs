@@ -6,7 +6,7 @@ use eyre::eyre;
 use reqwest::{Client, Url};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_era_downloader::{read_dir, EraClient, EraStream, EraStreamConfig};
+use reth_era_downloader::{read_beacon_dir, read_dir, EraClient, EraStream, EraStreamConfig};
 use reth_era_utils as era;
 use reth_etl::Collector;
 use reth_fs_util as fs;
@@ -24,6 +24,32 @@ pub struct ImportEraCommand<C: ChainSpecParser> {
 
     #[clap(flatten)]
     import: ImportArgs,
+
+    /// The path to a file with the known accumulator roots of all epochs.
+    ///
+    /// The file contains one hex encoded root per line, ordered by epoch, either the full root
+    /// or its first 4 bytes. Every ERA1 file is verified against the root of its epoch before
+    /// it is imported.
+    ///
+    /// Defaults to the roots that ship with reth for mainnet and sepolia. On these chains the
+    /// import fails if no roots are given or shipped.
+    #[arg(long, value_name = "ROOTS_FILE", verbatim_doc_comment)]
+    roots: Option<PathBuf>,
+
+    /// Import post-merge history from consensus-layer ERA files.
+    ///
+    /// The `.era` files are read from the local directory set by `--path`. The execution
+    /// payloads of their beacon blocks are converted to blocks, skipping blocks that precede
+    /// the merge or that are already imported.
+    #[arg(long, requires = "path", conflicts_with = "roots", verbatim_doc_comment)]
+    beacon: bool,
+
+    /// Import `.era` files that come without a `checksums.txt`.
+    ///
+    /// Only use this for files that were created locally, files from a remote source must be
+    /// verified against their checksums.
+    #[arg(long, requires = "beacon", verbatim_doc_comment)]
+    allow_unverified: bool,
 }
 
 #[derive(Debug, Args)]
@@ -74,6 +100,20 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
 
         let mut hash_collector = Collector::new(config.stages.etl.file_size, config.stages.etl.dir);
 
+        let chain = self.env.chain.chain();
+        let known_roots = self.roots.map(era::read_known_roots).transpose()?;
+        let known_roots = known_roots.as_deref().or_else(|| era::known_roots(chain));
+
+        // Files of public chains must never be imported without checking them against trusted
+        // roots, the accumulator embedded in a file from an untrusted mirror proves nothing
+        let is_public_chain =
+            matches!(chain.named(), Some(NamedChain::Mainnet | NamedChain::Sepolia));
+        if !self.beacon && is_public_chain && known_roots.is_none() {
+            return Err(eyre!(
+                "No known accumulator roots ship for {chain}, pass them with `--roots <ROOTS_FILE>`"
+            ))
+        }
+
         let next_block = provider_factory
             .static_file_provider()
             .get_highest_static_file_block(StaticFileSegment::Headers)
@@ -81,9 +121,15 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
             1;
 
         if let Some(path) = self.import.path {
-            let stream = read_dir(path, next_block)?;
+            if self.beacon {
+                let stream = read_beacon_dir(path, !self.allow_unverified)?;
 
-            era::import(stream, &provider_factory, &mut hash_collector)?;
+                era::import_beacon(stream, &provider_factory, &mut hash_collector)?;
+            } else {
+                let stream = read_dir(path, next_block)?;
+
+                era::import(stream, &provider_factory, &mut hash_collector, known_roots)?;
+            }
         } else {
             let url = match self.import.url {
                 Some(url) => url,
@@ -98,7 +144,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
             let client = EraClient::new(Client::new(), url, folder);
             let stream = EraStream::new(client, config);
 
-            era::import(stream, &provider_factory, &mut hash_collector)?;
+            era::import(stream, &provider_factory, &mut hash_collector, known_roots)?;
         }
 
         Ok(())
@@ -1,6 +1,6 @@
 use futures::Future;
 use reth_cli::chainspec::ChainSpecParser;
-use reth_db::DatabaseEnv;
+use reth_db::{DatabaseEnv, MemoryDatabase};
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use std::{fmt, sync::Arc};
 
@@ -33,6 +33,23 @@ where
         builder: WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, C::ChainSpec>>,
         builder_args: Ext,
     ) -> impl Future<Output = eyre::Result<()>>;
+
+    /// Entry point for launching a node with an in-memory database, used instead of
+    /// [`Launcher::entrypoint`] if `--datadir.memory` is set.
+    ///
+    /// Launchers don't support in-memory databases by default, see [`WithMemoryLauncher`] for
+    /// adding support to an existing launcher.
+    fn entrypoint_memory(
+        self,
+        builder: WithLaunchContext<NodeBuilder<Arc<MemoryDatabase>, C::ChainSpec>>,
+        builder_args: Ext,
+    ) -> impl Future<Output = eyre::Result<()>>
+    where
+        Self: Sized,
+    {
+        let _ = (builder, builder_args);
+        async { Err(eyre::eyre!("--datadir.memory is not supported by this node")) }
+    }
 }
 
 /// A function-based adapter implementation of the [`Launcher`] trait.
@@ -83,3 +100,58 @@ where
         (self.func)(builder, builder_args)
     }
 }
+
+/// A [`Launcher`] that launches nodes with an in-memory database with a separate function.
+///
+/// Nodes with a database on disk are launched by the wrapped launcher.
+pub struct WithMemoryLauncher<L, F> {
+    /// The launcher for nodes with a database on disk
+    launcher: L,
+    /// The function to execute when launching a node with an in-memory database
+    memory_func: F,
+}
+
+impl<L, F> WithMemoryLauncher<L, F> {
+    /// Creates a new launcher that uses `memory_func` for in-memory databases and `launcher`
+    /// otherwise.
+    ///
+    /// Type parameters `C` and `Ext` help the compiler infer correct types
+    /// since they're not stored in the struct itself.
+    pub fn new<C, Ext>(launcher: L, memory_func: F) -> Self
+    where
+        C: ChainSpecParser,
+        F: AsyncFnOnce(
+            WithLaunchContext<NodeBuilder<Arc<MemoryDatabase>, C::ChainSpec>>,
+            Ext,
+        ) -> eyre::Result<()>,
+    {
+        Self { launcher, memory_func }
+    }
+}
+
+impl<C, Ext, L, F> Launcher<C, Ext> for WithMemoryLauncher<L, F>
+where
+    C: ChainSpecParser,
+    Ext: clap::Args + fmt::Debug,
+    L: Launcher<C, Ext>,
+    F: AsyncFnOnce(
+        WithLaunchContext<NodeBuilder<Arc<MemoryDatabase>, C::ChainSpec>>,
+        Ext,
+    ) -> eyre::Result<()>,
+{
+    fn entrypoint(
+        self,
+        builder: WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, C::ChainSpec>>,
+        builder_args: Ext,
+    ) -> impl Future<Output = eyre::Result<()>> {
+        self.launcher.entrypoint(builder, builder_args)
+    }
+
+    fn entrypoint_memory(
+        self,
+        builder: WithLaunchContext<NodeBuilder<Arc<MemoryDatabase>, C::ChainSpec>>,
+        builder_args: Ext,
+    ) -> impl Future<Output = eyre::Result<()>> {
+        (self.memory_func)(builder, builder_args)
+    }
+}
@@ -13,6 +13,8 @@ pub mod config_cmd;
 pub mod db;
 pub mod download;
 pub mod dump_genesis;
+pub mod engine;
+pub mod exex;
 pub mod export_era;
 pub mod import;
 pub mod import_era;
@@ -6,12 +6,12 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use reth_cli_util::parse_socket_address;
-use reth_db::init_db;
+use reth_db::{init_db, MemoryDatabase};
 use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, NetworkArgs,
-        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, EventsArgs, ExExArgs,
+        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
@@ -113,6 +113,14 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten, next_help_heading = "ERA")]
     pub era: EraArgs,
 
+    /// All node events export related arguments with --events prefix
+    #[command(flatten)]
+    pub events: EventsArgs,
+
+    /// All ExEx write-ahead log related arguments with --exex prefix
+    #[command(flatten)]
+    pub exex: ExExArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -168,6 +176,8 @@ where
             ext,
             engine,
             era,
+            events,
+            exex,
         } = self;
 
         // set up node config
@@ -187,18 +197,44 @@ where
             pruning,
             engine,
             era,
+            events,
+            exex,
         };
 
-        let data_dir = node_config.datadir();
-        let db_path = data_dir.db();
+        // An in-memory database doesn't survive restarts, so the rest of the node data is kept in
+        // a temporary directory that lives as long as the node.
+        let _temp_datadir = if node_config.datadir.memory {
+            if !node_config.dev.dev {
+                eyre::bail!("--datadir.memory is only supported for dev nodes, use it with --dev")
+            }
+
+            let temp_datadir = tempfile::TempDir::with_prefix("reth-")?;
+            node_config.datadir.datadir = temp_datadir.path().to_path_buf().into();
+            Some(temp_datadir)
+        } else {
+            None
+        };
 
-        tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
+        let data_dir = node_config.datadir();
 
         if with_unused_ports {
             node_config = node_config.with_unused_ports();
         }
 
+        if node_config.datadir.memory {
+            tracing::info!(target: "reth::cli", path = ?data_dir.data_dir(), "Using in-memory database");
+            let builder = NodeBuilder::new(node_config)
+                .with_database(Arc::new(MemoryDatabase::new()))
+                .with_launch_context(ctx.task_executor);
+
+            return launcher.entrypoint_memory(builder, ext).await
+        }
+
+        let db_path = data_dir.db();
+
+        tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
+        let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
+
         let builder = NodeBuilder::new(node_config)
             .with_database(database)
             .with_launch_context(ctx.task_executor);
@@ -342,6 +378,7 @@ mod tests {
         assert_eq!(cmd.rpc.auth_port, 8551);
         assert_eq!(cmd.rpc.http_port, 8545);
         assert_eq!(cmd.rpc.ws_port, 8546);
+        assert_eq!(cmd.rpc.graphql_port, 8547);
         // check network listening port number
         assert_eq!(cmd.network.port, 30303);
 
@@ -353,6 +390,7 @@ mod tests {
         assert_eq!(cmd.rpc.auth_port, 8651);
         assert_eq!(cmd.rpc.http_port, 8544);
         assert_eq!(cmd.rpc.ws_port, 8548);
+        assert_eq!(cmd.rpc.graphql_port, 8549);
         // check network listening port number
         assert_eq!(cmd.network.port, 30304);
 
@@ -364,6 +402,7 @@ mod tests {
         assert_eq!(cmd.rpc.auth_port, 8751);
         assert_eq!(cmd.rpc.http_port, 8543);
         assert_eq!(cmd.rpc.ws_port, 8550);
+        assert_eq!(cmd.rpc.graphql_port, 8551);
         // check network listening port number
         assert_eq!(cmd.network.port, 30305);
     }
@@ -397,6 +436,7 @@ mod tests {
         assert_eq!(cmd.rpc.auth_port, 0);
         assert_eq!(cmd.rpc.http_port, 0);
         assert_eq!(cmd.rpc.ws_port, 0);
+        assert_eq!(cmd.rpc.graphql_port, 0);
 
         // make sure the network ports are zero
         assert_eq!(cmd.network.port, 0);
@@ -0,0 +1,612 @@
+//! `reth stage bench` command
+//!
+//! Benchmarks a single stage against a snapshot of a datadir, e.g. one created with
+//! `reth stage dump`.
+
+use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_config::config::{
+    HashingConfig, IndexLogsConfig, SenderRecoveryConfig, TransactionLookupConfig,
+};
+use reth_db::lockfile::StorageLock;
+use reth_exex::ExExManagerHandle;
+use reth_node_core::args::{DatadirArgs, StageEnum};
+use reth_provider::{
+    writer::UnifiedStorageWriter, DatabaseProviderFactory, HeaderProvider, StageCheckpointReader,
+};
+use reth_stages::{
+    stages::{
+        AccountHashingStage, ExecutionStage, IndexAccountHistoryStage, IndexLogsStage,
+        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
+        TransactionLookupStage,
+    },
+    ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+use tracing::*;
+
+/// Files that belong to an open database or static file provider and must not be copied into a
+/// snapshot.
+const LOCK_FILES: [&str; 2] = ["lock", "mdbx.lck"];
+
+/// Interval at which the resident memory of the process is sampled.
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// `reth stage bench` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The name of the stage to benchmark
+    #[arg(value_enum)]
+    stage: StageEnum,
+
+    /// The height to start at.
+    ///
+    /// The checkpoint of the stage in the datadir has to be at this height.
+    #[arg(long)]
+    from: u64,
+
+    /// The end of the stage
+    #[arg(long, short)]
+    to: u64,
+
+    /// Batch size for stage execution
+    #[arg(long)]
+    batch_size: Option<u64>,
+
+    /// Number of times to run the stage, each time from the same snapshot of the datadir.
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..))]
+    runs: u64,
+
+    /// Directory to create the datadir snapshots in.
+    ///
+    /// Defaults to the datadir itself. Every run copies the database and static files, so the
+    /// directory needs enough free space to hold another copy of them.
+    #[arg(long, value_name = "PATH")]
+    work_dir: Option<PathBuf>,
+
+    /// Write the JSON report to this file instead of stdout.
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// A JSON report of a previous run to compare against.
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// Maximum regression of any metric against the baseline, in percent, before the command
+    /// fails.
+    #[arg(long, value_name = "PERCENT", default_value_t = 10.0, requires = "baseline")]
+    max_regression: f64,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
+    /// Execute `stage bench` command
+    pub async fn execute<N, Comp, F>(self, components: F) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+        F: FnOnce(Arc<C::ChainSpec>) -> Comp,
+    {
+        // Raise the fd limit of the process.
+        // Does not do anything on windows.
+        let _ = fdlimit::raise_fd_limit();
+
+        if self.from >= self.to {
+            eyre::bail!("--from must be lower than --to")
+        }
+        if matches!(self.stage, StageEnum::Headers | StageEnum::Bodies | StageEnum::Hashing) {
+            eyre::bail!("stage {} can't be benchmarked", self.stage)
+        }
+
+        let baseline = self
+            .baseline
+            .as_deref()
+            .map(reth_fs_util::read_json_file::<BenchReport>)
+            .transpose()?;
+
+        // Open the datadir read-only and hold its storage locks for the whole benchmark, so that
+        // no node can write to it while the snapshots are copied.
+        let Environment { provider_factory: source_factory, data_dir, .. } =
+            self.env.init::<N>(AccessRights::RO)?;
+        let _locks = [data_dir.db(), data_dir.static_files()]
+            .iter()
+            .map(|path| StorageLock::try_acquire(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        let gas = source_factory
+            .provider()?
+            .headers_range(self.from + 1..=self.to)?
+            .iter()
+            .map(|header| header.gas_used())
+            .sum();
+
+        let work_dir = self.work_dir.clone().unwrap_or_else(|| data_dir.data_dir().to_path_buf());
+        reth_fs_util::create_dir_all(&work_dir)?;
+
+        let components = components(self.env.chain.clone());
+        let batch_size = self.batch_size.unwrap_or(self.to - self.from + 1);
+        let mut runs = Vec::with_capacity(self.runs as usize);
+
+        for run in 1..=self.runs {
+            let snapshot = tempfile::Builder::new().prefix("stage-bench-").tempdir_in(&work_dir)?;
+            info!(target: "reth::cli", run, path = ?snapshot.path(), "Copying datadir snapshot");
+            copy_dir(&data_dir.db(), &snapshot.path().join("db"))?;
+            copy_dir(&data_dir.static_files(), &snapshot.path().join("static_files"))?;
+
+            // Open the snapshot with the configuration of the original datadir.
+            let env = EnvironmentArgs::<C> {
+                datadir: DatadirArgs {
+                    datadir: snapshot.path().to_path_buf().into(),
+                    ..Default::default()
+                },
+                config: Some(self.env.config.clone().unwrap_or_else(|| data_dir.config())),
+                chain: self.env.chain.clone(),
+                db: self.env.db,
+            };
+            let Environment { provider_factory, config, data_dir: snapshot_dir } =
+                env.init::<N>(AccessRights::RW)?;
+
+            let etl_config = config.stages.etl.clone();
+            let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+
+            let mut stage: Box<dyn Stage<_>> = match self.stage {
+                StageEnum::Senders => Box::new(SenderRecoveryStage::new(SenderRecoveryConfig {
+                    commit_threshold: batch_size,
+                })),
+                StageEnum::Execution => Box::new(ExecutionStage::new(
+                    components.evm_config().clone(),
+                    Arc::new(components.consensus().clone()),
+                    ExecutionStageThresholds {
+                        max_blocks: Some(batch_size),
+                        max_changes: None,
+                        max_cumulative_gas: None,
+                        max_duration: None,
+                    },
+                    config.stages.merkle.incremental_threshold,
+                    ExExManagerHandle::empty(),
+                )),
+                StageEnum::TxLookup => Box::new(TransactionLookupStage::new(
+                    TransactionLookupConfig { chunk_size: batch_size },
+                    etl_config,
+                    prune_modes.transaction_lookup,
+                )),
+                StageEnum::AccountHashing => Box::new(AccountHashingStage::new(
+                    HashingConfig { clean_threshold: 1, commit_threshold: batch_size },
+                    etl_config,
+                )),
+                StageEnum::StorageHashing => Box::new(StorageHashingStage::new(
+                    HashingConfig { clean_threshold: 1, commit_threshold: batch_size },
+                    etl_config,
+                )),
+                StageEnum::Merkle => Box::new(MerkleStage::new_execution(
+                    config.stages.merkle.rebuild_threshold,
+                    config.stages.merkle.incremental_threshold,
+                )),
+                StageEnum::AccountHistory => Box::new(IndexAccountHistoryStage::new(
+                    config.stages.index_account_history,
+                    etl_config,
+                    prune_modes.account_history,
+                )),
+                StageEnum::StorageHistory => Box::new(IndexStorageHistoryStage::new(
+                    config.stages.index_storage_history,
+                    etl_config,
+                    prune_modes.storage_history,
+                )),
+                StageEnum::LogIndex => Box::new(IndexLogsStage::new(IndexLogsConfig {
+                    commit_threshold: batch_size,
+                    ..config.stages.index_logs
+                })),
+                StageEnum::Headers | StageEnum::Bodies | StageEnum::Hashing => unreachable!(),
+            };
+
+            let mut provider_rw = provider_factory.database_provider_rw()?;
+            let checkpoint = provider_rw.get_stage_checkpoint(stage.id())?.unwrap_or_default();
+            if checkpoint.block_number != self.from {
+                eyre::bail!(
+                    "stage {} is at block {}, but the benchmark starts at block {}. Unwind the \
+                     stage to --from first",
+                    self.stage,
+                    checkpoint.block_number,
+                    self.from
+                )
+            }
+            let mut input = ExecInput { target: Some(self.to), checkpoint: Some(checkpoint) };
+
+            let db_pages_before = written_pages(provider_factory.db_ref())?;
+            let static_files_before = dir_size(&snapshot_dir.static_files())?;
+            let memory = PeakMemory::start();
+
+            info!(target: "reth::cli", run, stage = %self.stage, "Executing stage");
+            let start = Instant::now();
+            loop {
+                stage.execute_ready(input).await?;
+                let ExecOutput { checkpoint, done } = stage.execute(&provider_rw, input)?;
+                input.checkpoint = Some(checkpoint);
+
+                UnifiedStorageWriter::commit(provider_rw)?;
+                provider_rw = provider_factory.database_provider_rw()?;
+
+                if done {
+                    break
+                }
+            }
+            let elapsed = start.elapsed();
+            drop(provider_rw);
+
+            let peak_memory = memory.stop();
+            let page_size = provider_factory.db_ref().stat()?.page_size() as u64;
+            let db_write_bytes = written_pages(provider_factory.db_ref())?
+                .saturating_sub(db_pages_before)
+                * page_size;
+            let static_file_write_bytes =
+                dir_size(&snapshot_dir.static_files())?.saturating_sub(static_files_before);
+
+            let result = BenchRun::new(
+                self.to - self.from,
+                gas,
+                elapsed,
+                db_write_bytes,
+                static_file_write_bytes,
+                peak_memory,
+            );
+            info!(
+                target: "reth::cli",
+                run,
+                stage = %self.stage,
+                time = ?elapsed,
+                blocks_per_second = result.blocks_per_second,
+                gas_per_second = result.gas_per_second,
+                "Finished stage"
+            );
+            runs.push(result);
+
+            // The database has to be closed before the snapshot is removed.
+            drop(provider_factory);
+            snapshot.close()?;
+        }
+
+        let mut report = BenchReport {
+            stage: self.stage.to_string(),
+            from: self.from,
+            to: self.to,
+            gas,
+            summary: BenchRun::median(&runs),
+            runs,
+            comparison: None,
+        };
+
+        let mut regressions = Vec::new();
+        if let Some(baseline) = &baseline {
+            if (baseline.stage.as_str(), baseline.from, baseline.to)
+                != (report.stage.as_str(), report.from, report.to)
+            {
+                warn!(
+                    target: "reth::cli",
+                    stage = %baseline.stage,
+                    from = baseline.from,
+                    to = baseline.to,
+                    "Baseline was recorded for a different stage or block range"
+                );
+            }
+
+            let comparison = report.summary.compare(&baseline.summary);
+            for diff in &comparison {
+                info!(
+                    target: "reth::cli",
+                    metric = diff.metric,
+                    baseline = diff.baseline,
+                    current = diff.current,
+                    change = format!("{:+.2}%", diff.change),
+                    "Compared against baseline"
+                );
+                if diff.regression > self.max_regression {
+                    regressions
+                        .push(format!("{} regressed by {:.2}%", diff.metric, diff.regression));
+                }
+            }
+            report.comparison = Some(comparison);
+        }
+
+        let json = serde_json::to_string_pretty(&report)?;
+        match &self.output {
+            Some(output) => {
+                reth_fs_util::write(output, json)?;
+                info!(target: "reth::cli", path = ?output, "Wrote stage bench report");
+            }
+            None => println!("{json}"),
+        }
+
+        if !regressions.is_empty() {
+            eyre::bail!(
+                "stage {} regressed more than {}% against the baseline: {}",
+                self.stage,
+                self.max_regression,
+                regressions.join(", ")
+            )
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Report of a `reth stage bench` invocation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    /// Name of the benchmarked stage.
+    pub stage: String,
+    /// The height the stage started at.
+    pub from: u64,
+    /// The height the stage executed to.
+    pub to: u64,
+    /// Total gas used by the blocks in the range.
+    pub gas: u64,
+    /// Median of all runs.
+    pub summary: BenchRun,
+    /// Results of the individual runs.
+    pub runs: Vec<BenchRun>,
+    /// Comparison of the summary against the baseline report, if one was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comparison: Option<Vec<MetricDiff>>,
+}
+
+/// Result of a single stage run.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BenchRun {
+    /// Wall-clock time of the stage execution in seconds.
+    pub elapsed_secs: f64,
+    /// Blocks processed per second.
+    pub blocks_per_second: f64,
+    /// Gas processed per second.
+    pub gas_per_second: f64,
+    /// Bytes of database pages written, including copy-on-write updates.
+    pub db_write_bytes: u64,
+    /// Growth of the static files in bytes.
+    pub static_file_write_bytes: u64,
+    /// Peak resident memory of the process in bytes.
+    pub peak_memory_bytes: u64,
+}
+
+impl BenchRun {
+    fn new(
+        blocks: u64,
+        gas: u64,
+        elapsed: Duration,
+        db_write_bytes: u64,
+        static_file_write_bytes: u64,
+        peak_memory_bytes: u64,
+    ) -> Self {
+        let elapsed_secs = elapsed.as_secs_f64();
+        let per_second =
+            |value: u64| if elapsed_secs > 0.0 { value as f64 / elapsed_secs } else { 0.0 };
+        Self {
+            elapsed_secs,
+            blocks_per_second: per_second(blocks),
+            gas_per_second: per_second(gas),
+            db_write_bytes,
+            static_file_write_bytes,
+            peak_memory_bytes,
+        }
+    }
+
+    /// Returns the median of every metric across the runs.
+    fn median(runs: &[Self]) -> Self {
+        fn median<T: Copy + PartialOrd>(mut values: Vec<T>) -> T {
+            values.sort_by(|a, b| a.partial_cmp(b).expect("metrics are not NaN"));
+            values[values.len() / 2]
+        }
+
+        Self {
+            elapsed_secs: median(runs.iter().map(|run| run.elapsed_secs).collect()),
+            blocks_per_second: median(runs.iter().map(|run| run.blocks_per_second).collect()),
+            gas_per_second: median(runs.iter().map(|run| run.gas_per_second).collect()),
+            db_write_bytes: median(runs.iter().map(|run| run.db_write_bytes).collect()),
+            static_file_write_bytes: median(
+                runs.iter().map(|run| run.static_file_write_bytes).collect(),
+            ),
+            peak_memory_bytes: median(runs.iter().map(|run| run.peak_memory_bytes).collect()),
+        }
+    }
+
+    /// Compares the metrics against a baseline.
+    fn compare(&self, baseline: &Self) -> Vec<MetricDiff> {
+        vec![
+            MetricDiff::new(
+                "blocks_per_second",
+                baseline.blocks_per_second,
+                self.blocks_per_second,
+                true,
+            ),
+            MetricDiff::new("gas_per_second", baseline.gas_per_second, self.gas_per_second, true),
+            MetricDiff::new(
+                "db_write_bytes",
+                baseline.db_write_bytes as f64,
+                self.db_write_bytes as f64,
+                false,
+            ),
+            MetricDiff::new(
+                "static_file_write_bytes",
+                baseline.static_file_write_bytes as f64,
+                self.static_file_write_bytes as f64,
+                false,
+            ),
+            MetricDiff::new(
+                "peak_memory_bytes",
+                baseline.peak_memory_bytes as f64,
+                self.peak_memory_bytes as f64,
+                false,
+            ),
+        ]
+    }
+}
+
+/// Change of a metric against the baseline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricDiff {
+    /// Name of the metric.
+    pub metric: String,
+    /// Value of the baseline.
+    pub baseline: f64,
+    /// Value of this run.
+    pub current: f64,
+    /// Change against the baseline in percent.
+    pub change: f64,
+    /// How much worse the metric got in percent, or zero if it improved.
+    pub regression: f64,
+}
+
+impl MetricDiff {
+    fn new(metric: &str, baseline: f64, current: f64, higher_is_better: bool) -> Self {
+        let change = if baseline == 0.0 {
+            if current == 0.0 {
+                0.0
+            } else {
+                f64::INFINITY.copysign(current)
+            }
+        } else {
+            (current - baseline) / baseline * 100.0
+        };
+        let regression = if higher_is_better { -change } else { change }.max(0.0);
+        Self { metric: metric.to_string(), baseline, current, change, regression }
+    }
+}
+
+/// Samples the resident memory of the process in the background and keeps track of its peak.
+struct PeakMemory {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<u64>,
+}
+
+impl PeakMemory {
+    fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = std::thread::spawn({
+            let stop = stop.clone();
+            move || {
+                let pid = sysinfo::Pid::from_u32(std::process::id());
+                let mut system = System::new();
+                let mut peak = 0;
+                loop {
+                    system.refresh_processes_specifics(
+                        ProcessesToUpdate::Some(&[pid]),
+                        true,
+                        ProcessRefreshKind::nothing().with_memory(),
+                    );
+                    if let Some(process) = system.process(pid) {
+                        peak = peak.max(process.memory());
+                    }
+                    if stop.load(Ordering::Relaxed) {
+                        break peak
+                    }
+                    std::thread::sleep(MEMORY_SAMPLE_INTERVAL);
+                }
+            }
+        });
+        Self { stop, handle }
+    }
+
+    /// Stops sampling and returns the peak resident memory in bytes.
+    fn stop(self) -> u64 {
+        self.stop.store(true, Ordering::Relaxed);
+        self.handle.join().unwrap_or_default()
+    }
+}
+
+/// Returns the number of database pages that were allocated or copied for an update since the
+/// database was opened.
+fn written_pages(db: &reth_db::DatabaseEnv) -> eyre::Result<u64> {
+    let page_ops = db.info()?.page_ops();
+    Ok(page_ops.newly + page_ops.cow)
+}
+
+/// Recursively copies a directory, skipping lock files.
+fn copy_dir(from: &Path, to: &Path) -> eyre::Result<()> {
+    reth_fs_util::create_dir_all(to)?;
+    if !from.exists() {
+        return Ok(())
+    }
+
+    for entry in reth_fs_util::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else if !LOCK_FILES.iter().any(|name| entry.file_name() == *name) {
+            std::fs::copy(&path, &target)
+                .map_err(|err| eyre::eyre!("failed to copy {path:?} to {target:?}: {err}"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the total size of all files in a directory.
+fn dir_size(path: &Path) -> eyre::Result<u64> {
+    let mut size = 0;
+    for entry in reth_fs_util::read_dir(path)? {
+        let entry = entry?;
+        let path = entry.path();
+        size += if path.is_dir() { dir_size(&path)? } else { reth_fs_util::metadata(&path)?.len() };
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(blocks_per_second: f64, peak_memory_bytes: u64) -> BenchRun {
+        BenchRun {
+            elapsed_secs: 1.0,
+            blocks_per_second,
+            gas_per_second: blocks_per_second,
+            db_write_bytes: 100,
+            static_file_write_bytes: 0,
+            peak_memory_bytes,
+        }
+    }
+
+    #[test]
+    fn median_of_runs() {
+        let summary = BenchRun::median(&[run(10.0, 300), run(30.0, 100), run(20.0, 200)]);
+        assert_eq!(summary.blocks_per_second, 20.0);
+        assert_eq!(summary.peak_memory_bytes, 200);
+    }
+
+    #[test]
+    fn compare_against_baseline() {
+        let diffs = run(80.0, 150).compare(&run(100.0, 100));
+        let diff = |metric: &str| diffs.iter().find(|diff| diff.metric == metric).unwrap();
+
+        assert_eq!(diff("blocks_per_second").change, -20.0);
+        assert_eq!(diff("blocks_per_second").regression, 20.0);
+        assert_eq!(diff("peak_memory_bytes").change, 50.0);
+        assert_eq!(diff("peak_memory_bytes").regression, 50.0);
+        assert_eq!(diff("db_write_bytes").regression, 0.0);
+        assert_eq!(diff("static_file_write_bytes").change, 0.0);
+
+        let diffs = run(120.0, 50).compare(&run(100.0, 100));
+        assert!(diffs.iter().all(|diff| diff.regression == 0.0));
+    }
+}
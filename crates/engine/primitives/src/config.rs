@@ -95,6 +95,14 @@ pub struct TreeConfig {
     /// where immediate payload regeneration is desired despite the head not changing or moving to
     /// an ancestor.
     always_process_payload_attributes_on_canonical_head: bool,
+    /// Whether to prewarm by executing the transactions of a block optimistically in parallel and
+    /// committing them in block order, to compute exact proof targets.
+    parallel_prewarming: bool,
+    /// Whether to compare the results of parallel prewarming with sequential execution.
+    parallel_prewarming_differential: bool,
+    /// Maximum number of already persisted blocks that are added to the log index when blocks
+    /// are persisted, or `None` if the log index is disabled.
+    log_index_commit_threshold: Option<u64>,
 }
 
 impl Default for TreeConfig {
@@ -117,6 +125,9 @@ impl Default for TreeConfig {
             precompile_cache_disabled: false,
             state_root_fallback: false,
             always_process_payload_attributes_on_canonical_head: false,
+            parallel_prewarming: false,
+            parallel_prewarming_differential: false,
+            log_index_commit_threshold: None,
         }
     }
 }
@@ -142,6 +153,9 @@ impl TreeConfig {
         precompile_cache_disabled: bool,
         state_root_fallback: bool,
         always_process_payload_attributes_on_canonical_head: bool,
+        parallel_prewarming: bool,
+        parallel_prewarming_differential: bool,
+        log_index_commit_threshold: Option<u64>,
     ) -> Self {
         Self {
             persistence_threshold,
@@ -161,6 +175,9 @@ impl TreeConfig {
             precompile_cache_disabled,
             state_root_fallback,
             always_process_payload_attributes_on_canonical_head,
+            parallel_prewarming,
+            parallel_prewarming_differential,
+            log_index_commit_threshold,
         }
     }
 
@@ -375,6 +392,51 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for whether to prewarm by executing transactions optimistically in parallel.
+    pub const fn with_parallel_prewarming(mut self, parallel_prewarming: bool) -> Self {
+        self.parallel_prewarming = parallel_prewarming;
+        self
+    }
+
+    /// Setter for whether to compare parallel prewarming results with sequential execution.
+    pub const fn with_parallel_prewarming_differential(
+        mut self,
+        parallel_prewarming_differential: bool,
+    ) -> Self {
+        self.parallel_prewarming_differential = parallel_prewarming_differential;
+        self
+    }
+
+    /// Setter for the log index, `None` disables it.
+    ///
+    /// See [`Self::log_index_commit_threshold`].
+    pub const fn with_log_index_commit_threshold(
+        mut self,
+        log_index_commit_threshold: Option<u64>,
+    ) -> Self {
+        self.log_index_commit_threshold = log_index_commit_threshold;
+        self
+    }
+
+    /// Returns the maximum number of already persisted blocks that are added to the log index when
+    /// blocks are persisted, or `None` if the log index is disabled.
+    ///
+    /// Blocks are persisted with their logs indexed. If the log index lags behind, e.g. because
+    /// it was enabled on an existing node, it catches up by this many blocks per persisted batch.
+    pub const fn log_index_commit_threshold(&self) -> Option<u64> {
+        self.log_index_commit_threshold
+    }
+
+    /// Returns whether transactions are prewarmed by executing them optimistically in parallel.
+    pub const fn parallel_prewarming(&self) -> bool {
+        self.parallel_prewarming
+    }
+
+    /// Returns whether parallel prewarming results are compared with sequential execution.
+    pub const fn parallel_prewarming_differential(&self) -> bool {
+        self.parallel_prewarming && self.parallel_prewarming_differential
+    }
+
     /// Whether or not to use state root task
     pub const fn use_state_root_task(&self) -> bool {
         self.has_enough_parallelism && !self.legacy_state_root
@@ -1,16 +1,19 @@
 use crate::metrics::PersistenceMetrics;
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{BlockHeader, TxReceipt};
 use alloy_eips::BlockNumHash;
+use alloy_primitives::{BlockNumber, Log};
 use reth_chain_state::ExecutedBlockWithTrieUpdates;
 use reth_errors::ProviderError;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
     providers::ProviderNodeTypes, writer::UnifiedStorageWriter, BlockHashReader,
-    ChainStateBlockWriter, DatabaseProviderFactory, ProviderFactory, StaticFileProviderFactory,
+    ChainStateBlockWriter, DatabaseProviderFactory, LogIndexWriter, ProviderFactory,
+    ProviderResult, PruneCheckpointReader, ReceiptProvider, StageCheckpointReader,
+    StageCheckpointWriter, StaticFileProviderFactory,
 };
-use reth_prune::{PrunerError, PrunerOutput, PrunerWithFactory};
-use reth_stages_api::{MetricEvent, MetricEventsSender};
+use reth_prune::{PruneSegment, PrunerError, PrunerOutput, PrunerWithFactory};
+use reth_stages_api::{MetricEvent, MetricEventsSender, StageCheckpoint, StageId};
 use std::{
     sync::mpsc::{Receiver, SendError, Sender},
     time::Instant,
@@ -41,6 +44,9 @@ where
     metrics: PersistenceMetrics,
     /// Sender for sync metrics - we only submit sync metrics for persisted blocks
     sync_metrics_tx: MetricEventsSender,
+    /// Maximum number of already persisted blocks added to the log index per saved batch, if the
+    /// log index is enabled.
+    log_index_commit_threshold: Option<u64>,
 }
 
 impl<N> PersistenceService<N>
@@ -54,7 +60,23 @@ where
         pruner: PrunerWithFactory<ProviderFactory<N>>,
         sync_metrics_tx: MetricEventsSender,
     ) -> Self {
-        Self { provider, incoming, pruner, metrics: PersistenceMetrics::default(), sync_metrics_tx }
+        Self {
+            provider,
+            incoming,
+            pruner,
+            metrics: PersistenceMetrics::default(),
+            sync_metrics_tx,
+            log_index_commit_threshold: None,
+        }
+    }
+
+    /// Enables the log index, which is updated whenever blocks are saved or removed.
+    ///
+    /// If the log index lags behind the saved blocks, up to `commit_threshold` already persisted
+    /// blocks are indexed per saved batch until it caught up.
+    pub const fn with_log_index(mut self, commit_threshold: u64) -> Self {
+        self.log_index_commit_threshold = Some(commit_threshold);
+        self
     }
 
     /// Prunes block data before the given block hash according to the configured prune
@@ -131,6 +153,8 @@ where
         let sf_provider = self.provider.static_file_provider();
 
         let new_tip_hash = provider_rw.block_hash(new_tip_num)?;
+        // the receipts of the removed blocks are required to unwind the log index
+        unwind_log_index(&*provider_rw, new_tip_num)?;
         UnifiedStorageWriter::from(&provider_rw, &sf_provider).remove_blocks_above(new_tip_num)?;
         UnifiedStorageWriter::commit_unwind(provider_rw)?;
 
@@ -154,7 +178,30 @@ where
             let provider_rw = self.provider.database_provider_rw()?;
             let static_file_provider = self.provider.static_file_provider();
 
+            // the logs are indexed after the blocks are written, in the same transaction
+            let block_logs = self.log_index_commit_threshold.is_some().then(|| {
+                blocks
+                    .iter()
+                    .map(|block| {
+                        let logs = block
+                            .execution_outcome()
+                            .receipts()
+                            .iter()
+                            .flatten()
+                            .flat_map(|receipt| receipt.logs())
+                            .cloned()
+                            .collect::<Vec<_>>();
+                        (block.recovered_block().number(), logs)
+                    })
+                    .collect::<Vec<_>>()
+            });
+
             UnifiedStorageWriter::from(&provider_rw, &static_file_provider).save_blocks(blocks)?;
+            if let Some((block_logs, commit_threshold)) =
+                block_logs.zip(self.log_index_commit_threshold)
+            {
+                update_log_index(&*provider_rw, block_logs, commit_threshold)?;
+            }
             UnifiedStorageWriter::commit(provider_rw)?;
         }
         self.metrics.save_blocks_duration_seconds.record(start_time.elapsed());
@@ -162,6 +209,102 @@ where
     }
 }
 
+/// Adds the logs of the saved blocks to the log index.
+///
+/// If the log index lags behind the saved blocks, e.g. because it was enabled on an existing node,
+/// up to `commit_threshold` already persisted blocks are indexed from the stored receipts instead,
+/// and the saved blocks are indexed once the log index caught up.
+///
+/// The highest indexed block is tracked with the [`StageId::IndexLogs`] stage checkpoint, shared
+/// with the `IndexLogsStage` of the pipeline.
+fn update_log_index<P>(
+    provider: &P,
+    block_logs: Vec<(BlockNumber, Vec<Log>)>,
+    commit_threshold: u64,
+) -> ProviderResult<()>
+where
+    P: LogIndexWriter
+        + ReceiptProvider<Receipt: TxReceipt<Log = Log>>
+        + PruneCheckpointReader
+        + StageCheckpointReader
+        + StageCheckpointWriter,
+{
+    let (Some((first_block, _)), Some((last_block, _))) = (block_logs.first(), block_logs.last())
+    else {
+        return Ok(())
+    };
+    let (first_block, last_block) = (*first_block, *last_block);
+
+    let indexed_to = log_indexed_to(provider)?;
+    let from = indexed_to.map_or(0, |indexed_to| indexed_to + 1);
+    if from < first_block {
+        let end = (first_block - 1).min(from + commit_threshold.max(1) - 1);
+
+        // Receipts below the prune checkpoint are not available anymore.
+        let start = provider
+            .get_prune_checkpoint(PruneSegment::Receipts)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(from, |pruned| from.max(pruned + 1));
+        if start <= end {
+            debug!(target: "engine::persistence", range = ?(start..=end), "Catching up log index");
+            let receipts = provider.receipts_by_block_range(start..=end)?;
+            provider.insert_log_indices(
+                (start..=end)
+                    .zip(receipts.iter().map(|receipts| receipts.iter().flat_map(|r| r.logs()))),
+            )?;
+        }
+        provider.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(end))?;
+
+        if end + 1 < first_block {
+            // the saved blocks are indexed once the log index caught up
+            return Ok(())
+        }
+    }
+
+    if indexed_to.is_some_and(|indexed_to| indexed_to >= last_block) {
+        return Ok(())
+    }
+    provider.insert_log_indices(
+        block_logs
+            .iter()
+            .filter(|(number, _)| indexed_to.is_none_or(|indexed_to| *number > indexed_to))
+            .map(|(number, logs)| (*number, logs)),
+    )?;
+    provider.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(last_block))?;
+
+    Ok(())
+}
+
+/// Removes all blocks above the new tip from the log index.
+///
+/// Must be called before the blocks are removed, because their receipts are required to find the
+/// affected index entries.
+fn unwind_log_index<P>(provider: &P, new_tip: BlockNumber) -> ProviderResult<()>
+where
+    P: LogIndexWriter
+        + ReceiptProvider<Receipt: TxReceipt<Log = Log>>
+        + StageCheckpointReader
+        + StageCheckpointWriter,
+{
+    let Some(indexed_to) = log_indexed_to(provider)?.filter(|indexed_to| *indexed_to > new_tip)
+    else {
+        return Ok(())
+    };
+
+    debug!(target: "engine::persistence", range = ?(new_tip + 1..=indexed_to), "Unwinding log index");
+    let receipts = provider.receipts_by_block_range(new_tip + 1..=indexed_to)?;
+    provider.unwind_log_indices(
+        new_tip + 1,
+        receipts.iter().flatten().flat_map(|receipt| receipt.logs()),
+    )?;
+    provider.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(new_tip))
+}
+
+/// Returns the highest block number covered by the log index.
+fn log_indexed_to<P: StageCheckpointReader>(provider: &P) -> ProviderResult<Option<BlockNumber>> {
+    Ok(provider.get_stage_checkpoint(StageId::IndexLogs)?.map(|checkpoint| checkpoint.block_number))
+}
+
 /// One of the errors that can happen when using the persistence service.
 #[derive(Debug, Error)]
 pub enum PersistenceError {
@@ -211,10 +354,14 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
     }
 
     /// Create a new [`PersistenceHandle`], and spawn the persistence service.
+    ///
+    /// The log index is maintained if `log_index_commit_threshold` is set, see
+    /// [`PersistenceService::with_log_index`].
     pub fn spawn_service<N>(
         provider_factory: ProviderFactory<N>,
         pruner: PrunerWithFactory<ProviderFactory<N>>,
         sync_metrics_tx: MetricEventsSender,
+        log_index_commit_threshold: Option<u64>,
     ) -> PersistenceHandle<N::Primitives>
     where
         N: ProviderNodeTypes,
@@ -226,8 +373,11 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
         let persistence_handle = PersistenceHandle::new(db_service_tx);
 
         // spawn the persistence service
-        let db_service =
+        let mut db_service =
             PersistenceService::new(provider_factory, db_service_rx, pruner, sync_metrics_tx);
+        if let Some(commit_threshold) = log_index_commit_threshold {
+            db_service = db_service.with_log_index(commit_threshold);
+        }
         std::thread::Builder::new()
             .name("Persistence Service".to_string())
             .spawn(|| {
@@ -298,10 +448,12 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::B256;
+    use alloy_primitives::{Address, B256};
     use reth_chain_state::test_utils::TestBlockBuilder;
     use reth_exex_types::FinishedExExHeight;
-    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, LogIndexMatches, LogIndexReader,
+    };
     use reth_prune::Pruner;
     use tokio::sync::mpsc::unbounded_channel;
 
@@ -315,7 +467,7 @@ mod tests {
             Pruner::new_with_factory(provider.clone(), vec![], 5, 0, None, finished_exex_height_rx);
 
         let (sync_metrics_tx, _sync_metrics_rx) = unbounded_channel();
-        PersistenceHandle::<EthPrimitives>::spawn_service(provider, pruner, sync_metrics_tx)
+        PersistenceHandle::<EthPrimitives>::spawn_service(provider, pruner, sync_metrics_tx, None)
     }
 
     #[tokio::test]
@@ -390,4 +542,28 @@ mod tests {
             assert_eq!(last_hash, actual_hash);
         }
     }
+
+    #[test]
+    fn test_update_log_index() {
+        let provider = create_test_provider_factory();
+        let provider_rw = provider.database_provider_rw().unwrap();
+        let address = Address::random();
+        let log = Log::new_unchecked(address, vec![], Default::default());
+
+        update_log_index(&*provider_rw, vec![(0, vec![]), (1, vec![log.clone()]), (2, vec![])], 10)
+            .unwrap();
+        assert_eq!(log_indexed_to(&*provider_rw).unwrap(), Some(2));
+        assert_eq!(
+            provider_rw.log_index_blocks(0..=2, &[address], &[]).unwrap(),
+            Some(LogIndexMatches { indexed_to: 2, blocks: vec![1] })
+        );
+
+        // blocks that are already indexed are skipped
+        update_log_index(&*provider_rw, vec![(2, vec![log])], 10).unwrap();
+        assert_eq!(log_indexed_to(&*provider_rw).unwrap(), Some(2));
+        assert_eq!(
+            provider_rw.log_index_blocks(0..=2, &[address], &[]).unwrap(),
+            Some(LogIndexMatches { indexed_to: 2, blocks: vec![1] })
+        );
+    }
 }
@@ -3,14 +3,17 @@
 use crate::tree::{
     cached_state::{CachedStateMetrics, ProviderCacheBuilder, ProviderCaches, SavedCache},
     payload_processor::{
+        parallel::{ParallelExecutionMetrics, ParallelExecutionOutcome},
         prewarm::{PrewarmCacheTask, PrewarmContext, PrewarmTaskEvent},
         sparse_trie::StateRootComputeOutcome,
     },
     sparse_trie::SparseTrieTask,
     StateProviderBuilder, TreeConfig,
 };
+use alloy_consensus::TxReceipt;
+use alloy_eips::BlockNumHash;
 use alloy_evm::block::StateChangeSource;
-use alloy_primitives::B256;
+use alloy_primitives::{Log, B256};
 use executor::WorkloadExecutor;
 use multiproof::{SparseTrieUpdate, *};
 use parking_lot::RwLock;
@@ -36,12 +39,14 @@ use std::sync::{
     mpsc::{self, channel, Sender},
     Arc,
 };
+use tracing::error;
 
 use super::precompile_cache::PrecompileCacheMap;
 
 mod configured_sparse_trie;
 pub mod executor;
 pub mod multiproof;
+pub mod parallel;
 pub mod prewarm;
 pub mod sparse_trie;
 
@@ -76,6 +81,10 @@ where
     >,
     /// Whether to use the parallel sparse trie.
     use_parallel_sparse_trie: bool,
+    /// Whether transactions are prewarmed by executing them optimistically in parallel.
+    parallel_prewarming: bool,
+    /// Whether the outcome of parallel prewarming is reported for comparison.
+    parallel_prewarming_differential: bool,
     /// A cleared trie input, kept around to be reused so allocations can be minimized.
     trie_input: Option<TrieInput>,
 }
@@ -104,6 +113,8 @@ where
             sparse_state_trie: Arc::default(),
             trie_input: None,
             use_parallel_sparse_trie: config.enable_parallel_sparse_trie(),
+            parallel_prewarming: config.parallel_prewarming(),
+            parallel_prewarming_differential: config.parallel_prewarming_differential(),
         }
     }
 }
@@ -315,6 +326,14 @@ where
             terminate_execution: Arc::new(AtomicBool::new(false)),
             precompile_cache_disabled: self.precompile_cache_disabled,
             precompile_cache_map: self.precompile_cache_map.clone(),
+            parallel_prewarming: self.parallel_prewarming,
+        };
+
+        let (parallel_outcome_tx, parallel_execution) = if self.parallel_prewarming_differential {
+            let (tx, rx) = channel();
+            (Some(tx), Some(rx))
+        } else {
+            (None, None)
         };
 
         let (prewarm_task, to_prewarm_task) = PrewarmCacheTask::new(
@@ -322,6 +341,7 @@ where
             self.execution_cache.clone(),
             prewarm_ctx,
             to_multi_proof,
+            parallel_outcome_tx,
         );
 
         // spawn pre-warm task
@@ -332,7 +352,41 @@ where
             });
         }
 
-        CacheTaskHandle { cache, to_prewarm_task: Some(to_prewarm_task), cache_metrics }
+        CacheTaskHandle {
+            cache,
+            to_prewarm_task: Some(to_prewarm_task),
+            cache_metrics,
+            parallel_execution,
+        }
+    }
+
+    /// Compares the outcome of parallel execution with the receipts of sequential execution.
+    ///
+    /// The comparison waits for parallel execution on a blocking task, so that it doesn't delay
+    /// the validation of the block. Does nothing if the outcome is not reported.
+    pub(super) fn spawn_parallel_execution_comparison<Tx, Err, R>(
+        &self,
+        handle: &mut PayloadHandle<Tx, Err>,
+        block: BlockNumHash,
+        receipts: Vec<R>,
+    ) where
+        R: TxReceipt<Log = Log> + Send + 'static,
+    {
+        let Some(outcome_rx) = handle.prewarm_handle.parallel_execution.take() else { return };
+        self.executor.spawn_blocking(move || {
+            // the sender is dropped if parallel execution was aborted
+            let Ok(outcome) = outcome_rx.recv() else { return };
+            if let Some(index) = outcome.first_mismatch(&receipts) {
+                error!(
+                    target: "engine::tree",
+                    ?block,
+                    index,
+                    reexecuted=outcome.reexecuted,
+                    "Parallel execution result differs from sequential execution"
+                );
+                ParallelExecutionMetrics::default().mismatches.increment(1);
+            }
+        });
     }
 
     /// Takes the trie input from the inner payload processor, if it exists.
@@ -480,6 +534,8 @@ pub(crate) struct CacheTaskHandle {
     cache_metrics: CachedStateMetrics,
     /// Channel to the spawned prewarm task if any
     to_prewarm_task: Option<Sender<PrewarmTaskEvent>>,
+    /// Receiver for the outcome of parallel execution, if it is reported
+    parallel_execution: Option<mpsc::Receiver<ParallelExecutionOutcome>>,
 }
 
 impl CacheTaskHandle {
@@ -591,25 +647,32 @@ where
 mod tests {
     use crate::tree::{
         payload_processor::{
-            evm_state_to_hashed_post_state, executor::WorkloadExecutor, PayloadProcessor,
+            evm_state_to_hashed_post_state, executor::WorkloadExecutor, ExecutionEnv,
+            PayloadProcessor,
         },
         precompile_cache::PrecompileCacheMap,
         StateProviderBuilder, TreeConfig,
     };
+    use alloy_consensus::{Header, TxLegacy};
     use alloy_evm::block::StateChangeSource;
+    use alloy_primitives::TxKind;
     use rand::Rng;
-    use reth_chainspec::ChainSpec;
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder};
+    use reth_db::{tables, transaction::DbTxMut};
     use reth_db_common::init::init_genesis;
-    use reth_ethereum_primitives::TransactionSigned;
-    use reth_evm::OnStateHook;
+    use reth_ethereum_primitives::{Block, BlockBody, Transaction, TransactionSigned};
+    use reth_evm::{execute::Executor, ConfigureEvm, OnStateHook};
     use reth_evm_ethereum::EthEvmConfig;
-    use reth_primitives_traits::{Account, Recovered, StorageEntry};
+    use reth_primitives_traits::{
+        crypto::secp256k1::public_key_to_address, Account, Recovered, RecoveredBlock, StorageEntry,
+    };
     use reth_provider::{
         providers::{BlockchainProvider, ConsistentDbView},
         test_utils::create_test_provider_factory_with_chain_spec,
-        ChainSpecProvider, HashingWriter,
+        ChainSpecProvider, HashingWriter, StateProviderFactory,
     };
-    use reth_testing_utils::generators;
+    use reth_revm::database::StateProviderDatabase;
+    use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
     use reth_trie::{test_utils::state_root, HashedPostState, TrieInput};
     use revm_primitives::{Address, HashMap, B256, KECCAK_EMPTY, U256};
     use revm_state::{AccountInfo, AccountStatus, EvmState, EvmStorageSlot};
@@ -746,4 +809,122 @@ mod tests {
             "State root mismatch: task={root_from_task}, base={root_from_regular}"
         );
     }
+
+    #[test]
+    fn test_parallel_execution_matches_sequential() {
+        reth_tracing::init_test_tracing();
+
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::mainnet()
+                .genesis(ChainSpec::default().genesis)
+                .shanghai_activated()
+                .build(),
+        );
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        let genesis_hash = init_genesis(&factory).unwrap();
+
+        let mut rng = generators::rng();
+        let keys = generators::generate_keys(&mut rng, 3);
+        let senders: Vec<Address> =
+            keys.iter().map(|key| public_key_to_address(key.public_key())).collect();
+        {
+            let provider_rw = factory.provider_rw().unwrap();
+            for sender in &senders {
+                let account = Account { balance: U256::from(10u128.pow(18)), ..Default::default() };
+                provider_rw.tx_ref().put::<tables::PlainAccountState>(*sender, account).unwrap();
+            }
+            provider_rw.commit().unwrap();
+        }
+
+        let beneficiary = Address::random();
+        let header = Header {
+            parent_hash: genesis_hash,
+            number: 1,
+            timestamp: 1,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            beneficiary,
+            ..Default::default()
+        };
+
+        // the second and third transaction read the account changed by the first one, the last
+        // transaction pays the beneficiary
+        let recipient = Address::random();
+        let transfers = [
+            (0, 0, recipient),
+            (1, 0, senders[0]),
+            (0, 1, recipient),
+            (2, 0, Address::random()),
+            (2, 1, beneficiary),
+        ];
+        let transactions: Vec<Recovered<TransactionSigned>> = transfers
+            .into_iter()
+            .map(|(sender, nonce, to)| {
+                let tx = sign_tx_with_key_pair(
+                    keys[sender],
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(chain_spec.chain.id()),
+                        nonce,
+                        gas_price: 10,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(to),
+                        value: U256::from(1),
+                        input: Default::default(),
+                    }),
+                );
+                Recovered::new_unchecked(tx, senders[sender])
+            })
+            .collect();
+
+        let evm_config = EthEvmConfig::new(factory.chain_spec());
+        let provider = BlockchainProvider::new(factory).unwrap();
+
+        // execute the block sequentially
+        let block = RecoveredBlock::new_unhashed(
+            Block {
+                header: header.clone(),
+                body: BlockBody {
+                    transactions: transactions.iter().map(|tx| tx.inner().clone()).collect(),
+                    ..Default::default()
+                },
+            },
+            transactions.iter().map(|tx| tx.signer()).collect(),
+        );
+        let sequential = evm_config
+            .batch_executor(StateProviderDatabase::new(provider.latest().unwrap()))
+            .execute_one(&block)
+            .unwrap();
+
+        let config = TreeConfig::default()
+            .with_parallel_prewarming(true)
+            .with_parallel_prewarming_differential(true);
+        let payload_processor = PayloadProcessor::new(
+            WorkloadExecutor::default(),
+            evm_config.clone(),
+            &config,
+            PrecompileCacheMap::default(),
+        );
+        let mut handle = payload_processor.spawn_cache_exclusive(
+            ExecutionEnv {
+                evm_env: evm_config.evm_env(&header),
+                hash: B256::random(),
+                parent_hash: genesis_hash,
+            },
+            transactions.into_iter().map(Ok::<_, core::convert::Infallible>),
+            StateProviderBuilder::new(provider, genesis_hash, None),
+        );
+
+        let outcome = handle
+            .prewarm_handle
+            .parallel_execution
+            .take()
+            .expect("outcome is reported")
+            .recv()
+            .expect("block is executed in parallel");
+        handle.terminate_caching(None);
+
+        assert_eq!(outcome.transactions.len(), transfers.len());
+        assert_eq!(outcome.first_mismatch(&sequential.receipts), None);
+        assert!(outcome.reexecuted >= 3, "conflicting transactions are re-executed");
+    }
 }
@@ -0,0 +1,710 @@
+//! Optimistic parallel execution of block transactions.
+//!
+//! All transactions of a block are first executed in parallel on top of the parent state, while
+//! recording every account and storage slot they read. The transactions are committed in block
+//! order while the speculative results arrive: a speculative result is reused if none of its reads
+//! were changed by an earlier transaction of the block, otherwise the transaction is re-executed
+//! on top of the committed state. The state changes of every committed transaction are final, so
+//! they are streamed to the state root task as exact proof targets ahead of sequential execution.
+//! The block output always comes from sequential execution, the outcome of parallel execution is
+//! only compared with it in differential mode.
+//!
+//! Every transaction pays fees to the block beneficiary, which would make all transactions
+//! conflict with each other. Speculative execution therefore credits fees to a random sentinel
+//! address and the fee is moved to the beneficiary on commit. Transactions that access the
+//! beneficiary, either by address or with the `COINBASE` opcode, are always re-executed.
+
+use crate::tree::{
+    cached_state::CachedStateProvider,
+    payload_processor::prewarm::{multiproof_targets_from_state, PrewarmContext, PrewarmTaskEvent},
+    precompile_cache::CachedPrecompile,
+};
+use alloy_eips::{eip2935::HISTORY_STORAGE_ADDRESS, eip4788::BEACON_ROOTS_ADDRESS};
+use alloy_evm::Database;
+use alloy_primitives::{
+    keccak256,
+    map::{AddressMap, B256Map, HashMap},
+    Address, Log, B256, U256,
+};
+use metrics::{Counter, Histogram};
+use reth_evm::{execute::OwnedExecutableTxFor, ConfigureEvm, Evm, EvmEnvFor, EvmFor, InspectorFor};
+use reth_metrics::Metrics;
+use reth_primitives_traits::{NodePrimitives, SignedTransaction};
+use reth_provider::{
+    BlockReader, StateCommitmentProvider, StateProviderBox, StateProviderFactory, StateReader,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    state::{AccountInfo, Bytecode, EvmState},
+};
+use revm::{
+    bytecode::opcode,
+    context::ContextTr,
+    context_interface::result::ResultAndState,
+    interpreter::{interpreter::EthInterpreter, interpreter_types::Jumps, Interpreter},
+    DatabaseRef, Inspector,
+};
+use std::{
+    collections::{hash_map::RandomState, BTreeMap},
+    hash::BuildHasher,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc, OnceLock,
+    },
+    time::Instant,
+};
+use tracing::{debug, trace};
+
+/// Database used for parallel execution.
+type StateDb = StateProviderDatabase<CachedStateProvider<StateProviderBox>>;
+
+/// System contracts whose storage is modified before the first transaction of a block.
+///
+/// Parallel execution does not apply the pre-block system calls, so it gives up if a transaction
+/// reads their storage.
+const PRE_BLOCK_SYSTEM_CONTRACTS: [Address; 2] = [BEACON_ROOTS_ADDRESS, HISTORY_STORAGE_ADDRESS];
+
+/// The result of a single transaction executed in parallel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParallelTransactionOutcome {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+    /// Gas used by the transaction.
+    pub gas_used: u64,
+    /// Logs emitted by the transaction.
+    pub logs: Vec<Log>,
+}
+
+/// The result of executing all transactions of a block in parallel.
+#[derive(Debug, Clone, Default)]
+pub struct ParallelExecutionOutcome {
+    /// The results of all transactions in block order.
+    pub transactions: Vec<ParallelTransactionOutcome>,
+    /// Number of transactions whose speculative result could not be reused.
+    pub reexecuted: usize,
+}
+
+impl ParallelExecutionOutcome {
+    /// Compares the outcome with the receipts of sequential execution and returns the index of
+    /// the first transaction that differs.
+    pub fn first_mismatch<R>(&self, receipts: &[R]) -> Option<usize>
+    where
+        R: alloy_consensus::TxReceipt<Log = Log>,
+    {
+        let mut cumulative_gas_used = 0;
+        for (idx, (tx, receipt)) in self.transactions.iter().zip(receipts).enumerate() {
+            cumulative_gas_used += tx.gas_used;
+            if tx.success != receipt.status() ||
+                cumulative_gas_used != receipt.cumulative_gas_used() ||
+                tx.logs != receipt.logs()
+            {
+                return Some(idx)
+            }
+        }
+
+        (self.transactions.len() != receipts.len())
+            .then(|| self.transactions.len().min(receipts.len()))
+    }
+}
+
+impl<N, P, Evm> PrewarmContext<N, P, Evm>
+where
+    N: NodePrimitives,
+    P: BlockReader + StateProviderFactory + StateReader + StateCommitmentProvider + Clone + 'static,
+    Evm: ConfigureEvm<Primitives = N> + 'static,
+{
+    /// Executes all transactions of the block in parallel and commits them in block order.
+    ///
+    /// Transactions are committed as soon as the speculative results of all preceding
+    /// transactions are available, and the proof targets of every committed transaction are
+    /// streamed to the prewarm task. The outcome is sent to the given sender once all
+    /// transactions are committed.
+    ///
+    /// Gives up if the block can't be executed in parallel, or if the task is terminated and the
+    /// outcome is not reported. If the outcome is reported, execution runs to completion so that
+    /// it can be compared with sequential execution.
+    pub(super) fn execute_parallel<Tx: OwnedExecutableTxFor<Evm>>(
+        self,
+        executor: &super::executor::WorkloadExecutor,
+        max_concurrency: usize,
+        pending: Receiver<Tx>,
+        actions_tx: Sender<PrewarmTaskEvent>,
+        outcome_tx: Option<Sender<ParallelExecutionOutcome>>,
+    ) {
+        let start = Instant::now();
+        let metrics = ParallelExecutionMetrics::default();
+        let run_to_completion = outcome_tx.is_some();
+
+        // distribute the transactions to the workers while they are received
+        let (results_tx, results_rx) = mpsc::channel();
+        {
+            let ctx = self.clone();
+            let workers_executor = executor.clone();
+            executor.spawn_blocking(move || {
+                let mut workers = Vec::new();
+                for (idx, tx) in pending.into_iter().enumerate() {
+                    let worker_idx = idx % max_concurrency;
+                    if workers.len() <= worker_idx {
+                        let (worker_tx, worker_rx) = mpsc::channel();
+                        let ctx = ctx.clone();
+                        let results_tx = results_tx.clone();
+                        workers_executor.spawn_blocking(move || {
+                            ctx.transact_speculative(worker_rx, results_tx, run_to_completion)
+                        });
+                        workers.push(worker_tx);
+                    }
+                    let _ = workers[worker_idx].send((idx, tx));
+                }
+            });
+        }
+
+        let mut executed_transactions = 0;
+        let outcome = self.commit(
+            results_rx.iter().inspect(|_| executed_transactions += 1),
+            &actions_tx,
+            run_to_completion,
+        );
+
+        // wait for the workers so that they don't read the caches after they are updated
+        executed_transactions += results_rx.iter().count();
+        let _ = actions_tx.send(PrewarmTaskEvent::FinishedTxExecution { executed_transactions });
+
+        let Some(outcome) = outcome else {
+            metrics.aborted.increment(1);
+            return
+        };
+
+        metrics.transactions.record(outcome.transactions.len() as f64);
+        metrics.reexecuted_transactions.record(outcome.reexecuted as f64);
+        metrics.duration.record(start.elapsed());
+        debug!(
+            target: "engine::tree",
+            transactions = outcome.transactions.len(),
+            reexecuted = outcome.reexecuted,
+            elapsed = ?start.elapsed(),
+            "Executed block in parallel"
+        );
+
+        if let Some(outcome_tx) = outcome_tx {
+            let _ = outcome_tx.send(outcome);
+        }
+    }
+
+    /// Executes the received transactions on top of the parent state.
+    ///
+    /// Stops once the task is terminated, unless `run_to_completion` is set.
+    fn transact_speculative<Tx: OwnedExecutableTxFor<Evm>>(
+        self,
+        txs: Receiver<(usize, Tx)>,
+        results: Sender<(usize, Tx, SpeculativeResult<Evm>)>,
+        run_to_completion: bool,
+    ) {
+        let Some(db) = self.state_db() else { return };
+        let beneficiary = self.env.evm_env.block_env.beneficiary;
+        let sentinel = self.sentinel();
+
+        // credit the fees to the sentinel so that transactions don't conflict on the beneficiary
+        let mut evm_env = self.env.evm_env.clone();
+        evm_env.block_env.beneficiary = sentinel;
+
+        while let Ok((idx, tx)) = txs.recv() {
+            if !run_to_completion && self.terminate_execution.load(Ordering::Relaxed) {
+                break
+            }
+
+            let coinbase = Arc::new(AtomicBool::new(false));
+            let mut evm = self.evm_with_db(
+                SpeculativeDb::new(&db, None, beneficiary, sentinel),
+                evm_env.clone(),
+                CoinbaseInspector(coinbase.clone()),
+            );
+            let result = evm.transact(tx.as_executable());
+            let db = evm.into_db();
+
+            let result = match result {
+                Ok(_) if db.touches_system_contract => SpeculativeResult::Unsupported,
+                Ok(result) => SpeculativeResult::Executed {
+                    result,
+                    reads: db.reads,
+                    accesses_beneficiary: db.accesses_beneficiary ||
+                        coinbase.load(Ordering::Relaxed),
+                },
+                Err(err) => {
+                    trace!(
+                        target: "engine::tree",
+                        %err,
+                        tx_hash=%tx.as_executable().tx().tx_hash(),
+                        "Speculative execution failed",
+                    );
+                    SpeculativeResult::Failed
+                }
+            };
+            let _ = results.send((idx, tx, result));
+        }
+    }
+
+    /// Commits the speculative results in block order as they are received, re-executing
+    /// transactions whose speculative result is invalid.
+    ///
+    /// Returns `None` if the block can't be executed in parallel, a transaction is missing or
+    /// execution was terminated and `run_to_completion` is not set.
+    fn commit<Tx: OwnedExecutableTxFor<Evm>>(
+        &self,
+        results: impl IntoIterator<Item = (usize, Tx, SpeculativeResult<Evm>)>,
+        actions_tx: &Sender<PrewarmTaskEvent>,
+        run_to_completion: bool,
+    ) -> Option<ParallelExecutionOutcome> {
+        let db = self.state_db()?;
+        let beneficiary = self.env.evm_env.block_env.beneficiary;
+        let sentinel = self.sentinel();
+
+        let mut overlay = Overlay::default();
+        let mut outcome = ParallelExecutionOutcome::default();
+        // results that were received before the results of all preceding transactions
+        let mut buffered = BTreeMap::new();
+        for (idx, tx, speculative) in results {
+            buffered.insert(idx, (tx, speculative));
+
+            while let Some((tx, speculative)) = buffered.remove(&outcome.transactions.len()) {
+                if !run_to_completion && self.terminate_execution.load(Ordering::Relaxed) {
+                    return None
+                }
+
+                let ResultAndState { result, mut state } = match speculative {
+                    SpeculativeResult::Unsupported => {
+                        debug!(target: "engine::tree", "Block reads pre-block system contract state, aborting parallel execution");
+                        return None
+                    }
+                    SpeculativeResult::Executed {
+                        mut result,
+                        reads,
+                        accesses_beneficiary: false,
+                    } if reads.is_valid(&overlay) => {
+                        if let Err(err) = overlay.credit_beneficiary(
+                            &db,
+                            &mut result.state,
+                            beneficiary,
+                            sentinel,
+                        ) {
+                            debug!(target: "engine::tree", %err, "Failed to credit beneficiary");
+                            return None
+                        }
+                        result
+                    }
+                    _ => {
+                        // re-execute on top of the committed state with the actual beneficiary
+                        outcome.reexecuted += 1;
+                        let mut evm = self.evm_with_db(
+                            SpeculativeDb::new(&db, Some(&overlay), beneficiary, sentinel),
+                            self.env.evm_env.clone(),
+                            CoinbaseInspector::default(),
+                        );
+                        match evm.transact(tx.as_executable()) {
+                            Ok(result) => result,
+                            Err(err) => {
+                                debug!(
+                                    target: "engine::tree",
+                                    %err,
+                                    tx_hash=%tx.as_executable().tx().tx_hash(),
+                                    "Transaction failed in parallel execution"
+                                );
+                                return None
+                            }
+                        }
+                    }
+                };
+
+                overlay.commit(&state);
+                outcome.transactions.push(ParallelTransactionOutcome {
+                    success: result.is_success(),
+                    gas_used: result.gas_used(),
+                    logs: result.into_logs(),
+                });
+
+                // the committed state is final, so the proof targets are exact
+                state.retain(|address, _| *address != sentinel);
+                let (targets, _) = multiproof_targets_from_state(state);
+                let _ = actions_tx.send(PrewarmTaskEvent::Outcome { proof_targets: Some(targets) });
+            }
+        }
+
+        buffered.is_empty().then_some(outcome)
+    }
+
+    /// Returns a database for the parent state that uses the shared caches.
+    fn state_db(&self) -> Option<StateDb> {
+        let state_provider = match self.provider.build() {
+            Ok(provider) => provider,
+            Err(err) => {
+                trace!(
+                    target: "engine::tree",
+                    %err,
+                    "Failed to build state provider in parallel execution"
+                );
+                return None
+            }
+        };
+
+        Some(StateProviderDatabase::new(CachedStateProvider::new_with_caches(
+            state_provider,
+            self.cache.clone(),
+            self.cache_metrics.clone(),
+        )))
+    }
+
+    /// Returns the address speculative execution credits fees to.
+    ///
+    /// The address is random for every process, so that it can't be targeted by transactions.
+    fn sentinel(&self) -> Address {
+        static SEED: OnceLock<RandomState> = OnceLock::new();
+        let seed = SEED.get_or_init(RandomState::new).hash_one(self.env.hash);
+        Address::from_word(keccak256([self.env.hash.as_slice(), &seed.to_be_bytes()[..]].concat()))
+    }
+
+    /// Creates an EVM with the given database and environment, wrapping precompiles with the
+    /// shared precompile cache.
+    fn evm_with_db<DB>(
+        &self,
+        db: DB,
+        evm_env: EvmEnvFor<Evm>,
+        inspector: CoinbaseInspector,
+    ) -> EvmFor<Evm, DB, CoinbaseInspector>
+    where
+        DB: Database,
+        CoinbaseInspector: InspectorFor<Evm, DB>,
+    {
+        let spec_id = *evm_env.spec_id();
+        let mut evm = self.evm_config.evm_with_env_and_inspector(db, evm_env, inspector);
+
+        if !self.precompile_cache_disabled {
+            let mut precompile_cache_map = self.precompile_cache_map.clone();
+            evm.precompiles_mut().map_precompiles(|address, precompile| {
+                CachedPrecompile::wrap(
+                    precompile,
+                    precompile_cache_map.cache_for_address(*address),
+                    spec_id,
+                    None,
+                )
+            });
+        }
+
+        evm
+    }
+}
+
+/// The result of executing a transaction on top of the parent state.
+enum SpeculativeResult<Evm: ConfigureEvm> {
+    /// The transaction was executed.
+    Executed {
+        /// The execution result and the changed state.
+        result: ResultAndState<reth_evm::HaltReasonFor<Evm>>,
+        /// The state read by the transaction.
+        reads: ReadSet,
+        /// Whether the transaction accessed the block beneficiary.
+        accesses_beneficiary: bool,
+    },
+    /// The transaction failed to execute on top of the parent state.
+    Failed,
+    /// The transaction reads state that is not supported by parallel execution.
+    Unsupported,
+}
+
+/// The accounts and storage slots a transaction read, with the values it observed.
+#[derive(Debug, Default)]
+struct ReadSet {
+    accounts: AddressMap<Option<AccountInfo>>,
+    storage: HashMap<(Address, U256), U256>,
+}
+
+impl ReadSet {
+    /// Returns true if no value read by the transaction was changed by a committed transaction.
+    fn is_valid(&self, overlay: &Overlay) -> bool {
+        self.accounts.iter().all(|(address, read)| {
+            overlay.account(*address).is_none_or(|info| same_account(info, read.as_ref()))
+        }) && self.storage.iter().all(|((address, slot), read)| {
+            overlay.storage(*address, *slot).is_none_or(|value| value == *read)
+        })
+    }
+}
+
+/// Returns true if both accounts are equal, ignoring loaded bytecode.
+fn same_account(a: Option<&AccountInfo>, b: Option<&AccountInfo>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            a.balance == b.balance && a.nonce == b.nonce && a.code_hash == b.code_hash
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// The state changed by committed transactions.
+#[derive(Debug, Default)]
+struct Overlay {
+    accounts: AddressMap<OverlayAccount>,
+    /// Bytecode deployed by committed transactions.
+    contracts: B256Map<Bytecode>,
+}
+
+#[derive(Debug, Default)]
+struct OverlayAccount {
+    /// The changed account info, `Some(None)` if the account was destroyed.
+    info: Option<Option<AccountInfo>>,
+    /// Whether storage that is not in `storage` was cleared.
+    storage_cleared: bool,
+    /// Changed storage slots.
+    storage: HashMap<U256, U256>,
+}
+
+impl Overlay {
+    /// Returns the account if it was changed by a committed transaction.
+    fn account(&self, address: Address) -> Option<Option<&AccountInfo>> {
+        self.accounts.get(&address)?.info.as_ref().map(Option::as_ref)
+    }
+
+    /// Returns the storage value if it was changed by a committed transaction.
+    fn storage(&self, address: Address, slot: U256) -> Option<U256> {
+        let account = self.accounts.get(&address)?;
+        account.storage.get(&slot).copied().or(account.storage_cleared.then_some(U256::ZERO))
+    }
+
+    /// Applies the state changes of a committed transaction.
+    fn commit(&mut self, state: &EvmState) {
+        for (address, account) in state {
+            if !account.is_touched() {
+                continue
+            }
+
+            let entry = self.accounts.entry(*address).or_default();
+            if account.is_selfdestructed() {
+                *entry = OverlayAccount {
+                    info: Some(None),
+                    storage_cleared: true,
+                    ..Default::default()
+                };
+                continue
+            }
+            if account.is_created() {
+                entry.storage_cleared = true;
+                entry.storage.clear();
+            }
+
+            // empty touched accounts are removed from the state
+            entry.info = Some((!account.is_empty()).then(|| account.info.clone()));
+            if let Some(code) = &account.info.code {
+                self.contracts.entry(account.info.code_hash).or_insert_with(|| code.clone());
+            }
+            entry.storage.extend(
+                account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(key, slot)| (*key, slot.present_value)),
+            );
+        }
+    }
+
+    /// Moves the fee credited to the sentinel to the beneficiary.
+    fn credit_beneficiary<DB: DatabaseRef>(
+        &self,
+        db: &DB,
+        state: &mut EvmState,
+        beneficiary: Address,
+        sentinel: Address,
+    ) -> Result<(), DB::Error> {
+        let Some(mut account) = state.remove(&sentinel) else { return Ok(()) };
+
+        let fee = account.info.balance;
+        let mut info = match self.account(beneficiary) {
+            Some(info) => info.cloned(),
+            None => db.basic_ref(beneficiary)?,
+        }
+        .unwrap_or_default();
+        info.balance = info.balance.saturating_add(fee);
+        account.info = info;
+        state.insert(beneficiary, account);
+
+        Ok(())
+    }
+}
+
+/// A database that reads the committed state on top of the parent state and records all reads.
+struct SpeculativeDb<'a, DB> {
+    db: &'a DB,
+    overlay: Option<&'a Overlay>,
+    reads: ReadSet,
+    beneficiary: Address,
+    sentinel: Address,
+    accesses_beneficiary: bool,
+    touches_system_contract: bool,
+}
+
+impl<'a, DB> SpeculativeDb<'a, DB> {
+    const fn new(
+        db: &'a DB,
+        overlay: Option<&'a Overlay>,
+        beneficiary: Address,
+        sentinel: Address,
+    ) -> Self {
+        Self {
+            db,
+            overlay,
+            reads: ReadSet { accounts: AddressMap::default(), storage: HashMap::default() },
+            beneficiary,
+            sentinel,
+            accesses_beneficiary: false,
+            touches_system_contract: false,
+        }
+    }
+}
+
+impl<DB> std::fmt::Debug for SpeculativeDb<'_, DB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpeculativeDb").field("reads", &self.reads).finish_non_exhaustive()
+    }
+}
+
+impl<DB: DatabaseRef> revm::Database for SpeculativeDb<'_, DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if address == self.sentinel {
+            return Ok(None)
+        }
+        if address == self.beneficiary {
+            self.accesses_beneficiary = true;
+        }
+
+        let info = match self.overlay.and_then(|overlay| overlay.account(address)) {
+            Some(info) => info.cloned(),
+            None => self.db.basic_ref(address)?,
+        };
+        self.reads.accounts.entry(address).or_insert_with(|| info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(code) = self.overlay.and_then(|overlay| overlay.contracts.get(&code_hash)) {
+            return Ok(code.clone())
+        }
+        self.db.code_by_hash_ref(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if PRE_BLOCK_SYSTEM_CONTRACTS.contains(&address) {
+            self.touches_system_contract = true;
+        }
+
+        let value = match self.overlay.and_then(|overlay| overlay.storage(address, index)) {
+            Some(value) => value,
+            None => self.db.storage_ref(address, index)?,
+        };
+        self.reads.storage.entry((address, index)).or_insert(value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.db.block_hash_ref(number)
+    }
+}
+
+/// Flags transactions that use the `COINBASE` opcode, since they observe the beneficiary.
+#[derive(Debug, Clone, Default)]
+struct CoinbaseInspector(Arc<AtomicBool>);
+
+impl<CTX: ContextTr> Inspector<CTX, EthInterpreter> for CoinbaseInspector {
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        if interp.bytecode.opcode() == opcode::COINBASE {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Metrics for parallel execution.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "sync.parallel_execution")]
+pub(crate) struct ParallelExecutionMetrics {
+    /// A histogram of the number of transactions executed in parallel per block
+    pub(crate) transactions: Histogram,
+    /// A histogram of the number of re-executed transactions per block
+    pub(crate) reexecuted_transactions: Histogram,
+    /// A histogram of the duration of parallel execution per block
+    pub(crate) duration: Histogram,
+    /// The number of blocks that could not be executed in parallel
+    pub(crate) aborted: Counter,
+    /// The number of blocks whose parallel execution result differs from sequential execution
+    pub(crate) mismatches: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Eip658Value, Receipt};
+    use revm_state::{Account, AccountStatus, EvmStorageSlot};
+
+    fn touched(storage: impl IntoIterator<Item = (U256, U256)>) -> Account {
+        Account {
+            info: AccountInfo::default(),
+            storage: storage
+                .into_iter()
+                .map(|(slot, value)| (slot, EvmStorageSlot::new_changed(U256::ZERO, value, 0)))
+                .collect(),
+            status: AccountStatus::Touched,
+            transaction_id: 0,
+        }
+    }
+
+    #[test]
+    fn read_set_validation() {
+        let address = Address::with_last_byte(1);
+        let slot = U256::from(1);
+
+        let mut reads = ReadSet::default();
+        reads.accounts.insert(address, None);
+        reads.storage.insert((address, slot), U256::ZERO);
+
+        let mut overlay = Overlay::default();
+        assert!(reads.is_valid(&overlay));
+
+        // the account stays empty and a different slot is written
+        overlay
+            .commit(&EvmState::from_iter([(address, touched([(U256::from(2), U256::from(1))]))]));
+        assert!(reads.is_valid(&overlay));
+
+        overlay.commit(&EvmState::from_iter([(address, touched([(slot, U256::from(1))]))]));
+        assert!(!reads.is_valid(&overlay));
+
+        // the slot is reset to the value that was read
+        overlay.commit(&EvmState::from_iter([(address, touched([(slot, U256::ZERO)]))]));
+        assert!(reads.is_valid(&overlay));
+    }
+
+    #[test]
+    fn compare_receipts() {
+        let log = Log::new_unchecked(Address::ZERO, vec![], Default::default());
+        let outcome = ParallelExecutionOutcome {
+            transactions: vec![
+                ParallelTransactionOutcome { success: true, gas_used: 21_000, logs: vec![] },
+                ParallelTransactionOutcome {
+                    success: false,
+                    gas_used: 30_000,
+                    logs: vec![log.clone()],
+                },
+            ],
+            reexecuted: 0,
+        };
+        let receipt = |status: bool, cumulative_gas_used, logs| Receipt {
+            status: Eip658Value::Eip658(status),
+            cumulative_gas_used,
+            logs,
+        };
+
+        let receipts = vec![receipt(true, 21_000, vec![]), receipt(false, 51_000, vec![log])];
+        assert_eq!(outcome.first_mismatch(&receipts), None);
+        assert_eq!(outcome.first_mismatch(&receipts[..1]), Some(1));
+
+        let receipts = vec![receipt(true, 21_000, vec![]), receipt(false, 51_000, vec![])];
+        assert_eq!(outcome.first_mismatch(&receipts), Some(1));
+    }
+}
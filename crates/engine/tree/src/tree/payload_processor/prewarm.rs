@@ -3,7 +3,8 @@
 use crate::tree::{
     cached_state::{CachedStateMetrics, CachedStateProvider, ProviderCaches, SavedCache},
     payload_processor::{
-        executor::WorkloadExecutor, multiproof::MultiProofMessage, ExecutionCache,
+        executor::WorkloadExecutor, multiproof::MultiProofMessage,
+        parallel::ParallelExecutionOutcome, ExecutionCache,
     },
     precompile_cache::{CachedPrecompile, PrecompileCacheMap},
     ExecutionEnv, StateProviderBuilder,
@@ -48,6 +49,8 @@ where
     to_multi_proof: Option<Sender<MultiProofMessage>>,
     /// Receiver for events produced by tx execution
     actions_rx: Receiver<PrewarmTaskEvent>,
+    /// Sender for the outcome of parallel execution, if it should be reported.
+    parallel_outcome_tx: Option<Sender<ParallelExecutionOutcome>>,
 }
 
 impl<N, P, Evm> PrewarmCacheTask<N, P, Evm>
@@ -62,6 +65,7 @@ where
         execution_cache: ExecutionCache,
         ctx: PrewarmContext<N, P, Evm>,
         to_multi_proof: Option<Sender<MultiProofMessage>>,
+        parallel_outcome_tx: Option<Sender<ParallelExecutionOutcome>>,
     ) -> (Self, Sender<PrewarmTaskEvent>) {
         let (actions_tx, actions_rx) = channel();
         (
@@ -72,6 +76,7 @@ where
                 max_concurrency: 64,
                 to_multi_proof,
                 actions_rx,
+                parallel_outcome_tx,
             },
             actions_tx,
         )
@@ -122,6 +127,24 @@ where
         });
     }
 
+    /// Spawns optimistic parallel execution of all pending transactions.
+    ///
+    /// See [`PrewarmContext::execute_parallel`].
+    fn spawn_parallel(
+        &mut self,
+        pending: mpsc::Receiver<impl OwnedExecutableTxFor<Evm>>,
+        actions_tx: Sender<PrewarmTaskEvent>,
+    ) {
+        let executor = self.executor.clone();
+        let ctx = self.ctx.clone();
+        let max_concurrency = self.max_concurrency;
+        let outcome_tx = self.parallel_outcome_tx.take();
+
+        self.executor.spawn_blocking(move || {
+            ctx.execute_parallel(&executor, max_concurrency, pending, actions_tx, outcome_tx);
+        });
+    }
+
     /// If configured and the tx returned proof targets, emit the targets the transaction produced
     fn send_multi_proof_targets(&self, targets: Option<MultiProofTargets>) {
         if let Some((proof_targets, to_multi_proof)) = targets.zip(self.to_multi_proof.as_ref()) {
@@ -155,12 +178,16 @@ where
     /// This will execute the transactions until all transactions have been processed or the task
     /// was cancelled.
     pub(super) fn run(
-        self,
+        mut self,
         pending: mpsc::Receiver<impl OwnedExecutableTxFor<Evm>>,
         actions_tx: Sender<PrewarmTaskEvent>,
     ) {
         // spawn execution tasks.
-        self.spawn_all(pending, actions_tx);
+        if self.ctx.parallel_prewarming {
+            self.spawn_parallel(pending, actions_tx);
+        } else {
+            self.spawn_all(pending, actions_tx);
+        }
 
         let mut final_block_output = None;
         let mut finished_execution = false;
@@ -221,6 +248,8 @@ where
     pub(super) terminate_execution: Arc<AtomicBool>,
     pub(super) precompile_cache_disabled: bool,
     pub(super) precompile_cache_map: PrecompileCacheMap<SpecFor<Evm>>,
+    /// Whether transactions are executed in parallel and committed to compute exact proof targets.
+    pub(super) parallel_prewarming: bool,
 }
 
 impl<N, P, Evm> PrewarmContext<N, P, Evm>
@@ -242,6 +271,7 @@ where
             terminate_execution,
             precompile_cache_disabled,
             mut precompile_cache_map,
+            ..
         } = self;
 
         let state_provider = match provider.build() {
@@ -341,7 +371,7 @@ where
 
 /// Returns a set of [`MultiProofTargets`] and the total amount of storage targets, based on the
 /// given state.
-fn multiproof_targets_from_state(state: EvmState) -> (MultiProofTargets, usize) {
+pub(super) fn multiproof_targets_from_state(state: EvmState) -> (MultiProofTargets, usize) {
     let mut targets = MultiProofTargets::with_capacity(state.len());
     let mut storage_targets = 0;
     for (addr, account) in state {
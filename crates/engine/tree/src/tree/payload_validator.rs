@@ -391,6 +391,15 @@ where
             ensure_ok!(self.execute_block(&state_provider, env, &block, &mut handle))
         };
 
+        if self.config.parallel_prewarming_differential() {
+            // compare the parallel execution outcome against the sequential receipts
+            self.payload_processor.spawn_parallel_execution_comparison(
+                &mut handle,
+                block_num_hash,
+                output.receipts.clone(),
+            );
+        }
+
         // after executing the block we can stop executing transactions
         handle.stop_prewarming_execution();
 
@@ -0,0 +1,291 @@
+//! Replays engine API messages stored by the [`EngineMessageStore`] into the consensus engine.
+
+use crate::engine_store::{EngineMessageStore, StoredEngineApiMessage};
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::PayloadStatusEnum;
+use reth_engine_primitives::{BeaconConsensusEngineHandle, ExecutionPayload};
+use reth_payload_primitives::PayloadTypes;
+use std::{
+    collections::VecDeque,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tracing::*;
+
+/// The kind of a replayed engine API message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayedMessageKind {
+    /// An `engine_newPayload` call.
+    NewPayload,
+    /// An `engine_forkchoiceUpdated` call.
+    ForkchoiceUpdated,
+}
+
+/// The result of replaying a single engine API message.
+#[derive(Debug, Clone)]
+pub struct ReplayedEngineMessage {
+    /// The kind of the message.
+    pub kind: ReplayedMessageKind,
+    /// The time in milliseconds at which the message was originally received.
+    pub received_at: u64,
+    /// The hash of the new payload or the head block hash of the forkchoice update.
+    pub block_hash: B256,
+    /// The payload status returned by the engine.
+    pub status: PayloadStatusEnum,
+    /// The time it took the engine to respond to the message.
+    pub latency: Duration,
+}
+
+/// Feeds the messages of an [`EngineMessageStore`] into the consensus engine, one at a time and in
+/// the order they were originally received.
+#[derive(Debug)]
+pub struct EngineMessageReplayer<T: PayloadTypes> {
+    /// Handle to the consensus engine.
+    engine: BeaconConsensusEngineHandle<T>,
+    /// The stored messages that were not replayed yet, with the time they were received at.
+    messages: VecDeque<(u64, PathBuf)>,
+    /// The time the previously replayed message was originally received at.
+    last_received_at: Option<u64>,
+}
+
+impl<T: PayloadTypes> EngineMessageReplayer<T> {
+    /// Creates a new replayer for all messages in the given store.
+    pub fn new(
+        store: &EngineMessageStore,
+        engine: BeaconConsensusEngineHandle<T>,
+    ) -> eyre::Result<Self> {
+        let messages = store.timestamped_engine_messages_iter()?.collect();
+        Ok(Self { engine, messages, last_received_at: None })
+    }
+
+    /// Returns the number of messages that are left to be replayed.
+    pub fn remaining(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns the time that passed between receiving the previously replayed message and the next
+    /// message, when they were originally recorded.
+    ///
+    /// Returns `None` if no message was replayed yet or there are no messages left.
+    pub fn next_delay(&self) -> Option<Duration> {
+        let last = self.last_received_at?;
+        let (next, _) = self.messages.front()?;
+        Some(Duration::from_millis(next.saturating_sub(last)))
+    }
+
+    /// Sends the next stored message to the engine and waits for the response.
+    ///
+    /// Returns `None` if all messages were replayed.
+    pub async fn replay_next(&mut self) -> Option<eyre::Result<ReplayedEngineMessage>> {
+        let (received_at, path) = self.messages.pop_front()?;
+        self.last_received_at = Some(received_at);
+        Some(self.replay(received_at, path).await)
+    }
+
+    async fn replay(&self, received_at: u64, path: PathBuf) -> eyre::Result<ReplayedEngineMessage> {
+        let message = EngineMessageStore::read_message::<T>(&path)?;
+        debug!(target: "engine::replay", ?path, "Replaying engine API message");
+
+        let started_at = Instant::now();
+        let (kind, block_hash, status) = match message {
+            StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs, version } => {
+                let response =
+                    self.engine.fork_choice_updated(state, payload_attrs, version).await?;
+                (
+                    ReplayedMessageKind::ForkchoiceUpdated,
+                    state.head_block_hash,
+                    response.payload_status.status,
+                )
+            }
+            StoredEngineApiMessage::NewPayload { payload } => {
+                let block_hash = payload.block_hash();
+                let response = self.engine.new_payload(payload).await?;
+                (ReplayedMessageKind::NewPayload, block_hash, response.status)
+            }
+        };
+
+        Ok(ReplayedEngineMessage {
+            kind,
+            received_at,
+            block_hash,
+            status,
+            latency: started_at.elapsed(),
+        })
+    }
+}
+
+/// Latency statistics of replayed engine API messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayLatencies {
+    /// The number of messages.
+    pub count: usize,
+    /// The lowest latency.
+    pub min: Duration,
+    /// The median latency.
+    pub p50: Duration,
+    /// The 99th percentile latency.
+    pub p99: Duration,
+    /// The highest latency.
+    pub max: Duration,
+    /// The mean latency.
+    pub mean: Duration,
+}
+
+impl ReplayLatencies {
+    /// Computes the statistics of the given latencies.
+    ///
+    /// Returns `None` if there are no latencies.
+    fn new(latencies: &[Duration]) -> Option<Self> {
+        let mut sorted = latencies.to_vec();
+        sorted.sort_unstable();
+        let percentile = |p: usize| sorted[(sorted.len() - 1) * p / 100];
+
+        Some(Self {
+            count: sorted.len(),
+            min: *sorted.first()?,
+            p50: percentile(50),
+            p99: percentile(99),
+            max: *sorted.last()?,
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+        })
+    }
+}
+
+/// Collects the results of an engine API message replay.
+#[derive(Debug, Default)]
+pub struct EngineReplayReport {
+    new_payload_latencies: Vec<Duration>,
+    forkchoice_updated_latencies: Vec<Duration>,
+    invalid: usize,
+}
+
+impl EngineReplayReport {
+    /// Records the result of a replayed message.
+    pub fn on_message(&mut self, message: &ReplayedEngineMessage) {
+        match message.kind {
+            ReplayedMessageKind::NewPayload => self.new_payload_latencies.push(message.latency),
+            ReplayedMessageKind::ForkchoiceUpdated => {
+                self.forkchoice_updated_latencies.push(message.latency)
+            }
+        }
+        if message.status.is_invalid() {
+            self.invalid += 1;
+        }
+    }
+
+    /// Returns the number of messages the engine responded to with an invalid status.
+    pub const fn invalid(&self) -> usize {
+        self.invalid
+    }
+
+    /// Returns the latency statistics of all messages of the given kind.
+    pub fn latencies(&self, kind: ReplayedMessageKind) -> Option<ReplayLatencies> {
+        match kind {
+            ReplayedMessageKind::NewPayload => ReplayLatencies::new(&self.new_payload_latencies),
+            ReplayedMessageKind::ForkchoiceUpdated => {
+                ReplayLatencies::new(&self.forkchoice_updated_latencies)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Block, TxEnvelope};
+    use alloy_rpc_types_engine::{ExecutionData, ExecutionPayload, ForkchoiceState, PayloadStatus};
+    use reth_engine_primitives::{BeaconEngineMessage, OnForkChoiceUpdated};
+    use reth_ethereum_engine_primitives::EthPayloadTypes;
+    use reth_payload_primitives::EngineApiMessageVersion;
+    use std::time::SystemTime;
+    use tokio::sync::{mpsc, oneshot};
+
+    #[tokio::test]
+    async fn replay_journal_into_engine() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EngineMessageStore::new(dir.path().to_path_buf());
+
+        let (payload, sidecar) = ExecutionPayload::from_block_slow(&Block::<TxEnvelope>::default());
+        let payload = ExecutionData::new(payload, sidecar);
+        let block_hash = payload.block_hash();
+        let state = ForkchoiceState { head_block_hash: block_hash, ..Default::default() };
+
+        let received_at = |millis| SystemTime::UNIX_EPOCH + Duration::from_millis(millis);
+        store
+            .on_message::<EthPayloadTypes>(
+                &BeaconEngineMessage::NewPayload { payload, tx: oneshot::channel().0 },
+                received_at(1_000),
+            )
+            .unwrap();
+        store
+            .on_message::<EthPayloadTypes>(
+                &BeaconEngineMessage::ForkchoiceUpdated {
+                    state,
+                    payload_attrs: None,
+                    version: EngineApiMessageVersion::V3,
+                    tx: oneshot::channel().0,
+                },
+                received_at(1_500),
+            )
+            .unwrap();
+
+        // Responds to every message like an engine that accepts the payload.
+        let (to_engine, mut from_replayer) = mpsc::unbounded_channel();
+        let engine = tokio::spawn(async move {
+            let mut fcu_versions = Vec::new();
+            while let Some(message) = from_replayer.recv().await {
+                match message {
+                    BeaconEngineMessage::NewPayload { tx, .. } => {
+                        let _ = tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Valid)));
+                    }
+                    BeaconEngineMessage::ForkchoiceUpdated { version, tx, .. } => {
+                        fcu_versions.push(version);
+                        let _ = tx.send(Ok(OnForkChoiceUpdated::syncing()));
+                    }
+                }
+            }
+            fcu_versions
+        });
+
+        let mut replayer = EngineMessageReplayer::<EthPayloadTypes>::new(
+            &store,
+            BeaconConsensusEngineHandle::new(to_engine),
+        )
+        .unwrap();
+        assert_eq!(replayer.remaining(), 2);
+        assert_eq!(replayer.next_delay(), None);
+
+        let new_payload = replayer.replay_next().await.unwrap().unwrap();
+        assert_eq!(new_payload.kind, ReplayedMessageKind::NewPayload);
+        assert_eq!(new_payload.received_at, 1_000);
+        assert_eq!(new_payload.block_hash, block_hash);
+        assert_eq!(new_payload.status, PayloadStatusEnum::Valid);
+        assert_eq!(replayer.next_delay(), Some(Duration::from_millis(500)));
+
+        let fcu = replayer.replay_next().await.unwrap().unwrap();
+        assert_eq!(fcu.kind, ReplayedMessageKind::ForkchoiceUpdated);
+        assert_eq!(fcu.received_at, 1_500);
+        assert_eq!(fcu.block_hash, block_hash);
+        assert_eq!(fcu.status, PayloadStatusEnum::Syncing);
+
+        assert!(replayer.replay_next().await.is_none());
+
+        // The engine stops once the replayer drops the handle.
+        drop(replayer);
+        assert_eq!(engine.await.unwrap(), vec![EngineApiMessageVersion::V3]);
+    }
+
+    #[test]
+    fn replay_latencies() {
+        assert_eq!(ReplayLatencies::new(&[]), None);
+
+        let latencies = (1..=100).map(Duration::from_millis).collect::<Vec<_>>();
+        let stats = ReplayLatencies::new(&latencies).unwrap();
+        assert_eq!(stats.count, 100);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.p50, Duration::from_millis(50));
+        assert_eq!(stats.p99, Duration::from_millis(99));
+        assert_eq!(stats.max, Duration::from_millis(100));
+        assert_eq!(stats.mean, Duration::from_micros(50_500));
+    }
+}
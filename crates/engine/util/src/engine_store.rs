@@ -4,11 +4,11 @@ use alloy_rpc_types_engine::ForkchoiceState;
 use futures::{Stream, StreamExt};
 use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload};
 use reth_fs_util as fs;
-use reth_payload_primitives::PayloadTypes;
+use reth_payload_primitives::{EngineApiMessageVersion, PayloadTypes};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
@@ -25,6 +25,11 @@ pub enum StoredEngineApiMessage<T: PayloadTypes> {
         state: ForkchoiceState,
         /// The payload attributes sent in the persisted call, if any.
         payload_attrs: Option<T::PayloadAttributes>,
+        /// The version of the persisted call.
+        ///
+        /// Messages stored before the version was recorded default to the latest version.
+        #[serde(default)]
+        version: EngineApiMessageVersion,
     },
     /// The on-disk representation of an `engine_newPayload` method call.
     NewPayload {
@@ -66,7 +71,7 @@ impl EngineMessageStore {
                 state,
                 payload_attrs,
                 tx: _tx,
-                version: _version,
+                version,
             } => {
                 let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
                 fs::write(
@@ -74,6 +79,7 @@ impl EngineMessageStore {
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::ForkchoiceUpdated {
                         state: *state,
                         payload_attrs: payload_attrs.clone(),
+                        version: *version,
                     })?,
                 )?;
             }
@@ -92,6 +98,14 @@ impl EngineMessageStore {
 
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
+        Ok(self.timestamped_engine_messages_iter()?.map(|(_, path)| path))
+    }
+
+    /// Finds and iterates through any stored engine API message files together with the time in
+    /// milliseconds at which they were received, ordered by timestamp.
+    pub fn timestamped_engine_messages_iter(
+        &self,
+    ) -> eyre::Result<impl Iterator<Item = (u64, PathBuf)>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
@@ -107,7 +121,17 @@ impl EngineMessageStore {
                 tracing::warn!(target: "engine::store", ?filename, "Skipping non json file");
             }
         }
-        Ok(filenames_by_ts.into_iter().flat_map(|(_, paths)| paths))
+        Ok(filenames_by_ts
+            .into_iter()
+            .flat_map(|(timestamp, paths)| paths.into_iter().map(move |path| (timestamp, path))))
+    }
+
+    /// Reads the stored engine API message from the given file.
+    pub fn read_message<T>(path: &Path) -> eyre::Result<StoredEngineApiMessage<T>>
+    where
+        T: PayloadTypes,
+    {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
     }
 }
 
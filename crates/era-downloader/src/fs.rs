@@ -10,6 +10,33 @@ use std::{fmt::Debug, io, io::BufRead, path::Path, str::FromStr};
 pub fn read_dir(
     dir: impl AsRef<Path> + Send + Sync + 'static,
     start_from: BlockNumber,
+) -> eyre::Result<impl Stream<Item = eyre::Result<EraLocalMeta>> + Send + Sync + 'static + Unpin> {
+    read_files(dir, "era1", start_from as usize / BLOCKS_PER_FILE, true)
+}
+
+/// Creates a new ordered asynchronous [`Stream`] of consensus-layer ERA files read from `dir`.
+///
+/// Unlike ERA1 files, ERA files are numbered by slot rather than by block, so all files are
+/// returned. The files are verified against `checksums.txt`, which may only be missing if
+/// `checksums_required` is false. Files that were obtained from a remote source must always be
+/// verified.
+pub fn read_beacon_dir(
+    dir: impl AsRef<Path> + Send + Sync + 'static,
+    checksums_required: bool,
+) -> eyre::Result<impl Stream<Item = eyre::Result<EraLocalMeta>> + Send + Sync + 'static + Unpin> {
+    read_files(dir, "era", 0, checksums_required)
+}
+
+/// Creates a new ordered asynchronous [`Stream`] of files with `extension` read from `dir`,
+/// skipping the first `skip` files.
+///
+/// Every file is verified against its checksum in `checksums.txt`, which may only be missing if
+/// `checksums_required` is false.
+fn read_files(
+    dir: impl AsRef<Path> + Send + Sync + 'static,
+    extension: &str,
+    skip: usize,
+    checksums_required: bool,
 ) -> eyre::Result<impl Stream<Item = eyre::Result<EraLocalMeta>> + Send + Sync + 'static + Unpin> {
     let mut checksums = None;
     let mut entries = fs::read_dir(dir)?
@@ -17,7 +44,7 @@ pub fn read_dir(
             (|| {
                 let path = entry?.path();
 
-                if path.extension() == Some("era1".as_ref()) {
+                if path.extension() == Some(extension.as_ref()) {
                     if let Some(last) = path.components().next_back() {
                         let str = last.as_os_str().to_string_lossy().to_string();
                         let parts = str.split('-').collect::<Vec<_>>();
@@ -41,33 +68,36 @@ pub fn read_dir(
             .transpose()
         })
         .collect::<eyre::Result<Vec<_>>>()?;
-    let mut checksums = checksums.ok_or_eyre("Missing file `checksums.txt` in the `dir`")?;
+    if checksums_required && checksums.is_none() {
+        return Err(eyre!("Missing file `checksums.txt` in the `dir`"));
+    }
 
     entries.sort_by(|(left, _), (right, _)| left.cmp(right));
 
-    Ok(stream::iter(entries.into_iter().skip(start_from as usize / BLOCKS_PER_FILE).map(
-        move |(_, path)| {
-            let expected_checksum =
-                checksums.next().transpose()?.ok_or_eyre("Got less checksums than ERA files")?;
-            let expected_checksum = hex::decode(expected_checksum)?;
-
-            let mut hasher = Sha256::new();
-            let mut reader = io::BufReader::new(fs::open(&path)?);
-
-            io::copy(&mut reader, &mut hasher)?;
-            let actual_checksum = hasher.finalize().to_vec();
-
-            if actual_checksum != expected_checksum {
-                return Err(eyre!(
-                    "Checksum mismatch, got: {}, expected: {}",
-                    actual_checksum.encode_hex(),
-                    expected_checksum.encode_hex()
-                ));
-            }
-
-            Ok(EraLocalMeta::new(path))
-        },
-    )))
+    Ok(stream::iter(entries.into_iter().skip(skip).map(move |(_, path)| {
+        let Some(checksums) = checksums.as_mut() else {
+            return Ok(EraLocalMeta::new(path));
+        };
+        let expected_checksum =
+            checksums.next().transpose()?.ok_or_eyre("Got less checksums than ERA files")?;
+        let expected_checksum = hex::decode(expected_checksum)?;
+
+        let mut hasher = Sha256::new();
+        let mut reader = io::BufReader::new(fs::open(&path)?);
+
+        io::copy(&mut reader, &mut hasher)?;
+        let actual_checksum = hasher.finalize().to_vec();
+
+        if actual_checksum != expected_checksum {
+            return Err(eyre!(
+                "Checksum mismatch, got: {}, expected: {}",
+                actual_checksum.encode_hex(),
+                expected_checksum.encode_hex()
+            ));
+        }
+
+        Ok(EraLocalMeta::new(path))
+    })))
 }
 
 /// Contains information about an ERA file that is on the local file-system and is read-only.
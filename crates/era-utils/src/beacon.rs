@@ -0,0 +1,180 @@
+use crate::history::{import_with, process_iter};
+use alloy_primitives::{BlockHash, BlockNumber, U256};
+use alloy_rlp::Decodable;
+use eyre::{bail, ensure};
+use futures_util::Stream;
+use reth_db_api::{table::Value, transaction::DbTxMut};
+use reth_era::{
+    consensus_types::CompressedSignedBeaconBlock, e2s_types::E2sError, era_file::EraReader,
+};
+use reth_era_downloader::EraMeta;
+use reth_etl::Collector;
+use reth_fs_util as fs;
+use reth_primitives_traits::{Block, FullBlockBody, FullBlockHeader, NodePrimitives};
+use reth_provider::{
+    providers::StaticFileProviderRWRefMut, BlockWriter, StaticFileProviderFactory,
+};
+use reth_storage_api::{
+    BlockHashReader, DBProvider, DatabaseProviderFactory, NodePrimitivesProvider,
+    StageCheckpointWriter,
+};
+use std::ops::{Bound, RangeBounds};
+
+/// Imports post-merge blocks from the consensus-layer `.era` files of `downloader` using
+/// `provider`.
+///
+/// Blocks that precede the merge or that are already stored are skipped, so the files may overlap
+/// with the history imported from ERA1 files.
+///
+/// Returns current block height.
+pub fn import_beacon<Downloader, Era, PF, B, BB, BH>(
+    downloader: Downloader,
+    provider_factory: &PF,
+    hash_collector: &mut Collector<BlockHash, BlockNumber>,
+) -> eyre::Result<BlockNumber>
+where
+    B: Block<Header = BH, Body = BB>,
+    BH: FullBlockHeader + Value,
+    BB: FullBlockBody<
+        Transaction = <<<PF as DatabaseProviderFactory>::ProviderRW as NodePrimitivesProvider>::Primitives as NodePrimitives>::SignedTx,
+        OmmerHeader = BH,
+    >,
+    Downloader: Stream<Item = eyre::Result<Era>> + Send + 'static + Unpin,
+    Era: EraMeta + Send + 'static,
+    PF: DatabaseProviderFactory<
+        ProviderRW: BlockWriter<Block = B>
+            + BlockHashReader
+            + DBProvider
+            + StaticFileProviderFactory<Primitives: NodePrimitives<Block = B, BlockHeader = BH, BlockBody = BB>>
+            + StageCheckpointWriter,
+    > + StaticFileProviderFactory<Primitives = <<PF as DatabaseProviderFactory>::ProviderRW as NodePrimitivesProvider>::Primitives>,
+{
+    import_with(
+        downloader,
+        provider_factory,
+        hash_collector,
+        |meta, writer, provider, hash_collector, total_difficulty, height| {
+            process_beacon(meta, writer, provider, hash_collector, total_difficulty, height..)
+        },
+    )
+}
+
+/// Extracts execution blocks from the beacon blocks of `meta` and appends their headers and
+/// bodies using `writer` and `provider`.
+///
+/// Adds on to `total_difficulty` and collects hash to height using `hash_collector`.
+///
+/// Skips all blocks below the [`start_bound`] of `block_numbers` and stops when reaching past the
+/// [`end_bound`] or the end of the file.
+///
+/// Every appended block must be the child of the block before it, starting with the stored block
+/// at the [`start_bound`].
+///
+/// Returns last block height.
+///
+/// [`start_bound`]: RangeBounds::start_bound
+/// [`end_bound`]: RangeBounds::end_bound
+pub fn process_beacon<Era, P, B, BB, BH>(
+    meta: &Era,
+    writer: &mut StaticFileProviderRWRefMut<'_, <P as NodePrimitivesProvider>::Primitives>,
+    provider: &P,
+    hash_collector: &mut Collector<BlockHash, BlockNumber>,
+    total_difficulty: &mut U256,
+    block_numbers: impl RangeBounds<BlockNumber>,
+) -> eyre::Result<BlockNumber>
+where
+    B: Block<Header = BH, Body = BB>,
+    BH: FullBlockHeader + Value,
+    BB: FullBlockBody<
+        Transaction = <<P as NodePrimitivesProvider>::Primitives as NodePrimitives>::SignedTx,
+        OmmerHeader = BH,
+    >,
+    Era: EraMeta + ?Sized,
+    P: DBProvider<Tx: DbTxMut> + NodePrimitivesProvider + BlockWriter<Block = B> + BlockHashReader,
+    <P as NodePrimitivesProvider>::Primitives: NodePrimitives<BlockHeader = BH, BlockBody = BB>,
+{
+    let start = match block_numbers.start_bound() {
+        Bound::Included(&number) => number,
+        Bound::Excluded(&number) => number.saturating_sub(1),
+        Bound::Unbounded => 0,
+    };
+    let mut parent = provider.block_hash(start)?.map(|hash| (start, hash));
+
+    let reader = open_beacon(meta)?;
+    let iter = reader.iter().filter_map(|block| decode_beacon(block).transpose()).map(
+        |block: eyre::Result<(BH, BB)>| {
+            let (header, body) = block?;
+            let number = header.number();
+
+            // Blocks up to `start` are skipped, all others are appended on top of each other
+            if number > start {
+                let Some((parent_number, parent_hash)) = parent else {
+                    bail!("Missing parent of block {number}")
+                };
+                ensure!(
+                    parent_number + 1 == number,
+                    "Expected block {} after block {parent_number}, got block {number}",
+                    parent_number + 1
+                );
+                ensure!(
+                    header.parent_hash() == parent_hash,
+                    "Block {number} has parent hash {}, expected {parent_hash}",
+                    header.parent_hash()
+                );
+                parent = Some((number, header.hash_slow()));
+            }
+
+            Ok((header, body))
+        },
+    );
+
+    let height =
+        process_iter(iter, writer, provider, hash_collector, total_difficulty, block_numbers)?;
+
+    meta.mark_as_processed()?;
+
+    Ok(height)
+}
+
+/// Opens the consensus-layer era file described by `meta`.
+pub fn open_beacon<Era>(meta: &Era) -> eyre::Result<EraReader<std::fs::File>>
+where
+    Era: EraMeta + ?Sized,
+{
+    let file = fs::open(meta.path())?;
+    let reader = EraReader::new(file);
+
+    Ok(reader)
+}
+
+/// Extracts a pair of [`FullBlockHeader`] and [`FullBlockBody`] from the execution payload of a
+/// [`CompressedSignedBeaconBlock`].
+///
+/// The hash of the decoded header is verified against the block hash of the payload.
+///
+/// Returns `None` for beacon blocks that precede the merge.
+pub fn decode_beacon<BH, BB>(
+    block: Result<CompressedSignedBeaconBlock, E2sError>,
+) -> eyre::Result<Option<(BH, BB)>>
+where
+    BH: FullBlockHeader + Value,
+    BB: FullBlockBody<OmmerHeader = BH>,
+{
+    let Some(payload) = block?.execution_payload()? else { return Ok(None) };
+    let expected_hash = payload.payload.block_hash();
+    let block = payload.payload.try_into_block_with_sidecar::<BB::Transaction>(&payload.sidecar)?;
+
+    // The payload converts into the Ethereum block types, re-encode them the same way as the
+    // ERA1 import decodes its header and body.
+    let header = BH::decode(&mut alloy_rlp::encode(&block.header).as_slice())?;
+    let body = BB::decode(&mut alloy_rlp::encode(&block.body).as_slice())?;
+
+    let hash = header.hash_slow();
+    ensure!(
+        hash == expected_hash,
+        "Block {} has hash {hash}, expected {expected_hash} from the execution payload",
+        header.number()
+    );
+
+    Ok(Some((header, body)))
+}
@@ -2,7 +2,7 @@
 //! and injecting them into era1 files with `Era1Writer`.
 
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{BlockNumber, B256, U256};
+use alloy_primitives::{keccak256, BlockNumber, U256};
 use eyre::{eyre, Result};
 use reth_era::{
     e2s_types::IndexEntry,
@@ -10,7 +10,7 @@ use reth_era::{
     era1_types::{BlockIndex, Era1Id},
     execution_types::{
         Accumulator, BlockTuple, CompressedBody, CompressedHeader, CompressedReceipts,
-        TotalDifficulty, MAX_BLOCKS_PER_ERA1,
+        HeaderRecord, TotalDifficulty, MAX_BLOCKS_PER_ERA1,
     },
 };
 use reth_fs_util as fs;
@@ -24,6 +24,7 @@ use tracing::{debug, info, warn};
 const REPORT_INTERVAL_SECS: u64 = 10;
 const ENTRY_HEADER_SIZE: usize = 8;
 const VERSION_ENTRY_SIZE: usize = ENTRY_HEADER_SIZE;
+const ACCUMULATOR_ENTRY_SIZE: usize = 32 + ENTRY_HEADER_SIZE;
 
 /// Configuration to export block history
 /// to era1 files
@@ -134,14 +135,19 @@ where
 
         let headers = provider.headers_range(start_block..=end_block)?;
 
-        // Extract first 4 bytes of last block's state root as historical identifier
-        let historical_root = headers
-            .last()
+        // Compute the accumulator from the header-records of all blocks up front, its first 4
+        // bytes identify the file
+        let mut record_difficulty = total_difficulty;
+        let records = headers
+            .iter()
             .map(|header| {
-                let state_root = header.state_root();
-                [state_root[0], state_root[1], state_root[2], state_root[3]]
+                record_difficulty += header.difficulty();
+                HeaderRecord::new(keccak256(alloy_rlp::encode(header)), record_difficulty)
             })
-            .unwrap_or([0u8; 4]);
+            .collect::<Vec<_>>();
+        let accumulator = Accumulator::from_header_records(&records)?;
+        let historical_root =
+            [accumulator.root[0], accumulator.root[1], accumulator.root[2], accumulator.root[3]];
 
         let era1_id = Era1Id::new(&config.network, start_block, block_count as u32)
             .with_hash(historical_root);
@@ -155,7 +161,6 @@ where
         let mut offsets = Vec::<u64>::with_capacity(block_count);
         let mut position = VERSION_ENTRY_SIZE as u64;
         let mut blocks_written = 0;
-        let mut final_header_data = Vec::new();
 
         for (i, header) in headers.into_iter().enumerate() {
             let expected_block_number = start_block + i as u64;
@@ -167,11 +172,6 @@ where
                 &mut total_difficulty,
             )?;
 
-            // Save last block's header data for accumulator
-            if expected_block_number == end_block {
-                final_header_data = compressed_header.data.clone();
-            }
-
             let difficulty = TotalDifficulty::new(total_difficulty);
 
             let header_size = compressed_header.data.len() + ENTRY_HEADER_SIZE;
@@ -207,9 +207,12 @@ where
             }
         }
         if blocks_written > 0 {
-            let accumulator_hash =
-                B256::from_slice(&final_header_data[0..32.min(final_header_data.len())]);
-            let accumulator = Accumulator::new(accumulator_hash);
+            // Offsets are relative to the beginning of the block index entry
+            let index_position = position + ACCUMULATOR_ENTRY_SIZE as u64;
+            let offsets = offsets
+                .into_iter()
+                .map(|offset| (offset as i64 - index_position as i64) as u64)
+                .collect();
             let block_index = BlockIndex::new(start_block, offsets);
 
             writer.write_accumulator(&accumulator)?;
@@ -1,3 +1,4 @@
+use crate::{verify, KnownRoot};
 use alloy_primitives::{BlockHash, BlockNumber, U256};
 use futures_util::{Stream, StreamExt};
 use reth_db_api::{
@@ -41,11 +42,15 @@ use tracing::info;
 
 /// Imports blocks from `downloader` using `provider`.
 ///
+/// Every file is verified before its blocks are written, see [`verify()`] for the role of
+/// `known_roots`.
+///
 /// Returns current block height.
 pub fn import<Downloader, Era, PF, B, BB, BH>(
-    mut downloader: Downloader,
+    downloader: Downloader,
     provider_factory: &PF,
     hash_collector: &mut Collector<BlockHash, BlockNumber>,
+    known_roots: Option<&[KnownRoot]>,
 ) -> eyre::Result<BlockNumber>
 where
     B: Block<Header = BH, Body = BB>,
@@ -62,6 +67,51 @@ where
             + StaticFileProviderFactory<Primitives: NodePrimitives<Block = B, BlockHeader = BH, BlockBody = BB>>
             + StageCheckpointWriter,
     > + StaticFileProviderFactory<Primitives = <<PF as DatabaseProviderFactory>::ProviderRW as NodePrimitivesProvider>::Primitives>,
+{
+    import_with(
+        downloader,
+        provider_factory,
+        hash_collector,
+        |meta, writer, provider, hash_collector, total_difficulty, height| {
+            process(meta, writer, provider, hash_collector, total_difficulty, height.., known_roots)
+        },
+    )
+}
+
+/// Imports blocks from `downloader` using `provider`, extracting every file with `process`.
+///
+/// `process` receives the file, the headers writer, the provider, the hash collector, the total
+/// difficulty and the current block height, and returns the new block height.
+///
+/// Returns current block height.
+pub(crate) fn import_with<Downloader, Era, PF, B, BB, BH>(
+    mut downloader: Downloader,
+    provider_factory: &PF,
+    hash_collector: &mut Collector<BlockHash, BlockNumber>,
+    mut process: impl FnMut(
+        &Era,
+        &mut StaticFileProviderRWRefMut<'_, ProviderPrimitives<PF>>,
+        &PF::ProviderRW,
+        &mut Collector<BlockHash, BlockNumber>,
+        &mut U256,
+        BlockNumber,
+    ) -> eyre::Result<BlockNumber>,
+) -> eyre::Result<BlockNumber>
+where
+    B: Block<Header = BH, Body = BB>,
+    BH: FullBlockHeader + Value,
+    BB: FullBlockBody<
+        Transaction = <ProviderPrimitives<PF> as NodePrimitives>::SignedTx,
+        OmmerHeader = BH,
+    >,
+    Downloader: Stream<Item = eyre::Result<Era>> + Send + 'static + Unpin,
+    Era: EraMeta + Send + 'static,
+    PF: DatabaseProviderFactory<
+        ProviderRW: BlockWriter<Block = B>
+            + DBProvider
+            + StaticFileProviderFactory<Primitives: NodePrimitives<Block = B, BlockHeader = BH, BlockBody = BB>>
+            + StageCheckpointWriter,
+    > + StaticFileProviderFactory<Primitives = ProviderPrimitives<PF>>,
 {
     let (tx, rx) = mpsc::channel();
 
@@ -96,7 +146,7 @@ where
             &provider,
             hash_collector,
             &mut td,
-            height..,
+            height,
         )?;
 
         save_stage_checkpoints(&provider, from, height, height, height)?;
@@ -113,6 +163,10 @@ where
     Ok(height)
 }
 
+/// Node primitives of the read-write provider created by `PF`.
+type ProviderPrimitives<PF> =
+    <<PF as DatabaseProviderFactory>::ProviderRW as NodePrimitivesProvider>::Primitives;
+
 /// Saves progress of ERA import into stages sync.
 ///
 /// Since the ERA import does the same work as `HeaderStage` and `BodyStage`, it needs to inform
@@ -145,6 +199,9 @@ where
 
 /// Extracts block headers and bodies from `meta` and appends them using `writer` and `provider`.
 ///
+/// The file is verified first and rejected before anything is written if it is invalid, see
+/// [`verify()`] for the role of `known_roots`.
+///
 /// Adds on to `total_difficulty` and collects hash to height using `hash_collector`.
 ///
 /// Skips all blocks below the [`start_bound`] of `block_numbers` and stops when reaching past the
@@ -161,6 +218,7 @@ pub fn process<Era, P, B, BB, BH>(
     hash_collector: &mut Collector<BlockHash, BlockNumber>,
     total_difficulty: &mut U256,
     block_numbers: impl RangeBounds<BlockNumber>,
+    known_roots: Option<&[KnownRoot]>,
 ) -> eyre::Result<BlockNumber>
 where
     B: Block<Header = BH, Body = BB>,
@@ -173,6 +231,8 @@ where
     P: DBProvider<Tx: DbTxMut> + NodePrimitivesProvider + BlockWriter<Block = B>,
     <P as NodePrimitivesProvider>::Primitives: NodePrimitives<BlockHeader = BH, BlockBody = BB>,
 {
+    verify(meta, known_roots)?;
+
     let reader = open(meta)?;
     let iter =
         reader
@@ -1,13 +1,25 @@
 //! Utilities to store history from downloaded ERA files with storage-api
 //!  and export it to recreate era1 files.
 //!
+//! Pre-merge history is imported from ERA1 files and post-merge history from the execution
+//! payloads of beacon blocks in consensus-layer ERA files.
+//!
 //! The import is downloaded using [`reth_era_downloader`] and parsed using [`reth_era`].
 
 mod history;
 
+/// Imports post-merge history from consensus-layer era files.
+mod beacon;
+
+/// Verification of era1 files before import.
+mod verify;
+
 /// Export block history data from the database to recreate era1 files.
 mod export;
 
+/// HTTP server hosting era1 files for other nodes to import.
+mod serve;
+
 /// Export history from storage-api between 2 blocks
 /// with parameters defined in [`ExportConfig`].
 pub use export::{export, ExportConfig};
@@ -16,3 +28,12 @@ pub use export::{export, ExportConfig};
 pub use history::{
     build_index, decode, import, open, process, process_iter, save_stage_checkpoints, ProcessIter,
 };
+
+/// Imports post-merge history from consensus-layer era files.
+pub use beacon::{decode_beacon, import_beacon, open_beacon, process_beacon};
+
+/// Serves era1 files over HTTP and exports new ones as blocks finalize.
+pub use serve::{EraFileServer, EraFileServerHandle};
+
+/// Verifies era1 files against their accumulator and known epoch roots.
+pub use verify::{known_roots, read_known_roots, verify, verify_reader, KnownRoot};
@@ -0,0 +1,448 @@
+//! HTTP server hosting ERA1 files in the layout that [`EraClient`] downloads from.
+//!
+//! The server answers:
+//! - `/` and `/index.html` with a page linking every hosted file
+//! - `/checksums.txt` with the SHA-256 checksum of every file, one per line in the order of the
+//!   index
+//! - `/<file name>` with the contents of a hosted file
+//!
+//! [`EraClient`]: reth_era_downloader::EraClient
+
+use crate::{export, ExportConfig};
+use alloy_primitives::{hex, BlockNumber, B256};
+use bytes::Bytes;
+use eyre::eyre;
+use futures_util::{Stream, StreamExt, TryStreamExt};
+use http::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    HeaderValue, Method, Request, Response, StatusCode,
+};
+use http_body::Frame;
+use http_body_util::{combinators::UnsyncBoxBody, BodyExt, Full, StreamBody};
+use parking_lot::RwLock;
+use reth_era::execution_types::MAX_BLOCKS_PER_ERA1;
+use reth_fs_util as fs;
+use reth_storage_api::BlockReader;
+use reth_tasks::TaskExecutor;
+use sha2::{Digest, Sha256};
+use std::{
+    convert::Infallible,
+    io::{self, BufRead},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+use tokio_util::io::ReaderStream;
+use tracing::{debug, error, info};
+
+/// File name of the index page
+const INDEX: &str = "index.html";
+
+/// File name of the checksums list
+const CHECKSUMS: &str = "checksums.txt";
+
+type ResponseBody = UnsyncBoxBody<Bytes, io::Error>;
+
+/// Serves the ERA1 files of a directory over HTTP.
+///
+/// The files are expected to each hold a complete era, so that new files can be exported with
+/// [`Self::export_finalized`] as blocks finalize.
+#[derive(Debug, Clone)]
+pub struct EraFileServer {
+    dir: PathBuf,
+    files: Arc<RwLock<EraFiles>>,
+}
+
+impl EraFileServer {
+    /// Creates a server for the ERA1 files in `dir`, creating the directory if missing.
+    ///
+    /// Checksums are read from `checksums.txt` if it lists every file, otherwise they are
+    /// computed on a blocking thread and the file is rewritten.
+    pub async fn new(dir: impl Into<PathBuf>) -> eyre::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let files = tokio::task::spawn_blocking({
+            let dir = dir.clone();
+            move || EraFiles::read(&dir)
+        })
+        .await??;
+
+        Ok(Self { dir, files: Arc::new(RwLock::new(files)) })
+    }
+
+    /// Returns the directory of the hosted files.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the first block that is not contained in any hosted file.
+    pub fn next_block(&self) -> BlockNumber {
+        self.files.read().next_block()
+    }
+
+    /// Exports every complete era up to the `finalized` block that is not hosted yet, and adds
+    /// the new files to the index.
+    ///
+    /// Returns the number of files written.
+    pub fn export_until<P>(
+        &self,
+        provider: &P,
+        network: &str,
+        finalized: BlockNumber,
+    ) -> eyre::Result<usize>
+    where
+        P: BlockReader,
+    {
+        let mut written = 0;
+
+        loop {
+            let first_block_number = self.next_block();
+            let last_block_number = first_block_number + MAX_BLOCKS_PER_ERA1 as u64 - 1;
+            if last_block_number > finalized {
+                return Ok(written)
+            }
+
+            let config = ExportConfig {
+                dir: self.dir.clone(),
+                first_block_number,
+                last_block_number,
+                max_blocks_per_file: MAX_BLOCKS_PER_ERA1 as u64,
+                network: network.to_string(),
+            };
+
+            let created = export(provider, &config)?;
+            if created.is_empty() {
+                return Err(eyre!(
+                    "No file exported for blocks {first_block_number}..={last_block_number}"
+                ));
+            }
+
+            for path in created {
+                self.files.write().push(&self.dir, &path)?;
+                written += 1;
+            }
+        }
+    }
+
+    /// Exports new files whenever a block of `finalized` completes an era, until the stream ends.
+    ///
+    /// Export failures are logged and retried on the next finalized block.
+    pub async fn export_finalized<P>(
+        self,
+        provider: P,
+        network: String,
+        mut finalized: impl Stream<Item = BlockNumber> + Unpin,
+    ) where
+        P: BlockReader + Clone + 'static,
+    {
+        while let Some(block) = finalized.next().await {
+            if block + 1 < self.next_block() + MAX_BLOCKS_PER_ERA1 as u64 {
+                continue
+            }
+
+            let server = self.clone();
+            let provider = provider.clone();
+            let network = network.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                server.export_until(&provider, &network, block)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(written)) => {
+                    info!(target: "era::serve", written, finalized = block, "Exported ERA1 files")
+                }
+                Ok(Err(err)) => {
+                    error!(target: "era::serve", %err, finalized = block, "Failed to export ERA1 files")
+                }
+                Err(err) => {
+                    error!(target: "era::serve", %err, "ERA1 export task failed");
+                    return
+                }
+            }
+        }
+    }
+
+    /// Binds to the given address and serves requests until the node shuts down.
+    pub async fn start(
+        self,
+        addr: SocketAddr,
+        executor: &TaskExecutor,
+    ) -> io::Result<EraFileServerHandle> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+
+        executor.spawn_with_graceful_shutdown_signal(|mut signal| {
+            Box::pin(async move {
+                loop {
+                    let io = tokio::select! {
+                        _ = &mut signal => break,
+                        io = listener.accept() => {
+                            match io {
+                                Ok((stream, _remote_addr)) => stream,
+                                Err(err) => {
+                                    error!(target: "era::serve", %err, "failed to accept connection");
+                                    continue
+                                }
+                            }
+                        }
+                    };
+
+                    let server = self.clone();
+                    let service = tower::service_fn(move |req| {
+                        let server = server.clone();
+                        async move { Ok::<_, Infallible>(server.handle_request(req).await) }
+                    });
+
+                    let mut shutdown = signal.clone().ignore_guard();
+                    tokio::task::spawn(async move {
+                        let _ = jsonrpsee_server::serve_with_graceful_shutdown(
+                            io,
+                            service,
+                            &mut shutdown,
+                        )
+                        .await
+                        .inspect_err(|error| debug!(target: "era::serve", %error, "failed to serve request"));
+                    });
+                }
+            })
+        });
+
+        Ok(EraFileServerHandle { local_addr })
+    }
+
+    /// Answers a single HTTP request.
+    async fn handle_request<B>(&self, req: Request<B>) -> Response<ResponseBody> {
+        if req.method() != Method::GET {
+            return text(StatusCode::METHOD_NOT_ALLOWED, "only GET is supported".to_string())
+        }
+
+        let name = req.uri().path().trim_start_matches('/');
+        match name {
+            "" | INDEX => {
+                let index = self.files.read().index();
+                let mut response = text(StatusCode::OK, index);
+                response
+                    .headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static("text/html; charset=utf-8"));
+                response
+            }
+            CHECKSUMS => {
+                let checksums = self.files.read().checksums();
+                text(StatusCode::OK, checksums)
+            }
+            _ => {
+                let hosted = self.files.read().contains(name);
+                if !hosted {
+                    return text(StatusCode::NOT_FOUND, "not found".to_string())
+                }
+
+                match self.file(name).await {
+                    Ok(response) => response,
+                    Err(err) => {
+                        error!(target: "era::serve", %err, name, "Failed to open ERA1 file");
+                        text(StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Streams the contents of the hosted file `name`.
+    async fn file(&self, name: &str) -> io::Result<Response<ResponseBody>> {
+        let file = tokio::fs::File::open(self.dir.join(name)).await?;
+        let len = file.metadata().await?.len();
+
+        let body = StreamBody::new(ReaderStream::new(file).map_ok(Frame::data)).boxed_unsync();
+        let mut response = Response::new(body);
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/octet-stream"));
+        response.headers_mut().insert(CONTENT_LENGTH, HeaderValue::from(len));
+
+        Ok(response)
+    }
+}
+
+/// Handle to a running [`EraFileServer`].
+#[derive(Clone, Copy, Debug)]
+pub struct EraFileServerHandle {
+    local_addr: SocketAddr,
+}
+
+impl EraFileServerHandle {
+    /// Returns the address the server is listening on.
+    pub const fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+/// ERA1 files hosted by [`EraFileServer`] ordered by era number.
+#[derive(Debug, Default)]
+struct EraFiles {
+    /// Era number, file name and SHA-256 checksum of every file
+    files: Vec<(u64, String, B256)>,
+}
+
+impl EraFiles {
+    /// Lists the ERA1 files in `dir` and their checksums.
+    fn read(dir: &Path) -> eyre::Result<Self> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension() != Some("era1".as_ref()) {
+                continue
+            }
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else { continue };
+            let Some(number) = era_number(name) else { continue };
+
+            files.push((number, name.to_string()));
+        }
+        files.sort();
+
+        let checksums = Self::read_checksums(dir)?;
+        let checksums = if checksums.len() == files.len() {
+            checksums
+        } else {
+            info!(target: "era::serve", files = files.len(), "Computing ERA1 file checksums");
+            files.iter().map(|(_, name)| checksum(&dir.join(name))).collect::<eyre::Result<_>>()?
+        };
+
+        let this = Self {
+            files: files
+                .into_iter()
+                .zip(checksums)
+                .map(|((number, name), checksum)| (number, name, checksum))
+                .collect(),
+        };
+        this.write_checksums(dir)?;
+
+        Ok(this)
+    }
+
+    /// Reads the checksums from `checksums.txt` in `dir`, if it exists.
+    fn read_checksums(dir: &Path) -> eyre::Result<Vec<B256>> {
+        let path = dir.join(CHECKSUMS);
+        if !path.exists() {
+            return Ok(Vec::new())
+        }
+
+        io::BufReader::new(fs::open(path)?)
+            .lines()
+            .map(|line| Ok(B256::from_str(line?.trim())?))
+            .collect()
+    }
+
+    /// Writes the checksums of all files to `checksums.txt` in `dir`.
+    fn write_checksums(&self, dir: &Path) -> eyre::Result<()> {
+        fs::write(dir.join(CHECKSUMS), self.checksums())?;
+        Ok(())
+    }
+
+    /// Adds the file at `path` and persists its checksum.
+    fn push(&mut self, dir: &Path, path: &Path) -> eyre::Result<()> {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| eyre!("Invalid ERA1 file name {}", path.display()))?;
+        let number =
+            era_number(name).ok_or_else(|| eyre!("Cannot parse era number from {name}"))?;
+
+        self.files.push((number, name.to_string(), checksum(path)?));
+        self.files.sort();
+        self.write_checksums(dir)
+    }
+
+    /// Returns the first block of the era following the last file.
+    fn next_block(&self) -> BlockNumber {
+        self.files.last().map_or(0, |(number, ..)| (number + 1) * MAX_BLOCKS_PER_ERA1 as u64)
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.files.iter().any(|(_, file, _)| file == name)
+    }
+
+    /// Renders the index page with one link per line.
+    fn index(&self) -> String {
+        let mut index = String::from("<html>\n<body>\n");
+        for (_, name, _) in &self.files {
+            index.push_str(&format!("<a href=\"{name}\">{name}</a>\n"));
+        }
+        index.push_str("</body>\n</html>\n");
+        index
+    }
+
+    /// Renders the checksums list with one hex encoded checksum per line.
+    fn checksums(&self) -> String {
+        self.files.iter().map(|(_, _, checksum)| format!("{}\n", hex::encode(checksum))).collect()
+    }
+}
+
+/// Parses the era number from a file name like `mainnet-00000-00001-5ec1ffb8.era1`.
+///
+/// The number is taken from the end of the name, since the network name may contain `-`.
+fn era_number(name: &str) -> Option<u64> {
+    name.trim_end_matches(".era1").rsplit('-').nth(2).and_then(|number| u64::from_str(number).ok())
+}
+
+/// Computes the SHA-256 checksum of the file at `path`.
+fn checksum(path: &Path) -> eyre::Result<B256> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut io::BufReader::new(fs::open(path)?), &mut hasher)?;
+    Ok(B256::from_slice(&hasher.finalize()))
+}
+
+fn text(status: StatusCode, body: String) -> Response<ResponseBody> {
+    let mut response =
+        Response::new(Full::new(Bytes::from(body)).map_err(|e| match e {}).boxed_unsync());
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("text/plain; charset=utf-8"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_files_index_and_checksums() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("mainnet-00001-00001-bbbbbbbb.era1"), b"second").unwrap();
+        fs::write(dir.path().join("mainnet-00000-00001-aaaaaaaa.era1"), b"first").unwrap();
+        fs::write(dir.path().join("notes.txt"), b"ignored").unwrap();
+
+        let files = EraFiles::read(dir.path()).unwrap();
+
+        assert_eq!(files.next_block(), 2 * MAX_BLOCKS_PER_ERA1 as u64);
+        assert!(files.contains("mainnet-00000-00001-aaaaaaaa.era1"));
+        assert!(!files.contains("notes.txt"));
+
+        let index = files.index();
+        let first = index.find("mainnet-00000").unwrap();
+        let second = index.find("mainnet-00001").unwrap();
+        assert!(first < second);
+
+        let expected = format!(
+            "{}\n{}\n",
+            hex::encode(Sha256::digest(b"first")),
+            hex::encode(Sha256::digest(b"second"))
+        );
+        assert_eq!(files.checksums(), expected);
+
+        // The checksums are persisted and reused
+        assert_eq!(fs::read_to_string(dir.path().join(CHECKSUMS)).unwrap(), expected);
+        assert_eq!(EraFiles::read(dir.path()).unwrap().checksums(), expected);
+    }
+
+    #[test]
+    fn test_era_number() {
+        assert_eq!(era_number("mainnet-00000-00001-5ec1ffb8.era1"), Some(0));
+        assert_eq!(era_number("mainnet-01234-00001-5ec1ffb8.era1"), Some(1234));
+        assert_eq!(era_number("my-dev-net-00007-00001-5ec1ffb8.era1"), Some(7));
+        assert_eq!(era_number("00001-5ec1ffb8.era1"), None);
+    }
+}
@@ -0,0 +1,393 @@
+//! Integrity verification of era1 files before their history is imported.
+
+use alloy_chains::{Chain, NamedChain};
+use alloy_primitives::{BlockNumber, FixedBytes, B256};
+use eyre::{bail, eyre, OptionExt};
+use reth_era::{
+    e2s_file::E2StoreReader,
+    e2s_types::{Entry, IndexEntry},
+    era1_types::{BlockIndex, BLOCK_INDEX},
+    execution_types::{
+        Accumulator, CompressedHeader, HeaderRecord, TotalDifficulty, ACCUMULATOR,
+        COMPRESSED_HEADER, MAX_BLOCKS_PER_ERA1, TOTAL_DIFFICULTY,
+    },
+};
+use reth_era_downloader::EraMeta;
+use reth_fs_util as fs;
+use std::{
+    fmt,
+    io::{Read, Seek},
+    path::Path,
+    str::FromStr,
+    sync::LazyLock,
+};
+
+/// Size of the header preceding the data of every e2store entry.
+const ENTRY_HEADER_SIZE: u64 = 8;
+
+/// The known accumulator root of an epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnownRoot {
+    /// The full accumulator root.
+    Full(B256),
+    /// The first 4 bytes of the accumulator root, the short root in the names of published era1
+    /// files, e.g. `5ec1ffb8` in `mainnet-00000-5ec1ffb8.era1`.
+    Short(FixedBytes<4>),
+}
+
+impl KnownRoot {
+    /// Returns `true` if the accumulator `root` matches this known root.
+    pub fn matches(&self, root: &B256) -> bool {
+        match self {
+            Self::Full(known) => known == root,
+            Self::Short(known) => root.starts_with(known.as_slice()),
+        }
+    }
+}
+
+impl From<B256> for KnownRoot {
+    fn from(root: B256) -> Self {
+        Self::Full(root)
+    }
+}
+
+impl fmt::Display for KnownRoot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full(root) => root.fmt(f),
+            Self::Short(root) => root.fmt(f),
+        }
+    }
+}
+
+impl FromStr for KnownRoot {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let root = if s.trim_start_matches("0x").len() == 8 {
+            Self::Short(s.parse()?)
+        } else {
+            Self::Full(s.parse()?)
+        };
+        Ok(root)
+    }
+}
+
+/// Verifies the era1 file described by `meta` before any of its content is imported.
+///
+/// The accumulator is recomputed from the header-records of all blocks in the file and compared
+/// against the accumulator embedded in the file. If `known_roots` are given, the accumulator of a
+/// complete epoch must also match the known root of that epoch, which makes it safe to import
+/// files from untrusted mirrors. The offsets in the block index must point at the block headers.
+pub fn verify<Era>(meta: &Era, known_roots: Option<&[KnownRoot]>) -> eyre::Result<()>
+where
+    Era: EraMeta + ?Sized,
+{
+    let file = fs::open(meta.path())?;
+    verify_reader(file, known_roots)
+        .map_err(|err| eyre!("Invalid era1 file {}: {err}", meta.path().display()))
+}
+
+/// Verifies the era1 file read from `reader`, see [`verify()`].
+pub fn verify_reader<R: Read + Seek>(
+    reader: R,
+    known_roots: Option<&[KnownRoot]>,
+) -> eyre::Result<()> {
+    let mut reader = E2StoreReader::new(reader);
+    let version = reader.read_version()?.ok_or_eyre("Empty era1 file")?;
+
+    let mut position = entry_size(&version);
+    let mut headers = Vec::new();
+    let mut difficulties = Vec::new();
+    let mut accumulator = None;
+    let mut block_index = None;
+
+    while let Some(entry) = reader.read_next_entry()? {
+        match entry.entry_type {
+            COMPRESSED_HEADER => {
+                let header = CompressedHeader::from_entry(&entry)?.decode_header()?;
+                headers.push((position, header));
+            }
+            TOTAL_DIFFICULTY => {
+                difficulties.push(TotalDifficulty::from_entry(&entry)?.value);
+            }
+            ACCUMULATOR => {
+                if accumulator.is_some() {
+                    bail!("Multiple accumulator entries found")
+                }
+                accumulator = Some(Accumulator::from_entry(&entry)?);
+            }
+            BLOCK_INDEX => {
+                if block_index.is_some() {
+                    bail!("Multiple block index entries found")
+                }
+                block_index = Some((position, BlockIndex::from_entry(&entry)?));
+            }
+            _ => {}
+        }
+        position += entry_size(&entry);
+    }
+
+    let accumulator = accumulator.ok_or_eyre("Missing accumulator entry")?;
+    let (index_position, block_index) = block_index.ok_or_eyre("Missing block index entry")?;
+
+    if headers.is_empty() || headers.len() > MAX_BLOCKS_PER_ERA1 {
+        bail!("Invalid number of blocks: {}", headers.len())
+    }
+    if headers.len() != difficulties.len() {
+        bail!(
+            "Mismatched block component counts: headers={}, difficulties={}",
+            headers.len(),
+            difficulties.len()
+        )
+    }
+
+    let start = block_index.starting_number();
+    if block_index.offsets().len() != headers.len() {
+        bail!(
+            "Block index has {} offsets for {} blocks",
+            block_index.offsets().len(),
+            headers.len()
+        )
+    }
+
+    let mut records = Vec::with_capacity(headers.len());
+    for (i, ((header_position, header), total_difficulty)) in
+        headers.iter().zip(&difficulties).enumerate()
+    {
+        let expected_number = start + i as BlockNumber;
+        if header.number != expected_number {
+            bail!("Expected block {expected_number}, got {}", header.number)
+        }
+
+        // Offsets are relative to the beginning of the block index entry
+        let offset = block_index.offsets()[i] as i64;
+        if index_position.checked_add_signed(offset) != Some(*header_position) {
+            bail!("Block index offset {offset} of block {expected_number} does not point at its header")
+        }
+
+        // The total difficulty must add up with the difficulty of the block
+        let expected_difficulty = match i.checked_sub(1) {
+            Some(parent) => total_difficulty.checked_sub(difficulties[parent]),
+            None if start == 0 => Some(*total_difficulty),
+            // The total difficulty of the parent of the first block is unknown
+            None => Some(header.difficulty),
+        };
+        if expected_difficulty != Some(header.difficulty) {
+            bail!("Total difficulty of block {expected_number} does not match its difficulty")
+        }
+
+        records.push(HeaderRecord::new(header.hash_slow(), *total_difficulty));
+    }
+
+    let computed = Accumulator::from_header_records(&records)?;
+    if computed != accumulator {
+        bail!(
+            "Accumulator mismatch: computed {}, found {} in file",
+            computed.root,
+            accumulator.root
+        )
+    }
+
+    if let Some(known_roots) = known_roots {
+        let epoch = start / MAX_BLOCKS_PER_ERA1 as u64;
+        let Some(known_root) = known_roots.get(epoch as usize) else {
+            bail!("No known accumulator root for epoch {epoch}")
+        };
+
+        // Only files covering the full epoch share its root, the last epoch may be partial
+        let is_full_epoch = start % MAX_BLOCKS_PER_ERA1 as u64 == 0 &&
+            (records.len() == MAX_BLOCKS_PER_ERA1 || epoch as usize == known_roots.len() - 1);
+        if !is_full_epoch {
+            bail!(
+                "File does not cover epoch {epoch} and can not be verified against its known root"
+            )
+        }
+        if !known_root.matches(&computed.root) {
+            bail!(
+                "Accumulator {} does not match known root {known_root} of epoch {epoch}",
+                computed.root
+            )
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads known epoch accumulator roots from the file at `path`.
+///
+/// The file contains one hex encoded root per line, ordered by epoch, either the full root or its
+/// first 4 bytes, see [`KnownRoot`]. Empty lines and lines starting with `#` are skipped.
+pub fn read_known_roots(path: impl AsRef<Path>) -> eyre::Result<Vec<KnownRoot>> {
+    parse_known_roots(&fs::read_to_string(path)?)
+}
+
+/// Returns the known epoch accumulator roots that ship with this crate for `chain`.
+///
+/// The mainnet roots are the short roots of the era1 files published for all 1897 pre-merge
+/// epochs.
+///
+/// Returns `None` for chains without ERA1 history and if no roots are listed for the chain.
+pub fn known_roots(chain: Chain) -> Option<&'static [KnownRoot]> {
+    static MAINNET: LazyLock<Vec<KnownRoot>> = LazyLock::new(|| {
+        parse_known_roots(include_str!("../roots/mainnet.txt"))
+            .expect("shipped mainnet roots should be valid")
+    });
+    static SEPOLIA: LazyLock<Vec<KnownRoot>> = LazyLock::new(|| {
+        parse_known_roots(include_str!("../roots/sepolia.txt"))
+            .expect("shipped sepolia roots should be valid")
+    });
+
+    let roots = match chain.named()? {
+        NamedChain::Mainnet => &MAINNET,
+        NamedChain::Sepolia => &SEPOLIA,
+        _ => return None,
+    };
+    (!roots.is_empty()).then_some(roots.as_slice())
+}
+
+/// Parses known epoch accumulator roots, see [`read_known_roots`].
+fn parse_known_roots(roots: &str) -> eyre::Result<Vec<KnownRoot>> {
+    roots
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.parse().map_err(|err| eyre!("Invalid accumulator root {line}: {err}")))
+        .collect()
+}
+
+/// Returns the size of `entry` including its header.
+fn entry_size(entry: &Entry) -> u64 {
+    ENTRY_HEADER_SIZE + entry.data.len() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::U256;
+    use reth_era::{
+        era1_file::Era1Writer,
+        execution_types::{BlockTuple, CompressedBody, CompressedReceipts},
+    };
+    use std::io::Cursor;
+
+    /// Writes an era1 file with `count` blocks, returning the file and its accumulator root.
+    fn write_era1(count: u64, tamper: impl FnOnce(&mut Vec<u64>)) -> (Vec<u8>, B256) {
+        let mut buf = Vec::new();
+        let mut writer = Era1Writer::new(&mut buf);
+        writer.write_version().unwrap();
+
+        let mut records = Vec::new();
+        let mut positions = Vec::new();
+        let mut position = ENTRY_HEADER_SIZE;
+        let mut total_difficulty = U256::ZERO;
+        for number in 0..count {
+            let header = Header { number, difficulty: U256::from(10), ..Default::default() };
+            total_difficulty += header.difficulty;
+            records.push(HeaderRecord::new(header.hash_slow(), total_difficulty));
+
+            let block = BlockTuple::new(
+                CompressedHeader::from_header(&header).unwrap(),
+                CompressedBody::from_rlp(&[0xc2, 0xc0, 0xc0]).unwrap(),
+                CompressedReceipts::from_rlp(&[0xc0]).unwrap(),
+                TotalDifficulty::new(total_difficulty),
+            );
+            positions.push(position);
+            position += [
+                block.header.to_entry(),
+                block.body.to_entry(),
+                block.receipts.to_entry(),
+                block.total_difficulty.to_entry(),
+            ]
+            .iter()
+            .map(entry_size)
+            .sum::<u64>();
+            writer.write_block(&block).unwrap();
+        }
+
+        let accumulator = Accumulator::from_header_records(&records).unwrap();
+        let index_position = position + entry_size(&accumulator.to_entry());
+        let mut offsets = positions
+            .into_iter()
+            .map(|position| (position as i64 - index_position as i64) as u64)
+            .collect::<Vec<_>>();
+        tamper(&mut offsets);
+
+        writer.write_accumulator(&accumulator).unwrap();
+        writer.write_block_index(&BlockIndex::new(0, offsets)).unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        (buf, accumulator.root)
+    }
+
+    #[test]
+    fn verifies_era1_file() {
+        let (file, root) = write_era1(3, |_| {});
+        verify_reader(Cursor::new(&file), None).unwrap();
+        verify_reader(Cursor::new(&file), Some(&[root.into()])).unwrap();
+        let short_root = KnownRoot::Short(FixedBytes::from_slice(&root[..4]));
+        verify_reader(Cursor::new(&file), Some(&[short_root])).unwrap();
+
+        // Root of a different epoch
+        let err = verify_reader(Cursor::new(&file), Some(&[B256::ZERO.into()])).unwrap_err();
+        assert!(err.to_string().contains("does not match known root"), "{err}");
+        let err = verify_reader(Cursor::new(&file), Some(&[KnownRoot::Short(FixedBytes::ZERO)]))
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match known root"), "{err}");
+
+        // Epoch without a known root
+        let err = verify_reader(Cursor::new(&file), Some(&[])).unwrap_err();
+        assert!(err.to_string().contains("No known accumulator root"), "{err}");
+
+        // Block index pointing at the wrong entry
+        let (file, _) = write_era1(3, |offsets| offsets[1] = offsets[1].wrapping_add(8));
+        let err = verify_reader(Cursor::new(&file), None).unwrap_err();
+        assert!(err.to_string().contains("does not point at its header"), "{err}");
+    }
+
+    #[test]
+    fn rejects_accumulator_mismatch() {
+        let (mut file, root) = write_era1(3, |_| {});
+
+        // Corrupt the embedded accumulator root
+        let position = file.windows(32).position(|window| window == root.as_slice()).unwrap();
+        file[position] ^= 1;
+
+        let err = verify_reader(Cursor::new(&file), None).unwrap_err();
+        assert!(err.to_string().contains("Accumulator mismatch"), "{err}");
+    }
+
+    #[test]
+    fn parses_known_roots() {
+        let root = B256::repeat_byte(1);
+        let roots =
+            parse_known_roots(&format!("# comment\n\n{root}\n  {root}  \n01010101\n")).unwrap();
+        assert_eq!(
+            roots,
+            vec![root.into(), root.into(), KnownRoot::Short(FixedBytes::repeat_byte(1))]
+        );
+        assert!(parse_known_roots("0x01").is_err());
+
+        // The shipped roots must parse
+        for chain in [Chain::mainnet(), Chain::sepolia()] {
+            assert!(known_roots(chain).is_none_or(|roots| !roots.is_empty()));
+        }
+        assert_eq!(known_roots(Chain::dev()), None);
+    }
+
+    #[test]
+    fn ships_mainnet_roots() {
+        let roots = known_roots(Chain::mainnet()).unwrap();
+        assert_eq!(roots.len(), 1897);
+
+        // The roots of the first and last epochs, as in `mainnet-00000-5ec1ffb8.era1` and
+        // `mainnet-01896-e6ebe562.era1`
+        let first = B256::right_padding_from(&[0x5e, 0xc1, 0xff, 0xb8]);
+        assert!(roots[0].matches(&first));
+        assert!(!roots[1].matches(&first));
+        let last = B256::right_padding_from(&[0xe6, 0xeb, 0xe5, 0x62]);
+        assert!(roots[1896].matches(&last));
+    }
+}
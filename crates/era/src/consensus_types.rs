@@ -4,6 +4,16 @@ use crate::{
     e2s_types::{E2sError, Entry},
     DecodeCompressedSsz,
 };
+use alloy_eips::{
+    eip4844::VERSIONED_HASH_VERSION_KZG, eip6110::DEPOSIT_REQUEST_TYPE,
+    eip7002::WITHDRAWAL_REQUEST_TYPE, eip7251::CONSOLIDATION_REQUEST_TYPE, eip7685::Requests,
+};
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_engine::{
+    CancunPayloadFields, ExecutionPayload, ExecutionPayloadSidecar, ExecutionPayloadV1,
+    ExecutionPayloadV2, ExecutionPayloadV3, PraguePayloadFields,
+};
+use sha2::{Digest, Sha256};
 use snap::{read::FrameDecoder, write::FrameEncoder};
 use ssz::Decode;
 use std::io::{Read, Write};
@@ -81,6 +91,13 @@ impl CompressedSignedBeaconBlock {
     pub fn decode_to_ssz(&self) -> Result<Vec<u8>, E2sError> {
         self.decompress()
     }
+
+    /// Extract the execution payload carried by the signed beacon block.
+    ///
+    /// Returns `None` for blocks that precede the merge.
+    pub fn execution_payload(&self) -> Result<Option<BeaconExecutionPayload>, E2sError> {
+        BeaconExecutionPayload::from_signed_block_ssz(&self.decompress()?)
+    }
 }
 
 impl DecodeCompressedSsz for CompressedSignedBeaconBlock {
@@ -92,6 +109,221 @@ impl DecodeCompressedSsz for CompressedSignedBeaconBlock {
     }
 }
 
+/// Consensus fork of a `BeaconBlockBody`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BeaconBlockFork {
+    /// Initial fork, without sync aggregate and execution payload
+    Phase0,
+    /// Adds the sync aggregate
+    Altair,
+    /// Adds the execution payload
+    Bellatrix,
+    /// Adds withdrawals and BLS to execution changes
+    Capella,
+    /// Adds blob KZG commitments
+    Deneb,
+    /// Adds execution requests
+    Electra,
+}
+
+impl BeaconBlockFork {
+    /// Returns the fork of a `BeaconBlockBody` with a fixed part of `size` bytes.
+    ///
+    /// Every fork appends fields to the body, so the size of the fixed part, which is also the
+    /// offset of its first variable-size field, identifies the fork.
+    pub const fn from_body_fixed_size(size: usize) -> Option<Self> {
+        Some(match size {
+            220 => Self::Phase0,
+            380 => Self::Altair,
+            384 => Self::Bellatrix,
+            388 => Self::Capella,
+            392 => Self::Deneb,
+            396 => Self::Electra,
+            _ => return None,
+        })
+    }
+
+    /// Size of the fixed part of a `BeaconBlockBody` in this fork
+    pub const fn body_fixed_size(&self) -> usize {
+        match self {
+            Self::Phase0 => 220,
+            Self::Altair => 380,
+            Self::Bellatrix => 384,
+            Self::Capella => 388,
+            Self::Deneb => 392,
+            Self::Electra => 396,
+        }
+    }
+}
+
+/// Position of the `message` offset in a `SignedBeaconBlock`
+const SIGNED_BLOCK_MESSAGE_OFFSET: usize = 0;
+
+/// Position of the `body` offset in a `BeaconBlock`
+const BLOCK_BODY_OFFSET: usize = 80;
+
+/// Position of the `proposer_slashings` offset, the first variable-size field of a
+/// `BeaconBlockBody`
+const BODY_FIRST_OFFSET: usize = 200;
+
+/// Position of the `execution_payload` offset in a `BeaconBlockBody`
+const BODY_EXECUTION_PAYLOAD_OFFSET: usize = 380;
+
+/// Size of a KZG commitment
+const KZG_COMMITMENT_SIZE: usize = 48;
+
+/// Execution payload of a signed beacon block together with the consensus fields that are needed
+/// to convert it into an execution block.
+#[derive(Debug, Clone)]
+pub struct BeaconExecutionPayload {
+    /// Slot of the beacon block
+    pub slot: u64,
+
+    /// Fork of the beacon block body
+    pub fork: BeaconBlockFork,
+
+    /// Execution payload
+    pub payload: ExecutionPayload,
+
+    /// Parent beacon block root, blob versioned hashes and execution requests of the block
+    pub sidecar: ExecutionPayloadSidecar,
+}
+
+impl BeaconExecutionPayload {
+    /// Extract the execution payload from a ssz-encoded `SignedBeaconBlock`.
+    ///
+    /// Only the fields required to build an execution block are read, the rest of the beacon
+    /// block is skipped over using the ssz offsets.
+    ///
+    /// Returns `None` for blocks that precede the merge.
+    pub fn from_signed_block_ssz(bytes: &[u8]) -> Result<Option<Self>, E2sError> {
+        let message = ssz_tail(bytes, ssz_offset(bytes, SIGNED_BLOCK_MESSAGE_OFFSET)?)?;
+        let slot = u64::from_le_bytes(
+            ssz_slice(message, 0, 8)?.try_into().expect("slice should be 8 bytes long"),
+        );
+        let parent_root = B256::from_slice(ssz_slice(message, 16, 48)?);
+        let body = ssz_tail(message, ssz_offset(message, BLOCK_BODY_OFFSET)?)?;
+
+        let fixed_size = ssz_offset(body, BODY_FIRST_OFFSET)?;
+        let fork = BeaconBlockFork::from_body_fixed_size(fixed_size).ok_or_else(|| {
+            E2sError::Ssz(format!("Unknown beacon block body with fixed size {fixed_size}"))
+        })?;
+
+        if fork < BeaconBlockFork::Bellatrix {
+            return Ok(None);
+        }
+
+        // Offsets of `execution_payload` and all variable-size fields that follow it
+        let offsets = (BODY_EXECUTION_PAYLOAD_OFFSET..fork.body_fixed_size())
+            .step_by(4)
+            .map(|position| ssz_offset(body, position))
+            .collect::<Result<Vec<_>, _>>()?;
+        let field = |index: usize| {
+            ssz_slice(body, offsets[index], offsets.get(index + 1).copied().unwrap_or(body.len()))
+        };
+
+        let payload = field(0)?;
+        let payload = match fork {
+            BeaconBlockFork::Bellatrix => ExecutionPayload::V1(
+                ExecutionPayloadV1::from_ssz_bytes(payload).map_err(ssz_error)?,
+            ),
+            BeaconBlockFork::Capella => ExecutionPayload::V2(
+                ExecutionPayloadV2::from_ssz_bytes(payload).map_err(ssz_error)?,
+            ),
+            _ => ExecutionPayload::V3(
+                ExecutionPayloadV3::from_ssz_bytes(payload).map_err(ssz_error)?,
+            ),
+        };
+
+        // Bellatrix blocks before the terminal block carry an empty payload
+        if payload.block_hash().is_zero() {
+            return Ok(None);
+        }
+
+        let sidecar = match fork {
+            BeaconBlockFork::Deneb | BeaconBlockFork::Electra => {
+                let commitments = field(2)?;
+                if commitments.len() % KZG_COMMITMENT_SIZE != 0 {
+                    return Err(E2sError::Ssz(format!(
+                        "Invalid blob KZG commitments length {}",
+                        commitments.len()
+                    )));
+                }
+                let versioned_hashes = commitments
+                    .chunks_exact(KZG_COMMITMENT_SIZE)
+                    .map(kzg_to_versioned_hash)
+                    .collect();
+                let cancun = CancunPayloadFields::new(parent_root, versioned_hashes);
+
+                if fork == BeaconBlockFork::Electra {
+                    let requests = decode_execution_requests(field(3)?)?;
+                    ExecutionPayloadSidecar::v4(cancun, PraguePayloadFields::new(requests))
+                } else {
+                    ExecutionPayloadSidecar::v3(cancun)
+                }
+            }
+            _ => ExecutionPayloadSidecar::none(),
+        };
+
+        Ok(Some(Self { slot, fork, payload, sidecar }))
+    }
+}
+
+/// Decode ssz-encoded `ExecutionRequests` into EIP-7685 [`Requests`].
+///
+/// Every list contains fixed-size items, so its ssz encoding is the concatenation of the request
+/// data, which is prefixed with the request type. Empty lists are omitted.
+fn decode_execution_requests(bytes: &[u8]) -> Result<Requests, E2sError> {
+    let types = [DEPOSIT_REQUEST_TYPE, WITHDRAWAL_REQUEST_TYPE, CONSOLIDATION_REQUEST_TYPE];
+    let offsets = (0..types.len())
+        .map(|index| ssz_offset(bytes, index * 4))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut requests = Vec::new();
+    for (index, request_type) in types.into_iter().enumerate() {
+        let data = ssz_slice(
+            bytes,
+            offsets[index],
+            offsets.get(index + 1).copied().unwrap_or(bytes.len()),
+        )?;
+
+        if !data.is_empty() {
+            requests.push(Bytes::from([&[request_type][..], data].concat()));
+        }
+    }
+
+    Ok(Requests::new(requests))
+}
+
+/// Compute the versioned hash of a KZG commitment
+fn kzg_to_versioned_hash(commitment: &[u8]) -> B256 {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    B256::from(hash)
+}
+
+/// Read the ssz offset stored at `position`
+fn ssz_offset(bytes: &[u8], position: usize) -> Result<usize, E2sError> {
+    let offset = ssz_slice(bytes, position, position + 4)?;
+    Ok(u32::from_le_bytes(offset.try_into().expect("slice should be 4 bytes long")) as usize)
+}
+
+/// Get `bytes[start..end]` or an error if out of bounds
+fn ssz_slice(bytes: &[u8], start: usize, end: usize) -> Result<&[u8], E2sError> {
+    bytes.get(start..end).ok_or_else(|| {
+        E2sError::Ssz(format!("Range {start}..{end} out of bounds of {} bytes", bytes.len()))
+    })
+}
+
+/// Get `bytes[start..]` or an error if out of bounds
+fn ssz_tail(bytes: &[u8], start: usize) -> Result<&[u8], E2sError> {
+    ssz_slice(bytes, start, bytes.len())
+}
+
+fn ssz_error(error: ssz::DecodeError) -> E2sError {
+    E2sError::Ssz(format!("Failed to decode execution payload: {error:?}"))
+}
+
 /// Compressed beacon state
 ///
 /// See also <https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md#compressedbeaconstate>.
@@ -173,6 +405,150 @@ impl DecodeCompressedSsz for CompressedBeaconState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::{Address, Bloom, U256};
+    use ssz::Encode;
+
+    /// Builds a ssz-encoded `SignedBeaconBlock` with a body of `fork` where all fields are empty
+    /// except for the variable-size fields starting at `execution_payload`.
+    fn signed_beacon_block(
+        fork: BeaconBlockFork,
+        slot: u64,
+        parent_root: B256,
+        execution_fields: &[&[u8]],
+    ) -> Vec<u8> {
+        let fixed_size = fork.body_fixed_size();
+        let mut body = vec![0; fixed_size];
+
+        // Empty operation lists
+        for position in (BODY_FIRST_OFFSET..BODY_FIRST_OFFSET + 20).step_by(4) {
+            body[position..position + 4].copy_from_slice(&(fixed_size as u32).to_le_bytes());
+        }
+
+        for (index, field) in execution_fields.iter().enumerate() {
+            let position = BODY_EXECUTION_PAYLOAD_OFFSET + index * 4;
+            let offset = body.len() as u32;
+            body[position..position + 4].copy_from_slice(&offset.to_le_bytes());
+            body.extend_from_slice(field);
+        }
+
+        let mut message = vec![0; 84];
+        message[0..8].copy_from_slice(&slot.to_le_bytes());
+        message[16..48].copy_from_slice(parent_root.as_slice());
+        message[80..84].copy_from_slice(&84u32.to_le_bytes());
+        message.extend(body);
+
+        let mut signed = vec![0; 100];
+        signed[0..4].copy_from_slice(&100u32.to_le_bytes());
+        signed.extend(message);
+        signed
+    }
+
+    fn payload_v1(block_hash: B256) -> ExecutionPayloadV1 {
+        ExecutionPayloadV1 {
+            parent_hash: B256::repeat_byte(1),
+            fee_recipient: Address::repeat_byte(2),
+            state_root: B256::repeat_byte(3),
+            receipts_root: B256::repeat_byte(4),
+            logs_bloom: Bloom::default(),
+            prev_randao: B256::repeat_byte(5),
+            block_number: 17_034_870,
+            gas_limit: 30_000_000,
+            gas_used: 21_000,
+            timestamp: 1_681_338_455,
+            extra_data: Bytes::from_static(b"reth"),
+            base_fee_per_gas: U256::from(7),
+            block_hash,
+            transactions: vec![Bytes::from_static(&[0xAA; 10])],
+        }
+    }
+
+    #[test]
+    fn test_execution_payload_capella() {
+        let payload = ExecutionPayloadV2 {
+            payload_inner: payload_v1(B256::repeat_byte(6)),
+            withdrawals: vec![],
+        };
+        let ssz = signed_beacon_block(
+            BeaconBlockFork::Capella,
+            6_209_536,
+            B256::repeat_byte(7),
+            &[&payload.as_ssz_bytes(), &[]],
+        );
+
+        let extracted = BeaconExecutionPayload::from_signed_block_ssz(&ssz).unwrap().unwrap();
+
+        assert_eq!(extracted.slot, 6_209_536);
+        assert_eq!(extracted.fork, BeaconBlockFork::Capella);
+        assert_eq!(extracted.payload, ExecutionPayload::V2(payload));
+        assert_eq!(extracted.sidecar.parent_beacon_block_root(), None);
+    }
+
+    #[test]
+    fn test_execution_payload_deneb() {
+        let payload = ExecutionPayloadV3 {
+            payload_inner: ExecutionPayloadV2 {
+                payload_inner: payload_v1(B256::repeat_byte(6)),
+                withdrawals: vec![],
+            },
+            blob_gas_used: 131_072,
+            excess_blob_gas: 0,
+        };
+        let commitment = [0x11; KZG_COMMITMENT_SIZE];
+        let ssz = signed_beacon_block(
+            BeaconBlockFork::Deneb,
+            8_626_176,
+            B256::repeat_byte(7),
+            &[&payload.as_ssz_bytes(), &[], &commitment],
+        );
+
+        let extracted = BeaconExecutionPayload::from_signed_block_ssz(&ssz).unwrap().unwrap();
+
+        assert_eq!(extracted.fork, BeaconBlockFork::Deneb);
+        assert_eq!(extracted.payload, ExecutionPayload::V3(payload));
+        assert_eq!(extracted.sidecar.parent_beacon_block_root(), Some(B256::repeat_byte(7)));
+        assert_eq!(
+            extracted.sidecar.versioned_hashes(),
+            Some(&vec![kzg_to_versioned_hash(&commitment)])
+        );
+    }
+
+    #[test]
+    fn test_execution_payload_before_merge() {
+        let ssz = signed_beacon_block(BeaconBlockFork::Altair, 100, B256::ZERO, &[]);
+        assert!(BeaconExecutionPayload::from_signed_block_ssz(&ssz).unwrap().is_none());
+
+        let payload = payload_v1(B256::ZERO);
+        let ssz = signed_beacon_block(
+            BeaconBlockFork::Bellatrix,
+            4_700_013,
+            B256::ZERO,
+            &[&payload.as_ssz_bytes()],
+        );
+        assert!(BeaconExecutionPayload::from_signed_block_ssz(&ssz).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_execution_requests() {
+        let deposit = [0x22; 192];
+        let consolidation = [0x33; 116];
+
+        let mut ssz = Vec::new();
+        ssz.extend_from_slice(&12u32.to_le_bytes());
+        ssz.extend_from_slice(&(12 + deposit.len() as u32).to_le_bytes());
+        ssz.extend_from_slice(&(12 + deposit.len() as u32).to_le_bytes());
+        ssz.extend_from_slice(&deposit);
+        ssz.extend_from_slice(&consolidation);
+
+        let requests = decode_execution_requests(&ssz).unwrap();
+
+        assert_eq!(
+            requests.take(),
+            vec![
+                Bytes::from([&[DEPOSIT_REQUEST_TYPE][..], &deposit].concat()),
+                Bytes::from([&[CONSOLIDATION_REQUEST_TYPE][..], &consolidation].concat()),
+            ]
+        );
+    }
 
     #[test]
     fn test_signed_beacon_block_compression_roundtrip() {
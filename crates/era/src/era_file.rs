@@ -0,0 +1,94 @@
+//! Represents a complete Era file
+//!
+//! The structure of an Era file follows the specification:
+//! `Version | block* | era-state | other-entries* | slot-index(block)? | slot-index(state)`
+//!
+//! See also <https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era.md>.
+
+use crate::{
+    consensus_types::{self, CompressedSignedBeaconBlock},
+    e2s_file::E2StoreReader,
+    e2s_types::E2sError,
+};
+use std::io::{Read, Seek};
+
+/// Reader for Era files that builds on top of [`E2StoreReader`]
+#[derive(Debug)]
+pub struct EraReader<R: Read> {
+    reader: E2StoreReader<R>,
+}
+
+impl<R: Read + Seek> EraReader<R> {
+    /// Create a new [`EraReader`]
+    pub fn new(reader: R) -> Self {
+        Self { reader: E2StoreReader::new(reader) }
+    }
+
+    /// Returns an iterator of [`CompressedSignedBeaconBlock`] streaming from `reader`.
+    pub fn iter(self) -> BeaconBlockIterator<R> {
+        BeaconBlockIterator { reader: self.reader }
+    }
+}
+
+/// An iterator of [`CompressedSignedBeaconBlock`] streaming from [`E2StoreReader`].
+///
+/// The era state, slot indices and any other entries are skipped.
+#[derive(Debug)]
+pub struct BeaconBlockIterator<R: Read> {
+    reader: E2StoreReader<R>,
+}
+
+impl<R: Read + Seek> Iterator for BeaconBlockIterator<R> {
+    type Item = Result<CompressedSignedBeaconBlock, E2sError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_result().transpose()
+    }
+}
+
+impl<R: Read + Seek> BeaconBlockIterator<R> {
+    fn next_result(&mut self) -> Result<Option<CompressedSignedBeaconBlock>, E2sError> {
+        loop {
+            let Some(entry) = self.reader.read_next_entry()? else {
+                return Ok(None);
+            };
+
+            if entry.entry_type == consensus_types::COMPRESSED_SIGNED_BEACON_BLOCK {
+                return CompressedSignedBeaconBlock::from_entry(&entry).map(Some);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        e2s_file::E2StoreWriter,
+        test_utils::{create_beacon_block, create_beacon_state},
+    };
+    use std::io::Cursor;
+
+    #[test]
+    fn test_iter_yields_only_blocks() {
+        let blocks = vec![create_beacon_block(10), create_beacon_block(15)];
+
+        let mut buffer = Vec::new();
+        {
+            let mut writer = E2StoreWriter::with_version(&mut buffer).unwrap();
+            for block in &blocks {
+                writer.write_entry(&block.to_entry()).unwrap();
+            }
+            writer.write_entry(&create_beacon_state(50).to_entry()).unwrap();
+            writer.flush().unwrap();
+        }
+
+        let read =
+            EraReader::new(Cursor::new(buffer)).iter().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(read.len(), blocks.len());
+        for (read, expected) in read.iter().zip(&blocks) {
+            assert_eq!(read.data, expected.data);
+        }
+    }
+}
@@ -5,6 +5,7 @@
 //! - [`CompressedBody`] - Block body
 //! - [`CompressedReceipts`] - Block receipts
 //! - [`TotalDifficulty`] - Block total difficulty
+//! - [`Accumulator`] - Hash tree root of the [`HeaderRecord`]s of all blocks
 //!
 //! These types use Snappy compression to match the specification.
 //!
@@ -78,6 +79,7 @@ use crate::{
 use alloy_consensus::{Block, BlockBody, Header};
 use alloy_primitives::{B256, U256};
 use alloy_rlp::{Decodable, Encodable};
+use sha2::{Digest, Sha256};
 use snap::{read::FrameDecoder, write::FrameEncoder};
 use std::{
     io::{Read, Write},
@@ -463,9 +465,33 @@ impl TotalDifficulty {
     }
 }
 
+/// A header-record of the [`Accumulator`]
+///
+/// SSZ container: `block-hash | total-difficulty`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// Hash of the block header
+    pub block_hash: B256,
+
+    /// Total difficulty of the chain up to and including the block
+    pub total_difficulty: U256,
+}
+
+impl HeaderRecord {
+    /// Create a new [`HeaderRecord`]
+    pub const fn new(block_hash: B256, total_difficulty: U256) -> Self {
+        Self { block_hash, total_difficulty }
+    }
+
+    /// Calculate the SSZ `hash_tree_root` of this record
+    pub fn tree_hash_root(&self) -> B256 {
+        hash_pair(self.block_hash, B256::from(self.total_difficulty.to_le_bytes::<32>()))
+    }
+}
+
 /// Accumulator is computed by constructing an SSZ list of header-records
 /// and calculating the `hash_tree_root`
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Accumulator {
     /// The accumulator root hash
     pub root: B256,
@@ -477,6 +503,36 @@ impl Accumulator {
         Self { root }
     }
 
+    /// Compute the [`Accumulator`] of the given header-records
+    ///
+    /// This is the `hash_tree_root` of `List[HeaderRecord, MAX_BLOCKS_PER_ERA1]`.
+    pub fn from_header_records(records: &[HeaderRecord]) -> Result<Self, E2sError> {
+        if records.len() > MAX_BLOCKS_PER_ERA1 {
+            return Err(E2sError::Ssz(format!(
+                "Too many header records for Accumulator: expected at most {MAX_BLOCKS_PER_ERA1}, got {}",
+                records.len()
+            )));
+        }
+
+        // Merkleize the records, padding the list to its limit with zero subtrees
+        let mut layer = records.iter().map(HeaderRecord::tree_hash_root).collect::<Vec<_>>();
+        let mut zero_hash = B256::ZERO;
+        for _ in 0..MAX_BLOCKS_PER_ERA1.trailing_zeros() {
+            if layer.len() % 2 == 1 {
+                layer.push(zero_hash);
+            }
+            layer = layer.chunks_exact(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+            zero_hash = hash_pair(zero_hash, zero_hash);
+        }
+        let root = layer.first().copied().unwrap_or(zero_hash);
+
+        // Mix in the length of the list
+        let mut length = B256::ZERO;
+        length[..8].copy_from_slice(&(records.len() as u64).to_le_bytes());
+
+        Ok(Self::new(hash_pair(root, length)))
+    }
+
     /// Convert to an [`Entry`]
     pub fn to_entry(&self) -> Entry {
         Entry::new(ACCUMULATOR, self.root.to_vec())
@@ -505,6 +561,14 @@ impl Accumulator {
     }
 }
 
+/// Hashes the concatenation of two SSZ chunks
+fn hash_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
 /// A block tuple in an Era1 file, containing all components for a single block
 #[derive(Debug, Clone)]
 pub struct BlockTuple {
@@ -717,4 +781,42 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_accumulator_from_header_records() {
+        // Merkleize the full padded tree as the reference
+        fn full_tree_root(records: &[HeaderRecord]) -> B256 {
+            let mut layer = vec![B256::ZERO; MAX_BLOCKS_PER_ERA1];
+            for (leaf, record) in layer.iter_mut().zip(records) {
+                *leaf = record.tree_hash_root();
+            }
+            while layer.len() > 1 {
+                layer = layer.chunks_exact(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+            }
+            let mut length = B256::ZERO;
+            length[..8].copy_from_slice(&(records.len() as u64).to_le_bytes());
+            hash_pair(layer[0], length)
+        }
+
+        let records = (0..5u64)
+            .map(|i| HeaderRecord::new(B256::with_last_byte(i as u8 + 1), U256::from(i * 1000)))
+            .collect::<Vec<_>>();
+
+        for len in 0..=records.len() {
+            let accumulator = Accumulator::from_header_records(&records[..len]).unwrap();
+            assert_eq!(accumulator.root, full_tree_root(&records[..len]), "length {len}");
+        }
+
+        // Different records produce different roots
+        let mut tampered = records.clone();
+        tampered[2].total_difficulty += U256::from(1);
+        assert_ne!(
+            Accumulator::from_header_records(&tampered).unwrap(),
+            Accumulator::from_header_records(&records).unwrap()
+        );
+
+        // The list is limited to the maximum number of blocks
+        let too_many = vec![records[0]; MAX_BLOCKS_PER_ERA1 + 1];
+        assert!(Accumulator::from_header_records(&too_many).is_err());
+    }
 }
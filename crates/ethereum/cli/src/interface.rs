@@ -6,13 +6,14 @@ use reth_chainspec::{ChainSpec, EthChainSpec, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     common::{CliComponentsBuilder, CliHeader, CliNodeTypes},
-    config_cmd, db, download, dump_genesis, export_era, import, import_era, init_cmd, init_state,
-    launcher::FnLauncher,
+    config_cmd, db, download, dump_genesis, engine, exex, export_era, import, import_era, init_cmd,
+    init_state,
+    launcher::{FnLauncher, Launcher, WithMemoryLauncher},
     node::{self, NoArgs},
     p2p, prune, re_execute, recover, stage,
 };
 use reth_cli_runner::CliRunner;
-use reth_db::DatabaseEnv;
+use reth_db::{DatabaseEnv, MemoryDatabase};
 use reth_node_api::NodePrimitives;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_core::{
@@ -109,6 +110,31 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
         self.with_runner(CliRunner::try_default_runtime()?, launcher)
     }
 
+    /// Execute the configured cli command like [`Cli::run`], but launch nodes with an in-memory
+    /// database, see `--datadir.memory`, with `memory_launcher`.
+    pub fn run_with_memory<L, Fut, M, MFut>(
+        self,
+        launcher: L,
+        memory_launcher: M,
+    ) -> eyre::Result<()>
+    where
+        L: FnOnce(WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, C::ChainSpec>>, Ext) -> Fut,
+        Fut: Future<Output = eyre::Result<()>>,
+        M: FnOnce(WithLaunchContext<NodeBuilder<Arc<MemoryDatabase>, C::ChainSpec>>, Ext) -> MFut,
+        MFut: Future<Output = eyre::Result<()>>,
+        C: ChainSpecParser<ChainSpec = ChainSpec>,
+    {
+        let launcher = WithMemoryLauncher::new::<C, Ext>(
+            FnLauncher::new::<C, Ext>(async move |builder, ext| launcher(builder, ext).await),
+            async move |builder, ext| memory_launcher(builder, ext).await,
+        );
+        self.with_runner_and_launcher::<EthereumNode>(
+            CliRunner::try_default_runtime()?,
+            ethereum_components,
+            launcher,
+        )
+    }
+
     /// Execute the configured cli command with the provided [`CliComponentsBuilder`].
     ///
     /// This accepts a closure that is used to launch the node via the
@@ -155,13 +181,9 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
         Fut: Future<Output = eyre::Result<()>>,
         C: ChainSpecParser<ChainSpec = ChainSpec>,
     {
-        let components = |spec: Arc<C::ChainSpec>| {
-            (EthEvmConfig::ethereum(spec.clone()), EthBeaconConsensus::new(spec))
-        };
-
         self.with_runner_and_components::<EthereumNode>(
             runner,
-            components,
+            ethereum_components,
             async move |builder, ext| launcher(builder, ext).await,
         )
     }
@@ -169,7 +191,7 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
     /// Execute the configured cli command with the provided [`CliRunner`] and
     /// [`CliComponentsBuilder`].
     pub fn with_runner_and_components<N>(
-        mut self,
+        self,
         runner: CliRunner,
         components: impl CliComponentsBuilder<N>,
         launcher: impl AsyncFnOnce(
@@ -177,6 +199,21 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
             Ext,
         ) -> eyre::Result<()>,
     ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<Primitives: NodePrimitives<BlockHeader: CliHeader>, ChainSpec: Hardforks>,
+        C: ChainSpecParser<ChainSpec = N::ChainSpec>,
+    {
+        self.with_runner_and_launcher(runner, components, FnLauncher::new::<C, Ext>(launcher))
+    }
+
+    /// Execute the configured cli command with the provided [`CliRunner`],
+    /// [`CliComponentsBuilder`] and [`Launcher`].
+    pub fn with_runner_and_launcher<N>(
+        mut self,
+        runner: CliRunner,
+        components: impl CliComponentsBuilder<N>,
+        launcher: impl Launcher<C, Ext>,
+    ) -> eyre::Result<()>
     where
         N: CliNodeTypes<Primitives: NodePrimitives<BlockHeader: CliHeader>, ChainSpec: Hardforks>,
         C: ChainSpecParser<ChainSpec = N::ChainSpec>,
@@ -193,9 +230,9 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
         let _ = install_prometheus_recorder();
 
         match self.command {
-            Commands::Node(command) => runner.run_command_until_exit(|ctx| {
-                command.execute(ctx, FnLauncher::new::<C, Ext>(launcher))
-            }),
+            Commands::Node(command) => {
+                runner.run_command_until_exit(|ctx| command.execute(ctx, launcher))
+            }
             Commands::Init(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
             Commands::InitState(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<N>())
@@ -223,6 +260,10 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
                 runner.run_command_until_exit(|ctx| command.execute::<N>(ctx))
             }
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<N>()),
+            Commands::Exex(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
+            Commands::Engine(command) => {
+                runner.run_command_until_exit(|ctx| command.execute(ctx, launcher))
+            }
             Commands::ReExecute(command) => {
                 runner.run_until_ctrl_c(command.execute::<N>(components))
             }
@@ -239,6 +280,11 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
     }
 }
 
+/// Returns the components of an Ethereum node.
+fn ethereum_components(spec: Arc<ChainSpec>) -> (EthEvmConfig, EthBeaconConsensus<ChainSpec>) {
+    (EthEvmConfig::ethereum(spec.clone()), EthBeaconConsensus::new(spec))
+}
+
 /// Commands to be executed
 #[derive(Debug, Subcommand)]
 pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
@@ -287,6 +333,12 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<C>),
+    /// `ExEx` management utilities
+    #[command(name = "exex")]
+    Exex(exex::Command<C>),
+    /// Engine API debugging utilities
+    #[command(name = "engine")]
+    Engine(engine::Command<C, Ext>),
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
@@ -312,6 +364,8 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
             Self::Config(_) => None,
             Self::Recover(cmd) => cmd.chain_spec(),
             Self::Prune(cmd) => cmd.chain_spec(),
+            Self::Exex(cmd) => cmd.chain_spec(),
+            Self::Engine(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
         }
     }
@@ -16,7 +16,7 @@ use alloy_rpc_types_engine::{
 };
 use core::convert::Infallible;
 use reth_ethereum_primitives::{Block, EthPrimitives};
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
+use reth_payload_primitives::{BuiltPayload, PayloadBuildReport, PayloadBuilderAttributes};
 use reth_primitives_traits::SealedBlock;
 
 use crate::BuiltPayloadConversionError;
@@ -39,6 +39,8 @@ pub struct EthBuiltPayload {
     pub(crate) sidecars: BlobSidecars,
     /// The requests of the payload
     pub(crate) requests: Option<Requests>,
+    /// The report of how the payload was built, if recorded by the builder.
+    pub(crate) build_report: Option<Arc<PayloadBuildReport>>,
 }
 
 // === impl BuiltPayload ===
@@ -53,7 +55,7 @@ impl EthBuiltPayload {
         fees: U256,
         requests: Option<Requests>,
     ) -> Self {
-        Self { id, block, fees, requests, sidecars: BlobSidecars::Empty }
+        Self { id, block, fees, requests, sidecars: BlobSidecars::Empty, build_report: None }
     }
 
     /// Returns the identifier of the payload.
@@ -82,6 +84,12 @@ impl EthBuiltPayload {
         self
     }
 
+    /// Sets the report of how the payload was built.
+    pub fn with_build_report(mut self, report: PayloadBuildReport) -> Self {
+        self.build_report = Some(Arc::new(report));
+        self
+    }
+
     /// Try converting built payload into [`ExecutionPayloadEnvelopeV3`].
     ///
     /// Returns an error if the payload contains non EIP-4844 sidecar.
@@ -172,6 +180,10 @@ impl BuiltPayload for EthBuiltPayload {
     fn requests(&self) -> Option<Requests> {
         self.requests.clone()
     }
+
+    fn build_report(&self) -> Option<Arc<PayloadBuildReport>> {
+        self.build_report.clone()
+    }
 }
 
 // V1 engine_getPayloadV1 response
@@ -34,12 +34,16 @@ use reth_node_builder::{
     },
     BuilderContext, DebugNode, Node, NodeAdapter, PayloadBuilderConfig, PayloadTypes,
 };
+use reth_payload_util::BundlePool;
 use reth_provider::{providers::ProviderFactoryBuilder, EthStorage};
 use reth_rpc::{
-    eth::core::{EthApiFor, EthRpcConverterFor},
+    eth::{
+        core::{EthApiFor, EthRpcConverterFor},
+        sim_bundle::EthSendBundle,
+    },
     ValidationApi,
 };
-use reth_rpc_api::servers::BlockSubmissionValidationApiServer;
+use reth_rpc_api::servers::{BlockSubmissionValidationApiServer, MevFullApiServer};
 use reth_rpc_builder::{config::RethRpcServerConfig, middleware::RethRpcMiddleware};
 use reth_rpc_eth_api::{
     helpers::pending_block::BuildPendingEnv, RpcConvert, RpcTypes, SignableTxRequest,
@@ -178,6 +182,8 @@ pub struct EthereumAddOns<
     RpcMiddleware = Identity,
 > {
     inner: RpcAddOns<N, EthB, EV, EB, RpcMiddleware>,
+    /// Bundles submitted via `mev_sendBundle`, if enabled.
+    bundle_pool: Option<BundlePool<TransactionSigned>>,
 }
 
 impl<N> Default for EthereumAddOns<N, EthereumEthApiBuilder, EthereumEngineValidatorBuilder>
@@ -193,6 +199,7 @@ where
                 BasicEngineApiBuilder::default(),
                 Default::default(),
             ),
+            bundle_pool: None,
         }
     }
 }
@@ -210,8 +217,8 @@ where
     where
         T: Send,
     {
-        let Self { inner } = self;
-        EthereumAddOns { inner: inner.with_engine_api(engine_api_builder) }
+        let Self { inner, bundle_pool } = self;
+        EthereumAddOns { inner: inner.with_engine_api(engine_api_builder), bundle_pool }
     }
 
     /// Replace the engine validator builder.
@@ -222,8 +229,8 @@ where
     where
         T: Send,
     {
-        let Self { inner } = self;
-        EthereumAddOns { inner: inner.with_engine_validator(engine_validator_builder) }
+        let Self { inner, bundle_pool } = self;
+        EthereumAddOns { inner: inner.with_engine_validator(engine_validator_builder), bundle_pool }
     }
 
     /// Sets rpc middleware
@@ -231,8 +238,14 @@ where
     where
         T: Send,
     {
-        let Self { inner } = self;
-        EthereumAddOns { inner: inner.with_rpc_middleware(rpc_middleware) }
+        let Self { inner, bundle_pool } = self;
+        EthereumAddOns { inner: inner.with_rpc_middleware(rpc_middleware), bundle_pool }
+    }
+
+    /// Serves `mev_sendBundle` and adds the submitted bundles to the given pool.
+    pub fn with_bundle_pool(mut self, bundle_pool: BundlePool<TransactionSigned>) -> Self {
+        self.bundle_pool = Some(bundle_pool);
+        self
     }
 }
 
@@ -269,13 +282,23 @@ where
             Arc::new(EthereumEngineValidator::new(ctx.config.chain.clone())),
         );
 
-        self.inner
+        let Self { inner, bundle_pool } = self;
+        inner
             .launch_add_ons_with(ctx, move |container| {
                 container.modules.merge_if_module_configured(
                     RethRpcModule::Flashbots,
                     validation_api.into_rpc(),
                 )?;
 
+                if let Some(bundle_pool) = bundle_pool {
+                    let send_bundle =
+                        EthSendBundle::new(container.registry.sim_bundle_api(), bundle_pool);
+                    container.modules.add_or_replace_if_module_configured(
+                        RethRpcModule::Mev,
+                        send_bundle.into_rpc(),
+                    )?;
+                }
+
                 Ok(())
             })
             .await
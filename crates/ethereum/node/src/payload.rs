@@ -1,22 +1,35 @@
 //! Payload component configuration for the Ethereum node.
 
+use eyre::WrapErr;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_ethereum_payload_builder::EthereumBuilderConfig;
-use reth_ethereum_primitives::EthPrimitives;
+use reth_ethereum_payload_builder::{EthereumBuilderConfig, PayloadBundles};
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::ConfigureEvm;
 use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
 use reth_node_builder::{
     components::PayloadBuilderBuilder, BuilderContext, PayloadBuilderConfig, PayloadTypes,
 };
+use reth_payload_util::BundlePool;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder {
+    /// Bundles that are included ahead of pool transactions.
+    pub bundle_pool: Option<BundlePool<TransactionSigned>>,
+}
+
+impl EthereumPayloadBuilder {
+    /// Includes the bundles of the given pool in built payloads.
+    pub fn with_bundle_pool(mut self, bundle_pool: BundlePool<TransactionSigned>) -> Self {
+        self.bundle_pool = Some(bundle_pool);
+        self
+    }
+}
 
 impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for EthereumPayloadBuilder
 where
@@ -48,11 +61,26 @@ where
         let chain = ctx.chain_spec().chain();
         let gas_limit = conf.gas_limit_for(chain);
 
-        Ok(reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
+        let mut payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new().with_gas_limit(gas_limit),
-        ))
+            EthereumBuilderConfig::new()
+                .with_gas_limit(gas_limit)
+                .with_disallow(ctx.config().rpc.builder_disallow.iter().flatten().copied()),
+        );
+        if let Some(bundle_pool) = self.bundle_pool {
+            let mut bundles = PayloadBundles::new(bundle_pool);
+            if let Some(path) = &ctx.config().builder.bundle_refund_key {
+                let key = std::fs::read_to_string(path).wrap_err_with(|| {
+                    format!("failed to read bundle refund key {}", path.display())
+                })?;
+                bundles = bundles
+                    .with_refund_signer(key.trim().parse().wrap_err("invalid bundle refund key")?);
+            }
+            payload_builder = payload_builder.with_bundles(bundles);
+        }
+
+        Ok(payload_builder)
     }
 }
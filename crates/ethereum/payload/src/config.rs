@@ -1,4 +1,5 @@
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
+use alloy_primitives::{map::HashSet, Address};
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
 
 /// Settings for the Ethereum builder.
@@ -9,6 +10,10 @@ pub struct EthereumBuilderConfig {
     /// Waits for the first payload to be built if there is no payload built when the payload is
     /// being resolved.
     pub await_payload_on_missing: bool,
+    /// Senders and recipients whose transactions are never included in built blocks.
+    ///
+    /// `None` if no address is disallowed.
+    pub disallow: Option<HashSet<Address>>,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -20,7 +25,11 @@ impl Default for EthereumBuilderConfig {
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new() -> Self {
-        Self { desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, await_payload_on_missing: true }
+        Self {
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            await_payload_on_missing: true,
+            disallow: None,
+        }
     }
 
     /// Set desired gas limit.
@@ -35,6 +44,19 @@ impl EthereumBuilderConfig {
         self.await_payload_on_missing = await_payload_on_missing;
         self
     }
+
+    /// Sets the addresses whose transactions, as sender or recipient, are never included in
+    /// built blocks.
+    pub fn with_disallow(mut self, disallow: impl IntoIterator<Item = Address>) -> Self {
+        let disallow: HashSet<_> = disallow.into_iter().collect();
+        self.disallow = (!disallow.is_empty()).then_some(disallow);
+        self
+    }
+
+    /// Returns `true` if transactions from or to the given address must not be included.
+    pub fn is_disallowed(&self, address: &Address) -> bool {
+        self.disallow.as_ref().is_some_and(|disallow| disallow.contains(address))
+    }
 }
 
 impl EthereumBuilderConfig {
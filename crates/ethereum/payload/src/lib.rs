@@ -9,8 +9,10 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![allow(clippy::useless_let_if_seq)]
 
-use alloy_consensus::Transaction;
-use alloy_primitives::U256;
+use alloy_consensus::{SignableTransaction, Transaction, TxEip1559};
+use alloy_primitives::{Address, TxKind, U256};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
 use reth_basic_payload_builder::{
     is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
@@ -19,23 +21,37 @@ use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_errors::{BlockExecutionError, BlockValidationError};
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::{
+    block::CommitChanges,
     execute::{BlockBuilder, BlockBuilderOutcome},
-    ConfigureEvm, Evm, NextBlockEnvAttributes,
+    system_calls::SystemCaller,
+    ConfigureEvm, Evm, EvmEnvFor, NextBlockEnvAttributes,
 };
 use reth_evm_ethereum::EthEvmConfig;
 use reth_payload_builder::{BlobSidecars, EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
-use reth_payload_primitives::PayloadBuilderAttributes;
-use reth_primitives_traits::transaction::error::InvalidTransactionError;
-use reth_revm::{database::StateProviderDatabase, db::State};
+use reth_payload_primitives::{
+    PayloadBuildReport, PayloadBuilderAttributes, TransactionBuildOutcome,
+};
+use reth_payload_util::{Bundle, BundlePool, BUNDLE_PAYOUT_MAX_COST};
+use reth_primitives_traits::{transaction::error::InvalidTransactionError, Recovered};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{CacheDB, State},
+};
 use reth_storage_api::StateProviderFactory;
 use reth_transaction_pool::{
-    error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
+    error::{Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolTransactionError},
     BestTransactions, BestTransactionsAttributes, PoolTransaction, TransactionPool,
     ValidPoolTransaction,
 };
-use revm::context_interface::Block as _;
-use std::sync::Arc;
+use revm::{
+    context_interface::{
+        result::{InvalidTransaction, ResultAndState},
+        Block as _,
+    },
+    Database, DatabaseCommit, DatabaseRef,
+};
+use std::{any::Any, sync::Arc, time::Instant};
 use tracing::{debug, trace, warn};
 
 mod config;
@@ -48,8 +64,49 @@ type BestTransactionsIter<Pool> = Box<
     dyn BestTransactions<Item = Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>,
 >;
 
+/// A transaction was skipped because its sender or recipient is disallowed by the
+/// [`EthereumBuilderConfig`].
+#[derive(Debug, thiserror::Error)]
+#[error("address {0} is disallowed by the payload builder")]
+pub struct DisallowedTransactionError(pub Address);
+
+impl PoolTransactionError for DisallowedTransactionError {
+    fn is_bad_transaction(&self) -> bool {
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Bundles that the payload builder includes ahead of pool transactions.
+#[derive(Debug, Clone)]
+pub struct PayloadBundles {
+    /// Pool of the submitted bundles.
+    pub pool: BundlePool<TransactionSigned>,
+    /// Key of the fee recipient that signs the transactions paying out the refunds of bundles.
+    ///
+    /// Bundles that pay refunds are only included if the fee recipient of the payload is the
+    /// address of this key.
+    pub refund_signer: Option<PrivateKeySigner>,
+}
+
+impl PayloadBundles {
+    /// Creates a new instance that includes the bundles of the given pool without paying refunds.
+    pub const fn new(pool: BundlePool<TransactionSigned>) -> Self {
+        Self { pool, refund_signer: None }
+    }
+
+    /// Pays out the refunds of bundles with the given key of the fee recipient.
+    pub fn with_refund_signer(mut self, refund_signer: PrivateKeySigner) -> Self {
+        self.refund_signer = Some(refund_signer);
+        self
+    }
+}
+
 /// Ethereum payload builder
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig> {
     /// Client providing access to node state.
     client: Client,
@@ -59,6 +116,8 @@ pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig> {
     evm_config: EvmConfig,
     /// Payload builder configuration.
     builder_config: EthereumBuilderConfig,
+    /// Bundles that are included ahead of pool transactions.
+    bundles: Option<PayloadBundles>,
 }
 
 impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
@@ -69,7 +128,13 @@ impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
         evm_config: EvmConfig,
         builder_config: EthereumBuilderConfig,
     ) -> Self {
-        Self { client, pool, evm_config, builder_config }
+        Self { client, pool, evm_config, builder_config, bundles: None }
+    }
+
+    /// Includes the most valuable of the given bundles ahead of pool transactions.
+    pub fn with_bundles(mut self, bundles: PayloadBundles) -> Self {
+        self.bundles = Some(bundles);
+        self
     }
 }
 
@@ -92,6 +157,7 @@ where
             self.client.clone(),
             self.pool.clone(),
             self.builder_config.clone(),
+            self.bundles.as_ref(),
             args,
             |attributes| self.pool.best_transactions_with_attributes(attributes),
         )
@@ -119,6 +185,7 @@ where
             self.client.clone(),
             self.pool.clone(),
             self.builder_config.clone(),
+            self.bundles.as_ref(),
             args,
             |attributes| self.pool.best_transactions_with_attributes(attributes),
         )?
@@ -132,12 +199,16 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// If bundles are given, the most valuable bundles that don't conflict with each other are
+/// included ahead of the pool transactions.
 #[inline]
 pub fn default_ethereum_payload<EvmConfig, Client, Pool, F>(
     evm_config: EvmConfig,
     client: Client,
     pool: Pool,
     builder_config: EthereumBuilderConfig,
+    bundles: Option<&PayloadBundles>,
     args: BuildArguments<EthPayloadBuilderAttributes, EthBuiltPayload>,
     best_txs: F,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
@@ -155,19 +226,17 @@ where
     let mut db =
         State::builder().with_database(cached_reads.as_db_mut(state)).with_bundle_update().build();
 
+    let next_block_attributes = NextBlockEnvAttributes {
+        timestamp: attributes.timestamp(),
+        suggested_fee_recipient: attributes.suggested_fee_recipient(),
+        prev_randao: attributes.prev_randao(),
+        gas_limit: builder_config.gas_limit(parent_header.gas_limit),
+        parent_beacon_block_root: attributes.parent_beacon_block_root(),
+        withdrawals: Some(attributes.withdrawals().clone()),
+    };
+
     let mut builder = evm_config
-        .builder_for_next_block(
-            &mut db,
-            &parent_header,
-            NextBlockEnvAttributes {
-                timestamp: attributes.timestamp(),
-                suggested_fee_recipient: attributes.suggested_fee_recipient(),
-                prev_randao: attributes.prev_randao(),
-                gas_limit: builder_config.gas_limit(parent_header.gas_limit),
-                parent_beacon_block_root: attributes.parent_beacon_block_root(),
-                withdrawals: Some(attributes.withdrawals().clone()),
-            },
-        )
+        .builder_for_next_block(&mut db, &parent_header, next_block_attributes.clone())
         .map_err(PayloadBuilderError::other)?;
 
     let chain_spec = client.chain_spec();
@@ -198,9 +267,131 @@ where
     let max_blob_count =
         blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
+    let mut report = PayloadBuildReport::new(parent_header.number + 1, block_gas_limit);
+    let started_at = Instant::now();
+
+    if let Some(bundles) = bundles {
+        let evm_env = evm_config
+            .next_evm_env(&parent_header, &next_block_attributes)
+            .map_err(PayloadBuilderError::other)?;
+
+        // refunds are paid out from the fee recipient, so they require its key
+        let refund_payer = bundles
+            .refund_signer
+            .as_ref()
+            .filter(|signer| signer.address() == attributes.suggested_fee_recipient())
+            .map(|signer| RefundPayer { signer, chain_id: evm_env.cfg_env.chain_id, base_fee });
+
+        // simulate the bundles on top of the same pre-execution changes as the block, so that
+        // the EIP-4788 beacon root and EIP-2935 history storage contracts are up to date
+        let mut sim_db = CacheDB::new(StateProviderDatabase::new(&state_provider));
+        {
+            let mut evm = evm_config.evm_with_env(&mut sim_db, evm_env.clone());
+            let mut system_caller = SystemCaller::new(chain_spec.clone());
+            system_caller
+                .apply_blockhashes_contract_call(parent_header.hash(), &mut evm)
+                .and_then(|_| {
+                    system_caller.apply_beacon_root_contract_call(
+                        attributes.parent_beacon_block_root(),
+                        &mut evm,
+                    )
+                })
+                .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+        }
+
+        let selected_bundles = select_bundles(
+            &evm_config,
+            evm_env,
+            sim_db,
+            attributes.suggested_fee_recipient(),
+            block_gas_limit,
+            refund_payer.as_ref(),
+            bundles.pool.bundles_for_block(parent_header.number + 1),
+        );
+
+        let mut included = Vec::new();
+        for selected in selected_bundles {
+            let bundle = &selected.bundle;
+            let committed = match execute_bundle(&mut builder, &selected)? {
+                BundleOutcome::Included(transactions) => {
+                    for (tx, gas_used) in transactions {
+                        let miner_fee = tx
+                            .effective_tip_per_gas(base_fee)
+                            .expect("fee is always valid; execution succeeded");
+                        let fee = U256::from(miner_fee) * U256::from(gas_used);
+                        total_fees += fee;
+                        cumulative_gas_used += gas_used;
+                        report.record(
+                            *tx.tx_hash(),
+                            tx.signer(),
+                            tx.nonce(),
+                            TransactionBuildOutcome::Included { gas_used, fee },
+                        );
+                    }
+                    debug!(target: "payload_builder", id=%attributes.id, bundle=%bundle.hash, "included bundle");
+                    included.push(selected);
+                    continue
+                }
+                BundleOutcome::Failed { committed } => committed,
+            };
+
+            warn!(target: "payload_builder", bundle=%bundle.hash, "bundle failed after successful simulation");
+            bundles.pool.remove(&bundle.hash);
+            if committed == 0 {
+                continue
+            }
+
+            // Some transactions of the bundle are already part of the block. Roll them back by
+            // starting over with only the bundles that were included so far.
+            drop(builder);
+            drop(db);
+            db = State::builder()
+                .with_database(cached_reads.as_db_mut(StateProviderDatabase::new(&state_provider)))
+                .with_bundle_update()
+                .build();
+            builder = evm_config
+                .builder_for_next_block(&mut db, &parent_header, next_block_attributes.clone())
+                .map_err(PayloadBuilderError::other)?;
+            builder.apply_pre_execution_changes().map_err(|err| {
+                warn!(target: "payload_builder", %err, "failed to apply pre-execution changes");
+                PayloadBuilderError::Internal(err.into())
+            })?;
+            for selected in &included {
+                if !matches!(execute_bundle(&mut builder, selected)?, BundleOutcome::Included(_)) {
+                    return Err(PayloadBuilderError::Other(
+                        format!("included bundle {} failed on re-execution", selected.bundle.hash)
+                            .into(),
+                    ))
+                }
+            }
+        }
+    }
+
     while let Some(pool_tx) = best_txs.next() {
+        let mut record =
+            |outcome| report.record(*pool_tx.hash(), pool_tx.sender(), pool_tx.nonce(), outcome);
+
+        // skip transactions from or to disallowed addresses
+        if let Some(address) = [Some(pool_tx.sender()), pool_tx.to()]
+            .into_iter()
+            .flatten()
+            .find(|address| builder_config.is_disallowed(address))
+        {
+            trace!(target: "payload_builder", tx=?pool_tx.hash(), %address, "skipping disallowed transaction");
+            record(TransactionBuildOutcome::Disallowed);
+            best_txs.mark_invalid(
+                &pool_tx,
+                InvalidPoolTransactionError::Other(Box::new(DisallowedTransactionError(address))),
+            );
+            continue
+        }
+
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+            record(TransactionBuildOutcome::ExceedsGasLimit {
+                gas_limit: pool_tx.gas_limit(),
+                gas_left: block_gas_limit - cumulative_gas_used,
+            });
             // we can't fit this transaction into the block, so we need to mark it as invalid
             // which also removes all dependent transaction from the iterator before we can
             // continue
@@ -231,6 +422,10 @@ where
                 // the iterator. This is similar to the gas limit condition
                 // for regular transactions above.
                 trace!(target: "payload_builder", tx=?tx.hash(), ?block_blob_count, "skipping blob transaction because it would exceed the max blob count per block");
+                record(TransactionBuildOutcome::ExceedsBlobLimit {
+                    blobs: tx_blob_count,
+                    blobs_left: max_blob_count - block_blob_count,
+                });
                 best_txs.mark_invalid(
                     &pool_tx,
                     InvalidPoolTransactionError::Eip4844(
@@ -266,6 +461,9 @@ where
             blob_tx_sidecar = match blob_sidecar_result {
                 Ok(sidecar) => Some(sidecar),
                 Err(error) => {
+                    record(TransactionBuildOutcome::InvalidBlobSidecar {
+                        error: error.to_string(),
+                    });
                     best_txs.mark_invalid(&pool_tx, InvalidPoolTransactionError::Eip4844(error));
                     continue
                 }
@@ -280,7 +478,14 @@ where
                 if error.is_nonce_too_low() {
                     // if the nonce is too low, we can skip this transaction
                     trace!(target: "payload_builder", %error, ?tx, "skipping nonce too low transaction");
+                    record(TransactionBuildOutcome::NonceTooLow);
                 } else {
+                    record(match error.as_invalid_tx_err() {
+                        Some(InvalidTransaction::NonceTooHigh { state, .. }) => {
+                            TransactionBuildOutcome::NonceGap { expected: *state }
+                        }
+                        _ => TransactionBuildOutcome::Invalid { error: error.to_string() },
+                    });
                     // if the transaction is invalid, we can skip it and all of its
                     // descendants
                     trace!(target: "payload_builder", %error, ?tx, "skipping invalid transaction and its descendants");
@@ -310,8 +515,10 @@ where
         // update and add to total fees
         let miner_fee =
             tx.effective_tip_per_gas(base_fee).expect("fee is always valid; execution succeeded");
-        total_fees += U256::from(miner_fee) * U256::from(gas_used);
+        let fee = U256::from(miner_fee) * U256::from(gas_used);
+        total_fees += fee;
         cumulative_gas_used += gas_used;
+        record(TransactionBuildOutcome::Included { gas_used, fee });
 
         // Add blob tx sidecar to the payload.
         if let Some(sidecar) = blob_tx_sidecar {
@@ -319,6 +526,8 @@ where
         }
     }
 
+    report.elapsed_micros = started_at.elapsed().as_micros() as u64;
+
     // check if we have a better block
     if !is_better_payload(best_payload.as_ref(), total_fees) {
         // Release db
@@ -338,7 +547,348 @@ where
 
     let payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees, requests)
         // add blob sidecars from the executed txs
-        .with_sidecars(blob_sidecars);
+        .with_sidecars(blob_sidecars)
+        .with_build_report(report);
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
+
+/// A bundle selected for inclusion by [`select_bundles`].
+struct SelectedBundle {
+    /// The bundle.
+    bundle: Arc<Bundle<TransactionSigned>>,
+    /// Transactions that pay out the refunds of the bundle, signed by the fee recipient.
+    payouts: Vec<Recovered<TransactionSigned>>,
+}
+
+/// Outcome of [`execute_bundle`].
+enum BundleOutcome {
+    /// All transactions of the bundle were included, followed by the transactions that pay out
+    /// its refunds, with the gas they used.
+    Included(Vec<(Recovered<TransactionSigned>, u64)>),
+    /// A transaction of the bundle was invalid or reverted without being allowed to, or its
+    /// refunds could not be paid out.
+    Failed {
+        /// Number of transactions that were included before the failing one.
+        committed: usize,
+    },
+}
+
+/// Signs the transactions that pay out the refunds of bundles from the fee recipient.
+#[derive(Debug)]
+struct RefundPayer<'a> {
+    /// Key of the fee recipient.
+    signer: &'a PrivateKeySigner,
+    /// Chain ID of the payout transactions.
+    chain_id: u64,
+    /// Base fee of the block.
+    base_fee: u64,
+}
+
+impl RefundPayer<'_> {
+    /// Returns a transaction that transfers the value to the recipient.
+    ///
+    /// The transaction pays the base fee without a priority fee, the cost of the transaction is
+    /// already deducted from the refund.
+    fn payout(
+        &self,
+        nonce: u64,
+        recipient: Address,
+        value: U256,
+    ) -> Result<Recovered<TransactionSigned>, PayloadBuilderError> {
+        let tx = TxEip1559 {
+            chain_id: self.chain_id,
+            nonce,
+            gas_limit: BUNDLE_PAYOUT_MAX_COST,
+            max_fee_per_gas: self.base_fee.into(),
+            max_priority_fee_per_gas: 0,
+            to: TxKind::Call(recipient),
+            value,
+            ..Default::default()
+        };
+        let signature =
+            self.signer.sign_hash_sync(&tx.signature_hash()).map_err(PayloadBuilderError::other)?;
+        Ok(Recovered::new_unchecked(tx.into_signed(signature).into(), self.signer.address()))
+    }
+}
+
+/// Executes the transactions of the bundle with the block builder, followed by the transactions
+/// that pay out its refunds.
+///
+/// The transaction that fails is not included, but the transactions of the bundle before it are.
+fn execute_bundle(
+    builder: &mut impl BlockBuilder<Primitives = EthPrimitives>,
+    selected: &SelectedBundle,
+) -> Result<BundleOutcome, PayloadBuilderError> {
+    let bundle_txs =
+        selected.bundle.transactions.iter().map(|bundle_tx| (&bundle_tx.tx, bundle_tx.can_revert));
+    let payouts = selected.payouts.iter().map(|payout| (payout, false));
+
+    let mut included = Vec::with_capacity(bundle_txs.len() + payouts.len());
+    for (tx, can_revert) in bundle_txs.chain(payouts) {
+        let result = builder.execute_transaction_with_commit_condition(tx.clone(), |result| {
+            if result.is_success() || can_revert {
+                CommitChanges::Yes
+            } else {
+                CommitChanges::No
+            }
+        });
+        match result {
+            Ok(Some(gas_used)) => included.push((tx.clone(), gas_used)),
+            Ok(None) |
+            Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx { .. })) => {
+                return Ok(BundleOutcome::Failed { committed: included.len() })
+            }
+            Err(err) => return Err(PayloadBuilderError::evm(err)),
+        }
+    }
+    Ok(BundleOutcome::Included(included))
+}
+
+/// Returns the balance of the given account.
+fn balance_of<DB: reth_evm::Database>(
+    db: &mut DB,
+    address: Address,
+) -> Result<U256, PayloadBuilderError> {
+    Ok(db
+        .basic(address)
+        .map_err(PayloadBuilderError::other)?
+        .map(|account| account.balance)
+        .unwrap_or_default())
+}
+
+/// Simulates the given bundles on top of the parent state and returns the bundles to include, most
+/// valuable first.
+///
+/// Every bundle is simulated on top of the bundles selected before it, so bundles that conflict
+/// with a more valuable bundle are skipped. Bundles that contain blob transactions, and bundles
+/// that pay refunds if there is no refund payer, are not supported and always skipped.
+///
+/// The given state is expected to include the pre-execution changes of the block.
+fn select_bundles<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    evm_env: EvmEnvFor<EvmConfig>,
+    db: CacheDB<DB>,
+    coinbase: Address,
+    block_gas_limit: u64,
+    refund_payer: Option<&RefundPayer<'_>>,
+    bundles: Vec<Arc<Bundle<TransactionSigned>>>,
+) -> Vec<SelectedBundle>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: DatabaseRef<Error: Send + Sync + 'static> + Clone + std::fmt::Debug,
+{
+    let simulate = |db, bundle: &Bundle<TransactionSigned>| {
+        simulate_bundle(evm_config, evm_env.clone(), db, coinbase, refund_payer, bundle)
+    };
+
+    // rank the bundles by their value on top of the parent state
+    let mut candidates = bundles
+        .into_iter()
+        .filter(|bundle| {
+            if (bundle.has_refunds() && refund_payer.is_none()) ||
+                bundle.transactions.iter().any(|bundle_tx| bundle_tx.tx.is_eip4844())
+            {
+                trace!(target: "payload_builder", bundle=%bundle.hash, "skipping unsupported bundle");
+                return false
+            }
+            true
+        })
+        .filter_map(|bundle| {
+            let (_, value, _) = simulate(db.clone(), &bundle)?;
+            Some((bundle, value))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    let mut db = db;
+    let mut gas_limit = 0;
+    let mut selected = Vec::new();
+    for (bundle, _) in candidates {
+        let bundle_gas_limit =
+            bundle.gas_limit() + bundle.payout_count() as u64 * BUNDLE_PAYOUT_MAX_COST;
+        if gas_limit + bundle_gas_limit > block_gas_limit {
+            continue
+        }
+
+        // re-simulate on top of the selected bundles to detect conflicts
+        match simulate(db.clone(), &bundle) {
+            Some((next_db, value, payouts)) if !value.is_zero() => {
+                db = next_db;
+                gas_limit += bundle_gas_limit;
+                selected.push(SelectedBundle { bundle, payouts });
+            }
+            _ => {
+                trace!(target: "payload_builder", bundle=%bundle.hash, "skipping conflicting bundle")
+            }
+        }
+    }
+
+    selected
+}
+
+/// Executes the transactions of the bundle, pays out its refunds and returns the resulting state,
+/// the value paid to the fee recipient and the transactions that paid out the refunds.
+///
+/// Returns `None` if a transaction is invalid or reverts without being allowed to, or if the
+/// refunds of the bundle can't be paid out.
+fn simulate_bundle<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    evm_env: EvmEnvFor<EvmConfig>,
+    db: CacheDB<DB>,
+    coinbase: Address,
+    refund_payer: Option<&RefundPayer<'_>>,
+    bundle: &Bundle<TransactionSigned>,
+) -> Option<(CacheDB<DB>, U256, Vec<Recovered<TransactionSigned>>)>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: DatabaseRef<Error: Send + Sync + 'static> + std::fmt::Debug,
+{
+    let base_fee = evm_env.block_env.basefee;
+    let mut evm = evm_config.evm_with_env(db, evm_env);
+    let balance_before = balance_of(evm.db_mut(), coinbase).ok()?;
+
+    let mut balance = balance_before;
+    let mut coinbase_diffs = Vec::with_capacity(bundle.transactions.len());
+    for bundle_tx in &bundle.transactions {
+        let ResultAndState { result, state } =
+            evm.transact(evm_config.tx_env(&bundle_tx.tx)).ok()?;
+        if !result.is_success() && !bundle_tx.can_revert {
+            return None
+        }
+        let balance_after = state.get(&coinbase).map_or(balance, |account| account.info.balance);
+        coinbase_diffs.push(balance_after.saturating_sub(balance));
+        balance = balance_after;
+        evm.db_mut().commit(state);
+    }
+
+    let mut payouts = Vec::new();
+    if bundle.has_refunds() {
+        let refund_payer = refund_payer?;
+        let nonce =
+            evm.db_mut().basic(coinbase).ok()?.map(|account| account.nonce).unwrap_or_default();
+        let refunds = bundle.refunds(&coinbase_diffs, base_fee)?;
+        for (nonce, (recipient, value)) in (nonce..).zip(refunds) {
+            let payout = refund_payer.payout(nonce, recipient, value).ok()?;
+            let ResultAndState { result, state } = evm.transact(evm_config.tx_env(&payout)).ok()?;
+            if !result.is_success() {
+                return None
+            }
+            evm.db_mut().commit(state);
+            payouts.push(payout);
+        }
+    }
+
+    let value = balance_of(evm.db_mut(), coinbase).ok()?.saturating_sub(balance_before);
+    Some((evm.into_db(), value, payouts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_eips::{eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M, eip4895::Withdrawals};
+    use alloy_primitives::B256;
+    use alloy_rpc_types_engine::PayloadId;
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder};
+    use reth_payload_primitives::BuiltPayload;
+    use reth_primitives_traits::{SealedHeader, SignerRecoverable};
+    use reth_revm::{cached::CachedReads, cancelled::CancelOnDrop};
+    use reth_storage_api::noop::NoopProvider;
+    use reth_transaction_pool::{
+        test_utils::{MockTransaction, TestPool, TestPoolBuilder},
+        PoolConfig,
+    };
+
+    #[test]
+    fn signs_refund_payouts() {
+        let signer = PrivateKeySigner::random();
+        let refund_payer = RefundPayer { signer: &signer, chain_id: 1, base_fee: 7 };
+        let recipient = Address::random();
+
+        let payout = refund_payer.payout(3, recipient, U256::from(100)).unwrap();
+        assert_eq!(payout.inner().recover_signer().unwrap(), signer.address());
+        assert_eq!(payout.signer(), signer.address());
+        assert_eq!(payout.nonce(), 3);
+        assert_eq!(payout.to(), Some(recipient));
+        assert_eq!(payout.value(), U256::from(100));
+        assert_eq!(payout.gas_limit(), BUNDLE_PAYOUT_MAX_COST);
+        assert_eq!(payout.effective_tip_per_gas(7), Some(0));
+    }
+
+    #[tokio::test]
+    async fn skips_disallowed_transactions() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::mainnet()
+                .genesis(ChainSpec::default().genesis)
+                .shanghai_activated()
+                .build(),
+        );
+        let client = NoopProvider::eth(chain_spec.clone());
+        // the pool rejects transactions below the minimal protocol base fee, which the senders
+        // could not afford
+        let pool: TestPool = TestPoolBuilder::default()
+            .with_config(PoolConfig { minimal_protocol_basefee: 0, ..Default::default() })
+            .into();
+
+        let allowed = MockTransaction::legacy().with_gas_limit(21_000);
+        let disallowed_sender = MockTransaction::legacy().with_gas_limit(21_000);
+        let disallowed_recipient = MockTransaction::legacy().with_gas_limit(21_000);
+        for tx in [&allowed, &disallowed_sender, &disallowed_recipient] {
+            pool.add_external_transaction(tx.clone()).await.unwrap();
+        }
+
+        let builder_config = EthereumBuilderConfig::new().with_disallow([
+            disallowed_sender.sender(),
+            disallowed_recipient.to().expect("not a create transaction"),
+        ]);
+
+        let parent = Arc::new(SealedHeader::seal_slow(Header {
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            base_fee_per_gas: Some(0),
+            ..Default::default()
+        }));
+        let attributes = EthPayloadBuilderAttributes {
+            id: PayloadId::new([0; 8]),
+            parent: parent.hash(),
+            timestamp: parent.timestamp + 12,
+            suggested_fee_recipient: Address::random(),
+            prev_randao: B256::random(),
+            withdrawals: Withdrawals::default(),
+            parent_beacon_block_root: None,
+        };
+        let args = BuildArguments::new(
+            CachedReads::default(),
+            PayloadConfig::new(parent, attributes),
+            CancelOnDrop::default(),
+            None,
+        );
+
+        let outcome = default_ethereum_payload(
+            EthEvmConfig::new(chain_spec),
+            client,
+            pool.clone(),
+            builder_config,
+            None,
+            args,
+            |attributes| pool.best_transactions_with_attributes(attributes),
+        )
+        .unwrap();
+        let BuildOutcome::Better { payload, .. } = outcome else {
+            panic!("expected a built payload, got {outcome:?}")
+        };
+
+        let transactions = &payload.block().body().transactions;
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].tx_hash(), allowed.hash());
+
+        let report = payload.build_report().unwrap();
+        assert_eq!(report.included(), 1);
+        for tx in [&disallowed_sender, &disallowed_recipient] {
+            assert_eq!(
+                report.transaction(tx.hash()).unwrap().outcome,
+                TransactionBuildOutcome::Disallowed
+            );
+        }
+    }
+}
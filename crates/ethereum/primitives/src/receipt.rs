@@ -547,7 +547,7 @@ mod compact {
             flags.set_cumulative_gas_used_len(cumulative_gas_used_len as u8);
             self.logs.to_compact(&mut buffer);
 
-            let zstd = buffer.len() > 7;
+            let zstd = buffer.len() > 7 && reth_zstd_compressors::is_compression_enabled();
             if zstd {
                 flags.set___zstd(1);
             }
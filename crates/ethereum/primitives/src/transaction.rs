@@ -573,7 +573,8 @@ impl reth_codecs::Compact for TransactionSigned {
         buf.put_u8(0);
 
         let sig_bit = self.signature.to_compact(buf) as u8;
-        let zstd_bit = self.transaction.input().len() >= 32;
+        let zstd_bit =
+            self.transaction.input().len() >= 32 && reth_zstd_compressors::is_compression_enabled();
 
         let tx_bits = if zstd_bit {
             let mut tmp = Vec::with_capacity(256);
@@ -726,5 +727,23 @@ mod tests {
 
             assert_eq!(actual_tx, expected_tx);
         }
+
+        #[test]
+        fn test_roundtrip_compact_without_zstd(mut reth_tx in arb::<TransactionSigned>()) {
+            // zstd would kick in if it wasn't disabled
+            *reth_tx.transaction.input_mut() = vec![0;33].into();
+
+            let mut buf = Vec::<u8>::new();
+            let len = reth_zstd_compressors::without_compression(|| reth_tx.to_compact(&mut buf));
+            assert_eq!(buf[0] >> 3, 0);
+
+            let mut actual_buf = Vec::<u8>::new();
+            let alloy_tx = EthereumTxEnvelope::<TxEip4844>::from(reth_tx.clone());
+            reth_zstd_compressors::without_compression(|| alloy_tx.to_compact(&mut actual_buf));
+            assert_eq!(actual_buf, buf);
+
+            let (actual_tx, _) = TransactionSigned::from_compact(&buf, len);
+            assert_eq!(actual_tx, reth_tx);
+        }
     }
 }
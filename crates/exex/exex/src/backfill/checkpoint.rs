@@ -0,0 +1,106 @@
+use alloy_primitives::BlockNumber;
+use reth_db_api::{
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_provider::{errors::provider::ProviderResult, DBProvider, DatabaseProviderFactory};
+use reth_tracing::tracing::debug;
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// How often the finished height of an `ExEx` is persisted as its backfill checkpoint at most.
+pub const BACKFILL_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Storage for the progress of `ExEx`s.
+///
+/// A checkpoint is the highest block an `ExEx` reported as processed with
+/// [`ExExEvent::FinishedHeight`](crate::ExExEvent::FinishedHeight), so that an `ExEx` that doesn't
+/// persist its own head can resume a backfill from the next block after a restart, see
+/// [`ExExNotifications::backfill_checkpoint`](crate::ExExNotifications::backfill_checkpoint).
+///
+/// It is implemented for every [`DatabaseProviderFactory`], storing the checkpoints in the
+/// [`tables::ExExBackfillCheckpoints`] table.
+pub trait BackfillCheckpointStore: Debug + Send + Sync {
+    /// Returns the checkpoint of the backfill job with the given ID.
+    fn backfill_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Saves the checkpoint of the backfill job with the given ID.
+    fn save_backfill_checkpoint(&self, id: &str, block_number: BlockNumber) -> ProviderResult<()>;
+
+    /// Removes the checkpoint of the backfill job with the given ID.
+    fn remove_backfill_checkpoint(&self, id: &str) -> ProviderResult<()>;
+}
+
+impl<F> BackfillCheckpointStore for F
+where
+    F: DatabaseProviderFactory + Debug,
+{
+    fn backfill_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>> {
+        let provider = self.database_provider_ro()?;
+        Ok(provider.tx_ref().get::<tables::ExExBackfillCheckpoints>(id.to_string())?)
+    }
+
+    fn save_backfill_checkpoint(&self, id: &str, block_number: BlockNumber) -> ProviderResult<()> {
+        let provider = self.database_provider_rw()?;
+        provider.tx_ref().put::<tables::ExExBackfillCheckpoints>(id.to_string(), block_number)?;
+        provider.commit()?;
+        Ok(())
+    }
+
+    fn remove_backfill_checkpoint(&self, id: &str) -> ProviderResult<()> {
+        let provider = self.database_provider_rw()?;
+        provider.tx_ref().delete::<tables::ExExBackfillCheckpoints>(id.to_string(), None)?;
+        provider.commit()?;
+        Ok(())
+    }
+}
+
+/// Persists the finished height of an `ExEx` in a [`BackfillCheckpointStore`].
+///
+/// Every save opens a write transaction, so the finished height is saved at most once per
+/// [`BACKFILL_CHECKPOINT_INTERVAL`] instead of on every event. The checkpoint can lag behind the
+/// finished height, which only means that a few blocks are delivered again after a restart.
+#[derive(Debug)]
+pub(crate) struct BackfillCheckpointWriter {
+    store: Arc<dyn BackfillCheckpointStore>,
+    /// The finished height that is not persisted yet.
+    pending: Option<BlockNumber>,
+    /// When the checkpoint was last persisted.
+    last_save: Option<Instant>,
+}
+
+impl BackfillCheckpointWriter {
+    pub(crate) fn new(store: Arc<dyn BackfillCheckpointStore>) -> Self {
+        Self { store, pending: None, last_save: None }
+    }
+
+    /// Records a new finished height, to be persisted with the next [`Self::save_if_due`].
+    pub(crate) const fn on_finished_height(&mut self, block_number: BlockNumber) {
+        self.pending = Some(block_number);
+    }
+
+    /// Persists the pending finished height if [`BACKFILL_CHECKPOINT_INTERVAL`] has elapsed since
+    /// the last save.
+    pub(crate) fn save_if_due(&mut self, id: &str) -> ProviderResult<()> {
+        if self
+            .last_save
+            .is_some_and(|last_save| last_save.elapsed() < BACKFILL_CHECKPOINT_INTERVAL)
+        {
+            return Ok(())
+        }
+        self.save(id)
+    }
+
+    /// Persists the pending finished height, if any.
+    pub(crate) fn save(&mut self, id: &str) -> ProviderResult<()> {
+        if let Some(block_number) = self.pending.take() {
+            debug!(target: "exex::backfill", %id, %block_number, "Saving backfill checkpoint");
+            self.store.save_backfill_checkpoint(id, block_number)?;
+            self.last_save = Some(Instant::now());
+        }
+        Ok(())
+    }
+}
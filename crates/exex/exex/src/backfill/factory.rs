@@ -16,6 +16,7 @@ pub struct BackfillJobFactory<E, P> {
     prune_modes: PruneModes,
     thresholds: ExecutionStageThresholds,
     stream_parallelism: usize,
+    stream_max_blocks_per_second: Option<u64>,
 }
 
 impl<E, P> BackfillJobFactory<E, P> {
@@ -35,6 +36,7 @@ impl<E, P> BackfillJobFactory<E, P> {
                 ..Default::default()
             },
             stream_parallelism: DEFAULT_PARALLELISM,
+            stream_max_blocks_per_second: None,
         }
     }
 
@@ -58,6 +60,16 @@ impl<E, P> BackfillJobFactory<E, P> {
         self.stream_parallelism = stream_parallelism;
         self
     }
+
+    /// Limits the number of blocks per second executed by the stream.
+    ///
+    /// Configures the [`StreamBackfillJob`](super::stream::StreamBackfillJob) created via
+    /// [`BackfillJob::into_stream`], so that the backfill doesn't starve the live block
+    /// processing.
+    pub const fn with_stream_max_blocks_per_second(mut self, max_blocks_per_second: u64) -> Self {
+        self.stream_max_blocks_per_second = Some(max_blocks_per_second);
+        self
+    }
 }
 
 impl<E: Clone, P: Clone> BackfillJobFactory<E, P> {
@@ -70,6 +82,7 @@ impl<E: Clone, P: Clone> BackfillJobFactory<E, P> {
             range,
             thresholds: self.thresholds.clone(),
             stream_parallelism: self.stream_parallelism,
+            stream_max_blocks_per_second: self.stream_max_blocks_per_second,
         }
     }
 }
@@ -35,6 +35,7 @@ pub struct BackfillJob<E, P> {
     pub(crate) thresholds: ExecutionStageThresholds,
     pub(crate) range: RangeInclusive<BlockNumber>,
     pub(crate) stream_parallelism: usize,
+    pub(crate) stream_max_blocks_per_second: Option<u64>,
 }
 
 impl<E, P> Iterator for BackfillJob<E, P>
@@ -165,6 +166,7 @@ pub struct SingleBlockBackfillJob<E, P> {
     pub(crate) provider: P,
     pub(crate) range: RangeInclusive<BlockNumber>,
     pub(crate) stream_parallelism: usize,
+    pub(crate) stream_max_blocks_per_second: Option<u64>,
 }
 
 impl<E, P> Iterator for SingleBlockBackfillJob<E, P>
@@ -236,6 +238,7 @@ impl<E, P> From<BackfillJob<E, P>> for SingleBlockBackfillJob<E, P> {
             provider: job.provider,
             range: job.range,
             stream_parallelism: job.stream_parallelism,
+            stream_max_blocks_per_second: job.stream_max_blocks_per_second,
         }
     }
 }
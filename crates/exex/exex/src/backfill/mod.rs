@@ -1,9 +1,12 @@
+mod checkpoint;
 mod factory;
 mod job;
 mod stream;
 #[cfg(test)]
 mod test_utils;
 
+pub(crate) use checkpoint::BackfillCheckpointWriter;
+pub use checkpoint::{BackfillCheckpointStore, BACKFILL_CHECKPOINT_INTERVAL};
 pub use factory::BackfillJobFactory;
 pub use job::{BackfillJob, SingleBlockBackfillJob};
 pub use stream::StreamBackfillJob;
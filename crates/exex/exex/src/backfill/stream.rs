@@ -3,7 +3,7 @@ use crate::{BackfillJob, SingleBlockBackfillJob};
 use alloy_primitives::BlockNumber;
 use futures::{
     stream::{FuturesOrdered, Stream},
-    StreamExt,
+    Future, StreamExt,
 };
 use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::{
@@ -20,8 +20,12 @@ use std::{
     ops::RangeInclusive,
     pin::Pin,
     task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    task::JoinHandle,
+    time::{Instant, Sleep},
 };
-use tokio::task::JoinHandle;
 
 /// The default parallelism for active tasks in [`StreamBackfillJob`].
 pub(crate) const DEFAULT_PARALLELISM: usize = 4;
@@ -47,10 +51,47 @@ type SingleBlockStreamItem<N = EthPrimitives> = (
 );
 type BatchBlockStreamItem<N = EthPrimitives> = Chain<N>;
 
+/// Limits the number of blocks per second that are spawned for execution.
+#[derive(Debug)]
+struct ThroughputLimiter {
+    max_blocks_per_second: u64,
+    /// The earliest instant at which the next batch of blocks can be spawned.
+    next_slot: Instant,
+    sleep: Pin<Box<Sleep>>,
+}
+
+impl ThroughputLimiter {
+    fn new(max_blocks_per_second: u64) -> Self {
+        let now = Instant::now();
+        Self {
+            max_blocks_per_second: max_blocks_per_second.max(1),
+            next_slot: now,
+            sleep: Box::pin(tokio::time::sleep_until(now)),
+        }
+    }
+
+    /// Waits until the given number of blocks can be spawned and reserves the slot for them.
+    fn poll_acquire(&mut self, cx: &mut Context<'_>, blocks: u64) -> Poll<()> {
+        if self.next_slot > Instant::now() {
+            if self.sleep.deadline() != self.next_slot {
+                self.sleep.as_mut().reset(self.next_slot);
+            }
+            ready!(self.sleep.as_mut().poll(cx));
+        }
+
+        let delay = Duration::from_secs_f64(blocks as f64 / self.max_blocks_per_second as f64);
+        self.next_slot = self.next_slot.max(Instant::now()) + delay;
+        Poll::Ready(())
+    }
+}
+
 /// Stream for processing backfill jobs asynchronously.
 ///
 /// This struct manages the execution of [`SingleBlockBackfillJob`] tasks, allowing blocks to be
 /// processed asynchronously but in order within a specified range.
+///
+/// The execution throughput can be limited with [`StreamBackfillJob::with_max_blocks_per_second`]
+/// so that the backfill doesn't starve the live block processing.
 #[derive(Debug)]
 pub struct StreamBackfillJob<E, P, T> {
     evm_config: E,
@@ -61,6 +102,7 @@ pub struct StreamBackfillJob<E, P, T> {
     parallelism: usize,
     batch_size: usize,
     thresholds: ExecutionStageThresholds,
+    throughput_limiter: Option<ThroughputLimiter>,
 }
 
 impl<E, P, T> StreamBackfillJob<E, P, T>
@@ -79,6 +121,25 @@ where
         self
     }
 
+    /// Limits the number of blocks per second that are executed by the [`StreamBackfillJob`].
+    pub fn with_max_blocks_per_second(mut self, max_blocks_per_second: u64) -> Self {
+        self.throughput_limiter = Some(ThroughputLimiter::new(max_blocks_per_second));
+        self
+    }
+
+    /// Returns the maximum number of blocks per second that are executed, if limited.
+    #[cfg(test)]
+    pub(crate) fn max_blocks_per_second(&self) -> Option<u64> {
+        self.throughput_limiter.as_ref().map(|limiter| limiter.max_blocks_per_second)
+    }
+
+    /// Waits until the throughput limiter allows spawning the given number of blocks.
+    fn poll_throughput(&mut self, cx: &mut Context<'_>, blocks: u64) -> Poll<()> {
+        self.throughput_limiter
+            .as_mut()
+            .map_or(Poll::Ready(()), |limiter| limiter.poll_acquire(cx, blocks))
+    }
+
     /// Spawns a new task calling the [`BackfillTaskIterator::next`] method and pushes it to the end
     /// of the [`BackfillTasks`] queue.
     fn push_back(&mut self, mut job: BackfillTaskIterator<T>) {
@@ -128,11 +189,18 @@ where
 
         // Spawn new tasks only if we are below the parallelism configured.
         while this.tasks.len() < this.parallelism {
-            // Get the next block number from the range. If it is empty, we are done.
-            let Some(block_number) = this.range.next() else {
+            if this.range.is_empty() {
                 debug!(target: "exex::backfill", tasks = %this.tasks.len(), range = ?this.range, "No more single blocks to backfill");
                 break;
-            };
+            }
+
+            // Wait for the throughput limiter, still polling the tasks that are already running.
+            if this.poll_throughput(cx, 1).is_pending() {
+                break
+            }
+
+            // Get the next block number from the range. If it is empty, we are done.
+            let Some(block_number) = this.range.next() else { break };
 
             // Spawn a new task for that block
             debug!(target: "exex::backfill", tasks = %this.tasks.len(), ?block_number, "Spawning new single block backfill task");
@@ -141,11 +209,18 @@ where
                 provider: this.provider.clone(),
                 range: block_number..=block_number,
                 stream_parallelism: this.parallelism,
+                stream_max_blocks_per_second: None,
             }) as BackfillTaskIterator<_>;
             this.push_back(job);
         }
 
-        this.poll_next_task(cx)
+        let res = ready!(this.poll_next_task(cx));
+        if res.is_none() && !this.range.is_empty() {
+            // The throughput limiter will wake us up to spawn the next task
+            return Poll::Pending
+        }
+
+        Poll::Ready(res)
     }
 }
 
@@ -161,7 +236,15 @@ where
 
         loop {
             // Spawn new tasks only if we are below the parallelism configured.
-            while this.tasks.len() < this.parallelism {
+            while this.tasks.len() < this.parallelism && !this.range.is_empty() {
+                // Wait for the throughput limiter, still polling the tasks that are already
+                // running.
+                let blocks =
+                    (this.range.end() - this.range.start() + 1).min(this.batch_size as u64);
+                if this.poll_throughput(cx, blocks).is_pending() {
+                    break
+                }
+
                 // Take the next `batch_size` blocks from the range and calculate the range bounds
                 let mut range = this.range.by_ref().take(this.batch_size);
                 let start = range.next();
@@ -182,20 +265,26 @@ where
                     thresholds: this.thresholds.clone(),
                     range,
                     stream_parallelism: this.parallelism,
+                    stream_max_blocks_per_second: None,
                 }) as BackfillTaskIterator<_>;
                 this.push_back(job);
             }
 
             let res = ready!(this.poll_next_task(cx));
 
-            if res.is_some() {
-                return Poll::Ready(res);
+            if let Some(res) = res {
+                return Poll::Ready(Some(res));
             }
 
             if this.range.is_empty() {
                 // only terminate the stream if there are no more blocks to process
                 return Poll::Ready(None);
             }
+
+            if this.tasks.is_empty() {
+                // The throughput limiter will wake us up to spawn the next task
+                return Poll::Pending
+            }
         }
     }
 }
@@ -211,6 +300,7 @@ impl<E, P> From<SingleBlockBackfillJob<E, P>> for StreamBackfillJob<E, P, Single
             parallelism: job.stream_parallelism,
             batch_size: 1,
             thresholds: ExecutionStageThresholds { max_blocks: Some(1), ..Default::default() },
+            throughput_limiter: job.stream_max_blocks_per_second.map(ThroughputLimiter::new),
         }
     }
 }
@@ -233,6 +323,7 @@ where
                 max_blocks: Some(batch_size as u64),
                 ..job.thresholds
             },
+            throughput_limiter: job.stream_max_blocks_per_second.map(ThroughputLimiter::new),
         }
     }
 }
@@ -106,6 +106,12 @@ pub use manager::*;
 mod notifications;
 pub use notifications::*;
 
+mod remote;
+pub use remote::*;
+
+mod rpc;
+pub use rpc::*;
+
 mod wal;
 pub use wal::*;
 
@@ -1,8 +1,10 @@
 use crate::{
-    wal::Wal, ExExEvent, ExExNotification, ExExNotifications, FinishedExExHeight, WalHandle,
+    wal::Wal, BackfillCheckpointStore, BackfillCheckpointWriter, ExExEvent, ExExNotification,
+    ExExNotifications, FinishedExExHeight, WalHandle,
 };
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
 use futures::StreamExt;
 use itertools::Itertools;
 use metrics::Gauge;
@@ -28,9 +30,9 @@ use std::{
 };
 use tokio::sync::{
     mpsc::{self, error::SendError, UnboundedReceiver, UnboundedSender},
-    watch,
+    oneshot, watch,
 };
-use tokio_util::sync::{PollSendError, PollSender, ReusableBoxFuture};
+use tokio_util::sync::{CancellationToken, PollSendError, PollSender, ReusableBoxFuture};
 
 /// Default max size of the internal state notifications buffer.
 ///
@@ -64,6 +66,95 @@ struct ExExMetrics {
     notifications_sent_total: Counter,
     /// The total number of events an `ExEx` has sent to the manager.
     events_sent_total: Counter,
+    /// The highest block number the `ExEx` has finished processing.
+    finished_height: Gauge,
+    /// The number of blocks the `ExEx` is behind the highest block sent to the manager.
+    lag_blocks: Gauge,
+    /// Whether the `ExEx` is paused.
+    paused: Gauge,
+}
+
+/// The status of an `ExEx` managed by the [`ExExManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExExStatus {
+    /// The `ExEx` receives notifications.
+    Running,
+    /// The `ExEx` is paused and doesn't receive notifications until it's resumed.
+    Paused,
+    /// The `ExEx` was resumed and receives the notifications it missed while it was paused.
+    Replaying,
+}
+
+/// Information about an `ExEx` managed by the [`ExExManager`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExExInfo {
+    /// The ID of the `ExEx`.
+    pub id: String,
+    /// The status of the `ExEx`.
+    pub status: ExExStatus,
+    /// The highest block the `ExEx` has finished processing, if it has emitted a
+    /// `FinishedHeight` event.
+    pub finished_height: Option<BlockNumHash>,
+    /// The number of blocks the `ExEx` is behind the highest block sent to the manager.
+    pub lag: Option<u64>,
+}
+
+/// Errors returned when controlling the `ExEx`s of a running [`ExExManager`].
+#[derive(Debug, thiserror::Error)]
+pub enum ExExControlError {
+    /// No `ExEx` with the given ID is installed.
+    #[error("ExEx {0} not found")]
+    NotFound(String),
+    /// An `ExEx` with the given ID is already installed.
+    #[error("ExEx {0} already exists")]
+    AlreadyExists(String),
+    /// The `ExEx` is already paused.
+    #[error("ExEx {0} is already paused")]
+    AlreadyPaused(String),
+    /// The `ExEx` is not paused.
+    #[error("ExEx {0} is not paused")]
+    NotPaused(String),
+    /// The [`ExExManager`] is not running.
+    #[error("ExEx manager is not running")]
+    ManagerClosed,
+}
+
+/// Commands sent to the [`ExExManager`] through the [`ExExManagerHandle`].
+#[derive(Debug)]
+enum ExExManagerCommand<N: NodePrimitives> {
+    Add(ExExHandle<N>, oneshot::Sender<Result<(), ExExControlError>>),
+    Pause(String, oneshot::Sender<Result<(), ExExControlError>>),
+    Resume(String, oneshot::Sender<Result<(), ExExControlError>>),
+    Remove(String, oneshot::Sender<Result<(), ExExControlError>>),
+    List(oneshot::Sender<Vec<ExExInfo>>),
+}
+
+/// A notification that an `ExEx` missed while it was paused.
+#[derive(Debug)]
+enum MissedNotification<N: NodePrimitives> {
+    /// The notification was committed to the WAL with the given ID, and is read from it when
+    /// it's delivered.
+    Wal(u32),
+    /// The notification is kept in memory, because it was not committed to the WAL, or was still
+    /// in the manager buffer at the moment of pausing.
+    Buffered(ExExNotification<N>),
+}
+
+/// State of a paused `ExEx`.
+#[derive(Debug)]
+struct PausedExEx<N: NodePrimitives> {
+    /// Notifications that were not delivered to the `ExEx`, in the order they were sent to the
+    /// manager.
+    missed: VecDeque<MissedNotification<N>>,
+}
+
+/// Notifications that a resumed `ExEx` missed while it was paused.
+#[derive(Debug)]
+struct ReplayNotifications<N: NodePrimitives> {
+    /// Notifications to deliver before any new notifications from the manager buffer.
+    missed: VecDeque<MissedNotification<N>>,
 }
 
 /// A handle to an `ExEx` used by the [`ExExManager`] to communicate with `ExEx`'s.
@@ -83,10 +174,21 @@ pub struct ExExHandle<N: NodePrimitives = EthPrimitives> {
     receiver: UnboundedReceiver<ExExEvent>,
     /// The ID of the next notification to send to this `ExEx`.
     next_notification_id: usize,
+    /// The head of the node when the `ExEx` was started.
+    node_head: BlockNumHash,
     /// The finished block of the `ExEx`.
     ///
     /// If this is `None`, the `ExEx` has not emitted a `FinishedHeight` event.
     finished_height: Option<BlockNumHash>,
+    /// The state of the `ExEx` if it's paused.
+    paused: Option<PausedExEx<N>>,
+    /// Notifications missed while the `ExEx` was paused, that are delivered before any new
+    /// notifications from the manager buffer.
+    replay: Option<ReplayNotifications<N>>,
+    /// Token that is cancelled when the `ExEx` is removed from the manager.
+    removed: CancellationToken,
+    /// Persists the finished height of the `ExEx` as its backfill checkpoint, if enabled.
+    checkpoint: Option<BackfillCheckpointWriter>,
 }
 
 impl<N: NodePrimitives> ExExHandle<N> {
@@ -113,13 +215,129 @@ impl<N: NodePrimitives> ExExHandle<N> {
                 sender: PollSender::new(notification_tx),
                 receiver: event_rx,
                 next_notification_id: 0,
+                node_head,
                 finished_height: None,
+                paused: None,
+                replay: None,
+                removed: CancellationToken::new(),
+                checkpoint: None,
             },
             event_tx,
             notifications,
         )
     }
 
+    /// Persists the finished height of the `ExEx` in the given store, so that it can resume from
+    /// it after a restart with [`ExExNotifications::backfill_checkpoint`].
+    ///
+    /// The checkpoint is saved at most once per
+    /// [`BACKFILL_CHECKPOINT_INTERVAL`](crate::BACKFILL_CHECKPOINT_INTERVAL) and when the `ExEx`
+    /// is removed from the manager.
+    pub fn with_backfill_checkpoint(
+        mut self,
+        store: impl BackfillCheckpointStore + 'static,
+    ) -> Self {
+        self.checkpoint = Some(BackfillCheckpointWriter::new(Arc::new(store)));
+        self
+    }
+
+    /// Saves the pending backfill checkpoint of the `ExEx`, if it's enabled. If `force` is
+    /// `false`, the checkpoint is only saved if the checkpoint interval has elapsed.
+    fn save_checkpoint(&mut self, force: bool) {
+        let Some(checkpoint) = &mut self.checkpoint else { return };
+        let res = if force { checkpoint.save(&self.id) } else { checkpoint.save_if_due(&self.id) };
+        if let Err(err) = res {
+            warn!(target: "exex::manager", exex_id = %self.id, %err, "Failed to save backfill checkpoint");
+        }
+    }
+
+    /// Returns a token that is cancelled when the `ExEx` is removed from the manager, either
+    /// through [`ExExManagerHandle::remove_exex`] or because it exceeded the maximum lag.
+    ///
+    /// The `ExEx` task should be stopped once the token is cancelled.
+    pub fn removed(&self) -> CancellationToken {
+        self.removed.clone()
+    }
+
+    /// Returns the status of the `ExEx`.
+    const fn status(&self) -> ExExStatus {
+        if self.paused.is_some() {
+            ExExStatus::Paused
+        } else if self.replay.is_some() {
+            ExExStatus::Replaying
+        } else {
+            ExExStatus::Running
+        }
+    }
+
+    /// Returns the number of blocks the `ExEx` is behind the given tip.
+    ///
+    /// Until the `ExEx` emits a `FinishedHeight` event, it's considered to have finished the node
+    /// head it was started with.
+    fn lag(&self, tip: Option<BlockNumber>) -> Option<u64> {
+        let finished_height = self.finished_height.unwrap_or(self.node_head);
+        tip.map(|tip| tip.saturating_sub(finished_height.number))
+    }
+
+    /// Returns `true` if the notification has already been processed by the `ExEx` according to
+    /// its finished height.
+    fn is_processed(&self, notification: &ExExNotification<N>) -> bool {
+        let Some(finished_height) = self.finished_height else { return false };
+
+        match notification {
+            // Skip the chain commit notification if the finished height of the ExEx is higher than
+            // or equal to the tip of the new notification.
+            ExExNotification::ChainCommitted { new } => {
+                finished_height.number >= new.tip().number()
+            }
+            // Do not handle [ExExNotification::ChainReorged] and
+            // [ExExNotification::ChainReverted] cases and always send the notification, because
+            // the ExEx should be aware of the reorgs and reverts lower than its finished height
+            ExExNotification::ChainReorged { .. } | ExExNotification::ChainReverted { .. } => false,
+        }
+    }
+
+    /// Delivers the notifications that the `ExEx` missed while it was paused.
+    ///
+    /// Returns [`Poll::Ready`] once all of them have been delivered.
+    fn poll_replay(&mut self, cx: &mut Context<'_>, wal: &Wal<N>) -> Poll<eyre::Result<()>> {
+        loop {
+            let Some(replay) = self.replay.as_mut() else { return Poll::Ready(Ok(())) };
+
+            let Some(missed) = replay.missed.pop_front() else {
+                debug!(target: "exex::manager", exex_id = %self.id, "Finished replaying notifications");
+                self.replay = None;
+                continue
+            };
+            let notification = match missed {
+                MissedNotification::Wal(wal_id) => match wal.read_notification(wal_id)? {
+                    Some(notification) => notification,
+                    // The notification was already finalized
+                    None => continue,
+                },
+                MissedNotification::Buffered(notification) => notification,
+            };
+
+            if self.is_processed(&notification) {
+                continue
+            }
+
+            match self.sender.poll_reserve(cx) {
+                Poll::Ready(Ok(())) => {
+                    self.sender.send_item(notification)?;
+                    self.metrics.notifications_sent_total.increment(1);
+                }
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err.into())),
+                Poll::Pending => {
+                    if let Some(replay) = self.replay.as_mut() {
+                        replay.missed.push_front(MissedNotification::Buffered(notification));
+                    }
+                    return Poll::Pending
+                }
+            }
+        }
+    }
+
     /// Reserves a slot in the `PollSender` channel and sends the notification if the slot was
     /// successfully reserved.
     ///
@@ -129,32 +347,17 @@ impl<N: NodePrimitives> ExExHandle<N> {
         cx: &mut Context<'_>,
         (notification_id, notification): &(usize, ExExNotification<N>),
     ) -> Poll<Result<(), PollSendError<ExExNotification<N>>>> {
-        if let Some(finished_height) = self.finished_height {
-            match notification {
-                ExExNotification::ChainCommitted { new } => {
-                    // Skip the chain commit notification if the finished height of the ExEx is
-                    // higher than or equal to the tip of the new notification.
-                    // I.e., the ExEx has already processed the notification.
-                    if finished_height.number >= new.tip().number() {
-                        debug!(
-                            target: "exex::manager",
-                            exex_id = %self.id,
-                            %notification_id,
-                            ?finished_height,
-                            new_tip = %new.tip().number(),
-                            "Skipping notification"
-                        );
-
-                        self.next_notification_id = notification_id + 1;
-                        return Poll::Ready(Ok(()))
-                    }
-                }
-                // Do not handle [ExExNotification::ChainReorged] and
-                // [ExExNotification::ChainReverted] cases and always send the
-                // notification, because the ExEx should be aware of the reorgs and reverts lower
-                // than its finished height
-                ExExNotification::ChainReorged { .. } | ExExNotification::ChainReverted { .. } => {}
-            }
+        if self.is_processed(notification) {
+            debug!(
+                target: "exex::manager",
+                exex_id = %self.id,
+                %notification_id,
+                finished_height = ?self.finished_height,
+                "Skipping notification"
+            );
+
+            self.next_notification_id = notification_id + 1;
+            return Poll::Ready(Ok(()))
         }
 
         debug!(
@@ -199,6 +402,9 @@ pub struct ExExManagerMetrics {
     buffer_size: Gauge,
     /// Current number of `ExEx`'s on the node.
     num_exexs: Gauge,
+    /// The total number of `ExEx`'s detached from the manager because they exceeded the maximum
+    /// lag.
+    detached_exexs_total: Counter,
 }
 
 /// The execution extension manager.
@@ -220,6 +426,8 @@ pub struct ExExManager<P, N: NodePrimitives> {
 
     /// [`ExExNotification`] channel from the [`ExExManagerHandle`]s.
     handle_rx: UnboundedReceiver<(ExExNotificationSource, ExExNotification<N>)>,
+    /// Channel for the commands controlling the `ExEx`'s from the [`ExExManagerHandle`]s.
+    control_rx: UnboundedReceiver<ExExManagerCommand<N>>,
 
     /// The minimum notification ID currently present in the buffer.
     min_id: usize,
@@ -248,6 +456,13 @@ pub struct ExExManager<P, N: NodePrimitives> {
     /// A stream of finalized headers.
     finalized_header_stream: ForkChoiceStream<SealedHeader<N::BlockHeader>>,
 
+    /// The highest committed block number sent to the manager.
+    highest_block: Option<BlockNumber>,
+    /// The maximum number of blocks an `ExEx` can lag behind before it's detached.
+    ///
+    /// If `None`, `ExEx`'s are never detached.
+    max_lag: Option<u64>,
+
     /// A handle to the `ExEx` manager.
     handle: ExExManagerHandle<N>,
     /// Metrics for the `ExEx` manager.
@@ -275,6 +490,7 @@ where
         let num_exexs = handles.len();
 
         let (handle_tx, handle_rx) = mpsc::unbounded_channel();
+        let (control_tx, control_rx) = mpsc::unbounded_channel();
         let (is_ready_tx, is_ready_rx) = watch::channel(true);
         let (finished_height_tx, finished_height_rx) = watch::channel(if num_exexs == 0 {
             FinishedExExHeight::NoExExs
@@ -294,6 +510,7 @@ where
             exex_handles: handles,
 
             handle_rx,
+            control_rx,
 
             min_id: 0,
             next_id: 0,
@@ -307,9 +524,13 @@ where
             wal,
             finalized_header_stream,
 
+            highest_block: None,
+            max_lag: None,
+
             handle: ExExManagerHandle {
                 exex_tx: handle_tx,
-                num_exexs,
+                control_tx,
+                num_exexs: Arc::new(AtomicUsize::new(num_exexs)),
                 is_ready_receiver: is_ready_rx.clone(),
                 is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
                 current_capacity,
@@ -319,11 +540,151 @@ where
         }
     }
 
+    /// Sets the maximum number of blocks an `ExEx` can lag behind the highest block sent to the
+    /// manager.
+    ///
+    /// `ExEx`'s that exceed the maximum lag are detached from the manager, so that they don't
+    /// block the WAL finalization and pruning. This includes paused `ExEx`'s and `ExEx`'s that
+    /// haven't emitted a `FinishedHeight` event yet.
+    pub const fn with_max_lag(mut self, max_lag: u64) -> Self {
+        self.max_lag = Some(max_lag);
+        self
+    }
+
     /// Returns the handle to the manager.
     pub fn handle(&self) -> ExExManagerHandle<N> {
         self.handle.clone()
     }
 
+    /// Updates the number of `ExEx`'s after one was added or removed.
+    fn update_num_exexs(&self) {
+        self.handle.num_exexs.store(self.exex_handles.len(), Ordering::Relaxed);
+        self.metrics.num_exexs.set(self.exex_handles.len() as f64);
+    }
+
+    /// Returns the index of the `ExEx` with the given ID.
+    fn exex_index(&self, id: &str) -> Result<usize, ExExControlError> {
+        self.exex_handles
+            .iter()
+            .position(|exex| exex.id == id)
+            .ok_or_else(|| ExExControlError::NotFound(id.to_string()))
+    }
+
+    /// Removes the `ExEx` at the given index and cancels its removal token.
+    fn remove_exex(&mut self, idx: usize) -> ExExHandle<N> {
+        let mut exex = self.exex_handles.remove(idx);
+        exex.save_checkpoint(true);
+        exex.removed.cancel();
+        exex.metrics.paused.set(0.0);
+        self.update_num_exexs();
+        exex
+    }
+
+    /// Handles a command received from an [`ExExManagerHandle`].
+    fn on_command(&mut self, command: ExExManagerCommand<N>) {
+        match command {
+            ExExManagerCommand::Add(mut exex, tx) => {
+                let res = if self.exex_handles.iter().any(|handle| handle.id == exex.id) {
+                    Err(ExExControlError::AlreadyExists(exex.id))
+                } else {
+                    debug!(target: "exex::manager", exex_id = %exex.id, "Adding ExEx");
+                    // The new ExEx only receives the notifications sent to the manager from now on
+                    exex.next_notification_id = self.next_id;
+                    self.exex_handles.push(exex);
+                    self.update_num_exexs();
+                    Ok(())
+                };
+                let _ = tx.send(res);
+            }
+            ExExManagerCommand::Pause(id, tx) => {
+                let res = self.exex_index(&id).and_then(|idx| {
+                    let exex = &mut self.exex_handles[idx];
+                    if exex.paused.is_some() {
+                        return Err(ExExControlError::AlreadyPaused(id))
+                    }
+
+                    debug!(target: "exex::manager", exex_id = %exex.id, "Pausing ExEx");
+                    // Notifications that are not replayed yet are delivered first, followed by the
+                    // ones from the manager buffer that were not sent to the `ExEx`
+                    let mut missed =
+                        exex.replay.take().map(|replay| replay.missed).unwrap_or_default();
+                    let buffered =
+                        self.buffer.iter().filter(|(id, _)| *id >= exex.next_notification_id);
+                    missed.extend(buffered.map(|(_, notification)| {
+                        MissedNotification::Buffered(notification.clone())
+                    }));
+                    exex.paused = Some(PausedExEx { missed });
+                    exex.metrics.paused.set(1.0);
+                    Ok(())
+                });
+                let _ = tx.send(res);
+            }
+            ExExManagerCommand::Resume(id, tx) => {
+                let res = self.exex_index(&id).and_then(|idx| {
+                    let exex = &mut self.exex_handles[idx];
+                    let Some(paused) = exex.paused.take() else {
+                        return Err(ExExControlError::NotPaused(id))
+                    };
+
+                    debug!(target: "exex::manager", exex_id = %exex.id, "Resuming ExEx");
+                    exex.replay = Some(ReplayNotifications { missed: paused.missed });
+                    exex.next_notification_id = self.next_id;
+                    exex.metrics.paused.set(0.0);
+                    Ok(())
+                });
+                let _ = tx.send(res);
+            }
+            ExExManagerCommand::Remove(id, tx) => {
+                let res = self.exex_index(&id).map(|idx| {
+                    let exex = self.remove_exex(idx);
+                    debug!(target: "exex::manager", exex_id = %exex.id, "Removed ExEx");
+                });
+                let _ = tx.send(res);
+            }
+            ExExManagerCommand::List(tx) => {
+                let infos = self
+                    .exex_handles
+                    .iter()
+                    .map(|exex| ExExInfo {
+                        id: exex.id.clone(),
+                        status: exex.status(),
+                        finished_height: exex.finished_height,
+                        lag: exex.lag(self.highest_block),
+                    })
+                    .collect();
+                let _ = tx.send(infos);
+            }
+        }
+    }
+
+    /// Updates the per-`ExEx` metrics and detaches the `ExEx`'s that exceeded the maximum lag.
+    fn update_lag(&mut self) {
+        for idx in (0..self.exex_handles.len()).rev() {
+            let exex = &self.exex_handles[idx];
+            let lag = exex.lag(self.highest_block);
+            if let Some(finished_height) = exex.finished_height {
+                exex.metrics.finished_height.set(finished_height.number as f64);
+            }
+            if let Some(lag) = lag {
+                exex.metrics.lag_blocks.set(lag as f64);
+            }
+
+            if let Some((lag, max_lag)) = lag.zip(self.max_lag) {
+                if lag > max_lag {
+                    let exex = self.remove_exex(idx);
+                    self.metrics.detached_exexs_total.increment(1);
+                    warn!(
+                        target: "exex::manager",
+                        exex_id = %exex.id,
+                        %lag,
+                        %max_lag,
+                        "ExEx exceeded the maximum lag and was detached"
+                    );
+                }
+            }
+        }
+    }
+
     /// Updates the current buffer capacity and notifies all `is_ready` watchers of the manager's
     /// readiness to receive notifications.
     fn update_capacity(&self) {
@@ -441,17 +802,31 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
+        // Handle commands controlling the ExExes
+        while let Poll::Ready(Some(command)) = this.control_rx.poll_recv(cx) {
+            this.on_command(command);
+        }
+
         // Handle incoming ExEx events
         for exex in &mut this.exex_handles {
             while let Poll::Ready(Some(event)) = exex.receiver.poll_recv(cx) {
                 debug!(target: "exex::manager", exex_id = %exex.id, ?event, "Received event from ExEx");
                 exex.metrics.events_sent_total.increment(1);
                 match event {
-                    ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::FinishedHeight(height) => {
+                        exex.finished_height = Some(height);
+                        if let Some(checkpoint) = &mut exex.checkpoint {
+                            checkpoint.on_finished_height(height.number);
+                        }
+                    }
                 }
             }
+            exex.save_checkpoint(false);
         }
 
+        // Update the ExEx lag and detach the ExExes that are too far behind
+        this.update_lag();
+
         // Drain the finalized header stream and finalize the WAL with the last header
         let mut last_finalized_header = None;
         while let Poll::Ready(finalized_header) = this.finalized_header_stream.poll_next_unpin(cx) {
@@ -468,17 +843,31 @@ where
                     notification.committed_chain().map(|chain| chain.tip().number());
                 let reverted_tip = notification.reverted_chain().map(|chain| chain.tip().number());
                 debug!(target: "exex::manager", ?committed_tip, ?reverted_tip, "Received new notification");
+                if let Some(committed_tip) = committed_tip {
+                    this.highest_block = Some(committed_tip);
+                }
 
                 // Commit to WAL only notifications from blockchain tree. Pipeline notifications
                 // always contain only finalized blocks.
-                match source {
+                let wal_id = match source {
                     ExExNotificationSource::BlockchainTree => {
                         debug!(target: "exex::manager", ?committed_tip, ?reverted_tip, "Committing notification to WAL");
+                        let wal_id = this.wal.next_notification_id();
                         this.wal.commit(&notification)?;
+                        Some(wal_id)
                     }
                     ExExNotificationSource::Pipeline => {
                         debug!(target: "exex::manager", ?committed_tip, ?reverted_tip, "Notification was sent from pipeline, skipping WAL commit");
+                        None
                     }
+                };
+
+                // Paused ExExes keep track of the notification to deliver it once they're resumed
+                for paused in this.exex_handles.iter_mut().filter_map(|exex| exex.paused.as_mut()) {
+                    paused.missed.push_back(wal_id.map_or_else(
+                        || MissedNotification::Buffered(notification.clone()),
+                        MissedNotification::Wal,
+                    ));
                 }
 
                 this.push_notification(notification);
@@ -495,13 +884,28 @@ where
         for idx in (0..this.exex_handles.len()).rev() {
             let mut exex = this.exex_handles.swap_remove(idx);
 
+            // Paused ExExes don't hold notifications in the buffer, they keep track of the missed
+            // notifications themselves
+            if exex.paused.is_some() {
+                this.exex_handles.push(exex);
+                continue
+            }
+
+            // Notifications missed while the ExEx was paused are delivered first
+            let replayed = match exex.poll_replay(cx, &this.wal) {
+                Poll::Ready(Ok(())) => true,
+                // The channel was closed, which is irrecoverable for the manager
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => false,
+            };
+
             // It is a logic error for this to ever underflow since the manager manages the
             // notification IDs
             let notification_index = exex
                 .next_notification_id
                 .checked_sub(this.min_id)
                 .expect("exex expected notification ID outside the manager's range");
-            if let Some(notification) = this.buffer.get(notification_index) {
+            if let Some(notification) = this.buffer.get(notification_index).filter(|_| replayed) {
                 if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
                     // The channel was closed, which is irrecoverable for the manager
                     return Poll::Ready(Err(err.into()))
@@ -510,6 +914,10 @@ where
             min_id = min_id.min(exex.next_notification_id);
             this.exex_handles.push(exex);
         }
+        // If no ExEx is receiving notifications, the whole buffer can be cleared
+        if min_id == usize::MAX {
+            min_id = this.next_id;
+        }
 
         // Remove processed buffered notifications
         debug!(target: "exex::manager", %min_id, "Updating lowest notification id in buffer");
@@ -520,12 +928,17 @@ where
         this.update_capacity();
 
         // Update watch channel block number
-        let finished_height = this.exex_handles.iter_mut().try_fold(u64::MAX, |curr, exex| {
-            exex.finished_height.map_or(Err(()), |height| Ok(height.number.min(curr)))
-        });
-        if let Ok(finished_height) = finished_height {
-            let _ = this.finished_height.send(FinishedExExHeight::Height(finished_height));
-        }
+        let finished_height = if this.exex_handles.is_empty() {
+            FinishedExExHeight::NoExExs
+        } else {
+            this.exex_handles
+                .iter_mut()
+                .try_fold(u64::MAX, |curr, exex| {
+                    exex.finished_height.map_or(Err(()), |height| Ok(height.number.min(curr)))
+                })
+                .map_or(FinishedExExHeight::NotReady, FinishedExExHeight::Height)
+        };
+        let _ = this.finished_height.send(finished_height);
 
         Poll::Pending
     }
@@ -536,8 +949,10 @@ where
 pub struct ExExManagerHandle<N: NodePrimitives = EthPrimitives> {
     /// Channel to send notifications to the `ExEx` manager.
     exex_tx: UnboundedSender<(ExExNotificationSource, ExExNotification<N>)>,
+    /// Channel to send commands controlling the `ExEx`'s to the `ExEx` manager.
+    control_tx: UnboundedSender<ExExManagerCommand<N>>,
     /// The number of `ExEx`'s running on the node.
-    num_exexs: usize,
+    num_exexs: Arc<AtomicUsize>,
     /// A watch channel denoting whether the manager is ready for new notifications or not.
     ///
     /// This is stored internally alongside a `ReusableBoxFuture` representation of the same value.
@@ -561,12 +976,14 @@ impl<N: NodePrimitives> ExExManagerHandle<N> {
     /// The handle will always be ready, and have a capacity of 0.
     pub fn empty() -> Self {
         let (exex_tx, _) = mpsc::unbounded_channel();
+        let (control_tx, _) = mpsc::unbounded_channel();
         let (_, is_ready_rx) = watch::channel(true);
         let (_, finished_height_rx) = watch::channel(FinishedExExHeight::NoExExs);
 
         Self {
             exex_tx,
-            num_exexs: 0,
+            control_tx,
+            num_exexs: Arc::new(AtomicUsize::new(0)),
             is_ready_receiver: is_ready_rx.clone(),
             is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
             current_capacity: Arc::new(AtomicUsize::new(0)),
@@ -612,8 +1029,58 @@ impl<N: NodePrimitives> ExExManagerHandle<N> {
     }
 
     /// Returns `true` if there are `ExEx`'s installed in the node.
-    pub const fn has_exexs(&self) -> bool {
-        self.num_exexs > 0
+    pub fn has_exexs(&self) -> bool {
+        self.num_exexs.load(Ordering::Relaxed) > 0
+    }
+
+    /// Sends a command to the manager and waits for the response.
+    async fn control<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> ExExManagerCommand<N>,
+    ) -> Result<T, ExExControlError> {
+        let (tx, rx) = oneshot::channel();
+        self.control_tx.send(command(tx)).map_err(|_| ExExControlError::ManagerClosed)?;
+        rx.await.map_err(|_| ExExControlError::ManagerClosed)
+    }
+
+    /// Installs a new `ExEx` in the running manager.
+    ///
+    /// The `ExEx` receives all notifications sent to the manager after it was installed. The
+    /// `ExEx` behind the handle has to be launched separately, the `ExExRegistry` of the node
+    /// builder launches an `ExEx` and installs it at once.
+    pub async fn add_exex(&self, exex: ExExHandle<N>) -> Result<(), ExExControlError> {
+        self.control(|tx| ExExManagerCommand::Add(exex, tx)).await?
+    }
+
+    /// Pauses the `ExEx` with the given ID.
+    ///
+    /// Notifications sent to the manager while the `ExEx` is paused are delivered when it's
+    /// resumed. Notifications from the blockchain tree are read back from the WAL, while
+    /// notifications from the pipeline are not committed to the WAL and are buffered in memory
+    /// instead.
+    pub async fn pause_exex(&self, id: impl Into<String>) -> Result<(), ExExControlError> {
+        let id = id.into();
+        self.control(|tx| ExExManagerCommand::Pause(id, tx)).await?
+    }
+
+    /// Resumes the paused `ExEx` with the given ID.
+    pub async fn resume_exex(&self, id: impl Into<String>) -> Result<(), ExExControlError> {
+        let id = id.into();
+        self.control(|tx| ExExManagerCommand::Resume(id, tx)).await?
+    }
+
+    /// Removes the `ExEx` with the given ID from the manager.
+    ///
+    /// The token returned by [`ExExHandle::removed`] is cancelled, and the `ExEx` doesn't receive
+    /// any new notifications.
+    pub async fn remove_exex(&self, id: impl Into<String>) -> Result<(), ExExControlError> {
+        let id = id.into();
+        self.control(|tx| ExExManagerCommand::Remove(id, tx)).await?
+    }
+
+    /// Returns the information about all `ExEx`'s installed in the manager.
+    pub async fn exexs(&self) -> Result<Vec<ExExInfo>, ExExControlError> {
+        self.control(ExExManagerCommand::List).await
     }
 
     /// The finished height of all `ExEx`'s.
@@ -646,7 +1113,8 @@ impl<N: NodePrimitives> Clone for ExExManagerHandle<N> {
     fn clone(&self) -> Self {
         Self {
             exex_tx: self.exex_tx.clone(),
-            num_exexs: self.num_exexs,
+            control_tx: self.control_tx.clone(),
+            num_exexs: self.num_exexs.clone(),
             is_ready_receiver: self.is_ready_receiver.clone(),
             is_ready: ReusableBoxFuture::new(make_wait_future(self.is_ready_receiver.clone())),
             current_capacity: self.current_capacity.clone(),
@@ -1393,4 +1861,295 @@ mod tests {
 
         Ok(())
     }
+
+    /// Sends a command to the manager through the handle and polls the manager to process it.
+    fn control<P, T>(
+        exex_manager: &mut Pin<&mut ExExManager<P, EthPrimitives>>,
+        command: impl Future<Output = T>,
+    ) -> eyre::Result<T>
+    where
+        P: HeaderProvider + Unpin + 'static,
+    {
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        let mut command = std::pin::pin!(command);
+        assert!(command.as_mut().poll(&mut cx).is_pending());
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        match command.poll(&mut cx) {
+            Poll::Ready(res) => Ok(res),
+            Poll::Pending => eyre::bail!("command was not processed by the manager"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exex_pause_resume_remove() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory).unwrap();
+        let provider = BlockchainProvider::new(provider_factory).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (exex_handle, _events_tx, mut notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            provider.clone(),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+        let removed = exex_handle.removed();
+
+        let block_1 = random_block(
+            &mut rng,
+            1,
+            BlockParams { parent: Some(genesis_hash), ..Default::default() },
+        )
+        .try_recover()?;
+        let block_2 = random_block(
+            &mut rng,
+            2,
+            BlockParams { parent: Some(block_1.hash()), ..Default::default() },
+        )
+        .try_recover()?;
+        let block_3 = random_block(
+            &mut rng,
+            3,
+            BlockParams { parent: Some(block_2.hash()), ..Default::default() },
+        )
+        .try_recover()?;
+        let notification_1 = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block_1], Default::default(), None)),
+        };
+        let notification_2 = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block_2], Default::default(), None)),
+        };
+        let notification_3 = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block_3], Default::default(), None)),
+        };
+
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            provider,
+            vec![exex_handle],
+            10,
+            wal,
+            empty_finalized_header_stream()
+        ));
+        let handle = exex_manager.handle();
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        handle.send(ExExNotificationSource::BlockchainTree, notification_1.clone())?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert_eq!(notifications.try_poll_next_unpin(&mut cx)?, Poll::Ready(Some(notification_1)));
+
+        // Pause the ExEx, the new notifications are not delivered and not kept in the buffer
+        control(&mut exex_manager, handle.pause_exex("test_exex"))??;
+        assert!(matches!(
+            control(&mut exex_manager, handle.pause_exex("test_exex"))?,
+            Err(ExExControlError::AlreadyPaused(_))
+        ));
+        handle.send(ExExNotificationSource::BlockchainTree, notification_2.clone())?;
+        // The pipeline notification is not committed to the WAL
+        handle.send(ExExNotificationSource::Pipeline, notification_3.clone())?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(notifications.try_poll_next_unpin(&mut cx)?.is_pending());
+        assert!(exex_manager.buffer.is_empty());
+        assert_eq!(
+            control(&mut exex_manager, handle.exexs())??
+                .into_iter()
+                .map(|info| info.status)
+                .collect::<Vec<_>>(),
+            vec![ExExStatus::Paused]
+        );
+
+        // Resume the ExEx, the missed notifications are replayed from the WAL and from memory in
+        // the order they were sent
+        control(&mut exex_manager, handle.resume_exex("test_exex"))??;
+        assert_eq!(notifications.try_poll_next_unpin(&mut cx)?, Poll::Ready(Some(notification_2)));
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert_eq!(notifications.try_poll_next_unpin(&mut cx)?, Poll::Ready(Some(notification_3)));
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert_eq!(
+            control(&mut exex_manager, handle.exexs())??
+                .into_iter()
+                .map(|info| info.status)
+                .collect::<Vec<_>>(),
+            vec![ExExStatus::Running]
+        );
+
+        // Remove the ExEx
+        control(&mut exex_manager, handle.remove_exex("test_exex"))??;
+        assert!(removed.is_cancelled());
+        assert!(!handle.has_exexs());
+        assert_eq!(*handle.finished_height().borrow(), FinishedExExHeight::NoExExs);
+        assert!(matches!(
+            control(&mut exex_manager, handle.remove_exex("test_exex"))?,
+            Err(ExExControlError::NotFound(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_exex_backfill_checkpoint() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+
+        let (exex_handle, event_tx, _notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            (),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+        let exex_handle = exex_handle.with_backfill_checkpoint(provider_factory.clone());
+
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            provider_factory.clone(),
+            vec![exex_handle],
+            10,
+            wal,
+            empty_finalized_header_stream()
+        ));
+        let handle = exex_manager.handle();
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        // The first finished height is saved right away
+        event_tx.send(ExExEvent::FinishedHeight(BlockNumHash::new(1, B256::random())))?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert_eq!(provider_factory.backfill_checkpoint("test_exex")?, Some(1));
+
+        // The following finished heights are batched until the checkpoint interval elapses
+        event_tx.send(ExExEvent::FinishedHeight(BlockNumHash::new(2, B256::random())))?;
+        event_tx.send(ExExEvent::FinishedHeight(BlockNumHash::new(3, B256::random())))?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert_eq!(provider_factory.backfill_checkpoint("test_exex")?, Some(1));
+
+        // The last finished height is saved when the ExEx is removed
+        control(&mut exex_manager, handle.remove_exex("test_exex"))??;
+        assert_eq!(provider_factory.backfill_checkpoint("test_exex")?, Some(3));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_exex_detached_on_max_lag() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+
+        let (exex_handle, events_tx, _notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            (),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+        let removed = exex_handle.removed();
+
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            provider_factory,
+            vec![exex_handle],
+            10,
+            wal,
+            empty_finalized_header_stream()
+        )
+        .with_max_lag(5));
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        let mut block: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+        block.set_block_number(10);
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block], Default::default(), Default::default())),
+        };
+
+        // The ExEx is within the maximum lag
+        events_tx.send(ExExEvent::FinishedHeight(BlockNumHash::new(5, B256::random())))?;
+        exex_manager.handle().send(ExExNotificationSource::Pipeline, notification)?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(!removed.is_cancelled());
+
+        // The ExEx falls behind the maximum lag and is detached
+        events_tx.send(ExExEvent::FinishedHeight(BlockNumHash::new(4, B256::random())))?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(removed.is_cancelled());
+        assert!(exex_manager.exex_handles.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_exex_detached_on_max_lag_without_finished_height() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+
+        // Neither ExEx emits a `FinishedHeight` event, so their lag is counted from the node head
+        let (exex_handle, _events_tx, _notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            BlockNumHash::new(4, B256::random()),
+            (),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+        let removed = exex_handle.removed();
+        let (paused_exex_handle, _paused_events_tx, _paused_notifications) = ExExHandle::new(
+            "paused_exex".to_string(),
+            BlockNumHash::new(5, B256::random()),
+            (),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+        let paused_removed = paused_exex_handle.removed();
+
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            provider_factory,
+            vec![exex_handle, paused_exex_handle],
+            10,
+            wal,
+            empty_finalized_header_stream()
+        )
+        .with_max_lag(5));
+        let handle = exex_manager.handle();
+        control(&mut exex_manager, handle.pause_exex("paused_exex"))??;
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        let mut block: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+        block.set_block_number(10);
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block], Default::default(), Default::default())),
+        };
+
+        // The running ExEx falls behind the maximum lag and is detached, while the paused ExEx
+        // is within the maximum lag
+        handle.send(ExExNotificationSource::Pipeline, notification)?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(removed.is_cancelled());
+        assert!(!paused_removed.is_cancelled());
+
+        let mut block: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+        block.set_block_number(11);
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block], Default::default(), Default::default())),
+        };
+
+        // The paused ExEx falls behind the maximum lag and is detached as well
+        handle.send(ExExNotificationSource::Pipeline, notification)?;
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(exex_manager.as_mut().poll(&mut cx)?.is_pending());
+        assert!(paused_removed.is_cancelled());
+        assert!(exex_manager.exex_handles.is_empty());
+
+        Ok(())
+    }
 }
@@ -1,4 +1,6 @@
-use crate::{BackfillJobFactory, ExExNotification, StreamBackfillJob, WalHandle};
+use crate::{
+    BackfillCheckpointStore, BackfillJobFactory, ExExNotification, StreamBackfillJob, WalHandle,
+};
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
 use futures::{Stream, StreamExt};
@@ -6,7 +8,7 @@ use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::ConfigureEvm;
 use reth_exex_types::ExExHead;
 use reth_node_api::NodePrimitives;
-use reth_provider::{BlockReader, Chain, HeaderProvider, StateProviderFactory};
+use reth_provider::{BlockHashReader, BlockReader, Chain, HeaderProvider, StateProviderFactory};
 use reth_tracing::tracing::debug;
 use std::{
     fmt::Debug,
@@ -25,6 +27,8 @@ where
     E: ConfigureEvm,
 {
     inner: ExExNotificationsInner<P, E>,
+    /// ID of the backfill checkpoint of the `ExEx`, see [`ExExNotifications::backfill_checkpoint`].
+    backfill_checkpoint_id: Option<String>,
 }
 
 /// A trait, that represents a stream of [`ExExNotification`]s. The stream will emit notifications
@@ -98,8 +102,66 @@ where
                 notifications,
                 wal_handle,
             )),
+            backfill_checkpoint_id: None,
         }
     }
+
+    /// Sets the ID under which the backfill checkpoint of the `ExEx` is stored in the provider,
+    /// usually the `ExEx` ID.
+    ///
+    /// See [`ExExNotifications::backfill_checkpoint`] for more details.
+    pub fn with_backfill_checkpoint(mut self, id: impl Into<String>) -> Self {
+        self.backfill_checkpoint_id = Some(id.into());
+        self
+    }
+
+    /// Limits the number of blocks per second that are executed by the backfill job that catches
+    /// the `ExEx` up to the node head, so that it doesn't starve the node of resources.
+    ///
+    /// See [`StreamBackfillJob::with_max_blocks_per_second`] for more details.
+    pub fn with_backfill_max_blocks_per_second(mut self, max_blocks_per_second: u64) -> Self {
+        match &mut self.inner {
+            ExExNotificationsInner::WithoutHead(notifications) => {
+                notifications.backfill_max_blocks_per_second = Some(max_blocks_per_second);
+            }
+            ExExNotificationsInner::WithHead(notifications) => {
+                notifications.backfill_max_blocks_per_second = Some(max_blocks_per_second);
+            }
+            ExExNotificationsInner::Invalid => unreachable!(),
+        }
+        self
+    }
+
+    /// Returns the backfill checkpoint of the `ExEx`, which is the last block that the `ExEx`
+    /// reported as processed with [`ExExEvent::FinishedHeight`](crate::ExExEvent::FinishedHeight),
+    /// as persisted by the node.
+    ///
+    /// An `ExEx` that doesn't persist its own head can pass the checkpoint to
+    /// [`ExExNotificationsStream::set_with_head`] to resume from the next block after a restart,
+    /// instead of backfilling from its initial head again. The checkpoint is saved in batches, so
+    /// it may lag behind the last finished height and some blocks are delivered again.
+    ///
+    /// Returns `None` if no checkpoint ID is set with
+    /// [`ExExNotifications::with_backfill_checkpoint`], nothing was saved yet, or the checkpoint
+    /// block is not canonical anymore.
+    pub fn backfill_checkpoint(&self) -> eyre::Result<Option<ExExHead>>
+    where
+        P: BackfillCheckpointStore + BlockHashReader,
+    {
+        let Some(id) = &self.backfill_checkpoint_id else { return Ok(None) };
+        let provider = match &self.inner {
+            ExExNotificationsInner::WithoutHead(notifications) => &notifications.provider,
+            ExExNotificationsInner::WithHead(notifications) => &notifications.provider,
+            ExExNotificationsInner::Invalid => unreachable!(),
+        };
+
+        let Some(block_number) = provider.backfill_checkpoint(id)? else { return Ok(None) };
+        let Some(block_hash) = provider.block_hash(block_number)? else {
+            debug!(target: "exex::notifications", %id, %block_number, "Backfill checkpoint is not canonical");
+            return Ok(None)
+        };
+        Ok(Some(ExExHead::new((block_number, block_hash).into())))
+    }
 }
 
 impl<P, E> ExExNotificationsStream<E::Primitives> for ExExNotifications<P, E>
@@ -111,13 +173,16 @@ where
         let current = std::mem::replace(&mut self.inner, ExExNotificationsInner::Invalid);
         self.inner = ExExNotificationsInner::WithoutHead(match current {
             ExExNotificationsInner::WithoutHead(notifications) => notifications,
-            ExExNotificationsInner::WithHead(notifications) => ExExNotificationsWithoutHead::new(
-                notifications.initial_local_head,
-                notifications.provider,
-                notifications.evm_config,
-                notifications.notifications,
-                notifications.wal_handle,
-            ),
+            ExExNotificationsInner::WithHead(notifications) => ExExNotificationsWithoutHead {
+                backfill_max_blocks_per_second: notifications.backfill_max_blocks_per_second,
+                ..ExExNotificationsWithoutHead::new(
+                    notifications.node_head,
+                    notifications.provider,
+                    notifications.evm_config,
+                    notifications.notifications,
+                    notifications.wal_handle,
+                )
+            },
             ExExNotificationsInner::Invalid => unreachable!(),
         });
     }
@@ -130,12 +195,13 @@ where
             }
             ExExNotificationsInner::WithHead(notifications) => {
                 Box::new(ExExNotificationsWithHead::new(
-                    notifications.initial_local_head,
+                    notifications.node_head,
                     notifications.provider,
                     notifications.evm_config,
                     notifications.notifications,
                     notifications.wal_handle,
                     exex_head,
+                    notifications.backfill_max_blocks_per_second,
                 ))
             }
             ExExNotificationsInner::Invalid => unreachable!(),
@@ -179,11 +245,14 @@ pub struct ExExNotificationsWithoutHead<P, E>
 where
     E: ConfigureEvm,
 {
+    /// The node's head as of the last notification received from the node.
     node_head: BlockNumHash,
     provider: P,
     evm_config: E,
     notifications: Receiver<ExExNotification<E::Primitives>>,
     wal_handle: WalHandle<E::Primitives>,
+    /// The maximum number of blocks per second executed by the backfill job, if limited.
+    backfill_max_blocks_per_second: Option<u64>,
 }
 
 impl<P: Debug, E> Debug for ExExNotificationsWithoutHead<P, E>
@@ -211,7 +280,14 @@ where
         notifications: Receiver<ExExNotification<E::Primitives>>,
         wal_handle: WalHandle<E::Primitives>,
     ) -> Self {
-        Self { node_head, provider, evm_config, notifications, wal_handle }
+        Self {
+            node_head,
+            provider,
+            evm_config,
+            notifications,
+            wal_handle,
+            backfill_max_blocks_per_second: None,
+        }
     }
 
     /// Subscribe to notifications with the given head.
@@ -223,6 +299,7 @@ where
             self.notifications,
             self.wal_handle,
             head,
+            self.backfill_max_blocks_per_second,
         )
     }
 }
@@ -234,7 +311,12 @@ where
     type Item = ExExNotification<E::Primitives>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.get_mut().notifications.poll_recv(cx)
+        let this = self.get_mut();
+        let notification = ready!(this.notifications.poll_recv(cx));
+        if let Some(head) = notification.as_ref().and_then(notification_head) {
+            this.node_head = head;
+        }
+        Poll::Ready(notification)
     }
 }
 
@@ -253,6 +335,8 @@ where
 {
     /// The node's local head at launch.
     initial_local_head: BlockNumHash,
+    /// The node's head as of the last notification received from the node.
+    node_head: BlockNumHash,
     provider: P,
     evm_config: E,
     notifications: Receiver<ExExNotification<E::Primitives>>,
@@ -268,6 +352,8 @@ where
     pending_check_backfill: bool,
     /// The backfill job to run before consuming any notifications.
     backfill_job: Option<StreamBackfillJob<E, P, Chain<E::Primitives>>>,
+    /// The maximum number of blocks per second executed by the backfill job, if limited.
+    backfill_max_blocks_per_second: Option<u64>,
 }
 
 impl<P, E> ExExNotificationsWithHead<P, E>
@@ -282,9 +368,11 @@ where
         notifications: Receiver<ExExNotification<E::Primitives>>,
         wal_handle: WalHandle<E::Primitives>,
         exex_head: ExExHead,
+        backfill_max_blocks_per_second: Option<u64>,
     ) -> Self {
         Self {
             initial_local_head: node_head,
+            node_head,
             provider,
             evm_config,
             notifications,
@@ -293,6 +381,7 @@ where
             pending_check_canonical: true,
             pending_check_backfill: true,
             backfill_job: None,
+            backfill_max_blocks_per_second,
         }
     }
 }
@@ -359,8 +448,12 @@ where
     /// - ExEx is at the same block number as the node head (`node_head.number ==
     ///   exex_head.number`). Nothing to do.
     fn check_backfill(&mut self) -> eyre::Result<()> {
-        let backfill_job_factory =
+        let mut backfill_job_factory =
             BackfillJobFactory::new(self.evm_config.clone(), self.provider.clone());
+        if let Some(max_blocks_per_second) = self.backfill_max_blocks_per_second {
+            backfill_job_factory =
+                backfill_job_factory.with_stream_max_blocks_per_second(max_blocks_per_second);
+        }
         match self.initial_exex_head.block.number.cmp(&self.initial_local_head.number) {
             std::cmp::Ordering::Less => {
                 // ExEx is behind the node head, start backfill
@@ -429,6 +522,9 @@ where
             let Some(notification) = ready!(this.notifications.poll_recv(cx)) else {
                 return Poll::Ready(None)
             };
+            if let Some(head) = notification_head(&notification) {
+                this.node_head = head;
+            }
 
             // 5. In case the exex is ahead of the new tip, we must skip it
             if let Some(committed) = notification.committed_chain() {
@@ -443,6 +539,20 @@ where
     }
 }
 
+/// Returns the node head after the given notification, i.e. the tip of the committed chain, or
+/// the parent of the reverted chain if nothing was committed.
+fn notification_head<N: NodePrimitives>(
+    notification: &ExExNotification<N>,
+) -> Option<BlockNumHash> {
+    if let Some(committed) = notification.committed_chain() {
+        return Some(committed.tip().num_hash())
+    }
+    notification.reverted_chain().map(|reverted| {
+        let first = reverted.first();
+        BlockNumHash::new(first.number() - 1, first.parent_hash())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -539,6 +649,183 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn exex_notifications_backfill_checkpoint() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+        let genesis_block = provider_factory
+            .block(genesis_hash.into())?
+            .ok_or_else(|| eyre::eyre!("genesis block not found"))?;
+
+        let provider = BlockchainProvider::new(provider_factory.clone())?;
+
+        let node_head_block = random_block(
+            &mut rng,
+            genesis_block.number + 1,
+            BlockParams { parent: Some(genesis_hash), tx_count: Some(0), ..Default::default() },
+        );
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw
+            .insert_block(node_head_block.clone().try_recover()?, StorageLocation::Database)?;
+        provider_rw.commit()?;
+
+        let node_head = node_head_block.num_hash();
+        let exex_head =
+            ExExHead { block: BlockNumHash { number: genesis_block.number, hash: genesis_hash } };
+
+        let (_notifications_tx, notifications_rx) = mpsc::channel(1);
+
+        let mut notifications = ExExNotifications::new(
+            node_head,
+            provider.clone(),
+            EthEvmConfig::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        )
+        .with_backfill_checkpoint("exex");
+
+        // Nothing is saved yet
+        assert_eq!(notifications.backfill_checkpoint()?, None);
+
+        // The ExEx finished the node head block before the restart
+        provider_factory.save_backfill_checkpoint("exex", node_head.number)?;
+        assert_eq!(notifications.backfill_checkpoint()?, Some(ExExHead { block: node_head }));
+
+        // The checkpoint doesn't override the head requested by the ExEx, so the node head block
+        // is backfilled again
+        notifications.set_with_head(exex_head);
+        assert_eq!(
+            notifications.next().await.transpose()?,
+            Some(ExExNotification::ChainCommitted {
+                new: Arc::new(
+                    BackfillJobFactory::new(EthEvmConfig::mainnet(), provider)
+                        .backfill(1..=1)
+                        .next()
+                        .ok_or_eyre("failed to backfill")??
+                )
+            })
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exex_notifications_backfill_max_blocks_per_second() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+        let genesis_block = provider_factory
+            .block(genesis_hash.into())?
+            .ok_or_else(|| eyre::eyre!("genesis block not found"))?;
+
+        let provider = BlockchainProvider::new(provider_factory.clone())?;
+
+        let node_head_block = random_block(
+            &mut rng,
+            genesis_block.number + 1,
+            BlockParams { parent: Some(genesis_hash), tx_count: Some(0), ..Default::default() },
+        );
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw
+            .insert_block(node_head_block.clone().try_recover()?, StorageLocation::Database)?;
+        provider_rw.commit()?;
+
+        let node_head = node_head_block.num_hash();
+        let exex_head =
+            ExExHead { block: BlockNumHash { number: genesis_block.number, hash: genesis_hash } };
+
+        let (_notifications_tx, notifications_rx) = mpsc::channel(1);
+
+        let mut notifications = ExExNotifications::new(
+            node_head,
+            provider,
+            EthEvmConfig::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        )
+        .with_backfill_max_blocks_per_second(10)
+        .with_head(exex_head);
+
+        // The backfilled block is delivered and the backfill job is limited
+        let notification = notifications.next().await.transpose()?.ok_or_eyre("no notification")?;
+        assert_eq!(notification.committed_chain().map(|chain| chain.range()), Some(1..=1));
+        let ExExNotificationsInner::WithHead(notifications) = &notifications.inner else {
+            eyre::bail!("notifications are not configured with a head")
+        };
+        assert_eq!(
+            notifications.backfill_job.as_ref().and_then(|job| job.max_blocks_per_second()),
+            Some(10)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exex_notifications_resubscribe_from_current_node_head() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+        let genesis_block = provider_factory
+            .block(genesis_hash.into())?
+            .ok_or_else(|| eyre::eyre!("genesis block not found"))?;
+        let genesis = BlockNumHash { number: genesis_block.number, hash: genesis_hash };
+
+        let provider = BlockchainProvider::new(provider_factory.clone())?;
+
+        // The node was launched at genesis and committed a block afterwards
+        let block = random_block(
+            &mut rng,
+            genesis.number + 1,
+            BlockParams { parent: Some(genesis.hash), tx_count: Some(0), ..Default::default() },
+        );
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw.insert_block(block.clone().try_recover()?, StorageLocation::Database)?;
+        provider_rw.commit()?;
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(
+                BackfillJobFactory::new(EthEvmConfig::mainnet(), provider.clone())
+                    .backfill(1..=1)
+                    .next()
+                    .ok_or_eyre("failed to backfill")??,
+            ),
+        };
+
+        let (notifications_tx, notifications_rx) = mpsc::channel(1);
+        notifications_tx.send(notification.clone()).await?;
+
+        let mut notifications = ExExNotifications::new(
+            genesis,
+            provider,
+            EthEvmConfig::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        );
+        assert_eq!(notifications.next().await.transpose()?, Some(notification.clone()));
+
+        // A consumer that only processed genesis resubscribes and is backfilled up to the block
+        // committed after launch
+        notifications.set_with_head(ExExHead { block: genesis });
+        assert_eq!(notifications.next().await.transpose()?, Some(notification));
+
+        drop(notifications_tx);
+        assert_eq!(notifications.next().await.transpose()?, None);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn exex_notifications_same_head_canonical() -> eyre::Result<()> {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,653 @@
+//! Remote (out-of-process) `ExEx` transport.
+//!
+//! [`RemoteExEx`] is an `ExEx` that forwards its [`ExExNotification`]s to a single client connected
+//! over an IPC socket or TCP, using newline-delimited JSON. This allows `ExEx`s to be written in
+//! other languages and deployed separately from the node.
+//!
+//! # Protocol
+//!
+//! Every message is a single JSON object terminated by `\n`.
+//!
+//! 1. The client connects and sends a [`RemoteExExRequest::Subscribe`] message, optionally
+//!    containing the head it has already processed. If a head is provided, the notifications stream
+//!    is switched to [`ExExNotificationsWithHead`](crate::ExExNotificationsWithHead), which replays
+//!    missed notifications from the [`Wal`](crate::Wal) and backfills the rest.
+//! 2. The server streams [`RemoteExExResponse::Notification`] messages.
+//! 3. The client acknowledges processed blocks with [`RemoteExExRequest::FinishedHeight`], which is
+//!    forwarded to the node as [`ExExEvent::FinishedHeight`].
+//!
+//! At most [`RemoteExExConfig::max_unacked_notifications`] notifications are in flight at any
+//! time. Once the limit is reached, the server stops pulling notifications until the client
+//! acknowledges them, which makes the notifications back up in the
+//! [`ExExManager`](crate::ExExManager) and eventually applies backpressure to the node through
+//! [`ExExManagerHandle`](crate::ExExManagerHandle).
+//!
+//! Only one client is served at a time. When a client disconnects, the next client resumes from
+//! the head it provides in its subscription request, and is backfilled up to the current node
+//! head. If it subscribes without a head, the notifications that were sent to the previous client
+//! but not acknowledged are sent again first.
+//!
+//! The node launches the server as the `remote` `ExEx` when it's configured with
+//! `--exex.remote.ipc` or `--exex.remote.addr`, see [`RemoteExExConfig::from_args`].
+
+use crate::{ExExContext, ExExEvent, ExExNotification, ExExNotificationsStream};
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
+use futures::StreamExt;
+use reth_exex_types::{serde_bincode_compat, ExExHead};
+use reth_node_api::{FullNodeComponents, NodePrimitives, NodeTypes};
+use reth_node_core::args::{ExExArgs, DEFAULT_EXEX_REMOTE_MAX_UNACKED};
+use reth_provider::BlockReader;
+use reth_tracing::tracing::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::{collections::VecDeque, net::SocketAddr};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    sync::mpsc::UnboundedSender,
+};
+
+/// Default maximum number of notifications that can be sent to a remote client without being
+/// acknowledged.
+pub const DEFAULT_MAX_UNACKED_NOTIFICATIONS: usize = DEFAULT_EXEX_REMOTE_MAX_UNACKED;
+
+/// Endpoint the [`RemoteExEx`] server listens on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteExExEndpoint {
+    /// Unix domain socket at the given path.
+    #[cfg(unix)]
+    Ipc(std::path::PathBuf),
+    /// TCP socket at the given address.
+    Tcp(SocketAddr),
+}
+
+/// Configuration of the [`RemoteExEx`] server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteExExConfig {
+    /// Endpoint to listen on.
+    pub endpoint: RemoteExExEndpoint,
+    /// Maximum number of notifications that can be sent to the client without being acknowledged
+    /// with a [`RemoteExExRequest::FinishedHeight`].
+    pub max_unacked_notifications: usize,
+}
+
+impl RemoteExExConfig {
+    /// Creates a new configuration for the given endpoint with default limits.
+    pub const fn new(endpoint: RemoteExExEndpoint) -> Self {
+        Self { endpoint, max_unacked_notifications: DEFAULT_MAX_UNACKED_NOTIFICATIONS }
+    }
+
+    /// Sets the maximum number of unacknowledged notifications.
+    ///
+    /// It must be at least 1, otherwise no notification is ever sent.
+    pub const fn with_max_unacked_notifications(mut self, max: usize) -> Self {
+        self.max_unacked_notifications = max;
+        self
+    }
+
+    /// Creates the configuration from the `--exex.remote.*` CLI arguments.
+    ///
+    /// Returns `None` if no endpoint is configured.
+    pub fn from_args(args: &ExExArgs) -> eyre::Result<Option<Self>> {
+        let endpoint = match (&args.remote_ipc, args.remote_addr) {
+            #[cfg(unix)]
+            (Some(path), _) => RemoteExExEndpoint::Ipc(path.clone()),
+            #[cfg(not(unix))]
+            (Some(_), _) => eyre::bail!("remote ExEx IPC sockets are only supported on unix"),
+            (None, Some(addr)) => RemoteExExEndpoint::Tcp(addr),
+            (None, None) => return Ok(None),
+        };
+
+        Ok(Some(Self::new(endpoint).with_max_unacked_notifications(args.remote_max_unacked)))
+    }
+}
+
+/// Message sent by the remote client to the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RemoteExExRequest {
+    /// Subscribe to notifications. Must be the first message sent on a connection.
+    Subscribe {
+        /// The highest block the client has fully processed. If `None`, notifications are
+        /// delivered starting from the current node head.
+        head: Option<BlockNumHash>,
+    },
+    /// The client has processed all blocks up to and including the given block.
+    FinishedHeight {
+        /// Highest processed block.
+        block: BlockNumHash,
+    },
+}
+
+/// Message sent by the server to the remote client.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[serde(bound = "")]
+pub enum RemoteExExResponse<N: NodePrimitives> {
+    /// A new notification.
+    Notification {
+        /// The notification.
+        #[serde_as(as = "serde_bincode_compat::ExExNotification<'_, N>")]
+        notification: ExExNotification<N>,
+    },
+    /// The request could not be handled. The connection is closed after this message.
+    Error {
+        /// Error description.
+        message: String,
+    },
+}
+
+/// An `ExEx` that streams notifications to an out-of-process client.
+///
+/// See the [module level documentation](self) for the protocol description.
+///
+/// # Example
+///
+/// ```no_run
+/// # use reth_exex::{RemoteExEx, RemoteExExConfig, RemoteExExEndpoint};
+/// # use reth_node_api::FullNodeComponents;
+/// # use reth_exex::ExExContext;
+/// async fn remote_exex<Node: FullNodeComponents>(ctx: ExExContext<Node>) -> eyre::Result<()> {
+///     let config = RemoteExExConfig::new(RemoteExExEndpoint::Tcp("127.0.0.1:10000".parse()?));
+///     RemoteExEx::new(config).serve(ctx).await
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RemoteExEx {
+    config: RemoteExExConfig,
+}
+
+impl RemoteExEx {
+    /// Creates a new remote `ExEx` with the given configuration.
+    pub const fn new(config: RemoteExExConfig) -> Self {
+        Self { config }
+    }
+
+    /// Binds to the configured endpoint and serves remote clients one after another until the
+    /// notifications stream of the [`ExExContext`] ends.
+    pub async fn serve<Node>(self, ctx: ExExContext<Node>) -> eyre::Result<()>
+    where
+        Node: FullNodeComponents<Types: NodeTypes<Primitives: NodePrimitives>>,
+        Node::Provider: BlockReader,
+    {
+        self.serve_notifications(ctx.notifications, ctx.events).await
+    }
+
+    /// Binds to the configured endpoint and serves the given notifications stream to remote
+    /// clients one after another until it ends.
+    ///
+    /// Acknowledgements received from the clients are sent to `events`.
+    pub async fn serve_notifications<N, S>(
+        self,
+        mut notifications: S,
+        events: UnboundedSender<ExExEvent>,
+    ) -> eyre::Result<()>
+    where
+        N: NodePrimitives,
+        S: ExExNotificationsStream<N>,
+    {
+        // Without a single notification in flight, the server would wait for an acknowledgement
+        // that never comes
+        eyre::ensure!(
+            self.config.max_unacked_notifications > 0,
+            "maximum number of unacknowledged remote ExEx notifications must be at least 1"
+        );
+
+        // Notifications sent to a client but not acknowledged yet, kept across connections
+        let mut unacked = VecDeque::new();
+
+        match &self.config.endpoint {
+            #[cfg(unix)]
+            RemoteExExEndpoint::Ipc(path) => {
+                use std::os::unix::fs::FileTypeExt;
+
+                // Remove a stale socket left behind by a previous run, anything else is left alone
+                if let Ok(metadata) = std::fs::symlink_metadata(path) {
+                    eyre::ensure!(
+                        metadata.file_type().is_socket(),
+                        "remote ExEx IPC path {path:?} exists and is not a socket"
+                    );
+                    reth_fs_util::remove_file(path)?;
+                }
+                let listener = tokio::net::UnixListener::bind(path)?;
+                info!(target: "exex::remote", path = %path.display(), "Remote ExEx listening");
+                loop {
+                    let (stream, _) = listener.accept().await?;
+                    if self
+                        .handle_connection(&mut notifications, &events, &mut unacked, stream)
+                        .await?
+                    {
+                        return Ok(())
+                    }
+                }
+            }
+            RemoteExExEndpoint::Tcp(addr) => {
+                let listener = tokio::net::TcpListener::bind(addr).await?;
+                info!(target: "exex::remote", %addr, "Remote ExEx listening");
+                loop {
+                    let (stream, _) = listener.accept().await?;
+                    if self
+                        .handle_connection(&mut notifications, &events, &mut unacked, stream)
+                        .await?
+                    {
+                        return Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Serves a single connection and logs the outcome.
+    ///
+    /// Returns `true` if the notifications stream has ended and the server should shut down.
+    async fn handle_connection<N, S, C>(
+        &self,
+        notifications: &mut S,
+        events: &UnboundedSender<ExExEvent>,
+        unacked: &mut Unacked<N>,
+        stream: C,
+    ) -> eyre::Result<bool>
+    where
+        N: NodePrimitives,
+        S: ExExNotificationsStream<N>,
+        C: AsyncRead + AsyncWrite + Unpin,
+    {
+        info!(target: "exex::remote", "Remote ExEx client connected");
+        match serve_connection(
+            notifications,
+            events,
+            unacked,
+            stream,
+            self.config.max_unacked_notifications,
+        )
+        .await
+        {
+            Ok(ConnectionOutcome::Disconnected) => {
+                info!(target: "exex::remote", "Remote ExEx client disconnected");
+                Ok(false)
+            }
+            Ok(ConnectionOutcome::NotificationsEnded) => Ok(true),
+            Err(ConnectionError::Io(err)) => {
+                warn!(target: "exex::remote", %err, "Remote ExEx connection failed");
+                Ok(false)
+            }
+            Err(ConnectionError::Notifications(err)) => Err(err),
+        }
+    }
+}
+
+/// Notifications with a committed chain that were sent to a client but not acknowledged yet,
+/// together with the tips of their committed chains.
+type Unacked<N> = VecDeque<(BlockNumber, ExExNotification<N>)>;
+
+/// How a connection was terminated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConnectionOutcome {
+    /// The client disconnected or sent an invalid message.
+    Disconnected,
+    /// The notifications stream of the node has ended.
+    NotificationsEnded,
+}
+
+/// Errors that can terminate a connection.
+#[derive(Debug)]
+enum ConnectionError {
+    /// The connection to the client failed. The server keeps accepting new clients.
+    Io(std::io::Error),
+    /// The notifications stream returned an error. The server is shut down.
+    Notifications(eyre::Report),
+}
+
+impl From<std::io::Error> for ConnectionError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Serves notifications to a single client until it disconnects.
+///
+/// The `unacked` notifications are left over from previous clients. They are sent again first if
+/// the client subscribes without a head.
+async fn serve_connection<N, S, C>(
+    notifications: &mut S,
+    events: &UnboundedSender<ExExEvent>,
+    unacked: &mut Unacked<N>,
+    stream: C,
+    max_unacked_notifications: usize,
+) -> Result<ConnectionOutcome, ConnectionError>
+where
+    N: NodePrimitives,
+    S: ExExNotificationsStream<N>,
+    C: AsyncRead + AsyncWrite + Unpin,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+
+    // The first message must be a subscription request
+    let Some(line) = lines.next_line().await? else { return Ok(ConnectionOutcome::Disconnected) };
+    match serde_json::from_str(&line) {
+        Ok(RemoteExExRequest::Subscribe { head: Some(head) }) => {
+            debug!(target: "exex::remote", ?head, "Remote ExEx client subscribed with head");
+            // Everything after the head is backfilled up to the current node head, including the
+            // unacknowledged notifications
+            unacked.clear();
+            notifications.set_with_head(ExExHead::new(head));
+        }
+        Ok(RemoteExExRequest::Subscribe { head: None }) => {
+            debug!(target: "exex::remote", unacked = unacked.len(), "Remote ExEx client subscribed without head");
+            notifications.set_without_head();
+            for (_, notification) in unacked.iter() {
+                write_message(
+                    &mut writer,
+                    &RemoteExExResponse::Notification { notification: notification.clone() },
+                )
+                .await?;
+            }
+        }
+        Ok(request) => {
+            let message = format!("expected subscribe request, got {request:?}");
+            write_message(&mut writer, &RemoteExExResponse::<N>::Error { message }).await?;
+            return Ok(ConnectionOutcome::Disconnected)
+        }
+        Err(err) => {
+            let message = format!("invalid request: {err}");
+            write_message(&mut writer, &RemoteExExResponse::<N>::Error { message }).await?;
+            return Ok(ConnectionOutcome::Disconnected)
+        }
+    }
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let Some(line) = line? else { return Ok(ConnectionOutcome::Disconnected) };
+                match serde_json::from_str(&line) {
+                    Ok(RemoteExExRequest::FinishedHeight { block }) => {
+                        while unacked.front().is_some_and(|(tip, _)| *tip <= block.number) {
+                            unacked.pop_front();
+                        }
+                        if events.send(ExExEvent::FinishedHeight(block)).is_err() {
+                            return Err(ConnectionError::Notifications(eyre::eyre!(
+                                "ExEx manager events channel closed"
+                            )))
+                        }
+                    }
+                    Ok(request) => {
+                        let message = format!("unexpected request {request:?}");
+                        write_message(&mut writer, &RemoteExExResponse::<N>::Error { message })
+                            .await?;
+                        return Ok(ConnectionOutcome::Disconnected)
+                    }
+                    Err(err) => {
+                        let message = format!("invalid request: {err}");
+                        write_message(&mut writer, &RemoteExExResponse::<N>::Error { message })
+                            .await?;
+                        return Ok(ConnectionOutcome::Disconnected)
+                    }
+                }
+            }
+            notification = notifications.next(), if unacked.len() < max_unacked_notifications => {
+                let Some(notification) = notification else {
+                    return Ok(ConnectionOutcome::NotificationsEnded)
+                };
+                let notification = notification.map_err(ConnectionError::Notifications)?;
+
+                if let Some(committed) = notification.committed_chain() {
+                    unacked.push_back((committed.tip().number(), notification.clone()));
+                }
+                write_message(&mut writer, &RemoteExExResponse::Notification { notification })
+                    .await?;
+            }
+        }
+    }
+}
+
+/// Writes a single newline-delimited JSON message and flushes the writer.
+async fn write_message<W, T>(writer: &mut W, message: &T) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut buf = serde_json::to_vec(message)?;
+    buf.push(b'\n');
+    writer.write_all(&buf).await?;
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use futures::Stream;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+    use std::{
+        pin::Pin,
+        sync::Arc,
+        task::{Context, Poll},
+    };
+    use tokio::sync::mpsc;
+
+    /// Notifications stream backed by a channel that records the head it was configured with.
+    struct TestNotifications {
+        rx: mpsc::UnboundedReceiver<ExExNotification>,
+        head: Arc<parking_lot::Mutex<Option<ExExHead>>>,
+    }
+
+    impl Stream for TestNotifications {
+        type Item = eyre::Result<ExExNotification>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.rx.poll_recv(cx).map(|notification| notification.map(Ok))
+        }
+    }
+
+    impl ExExNotificationsStream for TestNotifications {
+        fn set_without_head(&mut self) {
+            *self.head.lock() = None;
+        }
+
+        fn set_with_head(&mut self, exex_head: ExExHead) {
+            *self.head.lock() = Some(exex_head);
+        }
+
+        fn without_head(mut self) -> Self {
+            self.set_without_head();
+            self
+        }
+
+        fn with_head(mut self, exex_head: ExExHead) -> Self {
+            self.set_with_head(exex_head);
+            self
+        }
+    }
+
+    async fn write_request<W: AsyncWrite + Unpin>(
+        writer: &mut W,
+        request: RemoteExExRequest,
+    ) -> eyre::Result<()> {
+        Ok(write_message(writer, &request).await?)
+    }
+
+    #[tokio::test]
+    async fn test_remote_exex_delivers_notifications_and_acks() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let (notifications_tx, notifications_rx) = mpsc::unbounded_channel();
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let head = Arc::new(parking_lot::Mutex::new(None));
+        let mut notifications = TestNotifications { rx: notifications_rx, head: Arc::clone(&head) };
+
+        let block = random_block(&mut rng, 1, BlockParams::default()).try_recover()?;
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block.clone()], Default::default(), None)),
+        };
+        notifications_tx.send(notification.clone())?;
+
+        let (client, server) = tokio::io::duplex(1024 * 1024);
+        let server = tokio::spawn(async move {
+            serve_connection(&mut notifications, &events_tx, &mut Default::default(), server, 1)
+                .await
+        });
+
+        let (reader, mut writer) = tokio::io::split(client);
+        let mut lines = BufReader::new(reader).lines();
+
+        // Subscribe with a head
+        let client_head = BlockNumHash::new(0, B256::random());
+        write_request(&mut writer, RemoteExExRequest::Subscribe { head: Some(client_head) })
+            .await?;
+
+        // Receive the notification
+        let line = lines.next_line().await?.unwrap();
+        let response: RemoteExExResponse<EthPrimitives> = serde_json::from_str(&line)?;
+        assert_eq!(response, RemoteExExResponse::Notification { notification });
+        assert_eq!(*head.lock(), Some(ExExHead::new(client_head)));
+
+        // Acknowledge it and make sure the event is forwarded to the node
+        write_request(&mut writer, RemoteExExRequest::FinishedHeight { block: block.num_hash() })
+            .await?;
+        assert_eq!(events_rx.recv().await, Some(ExExEvent::FinishedHeight(block.num_hash())));
+
+        // Close the connection
+        drop(writer);
+        drop(lines);
+        assert!(matches!(server.await?, Ok(ConnectionOutcome::Disconnected)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remote_exex_resends_unacked_notifications_on_reconnect() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let (notifications_tx, notifications_rx) = mpsc::unbounded_channel();
+        let (events_tx, _events_rx) = mpsc::unbounded_channel();
+        let head = Arc::new(parking_lot::Mutex::new(None));
+        let mut notifications = TestNotifications { rx: notifications_rx, head: Arc::clone(&head) };
+        let mut unacked = Unacked::default();
+
+        let block = random_block(&mut rng, 1, BlockParams::default()).try_recover()?;
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block], Default::default(), None)),
+        };
+        notifications_tx.send(notification.clone())?;
+
+        // The notification is sent to every client that disconnects without acknowledging it
+        for _ in 0..2 {
+            let (client, server) = tokio::io::duplex(1024 * 1024);
+            let (reader, mut writer) = tokio::io::split(client);
+            write_request(&mut writer, RemoteExExRequest::Subscribe { head: None }).await?;
+
+            let client = async move {
+                let mut lines = BufReader::new(reader).lines();
+                let line = lines.next_line().await?.unwrap();
+                drop(lines);
+                drop(writer);
+                eyre::Ok(serde_json::from_str::<RemoteExExResponse<EthPrimitives>>(&line)?)
+            };
+            let (outcome, response) = tokio::join!(
+                serve_connection(&mut notifications, &events_tx, &mut unacked, server, 1),
+                client
+            );
+            assert!(matches!(outcome, Ok(ConnectionOutcome::Disconnected)));
+            assert_eq!(
+                response?,
+                RemoteExExResponse::Notification { notification: notification.clone() }
+            );
+        }
+        assert_eq!(unacked.len(), 1);
+
+        // A client with a head is backfilled by the notifications stream instead
+        let (client, server) = tokio::io::duplex(1024);
+        let (reader, mut writer) = tokio::io::split(client);
+        let client_head = BlockNumHash::new(0, B256::random());
+        write_request(&mut writer, RemoteExExRequest::Subscribe { head: Some(client_head) })
+            .await?;
+        drop((reader, writer));
+        let outcome =
+            serve_connection(&mut notifications, &events_tx, &mut unacked, server, 1).await;
+        assert!(matches!(outcome, Ok(ConnectionOutcome::Disconnected)));
+        assert_eq!(*head.lock(), Some(ExExHead::new(client_head)));
+        assert!(unacked.is_empty());
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_remote_exex_keeps_existing_file_at_ipc_path() {
+        let (_notifications_tx, notifications_rx) = mpsc::unbounded_channel();
+        let (events_tx, _events_rx) = mpsc::unbounded_channel();
+        let notifications = TestNotifications { rx: notifications_rx, head: Default::default() };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("exex.ipc");
+        std::fs::write(&path, b"not a socket").unwrap();
+
+        let config = RemoteExExConfig::new(RemoteExExEndpoint::Ipc(path.clone()));
+        let result = RemoteExEx::new(config).serve_notifications(notifications, events_tx).await;
+        assert!(result.is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"not a socket");
+    }
+
+    #[tokio::test]
+    async fn test_remote_exex_rejects_zero_max_unacked() {
+        let (_notifications_tx, notifications_rx) = mpsc::unbounded_channel();
+        let (events_tx, _events_rx) = mpsc::unbounded_channel();
+        let notifications = TestNotifications { rx: notifications_rx, head: Default::default() };
+
+        let config = RemoteExExConfig::new(RemoteExExEndpoint::Tcp(([127, 0, 0, 1], 0).into()))
+            .with_max_unacked_notifications(0);
+        let result = RemoteExEx::new(config).serve_notifications(notifications, events_tx).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_remote_exex_config_from_args() -> eyre::Result<()> {
+        assert_eq!(RemoteExExConfig::from_args(&ExExArgs::default())?, None);
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], 10000));
+        let args =
+            ExExArgs { remote_addr: Some(addr), remote_max_unacked: 1, ..Default::default() };
+        assert_eq!(
+            RemoteExExConfig::from_args(&args)?,
+            Some(
+                RemoteExExConfig::new(RemoteExExEndpoint::Tcp(addr))
+                    .with_max_unacked_notifications(1)
+            )
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remote_exex_rejects_missing_subscription() -> eyre::Result<()> {
+        let (_notifications_tx, notifications_rx) = mpsc::unbounded_channel();
+        let (events_tx, _events_rx) = mpsc::unbounded_channel();
+        let mut notifications =
+            TestNotifications { rx: notifications_rx, head: Default::default() };
+
+        let (client, server) = tokio::io::duplex(1024);
+        let (reader, mut writer) = tokio::io::split(client);
+
+        write_request(
+            &mut writer,
+            RemoteExExRequest::FinishedHeight { block: BlockNumHash::default() },
+        )
+        .await?;
+
+        let outcome =
+            serve_connection(&mut notifications, &events_tx, &mut Default::default(), server, 1)
+                .await;
+        assert!(matches!(outcome, Ok(ConnectionOutcome::Disconnected)));
+
+        let line = BufReader::new(reader).lines().next_line().await?.unwrap();
+        let response: RemoteExExResponse<EthPrimitives> = serde_json::from_str(&line)?;
+        assert!(matches!(response, RemoteExExResponse::Error { .. }));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+//! Admin RPC for controlling the `ExEx`'s of a running node.
+
+use crate::{ExExControlError, ExExInfo, ExExManagerHandle};
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::{
+        error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+        ErrorObjectOwned,
+    },
+};
+use reth_node_api::NodePrimitives;
+
+/// `ExEx` namespace rpc interface that gives access to the `ExEx`'s installed in the node.
+#[rpc(server, namespace = "exex")]
+pub trait ExExAdminApi {
+    /// Returns the information about all installed `ExEx`'s.
+    #[method(name = "list")]
+    async fn list(&self) -> RpcResult<Vec<ExExInfo>>;
+
+    /// Pauses the `ExEx` with the given ID.
+    ///
+    /// Notifications sent to the node while the `ExEx` is paused are delivered when it's resumed.
+    #[method(name = "pause")]
+    async fn pause(&self, id: String) -> RpcResult<()>;
+
+    /// Resumes the paused `ExEx` with the given ID.
+    #[method(name = "resume")]
+    async fn resume(&self, id: String) -> RpcResult<()>;
+
+    /// Removes the `ExEx` with the given ID from the node.
+    #[method(name = "remove")]
+    async fn remove(&self, id: String) -> RpcResult<()>;
+}
+
+/// Implementation of the [`ExExAdminApiServer`] backed by an [`ExExManagerHandle`].
+///
+/// The node builder serves it under the `exex` namespace on the IPC and the authenticated servers
+/// if the node has `ExEx`'s installed. It's never served over HTTP or WS.
+#[derive(Debug, Clone)]
+pub struct ExExAdminRpc<N: NodePrimitives> {
+    handle: ExExManagerHandle<N>,
+}
+
+impl<N: NodePrimitives> ExExAdminRpc<N> {
+    /// Creates a new instance of the `ExEx` admin RPC.
+    pub const fn new(handle: ExExManagerHandle<N>) -> Self {
+        Self { handle }
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: NodePrimitives> ExExAdminApiServer for ExExAdminRpc<N> {
+    async fn list(&self) -> RpcResult<Vec<ExExInfo>> {
+        self.handle.exexs().await.map_err(into_rpc_err)
+    }
+
+    async fn pause(&self, id: String) -> RpcResult<()> {
+        self.handle.pause_exex(id).await.map_err(into_rpc_err)
+    }
+
+    async fn resume(&self, id: String) -> RpcResult<()> {
+        self.handle.resume_exex(id).await.map_err(into_rpc_err)
+    }
+
+    async fn remove(&self, id: String) -> RpcResult<()> {
+        self.handle.remove_exex(id).await.map_err(into_rpc_err)
+    }
+}
+
+/// Converts the [`ExExControlError`] into an RPC error.
+fn into_rpc_err(err: ExExControlError) -> ErrorObjectOwned {
+    let code = match err {
+        ExExControlError::ManagerClosed => INTERNAL_ERROR_CODE,
+        _ => INVALID_PARAMS_CODE,
+    };
+    ErrorObjectOwned::owned(code, err.to_string(), None::<()>)
+}
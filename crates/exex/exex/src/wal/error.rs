@@ -26,4 +26,28 @@ pub enum WalError {
     /// Decode error
     #[error("failed to decode notification {0} from {1}: {2}")]
     Decode(u32, PathBuf, rmp_serde::decode::Error),
+    /// Encode error
+    #[error("failed to encode notification: {0}")]
+    Encode(rmp_serde::encode::Error),
+    /// Compression error
+    #[error("failed to compress notification: {0}")]
+    Compression(std::io::Error),
+    /// Notification is too large to be stored in a segment
+    #[error("notification {0} is too large: {1} bytes")]
+    NotificationTooLarge(u32, usize),
+    /// Segment read or write error
+    #[error("failed to access segment {0}: {1}")]
+    SegmentIo(PathBuf, std::io::Error),
+    /// Segment contains a corrupted record that is not at the end of the WAL
+    #[error("segment {0} is corrupted at offset {1}")]
+    CorruptedSegment(PathBuf, u64),
+    /// Record payload doesn't match its checksum
+    #[error("notification {0} in segment {1} doesn't match its checksum")]
+    ChecksumMismatch(u32, PathBuf),
+    /// WAL directory contains files in the legacy format that can't be migrated
+    #[error("WAL {0} contains legacy files that have to be migrated by the node first")]
+    LegacyFiles(PathBuf),
+    /// Write to a WAL that was opened read-only
+    #[error("WAL is opened read-only")]
+    ReadOnly,
 }
@@ -5,7 +5,7 @@ pub use cache::BlockCache;
 mod storage;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_node_api::NodePrimitives;
-pub use storage::Storage;
+pub use storage::{Storage, WalConfig, WalSegment, WalSyncPolicy, DEFAULT_WAL_MAX_SEGMENT_SIZE};
 mod metrics;
 use metrics::Metrics;
 mod error;
@@ -20,14 +20,14 @@ use std::{
 };
 
 use alloy_eips::BlockNumHash;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use parking_lot::{RwLock, RwLockReadGuard};
 use reth_exex_types::ExExNotification;
 use reth_tracing::tracing::{debug, instrument};
 
 /// WAL is a write-ahead log (WAL) that stores the notifications sent to ExExes.
 ///
-/// WAL is backed by a directory of binary files represented by [`Storage`] and a block cache
+/// WAL is backed by a directory of segment files represented by [`Storage`] and a block cache
 /// represented by [`BlockCache`]. The role of the block cache is to avoid walking the WAL directory
 /// and decoding notifications every time we want to iterate or finalize the WAL.
 ///
@@ -44,9 +44,23 @@ impl<N> Wal<N>
 where
     N: NodePrimitives,
 {
-    /// Creates a new instance of [`Wal`].
+    /// Creates a new instance of [`Wal`] with the default [`WalConfig`].
     pub fn new(directory: impl AsRef<Path>) -> WalResult<Self> {
-        Ok(Self { inner: Arc::new(WalInner::new(directory)?) })
+        Self::with_config(directory, WalConfig::default())
+    }
+
+    /// Creates a new instance of [`Wal`] with the given storage configuration.
+    pub fn with_config(directory: impl AsRef<Path>, config: WalConfig) -> WalResult<Self> {
+        Ok(Self { inner: Arc::new(WalInner::new(Storage::new(directory, config)?)?) })
+    }
+
+    /// Opens the existing WAL without modifying it, e.g. for inspection while the node is not
+    /// running.
+    ///
+    /// A partially written notification at the end of the WAL is ignored instead of truncated,
+    /// and all methods that write to the WAL fail with [`WalError::ReadOnly`].
+    pub fn open_read_only(directory: impl AsRef<Path>) -> WalResult<Self> {
+        Ok(Self { inner: Arc::new(WalInner::new(Storage::read_only(directory)?)?) })
     }
 
     /// Returns a read-only handle to the WAL.
@@ -64,6 +78,15 @@ where
     /// The caller should check that all ExExes are on the canonical chain and will not need any
     /// blocks from the WAL below the provided block, inclusive.
     pub fn finalize(&self, to_block: BlockNumHash) -> WalResult<()> {
+        self.inner.finalize(to_block.number)
+    }
+
+    /// Removes all notifications with the highest block less than or equal to the given block
+    /// number.
+    ///
+    /// Unlike [`Self::finalize`], the block is not required to be canonical. This is intended for
+    /// manual management of the WAL, when the node is not running.
+    pub fn truncate(&self, to_block: BlockNumber) -> WalResult<()> {
         self.inner.finalize(to_block)
     }
 
@@ -78,6 +101,24 @@ where
     pub fn num_blocks(&self) -> usize {
         self.inner.block_cache().num_blocks()
     }
+
+    /// Returns information about all segments of the WAL storage.
+    pub fn segments(&self) -> Vec<WalSegment> {
+        self.inner.storage.segments()
+    }
+
+    /// Returns the ID that will be assigned to the next committed notification.
+    pub(crate) fn next_notification_id(&self) -> u32 {
+        self.inner.next_file_id.load(Ordering::Relaxed)
+    }
+
+    /// Reads the notification with the given ID, if it wasn't finalized yet.
+    pub(crate) fn read_notification(&self, id: u32) -> WalResult<Option<ExExNotification<N>>> {
+        self.inner
+            .storage
+            .read_notification(id)
+            .map(|entry| entry.map(|(notification, _)| notification))
+    }
 }
 
 /// Inner type for the WAL.
@@ -95,10 +136,10 @@ impl<N> WalInner<N>
 where
     N: NodePrimitives,
 {
-    fn new(directory: impl AsRef<Path>) -> WalResult<Self> {
+    fn new(storage: Storage<N>) -> WalResult<Self> {
         let wal = Self {
             next_file_id: AtomicU32::new(0),
-            storage: Storage::new(directory)?,
+            storage,
             block_cache: RwLock::new(BlockCache::default()),
             metrics: Metrics::default(),
         };
@@ -162,9 +203,9 @@ where
     }
 
     #[instrument(skip(self))]
-    fn finalize(&self, to_block: BlockNumHash) -> WalResult<()> {
+    fn finalize(&self, to_block: BlockNumber) -> WalResult<()> {
         let mut block_cache = self.block_cache.write();
-        let file_ids = block_cache.remove_before(to_block.number);
+        let file_ids = block_cache.remove_before(to_block);
 
         // Remove notifications from the storage.
         if file_ids.is_empty() {
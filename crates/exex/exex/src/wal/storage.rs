@@ -1,26 +1,199 @@
 use std::{
-    fs::File,
+    collections::{BTreeMap, HashSet},
+    fs::{File, OpenOptions},
+    io::{BufReader, Read, Seek, SeekFrom, Write},
     ops::RangeInclusive,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use crate::wal::{WalError, WalResult};
+use parking_lot::Mutex;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_exex_types::ExExNotification;
 use reth_node_api::NodePrimitives;
+use reth_node_core::args::{ExExArgs, ExExWalSyncPolicy, DEFAULT_EXEX_WAL_MAX_SEGMENT_SIZE};
 use reth_tracing::tracing::debug;
 use tracing::instrument;
 
-static FILE_EXTENSION: &str = "wal";
+/// Extension of the legacy WAL files that contain a single notification each.
+static LEGACY_FILE_EXTENSION: &str = "wal";
+/// Extension of the segment files.
+static SEGMENT_FILE_EXTENSION: &str = "seg";
+/// Extension of the files that list the removed records of a segment that wasn't compacted yet.
+static REMOVED_FILE_EXTENSION: &str = "del";
 
-/// The underlying WAL storage backed by a directory of files.
+/// Size of the record header: notification ID (4 bytes), flags (1 byte), payload length (4 bytes),
+/// CRC32 checksum of the payload (4 bytes).
+const RECORD_HEADER_SIZE: u64 = 13;
+/// Record flag that indicates that the payload is compressed with zstd.
+const RECORD_FLAG_ZSTD: u8 = 0b1;
+/// Size of a removed record entry: notification ID (4 bytes), CRC32 checksum of the payload
+/// (4 bytes).
+const REMOVED_ENTRY_SIZE: usize = 8;
+/// Percentage of removed bytes in a sealed segment after which it's compacted.
+const COMPACTION_THRESHOLD_PERCENT: u64 = 50;
+
+/// Default maximum size of a single segment file in bytes.
+pub const DEFAULT_WAL_MAX_SEGMENT_SIZE: u64 = DEFAULT_EXEX_WAL_MAX_SEGMENT_SIZE;
+
+/// Policy that determines when the WAL segments are flushed to disk with `fsync`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WalSyncPolicy {
+    /// Sync the segment after every written notification.
+    #[default]
+    Always,
+    /// Sync the segment only when it's sealed, i.e. when a new segment is started, or when it's
+    /// rewritten during compaction.
+    OnSegmentSeal,
+    /// Never sync explicitly and leave it to the operating system.
+    Never,
+}
+
+/// Configuration of the WAL [`Storage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalConfig {
+    /// Maximum size of a single segment file in bytes. A notification that is larger than this
+    /// limit is written to its own segment.
+    pub max_segment_size: u64,
+    /// Zstd compression level of the notifications. `None` disables compression.
+    pub compression_level: Option<i32>,
+    /// When the segments are flushed to disk.
+    pub sync_policy: WalSyncPolicy,
+}
+
+impl Default for WalConfig {
+    fn default() -> Self {
+        Self {
+            max_segment_size: DEFAULT_WAL_MAX_SEGMENT_SIZE,
+            compression_level: None,
+            sync_policy: WalSyncPolicy::default(),
+        }
+    }
+}
+
+impl From<ExExWalSyncPolicy> for WalSyncPolicy {
+    fn from(policy: ExExWalSyncPolicy) -> Self {
+        match policy {
+            ExExWalSyncPolicy::Always => Self::Always,
+            ExExWalSyncPolicy::OnSegmentSeal => Self::OnSegmentSeal,
+            ExExWalSyncPolicy::Never => Self::Never,
+        }
+    }
+}
+
+impl From<&ExExArgs> for WalConfig {
+    fn from(args: &ExExArgs) -> Self {
+        Self {
+            max_segment_size: args.wal_max_segment_size,
+            compression_level: args.wal_compression_level,
+            sync_policy: args.wal_sync_policy.into(),
+        }
+    }
+}
+
+/// Information about a single WAL segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalSegment {
+    /// ID of the segment.
+    pub id: u32,
+    /// Size of the segment file in bytes.
+    pub size: u64,
+    /// Number of notifications stored in the segment.
+    pub notifications: usize,
+}
+
+/// Location of a notification record inside of a segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RecordLocation {
+    /// ID of the segment that contains the record.
+    segment_id: u32,
+    /// Offset of the record header in the segment file.
+    offset: u64,
+    /// Length of the record payload.
+    len: u32,
+    /// Record flags.
+    flags: u8,
+    /// CRC32 checksum of the record payload.
+    checksum: u32,
+}
+
+impl RecordLocation {
+    /// Total size of the record, including the header.
+    const fn size(&self) -> u64 {
+        RECORD_HEADER_SIZE + self.len as u64
+    }
+}
+
+/// Segment that notifications are currently appended to.
+#[derive(Debug)]
+struct ActiveSegment {
+    id: u32,
+    file: File,
+    size: u64,
+}
+
+/// Mutable state of the [`Storage`].
+#[derive(Debug, Default)]
+struct StorageState {
+    /// Index of all notifications `Notification ID -> Record Location`.
+    index: BTreeMap<u32, RecordLocation>,
+    /// Sizes of all segment files `Segment ID -> Size`.
+    segments: BTreeMap<u32, u64>,
+    /// Total size of the removed records that are still present in the segment files
+    /// `Segment ID -> Size`.
+    removed: BTreeMap<u32, u64>,
+    /// Segment that the notifications are appended to, if any.
+    active: Option<ActiveSegment>,
+    /// Open read handles of the segment files `Segment ID -> File`.
+    readers: BTreeMap<u32, Arc<Mutex<File>>>,
+}
+
+impl StorageState {
+    /// Returns the number of notifications stored in the given segment.
+    fn segment_notifications(&self, segment_id: u32) -> usize {
+        self.index.values().filter(|location| location.segment_id == segment_id).count()
+    }
+
+    /// Returns `true` if the segment is sealed and enough of its records were removed to compact
+    /// it.
+    fn should_compact(&self, segment_id: u32) -> bool {
+        if self.active.as_ref().is_some_and(|active| active.id == segment_id) {
+            return false
+        }
+
+        let size = self.segments.get(&segment_id).copied().unwrap_or_default();
+        let removed = self.removed.get(&segment_id).copied().unwrap_or_default();
+        removed * 100 >= size * COMPACTION_THRESHOLD_PERCENT
+    }
+}
+
+/// The underlying WAL storage backed by a directory of segment files.
+///
+/// Notifications are appended to a segment file as MessagePack-encoded records, optionally
+/// compressed with zstd. Once the segment reaches [`WalConfig::max_segment_size`], a new one is
+/// started. Every record is prefixed with a header containing the notification ID, the payload
+/// length and the payload checksum, so the in-memory index of all notifications is rebuilt on
+/// startup without decoding them. The checksum is verified on every read, and for the records of
+/// the last segment on startup, where a partially written record is truncated.
+///
+/// Segments without notifications left are deleted when notifications are removed. Removed records
+/// of the other segments are listed in a file next to the segment, and a sealed segment is
+/// atomically rewritten with only the remaining notifications once at least half of its size was
+/// removed.
+///
+/// WAL directories in the legacy format, with one file per notification, are migrated to segments
+/// on startup.
 ///
-/// Each notification is represented by a single file that contains a MessagePack-encoded
-/// notification.
-#[derive(Debug, Clone)]
+/// A storage opened with [`Storage::read_only`] never modifies the directory.
+#[derive(Debug)]
 pub struct Storage<N: NodePrimitives = EthPrimitives> {
-    /// The path to the WAL file.
+    /// The path to the WAL directory.
     path: PathBuf,
+    config: WalConfig,
+    /// Whether writes to the storage are rejected.
+    read_only: bool,
+    state: Mutex<StorageState>,
     _pd: std::marker::PhantomData<N>,
 }
 
@@ -28,160 +201,645 @@ impl<N> Storage<N>
 where
     N: NodePrimitives,
 {
-    /// Creates a new instance of [`Storage`] backed by the file at the given path and creates
-    /// it doesn't exist.
-    pub(super) fn new(path: impl AsRef<Path>) -> WalResult<Self> {
+    /// Creates a new instance of [`Storage`] backed by the directory at the given path and creates
+    /// it if it doesn't exist.
+    pub(super) fn new(path: impl AsRef<Path>, config: WalConfig) -> WalResult<Self> {
         reth_fs_util::create_dir_all(&path)?;
+        Self::open(path, config, false)
+    }
+
+    /// Opens the existing storage at the given path without modifying it.
+    ///
+    /// A partially written record at the end of the last segment is skipped instead of truncated,
+    /// and directories in the legacy format are rejected because they can't be migrated.
+    pub(super) fn read_only(path: impl AsRef<Path>) -> WalResult<Self> {
+        Self::open(path, WalConfig::default(), true)
+    }
+
+    fn open(path: impl AsRef<Path>, config: WalConfig, read_only: bool) -> WalResult<Self> {
+        let storage = Self {
+            path: path.as_ref().to_path_buf(),
+            config,
+            read_only,
+            state: Mutex::new(StorageState::default()),
+            _pd: std::marker::PhantomData,
+        };
+        storage.load_segments()?;
+        storage.migrate_legacy_files()?;
+
+        Ok(storage)
+    }
+
+    /// Returns an error if the storage was opened read-only.
+    const fn ensure_writable(&self) -> WalResult<()> {
+        if self.read_only {
+            return Err(WalError::ReadOnly)
+        }
+        Ok(())
+    }
 
-        Ok(Self { path: path.as_ref().to_path_buf(), _pd: std::marker::PhantomData })
+    fn segment_path(&self, segment_id: u32) -> PathBuf {
+        self.path.join(format!("{segment_id}.{SEGMENT_FILE_EXTENSION}"))
     }
 
-    fn file_path(&self, id: u32) -> PathBuf {
-        self.path.join(format!("{id}.{FILE_EXTENSION}"))
+    fn removed_file_path(&self, segment_id: u32) -> PathBuf {
+        self.path.join(format!("{segment_id}.{REMOVED_FILE_EXTENSION}"))
     }
 
-    fn parse_filename(filename: &str) -> WalResult<u32> {
+    fn legacy_file_path(&self, id: u32) -> PathBuf {
+        self.path.join(format!("{id}.{LEGACY_FILE_EXTENSION}"))
+    }
+
+    fn parse_filename(filename: &str, extension: &str) -> WalResult<u32> {
         filename
-            .strip_suffix(".wal")
+            .strip_suffix(extension)
+            .and_then(|s| s.strip_suffix('.'))
             .and_then(|s| s.parse().ok())
             .ok_or_else(|| WalError::Parse(filename.to_string()))
     }
 
-    /// Removes notification for the given file ID from the storage.
-    ///
-    /// # Returns
+    /// Returns the sorted IDs of all files with the given extension in the WAL directory.
+    fn list_files(&self, extension: &str) -> WalResult<Vec<u32>> {
+        let mut ids = Vec::new();
+
+        for entry in reth_fs_util::read_dir(&self.path)? {
+            let entry = entry.map_err(|err| WalError::DirEntry(self.path.clone(), err))?;
+
+            if entry.path().extension() == Some(extension.as_ref()) {
+                let file_name = entry.file_name();
+                ids.push(Self::parse_filename(&file_name.to_string_lossy(), extension)?);
+            }
+        }
+
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    /// Scans the record headers of all segments and builds the index.
     ///
-    /// The size of the file that was removed in bytes, if any.
+    /// The checksums of the records in the last segment are verified, and a partially written or
+    /// corrupted record at its end is truncated together with everything after it.
     #[instrument(skip(self))]
-    fn remove_notification(&self, file_id: u32) -> Option<u64> {
-        let path = self.file_path(file_id);
-        let size = path.metadata().ok()?.len();
-
-        match reth_fs_util::remove_file(self.file_path(file_id)) {
-            Ok(()) => {
-                debug!(target: "exex::wal::storage", "Notification was removed from the storage");
-                Some(size)
+    fn load_segments(&self) -> WalResult<()> {
+        let segment_ids = self.list_files(SEGMENT_FILE_EXTENSION)?;
+        let mut state = self.state.lock();
+
+        // The segment could have been deleted before its list of removed records after a crash
+        if !self.read_only {
+            for segment_id in self.list_files(REMOVED_FILE_EXTENSION)? {
+                if segment_ids.binary_search(&segment_id).is_err() {
+                    reth_fs_util::remove_file(self.removed_file_path(segment_id))?;
+                }
+            }
+        }
+
+        for (i, &segment_id) in segment_ids.iter().enumerate() {
+            let is_last = i + 1 == segment_ids.len();
+            let path = self.segment_path(segment_id);
+            let removed_records = self.read_removed_records(segment_id)?;
+            let mut removed_size = 0;
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(!self.read_only)
+                .open(&path)
+                .map_err(|err| reth_fs_util::FsPathError::open(err, &path))?;
+            let file_size =
+                file.metadata().map_err(|err| WalError::SegmentIo(path.clone(), err))?.len();
+
+            let mut reader = BufReader::new(&mut file);
+            let mut payload = Vec::new();
+            let mut offset = 0;
+            while offset < file_size {
+                if offset + RECORD_HEADER_SIZE > file_size {
+                    break
+                }
+
+                let mut header = [0; RECORD_HEADER_SIZE as usize];
+                reader
+                    .read_exact(&mut header)
+                    .map_err(|err| WalError::SegmentIo(path.clone(), err))?;
+                let (id, flags, len, checksum) = decode_record_header(&header);
+                let location = RecordLocation { segment_id, offset, len, flags, checksum };
+
+                if offset + location.size() > file_size {
+                    break
+                }
+                if is_last {
+                    payload.resize(len as usize, 0);
+                    reader
+                        .read_exact(&mut payload)
+                        .map_err(|err| WalError::SegmentIo(path.clone(), err))?;
+                    if crc32fast::hash(&payload) != checksum {
+                        break
+                    }
+                } else {
+                    reader
+                        .seek_relative(len as i64)
+                        .map_err(|err| WalError::SegmentIo(path.clone(), err))?;
+                }
+
+                if removed_records.contains(&(id, checksum)) {
+                    removed_size += location.size();
+                } else {
+                    state.index.insert(id, location);
+                }
+                offset += location.size();
             }
-            Err(err) => {
-                debug!(target: "exex::wal::storage", ?err, "Failed to remove notification from the storage");
-                None
+            drop(reader);
+
+            if offset < file_size {
+                if !is_last {
+                    return Err(WalError::CorruptedSegment(path, offset))
+                }
+
+                if self.read_only {
+                    debug!(target: "exex::wal::storage", ?path, ?offset, ?file_size, "Skipping partially written record");
+                } else {
+                    debug!(target: "exex::wal::storage", ?path, ?offset, ?file_size, "Truncating partially written record");
+                    file.set_len(offset).map_err(|err| WalError::SegmentIo(path.clone(), err))?;
+                }
+            }
+
+            state.segments.insert(segment_id, offset);
+            if removed_size > 0 {
+                state.removed.insert(segment_id, removed_size);
             }
         }
+
+        Ok(())
     }
 
-    /// Returns the range of file IDs in the storage.
-    ///
-    /// If there are no files in the storage, returns `None`.
-    pub(super) fn files_range(&self) -> WalResult<Option<RangeInclusive<u32>>> {
-        let mut min_id = None;
-        let mut max_id = None;
+    /// Reads the list of removed records of the segment, identified by the notification ID and
+    /// the payload checksum.
+    fn read_removed_records(&self, segment_id: u32) -> WalResult<HashSet<(u32, u32)>> {
+        let path = self.removed_file_path(segment_id);
+        if !path.exists() {
+            return Ok(HashSet::new())
+        }
 
-        for entry in reth_fs_util::read_dir(&self.path)? {
-            let entry = entry.map_err(|err| WalError::DirEntry(self.path.clone(), err))?;
+        Ok(reth_fs_util::read(&path)?
+            .chunks_exact(REMOVED_ENTRY_SIZE)
+            .map(|entry| {
+                let id =
+                    u32::from_le_bytes(entry[..4].try_into().expect("slice has correct length"));
+                let checksum =
+                    u32::from_le_bytes(entry[4..].try_into().expect("slice has correct length"));
+                (id, checksum)
+            })
+            .collect())
+    }
 
-            if entry.path().extension() == Some(FILE_EXTENSION.as_ref()) {
-                let file_name = entry.file_name();
-                let file_id = Self::parse_filename(&file_name.to_string_lossy())?;
+    /// Moves notifications from the legacy one-file-per-notification format into segments.
+    #[instrument(skip(self))]
+    fn migrate_legacy_files(&self) -> WalResult<()> {
+        let legacy_ids = self.list_files(LEGACY_FILE_EXTENSION)?;
+        if legacy_ids.is_empty() {
+            return Ok(())
+        }
+        if self.read_only {
+            return Err(WalError::LegacyFiles(self.path.clone()))
+        }
 
-                min_id = min_id.map_or(Some(file_id), |min_id: u32| Some(min_id.min(file_id)));
-                max_id = max_id.map_or(Some(file_id), |max_id: u32| Some(max_id.max(file_id)));
+        debug!(target: "exex::wal::storage", count = legacy_ids.len(), "Migrating legacy WAL files to segments");
+        for &id in &legacy_ids {
+            // The notification could have already been migrated before a crash
+            if self.state.lock().index.contains_key(&id) {
+                continue
             }
+
+            let path = self.legacy_file_path(id);
+            let payload = reth_fs_util::read(&path)?;
+            // Make sure the notification is not corrupted before migrating it
+            decode_notification::<N>(id, &path, &payload)?;
+
+            self.append_record(id, &payload)?;
+        }
+
+        self.sync_active_segment()?;
+        for id in legacy_ids {
+            reth_fs_util::remove_file(self.legacy_file_path(id))?;
         }
 
-        Ok(min_id.zip(max_id).map(|(min_id, max_id)| min_id..=max_id))
+        Ok(())
     }
 
-    /// Removes notifications from the storage according to the given list of file IDs.
+    /// Returns the range of notification IDs in the storage.
+    ///
+    /// If there are no notifications in the storage, returns `None`.
+    pub(super) fn files_range(&self) -> WalResult<Option<RangeInclusive<u32>>> {
+        let state = self.state.lock();
+        Ok(state
+            .index
+            .first_key_value()
+            .zip(state.index.last_key_value())
+            .map(|((min_id, _), (max_id, _))| *min_id..=*max_id))
+    }
+
+    /// Returns information about all segments in the storage.
+    pub(super) fn segments(&self) -> Vec<WalSegment> {
+        let state = self.state.lock();
+        state
+            .segments
+            .iter()
+            .map(|(&id, &size)| WalSegment {
+                id,
+                size,
+                notifications: state.segment_notifications(id),
+            })
+            .collect()
+    }
+
+    /// Removes notifications from the storage according to the given list of notification IDs.
+    ///
+    /// Segments without notifications left are deleted, and the removed records of the rest are
+    /// persisted in their lists of removed records. Sealed segments are compacted once enough of
+    /// their records were removed.
     ///
     /// # Returns
     ///
-    /// Number of removed notifications and the total size of the removed files in bytes.
+    /// Number of removed notifications and the total size of the removed records in bytes.
+    #[instrument(skip_all)]
     pub(super) fn remove_notifications(
         &self,
         file_ids: impl IntoIterator<Item = u32>,
     ) -> WalResult<(usize, u64)> {
+        self.ensure_writable()?;
+        let mut state = self.state.lock();
+
         let mut deleted_total = 0;
         let mut deleted_size = 0;
+        let mut removed_records = BTreeMap::<u32, Vec<(u32, u32)>>::new();
 
         for id in file_ids {
-            if let Some(size) = self.remove_notification(id) {
+            if let Some(location) = state.index.remove(&id) {
                 deleted_total += 1;
-                deleted_size += size;
+                deleted_size += location.size();
+                *state.removed.entry(location.segment_id).or_default() += location.size();
+                removed_records
+                    .entry(location.segment_id)
+                    .or_default()
+                    .push((id, location.checksum));
+            }
+        }
+
+        for (segment_id, records) in removed_records {
+            if state.segment_notifications(segment_id) == 0 {
+                self.remove_segment(&mut state, segment_id)?;
+            } else if state.should_compact(segment_id) {
+                self.compact_segment(&mut state, segment_id)?;
+            } else {
+                self.append_removed_records(segment_id, &records)?;
             }
         }
 
         Ok((deleted_total, deleted_size))
     }
 
+    /// Appends the removed records to the list of removed records of the segment.
+    fn append_removed_records(&self, segment_id: u32, records: &[(u32, u32)]) -> WalResult<()> {
+        let path = self.removed_file_path(segment_id);
+        let mut buf = Vec::with_capacity(records.len() * REMOVED_ENTRY_SIZE);
+        for (id, checksum) in records {
+            buf.extend_from_slice(&id.to_le_bytes());
+            buf.extend_from_slice(&checksum.to_le_bytes());
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|err| reth_fs_util::FsPathError::open(err, &path))?;
+        file.write_all(&buf).map_err(|err| WalError::SegmentIo(path.clone(), err))?;
+        if self.config.sync_policy == WalSyncPolicy::Always {
+            file.sync_data().map_err(|err| reth_fs_util::FsPathError::fsync(err, &path))?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes the segment file together with its list of removed records.
+    #[instrument(skip(self, state))]
+    fn remove_segment(&self, state: &mut StorageState, segment_id: u32) -> WalResult<()> {
+        if state.active.as_ref().is_some_and(|active| active.id == segment_id) {
+            state.active = None;
+        }
+        state.segments.remove(&segment_id);
+        state.removed.remove(&segment_id);
+        state.readers.remove(&segment_id);
+
+        // The list of removed records is deleted last, so that the removed records are never
+        // resurrected after a crash
+        reth_fs_util::remove_file(self.segment_path(segment_id))?;
+        let removed_path = self.removed_file_path(segment_id);
+        if removed_path.exists() {
+            reth_fs_util::remove_file(removed_path)?;
+        }
+
+        debug!(target: "exex::wal::storage", ?segment_id, "Segment was removed");
+        Ok(())
+    }
+
+    /// Atomically rewrites the sealed segment with only the notifications that are still present
+    /// in the index, and deletes its list of removed records.
+    #[instrument(skip(self, state))]
+    fn compact_segment(&self, state: &mut StorageState, segment_id: u32) -> WalResult<()> {
+        let path = self.segment_path(segment_id);
+
+        let mut records = state
+            .index
+            .iter()
+            .filter(|(_, location)| location.segment_id == segment_id)
+            .map(|(id, location)| (*id, *location))
+            .collect::<Vec<_>>();
+        records.sort_unstable_by_key(|(_, location)| location.offset);
+
+        // Read the remaining records and compute their new offsets
+        let mut file = reth_fs_util::open(&path)?;
+        let mut buf = Vec::new();
+        let mut new_locations = Vec::with_capacity(records.len());
+        for (id, location) in records {
+            let start = buf.len();
+            buf.resize(start + location.size() as usize, 0);
+            file.seek(SeekFrom::Start(location.offset))
+                .and_then(|_| file.read_exact(&mut buf[start..]))
+                .map_err(|err| WalError::SegmentIo(path.clone(), err))?;
+            new_locations.push((id, RecordLocation { offset: start as u64, ..location }));
+        }
+        drop(file);
+
+        // The records that are left out of the rewritten segment can't be confused with the
+        // remaining ones, so a crash before the list of removed records is deleted is harmless
+        reth_fs_util::atomic_write_file(&path, |file| file.write_all(&buf))?;
+        let removed_path = self.removed_file_path(segment_id);
+        if removed_path.exists() {
+            reth_fs_util::remove_file(removed_path)?;
+        }
+
+        let size = buf.len() as u64;
+        for (id, location) in new_locations {
+            state.index.insert(id, location);
+        }
+        state.segments.insert(segment_id, size);
+        state.removed.remove(&segment_id);
+        state.readers.remove(&segment_id);
+
+        debug!(target: "exex::wal::storage", ?segment_id, ?size, "Segment was compacted");
+        Ok(())
+    }
+
     pub(super) fn iter_notifications(
         &self,
         range: RangeInclusive<u32>,
     ) -> impl Iterator<Item = WalResult<(u32, u64, ExExNotification<N>)>> + '_ {
-        range.map(move |id| {
-            let (notification, size) =
-                self.read_notification(id)?.ok_or(WalError::FileNotFound(id))?;
+        let ids = self.state.lock().index.range(range).map(|(id, _)| *id).collect::<Vec<_>>();
 
-            Ok((id, size, notification))
+        // Notifications that were removed in the meantime are skipped
+        ids.into_iter().filter_map(move |id| {
+            self.read_notification(id)
+                .transpose()
+                .map(|result| result.map(|(notification, size)| (id, size, notification)))
         })
     }
 
-    /// Reads the notification from the file with the given ID.
+    /// Reads the notification with the given ID.
     #[instrument(skip(self))]
     pub(super) fn read_notification(
         &self,
         file_id: u32,
     ) -> WalResult<Option<(ExExNotification<N>, u64)>> {
-        let file_path = self.file_path(file_id);
-        debug!(target: "exex::wal::storage", ?file_path, "Reading notification from WAL");
+        let Some((location, reader)) = self.locate(file_id)? else { return Ok(None) };
+
+        let notification = self.read_record(file_id, location, &reader)?;
+        Ok(Some((notification, location.size())))
+    }
 
-        let mut file = match File::open(&file_path) {
-            Ok(file) => file,
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-            Err(err) => return Err(reth_fs_util::FsPathError::open(err, &file_path).into()),
+    /// Returns the location of the notification with the given ID and the read handle of its
+    /// segment file, opening it if needed.
+    ///
+    /// Both are returned under the same lock, so the location is valid for the file handle even if
+    /// the segment is compacted afterwards.
+    fn locate(&self, id: u32) -> WalResult<Option<(RecordLocation, Arc<Mutex<File>>)>> {
+        let mut state = self.state.lock();
+        let Some(location) = state.index.get(&id).copied() else { return Ok(None) };
+
+        let reader = match state.readers.get(&location.segment_id) {
+            Some(reader) => reader.clone(),
+            None => {
+                let reader = Arc::new(Mutex::new(reth_fs_util::open(
+                    self.segment_path(location.segment_id),
+                )?));
+                state.readers.insert(location.segment_id, reader.clone());
+                reader
+            }
         };
-        let size = file.metadata().map_err(|err| WalError::FileMetadata(file_id, err))?.len();
 
-        // Deserialize using the bincode- and msgpack-compatible serde wrapper
-        let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_, N> =
-            rmp_serde::decode::from_read(&mut file)
-                .map_err(|err| WalError::Decode(file_id, file_path, err))?;
+        Ok(Some((location, reader)))
+    }
 
-        Ok(Some((notification.into(), size)))
+    /// Reads and decodes the record at the given location.
+    fn read_record(
+        &self,
+        id: u32,
+        location: RecordLocation,
+        reader: &Mutex<File>,
+    ) -> WalResult<ExExNotification<N>> {
+        let path = self.segment_path(location.segment_id);
+        debug!(target: "exex::wal::storage", ?path, ?id, offset = ?location.offset, "Reading notification from WAL");
+
+        let mut payload = vec![0; location.len as usize];
+        {
+            let mut file = reader.lock();
+            file.seek(SeekFrom::Start(location.offset + RECORD_HEADER_SIZE))
+                .and_then(|_| file.read_exact(&mut payload))
+                .map_err(|err| WalError::SegmentIo(path.clone(), err))?;
+        }
+        if crc32fast::hash(&payload) != location.checksum {
+            return Err(WalError::ChecksumMismatch(id, path))
+        }
+
+        if location.flags & RECORD_FLAG_ZSTD != 0 {
+            payload = zstd::stream::decode_all(payload.as_slice())
+                .map_err(|err| WalError::SegmentIo(path.clone(), err))?;
+        }
+
+        decode_notification(id, &path, &payload)
     }
 
-    /// Writes the notification to the file with the given ID.
+    /// Writes the notification with the given ID.
     ///
     /// # Returns
     ///
-    /// The size of the file that was written in bytes.
+    /// The size of the record that was written in bytes.
     #[instrument(skip(self, notification))]
     pub(super) fn write_notification(
         &self,
         file_id: u32,
         notification: &ExExNotification<N>,
     ) -> WalResult<u64> {
-        let file_path = self.file_path(file_id);
-        debug!(target: "exex::wal::storage", ?file_path, "Writing notification to WAL");
+        self.ensure_writable()?;
+        debug!(target: "exex::wal::storage", ?file_id, "Writing notification to WAL");
 
         // Serialize using the bincode- and msgpack-compatible serde wrapper
         let notification =
             reth_exex_types::serde_bincode_compat::ExExNotification::<N>::from(notification);
+        let payload = rmp_serde::encode::to_vec(&notification).map_err(WalError::Encode)?;
+
+        let size = self.append_record(file_id, &payload)?;
+        if self.config.sync_policy == WalSyncPolicy::Always {
+            self.sync_active_segment()?;
+        }
+
+        Ok(size)
+    }
+
+    /// Appends the encoded notification to the active segment, starting a new one if needed.
+    ///
+    /// # Returns
+    ///
+    /// The size of the record that was written in bytes.
+    fn append_record(&self, id: u32, payload: &[u8]) -> WalResult<u64> {
+        let (payload, flags) = match self.config.compression_level {
+            Some(level) => (
+                std::borrow::Cow::Owned(
+                    zstd::bulk::compress(payload, level).map_err(WalError::Compression)?,
+                ),
+                RECORD_FLAG_ZSTD,
+            ),
+            None => (std::borrow::Cow::Borrowed(payload), 0),
+        };
+        let len = u32::try_from(payload.len())
+            .map_err(|_| WalError::NotificationTooLarge(id, payload.len()))?;
+        let record_size = RECORD_HEADER_SIZE + len as u64;
+        let checksum = crc32fast::hash(&payload);
+
+        let mut state = self.state.lock();
 
-        reth_fs_util::atomic_write_file(&file_path, |file| {
-            rmp_serde::encode::write(file, &notification)
-        })?;
+        // Seal the active segment if the record doesn't fit into it
+        if state.active.as_ref().is_some_and(|active| {
+            active.size > 0 && active.size + record_size > self.config.max_segment_size
+        }) {
+            let active = state.active.take().expect("active segment exists");
+            if self.config.sync_policy != WalSyncPolicy::Never {
+                let path = self.segment_path(active.id);
+                active
+                    .file
+                    .sync_all()
+                    .map_err(|err| reth_fs_util::FsPathError::fsync(err, &path))?;
+            }
+            debug!(target: "exex::wal::storage", segment_id = ?active.id, size = ?active.size, "Segment was sealed");
+        }
+
+        if state.active.is_none() {
+            // Append to the last segment if it has space left, otherwise start a new one
+            let last_segment = state.segments.last_key_value().map(|(id, size)| (*id, *size));
+            let active = match last_segment {
+                Some((segment_id, size)) if size + record_size <= self.config.max_segment_size => {
+                    ActiveSegment {
+                        id: segment_id,
+                        file: self.open_append(&self.segment_path(segment_id))?,
+                        size,
+                    }
+                }
+                _ => {
+                    let segment_id = last_segment.map_or(0, |(segment_id, _)| segment_id + 1);
+                    let path = self.segment_path(segment_id);
+                    let file = reth_fs_util::create_file(&path)?;
+                    state.segments.insert(segment_id, 0);
+                    ActiveSegment { id: segment_id, file, size: 0 }
+                }
+            };
+            state.active = Some(active);
+        }
+
+        let StorageState { index, segments, active } = &mut *state;
+        let active = active.as_mut().expect("active segment exists");
+        let path = self.segment_path(active.id);
 
-        Ok(file_path.metadata().map_err(|err| WalError::FileMetadata(file_id, err))?.len())
+        let mut record = Vec::with_capacity(record_size as usize);
+        record.extend_from_slice(&encode_record_header(id, flags, len, checksum));
+        record.extend_from_slice(&payload);
+        active.file.write_all(&record).map_err(|err| WalError::SegmentIo(path, err))?;
+
+        index.insert(
+            id,
+            RecordLocation { segment_id: active.id, offset: active.size, len, flags, checksum },
+        );
+        active.size += record_size;
+        segments.insert(active.id, active.size);
+
+        Ok(record_size)
     }
+
+    /// Flushes the active segment to disk.
+    fn sync_active_segment(&self) -> WalResult<()> {
+        if let Some(active) = self.state.lock().active.as_ref() {
+            let path = self.segment_path(active.id);
+            active.file.sync_data().map_err(|err| reth_fs_util::FsPathError::fsync(err, &path))?;
+        }
+        Ok(())
+    }
+
+    fn open_append(&self, path: &Path) -> WalResult<File> {
+        OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map_err(|err| reth_fs_util::FsPathError::open(err, path).into())
+    }
+}
+
+/// Encodes the record header.
+fn encode_record_header(
+    id: u32,
+    flags: u8,
+    len: u32,
+    checksum: u32,
+) -> [u8; RECORD_HEADER_SIZE as usize] {
+    let mut header = [0; RECORD_HEADER_SIZE as usize];
+    header[..4].copy_from_slice(&id.to_le_bytes());
+    header[4] = flags;
+    header[5..9].copy_from_slice(&len.to_le_bytes());
+    header[9..].copy_from_slice(&checksum.to_le_bytes());
+    header
+}
+
+/// Decodes the record header into notification ID, flags, payload length and payload checksum.
+fn decode_record_header(header: &[u8; RECORD_HEADER_SIZE as usize]) -> (u32, u8, u32, u32) {
+    let id = u32::from_le_bytes(header[..4].try_into().expect("slice has correct length"));
+    let len = u32::from_le_bytes(header[5..9].try_into().expect("slice has correct length"));
+    let checksum = u32::from_le_bytes(header[9..].try_into().expect("slice has correct length"));
+    (id, header[4], len, checksum)
+}
+
+/// Decodes the uncompressed MessagePack-encoded notification.
+fn decode_notification<N: NodePrimitives>(
+    id: u32,
+    path: &Path,
+    payload: &[u8],
+) -> WalResult<ExExNotification<N>> {
+    // Deserialize using the bincode- and msgpack-compatible serde wrapper
+    let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_, N> =
+        rmp_serde::decode::from_slice(payload)
+            .map_err(|err| WalError::Decode(id, path.to_path_buf(), err))?;
+
+    Ok(notification.into())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Storage;
+    use super::{Storage, WalConfig};
+    use crate::wal::WalError;
+    use reth_ethereum_primitives::EthPrimitives;
     use reth_exex_types::ExExNotification;
     use reth_provider::Chain;
     use reth_testing_utils::generators::{self, random_block};
-    use std::{fs::File, sync::Arc};
+    use std::sync::Arc;
+
+    fn notification(rng: &mut impl rand::Rng, number: u64) -> eyre::Result<ExExNotification> {
+        let block = random_block(rng, number, Default::default()).try_recover()?;
+        Ok(ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block], Default::default(), None)),
+        })
+    }
 
     // wal with 1 block and tx
     // <https://github.com/paradigmxyz/reth/issues/15012>
@@ -207,7 +865,7 @@ mod tests {
         let mut rng = generators::rng();
 
         let temp_dir = tempfile::tempdir()?;
-        let storage: Storage = Storage::new(&temp_dir)?;
+        let storage: Storage = Storage::new(&temp_dir, WalConfig::default())?;
 
         let old_block = random_block(&mut rng, 0, Default::default()).try_recover()?;
         let new_block = random_block(&mut rng, 0, Default::default()).try_recover()?;
@@ -230,21 +888,272 @@ mod tests {
     }
 
     #[test]
-    fn test_files_range() -> eyre::Result<()> {
+    fn test_compressed_roundtrip() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let config = WalConfig { compression_level: Some(3), ..Default::default() };
+        let storage: Storage = Storage::new(&temp_dir, config)?;
+
+        let notification = notification(&mut rng, 0)?;
+        storage.write_notification(0, &notification)?;
+        drop(storage);
+
+        // Reopen the storage and make sure the index is rebuilt
+        let storage: Storage = Storage::new(&temp_dir, config)?;
+        assert_eq!(storage.files_range()?, Some(0..=0));
+        assert_eq!(storage.read_notification(0)?.map(|(n, _)| n), Some(notification));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_segments_and_compaction() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
         let temp_dir = tempfile::tempdir()?;
-        let storage: Storage = Storage::new(&temp_dir)?;
+        // Small segments to fit only a couple of notifications each
+        let config = WalConfig { max_segment_size: 1, ..Default::default() };
+        let storage: Storage = Storage::new(&temp_dir, config)?;
+
+        let notifications =
+            (0..4).map(|i| notification(&mut rng, i)).collect::<eyre::Result<Vec<_>>>()?;
+        for (id, notification) in notifications.iter().enumerate() {
+            storage.write_notification(id as u32, notification)?;
+        }
+
+        // Every notification is larger than the segment size, so it gets its own segment
+        assert_eq!(storage.segments().len(), 4);
+        assert_eq!(storage.files_range()?, Some(0..=3));
+
+        // Removing notifications deletes their segments
+        let (removed, _) = storage.remove_notifications([0, 1])?;
+        assert_eq!(removed, 2);
+        assert_eq!(storage.segments().len(), 2);
+        assert_eq!(storage.files_range()?, Some(2..=3));
+
+        // The removal is persisted
+        drop(storage);
+        let storage: Storage = Storage::new(&temp_dir, config)?;
+        assert_eq!(storage.files_range()?, Some(2..=3));
+        let read = storage
+            .iter_notifications(2..=3)
+            .map(|entry| entry.map(|(_, _, n)| n))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(read, notifications[2..]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_removes_records_without_compaction() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let storage: Storage = Storage::new(&temp_dir, WalConfig::default())?;
+
+        let notifications =
+            (0..3).map(|i| notification(&mut rng, i)).collect::<eyre::Result<Vec<_>>>()?;
+        for (id, notification) in notifications.iter().enumerate() {
+            storage.write_notification(id as u32, notification)?;
+        }
+        assert_eq!(storage.segments().len(), 1);
+        let size_before = storage.segments()[0].size;
 
-        // Create WAL files
-        File::create(storage.file_path(1))?;
-        File::create(storage.file_path(2))?;
-        File::create(storage.file_path(3))?;
+        // The active segment is not rewritten, the removed record is listed instead
+        storage.remove_notifications([1])?;
+        let segments = storage.segments();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].notifications, 2);
+        assert_eq!(segments[0].size, size_before);
+        assert!(temp_dir.path().join("0.del").exists());
 
-        // Create non-WAL files that should be ignored
-        File::create(temp_dir.path().join("0.tmp"))?;
-        File::create(temp_dir.path().join("4.tmp"))?;
+        // Appending after the removal still works
+        let notification = notification(&mut rng, 3)?;
+        storage.write_notification(3, &notification)?;
+
+        drop(storage);
+        let storage: Storage = Storage::new(&temp_dir, WalConfig::default())?;
+        let read = storage
+            .iter_notifications(0..=3)
+            .map(|entry| entry.map(|(id, _, n)| (id, n)))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            read,
+            vec![(0, notifications[0].clone()), (2, notifications[2].clone()), (3, notification)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compacts_sealed_segment() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let notification = notification(&mut rng, 0)?;
+
+        // Measure the size of three records to fit exactly three of them into a segment
+        let temp_dir = tempfile::tempdir()?;
+        let storage: Storage = Storage::new(&temp_dir, WalConfig::default())?;
+        for id in 0..3 {
+            storage.write_notification(id, &notification)?;
+        }
+        let max_segment_size = storage.segments()[0].size;
+
+        let temp_dir = tempfile::tempdir()?;
+        let config = WalConfig { max_segment_size, ..Default::default() };
+        let storage: Storage = Storage::new(&temp_dir, config)?;
+        for id in 0..4 {
+            storage.write_notification(id, &notification)?;
+        }
+        assert_eq!(storage.segments().len(), 2);
+
+        // A third of the sealed segment is not enough to compact it
+        storage.remove_notifications([0])?;
+        assert_eq!(storage.segments()[0].size, max_segment_size);
+        assert!(temp_dir.path().join("0.del").exists());
+
+        // Two thirds are
+        storage.remove_notifications([1])?;
+        let segments = storage.segments();
+        assert_eq!(segments[0].notifications, 1);
+        assert_eq!(segments[0].size, max_segment_size / 3);
+        assert!(!temp_dir.path().join("0.del").exists());
+
+        // Removing the last notification deletes the segment
+        storage.remove_notifications([2])?;
+        assert_eq!(storage.segments().len(), 1);
+        assert!(!temp_dir.path().join("0.seg").exists());
+
+        drop(storage);
+        let storage: Storage = Storage::new(&temp_dir, config)?;
+        assert_eq!(storage.files_range()?, Some(3..=3));
+        assert_eq!(storage.read_notification(3)?.map(|(n, _)| n), Some(notification));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncates_partial_record() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let storage: Storage = Storage::new(&temp_dir, WalConfig::default())?;
+        let notification = notification(&mut rng, 0)?;
+        storage.write_notification(0, &notification)?;
+        storage.write_notification(1, &notification)?;
+        drop(storage);
+
+        // Simulate a crash in the middle of writing the second record
+        let path = temp_dir.path().join("0.seg");
+        let file = std::fs::OpenOptions::new().write(true).open(&path)?;
+        file.set_len(file.metadata()?.len() - 1)?;
+
+        let storage: Storage = Storage::new(&temp_dir, WalConfig::default())?;
+        assert_eq!(storage.files_range()?, Some(0..=0));
+        storage.write_notification(1, &notification)?;
+        assert_eq!(storage.read_notification(1)?.map(|(n, _)| n), Some(notification));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncates_corrupted_record() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let storage: Storage = Storage::new(&temp_dir, WalConfig::default())?;
+        let notification = notification(&mut rng, 0)?;
+        storage.write_notification(0, &notification)?;
+        storage.write_notification(1, &notification)?;
+        drop(storage);
+
+        // Flip the last byte of the second record, leaving its length intact
+        let path = temp_dir.path().join("0.seg");
+        let mut bytes = std::fs::read(&path)?;
+        *bytes.last_mut().unwrap() ^= 0xff;
+        std::fs::write(&path, bytes)?;
+
+        let storage: Storage = Storage::new(&temp_dir, WalConfig::default())?;
+        assert_eq!(storage.files_range()?, Some(0..=0));
+        assert_eq!(storage.read_notification(0)?.map(|(n, _)| n), Some(notification));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_checksum_mismatch() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        // Every notification gets its own segment
+        let config = WalConfig { max_segment_size: 1, ..Default::default() };
+        let storage: Storage = Storage::new(&temp_dir, config)?;
+        let notification = notification(&mut rng, 0)?;
+        storage.write_notification(0, &notification)?;
+        storage.write_notification(1, &notification)?;
+        drop(storage);
+
+        // Corrupt the record in the first segment, which is not verified on startup
+        let path = temp_dir.path().join("0.seg");
+        let mut bytes = std::fs::read(&path)?;
+        *bytes.last_mut().unwrap() ^= 0xff;
+        std::fs::write(&path, bytes)?;
+
+        let storage: Storage = Storage::new(&temp_dir, config)?;
+        assert_eq!(storage.files_range()?, Some(0..=1));
+        assert!(matches!(storage.read_notification(0), Err(WalError::ChecksumMismatch(0, _))));
+        assert_eq!(storage.read_notification(1)?.map(|(n, _)| n), Some(notification));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_only() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let storage: Storage = Storage::new(&temp_dir, WalConfig::default())?;
+        let notification = notification(&mut rng, 0)?;
+        storage.write_notification(0, &notification)?;
+        storage.write_notification(1, &notification)?;
+        drop(storage);
+
+        // Simulate a crash in the middle of writing the second record
+        let path = temp_dir.path().join("0.seg");
+        let file = std::fs::OpenOptions::new().write(true).open(&path)?;
+        let size = file.metadata()?.len() - 1;
+        file.set_len(size)?;
+
+        // The partial record is skipped but left in place
+        let storage: Storage = Storage::read_only(&temp_dir)?;
+        assert_eq!(storage.files_range()?, Some(0..=0));
+        assert_eq!(storage.read_notification(0)?.map(|(n, _)| n), Some(notification.clone()));
+        assert_eq!(std::fs::metadata(&path)?.len(), size);
+
+        assert!(matches!(storage.write_notification(1, &notification), Err(WalError::ReadOnly)));
+        assert!(matches!(storage.remove_notifications([0]), Err(WalError::ReadOnly)));
+        assert_eq!(std::fs::metadata(&path)?.len(), size);
+
+        // Legacy files are not migrated
+        std::fs::write(temp_dir.path().join("28.wal"), include_bytes!("../../test-data/28.wal"))?;
+        assert!(matches!(
+            Storage::<EthPrimitives>::read_only(&temp_dir),
+            Err(WalError::LegacyFiles(_))
+        ));
+        assert!(temp_dir.path().join("28.wal").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrates_legacy_files() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        std::fs::write(temp_dir.path().join("28.wal"), include_bytes!("../../test-data/28.wal"))?;
 
-        // Check files range
-        assert_eq!(storage.files_range()?, Some(1..=3));
+        let storage: Storage = Storage::new(&temp_dir, WalConfig::default())?;
+        assert_eq!(storage.files_range()?, Some(28..=28));
+        assert!(storage.read_notification(28)?.is_some());
+        assert!(!temp_dir.path().join("28.wal").exists());
 
         Ok(())
     }
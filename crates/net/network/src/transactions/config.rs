@@ -1,4 +1,4 @@
-use std::{fmt::Debug, marker::PhantomData, str::FromStr};
+use std::{collections::HashSet, fmt::Debug, marker::PhantomData, str::FromStr};
 
 use super::{
     PeerMetadata, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
@@ -9,10 +9,12 @@ use crate::transactions::constants::tx_fetcher::{
     DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
     DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
 };
-use alloy_primitives::B256;
+use alloy_primitives::{Address, TxHash, B256};
 use derive_more::{Constructor, Display};
 use reth_eth_wire::NetworkPrimitives;
 use reth_ethereum_primitives::TxType;
+use reth_network_peers::PeerId;
+use reth_transaction_pool::TransactionOrigin;
 
 /// Configuration for managing transactions within the network.
 #[derive(Debug, Clone)]
@@ -112,6 +114,44 @@ pub trait TransactionPropagationPolicy: Send + Sync + Unpin + 'static {
 
     /// A callback on the policy when a peer session is closed.
     fn on_session_closed<N: NetworkPrimitives>(&mut self, peer: &mut PeerMetadata<N>);
+
+    /// Filter a given transaction for a peer that passed [`Self::can_propagate`].
+    ///
+    /// This determines whether the transaction can be propagated to this peer, either in full or
+    /// as a hash. By default, all transactions are propagated.
+    fn can_propagate_transaction<N: NetworkPrimitives>(
+        &self,
+        _peer_id: &PeerId,
+        _peer: &PeerMetadata<N>,
+        _transaction: &PropagationCandidate,
+    ) -> bool {
+        true
+    }
+}
+
+/// Metadata of a transaction that is about to be propagated to a peer.
+///
+/// See [`TransactionPropagationPolicy::can_propagate_transaction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropagationCandidate {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The EIP-2718 type of the transaction.
+    pub tx_type: u8,
+    /// The sender of the transaction, if known.
+    ///
+    /// The sender is only known for transactions that are in the pool.
+    pub sender: Option<Address>,
+    /// The recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// The max priority fee per gas of the transaction, `None` for legacy and EIP-2930
+    /// transactions.
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// The origin of the transaction in the pool.
+    ///
+    /// `None` for transactions that are broadcast directly through the
+    /// [`TransactionsHandle`](super::TransactionsHandle) and are not in the pool.
+    pub origin: Option<TransactionOrigin>,
 }
 
 /// Determines which peers pending transactions are propagated to.
@@ -140,6 +180,142 @@ impl TransactionPropagationPolicy for TransactionPropagationKind {
     fn on_session_closed<N: NetworkPrimitives>(&mut self, _peer: &mut PeerMetadata<N>) {}
 }
 
+/// A [`TransactionPropagationPolicy`] that restricts which transactions are gossiped and to which
+/// peers, on top of an inner policy.
+///
+/// Transactions are not propagated if any of the configured filters matches them. This is useful
+/// for nodes that receive private order flow that must not be leaked to the network.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionGossipFilter<P = TransactionPropagationKind> {
+    inner: P,
+    blocked_senders: HashSet<Address>,
+    blocked_recipients: HashSet<Address>,
+    blocked_tx_types: HashSet<u8>,
+    blocked_origins: Vec<TransactionOrigin>,
+    min_priority_fee_per_gas: Option<u128>,
+    allowed_peers: Option<HashSet<PeerId>>,
+}
+
+impl<P> TransactionGossipFilter<P> {
+    /// Creates a new filter on top of the given propagation policy.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            blocked_senders: Default::default(),
+            blocked_recipients: Default::default(),
+            blocked_tx_types: Default::default(),
+            blocked_origins: Default::default(),
+            min_priority_fee_per_gas: None,
+            allowed_peers: None,
+        }
+    }
+
+    /// Never propagates transactions sent by the given address.
+    ///
+    /// Transactions with an unknown sender are not propagated if any sender is blocked.
+    pub fn block_sender(mut self, sender: Address) -> Self {
+        self.blocked_senders.insert(sender);
+        self
+    }
+
+    /// Never propagates transactions sent to the given address.
+    pub fn block_recipient(mut self, recipient: Address) -> Self {
+        self.blocked_recipients.insert(recipient);
+        self
+    }
+
+    /// Never propagates transactions of the given EIP-2718 type.
+    pub fn block_tx_type(mut self, tx_type: u8) -> Self {
+        self.blocked_tx_types.insert(tx_type);
+        self
+    }
+
+    /// Never propagates transactions with the given origin.
+    pub fn block_origin(mut self, origin: TransactionOrigin) -> Self {
+        if !self.blocked_origins.contains(&origin) {
+            self.blocked_origins.push(origin);
+        }
+        self
+    }
+
+    /// Only propagates transactions that pay at least the given priority fee per gas.
+    ///
+    /// The gas price is used as the priority fee of legacy and EIP-2930 transactions.
+    pub const fn with_min_priority_fee_per_gas(mut self, min_priority_fee_per_gas: u128) -> Self {
+        self.min_priority_fee_per_gas = Some(min_priority_fee_per_gas);
+        self
+    }
+
+    /// Only propagates transactions to the peers in the allow list.
+    ///
+    /// Can be called multiple times to allow multiple peers.
+    pub fn allow_peer(mut self, peer_id: PeerId) -> Self {
+        self.allowed_peers.get_or_insert_default().insert(peer_id);
+        self
+    }
+
+    /// Returns `true` if the transaction passes all transaction filters.
+    fn is_allowed(&self, tx: &PropagationCandidate) -> bool {
+        if self.blocked_tx_types.contains(&tx.tx_type) {
+            return false
+        }
+        if !self.blocked_senders.is_empty() &&
+            tx.sender.is_none_or(|sender| self.blocked_senders.contains(&sender))
+        {
+            return false
+        }
+        if tx.to.is_some_and(|to| self.blocked_recipients.contains(&to)) {
+            return false
+        }
+        if tx.origin.is_some_and(|origin| self.blocked_origins.contains(&origin)) {
+            return false
+        }
+        if let Some(min_priority_fee_per_gas) = self.min_priority_fee_per_gas {
+            if tx.max_priority_fee_per_gas.unwrap_or_default() < min_priority_fee_per_gas {
+                return false
+            }
+        }
+
+        true
+    }
+}
+
+impl<P: TransactionPropagationPolicy> TransactionPropagationPolicy for TransactionGossipFilter<P> {
+    fn can_propagate<N: NetworkPrimitives>(&self, peer: &mut PeerMetadata<N>) -> bool {
+        self.inner.can_propagate(peer)
+    }
+
+    fn on_session_established<N: NetworkPrimitives>(&mut self, peer: &mut PeerMetadata<N>) {
+        self.inner.on_session_established(peer)
+    }
+
+    fn on_session_closed<N: NetworkPrimitives>(&mut self, peer: &mut PeerMetadata<N>) {
+        self.inner.on_session_closed(peer)
+    }
+
+    fn can_propagate_transaction<N: NetworkPrimitives>(
+        &self,
+        peer_id: &PeerId,
+        peer: &PeerMetadata<N>,
+        transaction: &PropagationCandidate,
+    ) -> bool {
+        if self.allowed_peers.as_ref().is_some_and(|allowed| !allowed.contains(peer_id)) {
+            return false
+        }
+
+        if !self.is_allowed(transaction) {
+            tracing::trace!(target: "net::tx::policy::gossip_filter",
+                %peer_id,
+                hash = %transaction.hash,
+                "Transaction is filtered out from propagation"
+            );
+            return false
+        }
+
+        self.inner.can_propagate_transaction(peer_id, peer, transaction)
+    }
+}
+
 impl FromStr for TransactionPropagationKind {
     type Err = String;
 
@@ -173,6 +349,103 @@ pub enum AnnouncementAcceptance {
 pub trait AnnouncementFilteringPolicy: Send + Sync + Unpin + 'static {
     /// Decides how to handle a transaction announcement based on its type, hash, and size.
     fn decide_on_announcement(&self, ty: u8, hash: &B256, size: usize) -> AnnouncementAcceptance;
+
+    /// Decides how to handle a transaction announcement received from the given peer.
+    ///
+    /// This is called before the announced transaction is fetched. By default, the peer is
+    /// ignored and the decision is delegated to [`Self::decide_on_announcement`].
+    fn decide_on_peer_announcement(
+        &self,
+        _peer_id: &PeerId,
+        ty: u8,
+        hash: &B256,
+        size: usize,
+    ) -> AnnouncementAcceptance {
+        self.decide_on_announcement(ty, hash, size)
+    }
+}
+
+/// An [`AnnouncementFilteringPolicy`] that ignores announcements of certain transaction types, of
+/// oversized transactions, or from peers that are not allowed, on top of an inner policy.
+///
+/// Filtered announcements are ignored, i.e. the transactions are not fetched, but the peer is not
+/// penalized.
+#[derive(Debug, Clone, Default)]
+pub struct AnnouncementGossipFilter<A = StrictEthAnnouncementFilter> {
+    inner: A,
+    ignored_tx_types: HashSet<u8>,
+    max_size: Option<usize>,
+    allowed_peers: Option<HashSet<PeerId>>,
+}
+
+impl<A> AnnouncementGossipFilter<A> {
+    /// Creates a new filter on top of the given announcement filtering policy.
+    pub fn new(inner: A) -> Self {
+        Self { inner, ignored_tx_types: Default::default(), max_size: None, allowed_peers: None }
+    }
+
+    /// Ignores announcements of transactions with the given EIP-2718 type.
+    pub fn ignore_tx_type(mut self, tx_type: u8) -> Self {
+        self.ignored_tx_types.insert(tx_type);
+        self
+    }
+
+    /// Ignores announcements of transactions larger than the given size in bytes.
+    pub const fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Only accepts announcements from the peers in the allow list.
+    ///
+    /// Can be called multiple times to allow multiple peers.
+    pub fn allow_peer(mut self, peer_id: PeerId) -> Self {
+        self.allowed_peers.get_or_insert_default().insert(peer_id);
+        self
+    }
+}
+
+impl<A> AnnouncementGossipFilter<A> {
+    /// Returns `true` if the announcement should be ignored based on its type and size.
+    fn is_filtered(&self, ty: u8, hash: &B256, size: usize) -> bool {
+        let filtered =
+            self.ignored_tx_types.contains(&ty) || self.max_size.is_some_and(|max| size > max);
+        if filtered {
+            tracing::trace!(target: "net::tx::policy::gossip_filter",
+                %ty,
+                %size,
+                %hash,
+                "Ignoring filtered transaction announcement"
+            );
+        }
+        filtered
+    }
+}
+
+impl<A: AnnouncementFilteringPolicy> AnnouncementFilteringPolicy for AnnouncementGossipFilter<A> {
+    fn decide_on_announcement(&self, ty: u8, hash: &B256, size: usize) -> AnnouncementAcceptance {
+        if self.is_filtered(ty, hash, size) {
+            return AnnouncementAcceptance::Ignore
+        }
+
+        self.inner.decide_on_announcement(ty, hash, size)
+    }
+
+    fn decide_on_peer_announcement(
+        &self,
+        peer_id: &PeerId,
+        ty: u8,
+        hash: &B256,
+        size: usize,
+    ) -> AnnouncementAcceptance {
+        if self.allowed_peers.as_ref().is_some_and(|allowed| !allowed.contains(peer_id)) ||
+            self.is_filtered(ty, hash, size)
+        {
+            return AnnouncementAcceptance::Ignore
+        }
+
+        self.inner.decide_on_peer_announcement(peer_id, ty, hash, size)
+    }
 }
 
 /// A generic `AnnouncementFilteringPolicy` that enforces strict validation
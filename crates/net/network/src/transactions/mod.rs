@@ -15,7 +15,8 @@ pub use self::constants::{
 };
 use config::{AnnouncementAcceptance, StrictEthAnnouncementFilter, TransactionPropagationKind};
 pub use config::{
-    AnnouncementFilteringPolicy, TransactionFetcherConfig, TransactionPropagationMode,
+    AnnouncementFilteringPolicy, AnnouncementGossipFilter, PropagationCandidate,
+    TransactionFetcherConfig, TransactionGossipFilter, TransactionPropagationMode,
     TransactionPropagationPolicy, TransactionsManagerConfig,
 };
 use policy::{NetworkPolicies, TransactionPolicies};
@@ -36,7 +37,7 @@ use crate::{
     },
     NetworkHandle, TxTypesCounter,
 };
-use alloy_primitives::{TxHash, B256};
+use alloy_primitives::{Address, TxHash, B256};
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use reth_eth_wire::{
@@ -62,7 +63,7 @@ use reth_tokio_util::EventStream;
 use reth_transaction_pool::{
     error::{PoolError, PoolResult},
     AddedTransactionOutcome, GetPooledTransactionLimit, PoolTransaction, PropagateKind,
-    PropagatedTransactions, TransactionPool, ValidPoolTransaction,
+    PropagatedTransactions, TransactionOrigin, TransactionPool, ValidPoolTransaction,
 };
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
@@ -706,7 +707,7 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
             let decision = self
                 .policies
                 .announcement_filter()
-                .decide_on_announcement(ty_byte, tx_hash, size_val);
+                .decide_on_peer_announcement(&peer_id, ty_byte, tx_hash, size_val);
 
             match decision {
                 AnnouncementAcceptance::Accept => true,
@@ -885,6 +886,11 @@ where
 
         let to_propagate = self.pool.get_all(txs).into_iter().map(PropagateTransaction::pool_tx);
 
+        // skip transactions that must not be propagated to the peer
+        let policy = self.policies.propagation_policy();
+        let to_propagate = to_propagate
+            .filter(|tx| policy.can_propagate_transaction(&peer_id, peer, &tx.candidate()));
+
         if propagation_mode.is_forced() {
             // skip cache check if forced
             full_transactions.extend(to_propagate);
@@ -955,11 +961,14 @@ where
                 return
             };
 
+            let policy = self.policies.propagation_policy();
             let to_propagate = self
                 .pool
                 .get_all(hashes)
                 .into_iter()
                 .map(PropagateTransaction::pool_tx)
+                // skip transactions that must not be propagated to the peer
+                .filter(|tx| policy.can_propagate_transaction(&peer_id, peer, &tx.candidate()))
                 .collect::<Vec<_>>();
 
             let mut propagated = PropagatedTransactions::default();
@@ -1036,13 +1045,19 @@ where
                 PropagateTransactionsBuilder::full(peer.version)
             };
 
+            // skip transactions that must not be propagated to the peer
+            let policy = self.policies.propagation_policy();
+            let peer_to_propagate = to_propagate
+                .iter()
+                .filter(|tx| policy.can_propagate_transaction(peer_id, peer, &tx.candidate()));
+
             if propagation_mode.is_forced() {
-                builder.extend(to_propagate.iter());
+                builder.extend(peer_to_propagate);
             } else {
                 // Iterate through the transactions to propagate and fill the hashes and full
                 // transaction lists, before deciding whether or not to send full transactions to
                 // the peer.
-                for tx in &to_propagate {
+                for tx in peer_to_propagate {
                     // Only proceed if the transaction is not in the peer's list of seen
                     // transactions
                     if !peer.seen_transactions.contains(tx.tx_hash()) {
@@ -1132,8 +1147,29 @@ where
                 let _ = response.send(Ok(PooledTransactions::default()));
                 return
             }
+            // only serve transactions that may be propagated to the peer, the same as for
+            // announcements and broadcasts
+            let policy = self.policies.propagation_policy();
+            let hashes = if policy.can_propagate(peer) {
+                self.pool
+                    .get_all(request.0)
+                    .into_iter()
+                    .filter(|tx| {
+                        tx.propagate &&
+                            policy.can_propagate_transaction(
+                                &peer_id,
+                                peer,
+                                &pool_transaction_candidate(tx),
+                            )
+                    })
+                    .map(|tx| *tx.hash())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
             let transactions = self.pool.get_pooled_transaction_elements(
-                request.0,
+                hashes,
                 GetPooledTransactionLimit::ResponseSizeSoftLimit(
                     self.transaction_fetcher.info.soft_limit_byte_size_pooled_transactions_response,
                 ),
@@ -1170,7 +1206,14 @@ where
                 }
             }
             TransactionsCommand::PropagateTransactions(txs) => self.propagate_all(txs),
-            TransactionsCommand::BroadcastTransactions(txs) => {
+            TransactionsCommand::BroadcastTransactions(mut txs) => {
+                // use the sender and origin of the pooled transaction for the propagation policy
+                for tx in &mut txs {
+                    if let Some(pooled) = self.pool.get(tx.tx_hash()) {
+                        tx.sender = Some(pooled.sender());
+                        tx.origin = Some(pooled.origin);
+                    }
+                }
                 let propagated = self.propagate_transactions(txs, PropagationMode::Forced);
                 self.pool.on_propagated(propagated);
             }
@@ -1677,13 +1720,20 @@ impl PropagationMode {
 struct PropagateTransaction<T = TransactionSigned> {
     size: usize,
     transaction: Arc<T>,
+    /// The sender of the transaction, if known.
+    sender: Option<Address>,
+    /// The origin of the transaction, if it's a pooled transaction.
+    origin: Option<TransactionOrigin>,
 }
 
 impl<T: SignedTransaction> PropagateTransaction<T> {
     /// Create a new instance from a transaction.
+    ///
+    /// The sender is not recovered here, the [`TransactionsManager`] takes it from the pool if the
+    /// transaction is pooled.
     pub fn new(transaction: T) -> Self {
         let size = transaction.length();
-        Self { size, transaction: Arc::new(transaction) }
+        Self { size, transaction: Arc::new(transaction), sender: None, origin: None }
     }
 
     /// Create a new instance from a pooled transaction
@@ -1692,14 +1742,48 @@ impl<T: SignedTransaction> PropagateTransaction<T> {
         P: PoolTransaction<Consensus = T>,
     {
         let size = tx.encoded_length();
+        let sender = Some(tx.sender());
+        let origin = Some(tx.origin);
         let transaction = tx.transaction.clone_into_consensus();
         let transaction = Arc::new(transaction.into_inner());
-        Self { size, transaction }
+        Self { size, transaction, sender, origin }
     }
 
     fn tx_hash(&self) -> &TxHash {
         self.transaction.tx_hash()
     }
+
+    /// Returns the metadata of the transaction for the propagation policy.
+    fn candidate(&self) -> PropagationCandidate {
+        PropagationCandidate {
+            hash: *self.tx_hash(),
+            tx_type: self.transaction.ty(),
+            sender: self.sender,
+            to: self.transaction.to(),
+            max_priority_fee_per_gas: self
+                .transaction
+                .max_priority_fee_per_gas()
+                .or_else(|| self.transaction.gas_price()),
+            origin: self.origin,
+        }
+    }
+}
+
+/// Returns the metadata of the pooled transaction for the propagation policy.
+fn pool_transaction_candidate<T: PoolTransaction>(
+    tx: &ValidPoolTransaction<T>,
+) -> PropagationCandidate {
+    PropagationCandidate {
+        hash: *tx.hash(),
+        tx_type: tx.tx_type(),
+        sender: Some(tx.sender()),
+        to: tx.to(),
+        max_priority_fee_per_gas: tx
+            .transaction
+            .max_priority_fee_per_gas()
+            .or_else(|| tx.transaction.gas_price()),
+        origin: Some(tx.origin),
+    }
 }
 
 /// Helper type to construct the appropriate message to send to the peer based on whether the peer
@@ -2867,6 +2951,161 @@ mod tests {
         assert!(propagated.0.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_gossip_filter_propagation() {
+        reth_tracing::init_test_tracing();
+
+        let mut factory = MockTransactionFactory::default();
+        let eip1559_tx = Arc::new(factory.create_eip1559());
+        let eip4844_tx = Arc::new(factory.create_eip4844());
+        let blocked_tx = Arc::new(factory.create_eip1559());
+
+        let allowed_peer = PeerId::random();
+        let other_peer = PeerId::random();
+
+        let propagation_policy = TransactionGossipFilter::new(TransactionPropagationKind::All)
+            .block_tx_type(eip4844_tx.transaction.tx_type())
+            .block_sender(blocked_tx.sender())
+            .allow_peer(allowed_peer);
+        let policy_bundle =
+            NetworkPolicies::new(propagation_policy, StrictEthAnnouncementFilter::default());
+
+        let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+        let network_config = NetworkConfigBuilder::new(secret_key)
+            .listener_port(0)
+            .disable_discovery()
+            .build(NoopProvider::default());
+        let mut network_manager = NetworkManager::new(network_config).await.unwrap();
+        let (to_tx_manager_tx, from_network_rx) =
+            mpsc::unbounded_channel::<NetworkTransactionEvent<EthNetworkPrimitives>>();
+        network_manager.set_transactions(to_tx_manager_tx);
+        let network_handle = network_manager.handle().clone();
+        tokio::spawn(network_manager);
+
+        let mut tx_manager = TransactionsManager::with_policy(
+            network_handle,
+            testing_pool(),
+            from_network_rx,
+            TransactionsManagerConfig::default(),
+            policy_bundle,
+        );
+
+        let (allowed_peer_metadata, _allowed_rx) =
+            new_mock_session(allowed_peer, EthVersion::Eth68);
+        tx_manager.peers.insert(allowed_peer, allowed_peer_metadata);
+        let (other_peer_metadata, _other_rx) = new_mock_session(other_peer, EthVersion::Eth68);
+        tx_manager.peers.insert(other_peer, other_peer_metadata);
+
+        let propagate = vec![
+            PropagateTransaction::pool_tx(eip1559_tx.clone()),
+            PropagateTransaction::pool_tx(eip4844_tx.clone()),
+            PropagateTransaction::pool_tx(blocked_tx.clone()),
+        ];
+        let propagated = tx_manager.propagate_transactions(propagate, PropagationMode::Basic);
+
+        // only the unfiltered transaction is propagated, and only to the allowed peer
+        assert_eq!(propagated.0.len(), 1);
+        let prop_txs = propagated.0.get(eip1559_tx.transaction.hash()).unwrap();
+        assert_eq!(prop_txs.len(), 1);
+
+        assert!(tx_manager.peers[&allowed_peer]
+            .seen_transactions
+            .contains(eip1559_tx.transaction.hash()));
+        assert!(!tx_manager.peers[&other_peer]
+            .seen_transactions
+            .contains(eip1559_tx.transaction.hash()));
+    }
+
+    #[tokio::test]
+    async fn test_gossip_filter_pooled_transactions_request() {
+        reth_tracing::init_test_tracing();
+
+        let tx = MockTransaction::eip1559();
+        let blocked_tx = MockTransaction::eip1559();
+        let private_tx = MockTransaction::eip1559();
+
+        let propagation_policy = TransactionGossipFilter::new(TransactionPropagationKind::All)
+            .block_sender(blocked_tx.sender());
+        let policy_bundle =
+            NetworkPolicies::new(propagation_policy, StrictEthAnnouncementFilter::default());
+
+        let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+        let network_config = NetworkConfigBuilder::new(secret_key)
+            .listener_port(0)
+            .disable_discovery()
+            .build(NoopProvider::default());
+        let mut network_manager = NetworkManager::new(network_config).await.unwrap();
+        let (to_tx_manager_tx, from_network_rx) =
+            mpsc::unbounded_channel::<NetworkTransactionEvent<EthNetworkPrimitives>>();
+        network_manager.set_transactions(to_tx_manager_tx);
+        let network_handle = network_manager.handle().clone();
+        tokio::spawn(network_manager);
+
+        let pool = testing_pool();
+        pool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+        pool.add_transaction(TransactionOrigin::External, blocked_tx.clone()).await.unwrap();
+        pool.add_transaction(TransactionOrigin::Private, private_tx.clone()).await.unwrap();
+
+        let mut tx_manager = TransactionsManager::with_policy(
+            network_handle,
+            pool,
+            from_network_rx,
+            TransactionsManagerConfig::default(),
+            policy_bundle,
+        );
+
+        let peer_id = PeerId::random();
+        let (peer, _rx) = new_mock_session(peer_id, EthVersion::Eth68);
+        tx_manager.peers.insert(peer_id, peer);
+
+        let (send, receive) =
+            oneshot::channel::<RequestResult<PooledTransactions<PooledTransactionVariant>>>();
+        tx_manager.on_get_pooled_transactions(
+            peer_id,
+            GetPooledTransactions(vec![
+                *tx.get_hash(),
+                *blocked_tx.get_hash(),
+                *private_tx.get_hash(),
+            ]),
+            send,
+        );
+
+        // neither the filtered nor the private transaction is served
+        let PooledTransactions(transactions) = receive.await.unwrap().unwrap();
+        assert_eq!(
+            transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>(),
+            vec![*tx.get_hash()]
+        );
+        assert!(!tx_manager.peers[&peer_id].seen_transactions.contains(blocked_tx.get_hash()));
+    }
+
+    #[test]
+    fn test_announcement_gossip_filter() {
+        let allowed_peer = PeerId::random();
+        let filter = AnnouncementGossipFilter::new(StrictEthAnnouncementFilter::default())
+            .ignore_tx_type(3)
+            .with_max_size(1024)
+            .allow_peer(allowed_peer);
+        let hash = B256::random();
+
+        assert!(matches!(
+            filter.decide_on_peer_announcement(&allowed_peer, 2, &hash, 100),
+            AnnouncementAcceptance::Accept
+        ));
+        assert!(matches!(
+            filter.decide_on_peer_announcement(&allowed_peer, 3, &hash, 100),
+            AnnouncementAcceptance::Ignore
+        ));
+        assert!(matches!(
+            filter.decide_on_peer_announcement(&allowed_peer, 2, &hash, 2048),
+            AnnouncementAcceptance::Ignore
+        ));
+        assert!(matches!(
+            filter.decide_on_peer_announcement(&PeerId::random(), 2, &hash, 100),
+            AnnouncementAcceptance::Ignore
+        ));
+    }
+
     #[tokio::test]
     async fn test_relaxed_filter_ignores_unknown_tx_types() {
         reth_tracing::init_test_tracing();
@@ -12,10 +12,12 @@ use reth_node_core::node_config::NodeConfig;
 use reth_node_types::{NodeTypes, NodeTypesWithDBAdapter, TxTy};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_provider::FullProvider;
+use reth_stages_types::{NodeEventRecord, SyncStatus};
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 use std::{fmt::Debug, future::Future, marker::PhantomData};
+use tokio::sync::{broadcast, watch};
 
 /// A helper trait that is downstream of the [`NodeTypes`] trait and adds stateful
 /// components to the node.
@@ -118,6 +120,10 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub engine_events: EventSender<BeaconConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Detailed sync progress of the node.
+    pub sync_status: watch::Receiver<SyncStatus>,
+    /// Machine-readable events of the node.
+    pub node_events: broadcast::Sender<NodeEventRecord>,
 }
 
 /// Customizable node add-on types.
@@ -32,13 +32,14 @@
 use crate::{
     components::{NodeComponents, NodeComponentsBuilder},
     hooks::OnComponentInitializedHook,
-    BuilderContext, ExExLauncher, NodeAdapter, PrimitivesTy,
+    BuilderContext, ExExLauncher, ExExRegistry, NodeAdapter, PrimitivesTy,
 };
 use alloy_consensus::BlockHeader as _;
 use alloy_eips::eip2124::Head;
 use alloy_primitives::{BlockNumber, B256};
 use eyre::Context;
 use rayon::ThreadPoolBuilder;
+use reth_chain_state::ForkChoiceSubscriptions;
 use reth_chainspec::{Chain, EthChainSpec, EthereumHardfork, EthereumHardforks};
 use reth_config::{config::EtlConfig, PruneConfig};
 use reth_consensus::noop::NoopConsensus;
@@ -47,7 +48,6 @@ use reth_db_common::init::{init_genesis, InitStorageError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_local::MiningMode;
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
-use reth_exex::ExExManagerHandle;
 use reth_fs_util as fs;
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
@@ -79,21 +79,29 @@ use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
 use reth_stages::{
     sets::DefaultStages, stages::EraImportSource, MetricEvent, PipelineBuilder, PipelineTarget,
-    StageId,
+    StageCheckpoint, StageId,
 };
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info, warn};
 use reth_transaction_pool::TransactionPool;
-use std::{sync::Arc, thread::available_parallelism};
+use std::{net::SocketAddr, sync::Arc, thread::available_parallelism};
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedSender},
     oneshot, watch,
 };
 
 use futures::{future::Either, stream, Stream, StreamExt};
+use reth_era_utils::{known_roots, EraFileServer};
 use reth_node_ethstats::EthStatsService;
-use reth_node_events::{cl::ConsensusLayerHealthEvents, node::NodeEvent};
+use reth_node_events::{
+    cl::ConsensusLayerHealthEvents,
+    export::{self, NodeEventsSender},
+    node::NodeEvent,
+};
+
+/// Default folder name of the served ERA1 files, shared with `reth export-era`.
+const ERA1_EXPORT_FOLDER_NAME: &str = "era1-export";
 
 /// Reusable setup for launching a node.
 ///
@@ -467,13 +475,16 @@ where
         N: ProviderNodeTypes<DB = DB, ChainSpec = ChainSpec>,
         Evm: ConfigureEvm<Primitives = N::Primitives> + 'static,
     {
-        let factory = ProviderFactory::new(
+        let mut factory = ProviderFactory::new(
             self.right().clone(),
             self.chain_spec(),
             StaticFileProvider::read_write(self.data_dir().static_files())?,
         )
         .with_prune_modes(self.prune_modes())
         .with_static_files_metrics();
+        if self.node_config().datadir.verify_static_file_checksums {
+            factory = factory.with_static_file_checksum_verification();
+        }
 
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
@@ -947,6 +958,19 @@ where
         Ok(None)
     }
 
+    /// Returns the checkpoints of all stages in pipeline order, defaulting to the genesis block for
+    /// stages that never ran.
+    pub fn stage_checkpoints(&self) -> ProviderResult<Vec<(StageId, StageCheckpoint)>> {
+        StageId::ALL
+            .into_iter()
+            .map(|stage_id| {
+                let checkpoint =
+                    self.blockchain_db().get_stage_checkpoint(stage_id)?.unwrap_or_default();
+                Ok((stage_id, checkpoint))
+            })
+            .collect()
+    }
+
     /// Expire the pre-merge transactions if the node is configured to do so and the chain has a
     /// merge block.
     ///
@@ -987,7 +1011,7 @@ where
         &self.node_adapter().components
     }
 
-    /// Launches ExEx (Execution Extensions) and returns the ExEx manager handle.
+    /// Launches ExEx (Execution Extensions) and returns the registry of the ExEx manager.
     #[allow(clippy::type_complexity)]
     pub async fn launch_exex(
         &self,
@@ -995,7 +1019,7 @@ where
             String,
             Box<dyn crate::exex::BoxedLaunchExEx<NodeAdapter<T, CB::Components>>>,
         )>,
-    ) -> eyre::Result<Option<ExExManagerHandle<PrimitivesTy<T::Types>>>> {
+    ) -> eyre::Result<Option<ExExRegistry<NodeAdapter<T, CB::Components>>>> {
         ExExLauncher::new(
             self.head(),
             self.node_adapter().clone(),
@@ -1009,6 +1033,7 @@ where
     /// Creates the ERA import source based on node configuration.
     ///
     /// Returns `Some(EraImportSource)` if ERA is enabled in the node config, otherwise `None`.
+    /// The files are verified against the known accumulator roots of the chain, if any.
     pub fn era_import_source(&self) -> Option<EraImportSource> {
         let node_config = self.node_config();
         if !node_config.era.enabled {
@@ -1021,6 +1046,7 @@ where
             || node_config.chain.chain().kind().default_era_host(),
             || node_config.datadir().data_dir().join("era").into(),
         )
+        .map(|source| source.with_known_roots(known_roots(node_config.chain.chain())))
     }
 
     /// Creates consensus layer health events stream based on node configuration.
@@ -1061,6 +1087,80 @@ where
 
         Ok(())
     }
+
+    /// Spawns the [`EraFileServer`] if configured.
+    ///
+    /// The server keeps exporting new ERA1 files as blocks finalize.
+    pub async fn spawn_era_server(&self) -> eyre::Result<()> {
+        let serve = &self.node_config().era.serve;
+        if !serve.enabled {
+            return Ok(())
+        }
+
+        let dir = serve
+            .path
+            .clone()
+            .unwrap_or_else(|| self.data_dir().data_dir().join(ERA1_EXPORT_FOLDER_NAME));
+        let server = EraFileServer::new(dir).await?;
+
+        let addr = SocketAddr::new(serve.addr, serve.port);
+        let handle = server.clone().start(addr, self.task_executor()).await?;
+        info!(target: "reth::cli", url=%handle.local_addr(), dir=%server.dir().display(), "ERA1 file server started");
+
+        let provider = self.blockchain_db().clone();
+        let finalized = provider.finalized_block_stream().map(|header| header.number());
+        let network = self.node_config().chain.chain().to_string();
+        self.task_executor().spawn(server.export_finalized(provider, network, finalized));
+
+        Ok(())
+    }
+
+    /// Spawns the exports of the node events configured with `--events.file` and
+    /// `--events.socket`.
+    pub fn spawn_node_events_exports(&self, node_events: &NodeEventsSender) -> eyre::Result<()> {
+        let args = &self.node_config().events;
+
+        if let Some(path) = &args.file {
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|err| fs::FsPathError::open(err, path))?;
+            let events = node_events.subscribe();
+            self.task_executor().spawn(Box::pin(async move {
+                if let Err(err) =
+                    export::write_events(tokio::fs::File::from_std(file), events).await
+                {
+                    error!(target: "reth::cli", %err, "Failed to write node events");
+                }
+            }));
+            info!(target: "reth::cli", path=%path.display(), "Writing node events to file");
+        }
+
+        if let Some(path) = &args.socket {
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::FileTypeExt;
+
+                // The socket of a previous run would fail the bind, anything else is left alone
+                if let Ok(metadata) = std::fs::symlink_metadata(path) {
+                    if !metadata.file_type().is_socket() {
+                        eyre::bail!("--events.socket {path:?} exists and is not a socket");
+                    }
+                    fs::remove_file(path)?;
+                }
+                let listener = tokio::net::UnixListener::bind(path)
+                    .wrap_err_with(|| format!("Could not bind node events socket {path:?}"))?;
+                self.task_executor()
+                    .spawn(Box::pin(export::serve_events(listener, node_events.clone())));
+                info!(target: "reth::cli", path=%path.display(), "Serving node events on unix socket");
+            }
+            #[cfg(not(unix))]
+            eyre::bail!("--events.socket is only supported on unix, got {path:?}");
+        }
+
+        Ok(())
+    }
 }
 
 impl<T, CB>
@@ -1220,6 +1320,9 @@ mod tests {
                     bodies_distance: None,
                     receipts_log_filter: None,
                     bodies_before: None,
+                    trie_changesets_full: false,
+                    trie_changesets_distance: None,
+                    trie_changesets_before: None,
                 },
                 ..NodeConfig::test()
             };
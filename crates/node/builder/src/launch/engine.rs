@@ -4,7 +4,7 @@ use crate::{
     common::{Attached, LaunchContextWith, WithConfigs},
     hooks::NodeHooks,
     launch::invalid_block_hook::InvalidBlockHookExt,
-    rpc::{EngineValidatorAddOn, RethRpcAddOns, RpcHandle},
+    rpc::{EngineValidatorAddOn, RethRpcAddOns, RpcContext, RpcHandle},
     setup::build_networked_pipeline,
     AddOns, AddOnsContext, FullNode, LaunchContext, LaunchNode, NodeAdapter,
     NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle, NodeTypesAdapter,
@@ -18,31 +18,42 @@ use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
     tree::{BasicEngineValidator, TreeConfig},
 };
-use reth_engine_util::EngineMessageStreamExt;
-use reth_exex::ExExManagerHandle;
+use reth_engine_util::{
+    engine_replay::{EngineMessageReplayer, EngineReplayReport, ReplayedMessageKind},
+    engine_store::EngineMessageStore,
+    EngineMessageStreamExt,
+};
+use reth_exex::{ExExAdminApiServer, ExExAdminRpc, ExExManagerHandle};
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
     BeaconConsensusEngineHandle, BuiltPayload, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
+    PayloadTypes,
 };
 use reth_node_core::{
+    args::EngineApiReplayMode,
     dirs::{ChainPath, DataDirPath},
     exit::NodeExitFuture,
     primitives::Head,
 };
-use reth_node_events::node;
+use reth_node_events::{export::NodeEventsSender, node, sync_status::SyncStatusSender};
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     BlockNumReader,
 };
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
-use reth_tracing::tracing::{debug, error, info};
+use reth_tracing::tracing::{debug, error, info, warn};
 use std::sync::Arc;
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 /// The engine node launcher.
+///
+/// When `--debug.engine-api-replay <PATH>` is provided, the engine API messages recorded with
+/// `--debug.engine-api-store` are submitted to the engine once the node is launched, paced
+/// according to `--debug.engine-api-replay-mode`. The latency of every message is logged, followed
+/// by a summary once all messages were replayed.
 #[derive(Debug)]
 pub struct EngineNodeLauncher {
     /// The task executor for the node.
@@ -87,7 +98,7 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, mut add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -128,7 +139,33 @@ where
         ctx.expire_pre_merge_transactions()?;
 
         // spawn exexs if any
-        let maybe_exex_manager_handle = ctx.launch_exex(installed_exex).await?;
+        let exex_registry = ctx.launch_exex(installed_exex).await?;
+        let maybe_exex_manager_handle =
+            exex_registry.as_ref().map(|registry| registry.manager_handle().clone());
+
+        // serve the `exex` namespace on the local IPC and the authenticated servers only, ahead of
+        // the configured rpc modules hook
+        if let Some(exex_manager_handle) = maybe_exex_manager_handle.clone() {
+            let hooks = add_ons.hooks_mut();
+            let extend_rpc_modules = std::mem::replace(&mut hooks.extend_rpc_modules, Box::new(()));
+            hooks.set_extend_rpc_modules(
+                move |ctx: RpcContext<'_, NodeAdapter<T, CB::Components>, AO::EthApi>| {
+                    let exex_admin = ExExAdminRpc::new(exex_manager_handle).into_rpc();
+                    ctx.modules.merge_ipc(exex_admin.clone())?;
+                    ctx.auth_module.merge_auth_methods(exex_admin)?;
+                    extend_rpc_modules.extend_rpc_modules(ctx)
+                },
+            );
+        }
+
+        // the log index is updated by the persistence service if enabled
+        let index_logs_config = ctx.toml_config().stages.index_logs;
+        if index_logs_config.enabled {
+            info!(target: "reth::cli", "Log index enabled");
+        }
+        let engine_tree_config = engine_tree_config.with_log_index_commit_threshold(
+            index_logs_config.enabled.then_some(index_logs_config.commit_threshold),
+        );
 
         // create pipeline
         let network_handle = ctx.components().network().clone();
@@ -178,6 +215,10 @@ where
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
         let event_sender = EventSender::default();
+        let sync_status = SyncStatusSender::new();
+        sync_status.on_stage_checkpoints(ctx.stage_checkpoints()?);
+        let node_events = NodeEventsSender::default();
+        ctx.spawn_node_events_exports(&node_events)?;
 
         let beacon_engine_handle = BeaconConsensusEngineHandle::new(consensus_engine_tx.clone());
 
@@ -190,6 +231,8 @@ where
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            sync_status: sync_status.subscribe(),
+            node_events: node_events.inner().clone(),
         };
         let engine_payload_validator = add_ons.engine_validator(&add_ons_ctx).await?;
 
@@ -246,10 +289,12 @@ where
 
         ctx.task_executor().spawn_critical(
             "events task",
-            Box::pin(node::handle_events(
+            Box::pin(node::handle_events_with_publishers(
                 Some(Box::new(ctx.components().network().clone())),
                 Some(ctx.head().number),
                 events,
+                sync_status.clone(),
+                node_events,
             )),
         );
 
@@ -295,12 +340,14 @@ where
                         debug!(target: "reth::cli", "Event: {event}");
                         match event {
                             ChainEvent::BackfillSyncFinished => {
+                                sync_status.on_backfill_sync_finished();
                                 if terminate_after_backfill {
                                     debug!(target: "reth::cli", "Terminating after initial backfill");
                                     break
                                 }
                             }
                             ChainEvent::BackfillSyncStarted => {
+                                sync_status.on_backfill_sync_started();
                                 network_handle.update_sync_state(SyncState::Syncing);
                             }
                             ChainEvent::FatalError => {
@@ -347,6 +394,7 @@ where
             task_executor: ctx.task_executor().clone(),
             config: ctx.node_config().clone(),
             data_dir: ctx.data_dir().clone(),
+            exex_registry,
             add_ons_handle: RpcHandle {
                 rpc_server_handles,
                 rpc_registry,
@@ -357,7 +405,21 @@ where
         // Notify on node started
         on_node_started.on_event(FullNode::clone(&full_node))?;
 
+        if let Some(replay_path) = full_node.config.debug.engine_api_replay.clone() {
+            let mode = full_node.config.debug.engine_api_replay_mode;
+            let replayer = EngineMessageReplayer::new(
+                &EngineMessageStore::new(replay_path.clone()),
+                full_node.add_ons_handle.beacon_engine_handle.clone(),
+            )?;
+            info!(target: "reth::cli", ?replay_path, %mode, messages = replayer.remaining(), "Replaying stored engine API messages");
+
+            ctx.task_executor().spawn_critical("engine api replay", async move {
+                replay_engine_messages(replayer, mode).await
+            });
+        }
+
         ctx.spawn_ethstats().await?;
+        ctx.spawn_era_server().await?;
 
         let handle = NodeHandle {
             node_exit_future: NodeExitFuture::new(
@@ -370,3 +432,70 @@ where
         Ok(handle)
     }
 }
+
+/// Replays all messages of the [`EngineMessageReplayer`] with the given pacing and logs the latency
+/// of every message, followed by a summary once all messages were replayed.
+async fn replay_engine_messages<T: PayloadTypes>(
+    mut replayer: EngineMessageReplayer<T>,
+    mode: EngineApiReplayMode,
+) {
+    let mut report = EngineReplayReport::default();
+    loop {
+        match mode {
+            EngineApiReplayMode::Original => {
+                if let Some(delay) = replayer.next_delay() {
+                    tokio::time::sleep(delay).await;
+                }
+            }
+            EngineApiReplayMode::Fast => {}
+            EngineApiReplayMode::Step => {
+                if replayer.remaining() == 0 {
+                    break
+                }
+                info!(target: "reth::cli", remaining = replayer.remaining(), "Press enter to replay the next engine API message");
+                let line =
+                    tokio::task::spawn_blocking(|| std::io::stdin().read_line(&mut String::new()))
+                        .await;
+                if !matches!(line, Ok(Ok(read)) if read > 0) {
+                    warn!(target: "reth::cli", "Stdin closed, stopping engine API replay");
+                    break
+                }
+            }
+        }
+
+        let Some(result) = replayer.replay_next().await else { break };
+        match result {
+            Ok(message) => {
+                info!(
+                    target: "reth::cli",
+                    kind = ?message.kind,
+                    block_hash = %message.block_hash,
+                    status = ?message.status,
+                    latency = ?message.latency,
+                    "Replayed engine API message"
+                );
+                report.on_message(&message);
+            }
+            Err(error) => {
+                error!(target: "reth::cli", %error, "Failed to replay engine API message");
+            }
+        }
+    }
+
+    for kind in [ReplayedMessageKind::NewPayload, ReplayedMessageKind::ForkchoiceUpdated] {
+        if let Some(latencies) = report.latencies(kind) {
+            info!(
+                target: "reth::cli",
+                ?kind,
+                count = latencies.count,
+                min = ?latencies.min,
+                p50 = ?latencies.p50,
+                p99 = ?latencies.p99,
+                max = ?latencies.max,
+                mean = ?latencies.mean,
+                "Engine API replay latencies"
+            );
+        }
+    }
+    info!(target: "reth::cli", invalid = report.invalid(), "Engine API replay finished");
+}
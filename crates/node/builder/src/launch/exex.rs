@@ -5,16 +5,19 @@ use futures::future;
 use reth_chain_state::ForkChoiceSubscriptions;
 use reth_chainspec::EthChainSpec;
 use reth_exex::{
-    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotificationSource, Wal,
-    DEFAULT_EXEX_MANAGER_CAPACITY,
+    ExExContext, ExExControlError, ExExHandle, ExExManager, ExExManagerHandle,
+    ExExNotificationSource, RemoteExEx, RemoteExExConfig, Wal, DEFAULT_EXEX_MANAGER_CAPACITY,
 };
 use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
-use reth_provider::CanonStateSubscriptions;
-use reth_tracing::tracing::{debug, info};
-use std::{fmt, fmt::Debug};
+use reth_provider::{BlockNumReader, CanonStateSubscriptions};
+use reth_tracing::tracing::{debug, error, info, warn};
+use std::{fmt, fmt::Debug, future::Future};
 use tracing::Instrument;
 
-use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
+use crate::{
+    common::WithConfigs,
+    exex::{BoxedLaunchExEx, LaunchExEx},
+};
 
 /// Can launch execution extensions.
 pub struct ExExLauncher<Node: FullNodeComponents> {
@@ -37,53 +40,45 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
 
     /// Launches all execution extensions.
     ///
-    /// Spawns all extensions and returns the handle to the exex manager if any extensions are
-    /// installed.
-    pub async fn launch(
-        self,
-    ) -> eyre::Result<Option<ExExManagerHandle<PrimitivesTy<Node::Types>>>> {
-        let Self { head, extensions, components, config_container } = self;
+    /// Spawns all extensions and returns the [`ExExRegistry`] of the running exex manager if any
+    /// extensions are installed.
+    pub async fn launch(self) -> eyre::Result<Option<ExExRegistry<Node>>> {
+        let Self { head, mut extensions, components, config_container } = self;
         let head = BlockNumHash::new(head.number, head.hash);
 
+        // serve the notifications to an out-of-process client if configured
+        if let Some(remote) = RemoteExExConfig::from_args(&config_container.config.exex)? {
+            extensions.push((
+                "remote".to_string(),
+                Box::new(move |ctx: ExExContext<Node>| launch_remote_exex(remote, ctx)),
+            ));
+        }
+
         if extensions.is_empty() {
             // nothing to launch
             return Ok(None)
         }
 
         info!(target: "reth::cli", "Loading ExEx Write-Ahead Log...");
-        let exex_wal = Wal::new(
+        let exex_wal = Wal::with_config(
             config_container
                 .config
                 .datadir
                 .clone()
                 .resolve_datadir(config_container.config.chain.chain())
                 .exex_wal(),
+            (&config_container.config.exex).into(),
         )?;
 
         let mut exex_handles = Vec::with_capacity(extensions.len());
         let mut exexes = Vec::with_capacity(extensions.len());
 
         for (id, exex) in extensions {
-            // create a new exex handle
-            let (handle, events, notifications) = ExExHandle::new(
-                id.clone(),
-                head,
-                components.provider().clone(),
-                components.evm_config().clone(),
-                exex_wal.handle(),
-            );
+            let (handle, context) =
+                new_exex(id.clone(), head, &components, &config_container, &exex_wal);
+            let removed = handle.removed();
             exex_handles.push(handle);
 
-            // create the launch context for the exex
-            let context = ExExContext {
-                head,
-                config: config_container.config.clone(),
-                reth_config: config_container.toml_config.clone(),
-                components: components.clone(),
-                events,
-                notifications,
-            };
-
             let executor = components.task_executor().clone();
             exexes.push(async move {
                 debug!(target: "reth::cli", id, "spawning exex");
@@ -97,9 +92,14 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                     "exex",
                     async move {
                         info!(target: "reth::cli", "ExEx started");
-                        match exex.await {
-                            Ok(_) => panic!("ExEx {id} finished. ExExes should run indefinitely"),
-                            Err(err) => panic!("ExEx {id} crashed: {err}"),
+                        tokio::select! {
+                            res = exex => match res {
+                                Ok(_) => panic!("ExEx {id} finished. ExExes should run indefinitely"),
+                                Err(err) => panic!("ExEx {id} crashed: {err}"),
+                            },
+                            _ = removed.cancelled() => {
+                                info!(target: "reth::cli", "ExEx removed from the manager, stopping");
+                            }
                         }
                     }
                     .instrument(span),
@@ -115,9 +115,13 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
             components.provider().clone(),
             exex_handles,
             DEFAULT_EXEX_MANAGER_CAPACITY,
-            exex_wal,
+            exex_wal.clone(),
             components.provider().finalized_block_stream(),
         );
+        let exex_manager = match config_container.toml_config.exex.max_lag {
+            Some(max_lag) => exex_manager.with_max_lag(max_lag),
+            None => exex_manager,
+        };
         let exex_manager_handle = exex_manager.handle();
         components.task_executor().spawn_critical("exex manager", async move {
             exex_manager.await.expect("exex manager crashed");
@@ -140,8 +144,150 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
 
         info!(target: "reth::cli", "ExEx Manager started");
 
-        Ok(Some(exex_manager_handle))
+        Ok(Some(ExExRegistry {
+            components,
+            config_container,
+            wal: exex_wal,
+            manager_handle: exex_manager_handle,
+        }))
+    }
+}
+
+/// Installs execution extensions into the running node.
+///
+/// The registry is returned by [`ExExLauncher::launch`] if the node was launched with at least one
+/// `ExEx`, because the exex manager is only started in that case.
+///
+/// `ExEx`'s installed through the registry can be paused, resumed and removed through the
+/// [`ExExManagerHandle`] like the ones installed at launch. Unlike them, an `ExEx` installed at
+/// runtime that finishes or crashes is removed from the manager instead of shutting down the node.
+pub struct ExExRegistry<Node: FullNodeComponents> {
+    components: Node,
+    config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+    wal: Wal<PrimitivesTy<Node::Types>>,
+    manager_handle: ExExManagerHandle<PrimitivesTy<Node::Types>>,
+}
+
+impl<Node: FullNodeComponents> ExExRegistry<Node> {
+    /// Returns the handle to the running exex manager.
+    pub const fn manager_handle(&self) -> &ExExManagerHandle<PrimitivesTy<Node::Types>> {
+        &self.manager_handle
     }
+
+    /// Launches the `ExEx` with the given ID and installs it in the running exex manager.
+    ///
+    /// The `ExEx` is launched with the current head of the node, and receives all notifications
+    /// sent to the manager after it was installed.
+    pub async fn install_exex(
+        &self,
+        id: impl Into<String>,
+        exex: impl LaunchExEx<Node> + 'static,
+    ) -> eyre::Result<()> {
+        let id = id.into();
+        if self.manager_handle.exexs().await?.iter().any(|info| info.id == id) {
+            return Err(ExExControlError::AlreadyExists(id).into())
+        }
+
+        let chain_info = self.components.provider().chain_info()?;
+        let head = BlockNumHash::new(chain_info.best_number, chain_info.best_hash);
+        let (handle, context) =
+            new_exex(id.clone(), head, &self.components, &self.config_container, &self.wal);
+        let removed = handle.removed();
+
+        debug!(target: "reth::cli", id, "installing exex");
+        let span = reth_tracing::tracing::info_span!("exex", id);
+        let exex: Box<dyn BoxedLaunchExEx<Node>> = Box::new(exex);
+        let exex = exex.launch(context).instrument(span.clone()).await?;
+        self.manager_handle.add_exex(handle).await?;
+
+        let manager_handle = self.manager_handle.clone();
+        self.components.task_executor().spawn(
+            async move {
+                info!(target: "reth::cli", "ExEx started");
+                tokio::select! {
+                    res = exex => {
+                        match res {
+                            Ok(()) => warn!(target: "reth::cli", "ExEx finished"),
+                            Err(err) => error!(target: "reth::cli", %err, "ExEx crashed"),
+                        }
+                        let _ = manager_handle.remove_exex(id).await;
+                    }
+                    _ = removed.cancelled() => {
+                        info!(target: "reth::cli", "ExEx removed from the manager, stopping");
+                    }
+                }
+            }
+            .instrument(span),
+        );
+
+        Ok(())
+    }
+}
+
+impl<Node: FullNodeComponents> Clone for ExExRegistry<Node> {
+    fn clone(&self) -> Self {
+        Self {
+            components: self.components.clone(),
+            config_container: self.config_container.clone(),
+            wal: self.wal.clone(),
+            manager_handle: self.manager_handle.clone(),
+        }
+    }
+}
+
+impl<Node: FullNodeComponents> Debug for ExExRegistry<Node> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExExRegistry")
+            .field("components", &"...")
+            .field("config_container", &self.config_container)
+            .field("wal", &self.wal)
+            .field("manager_handle", &self.manager_handle)
+            .finish()
+    }
+}
+
+/// Creates the manager handle and the launch context of the `ExEx` with the given ID, starting
+/// from the given head.
+fn new_exex<Node: FullNodeComponents>(
+    id: String,
+    head: BlockNumHash,
+    components: &Node,
+    config_container: &WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+    wal: &Wal<PrimitivesTy<Node::Types>>,
+) -> (ExExHandle<PrimitivesTy<Node::Types>>, ExExContext<Node>) {
+    let (handle, events, notifications) = ExExHandle::new(
+        id.clone(),
+        head,
+        components.provider().clone(),
+        components.evm_config().clone(),
+        wal.handle(),
+    );
+    // persist the finished height, so the exex can resume from it after a restart
+    let handle = handle.with_backfill_checkpoint(components.provider().clone());
+    let mut notifications = notifications.with_backfill_checkpoint(id);
+    if let Some(max_blocks_per_second) = config_container.config.exex.backfill_max_blocks_per_second
+    {
+        notifications = notifications.with_backfill_max_blocks_per_second(max_blocks_per_second);
+    }
+
+    let context = ExExContext {
+        head,
+        config: config_container.config.clone(),
+        reth_config: config_container.toml_config.clone(),
+        components: components.clone(),
+        events,
+        notifications,
+    };
+
+    (handle, context)
+}
+
+/// Launches the [`RemoteExEx`] server with the given configuration.
+async fn launch_remote_exex<Node: FullNodeComponents>(
+    config: RemoteExExConfig,
+    ctx: ExExContext<Node>,
+) -> eyre::Result<impl Future<Output = eyre::Result<()>>> {
+    Ok(RemoteExEx::new(config).serve(ctx))
 }
 
 impl<Node: FullNodeComponents> Debug for ExExLauncher<Node> {
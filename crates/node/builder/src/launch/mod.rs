@@ -8,7 +8,7 @@ pub(crate) mod debug;
 pub(crate) mod engine;
 
 pub use common::LaunchContext;
-pub use exex::ExExLauncher;
+pub use exex::{ExExLauncher, ExExRegistry};
 
 use std::future::Future;
 
@@ -1,7 +1,9 @@
 // re-export the node api types
 pub use reth_node_api::{FullNodeTypes, NodeTypes};
 
-use crate::{components::NodeComponentsBuilder, rpc::RethRpcAddOns, NodeAdapter, NodeAddOns};
+use crate::{
+    components::NodeComponentsBuilder, rpc::RethRpcAddOns, ExExRegistry, NodeAdapter, NodeAddOns,
+};
 use reth_node_api::{EngineTypes, FullNodeComponents, PayloadTypes};
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -119,6 +121,8 @@ pub struct FullNode<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     pub config: NodeConfig<<Node::Types as NodeTypes>::ChainSpec>,
     /// The data dir of the node.
     pub data_dir: ChainPath<DataDirPath>,
+    /// The registry to install `ExEx`'s at runtime, if the node was launched with any `ExEx`'s.
+    pub exex_registry: Option<ExExRegistry<Node>>,
     /// The handle to launched add-ons
     pub add_ons_handle: AddOns::Handle,
 }
@@ -134,6 +138,7 @@ impl<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> Clone for FullNode<Node
             task_executor: self.task_executor.clone(),
             config: self.config.clone(),
             data_dir: self.data_dir.clone(),
+            exex_registry: self.exex_registry.clone(),
             add_ons_handle: self.add_ons_handle.clone(),
         }
     }
@@ -6,8 +6,9 @@ pub use reth_rpc_builder::{middleware::RethRpcMiddleware, Identity};
 use crate::{BeaconConsensusEngineEvent, BeaconConsensusEngineHandle};
 use alloy_rpc_types::engine::ClientVersionV1;
 use alloy_rpc_types_engine::ExecutionData;
+use futures::StreamExt;
 use jsonrpsee::{core::middleware::layer::Either, RpcModule};
-use reth_chain_state::CanonStateSubscriptions;
+use reth_chain_state::{CanonStateNotification, CanonStateSubscriptions};
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineTypes, EngineValidator, FullNodeComponents, FullNodeTypes,
@@ -18,22 +19,34 @@ use reth_node_core::{
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
-use reth_rpc::eth::{core::EthRpcConverterFor, EthApiTypes, FullEthApiServer};
-use reth_rpc_api::{eth::helpers::AddDevSigners, IntoEngineApiRpcModule};
+use reth_provider::BlockNumReader;
+use reth_rpc::{
+    eth::{core::EthRpcConverterFor, EthApiTypes, FullEthApiServer},
+    RethNodeEventsApi, RethPayloadApi, RethSyncApi,
+};
+use reth_rpc_api::{
+    eth::helpers::AddDevSigners, IntoEngineApiRpcModule, RethNodeEventsApiServer,
+    RethPayloadApiServer, RethSyncApiServer,
+};
 use reth_rpc_builder::{
+    api_keys::{ApiKeys, API_KEYS_RELOAD_INTERVAL, GRAPHQL_METHOD},
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, Stack,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, Stack,
     TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
+use reth_rpc_graphql::{GraphQlLogFilter, GraphQlServer};
+use reth_rpc_layer::{ResponseCacheConfig, RpcResponseCache};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, info};
 use std::{
     fmt::{self, Debug},
     future::Future,
+    net::SocketAddr,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
 /// Contains the handles to the spawned RPC servers.
@@ -659,8 +672,22 @@ where
             engine_handle,
         } = setup_ctx;
 
-        let server_config = config.rpc.rpc_server_config().set_rpc_middleware(rpc_middleware);
+        let api_keys = Self::load_api_keys(&node, config)?;
+        let server_config = config
+            .rpc
+            .rpc_server_config()
+            .with_api_keys(api_keys.clone())
+            .with_response_cache(Self::spawn_response_cache(&node, config)?)
+            .set_rpc_middleware(rpc_middleware);
         let rpc_server_handle = Self::launch_rpc_server_internal(server_config, &modules).await?;
+        Self::launch_graphql_server_internal(
+            &node,
+            config,
+            registry.eth_api().clone(),
+            Arc::new(registry.eth_handlers().filter.clone()),
+            api_keys,
+        )
+        .await?;
 
         let handles =
             RethRpcServerHandles { rpc: rpc_server_handle.clone(), auth: AuthServerHandle::noop() };
@@ -725,7 +752,13 @@ where
             engine_handle,
         } = setup_ctx;
 
-        let server_config = config.rpc.rpc_server_config().set_rpc_middleware(rpc_middleware);
+        let api_keys = Self::load_api_keys(&node, config)?;
+        let server_config = config
+            .rpc
+            .rpc_server_config()
+            .with_api_keys(api_keys.clone())
+            .with_response_cache(Self::spawn_response_cache(&node, config)?)
+            .set_rpc_middleware(rpc_middleware);
 
         let (rpc, auth) = if disable_auth {
             // Only launch the RPC server, use a noop auth handle
@@ -742,6 +775,15 @@ where
             (rpc, auth)
         };
 
+        Self::launch_graphql_server_internal(
+            &node,
+            config,
+            registry.eth_api().clone(),
+            Arc::new(registry.eth_handlers().filter.clone()),
+            api_keys,
+        )
+        .await?;
+
         let handles = RethRpcServerHandles { rpc, auth };
 
         Self::finalize_rpc_setup(
@@ -774,7 +816,15 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            sync_status,
+            node_events,
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -814,6 +864,25 @@ where
             registry.eth_api().with_dev_accounts();
         }
 
+        modules.merge_if_module_configured(
+            RethRpcModule::Reth,
+            RethPayloadApi::new(PayloadStore::new(node.payload_builder_handle().clone()))
+                .into_rpc(),
+        )?;
+        modules.merge_if_module_configured(
+            RethRpcModule::Reth,
+            RethSyncApi::new(
+                node.provider().clone(),
+                sync_status,
+                Box::new(node.task_executor().clone()),
+            )
+            .into_rpc(),
+        )?;
+        modules.merge_if_module_configured(
+            RethRpcModule::Reth,
+            RethNodeEventsApi::new(node_events, Box::new(node.task_executor().clone())).into_rpc(),
+        )?;
+
         let mut registry = RpcRegistry { registry };
         let ctx = RpcContext {
             node: node.clone(),
@@ -868,6 +937,94 @@ where
         Ok(handle)
     }
 
+    /// Helper to load the API keys of the RPC servers, if configured
+    ///
+    /// This also spawns a task that reloads the keys when the file changes.
+    fn load_api_keys(
+        node: &N,
+        config: &NodeConfig<<N::Types as NodeTypes>::ChainSpec>,
+    ) -> eyre::Result<Option<ApiKeys>> {
+        let Some(path) = &config.rpc.rpc_api_keys else { return Ok(None) };
+
+        let provider = node.provider().clone();
+        let api_keys = ApiKeys::load(path)?
+            .with_latest_block(Arc::new(move || provider.best_block_number().ok()));
+        info!(target: "reth::cli", path=%path.display(), keys=api_keys.len(), "Loaded RPC API keys");
+
+        node.task_executor()
+            .spawn(Box::pin(api_keys.clone().reload_on_change(API_KEYS_RELOAD_INTERVAL)));
+
+        Ok(Some(api_keys))
+    }
+
+    /// Helper to create the RPC response cache, if enabled
+    ///
+    /// This also spawns a task that drops the cached responses of reorged blocks.
+    fn spawn_response_cache(
+        node: &N,
+        config: &NodeConfig<<N::Types as NodeTypes>::ChainSpec>,
+    ) -> eyre::Result<Option<RpcResponseCache>> {
+        let args = &config.rpc.rpc_state_cache;
+        if args.max_response_mb == 0 {
+            return Ok(None)
+        }
+
+        let cache_config = ResponseCacheConfig {
+            max_memory_bytes: (args.max_response_mb * 1024 * 1024) as usize,
+            disk_dir: args.response_dir.clone(),
+            max_disk_bytes: args.max_response_disk_mb * 1024 * 1024,
+            max_response_size: config.rpc.rpc_max_response_size_bytes(),
+            ..Default::default()
+        };
+        let cache = RpcResponseCache::new(cache_config, Arc::new(node.provider().clone()))?;
+
+        let mut notifications = node.provider().canonical_state_stream();
+        let c = cache.clone();
+        node.task_executor().spawn(Box::pin(async move {
+            while let Some(notification) = notifications.next().await {
+                if let CanonStateNotification::Reorg { old, .. } = notification {
+                    let block_hashes =
+                        old.blocks_iter().map(|block| block.hash()).collect::<Vec<_>>();
+                    let c = c.clone();
+                    let _ = tokio::task::spawn_blocking(move || c.invalidate_blocks(block_hashes))
+                        .await;
+                }
+            }
+        }));
+
+        Ok(Some(cache))
+    }
+
+    /// Helper to launch the GraphQL server, if enabled
+    ///
+    /// If API keys are configured, GraphQL requests are checked and charged as
+    /// [`GRAPHQL_METHOD`] calls.
+    async fn launch_graphql_server_internal(
+        node: &N,
+        config: &NodeConfig<<N::Types as NodeTypes>::ChainSpec>,
+        eth_api: EthB::EthApi,
+        log_filter: Arc<dyn GraphQlLogFilter>,
+        api_keys: Option<ApiKeys>,
+    ) -> eyre::Result<()> {
+        if !config.rpc.graphql {
+            return Ok(())
+        }
+
+        let addr = SocketAddr::new(config.rpc.graphql_addr, config.rpc.graphql_port);
+        let mut server = GraphQlServer::new(eth_api, log_filter);
+        if let Some(api_keys) = api_keys {
+            server = server.with_authorizer(Arc::new(move |req| {
+                api_keys
+                    .check_http_request(req, GRAPHQL_METHOD)
+                    .map_err(|rejection| (rejection.http_status(), rejection.to_string()))
+            }));
+        }
+        let handle = server.start(addr, node.task_executor()).await?;
+        info!(target: "reth::cli", url=%handle.local_addr(), "GraphQL server started");
+
+        Ok(())
+    }
+
     /// Helper to launch the auth server
     async fn launch_auth_server_internal(
         auth_module: AuthRpcModule,
@@ -965,6 +1122,7 @@ impl<'a, N: FullNodeComponents<Types: NodeTypes<ChainSpec: EthereumHardforks>>>
             .gas_cap(self.config.rpc_gas_cap.into())
             .max_simulate_blocks(self.config.rpc_max_simulate_blocks)
             .eth_proof_window(self.config.eth_proof_window)
+            .eth_proof_changesets_window(self.config.eth_proof_changesets_window)
             .fee_history_cache_config(self.config.fee_history_cache)
             .proof_permits(self.config.proof_permits)
             .gas_oracle_config(self.config.gas_oracle)
@@ -27,6 +27,20 @@ pub struct DatadirArgs {
         verbatim_doc_comment
     )]
     pub static_files_path: Option<PathBuf>,
+
+    /// Keep the database in memory instead of on disk.
+    ///
+    /// Only supported for dev nodes. All other node data is written to a temporary directory
+    /// that is removed on shutdown, so nothing persists across restarts.
+    #[arg(long = "datadir.memory")]
+    pub memory: bool,
+
+    /// Verify the integrity checksums of static file data when reading it.
+    ///
+    /// Each chunk of rows is verified the first time it's read, and reads of corrupted data fail
+    /// instead of returning it. Use `reth db static-file verify` to check all static files at once.
+    #[arg(long = "datadir.static-files.verify-checksums")]
+    pub verify_static_file_checksums: bool,
 }
 
 impl DatadirArgs {
@@ -55,4 +69,20 @@ mod tests {
         let args = CommandParser::<DatadirArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_datadir_memory() {
+        let args = CommandParser::<DatadirArgs>::parse_from(["reth", "--datadir.memory"]).args;
+        assert!(args.memory);
+    }
+
+    #[test]
+    fn test_parse_datadir_verify_static_file_checksums() {
+        let args = CommandParser::<DatadirArgs>::parse_from([
+            "reth",
+            "--datadir.static-files.verify-checksums",
+        ])
+        .args;
+        assert!(args.verify_static_file_checksums);
+    }
 }
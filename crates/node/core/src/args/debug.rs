@@ -68,6 +68,21 @@ pub struct DebugArgs {
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
 
+    /// The path to engine API messages stored with `--debug.engine-api-store` that should be
+    /// replayed into the engine after the node is launched.
+    #[arg(long = "debug.engine-api-replay", help_heading = "Debug", value_name = "PATH")]
+    pub engine_api_replay: Option<PathBuf>,
+
+    /// The pacing of the replayed engine API messages.
+    #[arg(
+        long = "debug.engine-api-replay-mode",
+        help_heading = "Debug",
+        value_enum,
+        default_value_t = EngineApiReplayMode::Original,
+        requires = "engine_api_replay"
+    )]
+    pub engine_api_replay_mode: EngineApiReplayMode,
+
     /// Determines which type of invalid block hook to install
     ///
     /// Example: `witness,prestate`
@@ -112,6 +127,8 @@ impl Default for DebugArgs {
             reorg_frequency: None,
             reorg_depth: None,
             engine_api_store: None,
+            engine_api_replay: None,
+            engine_api_replay_mode: EngineApiReplayMode::Original,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
             ethstats: None,
@@ -119,6 +136,21 @@ impl Default for DebugArgs {
     }
 }
 
+/// Determines how the messages of `--debug.engine-api-replay` are paced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, derive_more::Display)]
+pub enum EngineApiReplayMode {
+    /// Sends the messages with the same delays between them as they were originally received.
+    #[default]
+    #[display("original")]
+    Original,
+    /// Sends the next message as soon as the engine responded to the previous one.
+    #[display("fast")]
+    Fast,
+    /// Waits for a newline on stdin before sending each message.
+    #[display("step")]
+    Step,
+}
+
 /// Describes the invalid block hooks that should be installed.
 ///
 /// # Example
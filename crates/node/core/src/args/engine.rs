@@ -89,6 +89,24 @@ pub struct EngineArgs {
         default_value = "false"
     )]
     pub always_process_payload_attributes_on_canonical_head: bool,
+
+    /// Prewarm by executing the transactions of a block optimistically in parallel and committing
+    /// them in block order.
+    ///
+    /// Transactions whose reads conflict with an earlier transaction are re-executed. The committed
+    /// state changes are only used as exact state root proof targets, the block output always comes
+    /// from sequential execution.
+    #[arg(long = "engine.parallel-prewarming", default_value = "false")]
+    pub parallel_prewarming: bool,
+
+    /// Compare the results of parallel prewarming with sequential execution and report
+    /// mismatches, useful for testing
+    #[arg(
+        long = "engine.parallel-prewarming-differential",
+        default_value = "false",
+        requires = "parallel_prewarming"
+    )]
+    pub parallel_prewarming_differential: bool,
 }
 
 #[allow(deprecated)]
@@ -111,6 +129,8 @@ impl Default for EngineArgs {
             precompile_cache_disabled: false,
             state_root_fallback: false,
             always_process_payload_attributes_on_canonical_head: false,
+            parallel_prewarming: false,
+            parallel_prewarming_differential: false,
         }
     }
 }
@@ -134,6 +154,8 @@ impl EngineArgs {
             .with_always_process_payload_attributes_on_canonical_head(
                 self.always_process_payload_attributes_on_canonical_head,
             )
+            .with_parallel_prewarming(self.parallel_prewarming)
+            .with_parallel_prewarming_differential(self.parallel_prewarming_differential)
     }
 }
 
@@ -1,8 +1,14 @@
 use clap::Args;
 use reth_chainspec::{ChainKind, NamedChain};
-use std::path::Path;
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    path::{Path, PathBuf},
+};
 use url::Url;
 
+/// Default port of the ERA1 file server
+pub const DEFAULT_ERA_SERVE_PORT: u16 = 8560;
+
 /// Syncs ERA1 encoded blocks from a local or remote source.
 #[derive(Clone, Debug, Default, Args)]
 pub struct EraArgs {
@@ -18,6 +24,47 @@ pub struct EraArgs {
     /// Describes where to get the ERA files to import from.
     #[clap(flatten)]
     pub source: EraSourceArgs,
+
+    /// Describes how to serve ERA1 files to other nodes.
+    #[clap(flatten)]
+    pub serve: EraServeArgs,
+}
+
+/// Arguments for hosting ERA1 files over HTTP.
+#[derive(Clone, Debug, Args)]
+pub struct EraServeArgs {
+    /// Enable the HTTP server hosting ERA1 files.
+    ///
+    /// The server lists the files and their checksums the same way as the hosts that
+    /// `--era.url` and `reth import-era --url` download from. A new file is exported whenever
+    /// the finalized block completes an era.
+    #[arg(id = "era.serve", long = "era.serve", default_value_t = false, verbatim_doc_comment)]
+    pub enabled: bool,
+
+    /// ERA1 file server address to listen on
+    #[arg(long = "era.serve.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub addr: IpAddr,
+
+    /// ERA1 file server port to listen on
+    #[arg(long = "era.serve.port", default_value_t = DEFAULT_ERA_SERVE_PORT)]
+    pub port: u16,
+
+    /// The directory of the served ERA1 files.
+    ///
+    /// Defaults to `<DATADIR>/era1-export`, where `reth export-era` writes its files.
+    #[arg(long = "era.serve.path", value_name = "ERA_SERVE_PATH", verbatim_doc_comment)]
+    pub path: Option<PathBuf>,
+}
+
+impl Default for EraServeArgs {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            port: DEFAULT_ERA_SERVE_PORT,
+            path: None,
+        }
+    }
 }
 
 /// Arguments for the block history import based on ERA1 encoded files.
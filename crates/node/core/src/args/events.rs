@@ -0,0 +1,55 @@
+//! clap [Args](clap::Args) for exporting node events
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Parameters for exporting the events of the node as newline-delimited JSON
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone)]
+#[command(next_help_heading = "Events")]
+pub struct EventsArgs {
+    /// Append the pipeline, consensus engine, pruner and static file producer events of the node
+    /// to this file, as newline-delimited JSON.
+    #[arg(long = "events.file", value_name = "PATH")]
+    pub file: Option<PathBuf>,
+
+    /// Serve the events of the node as newline-delimited JSON on a unix socket at this path.
+    ///
+    /// Every client that connects receives the events emitted from then on.
+    #[arg(long = "events.socket", value_name = "PATH", verbatim_doc_comment)]
+    pub socket: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_events_args() {
+        let args = CommandParser::<EventsArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, EventsArgs::default());
+
+        let args = CommandParser::<EventsArgs>::parse_from([
+            "reth",
+            "--events.file",
+            "events.ndjson",
+            "--events.socket",
+            "/tmp/reth-events.sock",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            EventsArgs {
+                file: Some("events.ndjson".into()),
+                socket: Some("/tmp/reth-events.sock".into()),
+            }
+        );
+    }
+}
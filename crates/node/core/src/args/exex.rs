@@ -0,0 +1,185 @@
+//! clap [Args](clap::Args) for execution extensions
+
+use clap::{builder::RangedU64ValueParser, Args, ValueEnum};
+use std::{net::SocketAddr, path::PathBuf};
+
+/// Default maximum size of a single ExEx WAL segment file in bytes.
+pub const DEFAULT_EXEX_WAL_MAX_SEGMENT_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Default maximum number of notifications that can be sent to a remote ExEx client without being
+/// acknowledged.
+pub const DEFAULT_EXEX_REMOTE_MAX_UNACKED: usize = 64;
+
+/// Parameters for execution extensions: the write-ahead log, the backfill and the remote ExEx
+/// server
+#[derive(Debug, Args, PartialEq, Eq, Clone)]
+#[command(next_help_heading = "ExEx")]
+pub struct ExExArgs {
+    /// Maximum size of a single ExEx WAL segment file in bytes.
+    ///
+    /// A notification that is larger than this limit is written to its own segment.
+    #[arg(
+        long = "exex.wal.max-segment-size",
+        value_name = "BYTES",
+        default_value_t = DEFAULT_EXEX_WAL_MAX_SEGMENT_SIZE,
+        verbatim_doc_comment
+    )]
+    pub wal_max_segment_size: u64,
+
+    /// Compress the notifications in the ExEx WAL with zstd at this level.
+    ///
+    /// Only notifications written from then on are compressed.
+    #[arg(
+        long = "exex.wal.compression-level",
+        value_name = "LEVEL",
+        value_parser = clap::value_parser!(i32).range(1..=22),
+        verbatim_doc_comment
+    )]
+    pub wal_compression_level: Option<i32>,
+
+    /// When the ExEx WAL segments are flushed to disk with `fsync`.
+    #[arg(long = "exex.wal.sync", value_name = "POLICY", value_enum, default_value_t)]
+    pub wal_sync_policy: ExExWalSyncPolicy,
+
+    /// Maximum number of blocks per second executed when backfilling an ExEx to the node head.
+    ///
+    /// Unlimited by default.
+    #[arg(
+        long = "exex.backfill.max-blocks-per-second",
+        value_name = "BLOCKS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        verbatim_doc_comment
+    )]
+    pub backfill_max_blocks_per_second: Option<u64>,
+
+    /// Serve the ExEx notifications to an out-of-process client on a unix socket at this path.
+    #[arg(long = "exex.remote.ipc", value_name = "PATH", conflicts_with = "remote_addr")]
+    pub remote_ipc: Option<PathBuf>,
+
+    /// Serve the ExEx notifications to an out-of-process client on this TCP address.
+    #[arg(long = "exex.remote.addr", value_name = "ADDR")]
+    pub remote_addr: Option<SocketAddr>,
+
+    /// Maximum number of notifications sent to the remote ExEx client without being acknowledged.
+    #[arg(
+        long = "exex.remote.max-unacked",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_EXEX_REMOTE_MAX_UNACKED,
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub remote_max_unacked: usize,
+}
+
+impl Default for ExExArgs {
+    fn default() -> Self {
+        Self {
+            wal_max_segment_size: DEFAULT_EXEX_WAL_MAX_SEGMENT_SIZE,
+            wal_compression_level: None,
+            wal_sync_policy: ExExWalSyncPolicy::default(),
+            backfill_max_blocks_per_second: None,
+            remote_ipc: None,
+            remote_addr: None,
+            remote_max_unacked: DEFAULT_EXEX_REMOTE_MAX_UNACKED,
+        }
+    }
+}
+
+/// When the ExEx WAL segments are flushed to disk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExExWalSyncPolicy {
+    /// After every written notification.
+    #[default]
+    Always,
+    /// Only when a segment is sealed, i.e. when a new segment is started, or when it's rewritten
+    /// during compaction.
+    OnSegmentSeal,
+    /// Never explicitly, leaving it to the operating system.
+    Never,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_exex_args() {
+        let args = CommandParser::<ExExArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ExExArgs::default());
+
+        let args = CommandParser::<ExExArgs>::parse_from([
+            "reth",
+            "--exex.wal.max-segment-size",
+            "1024",
+            "--exex.wal.compression-level",
+            "3",
+            "--exex.wal.sync",
+            "on-segment-seal",
+            "--exex.backfill.max-blocks-per-second",
+            "100",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            ExExArgs {
+                wal_max_segment_size: 1024,
+                wal_compression_level: Some(3),
+                wal_sync_policy: ExExWalSyncPolicy::OnSegmentSeal,
+                backfill_max_blocks_per_second: Some(100),
+                ..Default::default()
+            }
+        );
+
+        assert!(CommandParser::<ExExArgs>::try_parse_from([
+            "reth",
+            "--exex.wal.compression-level",
+            "23"
+        ])
+        .is_err());
+        assert!(CommandParser::<ExExArgs>::try_parse_from([
+            "reth",
+            "--exex.backfill.max-blocks-per-second",
+            "0"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_exex_remote_args() {
+        let args = CommandParser::<ExExArgs>::parse_from([
+            "reth",
+            "--exex.remote.addr",
+            "127.0.0.1:10000",
+            "--exex.remote.max-unacked",
+            "1",
+        ])
+        .args;
+        assert_eq!(args.remote_addr, Some("127.0.0.1:10000".parse().unwrap()));
+        assert_eq!(args.remote_max_unacked, 1);
+
+        // At least one notification has to be in flight
+        assert!(CommandParser::<ExExArgs>::try_parse_from([
+            "reth",
+            "--exex.remote.max-unacked",
+            "0"
+        ])
+        .is_err());
+
+        // Only one endpoint can be served
+        assert!(CommandParser::<ExExArgs>::try_parse_from([
+            "reth",
+            "--exex.remote.ipc",
+            "/tmp/reth-exex.ipc",
+            "--exex.remote.addr",
+            "127.0.0.1:10000"
+        ])
+        .is_err());
+    }
+}
@@ -14,7 +14,7 @@ pub use rpc_state_cache::RpcStateCacheArgs;
 
 /// DebugArgs struct for debugging purposes
 mod debug;
-pub use debug::{DebugArgs, InvalidBlockHookType, InvalidBlockSelection};
+pub use debug::{DebugArgs, EngineApiReplayMode, InvalidBlockHookType, InvalidBlockSelection};
 
 /// DatabaseArgs struct for configuring the database
 mod database;
@@ -66,7 +66,17 @@ pub use ress_args::RessArgs;
 
 /// `EraArgs` for configuring ERA files import.
 mod era;
-pub use era::{DefaultEraHost, EraArgs, EraSourceArgs};
+pub use era::{DefaultEraHost, EraArgs, EraServeArgs, EraSourceArgs, DEFAULT_ERA_SERVE_PORT};
+
+/// `EventsArgs` for exporting node events.
+mod events;
+pub use events::EventsArgs;
+
+/// `ExExArgs` for configuring execution extensions.
+mod exex;
+pub use exex::{
+    ExExArgs, ExExWalSyncPolicy, DEFAULT_EXEX_REMOTE_MAX_UNACKED, DEFAULT_EXEX_WAL_MAX_SEGMENT_SIZE,
+};
 
 mod error;
 pub mod types;
@@ -6,7 +6,7 @@ use clap::{
     Arg, Args, Command,
 };
 use reth_cli_util::{parse_duration_from_secs, parse_duration_from_secs_or_ms};
-use std::{borrow::Cow, ffi::OsStr, time::Duration};
+use std::{borrow::Cow, ffi::OsStr, path::PathBuf, time::Duration};
 
 /// Parameters for configuring the Payload Builder
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -35,6 +35,20 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Accept bundles via `mev_sendBundle` and include them in built payloads.
+    ///
+    /// Bundles are only served if the `mev` RPC module is enabled.
+    #[arg(long = "builder.bundles")]
+    pub bundles: bool,
+
+    /// Path to a file with the hex encoded secret key of the fee recipient, used to pay out the
+    /// refunds of bundles.
+    ///
+    /// Bundles that pay refunds are only included if the fee recipient of the payload is the
+    /// address of this key.
+    #[arg(long = "builder.bundle-refund-key", value_name = "PATH", requires = "bundles")]
+    pub bundle_refund_key: Option<PathBuf>,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -45,6 +59,8 @@ impl Default for PayloadBuilderArgs {
             gas_limit: None,
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            bundles: false,
+            bundle_refund_key: None,
         }
     }
 }
@@ -103,6 +103,20 @@ pub struct PruningArgs {
     /// pruned.
     #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge"])]
     pub bodies_before: Option<BlockNumber>,
+
+    // Trie Changesets
+    /// Prunes all trie changesets. Trie changesets are only written if a distance or block number
+    /// to keep them for is set.
+    #[arg(long = "prune.triechangesets.full", conflicts_with_all = &["trie_changesets_distance", "trie_changesets_before"])]
+    pub trie_changesets_full: bool,
+    /// Prune trie changesets before the `head-N` block number. In other words, keep last N + 1
+    /// blocks.
+    #[arg(long = "prune.triechangesets.distance", value_name = "BLOCKS", conflicts_with_all = &["trie_changesets_full", "trie_changesets_before"])]
+    pub trie_changesets_distance: Option<u64>,
+    /// Prune trie changesets before the specified block number. The specified block number is not
+    /// pruned.
+    #[arg(long = "prune.triechangesets.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["trie_changesets_full", "trie_changesets_distance"])]
+    pub trie_changesets_before: Option<BlockNumber>,
 }
 
 impl PruningArgs {
@@ -126,6 +140,7 @@ impl PruningArgs {
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     // TODO: set default to pre-merge block if available
                     bodies_history: None,
+                    trie_changesets: None,
                     receipts_log_filter: Default::default(),
                 },
             }
@@ -153,6 +168,9 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.trie_changesets_prune_mode() {
+            config.segments.trie_changesets = Some(mode);
+        }
         if let Some(receipt_logs) =
             self.receipts_log_filter.as_ref().filter(|c| !c.is_empty()).cloned()
         {
@@ -246,6 +264,18 @@ impl PruningArgs {
             None
         }
     }
+
+    const fn trie_changesets_prune_mode(&self) -> Option<PruneMode> {
+        if self.trie_changesets_full {
+            Some(PruneMode::Full)
+        } else if let Some(distance) = self.trie_changesets_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.trie_changesets_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
 }
 
 /// Parses `,` separated pruning info into [`ReceiptsLogPruneConfig`].
@@ -86,6 +86,18 @@ pub struct RpcServerArgs {
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Enable the GraphQL server (EIP-1767)
+    #[arg(long)]
+    pub graphql: bool,
+
+    /// GraphQL server address to listen on
+    #[arg(long = "graphql.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub graphql_addr: IpAddr,
+
+    /// GraphQL server port to listen on
+    #[arg(long = "graphql.port", default_value_t = constants::DEFAULT_GRAPHQL_PORT)]
+    pub graphql_port: u16,
+
     /// Disable the IPC-RPC server
     #[arg(long)]
     pub ipcdisable: bool,
@@ -117,6 +129,23 @@ pub struct RpcServerArgs {
     #[arg(long = "authrpc.jwtsecret", value_name = "PATH", global = true, required = false)]
     pub auth_jwtsecret: Option<PathBuf>,
 
+    /// Path to a PEM encoded certificate chain to serve the auth server over TLS.
+    ///
+    /// The certificate is reloaded when the file changes.
+    #[arg(long = "authrpc.tls-cert", value_name = "PATH", requires = "auth_tls_key")]
+    pub auth_tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM encoded private key of `--authrpc.tls-cert`.
+    #[arg(long = "authrpc.tls-key", value_name = "PATH", requires = "auth_tls_cert")]
+    pub auth_tls_key: Option<PathBuf>,
+
+    /// Path to PEM encoded CA certificates that the consensus client certificate must be signed
+    /// by.
+    ///
+    /// This enables mutual TLS in addition to the JWT authentication.
+    #[arg(long = "authrpc.tls-client-ca", value_name = "PATH", requires = "auth_tls_cert")]
+    pub auth_tls_client_ca: Option<PathBuf>,
+
     /// Enable auth engine API over IPC
     #[arg(long)]
     pub auth_ipc: bool,
@@ -140,6 +169,30 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.jwtsecret", value_name = "HEX", global = true, required = false)]
     pub rpc_jwtsecret: Option<JwtSecret>,
 
+    /// Path to a TOML file with API keys that are required for the HTTP and WS RPC servers and
+    /// the GraphQL server.
+    ///
+    /// Each key can be limited to namespaces or methods and have request, compute unit and block
+    /// range quotas. The file is reloaded when it changes. IPC is not affected.
+    #[arg(long = "rpc.api-keys", value_name = "PATH")]
+    pub rpc_api_keys: Option<PathBuf>,
+
+    /// Path to a PEM encoded certificate chain to serve the HTTP and WS RPC servers over TLS.
+    ///
+    /// The certificate is reloaded when the file changes.
+    #[arg(long = "rpc.tls-cert", value_name = "PATH", requires = "rpc_tls_key")]
+    pub rpc_tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM encoded private key of `--rpc.tls-cert`.
+    #[arg(long = "rpc.tls-key", value_name = "PATH", requires = "rpc_tls_cert")]
+    pub rpc_tls_key: Option<PathBuf>,
+
+    /// Path to PEM encoded CA certificates that client certificates must be signed by.
+    ///
+    /// If set, only clients with a valid certificate can connect to the HTTP and WS RPC servers.
+    #[arg(long = "rpc.tls-client-ca", value_name = "PATH", requires = "rpc_tls_cert")]
+    pub rpc_tls_client_ca: Option<PathBuf>,
+
     /// Set the maximum RPC request payload size for both HTTP and WS in megabytes.
     #[arg(long = "rpc.max-request-size", alias = "rpc-max-request-size", default_value_t = RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into())]
     pub rpc_max_request_size: MaxU32,
@@ -215,12 +268,24 @@ pub struct RpcServerArgs {
     )]
     pub rpc_eth_proof_window: u64,
 
+    /// The maximum proof window for historical proof generation from the retained trie
+    /// changesets, see `--prune.triechangesets.distance`.
+    /// Blocks beyond `--rpc.eth-proof-window` can be proven up to this number of blocks from
+    /// the current tip if their trie changesets are retained.
+    #[arg(
+        long = "rpc.eth-proof-changesets-window",
+        default_value_t = constants::DEFAULT_ETH_PROOF_CHANGESETS_WINDOW,
+        value_parser = RangedU64ValueParser::<u64>::new().range(..=constants::MAX_ETH_PROOF_WINDOW)
+    )]
+    pub rpc_eth_proof_changesets_window: u64,
+
     /// Maximum number of concurrent getproof requests.
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
     /// Path to file containing disallowed addresses, json-encoded list of strings. Block
-    /// validation API will reject blocks containing transactions from these addresses.
+    /// validation API will reject blocks containing transactions from these addresses and the
+    /// payload builder will not include transactions from or to these addresses.
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
     pub builder_disallow: Option<HashSet<Address>>,
 
@@ -262,6 +327,7 @@ impl RpcServerArgs {
     /// * The `auth_port` is scaled by a factor of `instance * 100`
     /// * The `http_port` is scaled by a factor of `-instance`
     /// * The `ws_port` is scaled by a factor of `instance * 2`
+    /// * The `graphql_port` is scaled by a factor of `instance * 2`
     /// * The `ipcpath` is appended with the instance number: `/tmp/reth.ipc-<instance>`
     ///
     /// # Panics
@@ -281,6 +347,8 @@ impl RpcServerArgs {
             self.http_port -= instance - 1;
             // ws port is scaled by a factor of instance * 2
             self.ws_port += instance * 2 - 2;
+            // graphql port is scaled by a factor of instance * 2
+            self.graphql_port += instance * 2 - 2;
             // append instance file to ipc path
             self.ipcpath = format!("{}-{}", self.ipcpath, instance);
         }
@@ -300,6 +368,13 @@ impl RpcServerArgs {
         self
     }
 
+    /// Set the graphql port to zero, to allow the OS to assign a random unused port when the
+    /// graphql server binds to a socket.
+    pub const fn with_graphql_unused_port(mut self) -> Self {
+        self.graphql_port = 0;
+        self
+    }
+
     /// Set the auth port to zero, to allow the OS to assign a random unused port when the rpc
     /// server binds to a socket.
     pub const fn with_auth_unused_port(mut self) -> Self {
@@ -321,6 +396,7 @@ impl RpcServerArgs {
     pub fn with_unused_ports(mut self) -> Self {
         self = self.with_http_unused_port();
         self = self.with_ws_unused_port();
+        self = self.with_graphql_unused_port();
         self = self.with_auth_unused_port();
         self = self.with_ipc_random_path();
         self
@@ -341,16 +417,26 @@ impl Default for RpcServerArgs {
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
             ws_api: None,
+            graphql: false,
+            graphql_addr: Ipv4Addr::LOCALHOST.into(),
+            graphql_port: constants::DEFAULT_GRAPHQL_PORT,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
             ipc_socket_permissions: None,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
+            auth_tls_cert: None,
+            auth_tls_key: None,
+            auth_tls_client_ca: None,
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
             disable_auth_server: false,
             rpc_jwtsecret: None,
+            rpc_api_keys: None,
+            rpc_tls_cert: None,
+            rpc_tls_key: None,
+            rpc_tls_client_ca: None,
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
@@ -363,6 +449,7 @@ impl Default for RpcServerArgs {
             rpc_tx_fee_cap: constants::DEFAULT_TX_FEE_CAP_WEI,
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_eth_proof_changesets_window: constants::DEFAULT_ETH_PROOF_CHANGESETS_WINDOW,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
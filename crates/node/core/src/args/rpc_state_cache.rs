@@ -1,8 +1,9 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_RESPONSE_CACHE_MAX_DISK_MB,
 };
+use std::path::PathBuf;
 
 /// Parameters to configure RPC state cache.
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -36,6 +37,27 @@ pub struct RpcStateCacheArgs {
         default_value_t = DEFAULT_CONCURRENT_DB_REQUESTS,
     )]
     pub max_concurrent_db_requests: usize,
+
+    /// Max size in megabytes of the in-memory cache for responses of expensive calls against
+    /// finalized blocks, e.g. `debug_traceBlockByNumber` or `trace_block`.
+    ///
+    /// The response cache is disabled if this is 0.
+    #[arg(long = "rpc-cache.max-response-mb", value_name = "MB", default_value_t = 0)]
+    pub max_response_mb: u64,
+
+    /// Directory to additionally store cached responses on disk.
+    ///
+    /// Responses in this directory are reused after a restart.
+    #[arg(long = "rpc-cache.response-dir", value_name = "PATH")]
+    pub response_dir: Option<PathBuf>,
+
+    /// Max size in megabytes of the cached responses on disk.
+    #[arg(
+        long = "rpc-cache.max-response-disk-mb",
+        value_name = "MB",
+        default_value_t = DEFAULT_RESPONSE_CACHE_MAX_DISK_MB,
+    )]
+    pub max_response_disk_mb: u64,
 }
 
 impl RpcStateCacheArgs {
@@ -44,6 +66,7 @@ impl RpcStateCacheArgs {
         self.max_blocks = 0;
         self.max_receipts = 0;
         self.max_headers = 0;
+        self.max_response_mb = 0;
     }
 }
 
@@ -54,6 +77,9 @@ impl Default for RpcStateCacheArgs {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            max_response_mb: 0,
+            response_dir: None,
+            max_response_disk_mb: DEFAULT_RESPONSE_CACHE_MAX_DISK_MB,
         }
     }
 }
@@ -33,7 +33,7 @@ use std::{
 };
 use tracing::*;
 
-use crate::args::EraArgs;
+use crate::args::{EraArgs, EventsArgs, ExExArgs};
 pub use reth_engine_primitives::{
     DEFAULT_MAX_PROOF_TASK_CONCURRENCY, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
     DEFAULT_RESERVED_CPU_CORES,
@@ -154,6 +154,12 @@ pub struct NodeConfig<ChainSpec> {
 
     /// All ERA import related arguments with --era prefix
     pub era: EraArgs,
+
+    /// All node events export related arguments with --events prefix
+    pub events: EventsArgs,
+
+    /// All ExEx related arguments with --exex prefix
+    pub exex: ExExArgs,
 }
 
 impl NodeConfig<ChainSpec> {
@@ -184,6 +190,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             datadir: DatadirArgs::default(),
             engine: EngineArgs::default(),
             era: EraArgs::default(),
+            events: EventsArgs::default(),
+            exex: ExExArgs::default(),
         }
     }
 
@@ -490,6 +498,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning: self.pruning,
             engine: self.engine,
             era: self.era,
+            events: self.events,
+            exex: self.exex,
         }
     }
 
@@ -527,6 +537,8 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
             era: self.era.clone(),
+            events: self.events.clone(),
+            exex: self.exex.clone(),
         }
     }
 }
@@ -0,0 +1,350 @@
+//! Export of the node events in a machine-readable form.
+
+use alloy_consensus::BlockHeader;
+use reth_engine_primitives::{BeaconConsensusEngineEvent, ForkchoiceStatus};
+use reth_primitives_traits::NodePrimitives;
+use reth_prune_types::{PruneProgress, PrunerEvent};
+use reth_stages::{ExecOutput, PipelineEvent};
+use reth_stages_types::{
+    NodeEventKind, NodeEventRecord, PrunedSegmentRecord, StaticFileTargetRecord,
+};
+use reth_static_file_types::{StaticFileProducerEvent, StaticFileSegment, StaticFileTargets};
+use std::{
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    sync::broadcast,
+};
+use tracing::{debug, warn};
+
+/// Default number of events a [`NodeEventsSender`] buffers for slow receivers.
+pub const DEFAULT_NODE_EVENTS_CAPACITY: usize = 1024;
+
+/// Publishes the events of the node as [`NodeEventRecord`]s.
+///
+/// Events are filled in by the node event handler, see
+/// [`handle_events_with_publishers`](crate::node::handle_events_with_publishers). Records are
+/// only built while there are receivers.
+#[derive(Debug, Clone)]
+pub struct NodeEventsSender {
+    tx: broadcast::Sender<NodeEventRecord>,
+}
+
+impl Default for NodeEventsSender {
+    fn default() -> Self {
+        Self::new(DEFAULT_NODE_EVENTS_CAPACITY)
+    }
+}
+
+impl NodeEventsSender {
+    /// Creates a new sender that buffers up to `capacity` events for slow receivers.
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    /// Returns a receiver of all events published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<NodeEventRecord> {
+        self.tx.subscribe()
+    }
+
+    /// Returns the underlying channel.
+    pub const fn inner(&self) -> &broadcast::Sender<NodeEventRecord> {
+        &self.tx
+    }
+
+    /// Publishes an event built by `f`, if anyone is listening.
+    fn send(&self, f: impl FnOnce() -> Option<NodeEventKind>) {
+        if self.tx.receiver_count() == 0 {
+            return
+        }
+
+        if let Some(event) = f() {
+            let _ = self.tx.send(NodeEventRecord { timestamp: unix_timestamp_millis(), event });
+        }
+    }
+
+    /// Publishes a pipeline event.
+    pub(crate) fn on_pipeline_event(&self, event: &PipelineEvent) {
+        self.send(|| {
+            Some(match event {
+                PipelineEvent::Run { pipeline_stages_progress, stage_id, checkpoint, target } => {
+                    NodeEventKind::StageStarted {
+                        stage: stage_id.to_string(),
+                        pipeline_stage: pipeline_stages_progress.current,
+                        pipeline_stages: pipeline_stages_progress.total,
+                        checkpoint: checkpoint.unwrap_or_default().block_number,
+                        target: *target,
+                    }
+                }
+                PipelineEvent::Ran { stage_id, result, .. } => {
+                    let ExecOutput { checkpoint, done } = result;
+                    let stage = stage_id.to_string();
+                    let entities = checkpoint.entities();
+                    if *done {
+                        NodeEventKind::StageFinished {
+                            stage,
+                            checkpoint: checkpoint.block_number,
+                            entities,
+                        }
+                    } else {
+                        NodeEventKind::StageProgress {
+                            stage,
+                            checkpoint: checkpoint.block_number,
+                            entities,
+                        }
+                    }
+                }
+                PipelineEvent::Unwind { stage_id, input } => NodeEventKind::StageUnwindStarted {
+                    stage: stage_id.to_string(),
+                    checkpoint: input.checkpoint.block_number,
+                    unwind_to: input.unwind_to,
+                    bad_block: input.bad_block,
+                },
+                PipelineEvent::Unwound { stage_id, result } => NodeEventKind::StageUnwound {
+                    stage: stage_id.to_string(),
+                    checkpoint: result.checkpoint.block_number,
+                },
+                PipelineEvent::Error { stage_id } => {
+                    NodeEventKind::StageError { stage: stage_id.to_string() }
+                }
+                PipelineEvent::Prepare { .. } | PipelineEvent::Skipped { .. } => return None,
+            })
+        });
+    }
+
+    /// Publishes a consensus engine event.
+    pub(crate) fn on_consensus_engine_event<N: NodePrimitives>(
+        &self,
+        event: &BeaconConsensusEngineEvent<N>,
+    ) {
+        self.send(|| {
+            Some(match event {
+                BeaconConsensusEngineEvent::ForkchoiceUpdated(state, status) => {
+                    NodeEventKind::ForkchoiceUpdated {
+                        head: state.head_block_hash,
+                        safe: state.safe_block_hash,
+                        finalized: state.finalized_block_hash,
+                        status: match status {
+                            ForkchoiceStatus::Valid => "valid",
+                            ForkchoiceStatus::Invalid => "invalid",
+                            ForkchoiceStatus::Syncing => "syncing",
+                        }
+                        .to_string(),
+                    }
+                }
+                BeaconConsensusEngineEvent::CanonicalChainCommitted(head, elapsed) => {
+                    NodeEventKind::CanonicalChainCommitted {
+                        number: head.number(),
+                        hash: head.hash(),
+                        elapsed_ms: elapsed.as_millis() as u64,
+                    }
+                }
+                BeaconConsensusEngineEvent::InvalidBlock(block) => {
+                    NodeEventKind::InvalidBlock { number: block.number(), hash: block.hash() }
+                }
+                _ => return None,
+            })
+        });
+    }
+
+    /// Publishes a pruner event.
+    pub(crate) fn on_pruner_event(&self, event: &PrunerEvent) {
+        self.send(|| {
+            Some(match event {
+                PrunerEvent::Started { tip_block_number } => {
+                    NodeEventKind::PrunerStarted { tip_block_number: *tip_block_number }
+                }
+                PrunerEvent::Finished { tip_block_number, elapsed, stats } => {
+                    NodeEventKind::PrunerFinished {
+                        tip_block_number: *tip_block_number,
+                        elapsed_ms: elapsed.as_millis() as u64,
+                        segments: stats
+                            .iter()
+                            .map(|info| PrunedSegmentRecord {
+                                segment: info.segment.to_string(),
+                                pruned: info.pruned,
+                                finished: matches!(info.progress, PruneProgress::Finished),
+                            })
+                            .collect(),
+                    }
+                }
+            })
+        });
+    }
+
+    /// Publishes a static file producer event.
+    pub(crate) fn on_static_file_producer_event(&self, event: &StaticFileProducerEvent) {
+        self.send(|| {
+            Some(match event {
+                StaticFileProducerEvent::Started { targets } => {
+                    NodeEventKind::StaticFileProducerStarted { targets: target_records(targets) }
+                }
+                StaticFileProducerEvent::Finished { targets, elapsed } => {
+                    NodeEventKind::StaticFileProducerFinished {
+                        targets: target_records(targets),
+                        elapsed_ms: elapsed.as_millis() as u64,
+                    }
+                }
+            })
+        });
+    }
+}
+
+/// Returns the block ranges of the static file producer targets, per segment.
+fn target_records(targets: &StaticFileTargets) -> Vec<StaticFileTargetRecord> {
+    [
+        (StaticFileSegment::Headers, &targets.headers),
+        (StaticFileSegment::Transactions, &targets.transactions),
+        (StaticFileSegment::Receipts, &targets.receipts),
+        (StaticFileSegment::BlockMeta, &targets.block_meta),
+    ]
+    .into_iter()
+    .filter_map(|(segment, range)| {
+        range.as_ref().map(|range| StaticFileTargetRecord {
+            segment: segment.as_str().to_string(),
+            from: *range.start(),
+            to: *range.end(),
+        })
+    })
+    .collect()
+}
+
+/// Writes the received events as newline-delimited JSON until the channel closes.
+///
+/// Events that are dropped because the writer can't keep up are skipped with a warning.
+pub async fn write_events<W>(
+    mut writer: W,
+    mut events: broadcast::Receiver<NodeEventRecord>,
+) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!(target: "reth::cli", skipped, "Node events writer is lagging, skipped events");
+                continue
+            }
+            Err(broadcast::error::RecvError::Closed) => return Ok(()),
+        };
+
+        let mut line = serde_json::to_vec(&event)?;
+        line.push(b'\n');
+        writer.write_all(&line).await?;
+        writer.flush().await?;
+    }
+}
+
+/// Serves the events as newline-delimited JSON to every client that connects to the unix socket.
+///
+/// Clients receive the events published after they connected.
+#[cfg(unix)]
+pub async fn serve_events(listener: tokio::net::UnixListener, events: NodeEventsSender) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let events = events.subscribe();
+                tokio::spawn(async move {
+                    if let Err(err) = write_events(stream, events).await {
+                        debug!(target: "reth::cli", %err, "Node events client disconnected");
+                    }
+                });
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", %err, "Failed to accept node events client");
+            }
+        }
+    }
+}
+
+/// Returns the current unix timestamp in milliseconds.
+fn unix_timestamp_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_stages::{PipelineStagesProgress, StageCheckpoint, StageId};
+    use std::time::Duration;
+
+    #[test]
+    fn publishes_only_with_receivers() {
+        let sender = NodeEventsSender::default();
+        sender.on_pruner_event(&PrunerEvent::Started { tip_block_number: 1 });
+
+        let mut events = sender.subscribe();
+        sender.on_pipeline_event(&PipelineEvent::Run {
+            pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 2 },
+            stage_id: StageId::Headers,
+            checkpoint: None,
+            target: Some(100),
+        });
+        sender.on_pipeline_event(&PipelineEvent::Ran {
+            pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 2 },
+            stage_id: StageId::Headers,
+            result: ExecOutput { checkpoint: StageCheckpoint::new(100), done: true },
+        });
+
+        assert_eq!(
+            events.try_recv().unwrap().event,
+            NodeEventKind::StageStarted {
+                stage: "Headers".to_string(),
+                pipeline_stage: 1,
+                pipeline_stages: 2,
+                checkpoint: 0,
+                target: Some(100),
+            }
+        );
+        assert_eq!(
+            events.try_recv().unwrap().event,
+            NodeEventKind::StageFinished {
+                stage: "Headers".to_string(),
+                checkpoint: 100,
+                entities: None
+            }
+        );
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn writes_ndjson() {
+        let sender = NodeEventsSender::default();
+        let events = sender.subscribe();
+
+        sender.on_static_file_producer_event(&StaticFileProducerEvent::Finished {
+            targets: StaticFileTargets {
+                headers: Some(0..=499_999),
+                receipts: None,
+                transactions: None,
+                block_meta: None,
+            },
+            elapsed: Duration::from_millis(1500),
+        });
+        sender.on_pruner_event(&PrunerEvent::Started { tip_block_number: 10 });
+        drop(sender);
+
+        let mut output = Vec::new();
+        write_events(&mut output, events).await.unwrap();
+
+        let lines = String::from_utf8(output).unwrap();
+        let lines = lines
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["type"], "staticFileProducerFinished");
+        assert_eq!(lines[0]["elapsedMs"], 1500);
+        assert_eq!(
+            lines[0]["targets"],
+            serde_json::json!([{ "segment": "headers", "from": 0, "to": 499_999 }])
+        );
+        assert_eq!(lines[1]["type"], "prunerStarted");
+        assert_eq!(lines[1]["tipBlockNumber"], 10);
+        assert!(lines[1]["timestamp"].is_u64());
+    }
+}
@@ -9,4 +9,6 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 pub mod cl;
+pub mod export;
 pub mod node;
+pub mod sync_status;
@@ -1,6 +1,10 @@
 //! Support for handling events emitted by node components.
 
-use crate::cl::ConsensusLayerHealthEvent;
+use crate::{
+    cl::ConsensusLayerHealthEvent,
+    export::NodeEventsSender,
+    sync_status::{SyncStatusSender, SyncStatusTracker},
+};
 use alloy_consensus::{constants::GWEI_TO_WEI, BlockHeader};
 use alloy_primitives::{BlockNumber, B256};
 use alloy_rpc_types_engine::ForkchoiceState;
@@ -45,12 +49,18 @@ struct NodeState {
     safe_block_hash: Option<B256>,
     /// Hash of finalized block last set by fork choice update
     finalized_block_hash: Option<B256>,
+    /// Progress of all pipeline stages, published as the sync status of the node.
+    sync_status: SyncStatusTracker,
+    /// Machine-readable export of the node events.
+    node_events: NodeEventsSender,
 }
 
 impl NodeState {
     const fn new(
         peers_info: Option<Box<dyn PeersInfo>>,
         latest_block: Option<BlockNumber>,
+        sync_status: SyncStatusSender,
+        node_events: NodeEventsSender,
     ) -> Self {
         Self {
             peers_info,
@@ -60,6 +70,8 @@ impl NodeState {
             head_block_hash: None,
             safe_block_hash: None,
             finalized_block_hash: None,
+            sync_status: SyncStatusTracker::new(sync_status),
+            node_events,
         }
     }
 
@@ -87,6 +99,9 @@ impl NodeState {
 
     /// Processes an event emitted by the pipeline
     fn handle_pipeline_event(&mut self, event: PipelineEvent) {
+        self.sync_status.on_pipeline_event(&event);
+        self.node_events.on_pipeline_event(&event);
+
         match event {
             PipelineEvent::Prepare { pipeline_stages_progress, stage_id, checkpoint, target } => {
                 let checkpoint = checkpoint.unwrap_or_default();
@@ -216,6 +231,8 @@ impl NodeState {
         &mut self,
         event: BeaconConsensusEngineEvent<N>,
     ) {
+        self.node_events.on_consensus_engine_event(&event);
+
         match event {
             BeaconConsensusEngineEvent::ForkchoiceUpdated(state, status) => {
                 let ForkchoiceState { head_block_hash, safe_block_hash, finalized_block_hash } =
@@ -271,6 +288,8 @@ impl NodeState {
             BeaconConsensusEngineEvent::CanonicalChainCommitted(head, elapsed) => {
                 self.latest_block = Some(head.number());
                 self.latest_block_time = Some(head.timestamp());
+                // Notify sync status subscribers, so they can see the persistence progress
+                self.sync_status.publish();
 
                 info!(number=head.number(), hash=?head.hash(), ?elapsed, "Canonical chain committed");
             }
@@ -319,6 +338,8 @@ impl NodeState {
     }
 
     fn handle_pruner_event(&self, event: PrunerEvent) {
+        self.node_events.on_pruner_event(&event);
+
         match event {
             PrunerEvent::Started { tip_block_number } => {
                 debug!(tip_block_number, "Pruner started");
@@ -334,6 +355,8 @@ impl NodeState {
     }
 
     fn handle_static_file_producer_event(&self, event: StaticFileProducerEvent) {
+        self.node_events.on_static_file_producer_event(&event);
+
         match event {
             StaticFileProducerEvent::Started { targets } => {
                 debug!(?targets, "Static File Producer started");
@@ -399,7 +422,29 @@ pub async fn handle_events<E, N: NodePrimitives>(
 ) where
     E: Stream<Item = NodeEvent<N>> + Unpin,
 {
-    let state = NodeState::new(peers_info, latest_block_number);
+    handle_events_with_publishers(
+        peers_info,
+        latest_block_number,
+        events,
+        SyncStatusSender::default(),
+        NodeEventsSender::default(),
+    )
+    .await
+}
+
+/// Same as [`handle_events`], but also publishes the progress of the pipeline stages with the
+/// given [`SyncStatusSender`] and the events in a machine-readable form with the given
+/// [`NodeEventsSender`].
+pub async fn handle_events_with_publishers<E, N: NodePrimitives>(
+    peers_info: Option<Box<dyn PeersInfo>>,
+    latest_block_number: Option<BlockNumber>,
+    events: E,
+    sync_status: SyncStatusSender,
+    node_events: NodeEventsSender,
+) where
+    E: Stream<Item = NodeEvent<N>> + Unpin,
+{
+    let state = NodeState::new(peers_info, latest_block_number, sync_status, node_events);
 
     let start = tokio::time::Instant::now() + Duration::from_secs(3);
     let mut info_interval = tokio::time::interval_at(start, INFO_MESSAGE_INTERVAL);
@@ -429,6 +474,11 @@ where
         let mut this = self.project();
 
         while this.info_interval.poll_tick(cx).is_ready() {
+            if this.state.current_stage.is_some() {
+                // Refresh the stage ETAs
+                this.state.sync_status.publish();
+            }
+
             if let Some(CurrentStage { stage_id, eta, checkpoint, entities_checkpoint, target }) =
                 &this.state.current_stage
             {
@@ -536,20 +586,27 @@ where
 ///
 /// One `Eta` is only valid for a single stage.
 #[derive(Default, Copy, Clone)]
-struct Eta {
+pub(crate) struct Eta {
     /// The last stage checkpoint
     last_checkpoint: EntitiesCheckpoint,
     /// The last time the stage reported its checkpoint
     last_checkpoint_time: Option<Instant>,
+    /// The number of entities processed per second between the last two checkpoints
+    per_second: Option<f64>,
     /// The current ETA
     eta: Option<Duration>,
 }
 
 impl Eta {
     /// Update the ETA given the checkpoint, if possible.
-    fn update(&mut self, stage: StageId, checkpoint: StageCheckpoint) {
-        let Some(current) = checkpoint.entities() else { return };
+    pub(crate) fn update(&mut self, stage: StageId, checkpoint: StageCheckpoint) {
+        if let Some(current) = checkpoint.entities() {
+            self.update_entities(stage, current);
+        }
+    }
 
+    /// Update the ETA given the entities processed by the stage so far.
+    pub(crate) fn update_entities(&mut self, stage: StageId, current: EntitiesCheckpoint) {
         if let Some(last_checkpoint_time) = &self.last_checkpoint_time {
             let Some(processed_since_last) =
                 current.processed.checked_sub(self.last_checkpoint.processed)
@@ -560,6 +617,7 @@ impl Eta {
             };
             let elapsed = last_checkpoint_time.elapsed();
             let per_second = processed_since_last as f64 / elapsed.as_secs_f64();
+            self.per_second = Some(per_second);
 
             let Some(remaining) = current.total.checked_sub(current.processed) else {
                 self.eta = None;
@@ -579,6 +637,18 @@ impl Eta {
         self.eta.zip(self.last_checkpoint_time).is_some()
     }
 
+    /// Returns the number of entities processed per second between the last two checkpoints.
+    pub(crate) const fn throughput(&self) -> Option<f64> {
+        self.per_second
+    }
+
+    /// Returns the remaining time until the stage completes, if the ETA is available and hasn't
+    /// passed yet.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        let (eta, last_checkpoint_time) = self.eta.zip(self.last_checkpoint_time)?;
+        eta.checked_sub(last_checkpoint_time.elapsed())
+    }
+
     /// Format ETA for a given stage.
     ///
     /// NOTE: Currently ETA is enabled only for the stages that have predictable progress.
@@ -597,16 +667,12 @@ impl Eta {
 
 impl Display for Eta {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if let Some((eta, last_checkpoint_time)) = self.eta.zip(self.last_checkpoint_time) {
-            let remaining = eta.checked_sub(last_checkpoint_time.elapsed());
-
-            if let Some(remaining) = remaining {
-                return write!(
-                    f,
-                    "{}",
-                    humantime::format_duration(Duration::from_secs(remaining.as_secs()))
-                )
-            }
+        if let Some(remaining) = self.remaining() {
+            return write!(
+                f,
+                "{}",
+                humantime::format_duration(Duration::from_secs(remaining.as_secs()))
+            )
         }
 
         write!(f, "unknown")
@@ -0,0 +1,340 @@
+//! Tracking of the detailed sync progress of the node.
+
+use crate::node::Eta;
+use alloy_primitives::BlockNumber;
+use reth_stages::{
+    BackfillSyncStatus, EntitiesCheckpoint, ExecOutput, PipelineEvent, StageCheckpoint, StageId,
+    StageSyncStatus, SyncStatus,
+};
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::watch;
+
+/// Publishes the [`SyncStatus`] of the node.
+///
+/// Pipeline progress is filled in by the node event handler, see
+/// [`handle_events_with_publishers`](crate::node::handle_events_with_publishers), while the
+/// backfill sync is reported by the engine launcher.
+#[derive(Debug, Clone)]
+pub struct SyncStatusSender {
+    tx: Arc<watch::Sender<SyncStatus>>,
+}
+
+impl Default for SyncStatusSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyncStatusSender {
+    /// Creates a new sender with an empty status.
+    pub fn new() -> Self {
+        let (tx, _) = watch::channel(SyncStatus::default());
+        Self { tx: Arc::new(tx) }
+    }
+
+    /// Returns a receiver that is notified on every status update.
+    pub fn subscribe(&self) -> watch::Receiver<SyncStatus> {
+        self.tx.subscribe()
+    }
+
+    /// Returns the latest status.
+    pub fn status(&self) -> SyncStatus {
+        self.tx.borrow().clone()
+    }
+
+    /// Records the checkpoints of the stages in the database, in pipeline order.
+    ///
+    /// Called when the node launches, so that the status includes all stages before the pipeline
+    /// runs them. Stages that reached the highest checkpoint of all stages are reported as done.
+    pub fn on_stage_checkpoints(&self, checkpoints: Vec<(StageId, StageCheckpoint)>) {
+        let highest =
+            checkpoints.iter().map(|(_, checkpoint)| checkpoint.block_number).max().unwrap_or(0);
+        let stages = checkpoints
+            .into_iter()
+            .map(|(stage_id, checkpoint)| StageSyncStatus {
+                stage: stage_id.to_string(),
+                checkpoint: checkpoint.block_number,
+                target: None,
+                entities: checkpoint.entities(),
+                throughput: None,
+                eta: None,
+                done: checkpoint.block_number >= highest,
+            })
+            .collect();
+
+        self.update(|status| status.stages = stages);
+    }
+
+    /// Records that the engine started a backfill sync.
+    pub fn on_backfill_sync_started(&self) {
+        self.update(|status| {
+            status.backfill =
+                Some(BackfillSyncStatus { started_at: unix_timestamp(), target: None })
+        });
+    }
+
+    /// Records that the backfill sync of the engine finished.
+    pub fn on_backfill_sync_finished(&self) {
+        self.update(|status| status.backfill = None);
+    }
+
+    /// Modifies the status and notifies all receivers.
+    fn update(&self, f: impl FnOnce(&mut SyncStatus)) {
+        self.tx.send_modify(|status| {
+            f(status);
+            status.updated_at = unix_timestamp();
+        });
+    }
+}
+
+/// Keeps track of the pipeline stages and publishes their progress with a [`SyncStatusSender`].
+///
+/// Stages that the pipeline didn't report yet keep the status they were seeded with, see
+/// [`SyncStatusSender::on_stage_checkpoints`].
+pub(crate) struct SyncStatusTracker {
+    sender: SyncStatusSender,
+    /// Stages that reported progress, in pipeline order.
+    stages: Vec<TrackedStage>,
+    /// The stage currently being executed or unwound.
+    current_stage: Option<StageId>,
+}
+
+/// Progress of a single stage.
+struct TrackedStage {
+    stage_id: StageId,
+    checkpoint: StageCheckpoint,
+    target: Option<BlockNumber>,
+    eta: Eta,
+    done: bool,
+}
+
+impl SyncStatusTracker {
+    pub(crate) const fn new(sender: SyncStatusSender) -> Self {
+        Self { sender, stages: Vec::new(), current_stage: None }
+    }
+
+    /// Returns the tracked stage with the given id, adding it after the already known stages if
+    /// it's new.
+    fn stage_mut(&mut self, stage_id: StageId) -> &mut TrackedStage {
+        let index = match self.stages.iter().position(|stage| stage.stage_id == stage_id) {
+            Some(index) => index,
+            None => {
+                self.stages.push(TrackedStage {
+                    stage_id,
+                    checkpoint: StageCheckpoint::default(),
+                    target: None,
+                    eta: Eta::default(),
+                    done: false,
+                });
+                self.stages.len() - 1
+            }
+        };
+        &mut self.stages[index]
+    }
+
+    /// Updates the stage progress from a pipeline event and publishes the new status.
+    pub(crate) fn on_pipeline_event(&mut self, event: &PipelineEvent) {
+        match event {
+            PipelineEvent::Prepare { stage_id, checkpoint, target, .. } |
+            PipelineEvent::Run { stage_id, checkpoint, target, .. } => {
+                let stage = self.stage_mut(*stage_id);
+                stage.checkpoint = checkpoint.unwrap_or_default();
+                stage.target = *target;
+                stage.done = false;
+                self.current_stage = Some(*stage_id);
+            }
+            PipelineEvent::Ran { stage_id, result: ExecOutput { checkpoint, done }, .. } => {
+                let stage = self.stage_mut(*stage_id);
+                stage.checkpoint = *checkpoint;
+                // Stages without entities in their checkpoint progress in blocks.
+                let entities = checkpoint.entities().or_else(|| {
+                    stage.target.map(|total| EntitiesCheckpoint {
+                        processed: checkpoint.block_number,
+                        total,
+                    })
+                });
+                if let Some(entities) = entities {
+                    stage.eta.update_entities(*stage_id, entities);
+                }
+                stage.done = *done;
+                if *done {
+                    self.current_stage = None;
+                }
+            }
+            PipelineEvent::Unwind { stage_id, input } => {
+                let stage = self.stage_mut(*stage_id);
+                stage.checkpoint = input.checkpoint;
+                stage.target = Some(input.unwind_to);
+                stage.eta = Eta::default();
+                stage.done = false;
+                self.current_stage = Some(*stage_id);
+            }
+            PipelineEvent::Unwound { stage_id, result } => {
+                let stage = self.stage_mut(*stage_id);
+                stage.checkpoint = result.checkpoint;
+                stage.done = true;
+                self.current_stage = None;
+            }
+            PipelineEvent::Error { .. } | PipelineEvent::Skipped { .. } => {
+                self.current_stage = None;
+            }
+        }
+
+        self.publish();
+    }
+
+    /// Publishes the current progress of all stages, refreshing their ETAs.
+    pub(crate) fn publish(&self) {
+        let stages = self
+            .stages
+            .iter()
+            .map(|stage| StageSyncStatus {
+                stage: stage.stage_id.to_string(),
+                checkpoint: stage.checkpoint.block_number,
+                target: stage.target,
+                entities: stage.checkpoint.entities(),
+                throughput: stage.eta.throughput(),
+                eta: (!stage.done)
+                    .then(|| stage.eta.remaining())
+                    .flatten()
+                    .map(|remaining| remaining.as_secs()),
+                done: stage.done,
+            })
+            .collect();
+        let current_stage = self.current_stage.map(|stage_id| stage_id.to_string());
+        let target = self
+            .current_stage
+            .and_then(|stage_id| self.stages.iter().find(|stage| stage.stage_id == stage_id))
+            .and_then(|stage| stage.target);
+
+        self.sender.update(|status| {
+            for stage in stages {
+                match status.stages.iter_mut().find(|existing| existing.stage == stage.stage) {
+                    Some(existing) => *existing = stage,
+                    None => status.stages.push(stage),
+                }
+            }
+            status.current_stage = current_stage;
+            if let Some(backfill) = &mut status.backfill {
+                backfill.target = target.or(backfill.target);
+            }
+        });
+    }
+}
+
+/// Returns the current unix timestamp in seconds.
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_stages::{EntitiesCheckpoint, PipelineStagesProgress};
+
+    fn progress() -> PipelineStagesProgress {
+        PipelineStagesProgress { current: 1, total: 2 }
+    }
+
+    #[test]
+    fn tracks_stages_in_pipeline_order() {
+        let sender = SyncStatusSender::new();
+        let mut tracker = SyncStatusTracker::new(sender.clone());
+
+        sender.on_backfill_sync_started();
+        for stage_id in [StageId::Headers, StageId::SenderRecovery] {
+            tracker.on_pipeline_event(&PipelineEvent::Run {
+                pipeline_stages_progress: progress(),
+                stage_id,
+                checkpoint: None,
+                target: Some(100),
+            });
+        }
+
+        let checkpoint = StageCheckpoint::new(50)
+            .with_entities_stage_checkpoint(EntitiesCheckpoint { processed: 50, total: 100 });
+        tracker.on_pipeline_event(&PipelineEvent::Ran {
+            pipeline_stages_progress: progress(),
+            stage_id: StageId::SenderRecovery,
+            result: ExecOutput { checkpoint, done: false },
+        });
+
+        let status = sender.status();
+        assert_eq!(
+            status.stages.iter().map(|stage| stage.stage.as_str()).collect::<Vec<_>>(),
+            ["Headers", "SenderRecovery"]
+        );
+        assert_eq!(status.current_stage.as_deref(), Some("SenderRecovery"));
+        assert_eq!(status.stages[1].checkpoint, 50);
+        assert_eq!(status.stages[1].target, Some(100));
+        assert_eq!(
+            status.stages[1].entities,
+            Some(EntitiesCheckpoint { processed: 50, total: 100 })
+        );
+        assert_eq!(status.backfill.and_then(|backfill| backfill.target), Some(100));
+
+        tracker.on_pipeline_event(&PipelineEvent::Ran {
+            pipeline_stages_progress: progress(),
+            stage_id: StageId::SenderRecovery,
+            result: ExecOutput { checkpoint: StageCheckpoint::new(100), done: true },
+        });
+        sender.on_backfill_sync_finished();
+
+        let status = sender.status();
+        assert_eq!(status.current_stage, None);
+        assert!(status.stages[1].done);
+        assert_eq!(status.stages[1].eta, None);
+        assert_eq!(status.backfill, None);
+    }
+
+    #[test]
+    fn seeds_all_stages_from_checkpoints() {
+        let sender = SyncStatusSender::new();
+        let mut tracker = SyncStatusTracker::new(sender.clone());
+
+        sender.on_stage_checkpoints(
+            StageId::ALL
+                .into_iter()
+                .map(|stage_id| {
+                    let block = if stage_id == StageId::Headers { 100 } else { 10 };
+                    (stage_id, StageCheckpoint::new(block))
+                })
+                .collect(),
+        );
+
+        let status = sender.status();
+        assert_eq!(
+            status.stages.iter().map(|stage| stage.stage.clone()).collect::<Vec<_>>(),
+            StageId::ALL.map(|stage_id| stage_id.to_string())
+        );
+        assert_eq!(status.stages[0].checkpoint, 10);
+        assert!(!status.stages[0].done);
+        assert!(status.stages[1].done);
+
+        // Stages without entities in their checkpoints report their throughput in blocks.
+        tracker.on_pipeline_event(&PipelineEvent::Run {
+            pipeline_stages_progress: progress(),
+            stage_id: StageId::Bodies,
+            checkpoint: Some(StageCheckpoint::new(10)),
+            target: Some(100),
+        });
+        for block in [20, 30] {
+            tracker.on_pipeline_event(&PipelineEvent::Ran {
+                pipeline_stages_progress: progress(),
+                stage_id: StageId::Bodies,
+                result: ExecOutput { checkpoint: StageCheckpoint::new(block), done: false },
+            });
+        }
+
+        let status = sender.status();
+        assert_eq!(status.stages.len(), StageId::ALL.len());
+        let bodies = &status.stages[2];
+        assert_eq!(bodies.stage, StageId::Bodies.to_string());
+        assert_eq!(bodies.checkpoint, 30);
+        assert!(bodies.throughput.is_some());
+        assert_eq!(status.stages[3].checkpoint, 10);
+    }
+}
@@ -419,7 +419,8 @@ impl reth_codecs::Compact for OpTransactionSigned {
         buf.put_u8(0);
 
         let sig_bit = self.signature.to_compact(buf) as u8;
-        let zstd_bit = self.transaction.input().len() >= 32;
+        let zstd_bit =
+            self.transaction.input().len() >= 32 && reth_zstd_compressors::is_compression_enabled();
 
         let tx_bits = if zstd_bit {
             let mut tmp = Vec::with_capacity(256);
@@ -222,6 +222,11 @@ where
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_api.eth_proof_window()
     }
+
+    #[inline]
+    fn max_proof_changesets_window(&self) -> u64 {
+        self.inner.eth_api.eth_proof_changesets_window()
+    }
 }
 
 impl<N, Rpc> EthFees for OpEthApi<N, Rpc>
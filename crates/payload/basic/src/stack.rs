@@ -6,11 +6,11 @@ use crate::{
 use alloy_eips::eip4895::Withdrawals;
 use alloy_primitives::{Address, B256, U256};
 use reth_payload_builder::PayloadId;
-use reth_payload_primitives::BuiltPayload;
+use reth_payload_primitives::{BuiltPayload, PayloadBuildReport};
 use reth_primitives_traits::{NodePrimitives, SealedBlock};
 
 use alloy_eips::eip7685::Requests;
-use std::{error::Error, fmt};
+use std::{error::Error, fmt, sync::Arc};
 
 /// hand rolled Either enum to handle two builder types
 #[derive(Debug, Clone)]
@@ -175,6 +175,13 @@ where
             Self::Right(r) => r.requests(),
         }
     }
+
+    fn build_report(&self) -> Option<Arc<PayloadBuildReport>> {
+        match self {
+            Self::Left(l) => l.build_report(),
+            Self::Right(r) => r.build_report(),
+        }
+    }
 }
 
 impl<L, R> PayloadBuilder for PayloadBuilderStack<L, R>
@@ -1,9 +1,10 @@
 //! Payload builder service metrics.
 
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
+use reth_payload_primitives::{PayloadBuildReport, TransactionBuildOutcome};
 
 /// Payload builder service metrics
 #[derive(Metrics, Clone)]
@@ -23,6 +24,22 @@ pub(crate) struct PayloadBuilderServiceMetrics {
     pub(crate) resolved_revenue: Gauge,
     /// Current block returned as the resolved payload
     pub(crate) resolved_block: Gauge,
+    /// Number of candidate transactions considered for the payloads of terminated jobs
+    pub(crate) candidate_transactions: Counter,
+    /// Number of transactions included in the payloads of terminated jobs
+    pub(crate) included_transactions: Counter,
+    /// Number of transactions skipped because they exceeded the gas left in the block
+    pub(crate) skipped_gas_limit: Counter,
+    /// Number of transactions skipped because they exceeded the blobs left in the block
+    pub(crate) skipped_blob_limit: Counter,
+    /// Number of transactions skipped because of a nonce that is too low or too high
+    pub(crate) skipped_nonce: Counter,
+    /// Number of transactions skipped because of a disallowed sender or recipient
+    pub(crate) skipped_disallowed: Counter,
+    /// Number of transactions skipped because they are invalid
+    pub(crate) skipped_invalid: Counter,
+    /// Time spent executing the candidate transactions of a payload
+    pub(crate) build_duration_seconds: Histogram,
 }
 
 impl PayloadBuilderServiceMetrics {
@@ -47,4 +64,24 @@ impl PayloadBuilderServiceMetrics {
         self.resolved_block.set(block as f64);
         self.resolved_revenue.set(value)
     }
+
+    pub(crate) fn record_build_report(&self, report: &PayloadBuildReport) {
+        self.candidate_transactions.increment(report.candidates() as u64);
+        self.build_duration_seconds.record(report.elapsed_micros as f64 / 1_000_000.0);
+
+        for tx in &report.transactions {
+            let counter = match tx.outcome {
+                TransactionBuildOutcome::Included { .. } => &self.included_transactions,
+                TransactionBuildOutcome::ExceedsGasLimit { .. } => &self.skipped_gas_limit,
+                TransactionBuildOutcome::ExceedsBlobLimit { .. } => &self.skipped_blob_limit,
+                TransactionBuildOutcome::NonceTooLow | TransactionBuildOutcome::NonceGap { .. } => {
+                    &self.skipped_nonce
+                }
+                TransactionBuildOutcome::Disallowed => &self.skipped_disallowed,
+                TransactionBuildOutcome::InvalidBlobSidecar { .. } |
+                TransactionBuildOutcome::Invalid { .. } => &self.skipped_invalid,
+            };
+            counter.increment(1);
+        }
+    }
 }
@@ -12,9 +12,12 @@ use alloy_rpc_types::engine::PayloadId;
 use futures_util::{future::FutureExt, Stream, StreamExt};
 use reth_chain_state::CanonStateNotification;
 use reth_payload_builder_primitives::{Events, PayloadBuilderError, PayloadEvents};
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadKind, PayloadTypes};
+use reth_payload_primitives::{
+    BuiltPayload, PayloadBuildReport, PayloadBuilderAttributes, PayloadKind, PayloadTypes,
+};
 use reth_primitives_traits::NodePrimitives;
 use std::{
+    collections::VecDeque,
     fmt,
     future::Future,
     pin::Pin,
@@ -82,6 +85,13 @@ where
     ) -> Option<Result<T::PayloadBuilderAttributes, PayloadBuilderError>> {
         self.inner.payload_attributes(id).await
     }
+
+    /// Returns the report of how the best payload for the given identifier was built.
+    ///
+    /// See [`PayloadBuilderHandle::build_report`].
+    pub async fn build_report(&self, id: PayloadId) -> Option<Arc<PayloadBuildReport>> {
+        self.inner.build_report(id).await
+    }
 }
 
 impl<T> PayloadStore<T>
@@ -177,6 +187,16 @@ impl<T: PayloadTypes> PayloadBuilderHandle<T> {
         self.to_service.send(PayloadServiceCommand::PayloadAttributes(id, tx)).ok()?;
         rx.await.ok()?
     }
+
+    /// Returns the report of how the best payload for the given identifier was built.
+    ///
+    /// Reports of the last [`MAX_BUILD_REPORTS`] terminated jobs remain available after the job
+    /// was resolved. Returns `None` if the payload builder does not record build reports.
+    pub async fn build_report(&self, id: PayloadId) -> Option<Arc<PayloadBuildReport>> {
+        let (tx, rx) = oneshot::channel();
+        self.to_service.send(PayloadServiceCommand::BuildReport(id, tx)).ok()?;
+        rx.await.ok()?
+    }
 }
 
 impl<T> Clone for PayloadBuilderHandle<T>
@@ -218,10 +238,15 @@ where
     chain_events: St,
     /// Payload events handler, used to broadcast and subscribe to payload events.
     payload_events: broadcast::Sender<Events<T>>,
+    /// Build reports of the most recently terminated jobs.
+    build_reports: VecDeque<(PayloadId, Arc<PayloadBuildReport>)>,
 }
 
 const PAYLOAD_EVENTS_BUFFER_SIZE: usize = 20;
 
+/// Number of build reports of terminated jobs that are kept.
+pub const MAX_BUILD_REPORTS: usize = 64;
+
 // === impl PayloadBuilderService ===
 
 impl<Gen, St, T> PayloadBuilderService<Gen, St, T>
@@ -249,6 +274,7 @@ where
             metrics: Default::default(),
             chain_events,
             payload_events,
+            build_reports: VecDeque::new(),
         };
 
         let handle = service.handle();
@@ -292,8 +318,9 @@ where
         let (fut, keep_alive) = self.payload_jobs[job].0.resolve_kind(kind);
 
         if keep_alive == KeepPayloadJobAlive::No {
-            let (_, id) = self.payload_jobs.swap_remove(job);
+            let (job, id) = self.payload_jobs.swap_remove(job);
             debug!(target: "payload_builder", %id, "terminated resolved job");
+            self.record_build_report(&job, id);
         }
 
         // Since the fees will not be known until the payload future is resolved / awaited, we wrap
@@ -316,6 +343,34 @@ where
 
         Some(Box::pin(fut))
     }
+
+    /// Returns the build report of the best payload of the given job, or of a recently
+    /// terminated job.
+    fn build_report(&self, id: PayloadId) -> Option<Arc<PayloadBuildReport>> {
+        if let Some((job, _)) = self.payload_jobs.iter().find(|(_, job_id)| *job_id == id) {
+            return job.best_payload().ok().and_then(|payload| payload.build_report())
+        }
+
+        self.build_reports
+            .iter()
+            .find(|(report_id, _)| *report_id == id)
+            .map(|(_, report)| report.clone())
+    }
+
+    /// Keeps the build report of the best payload of a terminated job and updates the metrics.
+    fn record_build_report(&mut self, job: &Gen::Job, id: PayloadId) {
+        let Some(report) = job.best_payload().ok().and_then(|payload| payload.build_report())
+        else {
+            return
+        };
+
+        self.metrics.record_build_report(&report);
+
+        if self.build_reports.len() == MAX_BUILD_REPORTS {
+            self.build_reports.pop_back();
+        }
+        self.build_reports.push_front((id, report));
+    }
 }
 
 impl<Gen, St, T> PayloadBuilderService<Gen, St, T>
@@ -375,11 +430,13 @@ where
                     Poll::Ready(Ok(_)) => {
                         this.metrics.set_active_jobs(this.payload_jobs.len());
                         trace!(target: "payload_builder", %id, "payload job finished");
+                        this.record_build_report(&job, id);
                     }
                     Poll::Ready(Err(err)) => {
                         warn!(target: "payload_builder",%err, ?id, "Payload builder job failed; resolving payload");
                         this.metrics.inc_failed_jobs();
                         this.metrics.set_active_jobs(this.payload_jobs.len());
+                        this.record_build_report(&job, id);
                     }
                     Poll::Pending => {
                         // still pending, put it back
@@ -436,6 +493,9 @@ where
                         let new_rx = this.payload_events.subscribe();
                         let _ = tx.send(new_rx);
                     }
+                    PayloadServiceCommand::BuildReport(id, tx) => {
+                        let _ = tx.send(this.build_report(id));
+                    }
                 }
             }
 
@@ -468,6 +528,8 @@ pub enum PayloadServiceCommand<T: PayloadTypes> {
     ),
     /// Payload service events
     Subscribe(oneshot::Sender<broadcast::Receiver<Events<T>>>),
+    /// Get the build report of the best payload so far
+    BuildReport(PayloadId, oneshot::Sender<Option<Arc<PayloadBuildReport>>>),
 }
 
 impl<T> fmt::Debug for PayloadServiceCommand<T>
@@ -487,6 +549,9 @@ where
             }
             Self::Resolve(f0, f1, _f2) => f.debug_tuple("Resolve").field(&f0).field(&f1).finish(),
             Self::Subscribe(f0) => f.debug_tuple("Subscribe").field(&f0).finish(),
+            Self::BuildReport(f0, f1) => {
+                f.debug_tuple("BuildReport").field(&f0).field(&f1).finish()
+            }
         }
     }
 }
@@ -17,6 +17,7 @@ use crate::alloc::string::ToString;
 use alloy_primitives::Bytes;
 use reth_chainspec::EthereumHardforks;
 use reth_primitives_traits::{NodePrimitives, SealedBlock};
+use serde::{Deserialize, Serialize};
 
 mod error;
 pub use error::{
@@ -33,6 +34,9 @@ pub use traits::{
 mod payload;
 pub use payload::{ExecutionPayload, PayloadOrAttributes};
 
+mod report;
+pub use report::{PayloadBuildReport, TransactionBuildOutcome, TransactionBuildReport};
+
 /// Core trait that defines the associated types for working with execution payloads.
 pub trait PayloadTypes: Send + Sync + Unpin + core::fmt::Debug + Clone + 'static {
     /// The format for execution payload data that can be processed and validated.
@@ -366,7 +370,7 @@ where
 }
 
 /// The version of Engine API message.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
 pub enum EngineApiMessageVersion {
     /// Version 1
     V1 = 1,
@@ -0,0 +1,159 @@
+//! Reports of how a payload was built.
+
+use alloc::{string::String, vec::Vec};
+use alloy_primitives::{Address, TxHash, U256};
+use serde::{Deserialize, Serialize};
+
+/// Records which candidate transactions were considered while building a payload and why they
+/// were included or skipped.
+///
+/// Transactions that were never yielded by the pool, e.g. descendants of a skipped transaction,
+/// are not part of the report.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadBuildReport {
+    /// Number of the built block.
+    pub block_number: u64,
+    /// Gas limit of the built block.
+    pub gas_limit: u64,
+    /// Gas used by all included transactions.
+    pub gas_used: u64,
+    /// Total fees paid to the fee recipient.
+    pub fees: U256,
+    /// Time spent executing the candidate transactions, in microseconds.
+    pub elapsed_micros: u64,
+    /// The outcome of every candidate transaction, in the order they were considered.
+    pub transactions: Vec<TransactionBuildReport>,
+}
+
+impl PayloadBuildReport {
+    /// Creates an empty report for the given block.
+    pub fn new(block_number: u64, gas_limit: u64) -> Self {
+        Self { block_number, gas_limit, ..Default::default() }
+    }
+
+    /// Records the outcome of a candidate transaction.
+    pub fn record(
+        &mut self,
+        hash: TxHash,
+        sender: Address,
+        nonce: u64,
+        outcome: TransactionBuildOutcome,
+    ) {
+        if let TransactionBuildOutcome::Included { gas_used, fee } = &outcome {
+            self.gas_used += gas_used;
+            self.fees += fee;
+        }
+        self.transactions.push(TransactionBuildReport { hash, sender, nonce, outcome });
+    }
+
+    /// Returns the number of considered candidate transactions.
+    pub fn candidates(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Returns the number of included transactions.
+    pub fn included(&self) -> usize {
+        self.transactions.iter().filter(|tx| tx.outcome.is_included()).count()
+    }
+
+    /// Returns the report of the given transaction, if it was considered.
+    pub fn transaction(&self, hash: &TxHash) -> Option<&TransactionBuildReport> {
+        self.transactions.iter().find(|tx| tx.hash == *hash)
+    }
+}
+
+/// The outcome of a single candidate transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBuildReport {
+    /// Hash of the transaction.
+    pub hash: TxHash,
+    /// Sender of the transaction.
+    pub sender: Address,
+    /// Nonce of the transaction.
+    pub nonce: u64,
+    /// Whether the transaction was included and if not, why.
+    #[serde(flatten)]
+    pub outcome: TransactionBuildOutcome,
+}
+
+/// Why a candidate transaction was included or skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TransactionBuildOutcome {
+    /// The transaction was included in the block.
+    Included {
+        /// Gas used by the transaction.
+        gas_used: u64,
+        /// Fee paid to the fee recipient.
+        fee: U256,
+    },
+    /// The gas limit of the transaction exceeds the gas left in the block.
+    ExceedsGasLimit {
+        /// Gas limit of the transaction.
+        gas_limit: u64,
+        /// Gas left in the block.
+        gas_left: u64,
+    },
+    /// The blobs of the transaction exceed the blobs left in the block.
+    ExceedsBlobLimit {
+        /// Number of blobs of the transaction.
+        blobs: u64,
+        /// Blobs left in the block.
+        blobs_left: u64,
+    },
+    /// The blob sidecar of the transaction is missing or has the wrong format.
+    InvalidBlobSidecar {
+        /// Description of the error.
+        error: String,
+    },
+    /// The nonce of the transaction is lower than the nonce of the sender.
+    NonceTooLow,
+    /// The nonce of the transaction is higher than the nonce of the sender.
+    NonceGap {
+        /// The nonce of the sender.
+        expected: u64,
+    },
+    /// The sender or recipient is on the disallow list of the builder.
+    Disallowed,
+    /// The transaction failed validation during execution.
+    Invalid {
+        /// Description of the error.
+        error: String,
+    },
+}
+
+impl TransactionBuildOutcome {
+    /// Returns true if the transaction was included.
+    pub const fn is_included(&self) -> bool {
+        matches!(self, Self::Included { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_outcome() {
+        let mut report = PayloadBuildReport::new(1, 30_000_000);
+        report.record(
+            TxHash::ZERO,
+            Address::ZERO,
+            0,
+            TransactionBuildOutcome::Included { gas_used: 21_000, fee: U256::from(42) },
+        );
+        report.record(TxHash::ZERO, Address::ZERO, 1, TransactionBuildOutcome::NonceTooLow);
+        assert_eq!(report.gas_used, 21_000);
+        assert_eq!(report.fees, U256::from(42));
+        assert_eq!(report.included(), 1);
+
+        let json = serde_json::to_value(&report.transactions[0]).unwrap();
+        assert_eq!(json["outcome"], "included");
+        assert_eq!(json["gasUsed"], 21_000);
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<PayloadBuildReport>(&json).unwrap(), report);
+    }
+}
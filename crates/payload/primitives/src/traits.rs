@@ -1,6 +1,6 @@
 //! Core traits for working with execution payloads.
 
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
 use alloy_eips::{
     eip4895::{Withdrawal, Withdrawals},
     eip7685::Requests,
@@ -11,7 +11,7 @@ use core::fmt;
 use reth_chain_state::ExecutedBlockWithTrieUpdates;
 use reth_primitives_traits::{NodePrimitives, SealedBlock, SealedHeader};
 
-use crate::PayloadBuilderError;
+use crate::{PayloadBuildReport, PayloadBuilderError};
 
 /// Represents a successfully built execution payload (block).
 ///
@@ -40,6 +40,13 @@ pub trait BuiltPayload: Send + Sync + fmt::Debug {
     /// These are requests generated by the execution layer that need to be
     /// processed by the consensus layer (e.g., validator deposits, withdrawals).
     fn requests(&self) -> Option<Requests>;
+
+    /// Returns the report of how the payload was built.
+    ///
+    /// Returns `None` if the builder does not record build reports.
+    fn build_report(&self) -> Option<Arc<PayloadBuildReport>> {
+        None
+    }
 }
 
 /// Attributes used to guide the construction of a new execution payload.
@@ -0,0 +1,446 @@
+//! Pool of bundles submitted via `mev_sendBundle`.
+
+use alloy_primitives::{keccak256, map::B256Map, Address, B256, U256};
+use alloy_rpc_types_mev::RefundConfig;
+use parking_lot::RwLock;
+use reth_primitives_traits::{Recovered, SignedTransaction};
+use std::sync::Arc;
+
+/// Default maximum number of bundles kept in the [`BundlePool`].
+pub const DEFAULT_MAX_BUNDLES: usize = 1024;
+
+/// Default maximum number of bundles of a single sender kept in the [`BundlePool`].
+pub const DEFAULT_MAX_BUNDLES_PER_SENDER: usize = 16;
+
+/// Default maximum number of blocks past the tip that the block range of a bundle can end at.
+pub const DEFAULT_MAX_BLOCKS_AHEAD: u64 = 32;
+
+/// Gas limit of a transaction that pays out a refund of a [`Bundle`].
+pub const BUNDLE_PAYOUT_MAX_COST: u64 = 30_000;
+
+/// A transaction of a [`Bundle`].
+#[derive(Debug, Clone)]
+pub struct BundleTransaction<T> {
+    /// The transaction with its recovered signer.
+    pub tx: Recovered<T>,
+    /// Whether the bundle remains valid if this transaction reverts.
+    pub can_revert: bool,
+    /// Percent of the value of the bundle that is refunded for this transaction.
+    pub refund_percent: Option<u64>,
+}
+
+/// A bundle of transactions that must be included in the given order and either all or none.
+#[derive(Debug, Clone)]
+pub struct Bundle<T> {
+    /// Hash of the bundle, the keccak hash of all transaction hashes.
+    pub hash: B256,
+    /// First block the bundle can be included in.
+    pub block_number: u64,
+    /// Last block the bundle can be included in.
+    pub max_block_number: u64,
+    /// The flattened transactions of the bundle.
+    pub transactions: Vec<BundleTransaction<T>>,
+    /// Recipients of refunds, if the bundle pays refunds.
+    pub refund_configs: Vec<RefundConfig>,
+}
+
+impl<T: SignedTransaction> Bundle<T> {
+    /// Creates a new bundle that is valid in the given inclusive block range.
+    pub fn new(
+        block_number: u64,
+        max_block_number: u64,
+        transactions: Vec<BundleTransaction<T>>,
+        refund_configs: Vec<RefundConfig>,
+    ) -> Self {
+        let hashes = transactions.iter().flat_map(|tx| tx.tx.tx_hash().0).collect::<Vec<_>>();
+        Self {
+            hash: keccak256(hashes),
+            block_number,
+            max_block_number,
+            transactions,
+            refund_configs,
+        }
+    }
+
+    /// Returns true if the bundle can be included in the given block.
+    pub const fn is_valid_for_block(&self, block_number: u64) -> bool {
+        self.block_number <= block_number && block_number <= self.max_block_number
+    }
+
+    /// Returns true if any transaction of the bundle is entitled to a refund.
+    pub fn has_refunds(&self) -> bool {
+        self.transactions.iter().any(|tx| tx.refund_percent.is_some())
+    }
+
+    /// Returns the refunds the bundle pays out as recipient and value, given the value that every
+    /// transaction of the bundle paid to the fee recipient.
+    ///
+    /// The value paid by transactions without a refund percent is refundable. Every transaction
+    /// with a refund percent is refunded that percent of the remaining refundable value, split
+    /// between the [`Bundle::refund_configs`] or paid to its signer if there are none. The cost of
+    /// a payout transaction at the given base fee is deducted from every refund.
+    ///
+    /// Returns `None` if a refund doesn't cover the cost of its payout transaction.
+    pub fn refunds(&self, coinbase_diffs: &[U256], base_fee: u64) -> Option<Vec<(Address, U256)>> {
+        let payout_fee = U256::from(base_fee) * U256::from(BUNDLE_PAYOUT_MAX_COST);
+        let mut refundable_value = self
+            .transactions
+            .iter()
+            .zip(coinbase_diffs)
+            .filter(|(tx, _)| tx.refund_percent.is_none())
+            .map(|(_, diff)| *diff)
+            .sum::<U256>();
+
+        let mut refunds = Vec::new();
+        for tx in &self.transactions {
+            let Some(refund_percent) = tx.refund_percent else { continue };
+            let payout_value = refundable_value * U256::from(refund_percent) / U256::from(100);
+            refundable_value -= payout_value;
+
+            let signer = [RefundConfig { address: tx.tx.signer(), percent: 100 }];
+            let configs =
+                if self.refund_configs.is_empty() { &signer[..] } else { &self.refund_configs };
+            for config in configs {
+                let value = payout_value * U256::from(config.percent) / U256::from(100);
+                refunds.push((config.address, value.checked_sub(payout_fee)?));
+            }
+        }
+
+        Some(refunds)
+    }
+
+    /// Returns the number of transactions that pay out the refunds of the bundle.
+    pub fn payout_count(&self) -> usize {
+        let refunded = self.transactions.iter().filter(|tx| tx.refund_percent.is_some()).count();
+        refunded * self.refund_configs.len().max(1)
+    }
+
+    /// Returns the sum of the gas limits of all transactions.
+    pub fn gas_limit(&self) -> u64 {
+        self.transactions.iter().map(|tx| tx.tx.gas_limit()).sum()
+    }
+
+    /// Returns the sender of the bundle, the signer of its first transaction.
+    pub fn sender(&self) -> Option<Address> {
+        self.transactions.first().map(|tx| tx.tx.signer())
+    }
+
+    /// Returns the priority fee per gas that the bundle offers, averaged over its transactions
+    /// and weighted by their gas limits.
+    pub fn priority_fee_per_gas(&self) -> u128 {
+        let fees = self.transactions.iter().fold(0u128, |fees, tx| {
+            fees.saturating_add(
+                tx.tx.priority_fee_or_price().saturating_mul(tx.tx.gas_limit() as u128),
+            )
+        });
+        fees.checked_div(self.gas_limit() as u128).unwrap_or_default()
+    }
+}
+
+/// Errors when adding a bundle to the [`BundlePool`].
+#[derive(Debug, thiserror::Error)]
+pub enum BundlePoolError {
+    /// The pool is full and the bundle doesn't pay more than any bundle in the pool.
+    #[error("bundle pool is full and the bundle is underpriced")]
+    Underpriced,
+    /// The sender already has the maximum number of bundles in the pool.
+    #[error("too many bundles from sender {0}")]
+    SenderLimitReached(Address),
+    /// The block range of the bundle ends at or before the tip.
+    #[error("bundle expired at block {max_block_number}")]
+    Expired {
+        /// Last block the bundle can be included in.
+        max_block_number: u64,
+    },
+    /// The block range of the bundle ends too far past the tip.
+    #[error("bundle max block number {max_block_number} exceeds the limit of {limit}")]
+    TooFarInFuture {
+        /// Last block the bundle can be included in.
+        max_block_number: u64,
+        /// Highest block number that bundles can end at.
+        limit: u64,
+    },
+    /// The bundle does not contain any transaction.
+    #[error("bundle is empty")]
+    EmptyBundle,
+}
+
+/// Configuration of the [`BundlePool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundlePoolConfig {
+    /// Maximum number of bundles in the pool.
+    pub max_bundles: usize,
+    /// Maximum number of bundles of a single sender in the pool.
+    pub max_bundles_per_sender: usize,
+    /// Maximum number of blocks past the tip that the block range of a bundle can end at.
+    pub max_blocks_ahead: u64,
+}
+
+impl Default for BundlePoolConfig {
+    fn default() -> Self {
+        Self {
+            max_bundles: DEFAULT_MAX_BUNDLES,
+            max_bundles_per_sender: DEFAULT_MAX_BUNDLES_PER_SENDER,
+            max_blocks_ahead: DEFAULT_MAX_BLOCKS_AHEAD,
+        }
+    }
+}
+
+/// Bundles that the payload builder includes ahead of pool transactions.
+///
+/// Bundles are dropped once the chain has advanced past their block range. If the pool is full,
+/// the bundle with the lowest priority fee is evicted for a bundle that pays more.
+#[derive(Debug)]
+pub struct BundlePool<T> {
+    inner: Arc<RwLock<BundlePoolInner<T>>>,
+}
+
+#[derive(Debug)]
+struct BundlePoolInner<T> {
+    bundles: B256Map<Arc<Bundle<T>>>,
+    config: BundlePoolConfig,
+}
+
+impl<T: SignedTransaction> BundlePool<T> {
+    /// Creates a new pool with the given configuration.
+    pub fn new(config: BundlePoolConfig) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(BundlePoolInner { bundles: Default::default(), config })),
+        }
+    }
+
+    /// Adds the bundle to the pool and returns its hash.
+    ///
+    /// `tip` is the number of the latest block, bundles have to end after it and within
+    /// [`BundlePoolConfig::max_blocks_ahead`] blocks. Adding a bundle with the same hash replaces
+    /// the existing bundle.
+    pub fn add(&self, bundle: Bundle<T>, tip: u64) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+
+        let max_block_number = bundle.max_block_number;
+        if max_block_number <= tip {
+            return Err(BundlePoolError::Expired { max_block_number })
+        }
+
+        let mut inner = self.inner.write();
+        let config = inner.config;
+        let limit = tip.saturating_add(config.max_blocks_ahead);
+        if max_block_number > limit {
+            return Err(BundlePoolError::TooFarInFuture { max_block_number, limit })
+        }
+
+        let hash = bundle.hash;
+        if !inner.bundles.contains_key(&hash) {
+            inner.bundles.retain(|_, bundle| bundle.max_block_number > tip);
+
+            let sender = bundle.sender();
+            if inner.bundles.values().filter(|other| other.sender() == sender).count() >=
+                config.max_bundles_per_sender
+            {
+                return Err(BundlePoolError::SenderLimitReached(sender.unwrap_or_default()))
+            }
+
+            if inner.bundles.len() >= config.max_bundles {
+                let Some((cheapest, _)) = inner
+                    .bundles
+                    .iter()
+                    .map(|(hash, bundle)| (*hash, bundle.priority_fee_per_gas()))
+                    .min_by_key(|(_, priority_fee)| *priority_fee)
+                    .filter(|(_, priority_fee)| bundle.priority_fee_per_gas() > *priority_fee)
+                else {
+                    return Err(BundlePoolError::Underpriced)
+                };
+                inner.bundles.remove(&cheapest);
+            }
+        }
+
+        inner.bundles.insert(hash, Arc::new(bundle));
+        Ok(hash)
+    }
+
+    /// Removes the bundle with the given hash.
+    pub fn remove(&self, hash: &B256) -> Option<Arc<Bundle<T>>> {
+        self.inner.write().bundles.remove(hash)
+    }
+
+    /// Returns all bundles that can be included in the given block.
+    ///
+    /// Bundles whose block range ends before the given block are removed.
+    pub fn bundles_for_block(&self, block_number: u64) -> Vec<Arc<Bundle<T>>> {
+        let mut inner = self.inner.write();
+        inner.bundles.retain(|_, bundle| bundle.max_block_number >= block_number);
+        inner
+            .bundles
+            .values()
+            .filter(|bundle| bundle.block_number <= block_number)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().bundles.len()
+    }
+
+    /// Returns true if the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: SignedTransaction> Default for BundlePool<T> {
+    fn default() -> Self {
+        Self::new(BundlePoolConfig::default())
+    }
+}
+
+impl<T> Clone for BundlePool<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::Signature;
+    use reth_ethereum_primitives::TransactionSigned;
+
+    fn bundle(
+        sender: Address,
+        gas_price: u128,
+        block_number: u64,
+        max_block_number: u64,
+    ) -> Bundle<TransactionSigned> {
+        // make the transaction, and so the bundle hash, unique per sender and block range
+        let nonce = (block_number << 32) | (max_block_number << 8) | sender.0[19] as u64;
+        let tx = TransactionSigned::new_unhashed(
+            TxLegacy { nonce, gas_price, gas_limit: 21_000, ..Default::default() }.into(),
+            Signature::test_signature(),
+        );
+        Bundle::new(
+            block_number,
+            max_block_number,
+            vec![BundleTransaction {
+                tx: Recovered::new_unchecked(tx, sender),
+                can_revert: false,
+                refund_percent: None,
+            }],
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn refunds() {
+        let searcher = Address::with_last_byte(1);
+        let user = Address::with_last_byte(2);
+        let mut bundle = bundle(searcher, 1, 1, 1);
+        let mut refunded = bundle.transactions[0].clone();
+        refunded.tx = Recovered::new_unchecked(refunded.tx.into_inner(), user);
+        refunded.refund_percent = Some(90);
+        bundle.transactions.insert(0, refunded);
+
+        // the refunded transaction doesn't add to the refundable value
+        let diffs = [U256::from(1_000_000), U256::from(10_000_000)];
+        let payout_fee = U256::from(BUNDLE_PAYOUT_MAX_COST);
+        assert_eq!(
+            bundle.refunds(&diffs, 1),
+            Some(vec![(user, U256::from(9_000_000) - payout_fee)])
+        );
+
+        bundle.refund_configs = vec![
+            RefundConfig { address: Address::with_last_byte(3), percent: 60 },
+            RefundConfig { address: Address::with_last_byte(4), percent: 40 },
+        ];
+        assert_eq!(
+            bundle.refunds(&diffs, 1),
+            Some(vec![
+                (Address::with_last_byte(3), U256::from(5_400_000) - payout_fee),
+                (Address::with_last_byte(4), U256::from(3_600_000) - payout_fee),
+            ])
+        );
+
+        assert_eq!(bundle.payout_count(), 2);
+
+        // the refund doesn't cover the payout transaction
+        assert_eq!(bundle.refunds(&diffs, 1_000), None);
+    }
+
+    #[test]
+    fn bundles_for_block() {
+        let pool = BundlePool::default();
+        let first = pool.add(bundle(Address::ZERO, 1, 1, 2), 0).unwrap();
+        let second = pool.add(bundle(Address::ZERO, 1, 2, 3), 0).unwrap();
+        assert_ne!(first, second);
+
+        let hashes = |block| {
+            pool.bundles_for_block(block).iter().map(|bundle| bundle.hash).collect::<Vec<_>>()
+        };
+        assert_eq!(hashes(1), vec![first]);
+        assert_eq!(hashes(2).len(), 2);
+        assert_eq!(hashes(3), vec![second]);
+        assert_eq!(pool.len(), 1);
+        assert!(hashes(4).is_empty());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn rejects_block_range() {
+        let pool = BundlePool::new(BundlePoolConfig { max_blocks_ahead: 5, ..Default::default() });
+        assert!(matches!(
+            pool.add(bundle(Address::ZERO, 1, 9, 10), 10),
+            Err(BundlePoolError::Expired { max_block_number: 10 })
+        ));
+        assert!(matches!(
+            pool.add(bundle(Address::ZERO, 1, 11, 16), 10),
+            Err(BundlePoolError::TooFarInFuture { max_block_number: 16, limit: 15 })
+        ));
+        assert!(pool.add(bundle(Address::ZERO, 1, 11, 15), 10).is_ok());
+    }
+
+    #[test]
+    fn limits_bundles_per_sender() {
+        let pool =
+            BundlePool::new(BundlePoolConfig { max_bundles_per_sender: 1, ..Default::default() });
+        let sender = Address::with_last_byte(1);
+        let first = pool.add(bundle(sender, 1, 1, 1), 0).unwrap();
+        assert!(matches!(
+            pool.add(bundle(sender, 1, 1, 2), 0),
+            Err(BundlePoolError::SenderLimitReached(address)) if address == sender
+        ));
+        // replacing the same bundle is fine
+        assert_eq!(pool.add(bundle(sender, 1, 1, 1), 0).unwrap(), first);
+        assert!(pool.add(bundle(Address::ZERO, 1, 1, 2), 0).is_ok());
+
+        // the bundle expired, so the sender can submit another one
+        assert!(pool.add(bundle(sender, 1, 2, 3), 1).is_ok());
+    }
+
+    #[test]
+    fn evicts_cheapest_bundle() {
+        let pool = BundlePool::new(BundlePoolConfig { max_bundles: 2, ..Default::default() });
+        let cheap = pool.add(bundle(Address::with_last_byte(1), 1, 1, 2), 0).unwrap();
+        let expensive = pool.add(bundle(Address::with_last_byte(2), 3, 1, 2), 0).unwrap();
+
+        assert!(matches!(
+            pool.add(bundle(Address::with_last_byte(3), 1, 1, 2), 0),
+            Err(BundlePoolError::Underpriced)
+        ));
+
+        let replacement = pool.add(bundle(Address::with_last_byte(3), 2, 1, 2), 0).unwrap();
+        let mut hashes =
+            pool.bundles_for_block(1).iter().map(|bundle| bundle.hash).collect::<Vec<_>>();
+        hashes.sort();
+        let mut expected = vec![expensive, replacement];
+        expected.sort();
+        assert_eq!(hashes, expected);
+        assert!(!hashes.contains(&cheap));
+
+        // expired bundles make room before anything is evicted
+        assert!(pool.add(bundle(Address::with_last_byte(4), 1, 3, 4), 2).is_ok());
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -8,8 +8,14 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod bundle;
 mod traits;
 mod transaction;
 
+pub use bundle::{
+    Bundle, BundlePool, BundlePoolConfig, BundlePoolError, BundleTransaction,
+    BUNDLE_PAYOUT_MAX_COST, DEFAULT_MAX_BLOCKS_AHEAD, DEFAULT_MAX_BUNDLES,
+    DEFAULT_MAX_BUNDLES_PER_SENDER,
+};
 pub use traits::{BestPayloadTransactions, NoopPayloadTransactions, PayloadTransactions};
 pub use transaction::{PayloadTransactionsChain, PayloadTransactionsFixed};
@@ -16,7 +16,7 @@ use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::error;
 pub use user::{
     AccountHistory, Receipts as UserReceipts, ReceiptsByLogs, SenderRecovery, StorageHistory,
-    TransactionLookup,
+    TransactionLookup, TrieChangeSets,
 };
 
 /// A segment represents a pruning of some portion of the data.
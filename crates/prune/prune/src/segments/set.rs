@@ -1,6 +1,6 @@
 use crate::segments::{
     AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    TrieChangeSets, UserReceipts,
 };
 use alloy_eips::eip2718::Encodable2718;
 use reth_db_api::{table::Value, transaction::DbTxMut};
@@ -66,6 +66,7 @@ where
             account_history,
             storage_history,
             bodies_history: _,
+            trie_changesets,
             receipts_log_filter,
         } = prune_modes;
 
@@ -80,6 +81,8 @@ where
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
+            // Trie changesets
+            .segment_opt(trie_changesets.map(TrieChangeSets::new))
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
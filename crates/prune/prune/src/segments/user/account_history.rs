@@ -1,21 +1,26 @@
 use crate::{
     db_ext::DbTxPruneExt,
-    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    segments::{
+        user::history::{prune_history_indices, prune_static_file_changesets},
+        PruneInput, Segment,
+    },
     PrunerError,
 };
 use itertools::Itertools;
 use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut};
-use reth_provider::DBProvider;
+use reth_provider::{ChangeSetReader, DBProvider, StaticFileProviderFactory};
 use reth_prune_types::{
     PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
 };
+use reth_static_file_types::StaticFileSegment;
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
 
 /// Number of account history tables to prune in one step.
 ///
 /// Account History consists of two tables: [`tables::AccountChangeSets`] and
-/// [`tables::AccountsHistory`]. We want to prune them to the same block number.
+/// [`tables::AccountsHistory`]. We want to prune them to the same block number. Changesets that
+/// were moved to the [`StaticFileSegment::AccountChangeSets`] segment are pruned from there.
 const ACCOUNT_HISTORY_TABLES_TO_PRUNE: usize = 2;
 
 #[derive(Debug)]
@@ -31,7 +36,7 @@ impl AccountHistory {
 
 impl<Provider> Segment<Provider> for AccountHistory
 where
-    Provider: DBProvider<Tx: DbTxMut>,
+    Provider: DBProvider<Tx: DbTxMut> + StaticFileProviderFactory,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::AccountHistory
@@ -78,7 +83,24 @@ where
         // size should be up to 0.5MB + some hashmap overhead. `blocks_since_last_run` is
         // additionally limited by the `max_reorg_depth`, so no OOM is expected here.
         let mut highest_deleted_accounts = FxHashMap::default();
-        let (pruned_changesets, done) =
+
+        // The oldest changesets may be stored in static files, the rest is in the database.
+        let (pruned_static_file_changesets, last_static_file_pruned_block, static_file_done) =
+            prune_static_file_changesets(
+                provider,
+                StaticFileSegment::AccountChangeSets,
+                range.clone(),
+                &mut limiter,
+                |static_file_provider, block_number| {
+                    static_file_provider.account_block_changeset(block_number)
+                },
+                |block_number, account| {
+                    highest_deleted_accounts.insert(account.address, block_number);
+                },
+            )?;
+        trace!(target: "pruner", pruned = %pruned_static_file_changesets, done = %static_file_done, "Pruned account history (static file changesets)");
+
+        let (pruned_changesets, done) = if static_file_done && !limiter.is_limit_reached() {
             provider.tx_ref().prune_table_with_range::<tables::AccountChangeSets>(
                 range,
                 &mut limiter,
@@ -87,13 +109,18 @@ where
                     highest_deleted_accounts.insert(account.address, block_number);
                     last_changeset_pruned_block = Some(block_number);
                 },
-            )?;
+            )?
+        } else {
+            (0, false)
+        };
         trace!(target: "pruner", pruned = %pruned_changesets, %done, "Pruned account history (changesets)");
 
         let last_changeset_pruned_block = last_changeset_pruned_block
             // If there's more account changesets to prune, set the checkpoint block number to
             // previous, so we could finish pruning its account changesets on the next run.
             .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            // Blocks in static files are always pruned entirely.
+            .or(if done { None } else { last_static_file_pruned_block })
             .unwrap_or(range_end);
 
         // Sort highest deleted block numbers by account address and turn them into sharded keys.
@@ -115,7 +142,7 @@ where
 
         Ok(SegmentOutput {
             progress,
-            pruned: pruned_changesets + outcomes.deleted,
+            pruned: pruned_static_file_changesets + pruned_changesets + outcomes.deleted,
             checkpoint: Some(SegmentOutputCheckpoint {
                 block_number: Some(last_changeset_pruned_block),
                 tx_number: None,
@@ -132,12 +159,15 @@ mod tests {
     };
     use alloy_primitives::{BlockNumber, B256};
     use assert_matches::assert_matches;
-    use reth_db_api::{tables, BlockNumberList};
-    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_db_api::{models::AccountBeforeTx, tables, BlockNumberList};
+    use reth_provider::{
+        DatabaseProviderFactory, PruneCheckpointReader, StaticFileProviderFactory, StaticFileWriter,
+    };
     use reth_prune_types::{
         PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress, PruneSegment,
     };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_static_file_types::StaticFileSegment;
     use reth_testing_utils::generators::{
         self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
     };
@@ -303,4 +333,98 @@ mod tests {
         test_prune(998, 2, (PruneProgress::Finished, 998));
         test_prune(1400, 3, (PruneProgress::Finished, 804));
     }
+
+    #[test]
+    fn prune_static_file_changesets() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=20,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let accounts = random_eoa_accounts(&mut rng, 2).into_iter().collect::<BTreeMap<_, _>>();
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..0,
+            0..0,
+        );
+
+        // The changesets of blocks 0..=9 are stored in static files, the rest in the database.
+        let static_file_provider = db.factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        for (block_number, changeset) in changesets.iter().enumerate().take(10) {
+            let mut changes = changeset
+                .iter()
+                .map(|(address, account, _)| AccountBeforeTx {
+                    address: *address,
+                    info: Some(*account),
+                })
+                .collect::<Vec<_>>();
+            changes.sort_by_key(|change| change.address);
+            writer.append_account_changeset(block_number as BlockNumber, changes).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        db.insert_changesets(changesets[10..].to_vec(), Some(10)).expect("insert changesets");
+        db.insert_history(changesets.clone(), None).expect("insert history");
+
+        let prune = |to_block: BlockNumber| {
+            let prune_mode = PruneMode::Before(to_block);
+            let input = PruneInput {
+                previous_checkpoint: db
+                    .factory
+                    .provider()
+                    .unwrap()
+                    .get_prune_checkpoint(PruneSegment::AccountHistory)
+                    .unwrap(),
+                to_block,
+                limiter: PruneLimiter::default(),
+            };
+            let segment = AccountHistory::new(prune_mode);
+
+            let provider = db.factory.database_provider_rw().unwrap();
+            let result = segment.prune(&provider, input).unwrap();
+            segment
+                .save_checkpoint(
+                    &provider,
+                    result.checkpoint.unwrap().as_prune_checkpoint(prune_mode),
+                )
+                .unwrap();
+            provider.commit().expect("commit");
+
+            assert!(result.progress.is_finished());
+            assert!(db
+                .table::<tables::AccountsHistory>()
+                .unwrap()
+                .iter()
+                .all(|(_, blocks)| blocks.iter().all(|block| block > to_block)));
+        };
+
+        // Pruning blocks in static files keeps the file until all of its blocks are pruned
+        prune(5);
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            Some(9)
+        );
+
+        prune(15);
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            None
+        );
+        assert!(db
+            .table::<tables::AccountChangeSets>()
+            .unwrap()
+            .iter()
+            .all(|(block, _)| *block > 15));
+    }
 }
@@ -1,3 +1,4 @@
+use crate::{PruneLimiter, PrunerError};
 use alloy_primitives::BlockNumber;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
@@ -6,7 +7,11 @@ use reth_db_api::{
     transaction::DbTxMut,
     BlockNumberList, DatabaseError, RawKey, RawTable, RawValue,
 };
-use reth_provider::DBProvider;
+use reth_provider::{
+    providers::StaticFileProvider, DBProvider, ProviderResult, StaticFileProviderFactory,
+};
+use reth_static_file_types::StaticFileSegment;
+use std::ops::RangeInclusive;
 
 enum PruneShardOutcome {
     Deleted,
@@ -21,6 +26,63 @@ pub(crate) struct PrunedIndices {
     pub(crate) unchanged: usize,
 }
 
+/// Prunes the changesets of the blocks in `range` that are stored in the changesets static file
+/// `segment`, calling `delete_callback` with the changes of each pruned block.
+///
+/// Static files can only be removed entirely, so a file is only deleted once all of its blocks are
+/// pruned. Blocks are pruned as a whole, even if that exceeds the limit.
+///
+/// Returns the number of pruned changes, the last pruned block and whether all blocks of the range
+/// that are stored in static files were pruned.
+pub(crate) fn prune_static_file_changesets<Provider, C>(
+    provider: &Provider,
+    segment: StaticFileSegment,
+    range: RangeInclusive<BlockNumber>,
+    limiter: &mut PruneLimiter,
+    read_changeset: impl Fn(
+        &StaticFileProvider<Provider::Primitives>,
+        BlockNumber,
+    ) -> ProviderResult<Vec<C>>,
+    mut delete_callback: impl FnMut(BlockNumber, C),
+) -> Result<(usize, Option<BlockNumber>, bool), PrunerError>
+where
+    Provider: StaticFileProviderFactory,
+{
+    let static_file_provider = provider.static_file_provider();
+    let (Some(lowest_file), Some(highest_block)) = (
+        static_file_provider.get_lowest_static_file_block(segment),
+        static_file_provider.get_highest_static_file_block(segment),
+    ) else {
+        return Ok((0, None, true))
+    };
+    let start = (*range.start()).max(static_file_provider.find_fixed_range(lowest_file).start());
+    let end = (*range.end()).min(highest_block);
+
+    let mut pruned = 0;
+    let mut last_pruned_block = None;
+    let mut done = true;
+    for block_number in start..=end {
+        if limiter.is_limit_reached() {
+            done = false;
+            break
+        }
+
+        let changes = read_changeset(&static_file_provider, block_number)?;
+        limiter.increment_deleted_entries_count_by(changes.len());
+        pruned += changes.len();
+        for change in changes {
+            delete_callback(block_number, change);
+        }
+        last_pruned_block = Some(block_number);
+    }
+
+    if let Some(last_pruned_block) = last_pruned_block {
+        static_file_provider.delete_segment_below_block(segment, last_pruned_block + 1)?;
+    }
+
+    Ok((pruned, last_pruned_block, done))
+}
+
 /// Prune history indices according to the provided list of highest sharded keys.
 ///
 /// Returns total number of deleted, updated and unchanged entities.
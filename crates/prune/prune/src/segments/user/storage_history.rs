@@ -1,6 +1,9 @@
 use crate::{
     db_ext::DbTxPruneExt,
-    segments::{user::history::prune_history_indices, PruneInput, Segment, SegmentOutput},
+    segments::{
+        user::history::{prune_history_indices, prune_static_file_changesets},
+        PruneInput, Segment, SegmentOutput,
+    },
     PrunerError,
 };
 use itertools::Itertools;
@@ -9,15 +12,17 @@ use reth_db_api::{
     tables,
     transaction::DbTxMut,
 };
-use reth_provider::DBProvider;
+use reth_provider::{DBProvider, StaticFileProviderFactory, StorageChangeSetReader};
 use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutputCheckpoint};
+use reth_static_file_types::StaticFileSegment;
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
 
 /// Number of storage history tables to prune in one step
 ///
 /// Storage History consists of two tables: [`tables::StorageChangeSets`] and
-/// [`tables::StoragesHistory`]. We want to prune them to the same block number.
+/// [`tables::StoragesHistory`]. We want to prune them to the same block number. Changesets that
+/// were moved to the [`StaticFileSegment::StorageChangeSets`] segment are pruned from there.
 const STORAGE_HISTORY_TABLES_TO_PRUNE: usize = 2;
 
 #[derive(Debug)]
@@ -33,7 +38,7 @@ impl StorageHistory {
 
 impl<Provider> Segment<Provider> for StorageHistory
 where
-    Provider: DBProvider<Tx: DbTxMut>,
+    Provider: DBProvider<Tx: DbTxMut> + StaticFileProviderFactory,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::StorageHistory
@@ -80,7 +85,24 @@ where
         // size should be up to 0.5MB + some hashmap overhead. `blocks_since_last_run` is
         // additionally limited by the `max_reorg_depth`, so no OOM is expected here.
         let mut highest_deleted_storages = FxHashMap::default();
-        let (pruned_changesets, done) =
+
+        // The oldest changesets may be stored in static files, the rest is in the database.
+        let (pruned_static_file_changesets, last_static_file_pruned_block, static_file_done) =
+            prune_static_file_changesets(
+                provider,
+                StaticFileSegment::StorageChangeSets,
+                range.clone(),
+                &mut limiter,
+                |static_file_provider, block_number| {
+                    static_file_provider.storage_changeset(block_number)
+                },
+                |block_number, (BlockNumberAddress((_, address)), entry)| {
+                    highest_deleted_storages.insert((address, entry.key), block_number);
+                },
+            )?;
+        trace!(target: "pruner", deleted = %pruned_static_file_changesets, done = %static_file_done, "Pruned storage history (static file changesets)");
+
+        let (pruned_changesets, done) = if static_file_done && !limiter.is_limit_reached() {
             provider.tx_ref().prune_table_with_range::<tables::StorageChangeSets>(
                 BlockNumberAddress::range(range),
                 &mut limiter,
@@ -89,13 +111,18 @@ where
                     highest_deleted_storages.insert((address, entry.key), block_number);
                     last_changeset_pruned_block = Some(block_number);
                 },
-            )?;
+            )?
+        } else {
+            (0, false)
+        };
         trace!(target: "pruner", deleted = %pruned_changesets, %done, "Pruned storage history (changesets)");
 
         let last_changeset_pruned_block = last_changeset_pruned_block
             // If there's more storage changesets to prune, set the checkpoint block number to
             // previous, so we could finish pruning its storage changesets on the next run.
             .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            // Blocks in static files are always pruned entirely.
+            .or(if done { None } else { last_static_file_pruned_block })
             .unwrap_or(range_end);
 
         // Sort highest deleted block numbers by account address and storage key and turn them into
@@ -122,7 +149,7 @@ where
 
         Ok(SegmentOutput {
             progress,
-            pruned: pruned_changesets + outcomes.deleted,
+            pruned: pruned_static_file_changesets + pruned_changesets + outcomes.deleted,
             checkpoint: Some(SegmentOutputCheckpoint {
                 block_number: Some(last_changeset_pruned_block),
                 tx_number: None,
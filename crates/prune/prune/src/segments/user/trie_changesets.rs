@@ -0,0 +1,170 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use alloy_primitives::BlockNumber;
+use reth_db_api::{models::BlockNumberHashedAddress, tables, transaction::DbTxMut};
+use reth_provider::DBProvider;
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use tracing::{instrument, trace};
+
+/// Number of trie changeset tables to prune in one step.
+///
+/// Trie changesets consist of two tables: [`tables::AccountsTrieChangeSets`] and
+/// [`tables::StoragesTrieChangeSets`]. We want to prune them to the same block number.
+const TRIE_CHANGESETS_TABLES_TO_PRUNE: usize = 2;
+
+#[derive(Debug)]
+pub struct TrieChangeSets {
+    mode: PruneMode,
+}
+
+impl TrieChangeSets {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for TrieChangeSets
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::TrieChangeSets
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No trie changesets to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_start = *range.start();
+        let range_end = *range.end();
+
+        let mut limiter = if let Some(limit) = input.limiter.deleted_entries_limit() {
+            input.limiter.set_deleted_entries_limit(limit / TRIE_CHANGESETS_TABLES_TO_PRUNE)
+        } else {
+            input.limiter
+        };
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                limiter.interrupt_reason(),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        let mut last_account_pruned_block = None;
+        let (pruned_accounts, accounts_done) =
+            provider.tx_ref().prune_table_with_range::<tables::AccountsTrieChangeSets>(
+                range.clone(),
+                &mut limiter,
+                |_| false,
+                |(block_number, _)| last_account_pruned_block = Some(block_number),
+            )?;
+        trace!(target: "pruner", pruned = %pruned_accounts, done = %accounts_done, "Pruned trie changesets (accounts)");
+
+        let mut last_storage_pruned_block = None;
+        let (pruned_storages, storages_done) =
+            provider.tx_ref().prune_table_with_range::<tables::StoragesTrieChangeSets>(
+                BlockNumberHashedAddress::range(range),
+                &mut limiter,
+                |_| false,
+                |(key, _)| last_storage_pruned_block = Some(key.block_number()),
+            )?;
+        trace!(target: "pruner", pruned = %pruned_storages, done = %storages_done, "Pruned trie changesets (storages)");
+
+        // The highest block which changesets were fully pruned from the table. If there's more
+        // changesets to prune, it's the block previous to the last pruned one, so we could finish
+        // pruning its changesets on the next run.
+        let pruned_to = |done: bool, last_pruned_block: Option<BlockNumber>| {
+            if done {
+                Some(range_end)
+            } else {
+                last_pruned_block.unwrap_or(range_start).checked_sub(1)
+            }
+        };
+        let last_pruned_block = pruned_to(accounts_done, last_account_pruned_block)
+            .min(pruned_to(storages_done, last_storage_pruned_block));
+
+        let done = accounts_done && storages_done;
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: pruned_accounts + pruned_storages,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, PruneLimiter, Segment, TrieChangeSets};
+    use alloy_primitives::B256;
+    use reth_db_api::{models::BlockNumberHashedAddress, tables, transaction::DbTxMut};
+    use reth_provider::DatabaseProviderFactory;
+    use reth_prune_types::{PruneMode, PruneProgress};
+    use reth_stages::test_utils::TestStageDB;
+    use reth_trie::{Nibbles, StoredNibblesSubKey, TrieChangeSetsEntry};
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+
+        let hashed_address = B256::random();
+        db.commit(|tx| {
+            for block_number in 1..=10 {
+                for nibble in 0..2 {
+                    let entry = TrieChangeSetsEntry {
+                        nibbles: StoredNibblesSubKey(Nibbles::from_nibbles_unchecked([nibble])),
+                        node: None,
+                    };
+                    tx.put::<tables::AccountsTrieChangeSets>(block_number, entry.clone())?;
+                    tx.put::<tables::StoragesTrieChangeSets>(
+                        BlockNumberHashedAddress((block_number, hashed_address)),
+                        entry,
+                    )?;
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let input =
+            PruneInput { previous_checkpoint: None, to_block: 5, limiter: PruneLimiter::default() };
+        let segment = TrieChangeSets::new(PruneMode::Before(6));
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(result.progress, PruneProgress::Finished);
+        assert_eq!(result.pruned, 20);
+        assert_eq!(result.checkpoint.unwrap().block_number, Some(5));
+
+        let accounts = db.table::<tables::AccountsTrieChangeSets>().unwrap();
+        assert_eq!(accounts.len(), 10);
+        assert!(accounts.iter().all(|(block_number, _)| *block_number > 5));
+        let storages = db.table::<tables::StoragesTrieChangeSets>().unwrap();
+        assert_eq!(storages.len(), 10);
+        assert!(storages.iter().all(|(key, _)| key.block_number() > 5));
+    }
+}
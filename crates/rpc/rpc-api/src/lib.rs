@@ -44,7 +44,9 @@ pub mod servers {
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{
+            RethApiServer, RethNodeEventsApiServer, RethPayloadApiServer, RethSyncApiServer,
+        },
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
@@ -75,7 +77,9 @@ pub mod clients {
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{
+            RethApiClient, RethNodeEventsApiClient, RethPayloadApiClient, RethSyncApiClient,
+        },
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
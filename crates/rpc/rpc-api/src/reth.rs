@@ -1,6 +1,9 @@
 use alloy_eips::BlockId;
 use alloy_primitives::{Address, U256};
+use alloy_rpc_types_engine::PayloadId;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_payload_primitives::PayloadBuildReport;
+use reth_stages_types::SyncStatus;
 use std::collections::HashMap;
 
 // Required for the subscription attribute below
@@ -25,3 +28,48 @@ pub trait RethApi {
     )]
     async fn reth_subscribe_chain_notifications(&self) -> jsonrpsee::core::SubscriptionResult;
 }
+
+/// Reth API namespace for inspecting the payload builder
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethPayloadApi {
+    /// Returns the report of which candidate transactions were included in the best payload for
+    /// the given payload id, and why the others were skipped.
+    #[method(name = "getPayloadBuildReport")]
+    async fn reth_get_payload_build_report(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<Option<PayloadBuildReport>>;
+}
+
+/// Reth API namespace for inspecting the sync progress of the node
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethSyncApi {
+    /// Returns the detailed sync progress of the node: the progress of every pipeline stage,
+    /// the engine backfill sync and the persistence of canonical blocks.
+    #[method(name = "syncStatus")]
+    async fn reth_sync_status(&self) -> RpcResult<SyncStatus>;
+
+    /// Subscribe to updates of the detailed sync progress of the node.
+    #[subscription(
+        name = "subscribeSyncStatus",
+        unsubscribe = "unsubscribeSyncStatus",
+        item = reth_stages_types::SyncStatus
+    )]
+    async fn reth_subscribe_sync_status(&self) -> jsonrpsee::core::SubscriptionResult;
+}
+
+/// Reth API namespace for following the events of the node
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethNodeEventsApi {
+    /// Subscribe to the pipeline, consensus engine, pruner and static file producer events of the
+    /// node.
+    #[subscription(
+        name = "subscribeNodeEvents",
+        unsubscribe = "unsubscribeNodeEvents",
+        item = reth_stages_types::NodeEventRecord
+    )]
+    async fn reth_subscribe_node_events(&self) -> jsonrpsee::core::SubscriptionResult;
+}
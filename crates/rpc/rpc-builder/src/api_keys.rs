@@ -0,0 +1,890 @@
+//! API key authentication, method ACLs and per-key quotas for the RPC server.
+//!
+//! API keys are configured in a TOML file that is reloaded when it changes:
+//!
+//! ```toml
+//! # compute units charged per method, methods not listed cost 1 unit
+//! [compute_units]
+//! eth_getLogs = 20
+//! debug_traceTransaction = 100
+//!
+//! [[keys]]
+//! name = "indexer"
+//! key = "0f1e2d3c4b5a"
+//! # namespaces (`eth`) or methods (`debug_traceTransaction`), all methods are allowed if empty
+//! allow = ["eth", "net", "web3", "debug_traceTransaction"]
+//! deny = ["eth_sendRawTransaction"]
+//! requests_per_second = 50
+//! compute_units_per_second = 1000
+//! max_block_range = 10000
+//! ```
+//!
+//! Clients pass the key with the `x-api-key` header or as the URL path, e.g.
+//! `http://localhost:8545/0f1e2d3c4b5a`. The key is extracted by the [`ApiKeyHttpLayer`] and
+//! checked for every call by the [`ApiKeyRpcLayer`].
+//!
+//! GraphQL requests are checked with [`ApiKeys::check_http_request`] and charged as calls of the
+//! [`GRAPHQL_METHOD`], so they can be allowed or denied with the `graphql` namespace.
+
+use http::{uri::PathAndQuery, HeaderName, Request, StatusCode, Uri};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, ErrorObjectOwned, Id},
+    MethodResponse,
+};
+use parking_lot::{Mutex, RwLock};
+use reth_metrics::{metrics::Counter, Metrics};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant, SystemTime},
+};
+use tower::{Layer, Service};
+use tracing::{debug, warn};
+
+/// The header that carries the API key.
+pub const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
+
+/// How often the API keys file is checked for changes.
+pub const API_KEYS_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Compute units charged for methods without an explicit cost.
+pub const DEFAULT_COMPUTE_UNITS: u64 = 1;
+
+/// Error code for requests without a valid API key.
+pub const UNAUTHORIZED_CODE: i32 = -32001;
+
+/// Error code for methods that are not allowed for the API key (EIP-1474 "method not supported").
+pub const METHOD_NOT_ALLOWED_CODE: i32 = -32004;
+
+/// Error code for requests that exceed a quota of the API key (EIP-1474 "limit exceeded").
+pub const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// Method that GraphQL requests are checked and charged as.
+pub const GRAPHQL_METHOD: &str = "graphql_query";
+
+/// Methods whose block range is limited by [`ApiKeyConfig::max_block_range`].
+const BLOCK_RANGE_METHODS: &[&str] = &["eth_getLogs", "eth_newFilter", "trace_filter"];
+
+/// Contents of the API keys file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeysConfig {
+    /// Compute units charged per method.
+    #[serde(default)]
+    pub compute_units: HashMap<String, u64>,
+    /// The configured API keys.
+    #[serde(default)]
+    pub keys: Vec<ApiKeyConfig>,
+}
+
+impl ApiKeysConfig {
+    /// Parses the config from TOML.
+    pub fn from_toml(s: &str) -> Result<Self, ApiKeysError> {
+        let config: Self = toml::from_str(s)?;
+        let mut names = std::collections::HashSet::new();
+        let mut keys = std::collections::HashSet::new();
+        for key in &config.keys {
+            if !names.insert(key.name.as_str()) {
+                return Err(ApiKeysError::DuplicateName(key.name.clone()))
+            }
+            if !keys.insert(key.key.as_str()) {
+                return Err(ApiKeysError::DuplicateKey(key.name.clone()))
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Settings of a single API key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// Name of the key, used in logs and metrics.
+    pub name: String,
+    /// The secret key.
+    pub key: String,
+    /// Allowed namespaces or methods, all methods are allowed if empty.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Denied namespaces or methods, takes precedence over `allow`.
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Maximum number of requests per second.
+    pub requests_per_second: Option<u64>,
+    /// Maximum number of compute units per second.
+    pub compute_units_per_second: Option<u64>,
+    /// Maximum block range of `eth_getLogs`, `eth_newFilter` and `trace_filter` requests.
+    pub max_block_range: Option<u64>,
+}
+
+impl ApiKeyConfig {
+    /// Returns true if the method is allowed for this key.
+    pub fn is_method_allowed(&self, method: &str) -> bool {
+        let matches = |rule: &String| {
+            rule == method || method.split_once('_').is_some_and(|(namespace, _)| namespace == rule)
+        };
+        !self.deny.iter().any(matches) && (self.allow.is_empty() || self.allow.iter().any(matches))
+    }
+}
+
+/// Errors when loading the API keys file.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeysError {
+    /// Failed to read the file.
+    #[error("failed to read API keys file {path}: {err}")]
+    Io {
+        /// Path of the file.
+        path: PathBuf,
+        /// The underlying error.
+        err: std::io::Error,
+    },
+    /// Failed to parse the file.
+    #[error("failed to parse API keys file: {0}")]
+    Parse(#[from] toml::de::Error),
+    /// The same name is used by multiple keys.
+    #[error("duplicate API key name {0}")]
+    DuplicateName(String),
+    /// The same secret is used by multiple keys.
+    #[error("API key {0} uses the same secret as another key")]
+    DuplicateKey(String),
+}
+
+/// Reasons a request is rejected.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ApiKeyRejection {
+    /// No API key was provided.
+    #[error("missing API key")]
+    MissingKey,
+    /// The API key is not configured.
+    #[error("invalid API key")]
+    InvalidKey,
+    /// The method is not allowed for the key.
+    #[error("method {0} is not allowed for this API key")]
+    MethodNotAllowed(String),
+    /// The requests per second quota was exceeded.
+    #[error("request rate limit of {0} requests per second exceeded")]
+    RateLimited(u64),
+    /// The compute units per second quota was exceeded.
+    #[error("compute unit limit of {0} units per second exceeded")]
+    ComputeUnitsExceeded(u64),
+    /// The requested block range is too large.
+    #[error("block range of {range} exceeds the maximum of {max} blocks for this API key")]
+    BlockRangeExceeded {
+        /// The requested range.
+        range: u64,
+        /// The maximum range of the key.
+        max: u64,
+    },
+}
+
+impl ApiKeyRejection {
+    /// Returns the JSON-RPC error code of the rejection.
+    pub const fn code(&self) -> i32 {
+        match self {
+            Self::MissingKey | Self::InvalidKey => UNAUTHORIZED_CODE,
+            Self::MethodNotAllowed(_) => METHOD_NOT_ALLOWED_CODE,
+            Self::RateLimited(_) |
+            Self::ComputeUnitsExceeded(_) |
+            Self::BlockRangeExceeded { .. } => LIMIT_EXCEEDED_CODE,
+        }
+    }
+
+    /// Returns the HTTP status of the rejection, for endpoints that are not JSON-RPC.
+    pub const fn http_status(&self) -> StatusCode {
+        match self {
+            Self::MissingKey | Self::InvalidKey => StatusCode::UNAUTHORIZED,
+            Self::MethodNotAllowed(_) => StatusCode::FORBIDDEN,
+            Self::RateLimited(_) |
+            Self::ComputeUnitsExceeded(_) |
+            Self::BlockRangeExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
+
+impl From<ApiKeyRejection> for ErrorObjectOwned {
+    fn from(rejection: ApiKeyRejection) -> Self {
+        ErrorObject::owned(rejection.code(), rejection.to_string(), None::<()>)
+    }
+}
+
+/// The API key of a request, inserted into the request extensions by the [`ApiKeyHttpLayer`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct ApiKey(pub String);
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // never log the secret
+        f.write_str("ApiKey(..)")
+    }
+}
+
+/// Returns the latest block number, used to resolve block tags of range limited requests.
+pub type LatestBlockFn = Arc<dyn Fn() -> Option<u64> + Send + Sync>;
+
+/// Shared, reloadable set of API keys.
+#[derive(Clone)]
+pub struct ApiKeys {
+    inner: Arc<ApiKeysInner>,
+}
+
+struct ApiKeysInner {
+    /// Path of the API keys file, if loaded from a file.
+    path: Option<PathBuf>,
+    /// The currently active keys.
+    state: RwLock<ApiKeysState>,
+    /// Resolves block tags to numbers.
+    latest_block: RwLock<Option<LatestBlockFn>>,
+    /// Metrics that are not tied to a key.
+    metrics: ApiKeysMetrics,
+}
+
+/// The active keys, replaced on reload.
+struct ApiKeysState {
+    /// Last modification time of the loaded file.
+    modified: Option<SystemTime>,
+    /// Compute units charged per method.
+    compute_units: HashMap<String, u64>,
+    /// Keys by secret.
+    keys: HashMap<String, Arc<KeyState>>,
+}
+
+/// A configured key with its quota state.
+struct KeyState {
+    config: ApiKeyConfig,
+    requests: Option<Mutex<TokenBucket>>,
+    compute_units: Option<Mutex<TokenBucket>>,
+    metrics: ApiKeyMetrics,
+}
+
+impl ApiKeys {
+    /// Creates the API keys from the given config.
+    pub fn new(config: ApiKeysConfig) -> Self {
+        Self::with_state(None, ApiKeysState::new(config, None, None))
+    }
+
+    /// Loads the API keys from the given TOML file.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, ApiKeysError> {
+        let path = path.into();
+        let state = ApiKeysState::load(&path, None)?;
+        Ok(Self::with_state(Some(path), state))
+    }
+
+    fn with_state(path: Option<PathBuf>, state: ApiKeysState) -> Self {
+        Self {
+            inner: Arc::new(ApiKeysInner {
+                path,
+                state: RwLock::new(state),
+                latest_block: RwLock::new(None),
+                metrics: ApiKeysMetrics::default(),
+            }),
+        }
+    }
+
+    /// Sets the function used to resolve block tags, e.g. `latest`, of range limited requests.
+    ///
+    /// Without it only numeric block ranges are checked against the maximum block range.
+    pub fn with_latest_block(self, latest_block: LatestBlockFn) -> Self {
+        *self.inner.latest_block.write() = Some(latest_block);
+        self
+    }
+
+    /// Returns the number of configured keys.
+    pub fn len(&self) -> usize {
+        self.inner.state.read().keys.len()
+    }
+
+    /// Returns true if no keys are configured.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reloads the keys if the file changed since it was last loaded.
+    ///
+    /// Keys that are still configured keep the tokens of their quotas, so a reload doesn't reset
+    /// the rate limits. Returns `true` if the keys were reloaded. On error the current keys stay
+    /// active.
+    pub fn reload_if_changed(&self) -> Result<bool, ApiKeysError> {
+        let Some(path) = &self.inner.path else { return Ok(false) };
+        let modified = file_modified(path)?;
+        if modified.is_some() && modified == self.inner.state.read().modified {
+            return Ok(false)
+        }
+        let mut state = self.inner.state.write();
+        *state = ApiKeysState::load(path, Some(&state))?;
+        Ok(true)
+    }
+
+    /// Returns a future that reloads the keys whenever the file changes.
+    ///
+    /// Errors are logged and the previous keys stay active.
+    pub fn reload_on_change(self, interval: Duration) -> impl Future<Output = ()> + Send {
+        async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                match self.reload_if_changed() {
+                    Ok(true) => {
+                        debug!(target: "rpc::api_keys", keys = self.len(), "Reloaded API keys")
+                    }
+                    Ok(false) => {}
+                    Err(err) => {
+                        warn!(target: "rpc::api_keys", %err, "Failed to reload API keys")
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks whether the call is allowed and charges it against the quotas of the key.
+    pub fn check(
+        &self,
+        key: Option<&ApiKey>,
+        method: &str,
+        params: Option<&str>,
+    ) -> Result<(), ApiKeyRejection> {
+        let state = self.inner.state.read();
+        let Some(key) = key else {
+            self.inner.metrics.rejected_unauthorized_total.increment(1);
+            return Err(ApiKeyRejection::MissingKey)
+        };
+        let Some(key) = state.keys.get(&key.0) else {
+            self.inner.metrics.rejected_unauthorized_total.increment(1);
+            return Err(ApiKeyRejection::InvalidKey)
+        };
+        let key = key.clone();
+        let cost = state.compute_units.get(method).copied().unwrap_or(DEFAULT_COMPUTE_UNITS);
+        drop(state);
+
+        key.metrics.requests_total.increment(1);
+
+        if !key.config.is_method_allowed(method) {
+            key.metrics.rejected_method_total.increment(1);
+            return Err(ApiKeyRejection::MethodNotAllowed(method.to_string()))
+        }
+
+        if let Some(max) = key.config.max_block_range {
+            if BLOCK_RANGE_METHODS.contains(&method) {
+                if let Some(range) = params.and_then(|params| self.block_range(params)) {
+                    if range > max {
+                        key.metrics.rejected_block_range_total.increment(1);
+                        return Err(ApiKeyRejection::BlockRangeExceeded { range, max })
+                    }
+                }
+            }
+        }
+
+        if let Some(requests) = &key.requests {
+            if !requests.lock().try_acquire(1) {
+                key.metrics.rejected_rate_limit_total.increment(1);
+                return Err(ApiKeyRejection::RateLimited(
+                    key.config.requests_per_second.unwrap_or_default(),
+                ))
+            }
+        }
+
+        if let Some(compute_units) = &key.compute_units {
+            if !compute_units.lock().try_acquire(cost) {
+                key.metrics.rejected_compute_units_total.increment(1);
+                return Err(ApiKeyRejection::ComputeUnitsExceeded(
+                    key.config.compute_units_per_second.unwrap_or_default(),
+                ))
+            }
+        }
+
+        key.metrics.compute_units_total.increment(cost);
+        Ok(())
+    }
+
+    /// Checks an HTTP request to an endpoint that is not served by the RPC server, e.g. GraphQL,
+    /// as a call of the given method.
+    ///
+    /// The key is extracted from the request the same way as by the [`ApiKeyHttpLayer`].
+    pub fn check_http_request<B>(
+        &self,
+        req: &mut Request<B>,
+        method: &str,
+    ) -> Result<(), ApiKeyRejection> {
+        let key = extract_api_key(req);
+        self.check(key.as_ref(), method, None)
+    }
+
+    /// Returns the block range spanned by the filter in the first parameter, if it can be
+    /// determined.
+    fn block_range(&self, params: &str) -> Option<u64> {
+        let params: Vec<serde_json::Value> = serde_json::from_str(params).ok()?;
+        let filter = params.first()?.as_object()?;
+        if filter.contains_key("blockHash") {
+            return Some(0)
+        }
+        let resolve = |field: &str| match filter.get(field).and_then(|value| value.as_str()) {
+            None | Some("latest" | "pending" | "safe" | "finalized") => {
+                self.inner.latest_block.read().as_ref().and_then(|latest| latest())
+            }
+            Some("earliest") => Some(0),
+            Some(number) => u64::from_str_radix(number.strip_prefix("0x")?, 16).ok(),
+        };
+        Some(resolve("toBlock")?.saturating_sub(resolve("fromBlock")?))
+    }
+}
+
+impl fmt::Debug for ApiKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeys")
+            .field("path", &self.inner.path)
+            .field("keys", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ApiKeysState {
+    /// Creates the state of the configured keys, carrying over the quota state of the keys that
+    /// are also in the `previous` state.
+    fn new(config: ApiKeysConfig, modified: Option<SystemTime>, previous: Option<&Self>) -> Self {
+        let keys = config
+            .keys
+            .into_iter()
+            .map(|config| {
+                let previous = previous.and_then(|previous| previous.keys.get(&config.key));
+                let state = KeyState {
+                    requests: config.requests_per_second.map(|rate| {
+                        let previous = previous.and_then(|previous| previous.requests.as_ref());
+                        Mutex::new(TokenBucket::carry_over(rate, previous))
+                    }),
+                    compute_units: config.compute_units_per_second.map(|rate| {
+                        let previous =
+                            previous.and_then(|previous| previous.compute_units.as_ref());
+                        Mutex::new(TokenBucket::carry_over(rate, previous))
+                    }),
+                    metrics: ApiKeyMetrics::new_with_labels(&[("key", config.name.clone())]),
+                    config,
+                };
+                (state.config.key.clone(), Arc::new(state))
+            })
+            .collect();
+        Self { modified, compute_units: config.compute_units, keys }
+    }
+
+    fn load(path: &Path, previous: Option<&Self>) -> Result<Self, ApiKeysError> {
+        let io_err = |err| ApiKeysError::Io { path: path.to_path_buf(), err };
+        let modified = file_modified(path)?;
+        let contents = std::fs::read_to_string(path).map_err(io_err)?;
+        Ok(Self::new(ApiKeysConfig::from_toml(&contents)?, modified, previous))
+    }
+}
+
+/// Returns the modification time of the file, if supported by the platform.
+fn file_modified(path: &Path) -> Result<Option<SystemTime>, ApiKeysError> {
+    let metadata = std::fs::metadata(path)
+        .map_err(|err| ApiKeysError::Io { path: path.to_path_buf(), err })?;
+    Ok(metadata.modified().ok())
+}
+
+/// A token bucket that allows bursts of up to one second worth of tokens.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Tokens added per second, also the capacity of the bucket.
+    rate: u64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64) -> Self {
+        Self { rate, tokens: rate as f64, last_refill: Instant::now() }
+    }
+
+    /// Creates a bucket with the given rate that keeps the tokens of the previous bucket, if any.
+    fn carry_over(rate: u64, previous: Option<&Mutex<Self>>) -> Self {
+        let mut bucket = Self::new(rate);
+        if let Some(previous) = previous {
+            let previous = previous.lock();
+            bucket.tokens = previous.tokens.min(rate as f64);
+            bucket.last_refill = previous.last_refill;
+        }
+        bucket
+    }
+
+    /// Takes the given number of tokens if available.
+    fn try_acquire(&mut self, tokens: u64) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate as f64).min(self.rate as f64);
+        self.last_refill = now;
+
+        if self.tokens < tokens as f64 {
+            return false
+        }
+        self.tokens -= tokens as f64;
+        true
+    }
+}
+
+/// Metrics of the API keys that are not tied to a key.
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.api_keys")]
+struct ApiKeysMetrics {
+    /// Number of requests rejected because of a missing or unknown API key
+    rejected_unauthorized_total: Counter,
+}
+
+/// Metrics of a single API key.
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.api_keys")]
+struct ApiKeyMetrics {
+    /// Number of calls made with the key
+    requests_total: Counter,
+    /// Number of compute units charged to the key
+    compute_units_total: Counter,
+    /// Number of calls rejected because the method is not allowed
+    rejected_method_total: Counter,
+    /// Number of calls rejected because the request rate was exceeded
+    rejected_rate_limit_total: Counter,
+    /// Number of calls rejected because the compute unit rate was exceeded
+    rejected_compute_units_total: Counter,
+    /// Number of calls rejected because the block range was too large
+    rejected_block_range_total: Counter,
+}
+
+/// HTTP layer that extracts the API key from the `x-api-key` header or the URL path.
+///
+/// The key is inserted as [`ApiKey`] into the request extensions, which are passed on to the
+/// [`ApiKeyRpcLayer`] for every call. If the key is passed as the URL path, the path is rewritten
+/// to `/`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiKeyHttpLayer;
+
+impl<S> Layer<S> for ApiKeyHttpLayer {
+    type Service = ApiKeyHttpService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiKeyHttpService { inner }
+    }
+}
+
+/// Service created by the [`ApiKeyHttpLayer`].
+#[derive(Debug, Clone)]
+pub struct ApiKeyHttpService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for ApiKeyHttpService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        if let Some(key) = extract_api_key(&mut req) {
+            req.extensions_mut().insert(key);
+        }
+        self.inner.call(req)
+    }
+}
+
+/// Extracts the API key from the header or the URL path, stripping it from the path.
+fn extract_api_key<B>(req: &mut Request<B>) -> Option<ApiKey> {
+    let path_key = req.uri().path().trim_matches('/');
+    if !path_key.is_empty() && !path_key.contains('/') {
+        let key = ApiKey(path_key.to_string());
+        let path_and_query = match req.uri().query() {
+            Some(query) => format!("/?{query}"),
+            None => "/".to_string(),
+        };
+        let mut parts = req.uri().clone().into_parts();
+        parts.path_and_query = PathAndQuery::try_from(path_and_query).ok();
+        if let Ok(uri) = Uri::from_parts(parts) {
+            *req.uri_mut() = uri;
+        }
+        return Some(key)
+    }
+
+    req.headers()
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|key| ApiKey(key.to_string()))
+}
+
+/// RPC middleware that enforces the [`ApiKeys`] for every call.
+#[derive(Debug, Clone)]
+pub struct ApiKeyRpcLayer {
+    api_keys: ApiKeys,
+}
+
+impl ApiKeyRpcLayer {
+    /// Creates a new layer that checks calls against the given keys.
+    pub const fn new(api_keys: ApiKeys) -> Self {
+        Self { api_keys }
+    }
+}
+
+impl<S> Layer<S> for ApiKeyRpcLayer {
+    type Service = ApiKeyRpcService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiKeyRpcService { inner, api_keys: self.api_keys.clone() }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that rejects calls that are not allowed for the API key.
+#[derive(Debug, Clone)]
+pub struct ApiKeyRpcService<S> {
+    inner: S,
+    api_keys: ApiKeys,
+}
+
+impl<S> RpcServiceT for ApiKeyRpcService<S>
+where
+    S: RpcServiceT<
+            MethodResponse = MethodResponse,
+            BatchResponse = MethodResponse,
+            NotificationResponse = MethodResponse,
+        > + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(
+        &self,
+        req: jsonrpsee::types::Request<'a>,
+    ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let rejection = self
+            .api_keys
+            .check(req.extensions().get::<ApiKey>(), &req.method, req.params().as_str())
+            .err();
+        let inner = self.inner.clone();
+        async move {
+            match rejection {
+                Some(rejection) => MethodResponse::error(req.id, ErrorObjectOwned::from(rejection)),
+                None => inner.call(req).await,
+            }
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut batch: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        for entry in batch.iter_mut() {
+            let rejection = match entry {
+                Ok(BatchEntry::Call(req)) => self
+                    .api_keys
+                    .check(req.extensions().get::<ApiKey>(), &req.method, req.params().as_str())
+                    .err()
+                    .map(|rejection| (req.id.clone(), rejection)),
+                Ok(BatchEntry::Notification(n)) => self
+                    .api_keys
+                    .check(n.extensions().get::<ApiKey>(), &n.method, None)
+                    .err()
+                    .map(|rejection| (Id::Null, rejection)),
+                Err(_) => None,
+            };
+            if let Some((id, rejection)) = rejection {
+                *entry = Err(BatchEntryErr::new(id, rejection.into()));
+            }
+        }
+        self.inner.batch(batch)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        let allowed = self.api_keys.check(n.extensions().get::<ApiKey>(), &n.method, None).is_ok();
+        let inner = self.inner.clone();
+        async move {
+            if allowed {
+                inner.notification(n).await
+            } else {
+                MethodResponse::notification()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[compute_units]
+debug_traceTransaction = 10
+
+[[keys]]
+name = "indexer"
+key = "secret"
+allow = ["eth", "debug_traceTransaction"]
+deny = ["eth_sendRawTransaction"]
+requests_per_second = 3
+compute_units_per_second = 12
+max_block_range = 100
+"#;
+
+    fn key() -> ApiKey {
+        ApiKey("secret".to_string())
+    }
+
+    #[test]
+    fn parse_config() {
+        let config = ApiKeysConfig::from_toml(CONFIG).unwrap();
+        assert_eq!(config.compute_units.get("debug_traceTransaction"), Some(&10));
+        assert_eq!(config.keys.len(), 1);
+        assert_eq!(config.keys[0].max_block_range, Some(100));
+
+        let duplicate = r#"
+[[keys]]
+name = "a"
+key = "secret"
+
+[[keys]]
+name = "b"
+key = "secret"
+"#;
+        assert!(matches!(
+            ApiKeysConfig::from_toml(duplicate),
+            Err(ApiKeysError::DuplicateKey(name)) if name == "b"
+        ));
+    }
+
+    #[test]
+    fn method_acl() {
+        let config = ApiKeysConfig::from_toml(CONFIG).unwrap();
+        let key = &config.keys[0];
+        assert!(key.is_method_allowed("eth_blockNumber"));
+        assert!(key.is_method_allowed("debug_traceTransaction"));
+        assert!(!key.is_method_allowed("debug_traceCall"));
+        assert!(!key.is_method_allowed("eth_sendRawTransaction"));
+        assert!(!key.is_method_allowed("ethx_call"));
+        assert!(ApiKeyConfig::default().is_method_allowed("admin_addPeer"));
+    }
+
+    #[test]
+    fn check_keys_and_quotas() {
+        let keys = ApiKeys::new(ApiKeysConfig::from_toml(CONFIG).unwrap());
+
+        assert_eq!(keys.check(None, "eth_blockNumber", None), Err(ApiKeyRejection::MissingKey));
+        assert_eq!(
+            keys.check(Some(&ApiKey("other".to_string())), "eth_blockNumber", None),
+            Err(ApiKeyRejection::InvalidKey)
+        );
+        assert_eq!(
+            keys.check(Some(&key()), "admin_addPeer", None),
+            Err(ApiKeyRejection::MethodNotAllowed("admin_addPeer".to_string()))
+        );
+
+        // 10 compute units for the trace and 1 for the block number fit into the 12 units
+        assert_eq!(keys.check(Some(&key()), "debug_traceTransaction", None), Ok(()));
+        assert_eq!(keys.check(Some(&key()), "eth_blockNumber", None), Ok(()));
+        assert_eq!(
+            keys.check(Some(&key()), "debug_traceTransaction", None),
+            Err(ApiKeyRejection::ComputeUnitsExceeded(12))
+        );
+        // 3 requests per second were used up
+        assert_eq!(
+            keys.check(Some(&key()), "eth_blockNumber", None),
+            Err(ApiKeyRejection::RateLimited(3))
+        );
+    }
+
+    #[test]
+    fn check_block_range() {
+        let keys = ApiKeys::new(ApiKeysConfig::from_toml(CONFIG).unwrap())
+            .with_latest_block(Arc::new(|| Some(1_000)));
+
+        let params = r#"[{"fromBlock":"0x0","toBlock":"0x64"}]"#;
+        assert_eq!(keys.check(Some(&key()), "eth_getLogs", Some(params)), Ok(()));
+
+        let params = r#"[{"fromBlock":"0x0","toBlock":"latest"}]"#;
+        assert_eq!(
+            keys.check(Some(&key()), "eth_getLogs", Some(params)),
+            Err(ApiKeyRejection::BlockRangeExceeded { range: 1_000, max: 100 })
+        );
+
+        let params = r#"[{"blockHash":"0x0000000000000000000000000000000000000000000000000000000000000000"}]"#;
+        assert_eq!(keys.check(Some(&key()), "eth_getLogs", Some(params)), Ok(()));
+    }
+
+    #[test]
+    fn reload_keeps_quotas() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api-keys.toml");
+        std::fs::write(&path, CONFIG).unwrap();
+        let keys = ApiKeys::load(&path).unwrap();
+
+        for _ in 0..3 {
+            assert_eq!(keys.check(Some(&key()), "eth_blockNumber", None), Ok(()));
+        }
+        assert_eq!(
+            keys.check(Some(&key()), "eth_blockNumber", None),
+            Err(ApiKeyRejection::RateLimited(3))
+        );
+
+        // Force a reload with a changed rate, the used up requests stay used up
+        keys.inner.state.write().modified = None;
+        std::fs::write(&path, CONFIG.replace("requests_per_second = 3", "requests_per_second = 4"))
+            .unwrap();
+        assert!(keys.reload_if_changed().unwrap());
+        assert_eq!(
+            keys.check(Some(&key()), "eth_blockNumber", None),
+            Err(ApiKeyRejection::RateLimited(4))
+        );
+    }
+
+    #[test]
+    fn check_http_request() {
+        let keys = ApiKeys::new(ApiKeysConfig::from_toml(CONFIG).unwrap());
+
+        // The key doesn't allow the `graphql` namespace
+        let mut req = Request::builder().uri("http://localhost:8547/secret").body(()).unwrap();
+        let rejection = keys.check_http_request(&mut req, GRAPHQL_METHOD).unwrap_err();
+        assert_eq!(rejection, ApiKeyRejection::MethodNotAllowed(GRAPHQL_METHOD.to_string()));
+        assert_eq!(rejection.http_status(), StatusCode::FORBIDDEN);
+
+        let mut req = Request::builder().uri("http://localhost:8547/").body(()).unwrap();
+        let rejection = keys.check_http_request(&mut req, GRAPHQL_METHOD).unwrap_err();
+        assert_eq!(rejection.http_status(), StatusCode::UNAUTHORIZED);
+
+        let keys = ApiKeys::new(
+            ApiKeysConfig::from_toml(&CONFIG.replace(r#""eth","#, r#""eth", "graphql","#)).unwrap(),
+        );
+        let mut req = Request::builder()
+            .uri("http://localhost:8547/")
+            .header(API_KEY_HEADER, "secret")
+            .body(())
+            .unwrap();
+        assert_eq!(keys.check_http_request(&mut req, GRAPHQL_METHOD), Ok(()));
+    }
+
+    #[test]
+    fn extract_key() {
+        let mut req = Request::builder().uri("http://localhost:8545/secret").body(()).unwrap();
+        assert_eq!(extract_api_key(&mut req), Some(key()));
+        assert_eq!(req.uri().path(), "/");
+
+        let mut req = Request::builder()
+            .uri("http://localhost:8545/")
+            .header(API_KEY_HEADER, "secret")
+            .body(())
+            .unwrap();
+        assert_eq!(extract_api_key(&mut req), Some(key()));
+
+        let mut req = Request::builder().uri("http://localhost:8545/").body(()).unwrap();
+        assert_eq!(extract_api_key(&mut req), None);
+    }
+}
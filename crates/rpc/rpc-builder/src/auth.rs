@@ -1,6 +1,7 @@
 use crate::{
     error::{RpcError, ServerKind},
     middleware::RethRpcMiddleware,
+    tls::{self, ReloadableTlsAcceptor, TlsConfig},
 };
 use http::header::AUTHORIZATION;
 use jsonrpsee::{
@@ -36,6 +37,8 @@ pub struct AuthServerConfig<RpcMiddleware = Identity> {
     pub(crate) ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     /// IPC endpoint
     pub(crate) ipc_endpoint: Option<String>,
+    /// TLS certificates for the http and ws server
+    pub(crate) tls: Option<TlsConfig>,
     /// Configurable RPC middleware
     pub(crate) rpc_middleware: RpcMiddleware,
 }
@@ -56,13 +59,16 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
 
     /// Configures the rpc middleware.
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> AuthServerConfig<T> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint, .. } = self;
+        let Self {
+            socket_addr, secret, server_config, ipc_server_config, ipc_endpoint, tls, ..
+        } = self;
         AuthServerConfig {
             socket_addr,
             secret,
             server_config,
             ipc_server_config,
             ipc_endpoint,
+            tls,
             rpc_middleware,
         }
     }
@@ -78,6 +84,7 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
             server_config,
             ipc_server_config,
             ipc_endpoint,
+            tls,
             rpc_middleware,
         } = self;
 
@@ -88,19 +95,32 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
         let rpc_middleware = RpcServiceBuilder::default().layer(rpc_middleware);
 
         // By default, both http and ws are enabled.
-        let server = ServerBuilder::new()
+        let builder = ServerBuilder::new()
             .set_config(server_config.build())
             .set_http_middleware(middleware)
-            .set_rpc_middleware(rpc_middleware)
-            .build(socket_addr)
+            .set_rpc_middleware(rpc_middleware);
+
+        let (local_addr, handle) = if let Some(config) = tls.clone() {
+            let acceptor = ReloadableTlsAcceptor::load(config)?;
+            let service_builder = builder.to_service_builder();
+            let methods = Methods::from(module.inner.clone());
+            tls::serve_tls(socket_addr, acceptor, move |stop_handle| {
+                service_builder.clone().build(methods.clone(), stop_handle)
+            })
             .await
-            .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?
+        } else {
+            let server = builder
+                .build(socket_addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
 
-        let local_addr = server
-            .local_addr()
-            .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            let local_addr = server
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
 
-        let handle = server.start(module.inner.clone());
+            (local_addr, server.start(module.inner.clone()))
+        };
 
         let ipc_handle = if let Some(ipc_server_config) = ipc_server_config {
             let ipc_endpoint_str = ipc_endpoint
@@ -113,7 +133,14 @@ impl<RpcMiddleware> AuthServerConfig<RpcMiddleware> {
             None
         };
 
-        Ok(AuthServerHandle { handle: Some(handle), local_addr, secret, ipc_endpoint, ipc_handle })
+        Ok(AuthServerHandle {
+            handle: Some(handle),
+            local_addr,
+            secret,
+            ipc_endpoint,
+            ipc_handle,
+            tls: tls.is_some(),
+        })
     }
 }
 
@@ -125,6 +152,7 @@ pub struct AuthServerConfigBuilder<RpcMiddleware = Identity> {
     server_config: Option<ServerConfigBuilder>,
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     ipc_endpoint: Option<String>,
+    tls: Option<TlsConfig>,
     rpc_middleware: RpcMiddleware,
 }
 
@@ -139,6 +167,7 @@ impl AuthServerConfigBuilder {
             server_config: None,
             ipc_server_config: None,
             ipc_endpoint: None,
+            tls: None,
             rpc_middleware: Identity::new(),
         }
     }
@@ -147,13 +176,16 @@ impl AuthServerConfigBuilder {
 impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
     /// Configures the rpc middleware.
     pub fn with_rpc_middleware<T>(self, rpc_middleware: T) -> AuthServerConfigBuilder<T> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint, .. } = self;
+        let Self {
+            socket_addr, secret, server_config, ipc_server_config, ipc_endpoint, tls, ..
+        } = self;
         AuthServerConfigBuilder {
             socket_addr,
             secret,
             server_config,
             ipc_server_config,
             ipc_endpoint,
+            tls,
             rpc_middleware,
         }
     }
@@ -191,6 +223,15 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
         self
     }
 
+    /// Configures TLS for the http and ws server.
+    ///
+    /// This can be combined with a client CA to require client certificates in addition to the
+    /// JWT secret.
+    pub fn with_tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+        self
+    }
+
     /// Configures the IPC server
     ///
     /// Note: this always configures an [`EthSubscriptionIdProvider`]
@@ -232,6 +273,7 @@ impl<RpcMiddleware> AuthServerConfigBuilder<RpcMiddleware> {
                     .set_id_provider(EthSubscriptionIdProvider::default())
             }),
             ipc_endpoint: self.ipc_endpoint,
+            tls: self.tls,
             rpc_middleware: self.rpc_middleware,
         }
     }
@@ -309,6 +351,7 @@ pub struct AuthServerHandle {
     secret: JwtSecret,
     ipc_endpoint: Option<String>,
     ipc_handle: Option<jsonrpsee::server::ServerHandle>,
+    tls: bool,
 }
 
 // === impl AuthServerHandle ===
@@ -327,6 +370,7 @@ impl AuthServerHandle {
             secret: JwtSecret::random(),
             ipc_endpoint: None,
             ipc_handle: None,
+            tls: false,
         }
     }
 
@@ -343,12 +387,14 @@ impl AuthServerHandle {
 
     /// Returns the url to the http server
     pub fn http_url(&self) -> String {
-        format!("http://{}", self.local_addr)
+        let scheme = if self.tls { "https" } else { "http" };
+        format!("{scheme}://{}", self.local_addr)
     }
 
     /// Returns the url to the ws server
     pub fn ws_url(&self) -> String {
-        format!("ws://{}", self.local_addr)
+        let scheme = if self.tls { "wss" } else { "ws" };
+        format!("{scheme}://{}", self.local_addr)
     }
 
     /// Returns a http client connected to the server.
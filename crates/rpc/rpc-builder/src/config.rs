@@ -9,8 +9,8 @@ use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
-    TransportRpcModuleConfig,
+    auth::AuthServerConfig, error::RpcError, tls::TlsConfig, IpcServerBuilder, RpcModuleConfig,
+    RpcServerConfig, TransportRpcModuleConfig,
 };
 
 /// A trait that provides a configured RPC server.
@@ -98,6 +98,7 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
             .eth_proof_window(self.rpc_eth_proof_window)
+            .eth_proof_changesets_window(self.rpc_eth_proof_changesets_window)
             .rpc_gas_cap(self.rpc_gas_cap)
             .rpc_max_simulate_blocks(self.rpc_max_simulate_blocks)
             .state_cache(self.state_cache_config())
@@ -178,7 +179,12 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let tls = self.rpc_tls_cert.clone().zip(self.rpc_tls_key.clone()).map(|(cert, key)| {
+            TlsConfig::new(cert, key).with_client_ca(self.rpc_tls_client_ca.clone())
+        });
+
+        let mut config =
+            RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key()).with_tls(tls);
 
         if self.http_api.is_some() && !self.http {
             warn!(
@@ -213,7 +219,11 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
-        let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
+        let tls = self.auth_tls_cert.clone().zip(self.auth_tls_key.clone()).map(|(cert, key)| {
+            TlsConfig::new(cert, key).with_client_ca(self.auth_tls_client_ca.clone())
+        });
+
+        let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address).with_tls(tls);
         if self.auth_ipc {
             builder = builder
                 .ipc_endpoint(self.auth_ipc_path.clone())
@@ -245,7 +255,8 @@ mod tests {
     use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
     use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 
-    use crate::config::RethRpcServerConfig;
+    use crate::{config::RethRpcServerConfig, tls::TlsConfig};
+    use reth_rpc_layer::JwtSecret;
 
     /// A helper type to parse Args more easily
     #[derive(Parser)]
@@ -355,6 +366,32 @@ mod tests {
         assert_eq!(config.ipc_endpoint().unwrap(), constants::DEFAULT_IPC_ENDPOINT);
     }
 
+    #[test]
+    fn test_auth_server_tls_config() {
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--authrpc.tls-cert",
+            "cert.pem",
+        ]);
+        assert!(args.is_err());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--authrpc.tls-cert",
+            "cert.pem",
+            "--authrpc.tls-key",
+            "key.pem",
+            "--authrpc.tls-client-ca",
+            "ca.pem",
+        ])
+        .args;
+        let config = args.auth_server_config(JwtSecret::random()).unwrap();
+        assert_eq!(
+            config.tls,
+            Some(TlsConfig::new("cert.pem", "key.pem").with_client_ca(Some("ca.pem".into())))
+        );
+    }
+
     #[test]
     fn test_zero_filter_limits() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
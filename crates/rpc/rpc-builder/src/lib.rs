@@ -50,7 +50,10 @@ use reth_rpc_eth_api::{
     RpcNodeCore, RpcReceipt, RpcTransaction, RpcTxReq,
 };
 use reth_rpc_eth_types::{receipt::EthReceiptConverter, EthConfig, EthSubscriptionIdProvider};
-use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
+use reth_rpc_layer::{
+    AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret, ResponseCacheLayer,
+    RpcResponseCache,
+};
 use reth_storage_api::{
     AccountReader, BlockReader, ChangeSetReader, FullRpcProvider, ProviderBlock,
     StateProviderFactory,
@@ -106,6 +109,14 @@ use reth_rpc::eth::sim_bundle::EthSimBundle;
 // Rpc rate limiter
 pub mod rate_limiter;
 
+/// API keys, method ACLs and per-key quotas
+pub mod api_keys;
+use api_keys::{ApiKeyHttpLayer, ApiKeyRpcLayer, ApiKeys};
+
+/// Native TLS for the http, ws and auth servers
+pub mod tls;
+use tls::{ReloadableTlsAcceptor, TlsConfig};
+
 /// A builder type to configure the RPC module: See [`RpcModule`]
 ///
 /// This is the main entrypoint and the easiest way to configure an RPC server.
@@ -802,6 +813,15 @@ where
         EthBundle::new(eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates [`EthSimBundle`] Api
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn sim_bundle_api(&self) -> EthSimBundle<EthApi> {
+        EthSimBundle::new(self.eth_api().clone(), self.blocking_pool_guard.clone())
+    }
+
     /// Instantiates `DebugApi`
     ///
     /// # Panics
@@ -1028,6 +1048,12 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// API keys required for http and ws requests
+    api_keys: Option<ApiKeys>,
+    /// TLS certificates for the http and ws servers
+    tls: Option<TlsConfig>,
+    /// Cache for responses of calls against finalized blocks
+    response_cache: Option<RpcResponseCache>,
     /// Configurable RPC middleware
     rpc_middleware: RpcMiddleware,
 }
@@ -1048,6 +1074,9 @@ impl Default for RpcServerConfig<Identity> {
             ipc_server_config: None,
             ipc_endpoint: None,
             jwt_secret: None,
+            api_keys: None,
+            tls: None,
+            response_cache: None,
             rpc_middleware: Default::default(),
         }
     }
@@ -1112,6 +1141,9 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
+            api_keys: self.api_keys,
+            tls: self.tls,
+            response_cache: self.response_cache,
             rpc_middleware,
         }
     }
@@ -1191,6 +1223,27 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the API keys that are required for http and ws requests.
+    ///
+    /// The ipc server is not affected.
+    pub fn with_api_keys(mut self, api_keys: Option<ApiKeys>) -> Self {
+        self.api_keys = api_keys;
+        self
+    }
+
+    /// Configures TLS for the http and ws servers.
+    pub fn with_tls(mut self, tls: Option<TlsConfig>) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Configures the cache for responses of calls against finalized blocks, shared by all
+    /// servers.
+    pub fn with_response_cache(mut self, response_cache: Option<RpcResponseCache>) -> Self {
+        self.response_cache = response_cache;
+        self
+    }
+
     /// Configures a custom tokio runtime for the rpc server.
     pub fn with_tokio_runtime(mut self, tokio_runtime: tokio::runtime::Handle) -> Self {
         if let Some(http_server_config) = self.http_server_config {
@@ -1251,6 +1304,17 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         }
     }
 
+    /// Returns the [`ResponseCacheLayer`] for a server with the given module, if the cache is
+    /// enabled
+    fn maybe_response_cache_layer(
+        &self,
+        module: Option<&RpcModule<()>>,
+    ) -> Option<ResponseCacheLayer> {
+        let cache = self.response_cache.clone()?;
+        let methods = module.map(|module| &**module).cloned().unwrap_or_default();
+        Some(ResponseCacheLayer::new(cache, &methods))
+    }
+
     /// Builds and starts the configured server(s): http, ws, ipc.
     ///
     /// If both http and ws are on the same port, they are combined into one server.
@@ -1274,13 +1338,19 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             constants::DEFAULT_WS_RPC_PORT,
         )));
 
+        let tls = self.tls.clone().map(ReloadableTlsAcceptor::load).transpose()?;
+
         let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
 
         if let Some(builder) = self.ipc_server_config {
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new()
+                        .layer(metrics)
+                        .option_layer(self.maybe_response_cache_layer(modules.ipc.as_ref())),
+                )
                 .build(ipc_path);
             ipc_handle = Some(ipc.start(modules.ipc.clone().expect("ipc server error")).await?);
         }
@@ -1309,11 +1379,12 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             modules.config.ensure_ws_http_identical()?;
 
             if let Some(config) = self.http_server_config {
-                let server = ServerBuilder::new()
+                let builder = ServerBuilder::new()
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
                             .option_layer(Self::maybe_cors_layer(cors)?)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(self.api_keys.as_ref().map(|_| ApiKeyHttpLayer))
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
                             )),
@@ -1328,22 +1399,44 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                                     .map(RpcRequestMetrics::same_port)
                                     .unwrap_or_default(),
                             )
+                            .option_layer(self.api_keys.clone().map(ApiKeyRpcLayer::new))
+                            .option_layer(self.maybe_response_cache_layer(
+                                modules.http.as_ref().or(modules.ws.as_ref()),
+                            ))
                             .layer(self.rpc_middleware.clone()),
                     )
-                    .set_config(config.build())
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
-                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                    })?;
-                let addr = server.local_addr().map_err(|err| {
-                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                })?;
-                if let Some(module) = modules.http.as_ref().or(modules.ws.as_ref()) {
-                    let handle = server.start(module.clone());
+                    .set_config(config.build());
+                let module = modules.http.as_ref().or(modules.ws.as_ref());
+
+                // Nothing is served without a module, the same as without TLS
+                let addr = if let (Some(acceptor), Some(module)) = (tls, module) {
+                    let service_builder = builder.to_service_builder();
+                    let methods = Methods::from(module.clone());
+                    let (addr, handle) =
+                        tls::serve_tls(http_socket_addr, acceptor, move |stop_handle| {
+                            service_builder.clone().build(methods.clone(), stop_handle)
+                        })
+                        .await
+                        .map_err(|err| {
+                            RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                        })?;
                     http_handle = Some(handle.clone());
                     ws_handle = Some(handle);
-                }
+                    addr
+                } else {
+                    let server = builder.build(http_socket_addr).await.map_err(|err| {
+                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                    })?;
+                    let addr = server.local_addr().map_err(|err| {
+                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                    })?;
+                    if let Some(module) = module {
+                        let handle = server.start(module.clone());
+                        http_handle = Some(handle.clone());
+                        ws_handle = Some(handle);
+                    }
+                    addr
+                };
                 return Ok(RpcServerHandle {
                     http_local_addr: Some(addr),
                     ws_local_addr: Some(addr),
@@ -1352,6 +1445,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     ipc_endpoint: self.ipc_endpoint.clone(),
                     ipc: ipc_handle,
                     jwt_secret: self.jwt_secret,
+                    tls: self.tls.is_some(),
                 });
             }
         }
@@ -1362,37 +1456,56 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         let mut http_server = None;
 
         if let Some(config) = self.ws_server_config {
-            let server = ServerBuilder::new()
+            let builder = ServerBuilder::new()
                 .set_config(config.ws_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(self.api_keys.as_ref().map(|_| ApiKeyHttpLayer)),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
                         .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .option_layer(self.api_keys.clone().map(ApiKeyRpcLayer::new))
+                        .option_layer(self.maybe_response_cache_layer(modules.ws.as_ref()))
                         .layer(self.rpc_middleware.clone()),
-                )
-                .build(ws_socket_addr)
+                );
+
+            if let Some(acceptor) = tls.clone() {
+                let service_builder = builder.to_service_builder();
+                let methods = Methods::from(modules.ws.clone().expect("ws server error"));
+                let (addr, handle) = tls::serve_tls(ws_socket_addr, acceptor, move |stop_handle| {
+                    service_builder.clone().build(methods.clone(), stop_handle)
+                })
                 .await
                 .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
-            let addr = server
-                .local_addr()
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+                ws_local_addr = Some(addr);
+                ws_handle = Some(handle);
+            } else {
+                let server = builder
+                    .build(ws_socket_addr)
+                    .await
+                    .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+
+                let addr = server
+                    .local_addr()
+                    .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
-            ws_local_addr = Some(addr);
-            ws_server = Some(server);
+                ws_local_addr = Some(addr);
+                ws_server = Some(server);
+            }
         }
 
         if let Some(config) = self.http_server_config {
-            let server = ServerBuilder::new()
+            let builder = ServerBuilder::new()
                 .set_config(config.http_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(self.api_keys.as_ref().map(|_| ApiKeyHttpLayer))
                         .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
                 )
                 .set_rpc_middleware(
@@ -1400,22 +1513,43 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                         .layer(
                             modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
                         )
+                        .option_layer(self.api_keys.clone().map(ApiKeyRpcLayer::new))
+                        .option_layer(self.maybe_response_cache_layer(modules.http.as_ref()))
                         .layer(self.rpc_middleware.clone()),
-                )
-                .build(http_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
-            let local_addr = server
-                .local_addr()
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
-            http_local_addr = Some(local_addr);
-            http_server = Some(server);
+                );
+
+            if let Some(acceptor) = tls {
+                let service_builder = builder.to_service_builder();
+                let methods = Methods::from(modules.http.clone().expect("http server error"));
+                let (addr, handle) =
+                    tls::serve_tls(http_socket_addr, acceptor, move |stop_handle| {
+                        service_builder.clone().build(methods.clone(), stop_handle)
+                    })
+                    .await
+                    .map_err(|err| {
+                        RpcError::server_error(err, ServerKind::Http(http_socket_addr))
+                    })?;
+
+                http_local_addr = Some(addr);
+                http_handle = Some(handle);
+            } else {
+                let server = builder.build(http_socket_addr).await.map_err(|err| {
+                    RpcError::server_error(err, ServerKind::Http(http_socket_addr))
+                })?;
+                let local_addr = server.local_addr().map_err(|err| {
+                    RpcError::server_error(err, ServerKind::Http(http_socket_addr))
+                })?;
+                http_local_addr = Some(local_addr);
+                http_server = Some(server);
+            }
         }
 
-        http_handle = http_server
-            .map(|http_server| http_server.start(modules.http.clone().expect("http server error")));
-        ws_handle = ws_server
-            .map(|ws_server| ws_server.start(modules.ws.clone().expect("ws server error")));
+        if let Some(http_server) = http_server {
+            http_handle = Some(http_server.start(modules.http.clone().expect("http server error")));
+        }
+        if let Some(ws_server) = ws_server {
+            ws_handle = Some(ws_server.start(modules.ws.clone().expect("ws server error")));
+        }
         Ok(RpcServerHandle {
             http_local_addr,
             ws_local_addr,
@@ -1424,6 +1558,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_endpoint: self.ipc_endpoint.clone(),
             ipc: ipc_handle,
             jwt_secret: self.jwt_secret,
+            tls: self.tls.is_some(),
         })
     }
 }
@@ -1955,6 +2090,26 @@ impl TransportRpcModules {
         self.add_or_replace_ipc(other)?;
         Ok(())
     }
+
+    /// Adds or replaces the given [`Methods`] in all transport modules for which the given
+    /// [`RethRpcModule`] is configured.
+    pub fn add_or_replace_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.module_config().contains_http(&module) {
+            self.add_or_replace_http(other.clone())?;
+        }
+        if self.module_config().contains_ws(&module) {
+            self.add_or_replace_ws(other.clone())?;
+        }
+        if self.module_config().contains_ipc(&module) {
+            self.add_or_replace_ipc(other)?;
+        }
+        Ok(())
+    }
 }
 
 /// Returns the methods installed in the given module that match the given filter.
@@ -1989,6 +2144,7 @@ pub struct RpcServerHandle {
     ipc_endpoint: Option<String>,
     ipc: Option<jsonrpsee::server::ServerHandle>,
     jwt_secret: Option<JwtSecret>,
+    tls: bool,
 }
 
 // === impl RpcServerHandle ===
@@ -2044,12 +2200,14 @@ impl RpcServerHandle {
 
     /// Returns the url to the http server
     pub fn http_url(&self) -> Option<String> {
-        self.http_local_addr.map(|addr| format!("http://{addr}"))
+        let scheme = if self.tls { "https" } else { "http" };
+        self.http_local_addr.map(|addr| format!("{scheme}://{addr}"))
     }
 
     /// Returns the url to the ws server
     pub fn ws_url(&self) -> Option<String> {
-        self.ws_local_addr.map(|addr| format!("ws://{addr}"))
+        let scheme = if self.tls { "wss" } else { "ws" };
+        self.ws_local_addr.map(|addr| format!("{scheme}://{addr}"))
     }
 
     /// Returns a http client connected to the server.
@@ -0,0 +1,437 @@
+//! Native TLS for the http, ws and auth servers.
+//!
+//! The certificate chain and private key are read from PEM files and reloaded when the files
+//! change, so certificates can be rotated without restarting the node. If a client CA is
+//! configured, clients must present a certificate signed by it (mTLS).
+
+use http::{Request, Response};
+use hyper::body::Incoming;
+use jsonrpsee::server::{serve_with_graceful_shutdown, stop_channel, ServerHandle, StopHandle};
+use parking_lot::Mutex;
+use rustls::{
+    crypto::ring,
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    server::{VerifierBuilderError, WebPkiClientVerifier},
+    RootCertStore, ServerConfig,
+};
+use std::{
+    fmt, io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tower::{BoxError, Service};
+use tracing::{debug, info, warn};
+
+/// How often the certificate files are checked for changes.
+pub const TLS_RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Maximum duration of a TLS handshake before the connection is dropped.
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Paths of the PEM files used to serve TLS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// The certificate chain, leaf certificate first.
+    pub cert: PathBuf,
+    /// The private key of the leaf certificate.
+    pub key: PathBuf,
+    /// CA certificates used to verify client certificates, if client authentication is required.
+    pub client_ca: Option<PathBuf>,
+}
+
+impl TlsConfig {
+    /// Creates a new config with the given certificate chain and private key.
+    pub fn new(cert: impl Into<PathBuf>, key: impl Into<PathBuf>) -> Self {
+        Self { cert: cert.into(), key: key.into(), client_ca: None }
+    }
+
+    /// Requires clients to present a certificate signed by one of the CAs in the given file.
+    pub fn with_client_ca(mut self, client_ca: Option<PathBuf>) -> Self {
+        self.client_ca = client_ca;
+        self
+    }
+
+    /// Returns the paths of all configured files.
+    fn paths(&self) -> impl Iterator<Item = &Path> {
+        [Some(&self.cert), Some(&self.key), self.client_ca.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(PathBuf::as_path)
+    }
+
+    /// Reads the files and builds the rustls server config.
+    fn load(&self) -> Result<ServerConfig, TlsError> {
+        let provider = Arc::new(ring::default_provider());
+
+        let certs = read_certs(&self.cert)?;
+        let key = PrivateKeyDer::from_pem_file(&self.key)
+            .map_err(|err| TlsError::Pem { path: self.key.clone(), err })?;
+
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()?;
+        let builder = match &self.client_ca {
+            Some(path) => {
+                let mut roots = RootCertStore::empty();
+                for cert in read_certs(path)? {
+                    roots.add(cert)?;
+                }
+                let verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                        .build()?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder.with_single_cert(certs, key)?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+
+    /// Returns the modification times of all configured files.
+    fn modified(&self) -> Vec<Option<SystemTime>> {
+        self.paths()
+            .map(|path| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+            .collect()
+    }
+}
+
+/// Reads all certificates from the PEM file.
+fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| TlsError::Pem { path: path.to_path_buf(), err })?;
+    if certs.is_empty() {
+        return Err(TlsError::NoCertificates(path.to_path_buf()))
+    }
+    Ok(certs)
+}
+
+/// Errors when loading the TLS files.
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    /// Failed to read or parse a PEM file.
+    #[error("failed to read {path}: {err}")]
+    Pem {
+        /// Path of the file.
+        path: PathBuf,
+        /// The underlying error.
+        err: rustls::pki_types::pem::Error,
+    },
+    /// The PEM file does not contain any certificate.
+    #[error("no certificates found in {0}")]
+    NoCertificates(PathBuf),
+    /// The certificates or key are invalid.
+    #[error(transparent)]
+    Rustls(#[from] rustls::Error),
+    /// The client CA certificates are invalid.
+    #[error(transparent)]
+    ClientVerifier(#[from] VerifierBuilderError),
+}
+
+/// A TLS acceptor that reloads the certificate files when they change.
+#[derive(Clone)]
+pub struct ReloadableTlsAcceptor {
+    config: TlsConfig,
+    state: Arc<Mutex<AcceptorState>>,
+}
+
+struct AcceptorState {
+    acceptor: TlsAcceptor,
+    modified: Vec<Option<SystemTime>>,
+    last_check: Instant,
+}
+
+impl ReloadableTlsAcceptor {
+    /// Loads the certificate files of the given config.
+    pub fn load(config: TlsConfig) -> Result<Self, TlsError> {
+        let modified = config.modified();
+        let acceptor = TlsAcceptor::from(Arc::new(config.load()?));
+        Ok(Self {
+            config,
+            state: Arc::new(Mutex::new(AcceptorState {
+                acceptor,
+                modified,
+                last_check: Instant::now(),
+            })),
+        })
+    }
+
+    /// Returns the acceptor for a new connection.
+    ///
+    /// Every [`TLS_RELOAD_INTERVAL`] the files are checked for changes and reloaded. If reloading
+    /// fails, the previous certificates stay active.
+    pub fn acceptor(&self) -> TlsAcceptor {
+        let mut state = self.state.lock();
+        if state.last_check.elapsed() >= TLS_RELOAD_INTERVAL {
+            state.last_check = Instant::now();
+            let modified = self.config.modified();
+            if modified != state.modified {
+                match self.config.load() {
+                    Ok(config) => {
+                        state.acceptor = TlsAcceptor::from(Arc::new(config));
+                        state.modified = modified;
+                        info!(target: "rpc::tls", cert=%self.config.cert.display(), "Reloaded TLS certificates");
+                    }
+                    Err(err) => {
+                        warn!(target: "rpc::tls", %err, "Failed to reload TLS certificates")
+                    }
+                }
+            }
+        }
+        state.acceptor.clone()
+    }
+}
+
+impl fmt::Debug for ReloadableTlsAcceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadableTlsAcceptor")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Binds to the given address and serves TLS connections until the returned [`ServerHandle`] is
+/// stopped.
+///
+/// A new service is created by `make_service` for every connection, usually with
+/// [`TowerServiceBuilder::build`](jsonrpsee::server::TowerServiceBuilder::build).
+pub(crate) async fn serve_tls<F, S, B>(
+    addr: SocketAddr,
+    tls: ReloadableTlsAcceptor,
+    make_service: F,
+) -> io::Result<(SocketAddr, ServerHandle)>
+where
+    F: Fn(StopHandle) -> S + Send + 'static,
+    S: Service<Request<Incoming>, Response = Response<B>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Into<BoxError>,
+    B: http_body::Body<Data = bytes::Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    let (stop_handle, server_handle) = stop_channel();
+    let listener = TcpListener::bind(addr).await?;
+    let local_addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, remote_addr) = tokio::select! {
+                res = listener.accept() => match res {
+                    Ok(conn) => conn,
+                    Err(err) => {
+                        debug!(target: "rpc::tls", %err, "Failed to accept connection");
+                        continue
+                    }
+                },
+                _ = stop_handle.clone().shutdown() => break,
+            };
+
+            let acceptor = tls.acceptor();
+            let service = make_service(stop_handle.clone());
+            let stop_handle = stop_handle.clone();
+            tokio::spawn(async move {
+                let stream = match tokio::time::timeout(
+                    TLS_HANDSHAKE_TIMEOUT,
+                    acceptor.accept(stream),
+                )
+                .await
+                {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(err)) => {
+                        debug!(target: "rpc::tls", %remote_addr, %err, "TLS handshake failed");
+                        return
+                    }
+                    Err(_) => {
+                        debug!(target: "rpc::tls", %remote_addr, "TLS handshake timed out");
+                        return
+                    }
+                };
+                if let Err(err) =
+                    serve_with_graceful_shutdown(stream, service, stop_handle.shutdown()).await
+                {
+                    debug!(target: "rpc::tls", %remote_addr, %err, "Failed to serve connection");
+                }
+            });
+        }
+    });
+
+    Ok((local_addr, server_handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{server::Server, Methods, RpcModule};
+    use rustls::ClientConfig;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::TlsConnector;
+
+    const REQUEST: &str = r#"{"jsonrpc":"2.0","id":1,"method":"test_ping","params":[]}"#;
+
+    fn testdata(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/tls").join(name)
+    }
+
+    /// Serves a module with a single `test_ping` method over TLS.
+    async fn serve(tls: ReloadableTlsAcceptor) -> (SocketAddr, ServerHandle) {
+        let mut module = RpcModule::new(());
+        module.register_method("test_ping", |_, _, _| "pong").unwrap();
+        let methods = Methods::from(module);
+        let service_builder = Server::builder().to_service_builder();
+        serve_tls("127.0.0.1:0".parse().unwrap(), tls, move |stop_handle| {
+            service_builder.clone().build(methods.clone(), stop_handle)
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Connects to the server, trusting the test CA and optionally presenting a client
+    /// certificate, sends the request and returns the server certificate and the response.
+    async fn request(
+        addr: SocketAddr,
+        client_cert: Option<&str>,
+    ) -> io::Result<(CertificateDer<'static>, String)> {
+        let mut roots = RootCertStore::empty();
+        for cert in read_certs(&testdata("ca.pem")).unwrap() {
+            roots.add(cert).unwrap();
+        }
+        let builder = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots);
+        let config = match client_cert {
+            Some(name) => builder
+                .with_client_auth_cert(
+                    read_certs(&testdata(&format!("{name}.pem"))).unwrap(),
+                    PrivateKeyDer::from_pem_file(testdata(&format!("{name}-key.pem"))).unwrap(),
+                )
+                .unwrap(),
+            None => builder.with_no_client_auth(),
+        };
+
+        let stream = tokio::net::TcpStream::connect(addr).await?;
+        let mut stream = TlsConnector::from(Arc::new(config))
+            .connect("localhost".try_into().unwrap(), stream)
+            .await?;
+        let server_cert = stream.get_ref().1.peer_certificates().unwrap()[0].clone().into_owned();
+
+        stream
+            .write_all(
+                format!(
+                    "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{REQUEST}",
+                    REQUEST.len()
+                )
+                .as_bytes(),
+            )
+            .await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok((server_cert, response))
+    }
+
+    #[tokio::test]
+    async fn handshake() {
+        let tls = ReloadableTlsAcceptor::load(TlsConfig::new(
+            testdata("server.pem"),
+            testdata("server-key.pem"),
+        ))
+        .unwrap();
+        let (addr, handle) = serve(tls).await;
+
+        let (server_cert, response) = request(addr, None).await.unwrap();
+        assert_eq!(server_cert, read_certs(&testdata("server.pem")).unwrap()[0]);
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.ends_with(r#""result":"pong"}"#), "{response}");
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test]
+    async fn client_certificate_required() {
+        let tls = ReloadableTlsAcceptor::load(
+            TlsConfig::new(testdata("server.pem"), testdata("server-key.pem"))
+                .with_client_ca(Some(testdata("ca.pem"))),
+        )
+        .unwrap();
+        let (addr, handle) = serve(tls).await;
+
+        let (_, response) = request(addr, Some("client")).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+
+        // With TLS 1.3 the client only learns about the rejection when reading the response
+        for client_cert in [None, Some("other-client")] {
+            match request(addr, client_cert).await {
+                Ok((_, response)) => assert!(response.is_empty(), "{response}"),
+                Err(err) => assert_ne!(err.kind(), io::ErrorKind::TimedOut),
+            }
+        }
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test]
+    async fn reload_certificates() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert = dir.path().join("cert.pem");
+        let key = dir.path().join("key.pem");
+        std::fs::copy(testdata("server.pem"), &cert).unwrap();
+        std::fs::copy(testdata("server-key.pem"), &key).unwrap();
+
+        let tls = ReloadableTlsAcceptor::load(TlsConfig::new(&cert, &key)).unwrap();
+        let (addr, handle) = serve(tls.clone()).await;
+
+        let (server_cert, _) = request(addr, None).await.unwrap();
+        assert_eq!(server_cert, read_certs(&testdata("server.pem")).unwrap()[0]);
+
+        std::fs::copy(testdata("server2.pem"), &cert).unwrap();
+        std::fs::copy(testdata("server2-key.pem"), &key).unwrap();
+
+        // Not reloaded before the next check
+        let (server_cert, _) = request(addr, None).await.unwrap();
+        assert_eq!(server_cert, read_certs(&testdata("server.pem")).unwrap()[0]);
+
+        // Pretend the interval elapsed, the modification times may not have changed yet on
+        // filesystems with a coarse resolution
+        {
+            let mut state = tls.state.lock();
+            state.last_check = Instant::now().checked_sub(TLS_RELOAD_INTERVAL).unwrap();
+            state.modified.clear();
+        }
+        let (server_cert, _) = request(addr, None).await.unwrap();
+        assert_eq!(server_cert, read_certs(&testdata("server2.pem")).unwrap()[0]);
+
+        // A broken file keeps the current certificates
+        std::fs::write(&cert, "").unwrap();
+        {
+            let mut state = tls.state.lock();
+            state.last_check = Instant::now().checked_sub(TLS_RELOAD_INTERVAL).unwrap();
+            state.modified.clear();
+        }
+        let (server_cert, _) = request(addr, None).await.unwrap();
+        assert_eq!(server_cert, read_certs(&testdata("server2.pem")).unwrap()[0]);
+
+        handle.stop().unwrap();
+    }
+
+    #[test]
+    fn missing_files() {
+        let config = TlsConfig::new("/does/not/exist/cert.pem", "/does/not/exist/key.pem");
+        assert!(matches!(ReloadableTlsAcceptor::load(config), Err(TlsError::Pem { .. })));
+    }
+
+    #[test]
+    fn empty_certificate_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert = dir.path().join("cert.pem");
+        std::fs::write(&cert, "").unwrap();
+        assert!(matches!(
+            ReloadableTlsAcceptor::load(TlsConfig::new(&cert, dir.path().join("key.pem"))),
+            Err(TlsError::NoCertificates(path)) if path == cert
+        ));
+    }
+}
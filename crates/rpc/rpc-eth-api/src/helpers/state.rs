@@ -21,6 +21,14 @@ pub trait EthState: LoadState + SpawnBlocking {
     /// Returns the maximum number of blocks into the past for generating state proofs.
     fn max_proof_window(&self) -> u64;
 
+    /// Returns the maximum number of blocks into the past for generating state proofs from the
+    /// retained trie changesets, beyond [`EthState::max_proof_window`].
+    ///
+    /// Proofs outside of [`EthState::max_proof_window`] are rejected by default.
+    fn max_proof_changesets_window(&self) -> u64 {
+        0
+    }
+
     /// Returns the number of transactions sent from an address at the given block identifier.
     ///
     /// If this is [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag) then this will
@@ -104,13 +112,23 @@ pub trait EthState: LoadState + SpawnBlocking {
                 .block_number_for_id(block_id)
                 .map_err(Self::Error::from_eth_err)?
                 .ok_or(EthApiError::HeaderNotFound(block_id))?;
-            let max_window = self.max_proof_window();
-            if chain_info.best_number.saturating_sub(block_number) > max_window {
+            let distance = chain_info.best_number.saturating_sub(block_number);
+            let exceeds_max_window = distance > self.max_proof_window();
+            if exceeds_max_window && distance > self.max_proof_changesets_window() {
                 return Err(EthApiError::ExceedsMaxProofWindow.into())
             }
 
             self.spawn_blocking_io(move |this| {
                 let state = this.state_at_block_id(block_id)?;
+
+                // Blocks outside of the window can only be proven within the changesets window if
+                // the trie can be restored from the trie changesets.
+                if exceeds_max_window &&
+                    !state.has_trie_changesets().map_err(Self::Error::from_eth_err)?
+                {
+                    return Err(EthApiError::ExceedsMaxProofWindow.into())
+                }
+
                 let storage_keys = keys.iter().map(|key| key.as_b256()).collect::<Vec<_>>();
                 let proof = state
                     .proof(Default::default(), address, &storage_keys)
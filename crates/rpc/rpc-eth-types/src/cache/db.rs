@@ -105,6 +105,10 @@ impl reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'_> {
     ) -> reth_errors::ProviderResult<Vec<alloy_primitives::Bytes>> {
         self.0.witness(input, target)
     }
+
+    fn has_trie_changesets(&self) -> reth_errors::ProviderResult<bool> {
+        self.0.has_trie_changesets()
+    }
 }
 
 impl reth_storage_api::AccountReader for StateProviderTraitObjWrapper<'_> {
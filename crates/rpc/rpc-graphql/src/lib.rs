@@ -0,0 +1,265 @@
+//! Ethereum GraphQL API as specified in [EIP-1767](https://eips.ethereum.org/EIPS/eip-1767).
+//!
+//! The schema is backed by the same `eth_` helpers that serve the JSON-RPC API, so nested
+//! queries for blocks, transactions, receipts and accounts can be answered in a single round
+//! trip.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use async_graphql::{EmptySubscription, Schema};
+use reth_rpc_eth_api::helpers::FullEthApi;
+use std::sync::Arc;
+
+mod query;
+pub mod scalars;
+mod server;
+pub mod types;
+
+pub use query::{GraphQlLogFilter, Mutation, Query, MAX_BLOCKS_PER_QUERY};
+pub use server::{GraphQlAuthorizer, GraphQlServer, GraphQlServerHandle, MAX_BATCH_SIZE};
+
+/// The maximum nesting depth of a query.
+///
+/// This bounds chains of nested objects, e.g. `block { parent { parent { .. } } }`, while leaving
+/// enough room for the introspection query of GraphiQL.
+pub const MAX_QUERY_DEPTH: usize = 20;
+
+/// The maximum complexity of a query, every requested field counts as one.
+pub const MAX_QUERY_COMPLEXITY: usize = 1_000;
+
+/// Helper trait with all `EthApi` bounds required by the GraphQL schema.
+pub trait GraphQlEthApi: FullEthApi + 'static {}
+
+impl<T> GraphQlEthApi for T where T: FullEthApi + 'static {}
+
+/// The EIP-1767 schema backed by the given `EthApi`.
+pub type GraphQlSchema<Eth> = Schema<Query<Eth>, Mutation<Eth>, EmptySubscription>;
+
+/// Builds the EIP-1767 schema for the given `EthApi`, which looks up logs with the given filter.
+pub fn schema<Eth: GraphQlEthApi>(
+    eth: Eth,
+    log_filter: Arc<dyn GraphQlLogFilter>,
+) -> GraphQlSchema<Eth> {
+    Schema::build(Query::new(eth.clone(), log_filter), Mutation::new(eth), EmptySubscription)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{Address, B256};
+    use alloy_rpc_types_eth::{Filter, Log};
+    use async_graphql::Result;
+    use http::{Request, StatusCode};
+    use reth_chainspec::{ChainSpec, ChainSpecProvider};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_rpc::{eth::helpers::types::EthRpcConverter, EthApi, EthApiBuilder, EthFilter};
+    use reth_rpc_eth_api::{node::RpcNodeCoreAdapter, QueryLimits, RpcNodeCore};
+    use reth_rpc_eth_types::EthFilterConfig;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction, TestPool},
+        TransactionOrigin, TransactionPool,
+    };
+    use serde_json::json;
+    use std::{future::Future, pin::Pin, sync::Mutex};
+
+    type TestEthApi = EthApi<
+        RpcNodeCoreAdapter<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>,
+        EthRpcConverter<ChainSpec>,
+    >;
+
+    fn test_eth_api() -> TestEthApi {
+        let provider = MockEthProvider::default();
+        // block ranges are resolved against the best block
+        provider.add_header(B256::random(), Header { number: 2_000, ..Default::default() });
+        EthApiBuilder::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build()
+    }
+
+    /// Builds the schema with the `eth_getLogs` filter of the given `EthApi`.
+    fn test_schema(eth: TestEthApi) -> GraphQlSchema<TestEthApi> {
+        let filter =
+            EthFilter::new(eth.clone(), EthFilterConfig::default(), Box::new(TokioTaskExecutor::default()));
+        schema(eth, Arc::new(filter))
+    }
+
+    /// Executes the query and returns the data and the error messages as JSON.
+    async fn execute(eth: TestEthApi, query: &str) -> (serde_json::Value, Vec<String>) {
+        let response = test_schema(eth).execute(query).await;
+        let errors = response.errors.into_iter().map(|err| err.message).collect();
+        (response.data.into_json().unwrap(), errors)
+    }
+
+    #[tokio::test]
+    async fn resolves_chain_and_sync_state() {
+        let (data, errors) = execute(test_eth_api(), "{ chainID syncing { currentBlock } }").await;
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(data, json!({ "chainID": "0x1", "syncing": null }));
+    }
+
+    #[tokio::test]
+    async fn resolves_pending_transactions() {
+        let eth = test_eth_api();
+        let tx = MockTransaction::eip1559();
+        eth.pool().add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+        let hash = tx.get_hash().to_string();
+
+        let (data, errors) = execute(
+            eth.clone(),
+            "{ pending { transactionCount transactions { hash nonce index } } }",
+        )
+        .await;
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(
+            data,
+            json!({
+                "pending": {
+                    "transactionCount": 1,
+                    "transactions": [{ "hash": hash, "nonce": 0, "index": null }]
+                }
+            })
+        );
+
+        let (data, errors) = execute(
+            eth,
+            &format!(r#"{{ transaction(hash: "{hash}") {{ hash block {{ number }} }} }}"#),
+        )
+        .await;
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(data, json!({ "transaction": { "hash": hash, "block": null } }));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_block_arguments() {
+        let query = format!(r#"{{ block(number: 1, hash: "{}") {{ number }} }}"#, B256::ZERO);
+        let (_, errors) = execute(test_eth_api(), &query).await;
+        assert_eq!(errors, ["only one of number or hash must be set"]);
+
+        let (_, errors) = execute(test_eth_api(), "{ blocks(from: 0) { number } }").await;
+        assert_eq!(
+            errors,
+            [format!("block range exceeds the maximum of {MAX_BLOCKS_PER_QUERY} blocks")]
+        );
+    }
+
+    /// Records the filters of `logs` queries without finding any logs.
+    #[derive(Default)]
+    struct RecordingLogFilter(Mutex<Vec<(Filter, QueryLimits)>>);
+
+    impl GraphQlLogFilter for RecordingLogFilter {
+        fn logs(
+            &self,
+            filter: Filter,
+            limits: QueryLimits,
+        ) -> Pin<Box<dyn Future<Output = Result<Vec<Log>>> + Send + '_>> {
+            self.0.lock().unwrap().push((filter, limits));
+            Box::pin(async { Ok(Vec::new()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn looks_up_logs_with_the_log_filter() {
+        let log_filter = Arc::new(RecordingLogFilter::default());
+        let schema = schema(test_eth_api(), log_filter.clone());
+        let address = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(2);
+
+        let query = format!(
+            r#"{{ logs(filter: {{ fromBlock: 1990, addresses: ["{address}"], topics: [[], ["{topic}"]] }}) {{ index }} }}"#
+        );
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(response.data.into_json().unwrap(), json!({ "logs": [] }));
+
+        let (filter, limits) = log_filter.0.lock().unwrap().pop().unwrap();
+        assert_eq!(filter.get_from_block(), Some(1_990));
+        assert_eq!(filter.get_to_block(), Some(2_000));
+        assert!(filter.address.matches(&address));
+        assert!(filter.topics[0].is_empty());
+        assert!(filter.topics[1].matches(&topic));
+        assert_eq!(limits.max_blocks_per_filter, Some(MAX_BLOCKS_PER_QUERY));
+
+        // logs with more than 4 topics don't exist
+        let query = format!(r#"{{ logs(filter: {{ topics: [[], [], [], [], ["{topic}"]] }}) {{ index }} }}"#);
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert!(log_filter.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn limits_batch_size() {
+        let schema = test_schema(test_eth_api());
+        let batch = |size: usize| {
+            let queries = vec![r#"{"query":"{ chainID }"}"#; size];
+            Request::post("/").body(format!("[{}]", queries.join(","))).unwrap()
+        };
+
+        let response = server::handle_request(&schema, None, batch(MAX_BATCH_SIZE)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = server::handle_request(&schema, None, batch(MAX_BATCH_SIZE + 1)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(response.body().contains("batch exceeds the maximum"));
+    }
+
+    #[tokio::test]
+    async fn authorizes_requests() {
+        let schema = test_schema(test_eth_api());
+        let authorizer: GraphQlAuthorizer = Arc::new(|req| {
+            if req.headers().contains_key("x-api-key") {
+                Ok(())
+            } else {
+                Err((StatusCode::UNAUTHORIZED, "missing API key".to_string()))
+            }
+        });
+        let request = |api_key: Option<&str>| {
+            let mut req = Request::post("/");
+            if let Some(api_key) = api_key {
+                req = req.header("x-api-key", api_key);
+            }
+            req.body(r#"{"query":"{ chainID }"}"#.to_string()).unwrap()
+        };
+
+        let response = server::handle_request(&schema, Some(&authorizer), request(None)).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(response.body().contains("missing API key"));
+
+        let response =
+            server::handle_request(&schema, Some(&authorizer), request(Some("secret"))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.body().contains("chainID"));
+    }
+
+    #[tokio::test]
+    async fn limits_query_depth_and_complexity() {
+        let nested = |depth: usize| {
+            format!("{{ block {{ {}number{} }} }}", "parent { ".repeat(depth), " }".repeat(depth))
+        };
+        let (_, errors) = execute(test_eth_api(), &nested(MAX_QUERY_DEPTH)).await;
+        assert_eq!(errors, ["Query is nested too deep."]);
+        let (_, errors) = execute(test_eth_api(), &nested(MAX_QUERY_DEPTH - 2)).await;
+        assert!(!errors.contains(&"Query is nested too deep.".to_string()), "{errors:?}");
+
+        let fields =
+            (0..=MAX_QUERY_COMPLEXITY).map(|idx| format!("f{idx}: chainID")).collect::<Vec<_>>();
+        let (_, errors) = execute(test_eth_api(), &format!("{{ {} }}", fields.join(" "))).await;
+        assert_eq!(errors, ["Query is too complex."]);
+    }
+}
@@ -0,0 +1,213 @@
+//! Query and mutation roots of the EIP-1767 schema.
+
+use crate::{
+    scalars::{BigInt, Bytes, Bytes32, Long},
+    types::{Block, FilterCriteria, Log, Pending, SyncState, Transaction},
+    GraphQlEthApi,
+};
+use alloy_eips::BlockId;
+use alloy_primitives::U256;
+use alloy_rpc_types_eth::{Filter, SyncStatus};
+use async_graphql::{Error, Object, Result};
+use reth_rpc_eth_api::{
+    helpers::{EthApiSpec, EthFees, EthTransactions},
+    EngineEthFilter, QueryLimits, RpcNodeCore,
+};
+use reth_storage_api::BlockNumReader;
+use std::{fmt, future::Future, ops::RangeInclusive, pin::Pin, sync::Arc};
+
+/// The maximum number of blocks a single `blocks` or `logs` query may span.
+pub const MAX_BLOCKS_PER_QUERY: u64 = 1_000;
+
+/// Looks up the logs of `logs` queries.
+///
+/// This is implemented for every [`EngineEthFilter`], so that the logs are found the same way as
+/// for `eth_getLogs`, through the log index if it's available.
+pub trait GraphQlLogFilter: Send + Sync + 'static {
+    /// Returns the logs matching the filter within the given limits.
+    fn logs(
+        &self,
+        filter: Filter,
+        limits: QueryLimits,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<alloy_rpc_types_eth::Log>>> + Send + '_>>;
+}
+
+impl<T: EngineEthFilter> GraphQlLogFilter for T {
+    fn logs(
+        &self,
+        filter: Filter,
+        limits: QueryLimits,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<alloy_rpc_types_eth::Log>>> + Send + '_>> {
+        Box::pin(async move {
+            EngineEthFilter::logs(self, filter, limits)
+                .await
+                .map_err(|err| Error::new(err.message()))
+        })
+    }
+}
+
+/// The query root of the schema.
+#[derive(Clone)]
+pub struct Query<Eth> {
+    eth: Eth,
+    log_filter: Arc<dyn GraphQlLogFilter>,
+}
+
+impl<Eth: GraphQlEthApi> Query<Eth> {
+    /// Creates a new query root backed by the given `EthApi`, which looks up logs with the given
+    /// filter.
+    pub const fn new(eth: Eth, log_filter: Arc<dyn GraphQlLogFilter>) -> Self {
+        Self { eth, log_filter }
+    }
+
+    /// Resolves the requested block range, defaulting both ends to the latest block.
+    fn block_range(&self, from: Option<Long>, to: Option<Long>) -> Result<RangeInclusive<u64>> {
+        let latest = self.eth.provider().best_block_number()?;
+        let from = from.map_or(latest, |from| from.0);
+        let to = to.map_or(latest, |to| to.0).min(latest);
+        if to.saturating_sub(from) >= MAX_BLOCKS_PER_QUERY {
+            return Err(Error::new(format!(
+                "block range exceeds the maximum of {MAX_BLOCKS_PER_QUERY} blocks"
+            )))
+        }
+        Ok(from..=to)
+    }
+}
+
+#[Object(name = "Query")]
+impl<Eth: GraphQlEthApi> Query<Eth> {
+    /// Fetches a block by number or hash, defaults to the latest block.
+    async fn block(
+        &self,
+        number: Option<Long>,
+        hash: Option<Bytes32>,
+    ) -> Result<Option<Block<Eth>>> {
+        let block_id = match (number, hash) {
+            (Some(_), Some(_)) => return Err(Error::new("only one of number or hash must be set")),
+            (Some(number), None) => BlockId::number(number.0),
+            (None, Some(hash)) => hash.0.into(),
+            (None, None) => BlockId::latest(),
+        };
+        Block::load(&self.eth, block_id).await
+    }
+
+    /// Returns all blocks in the given range, `to` defaults to the latest block.
+    async fn blocks(&self, from: Option<Long>, to: Option<Long>) -> Result<Vec<Block<Eth>>> {
+        let mut blocks = Vec::new();
+        for number in self.block_range(from, to)? {
+            let Some(block) = Block::load(&self.eth, BlockId::number(number)).await? else { break };
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    /// The current pending state.
+    async fn pending(&self) -> Pending<Eth> {
+        Pending::new(self.eth.clone())
+    }
+
+    /// Fetches a transaction by hash, from the chain or the pool.
+    async fn transaction(&self, hash: Bytes32) -> Result<Option<Transaction<Eth>>> {
+        Transaction::load(&self.eth, hash.0).await
+    }
+
+    /// Returns all logs in the given block range that match the filter.
+    async fn logs(&self, filter: FilterCriteria) -> Result<Vec<Log<Eth>>> {
+        let range = self.block_range(filter.from_block, filter.to_block)?;
+        let addresses = filter.addresses.unwrap_or_default();
+        let mut eth_filter = Filter::new()
+            .from_block(*range.start())
+            .to_block(*range.end())
+            .address(addresses.into_iter().map(|address| address.0).collect::<Vec<_>>());
+        for (idx, topics) in filter.topics.unwrap_or_default().into_iter().enumerate() {
+            let topics = topics.into_iter().map(|topic| topic.0).collect::<Vec<_>>();
+            match eth_filter.topics.get_mut(idx) {
+                Some(position) => *position = topics.into(),
+                // logs have at most 4 topics
+                None if !topics.is_empty() => return Ok(Vec::new()),
+                None => {}
+            }
+        }
+
+        let limits =
+            QueryLimits { max_blocks_per_filter: Some(MAX_BLOCKS_PER_QUERY), ..Default::default() };
+        let mut logs = Vec::new();
+        let mut block: Option<Block<Eth>> = None;
+        for log in self.log_filter.logs(eth_filter, limits).await? {
+            let (Some(block_hash), Some(tx_index), Some(index)) =
+                (log.block_hash, log.transaction_index, log.log_index)
+            else {
+                continue
+            };
+            // logs are ordered by block, so each block is only loaded once
+            if block.as_ref().is_none_or(|block| block.block_hash() != block_hash) {
+                block = Block::load(&self.eth, block_hash.into()).await?;
+            }
+            if let Some(block) = &block {
+                logs.extend(block.log_at(tx_index as usize, index, log.inner));
+            }
+        }
+        Ok(logs)
+    }
+
+    /// The suggested legacy gas price.
+    async fn gas_price(&self) -> Result<BigInt> {
+        Ok(BigInt(EthFees::gas_price(&self.eth).await?))
+    }
+
+    /// The suggested priority fee per gas.
+    async fn max_priority_fee_per_gas(&self) -> Result<BigInt> {
+        Ok(BigInt(EthFees::suggested_priority_fee(&self.eth).await?))
+    }
+
+    /// The sync progress of the node, null if the node is not syncing.
+    async fn syncing(&self) -> Result<Option<SyncState>> {
+        match self.eth.sync_status()? {
+            SyncStatus::Info(info) => Ok(Some(SyncState {
+                starting_block: Long(info.starting_block.saturating_to()),
+                current_block: Long(info.current_block.saturating_to()),
+                highest_block: Long(info.highest_block.saturating_to()),
+            })),
+            SyncStatus::None => Ok(None),
+        }
+    }
+
+    /// The chain id used for transaction signing.
+    #[graphql(name = "chainID")]
+    async fn chain_id(&self) -> BigInt {
+        BigInt(U256::from(self.eth.chain_id().to::<u64>()))
+    }
+}
+
+impl<Eth> fmt::Debug for Query<Eth> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Query").finish_non_exhaustive()
+    }
+}
+
+/// The mutation root of the schema.
+#[derive(Clone)]
+pub struct Mutation<Eth> {
+    eth: Eth,
+}
+
+impl<Eth: GraphQlEthApi> Mutation<Eth> {
+    /// Creates a new mutation root backed by the given `EthApi`.
+    pub const fn new(eth: Eth) -> Self {
+        Self { eth }
+    }
+}
+
+#[Object(name = "Mutation")]
+impl<Eth: GraphQlEthApi> Mutation<Eth> {
+    /// Submits an EIP-2718 encoded transaction to the pool and returns its hash.
+    async fn send_raw_transaction(&self, data: Bytes) -> Result<Bytes32> {
+        Ok(Bytes32(EthTransactions::send_raw_transaction(&self.eth, data.0).await?))
+    }
+}
+
+impl<Eth> fmt::Debug for Mutation<Eth> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mutation").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,143 @@
+//! Custom scalars of the EIP-1767 schema.
+//!
+//! Hashes, addresses and byte strings are encoded as `0x`-prefixed hex strings. `BigInt` is
+//! encoded as a hex string as well, but also accepts decimal input. `Long` is encoded as a JSON
+//! number and accepts numbers as well as hex or decimal strings as input.
+
+use alloy_primitives::{self as primitives, B256, U256};
+use async_graphql::{InputValueError, InputValueResult, Number, Scalar, ScalarType, Value};
+
+/// A 32 byte binary string, e.g. a block or transaction hash.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bytes32(pub B256);
+
+#[Scalar]
+impl ScalarType for Bytes32 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Self(s.parse()?)),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// A 20 byte Ethereum address.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Address(pub primitives::Address);
+
+#[Scalar]
+impl ScalarType for Address {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Self(s.parse()?)),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// An arbitrary length binary string.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bytes(pub primitives::Bytes);
+
+#[Scalar]
+impl ScalarType for Bytes {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Self(s.parse()?)),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// A 256 bit unsigned integer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BigInt(pub U256);
+
+#[Scalar]
+impl ScalarType for BigInt {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Self(s.parse()?)),
+            Value::Number(n) => match n.as_u64() {
+                Some(n) => Ok(Self(U256::from(n))),
+                None => Err(InputValueError::custom("expected an unsigned integer")),
+            },
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+/// A 64 bit unsigned integer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Long(pub u64);
+
+#[Scalar]
+impl ScalarType for Long {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => {
+                let n = match s.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16)?,
+                    None => s.parse()?,
+                };
+                Ok(Self(n))
+            }
+            Value::Number(n) => match n.as_u64() {
+                Some(n) => Ok(Self(n)),
+                None => Err(InputValueError::custom("expected an unsigned integer")),
+            },
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Number(Number::from(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_long() {
+        assert_eq!(Long::parse(Value::Number(Number::from(10u64))).unwrap(), Long(10));
+        assert_eq!(Long::parse(Value::String("0x10".to_string())).unwrap(), Long(16));
+        assert_eq!(Long::parse(Value::String("10".to_string())).unwrap(), Long(10));
+        assert!(Long::parse(Value::Boolean(true)).is_err());
+        assert_eq!(Long(16).to_value(), Value::Number(Number::from(16u64)));
+    }
+
+    #[test]
+    fn parse_big_int() {
+        assert_eq!(
+            BigInt::parse(Value::String("0x10".to_string())).unwrap(),
+            BigInt(U256::from(16))
+        );
+        assert_eq!(BigInt::parse(Value::String("16".to_string())).unwrap(), BigInt(U256::from(16)));
+        assert_eq!(BigInt(U256::from(16)).to_value(), Value::String("0x10".to_string()));
+    }
+
+    #[test]
+    fn parse_bytes32() {
+        let hash = B256::with_last_byte(1);
+        assert_eq!(Bytes32::parse(Bytes32(hash).to_value()).unwrap(), Bytes32(hash));
+        assert!(Bytes32::parse(Value::String("0x01".to_string())).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+//! HTTP server for the GraphQL schema.
+
+use crate::{schema, GraphQlEthApi, GraphQlLogFilter, GraphQlSchema};
+use async_graphql::{http::GraphiQLSource, BatchRequest};
+use http::{header::CONTENT_TYPE, HeaderValue, Method, Request, Response, StatusCode};
+use http_body::Body;
+use http_body_util::{BodyExt, Limited};
+use reth_tasks::TaskExecutor;
+use std::{convert::Infallible, error::Error, fmt, io, net::SocketAddr, sync::Arc};
+use tracing::{debug, error};
+
+/// The maximum size of a request body in bytes.
+const MAX_REQUEST_BODY_SIZE: usize = 5 * 1024 * 1024;
+
+/// The maximum number of queries in a batched request.
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// Authorizes a request before it's handled, e.g. by checking its API key.
+///
+/// Rejected requests are answered with the returned status and error message.
+pub type GraphQlAuthorizer =
+    Arc<dyn Fn(&mut Request<()>) -> Result<(), (StatusCode, String)> + Send + Sync>;
+
+/// Serves the EIP-1767 schema over HTTP.
+///
+/// Queries are accepted as JSON encoded `POST` requests, single or batched. `GET` requests are
+/// answered with a GraphiQL page to explore the schema.
+pub struct GraphQlServer<Eth: GraphQlEthApi> {
+    schema: GraphQlSchema<Eth>,
+    authorizer: Option<GraphQlAuthorizer>,
+}
+
+impl<Eth: GraphQlEthApi> GraphQlServer<Eth> {
+    /// Creates a new server for the schema backed by the given `EthApi`, which looks up logs with
+    /// the given filter.
+    pub fn new(eth: Eth, log_filter: Arc<dyn GraphQlLogFilter>) -> Self {
+        Self { schema: schema(eth, log_filter), authorizer: None }
+    }
+
+    /// Authorizes every request with the given function before it's handled.
+    pub fn with_authorizer(mut self, authorizer: GraphQlAuthorizer) -> Self {
+        self.authorizer = Some(authorizer);
+        self
+    }
+
+    /// Returns the schema served by this server.
+    pub const fn schema(&self) -> &GraphQlSchema<Eth> {
+        &self.schema
+    }
+
+    /// Binds to the given address and serves requests until the node shuts down.
+    pub async fn start(
+        self,
+        addr: SocketAddr,
+        executor: &TaskExecutor,
+    ) -> io::Result<GraphQlServerHandle> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let Self { schema, authorizer } = self;
+
+        let connections = executor.clone();
+        executor.spawn_with_graceful_shutdown_signal(|mut signal| {
+            Box::pin(async move {
+                loop {
+                    let io = tokio::select! {
+                        _ = &mut signal => break,
+                        io = listener.accept() => {
+                            match io {
+                                Ok((stream, _remote_addr)) => stream,
+                                Err(err) => {
+                                    error!(target: "rpc::graphql", %err, "failed to accept connection");
+                                    continue
+                                }
+                            }
+                        }
+                    };
+
+                    let schema = schema.clone();
+                    let authorizer = authorizer.clone();
+                    let service = tower::service_fn(move |req| {
+                        let schema = schema.clone();
+                        let authorizer = authorizer.clone();
+                        async move {
+                            Ok::<_, Infallible>(
+                                handle_request(&schema, authorizer.as_ref(), req).await,
+                            )
+                        }
+                    });
+
+                    let mut shutdown = signal.clone().ignore_guard();
+                    connections.spawn(Box::pin(async move {
+                        let _ = jsonrpsee_server::serve_with_graceful_shutdown(
+                            io,
+                            service,
+                            &mut shutdown,
+                        )
+                        .await
+                        .inspect_err(|error| debug!(target: "rpc::graphql", %error, "failed to serve request"));
+                    }));
+                }
+            })
+        });
+
+        Ok(GraphQlServerHandle { local_addr })
+    }
+}
+
+impl<Eth: GraphQlEthApi> fmt::Debug for GraphQlServer<Eth> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQlServer").finish_non_exhaustive()
+    }
+}
+
+/// Handle to a running [`GraphQlServer`].
+#[derive(Clone, Copy, Debug)]
+pub struct GraphQlServerHandle {
+    local_addr: SocketAddr,
+}
+
+impl GraphQlServerHandle {
+    /// Returns the address the server is listening on.
+    pub const fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+/// Authorizes a single HTTP request and executes it against the schema.
+pub(crate) async fn handle_request<Eth, B>(
+    schema: &GraphQlSchema<Eth>,
+    authorizer: Option<&GraphQlAuthorizer>,
+    req: Request<B>,
+) -> Response<String>
+where
+    Eth: GraphQlEthApi,
+    B: Body,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    let req = match authorizer {
+        Some(authorize) => {
+            let (parts, body) = req.into_parts();
+            let mut head = Request::from_parts(parts, ());
+            if let Err((status, message)) = authorize(&mut head) {
+                return error_response(status, message)
+            }
+            Request::from_parts(head.into_parts().0, body)
+        }
+        None => req,
+    };
+
+    match *req.method() {
+        Method::GET => response(
+            StatusCode::OK,
+            "text/html; charset=utf-8",
+            GraphiQLSource::build().endpoint("/").finish(),
+        ),
+        Method::POST => {
+            let body = match Limited::new(req.into_body(), MAX_REQUEST_BODY_SIZE).collect().await {
+                Ok(body) => body.to_bytes(),
+                Err(err) => return error_response(StatusCode::BAD_REQUEST, err),
+            };
+            let request = match serde_json::from_slice::<BatchRequest>(&body) {
+                Ok(request) => request,
+                Err(err) => return error_response(StatusCode::BAD_REQUEST, err),
+            };
+            if let BatchRequest::Batch(requests) = &request {
+                if requests.len() > MAX_BATCH_SIZE {
+                    return error_response(
+                        StatusCode::BAD_REQUEST,
+                        format!("batch exceeds the maximum of {MAX_BATCH_SIZE} queries"),
+                    )
+                }
+            }
+            match serde_json::to_string(&schema.execute_batch(request).await) {
+                Ok(body) => response(StatusCode::OK, "application/json", body),
+                Err(err) => error_response(StatusCode::INTERNAL_SERVER_ERROR, err),
+            }
+        }
+        _ => error_response(StatusCode::METHOD_NOT_ALLOWED, "only GET and POST are supported"),
+    }
+}
+
+/// Returns a GraphQL error response with the given status.
+fn error_response(status: StatusCode, err: impl fmt::Display) -> Response<String> {
+    let body = serde_json::json!({ "errors": [{ "message": err.to_string() }] });
+    response(status, "application/json", body.to_string())
+}
+
+fn response(status: StatusCode, content_type: &'static str, body: String) -> Response<String> {
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
@@ -0,0 +1,796 @@
+//! Object and input types of the EIP-1767 schema.
+
+use crate::{
+    scalars::{Address, BigInt, Bytes, Bytes32, Long},
+    GraphQlEthApi,
+};
+use alloy_consensus::{BlockHeader, Eip2718EncodableReceipt, Transaction as _, TxReceipt};
+use alloy_eips::{eip2718::Encodable2718, BlockId};
+use alloy_primitives::{self as primitives, B256, U256};
+use alloy_rpc_types_eth::{state::EvmOverrides, TransactionInput, TransactionRequest};
+use async_graphql::{InputObject, Object, Result, SimpleObject};
+use reth_primitives_traits::{BlockBody, Recovered, RecoveredBlock, SignedTransaction};
+use reth_rpc_eth_api::{
+    helpers::{Call, EthCall, EthState, EthTransactions, LoadBlock},
+    RpcNodeCore, RpcTxReq,
+};
+use reth_rpc_eth_types::TransactionSource;
+use reth_storage_api::{ProviderBlock, ProviderReceipt, ProviderTx};
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use std::{fmt, sync::Arc};
+
+/// Provider transaction type of the given `EthApi`.
+type TxOf<Eth> = ProviderTx<<Eth as RpcNodeCore>::Provider>;
+
+/// A block with its receipts.
+#[derive(Clone)]
+pub struct Block<Eth: GraphQlEthApi> {
+    eth: Eth,
+    block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
+    receipts: Arc<Vec<ProviderReceipt<Eth::Provider>>>,
+}
+
+impl<Eth: GraphQlEthApi> Block<Eth> {
+    /// Loads the block and its receipts for the given [`BlockId`].
+    pub(crate) async fn load(eth: &Eth, block_id: BlockId) -> Result<Option<Self>> {
+        Ok(eth.load_block_and_receipts(block_id).await?.map(|(block, receipts)| Self {
+            eth: eth.clone(),
+            block,
+            receipts,
+        }))
+    }
+
+    /// Returns the [`BlockId`] of this block.
+    fn id(&self) -> BlockId {
+        self.block.hash().into()
+    }
+
+    /// Returns the hash of this block.
+    pub(crate) fn block_hash(&self) -> B256 {
+        self.block.hash()
+    }
+
+    /// Returns the transaction at the given index.
+    fn tx_at(&self, index: usize) -> Option<Transaction<Eth>> {
+        let tx = self.block.body().transactions().get(index)?.clone();
+        let signer = *self.block.senders().get(index)?;
+        Some(Transaction {
+            eth: self.eth.clone(),
+            tx: Recovered::new_unchecked(tx, signer),
+            included: Some((self.clone(), index)),
+        })
+    }
+
+    /// Returns all logs of the transaction at the given index that match the filter.
+    fn tx_logs(&self, index: usize, filter: &LogFilter) -> Vec<Log<Eth>> {
+        let Some(receipt) = self.receipts.get(index) else { return Vec::new() };
+        let Some(transaction) = self.tx_at(index) else { return Vec::new() };
+
+        // log indices are counted across the whole block
+        let first_log_index =
+            self.receipts[..index].iter().map(|receipt| receipt.logs().len() as u64).sum::<u64>();
+
+        receipt
+            .logs()
+            .iter()
+            .enumerate()
+            .filter(|(_, log)| filter.matches(log))
+            .map(|(idx, log)| Log {
+                transaction: transaction.clone(),
+                index: first_log_index + idx as u64,
+                log: log.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the log with the given index in the block, emitted by the transaction at
+    /// `tx_index`.
+    pub(crate) fn log_at(
+        &self,
+        tx_index: usize,
+        index: u64,
+        log: primitives::Log,
+    ) -> Option<Log<Eth>> {
+        Some(Log { transaction: self.tx_at(tx_index)?, index, log })
+    }
+
+    /// Returns all logs of this block that match the filter.
+    fn filter_logs(&self, filter: &LogFilter) -> Vec<Log<Eth>> {
+        (0..self.receipts.len()).flat_map(|index| self.tx_logs(index, filter)).collect()
+    }
+}
+
+#[Object(name = "Block")]
+impl<Eth: GraphQlEthApi> Block<Eth> {
+    /// The block number, starting at 0 for the genesis block.
+    async fn number(&self) -> Long {
+        Long(self.block.header().number())
+    }
+
+    /// The block hash.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.block.hash())
+    }
+
+    /// The parent block, or null for the genesis block.
+    async fn parent(&self) -> Result<Option<Self>> {
+        if self.block.header().number() == 0 {
+            return Ok(None)
+        }
+        Self::load(&self.eth, self.block.header().parent_hash().into()).await
+    }
+
+    /// The block nonce, an 8 byte sequence determined by the miner.
+    async fn nonce(&self) -> Bytes {
+        Bytes(primitives::Bytes::copy_from_slice(
+            self.block.header().nonce().unwrap_or_default().as_slice(),
+        ))
+    }
+
+    /// The root of the transaction trie of the block.
+    async fn transactions_root(&self) -> Bytes32 {
+        Bytes32(self.block.header().transactions_root())
+    }
+
+    /// The number of transactions in the block.
+    async fn transaction_count(&self) -> Long {
+        Long(self.block.body().transactions().len() as u64)
+    }
+
+    /// The root of the final state trie of the block.
+    async fn state_root(&self) -> Bytes32 {
+        Bytes32(self.block.header().state_root())
+    }
+
+    /// The root of the receipts trie of the block.
+    async fn receipts_root(&self) -> Bytes32 {
+        Bytes32(self.block.header().receipts_root())
+    }
+
+    /// The account that mined the block, at the given block or at this block.
+    async fn miner(&self, block: Option<Long>) -> Account<Eth> {
+        Account::new(&self.eth, self.block.header().beneficiary(), block, self.id())
+    }
+
+    /// An arbitrary data field supplied by the miner.
+    async fn extra_data(&self) -> Bytes {
+        Bytes(self.block.header().extra_data().clone())
+    }
+
+    /// The maximum amount of gas that was available to transactions in the block.
+    async fn gas_limit(&self) -> Long {
+        Long(self.block.header().gas_limit())
+    }
+
+    /// The amount of gas that was used executing transactions in the block.
+    async fn gas_used(&self) -> Long {
+        Long(self.block.header().gas_used())
+    }
+
+    /// The base fee per gas of the block, null before London.
+    async fn base_fee_per_gas(&self) -> Option<BigInt> {
+        self.block.header().base_fee_per_gas().map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The unix timestamp at which the block was mined.
+    async fn timestamp(&self) -> Long {
+        Long(self.block.header().timestamp())
+    }
+
+    /// The bloom filter of the logs emitted by transactions in the block.
+    async fn logs_bloom(&self) -> Bytes {
+        Bytes(primitives::Bytes::copy_from_slice(self.block.header().logs_bloom().as_slice()))
+    }
+
+    /// The hash that was used as an input to the PoW process, or the prevrandao value.
+    async fn mix_hash(&self) -> Bytes32 {
+        Bytes32(self.block.header().mix_hash().unwrap_or_default())
+    }
+
+    /// The difficulty of the block.
+    async fn difficulty(&self) -> BigInt {
+        BigInt(self.block.header().difficulty())
+    }
+
+    /// The number of ommers of the block.
+    async fn ommer_count(&self) -> Long {
+        Long(self.block.body().ommers().map_or(0, |ommers| ommers.len()) as u64)
+    }
+
+    /// The hash of the ommers list of the block.
+    async fn ommer_hash(&self) -> Bytes32 {
+        Bytes32(self.block.header().ommers_hash())
+    }
+
+    /// The withdrawals root of the block, null before Shanghai.
+    async fn withdrawals_root(&self) -> Option<Bytes32> {
+        self.block.header().withdrawals_root().map(Bytes32)
+    }
+
+    /// All transactions of the block.
+    async fn transactions(&self) -> Vec<Transaction<Eth>> {
+        (0..self.block.body().transactions().len()).filter_map(|index| self.tx_at(index)).collect()
+    }
+
+    /// The transaction at the given index, if any.
+    async fn transaction_at(&self, index: Long) -> Option<Transaction<Eth>> {
+        self.tx_at(index.0 as usize)
+    }
+
+    /// Logs emitted by transactions in the block that match the given filter.
+    async fn logs(&self, filter: BlockFilterCriteria) -> Vec<Log<Eth>> {
+        self.filter_logs(&LogFilter::new(filter.addresses, filter.topics))
+    }
+
+    /// The given account at the end of this block.
+    async fn account(&self, address: Address) -> Account<Eth> {
+        Account::new(&self.eth, address.0, None, self.id())
+    }
+
+    /// Executes a local call on top of this block.
+    async fn call(&self, data: CallData) -> Result<CallResult> {
+        call(&self.eth, data, self.id()).await
+    }
+
+    /// Estimates the gas a transaction would use on top of this block.
+    async fn estimate_gas(&self, data: CallData) -> Result<Long> {
+        estimate_gas(&self.eth, data, self.id()).await
+    }
+
+    /// The RLP encoded header of the block.
+    async fn raw_header(&self) -> Bytes {
+        Bytes(alloy_rlp::encode(self.block.header()).into())
+    }
+
+    /// The RLP encoded block.
+    async fn raw(&self) -> Bytes {
+        Bytes(alloy_rlp::encode(self.block.sealed_block()).into())
+    }
+}
+
+impl<Eth: GraphQlEthApi> fmt::Debug for Block<Eth> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Block")
+            .field("number", &self.block.header().number())
+            .field("hash", &self.block.hash())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A transaction, either included in a block or pending in the pool.
+#[derive(Clone)]
+pub struct Transaction<Eth: GraphQlEthApi> {
+    eth: Eth,
+    tx: Recovered<TxOf<Eth>>,
+    /// The block that includes the transaction and its index in the block.
+    included: Option<(Block<Eth>, usize)>,
+}
+
+impl<Eth: GraphQlEthApi> Transaction<Eth> {
+    /// Creates a transaction that is pending in the pool.
+    pub(crate) const fn pending(eth: Eth, tx: Recovered<TxOf<Eth>>) -> Self {
+        Self { eth, tx, included: None }
+    }
+
+    /// Loads the transaction with the given hash from the chain or the pool.
+    pub(crate) async fn load(eth: &Eth, hash: B256) -> Result<Option<Self>> {
+        let Some(source) = eth.transaction_by_hash(hash).await? else { return Ok(None) };
+        match source {
+            TransactionSource::Pool(tx) => Ok(Some(Self::pending(eth.clone(), tx))),
+            TransactionSource::Block { block_hash, index, .. } => {
+                Ok(Block::load(eth, block_hash.into())
+                    .await?
+                    .and_then(|block| block.tx_at(index as usize)))
+            }
+        }
+    }
+
+    /// Returns the receipt of the transaction if it is included in a block.
+    fn receipt(&self) -> Option<&ProviderReceipt<Eth::Provider>> {
+        self.included.as_ref().and_then(|(block, index)| block.receipts.get(*index))
+    }
+
+    /// Returns the base fee of the including block.
+    fn base_fee(&self) -> Option<u64> {
+        self.included.as_ref().and_then(|(block, _)| block.block.header().base_fee_per_gas())
+    }
+
+    /// Returns the [`BlockId`] to resolve accounts at, if no block was requested explicitly.
+    fn default_block_id(&self) -> BlockId {
+        self.included.as_ref().map(|(block, _)| block.id()).unwrap_or_default()
+    }
+}
+
+#[Object(name = "Transaction")]
+impl<Eth: GraphQlEthApi> Transaction<Eth> {
+    /// The hash of the transaction.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(*self.tx.tx_hash())
+    }
+
+    /// The nonce of the transaction.
+    async fn nonce(&self) -> Long {
+        Long(self.tx.nonce())
+    }
+
+    /// The index of the transaction in its block, null for pending transactions.
+    async fn index(&self) -> Option<Long> {
+        self.included.as_ref().map(|(_, index)| Long(*index as u64))
+    }
+
+    /// The account that sent the transaction.
+    async fn from(&self, block: Option<Long>) -> Account<Eth> {
+        Account::new(&self.eth, self.tx.signer(), block, self.default_block_id())
+    }
+
+    /// The account the transaction was sent to, null for contract creations.
+    async fn to(&self, block: Option<Long>) -> Option<Account<Eth>> {
+        self.tx.to().map(|to| Account::new(&self.eth, to, block, self.default_block_id()))
+    }
+
+    /// The value in wei sent with the transaction.
+    async fn value(&self) -> BigInt {
+        BigInt(self.tx.value())
+    }
+
+    /// The price offered per unit of gas, or the effective price once included.
+    async fn gas_price(&self) -> BigInt {
+        BigInt(U256::from(self.tx.effective_gas_price(self.base_fee())))
+    }
+
+    /// The maximum fee per gas, null for non dynamic fee transactions.
+    async fn max_fee_per_gas(&self) -> Option<BigInt> {
+        self.tx.is_dynamic_fee().then(|| BigInt(U256::from(self.tx.max_fee_per_gas())))
+    }
+
+    /// The maximum priority fee per gas, null for non dynamic fee transactions.
+    async fn max_priority_fee_per_gas(&self) -> Option<BigInt> {
+        self.tx.max_priority_fee_per_gas().map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The priority fee per gas that was paid to the miner.
+    async fn effective_tip(&self) -> Option<BigInt> {
+        self.base_fee()
+            .and_then(|base_fee| self.tx.effective_tip_per_gas(base_fee))
+            .map(|tip| BigInt(U256::from(tip)))
+    }
+
+    /// The maximum amount of gas the transaction may use.
+    async fn gas(&self) -> Long {
+        Long(self.tx.gas_limit())
+    }
+
+    /// The input data of the transaction.
+    async fn input_data(&self) -> Bytes {
+        Bytes(self.tx.input().clone())
+    }
+
+    /// The block that includes the transaction, null for pending transactions.
+    async fn block(&self) -> Option<Block<Eth>> {
+        self.included.as_ref().map(|(block, _)| block.clone())
+    }
+
+    /// The status of the transaction, 1 for success and 0 for failure.
+    async fn status(&self) -> Option<Long> {
+        self.receipt().map(|receipt| Long(receipt.status() as u64))
+    }
+
+    /// The amount of gas used by the transaction.
+    async fn gas_used(&self) -> Option<Long> {
+        let (block, index) = self.included.as_ref()?;
+        let cumulative = block.receipts.get(*index)?.cumulative_gas_used();
+        let previous = index
+            .checked_sub(1)
+            .and_then(|prev| block.receipts.get(prev))
+            .map_or(0, |receipt| receipt.cumulative_gas_used());
+        Some(Long(cumulative - previous))
+    }
+
+    /// The total amount of gas used in the block up to and including this transaction.
+    async fn cumulative_gas_used(&self) -> Option<Long> {
+        self.receipt().map(|receipt| Long(receipt.cumulative_gas_used()))
+    }
+
+    /// The effective price per unit of gas paid by the transaction.
+    async fn effective_gas_price(&self) -> Option<BigInt> {
+        self.included
+            .as_ref()
+            .map(|_| BigInt(U256::from(self.tx.effective_gas_price(self.base_fee()))))
+    }
+
+    /// The contract created by the transaction, null if it is not a contract creation.
+    async fn created_contract(&self, block: Option<Long>) -> Option<Account<Eth>> {
+        if self.included.is_none() || !self.tx.kind().is_create() {
+            return None
+        }
+        let address = self.tx.signer().create(self.tx.nonce());
+        Some(Account::new(&self.eth, address, block, self.default_block_id()))
+    }
+
+    /// The logs emitted by the transaction, null for pending transactions.
+    async fn logs(&self) -> Option<Vec<Log<Eth>>> {
+        let (block, index) = self.included.as_ref()?;
+        Some(block.tx_logs(*index, &LogFilter::default()))
+    }
+
+    /// The EIP-2718 type of the transaction.
+    #[graphql(name = "type")]
+    async fn ty(&self) -> Long {
+        Long(self.tx.ty() as u64)
+    }
+
+    /// The EIP-2930 access list of the transaction.
+    async fn access_list(&self) -> Option<Vec<AccessTuple>> {
+        self.tx.access_list().map(|list| {
+            list.iter()
+                .map(|item| AccessTuple {
+                    address: Address(item.address),
+                    storage_keys: item.storage_keys.iter().copied().map(Bytes32).collect(),
+                })
+                .collect()
+        })
+    }
+
+    /// The EIP-2718 encoded transaction.
+    async fn raw(&self) -> Bytes {
+        Bytes(self.tx.encoded_2718().into())
+    }
+
+    /// The EIP-2718 encoded receipt of the transaction.
+    async fn raw_receipt(&self) -> Option<Bytes> {
+        let receipt = self.receipt()?;
+        let mut out = Vec::new();
+        receipt.eip2718_encode_with_bloom(&receipt.bloom(), &mut out);
+        Some(Bytes(out.into()))
+    }
+}
+
+impl<Eth: GraphQlEthApi> fmt::Debug for Transaction<Eth> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transaction")
+            .field("hash", self.tx.tx_hash())
+            .field("included", &self.included.as_ref().map(|(block, index)| (block.id(), index)))
+            .finish_non_exhaustive()
+    }
+}
+
+/// A log emitted by a transaction.
+#[derive(Clone)]
+pub struct Log<Eth: GraphQlEthApi> {
+    transaction: Transaction<Eth>,
+    /// Index of the log in the block.
+    index: u64,
+    log: primitives::Log,
+}
+
+#[Object(name = "Log")]
+impl<Eth: GraphQlEthApi> Log<Eth> {
+    /// The index of the log in the block.
+    async fn index(&self) -> Long {
+        Long(self.index)
+    }
+
+    /// The account that emitted the log.
+    async fn account(&self, block: Option<Long>) -> Account<Eth> {
+        Account::new(
+            &self.transaction.eth,
+            self.log.address,
+            block,
+            self.transaction.default_block_id(),
+        )
+    }
+
+    /// The topics of the log.
+    async fn topics(&self) -> Vec<Bytes32> {
+        self.log.topics().iter().copied().map(Bytes32).collect()
+    }
+
+    /// The data of the log.
+    async fn data(&self) -> Bytes {
+        Bytes(self.log.data.data.clone())
+    }
+
+    /// The transaction that emitted the log.
+    async fn transaction(&self) -> Transaction<Eth> {
+        self.transaction.clone()
+    }
+}
+
+impl<Eth: GraphQlEthApi> fmt::Debug for Log<Eth> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Log")
+            .field("index", &self.index)
+            .field("log", &self.log)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An account at a specific block.
+#[derive(Clone)]
+pub struct Account<Eth: GraphQlEthApi> {
+    eth: Eth,
+    address: primitives::Address,
+    block_id: BlockId,
+}
+
+impl<Eth: GraphQlEthApi> Account<Eth> {
+    /// Creates a new account at the given block number, or at the fallback block.
+    pub(crate) fn new(
+        eth: &Eth,
+        address: primitives::Address,
+        block: Option<Long>,
+        fallback: BlockId,
+    ) -> Self {
+        let block_id = block.map(|block| BlockId::number(block.0)).unwrap_or(fallback);
+        Self { eth: eth.clone(), address, block_id }
+    }
+}
+
+#[Object(name = "Account")]
+impl<Eth: GraphQlEthApi> Account<Eth> {
+    /// The address of the account.
+    async fn address(&self) -> Address {
+        Address(self.address)
+    }
+
+    /// The balance of the account in wei.
+    async fn balance(&self) -> Result<BigInt> {
+        Ok(BigInt(self.eth.balance(self.address, Some(self.block_id)).await?))
+    }
+
+    /// The number of transactions sent from the account.
+    async fn transaction_count(&self) -> Result<Long> {
+        let count = self.eth.transaction_count(self.address, Some(self.block_id)).await?;
+        Ok(Long(count.saturating_to()))
+    }
+
+    /// The code of the account, empty if it is not a contract.
+    async fn code(&self) -> Result<Bytes> {
+        Ok(Bytes(self.eth.get_code(self.address, Some(self.block_id)).await?))
+    }
+
+    /// The value of the storage slot of the account.
+    async fn storage(&self, slot: Bytes32) -> Result<Bytes32> {
+        let value = self.eth.storage_at(self.address, slot.0.into(), Some(self.block_id)).await?;
+        Ok(Bytes32(value))
+    }
+}
+
+impl<Eth: GraphQlEthApi> fmt::Debug for Account<Eth> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Account")
+            .field("address", &self.address)
+            .field("block_id", &self.block_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The state of the pending block.
+#[derive(Clone)]
+pub struct Pending<Eth: GraphQlEthApi> {
+    eth: Eth,
+}
+
+impl<Eth: GraphQlEthApi> Pending<Eth> {
+    /// Creates a new pending state object.
+    pub(crate) const fn new(eth: Eth) -> Self {
+        Self { eth }
+    }
+}
+
+#[Object(name = "Pending")]
+impl<Eth: GraphQlEthApi> Pending<Eth> {
+    /// The number of transactions that are ready to be included.
+    async fn transaction_count(&self) -> Long {
+        Long(self.eth.pool().pending_transactions().len() as u64)
+    }
+
+    /// The transactions that are ready to be included.
+    async fn transactions(&self) -> Vec<Transaction<Eth>> {
+        self.eth
+            .pool()
+            .pending_transactions()
+            .into_iter()
+            .map(|tx| Transaction::pending(self.eth.clone(), tx.transaction.clone_into_consensus()))
+            .collect()
+    }
+
+    /// The given account in the pending state.
+    async fn account(&self, address: Address) -> Account<Eth> {
+        Account::new(&self.eth, address.0, None, BlockId::pending())
+    }
+
+    /// Executes a local call on top of the pending state.
+    async fn call(&self, data: CallData) -> Result<CallResult> {
+        call(&self.eth, data, BlockId::pending()).await
+    }
+
+    /// Estimates the gas a transaction would use on top of the pending state.
+    async fn estimate_gas(&self, data: CallData) -> Result<Long> {
+        estimate_gas(&self.eth, data, BlockId::pending()).await
+    }
+}
+
+impl<Eth: GraphQlEthApi> fmt::Debug for Pending<Eth> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pending").finish_non_exhaustive()
+    }
+}
+
+/// The result of a local call.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct CallResult {
+    /// The return data of the call.
+    pub data: Bytes,
+    /// The amount of gas used by the call.
+    pub gas_used: Long,
+    /// The status of the call, 1 for success and 0 for failure.
+    pub status: Long,
+}
+
+/// The sync progress of the node.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct SyncState {
+    /// The block number the sync started at.
+    pub starting_block: Long,
+    /// The current block number.
+    pub current_block: Long,
+    /// The highest known block number.
+    pub highest_block: Long,
+}
+
+/// An EIP-2930 access list entry.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct AccessTuple {
+    /// The address that is accessed.
+    pub address: Address,
+    /// The storage keys that are accessed.
+    pub storage_keys: Vec<Bytes32>,
+}
+
+/// The arguments of a local call or gas estimation.
+#[derive(Clone, Debug, Default, InputObject)]
+pub struct CallData {
+    /// The sender of the call, defaults to the zero address.
+    pub from: Option<Address>,
+    /// The recipient of the call, null for contract creations.
+    pub to: Option<Address>,
+    /// The gas limit of the call.
+    pub gas: Option<Long>,
+    /// The legacy gas price of the call.
+    pub gas_price: Option<BigInt>,
+    /// The maximum fee per gas of the call.
+    pub max_fee_per_gas: Option<BigInt>,
+    /// The maximum priority fee per gas of the call.
+    pub max_priority_fee_per_gas: Option<BigInt>,
+    /// The value sent with the call.
+    pub value: Option<BigInt>,
+    /// The input data of the call.
+    pub data: Option<Bytes>,
+}
+
+impl From<CallData> for TransactionRequest {
+    fn from(data: CallData) -> Self {
+        Self {
+            from: data.from.map(|from| from.0),
+            to: data.to.map(|to| to.0.into()),
+            gas: data.gas.map(|gas| gas.0),
+            gas_price: data.gas_price.map(|price| price.0.saturating_to()),
+            max_fee_per_gas: data.max_fee_per_gas.map(|fee| fee.0.saturating_to()),
+            max_priority_fee_per_gas: data
+                .max_priority_fee_per_gas
+                .map(|fee| fee.0.saturating_to()),
+            value: data.value.map(|value| value.0),
+            input: TransactionInput::maybe_input(data.data.map(|data| data.0)),
+            ..Default::default()
+        }
+    }
+}
+
+/// Log filter applied to a single block.
+#[derive(Clone, Debug, Default, InputObject)]
+pub struct BlockFilterCriteria {
+    /// Addresses to match, matches any address if empty.
+    pub addresses: Option<Vec<Address>>,
+    /// Topics to match by position, an empty position matches any topic.
+    pub topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// Log filter applied to a range of blocks.
+#[derive(Clone, Debug, Default, InputObject)]
+pub struct FilterCriteria {
+    /// The first block to search, defaults to the latest block.
+    pub from_block: Option<Long>,
+    /// The last block to search, defaults to the latest block.
+    pub to_block: Option<Long>,
+    /// Addresses to match, matches any address if empty.
+    pub addresses: Option<Vec<Address>>,
+    /// Topics to match by position, an empty position matches any topic.
+    pub topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// Matches logs by address and positional topics.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LogFilter {
+    addresses: Vec<primitives::Address>,
+    topics: Vec<Vec<B256>>,
+}
+
+impl LogFilter {
+    /// Creates a new filter from the GraphQL filter arguments.
+    pub(crate) fn new(addresses: Option<Vec<Address>>, topics: Option<Vec<Vec<Bytes32>>>) -> Self {
+        Self {
+            addresses: addresses.unwrap_or_default().into_iter().map(|address| address.0).collect(),
+            topics: topics
+                .unwrap_or_default()
+                .into_iter()
+                .map(|topics| topics.into_iter().map(|topic| topic.0).collect())
+                .collect(),
+        }
+    }
+
+    /// Returns true if the log matches the filter.
+    pub(crate) fn matches(&self, log: &primitives::Log) -> bool {
+        if !self.addresses.is_empty() && !self.addresses.contains(&log.address) {
+            return false
+        }
+        self.topics.iter().enumerate().all(|(idx, topics)| {
+            topics.is_empty() || log.topics().get(idx).is_some_and(|topic| topics.contains(topic))
+        })
+    }
+}
+
+/// Converts the call arguments into the network's transaction request type.
+fn call_request<Eth: GraphQlEthApi>(data: CallData) -> Result<RpcTxReq<Eth::NetworkTypes>> {
+    let request = TransactionRequest::from(data);
+    Ok(serde_json::from_value(serde_json::to_value(request)?)?)
+}
+
+/// Executes a local call at the given block.
+async fn call<Eth: GraphQlEthApi>(eth: &Eth, data: CallData, at: BlockId) -> Result<CallResult> {
+    let request = call_request::<Eth>(data)?;
+    let result = eth.transact_call_at(request, at, EvmOverrides::default()).await?.result;
+    Ok(CallResult {
+        data: Bytes(result.output().cloned().unwrap_or_default()),
+        gas_used: Long(result.gas_used()),
+        status: Long(result.is_success() as u64),
+    })
+}
+
+/// Estimates the gas of a transaction at the given block.
+async fn estimate_gas<Eth: GraphQlEthApi>(eth: &Eth, data: CallData, at: BlockId) -> Result<Long> {
+    let request = call_request::<Eth>(data)?;
+    let gas = EthCall::estimate_gas_at(eth, request, at, None).await?;
+    Ok(Long(gas.saturating_to()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, LogData};
+
+    #[test]
+    fn log_filter_matches() {
+        let topic = b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+        let other = b256!("0x0000000000000000000000000000000000000000000000000000000000000002");
+        let log = primitives::Log {
+            address: address!("0x0000000000000000000000000000000000000001"),
+            data: LogData::new_unchecked(vec![topic, other], Default::default()),
+        };
+
+        assert!(LogFilter::default().matches(&log));
+        assert!(LogFilter::new(Some(vec![Address(log.address)]), None).matches(&log));
+        assert!(!LogFilter::new(Some(vec![Address::default()]), None).matches(&log));
+
+        // wildcard at the first position
+        let filter = LogFilter::new(None, Some(vec![vec![], vec![Bytes32(other)]]));
+        assert!(filter.matches(&log));
+
+        let filter = LogFilter::new(None, Some(vec![vec![Bytes32(other)]]));
+        assert!(!filter.matches(&log));
+
+        // more topic positions than the log has
+        let filter = LogFilter::new(None, Some(vec![vec![], vec![], vec![Bytes32(topic)]]));
+        assert!(!filter.matches(&log));
+    }
+}
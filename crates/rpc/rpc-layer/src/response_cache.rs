@@ -0,0 +1,618 @@
+//! Response cache for expensive calls against finalized blocks.
+//!
+//! Responses of methods like `debug_traceBlockByNumber` or `trace_block` never change once the
+//! requested block is finalized. The [`ResponseCacheLayer`] stores the JSON result of such calls,
+//! keyed by method, params and the hash of the requested block, in a size bounded memory tier and
+//! an optional disk tier.
+//!
+//! Calls for blocks that are not finalized yet are never cached. Because the block hash is part of
+//! the key, a cached response can only be served for the exact block it was computed for. Entries
+//! of blocks that are removed by a reorg can be dropped with
+//! [`RpcResponseCache::invalidate_blocks`].
+//!
+//! The cache can be shared between servers, but each [`ResponseCacheLayer`] only serves the methods
+//! that are registered on its transport.
+
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{keccak256, B256};
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{Request, ResponsePayload},
+    MethodResponse, Methods,
+};
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_storage_api::BlockIdReader;
+use schnellru::{LruMap, Unlimited};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tower::Layer;
+use tracing::{debug, warn};
+
+/// Methods that are cached by default.
+///
+/// All of them take the block as first parameter.
+pub const DEFAULT_CACHED_METHODS: &[&str] = &[
+    "debug_traceBlockByNumber",
+    "debug_traceBlockByHash",
+    "trace_block",
+    "trace_replayBlockTransactions",
+    "eth_getBlockReceipts",
+    "ots_getBlockDetails",
+    "ots_getBlockDetailsByHash",
+];
+
+/// Configuration of the [`RpcResponseCache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseCacheConfig {
+    /// Maximum total size of the responses kept in memory, in bytes.
+    pub max_memory_bytes: usize,
+    /// Directory of the disk tier, disabled if `None`.
+    pub disk_dir: Option<PathBuf>,
+    /// Maximum total size of the responses kept on disk, in bytes.
+    pub max_disk_bytes: u64,
+    /// Methods whose responses are cached, the block must be the first parameter.
+    pub methods: HashSet<String>,
+    /// Maximum size of a response served from the cache, in bytes.
+    ///
+    /// This should match the response size limit of the servers the cache is layered on.
+    pub max_response_size: u32,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_memory_bytes: 256 * 1024 * 1024,
+            disk_dir: None,
+            max_disk_bytes: 4 * 1024 * 1024 * 1024,
+            methods: DEFAULT_CACHED_METHODS.iter().map(|method| method.to_string()).collect(),
+            max_response_size: 160 * 1024 * 1024,
+        }
+    }
+}
+
+/// A cache for JSON-RPC results of calls against finalized blocks.
+///
+/// This type is cheap to clone and can be shared between servers.
+#[derive(Clone)]
+pub struct RpcResponseCache {
+    inner: Arc<CacheInner>,
+}
+
+struct CacheInner {
+    config: ResponseCacheConfig,
+    /// Resolves the requested block to the hash of a finalized block.
+    provider: Arc<dyn BlockIdReader>,
+    memory: Mutex<MemoryTier>,
+    /// Accessed on the blocking pool only, since all operations do file IO.
+    disk: Option<Arc<Mutex<DiskTier>>>,
+    metrics: ResponseCacheMetrics,
+}
+
+impl RpcResponseCache {
+    /// Creates a new cache that resolves blocks with the given provider.
+    ///
+    /// If a disk tier is configured, responses already stored in the directory are reused.
+    pub fn new(config: ResponseCacheConfig, provider: Arc<dyn BlockIdReader>) -> io::Result<Self> {
+        let disk = config
+            .disk_dir
+            .as_ref()
+            .map(|dir| {
+                DiskTier::open(dir.clone(), config.max_disk_bytes)
+                    .map(|disk| Arc::new(Mutex::new(disk)))
+            })
+            .transpose()?;
+        Ok(Self {
+            inner: Arc::new(CacheInner {
+                memory: Mutex::new(MemoryTier::new(config.max_memory_bytes)),
+                config,
+                provider,
+                disk,
+                metrics: ResponseCacheMetrics::default(),
+            }),
+        })
+    }
+
+    /// Returns true if responses of the method are cached.
+    pub fn is_cached_method(&self, method: &str) -> bool {
+        self.inner.config.methods.contains(method)
+    }
+
+    /// Returns the cache key of the call, if the requested block is finalized.
+    fn key(&self, method: &str, params: Option<&str>) -> Option<CacheKey> {
+        let params: Vec<serde_json::Value> = serde_json::from_str(params.unwrap_or("[]")).ok()?;
+        let block = block_id(params.first()?)?;
+        let block_hash = finalized_block_hash(&*self.inner.provider, block)?;
+
+        // re-serialize to ignore formatting differences of the params
+        let params = serde_json::to_string(&params).ok()?;
+        let mut preimage = Vec::with_capacity(method.len() + params.len() + 34);
+        preimage.extend_from_slice(method.as_bytes());
+        preimage.push(0);
+        preimage.extend_from_slice(params.as_bytes());
+        preimage.push(0);
+        preimage.extend_from_slice(block_hash.as_slice());
+
+        Some(CacheKey { block_hash, hash: keccak256(preimage) })
+    }
+
+    /// Returns the cached result for the key.
+    ///
+    /// The disk tier is read on the blocking pool.
+    async fn get(&self, key: CacheKey) -> Option<Arc<RawValue>> {
+        if let Some(result) = self.inner.memory.lock().get(&key.hash) {
+            self.inner.metrics.memory_hits_total.increment(1);
+            return Some(result)
+        }
+
+        let disk = self.inner.disk.clone()?;
+        let result = tokio::task::spawn_blocking(move || disk.lock().get(&key)).await.ok()??;
+        self.inner.metrics.disk_hits_total.increment(1);
+        self.insert_memory(&key, result.clone());
+        Some(result)
+    }
+
+    /// Stores the result for the key in all tiers.
+    ///
+    /// The disk tier is written in the background on the blocking pool.
+    fn insert(&self, key: CacheKey, result: Arc<RawValue>) {
+        if let Some(disk) = self.inner.disk.clone() {
+            let this = self.clone();
+            let result = result.clone();
+            tokio::task::spawn_blocking(move || {
+                let mut disk = disk.lock();
+                if let Err(err) = disk.insert(&key, &result) {
+                    warn!(target: "rpc::response_cache", %err, "Failed to write response to disk");
+                }
+                this.inner.metrics.disk_bytes.set(disk.size as f64);
+            });
+        }
+        self.insert_memory(&key, result);
+    }
+
+    fn insert_memory(&self, key: &CacheKey, result: Arc<RawValue>) {
+        let mut memory = self.inner.memory.lock();
+        memory.insert(*key, result);
+        self.inner.metrics.memory_bytes.set(memory.size as f64);
+    }
+
+    /// Removes all responses for the given blocks, e.g. blocks that were reorged out.
+    ///
+    /// This removes the files of the disk tier and should not be called on an async task.
+    pub fn invalidate_blocks(&self, block_hashes: impl IntoIterator<Item = B256>) {
+        let block_hashes = block_hashes.into_iter().collect::<HashSet<_>>();
+        if block_hashes.is_empty() {
+            return
+        }
+
+        let mut memory = self.inner.memory.lock();
+        memory.remove_blocks(&block_hashes);
+        self.inner.metrics.memory_bytes.set(memory.size as f64);
+        drop(memory);
+
+        if let Some(disk) = &self.inner.disk {
+            let mut disk = disk.lock();
+            disk.remove_blocks(&block_hashes);
+            self.inner.metrics.disk_bytes.set(disk.size as f64);
+        }
+        debug!(target: "rpc::response_cache", blocks = block_hashes.len(), "Invalidated cached responses");
+    }
+}
+
+impl fmt::Debug for RpcResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcResponseCache")
+            .field("config", &self.inner.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Parses the block parameter, which is a block id or a plain block number.
+fn block_id(param: &serde_json::Value) -> Option<BlockId> {
+    if let Some(number) = param.as_u64() {
+        return Some(BlockId::number(number))
+    }
+    BlockId::deserialize(param).ok()
+}
+
+/// Returns the hash of the requested block if it is canonical and finalized.
+fn finalized_block_hash(provider: &dyn BlockIdReader, block: BlockId) -> Option<B256> {
+    let finalized = provider.finalized_block_number().ok()??;
+    let number = match block {
+        BlockId::Number(BlockNumberOrTag::Number(number)) => number,
+        BlockId::Number(BlockNumberOrTag::Earliest) => provider.earliest_block_number().ok()?,
+        BlockId::Number(BlockNumberOrTag::Finalized) => finalized,
+        BlockId::Hash(hash) => provider.block_number(hash.block_hash).ok()??,
+        // these tags move with the chain
+        BlockId::Number(
+            BlockNumberOrTag::Latest | BlockNumberOrTag::Pending | BlockNumberOrTag::Safe,
+        ) => return None,
+    };
+    if number > finalized {
+        return None
+    }
+
+    let canonical = provider.block_hash(number).ok()??;
+    match block {
+        BlockId::Hash(hash) if hash.block_hash != canonical => None,
+        _ => Some(canonical),
+    }
+}
+
+/// Key of a cached response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CacheKey {
+    /// Hash of the requested block.
+    block_hash: B256,
+    /// Hash of method, params and block hash.
+    hash: B256,
+}
+
+/// Responses kept in memory, the least recently used are evicted first.
+struct MemoryTier {
+    entries: LruMap<B256, (B256, Arc<RawValue>), Unlimited>,
+    size: usize,
+    max_size: usize,
+}
+
+impl MemoryTier {
+    fn new(max_size: usize) -> Self {
+        Self { entries: LruMap::new(Unlimited), size: 0, max_size }
+    }
+
+    fn get(&mut self, key: &B256) -> Option<Arc<RawValue>> {
+        self.entries.get(key).map(|(_, result)| result.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, result: Arc<RawValue>) {
+        let len = result.get().len();
+        if len > self.max_size {
+            return
+        }
+        if let Some((_, old)) = self.entries.peek(&key.hash) {
+            self.size -= old.get().len();
+        }
+        self.entries.insert(key.hash, (key.block_hash, result));
+        self.size += len;
+
+        while self.size > self.max_size {
+            let Some((_, (_, evicted))) = self.entries.pop_oldest() else { break };
+            self.size -= evicted.get().len();
+        }
+    }
+
+    fn remove_blocks(&mut self, block_hashes: &HashSet<B256>) {
+        let keys = self
+            .entries
+            .iter()
+            .filter(|(_, (block_hash, _))| block_hashes.contains(block_hash))
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        for key in keys {
+            if let Some((_, result)) = self.entries.remove(&key) {
+                self.size -= result.get().len();
+            }
+        }
+    }
+}
+
+/// Responses stored as files in `<dir>/<block hash>/<key>.json`, the oldest are evicted first.
+struct DiskTier {
+    dir: PathBuf,
+    /// Size of the stored files by key.
+    entries: HashMap<CacheKey, u64>,
+    /// Insertion order of the keys.
+    order: VecDeque<CacheKey>,
+    size: u64,
+    max_size: u64,
+}
+
+impl DiskTier {
+    /// Opens the directory and indexes the responses already stored in it.
+    fn open(dir: PathBuf, max_size: u64) -> io::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        let mut tier =
+            Self { dir, entries: HashMap::new(), order: VecDeque::new(), size: 0, max_size };
+
+        for block_dir in std::fs::read_dir(&tier.dir)? {
+            let block_dir = block_dir?;
+            let Some(block_hash) =
+                block_dir.file_name().to_str().and_then(|name| name.parse::<B256>().ok())
+            else {
+                continue
+            };
+            for file in std::fs::read_dir(block_dir.path())? {
+                let file = file?;
+                let Some(hash) = file
+                    .file_name()
+                    .to_str()
+                    .and_then(|name| name.strip_suffix(".json"))
+                    .and_then(|name| name.parse::<B256>().ok())
+                else {
+                    continue
+                };
+                let key = CacheKey { block_hash, hash };
+                let len = file.metadata()?.len();
+                tier.entries.insert(key, len);
+                tier.order.push_back(key);
+                tier.size += len;
+            }
+        }
+        tier.evict()?;
+        Ok(tier)
+    }
+
+    fn block_dir(&self, block_hash: &B256) -> PathBuf {
+        self.dir.join(block_hash.to_string())
+    }
+
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        self.block_dir(&key.block_hash).join(format!("{}.json", key.hash))
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<Arc<RawValue>> {
+        if !self.entries.contains_key(key) {
+            return None
+        }
+        let json = std::fs::read_to_string(self.path(key)).ok()?;
+        RawValue::from_string(json).ok().map(Arc::from)
+    }
+
+    fn insert(&mut self, key: &CacheKey, result: &RawValue) -> io::Result<()> {
+        let len = result.get().len() as u64;
+        if len > self.max_size || self.entries.contains_key(key) {
+            return Ok(())
+        }
+        std::fs::create_dir_all(self.block_dir(&key.block_hash))?;
+        write_atomic(&self.path(key), result.get().as_bytes())?;
+
+        self.entries.insert(*key, len);
+        self.order.push_back(*key);
+        self.size += len;
+        self.evict()
+    }
+
+    /// Removes the oldest responses until the size limit is met.
+    fn evict(&mut self) -> io::Result<()> {
+        while self.size > self.max_size {
+            let Some(key) = self.order.pop_front() else { break };
+            let Some(len) = self.entries.remove(&key) else { continue };
+            self.size -= len;
+            match std::fs::remove_file(self.path(&key)) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_blocks(&mut self, block_hashes: &HashSet<B256>) {
+        self.entries.retain(|key, len| {
+            let keep = !block_hashes.contains(&key.block_hash);
+            if !keep {
+                self.size -= *len;
+            }
+            keep
+        });
+        self.order.retain(|key| !block_hashes.contains(&key.block_hash));
+        for block_hash in block_hashes {
+            match std::fs::remove_dir_all(self.block_dir(block_hash)) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                    warn!(target: "rpc::response_cache", %err, %block_hash, "Failed to remove cached responses")
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Writes the file through a temporary file, so readers never see partial responses.
+fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(tmp, path)
+}
+
+/// Metrics of the [`RpcResponseCache`].
+#[derive(Metrics)]
+#[metrics(scope = "rpc_server.response_cache")]
+struct ResponseCacheMetrics {
+    /// Number of calls answered from memory
+    memory_hits_total: Counter,
+    /// Number of calls answered from disk
+    disk_hits_total: Counter,
+    /// Number of cacheable calls that had to be executed
+    misses_total: Counter,
+    /// Total size of the responses in memory
+    memory_bytes: Gauge,
+    /// Total size of the responses on disk
+    disk_bytes: Gauge,
+}
+
+/// RPC middleware that answers calls from the [`RpcResponseCache`].
+///
+/// The layer runs before the call is routed to a method, so it only serves the cached methods that
+/// are registered on the server it is layered on.
+#[derive(Debug, Clone)]
+pub struct ResponseCacheLayer {
+    cache: RpcResponseCache,
+    /// Cached methods registered on the server.
+    methods: Arc<HashSet<String>>,
+}
+
+impl ResponseCacheLayer {
+    /// Creates a new layer backed by the given cache, for a server with the given methods.
+    pub fn new(cache: RpcResponseCache, methods: &Methods) -> Self {
+        let methods = methods
+            .method_names()
+            .filter(|method| cache.is_cached_method(method))
+            .map(String::from)
+            .collect();
+        Self { cache, methods: Arc::new(methods) }
+    }
+}
+
+impl<S> Layer<S> for ResponseCacheLayer {
+    type Service = ResponseCacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponseCacheService { inner, cache: self.cache.clone(), methods: self.methods.clone() }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that serves cached responses of finalized blocks.
+#[derive(Debug, Clone)]
+pub struct ResponseCacheService<S> {
+    inner: S,
+    cache: RpcResponseCache,
+    methods: Arc<HashSet<String>>,
+}
+
+impl<S> RpcServiceT for ResponseCacheService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let inner = self.inner.clone();
+        let cache = self.cache.clone();
+        let methods = self.methods.clone();
+
+        Box::pin(async move {
+            if !methods.contains(req.method_name()) {
+                return inner.call(req).await
+            }
+            let Some(key) = cache.key(req.method_name(), req.params().as_str()) else {
+                return inner.call(req).await
+            };
+
+            if let Some(result) = cache.get(key).await {
+                let payload = ResponsePayload::success(result.as_ref().to_owned()).into();
+                let max_response_size = cache.inner.config.max_response_size as usize;
+                return MethodResponse::response(req.id, payload, max_response_size)
+            }
+            cache.inner.metrics.misses_total.increment(1);
+
+            let response = inner.call(req).await;
+            if response.is_success() {
+                if let Some(result) = extract_result(&response) {
+                    cache.insert(key, result);
+                }
+            }
+            response
+        })
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Returns the `result` of a successful response.
+fn extract_result(response: &MethodResponse) -> Option<Arc<RawValue>> {
+    #[derive(Deserialize)]
+    struct Success<'a> {
+        #[serde(borrow)]
+        result: &'a RawValue,
+    }
+    let json = response.to_json();
+    let success: Success<'_> = serde_json::from_str(json.get()).ok()?;
+    Some(Arc::from(success.result.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(block: u8, hash: u8) -> CacheKey {
+        CacheKey { block_hash: B256::with_last_byte(block), hash: B256::with_last_byte(hash) }
+    }
+
+    fn result(json: &str) -> Arc<RawValue> {
+        Arc::from(RawValue::from_string(json.to_string()).unwrap())
+    }
+
+    #[test]
+    fn parse_block_param() {
+        assert_eq!(block_id(&serde_json::json!(10)), Some(BlockId::number(10)));
+        assert_eq!(block_id(&serde_json::json!("0xa")), Some(BlockId::number(10)));
+        assert_eq!(block_id(&serde_json::json!("latest")), Some(BlockId::latest()));
+        assert_eq!(block_id(&serde_json::json!({"from": 1})), None);
+    }
+
+    #[test]
+    fn memory_tier_evicts_oldest() {
+        let mut memory = MemoryTier::new(10);
+        memory.insert(key(1, 1), result("\"abcd\""));
+        memory.insert(key(1, 2), result("\"efgh\""));
+        assert_eq!(memory.size, 6);
+        assert_eq!(memory.entries.len(), 1);
+        assert!(memory.get(&key(1, 1).hash).is_none());
+        assert_eq!(memory.get(&key(1, 2).hash).unwrap().get(), "\"efgh\"");
+
+        memory.insert(key(2, 3), result("1"));
+        memory.remove_blocks(&HashSet::from([B256::with_last_byte(1)]));
+        assert_eq!(memory.size, 1);
+        assert!(memory.get(&key(2, 3).hash).is_some());
+    }
+
+    #[test]
+    fn layer_serves_registered_methods() {
+        let cache = RpcResponseCache::new(
+            ResponseCacheConfig::default(),
+            Arc::new(reth_storage_api::noop::NoopProvider::default()),
+        )
+        .unwrap();
+        let mut module = jsonrpsee::RpcModule::new(());
+        module.register_method("trace_block", |_, _, _| "").unwrap();
+        module.register_method("eth_blockNumber", |_, _, _| "").unwrap();
+
+        let layer = ResponseCacheLayer::new(cache.clone(), &module.into());
+        assert_eq!(*layer.methods, HashSet::from(["trace_block".to_string()]));
+
+        // a server without the cached methods never answers from the cache
+        let layer = ResponseCacheLayer::new(cache, &Methods::default());
+        assert!(layer.methods.is_empty());
+    }
+
+    #[test]
+    fn disk_tier_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut disk = DiskTier::open(dir.path().to_path_buf(), 100).unwrap();
+        disk.insert(&key(1, 1), &result("[1,2,3]")).unwrap();
+        disk.insert(&key(2, 2), &result("{}")).unwrap();
+        assert_eq!(disk.get(&key(1, 1)).unwrap().get(), "[1,2,3]");
+
+        // responses are indexed again after a restart
+        let mut disk = DiskTier::open(dir.path().to_path_buf(), 100).unwrap();
+        assert_eq!(disk.size, 9);
+        assert_eq!(disk.get(&key(2, 2)).unwrap().get(), "{}");
+
+        disk.remove_blocks(&HashSet::from([B256::with_last_byte(1)]));
+        assert!(disk.get(&key(1, 1)).is_none());
+        assert!(!dir.path().join(B256::with_last_byte(1).to_string()).exists());
+        assert_eq!(disk.size, 2);
+    }
+}
@@ -6,6 +6,9 @@ pub const DEFAULT_HTTP_RPC_PORT: u16 = 8545;
 /// The default port for the ws server
 pub const DEFAULT_WS_RPC_PORT: u16 = 8546;
 
+/// The default port for the `GraphQL` server
+pub const DEFAULT_GRAPHQL_PORT: u16 = 8547;
+
 /// The default port for the auth server.
 pub const DEFAULT_AUTH_PORT: u16 = 8551;
 
@@ -54,6 +57,10 @@ pub const DEFAULT_MAX_SIMULATE_BLOCKS: u64 = 256;
 /// The default eth historical proof window.
 pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
 
+/// The default eth historical proof window for blocks with retained trie changesets. Proofs are
+/// not generated from trie changesets by default.
+pub const DEFAULT_ETH_PROOF_CHANGESETS_WINDOW: u64 = 0;
+
 /// The default eth tx fee cap is 1 ETH
 pub const DEFAULT_TX_FEE_CAP_WEI: u128 = 1_000_000_000_000_000_000u128;
 
@@ -127,4 +134,7 @@ pub mod cache {
 
     /// Default number of concurrent database requests.
     pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
+
+    /// Default size of the response cache on disk: 4GB
+    pub const DEFAULT_RESPONSE_CACHE_MAX_DISK_MB: u64 = 4 * 1024;
 }
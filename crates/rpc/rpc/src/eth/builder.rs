@@ -15,7 +15,8 @@ use reth_rpc_eth_types::{
     GasPriceOracleConfig,
 };
 use reth_rpc_server_types::constants::{
-    DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    DEFAULT_ETH_PROOF_CHANGESETS_WINDOW, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS,
+    DEFAULT_PROOF_PERMITS,
 };
 use reth_tasks::{pool::BlockingTaskPool, TaskSpawner, TokioTaskExecutor};
 use std::sync::Arc;
@@ -31,6 +32,7 @@ pub struct EthApiBuilder<N: RpcNodeCore, Rpc, NextEnv = ()> {
     gas_cap: GasCap,
     max_simulate_blocks: u64,
     eth_proof_window: u64,
+    eth_proof_changesets_window: u64,
     fee_history_cache_config: FeeHistoryCacheConfig,
     proof_permits: usize,
     eth_state_cache_config: EthStateCacheConfig,
@@ -69,6 +71,7 @@ impl<N: RpcNodeCore, Rpc, NextEnv> EthApiBuilder<N, Rpc, NextEnv> {
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            eth_proof_changesets_window,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -85,6 +88,7 @@ impl<N: RpcNodeCore, Rpc, NextEnv> EthApiBuilder<N, Rpc, NextEnv> {
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            eth_proof_changesets_window,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -114,6 +118,7 @@ where
             gas_cap: GasCap::default(),
             max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            eth_proof_changesets_window: DEFAULT_ETH_PROOF_CHANGESETS_WINDOW,
             blocking_task_pool: None,
             fee_history_cache_config: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
@@ -146,6 +151,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            eth_proof_changesets_window,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -162,6 +168,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            eth_proof_changesets_window,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -185,6 +192,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            eth_proof_changesets_window,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -201,6 +209,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            eth_proof_changesets_window,
             fee_history_cache_config,
             proof_permits,
             eth_state_cache_config,
@@ -260,6 +269,13 @@ where
         self
     }
 
+    /// Sets the maximum number of blocks into the past for generating state proofs from the
+    /// retained trie changesets, beyond the [`Self::eth_proof_window`].
+    pub const fn eth_proof_changesets_window(mut self, eth_proof_changesets_window: u64) -> Self {
+        self.eth_proof_changesets_window = eth_proof_changesets_window;
+        self
+    }
+
     /// Sets the blocking task pool.
     pub fn blocking_task_pool(mut self, blocking_task_pool: BlockingTaskPool) -> Self {
         self.blocking_task_pool = Some(blocking_task_pool);
@@ -304,6 +320,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            eth_proof_changesets_window,
             blocking_task_pool,
             fee_history_cache_config,
             proof_permits,
@@ -337,6 +354,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            eth_proof_changesets_window,
             blocking_task_pool.unwrap_or_else(|| {
                 BlockingTaskPool::build().expect("failed to build blocking task pool")
             }),
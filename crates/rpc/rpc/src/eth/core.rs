@@ -155,6 +155,7 @@ where
             gas_cap,
             max_simulate_blocks,
             eth_proof_window,
+            0,
             blocking_task_pool,
             fee_history_cache,
             TokioTaskExecutor::default().boxed(),
@@ -268,6 +269,9 @@ pub struct EthApiInner<N: RpcNodeCore, Rpc: RpcConvert> {
     max_simulate_blocks: u64,
     /// The maximum number of blocks into the past for generating state proofs.
     eth_proof_window: u64,
+    /// The maximum number of blocks into the past for generating state proofs from the retained
+    /// trie changesets.
+    eth_proof_changesets_window: u64,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
@@ -306,6 +310,7 @@ where
         gas_cap: impl Into<GasCap>,
         max_simulate_blocks: u64,
         eth_proof_window: u64,
+        eth_proof_changesets_window: u64,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache<ProviderHeader<N::Provider>>,
         task_spawner: Box<dyn TaskSpawner + 'static>,
@@ -335,6 +340,7 @@ where
             gas_cap: gas_cap.into().into(),
             max_simulate_blocks,
             eth_proof_window,
+            eth_proof_changesets_window,
             starting_block,
             task_spawner,
             pending_block: Default::default(),
@@ -456,6 +462,13 @@ where
         self.eth_proof_window
     }
 
+    /// The maximum number of blocks into the past for generating state proofs from the retained
+    /// trie changesets.
+    #[inline]
+    pub const fn eth_proof_changesets_window(&self) -> u64 {
+        self.eth_proof_changesets_window
+    }
+
     /// Returns reference to [`BlockingTaskGuard`].
     #[inline]
     pub const fn blocking_task_guard(&self) -> &BlockingTaskGuard {
@@ -22,8 +22,8 @@ use reth_rpc_eth_types::{
 };
 use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
 use reth_storage_api::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
-    ProviderReceipt, ReceiptProvider,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, LogIndexMatches,
+    LogIndexReader, ProviderBlock, ProviderReceipt, ReceiptProvider,
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
@@ -585,9 +585,29 @@ where
         // get current chain tip to determine processing mode
         let chain_tip = self.provider().best_block_number()?;
 
-        // first collect all headers that match the bloom filter for cached mode decision
+        // use the log index for the part of the range it covers, if it's enabled
+        let mut scan_from_block = from_block;
+        let addresses = filter.address.iter().copied().collect::<Vec<_>>();
+        let topics = filter
+            .topics
+            .iter()
+            .map(|topic| topic.iter().copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        if let Some(LogIndexMatches { indexed_to, blocks }) =
+            self.provider().log_index_blocks(from_block..=to_block, &addresses, &topics)?
+        {
+            trace!(target: "rpc::eth::filter", from_block, indexed_to, matches = blocks.len(), "Using log index");
+            for number in blocks {
+                if let Some(header) = self.provider().sealed_header(number)? {
+                    matching_headers.push(header);
+                }
+            }
+            scan_from_block = indexed_to + 1;
+        }
+
+        // collect all remaining headers that match the bloom filter for cached mode decision
         for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+            BlockRangeInclusiveIter::new(scan_from_block..=to_block, self.max_headers_range)
         {
             let headers = self.provider().headers_range(from..=to)?;
 
@@ -16,6 +16,10 @@ where
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_proof_window()
     }
+
+    fn max_proof_changesets_window(&self) -> u64 {
+        self.inner.eth_proof_changesets_window()
+    }
 }
 
 impl<N, Rpc> LoadState for EthApi<N, Rpc>
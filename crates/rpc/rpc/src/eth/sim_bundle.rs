@@ -1,25 +1,26 @@
 //! `Eth` Sim bundle implementation and helpers.
 
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{BlockHeader, Typed2718};
 use alloy_eips::BlockNumberOrTag;
 use alloy_evm::overrides::apply_block_overrides;
 use alloy_primitives::U256;
 use alloy_rpc_types_eth::BlockId;
 use alloy_rpc_types_mev::{
-    BundleItem, Inclusion, MevSendBundle, Privacy, RefundConfig, SimBundleLogs, SimBundleOverrides,
-    SimBundleResponse, Validity,
+    BundleItem, EthBundleHash, Inclusion, MevSendBundle, Privacy, RefundConfig, SimBundleLogs,
+    SimBundleOverrides, SimBundleResponse, Validity,
 };
 use jsonrpsee::core::RpcResult;
 use reth_evm::{ConfigureEvm, Evm};
+use reth_payload_util::{Bundle, BundlePool, BundleTransaction};
 use reth_primitives_traits::{Recovered, SignedTransaction};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
-use reth_rpc_api::MevSimApiServer;
+use reth_rpc_api::{MevFullApiServer, MevSimApiServer};
 use reth_rpc_eth_api::{
     helpers::{block::LoadBlock, Call, EthTransactions},
-    FromEthApiError, FromEvmError,
+    FromEthApiError, FromEvmError, RpcNodeCore,
 };
 use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
-use reth_storage_api::ProviderTx;
+use reth_storage_api::{BlockNumReader, ProviderTx};
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
 use revm::{context_interface::result::ResultAndState, DatabaseCommit, DatabaseRef};
@@ -438,6 +439,91 @@ where
     }
 }
 
+/// `Eth` bundle implementation that submits bundles to the local [`BundlePool`].
+///
+/// Bundles are included by the payload builder of this node, simulations are delegated to
+/// [`EthSimBundle`].
+pub struct EthSendBundle<Eth: RpcNodeCore> {
+    /// Simulates bundles.
+    sim: EthSimBundle<Eth>,
+    /// Bundles for the payload builder.
+    bundle_pool: BundlePool<ProviderTx<Eth::Provider>>,
+}
+
+impl<Eth: RpcNodeCore> EthSendBundle<Eth> {
+    /// Create a new `EthSendBundle` instance.
+    pub const fn new(
+        sim: EthSimBundle<Eth>,
+        bundle_pool: BundlePool<ProviderTx<Eth::Provider>>,
+    ) -> Self {
+        Self { sim, bundle_pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> MevFullApiServer for EthSendBundle<Eth>
+where
+    Eth: EthTransactions + LoadBlock + Call + 'static,
+{
+    async fn send_bundle(&self, request: MevSendBundle) -> RpcResult<EthBundleHash> {
+        trace!("mev_sendBundle called, request: {:?}", request);
+
+        let transactions = self
+            .sim
+            .parse_and_flatten_bundle(&request)?
+            .into_iter()
+            .map(|item| {
+                if item.tx.is_eip4844() {
+                    return Err(EthApiError::InvalidParams(
+                        EthSimBundleError::BlobTransaction.to_string(),
+                    ))
+                }
+                Ok(BundleTransaction {
+                    tx: item.tx,
+                    can_revert: item.can_revert,
+                    refund_percent: item.refund_percent,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let block_number = request.inclusion.block_number();
+        let bundle = Bundle::new(
+            block_number,
+            request.inclusion.max_block_number().unwrap_or(block_number),
+            transactions,
+            request.validity.and_then(|validity| validity.refund_config).unwrap_or_default(),
+        );
+
+        let tip = self.sim.eth_api().provider().best_block_number().map_err(EthApiError::from)?;
+        let bundle_hash = self
+            .bundle_pool
+            .add(bundle, tip)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    async fn sim_bundle(
+        &self,
+        request: MevSendBundle,
+        overrides: SimBundleOverrides,
+    ) -> RpcResult<SimBundleResponse> {
+        MevSimApiServer::sim_bundle(&self.sim, request, overrides).await
+    }
+}
+
+impl<Eth: RpcNodeCore> std::fmt::Debug for EthSendBundle<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthSendBundle").field("bundles", &self.bundle_pool.len()).finish()
+    }
+}
+
+impl<Eth: RpcNodeCore> Clone for EthSendBundle<Eth> {
+    fn clone(&self) -> Self {
+        Self { sim: self.sim.clone(), bundle_pool: self.bundle_pool.clone() }
+    }
+}
+
 /// Container type for `EthSimBundle` internals
 #[derive(Debug)]
 struct EthSimBundleInner<Eth> {
@@ -490,4 +576,7 @@ pub enum EthSimBundleError {
     /// Thrown when a bundle simulation returns negative profit
     #[error("bundle simulation returned negative profit")]
     NegativeProfit,
+    /// Thrown when a bundle submitted to the local pool contains a blob transaction
+    #[error("blob transactions are not supported in bundles")]
+    BlobTransaction,
 }
@@ -2,19 +2,27 @@ use std::{collections::HashMap, future::Future, sync::Arc};
 
 use alloy_eips::BlockId;
 use alloy_primitives::{Address, U256};
+use alloy_rpc_types_engine::PayloadId;
 use async_trait::async_trait;
 use futures::StreamExt;
 use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
 use jsonrpsee_types::ErrorObject;
 use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
 use reth_errors::RethResult;
+use reth_payload_builder::PayloadStore;
+use reth_payload_primitives::{PayloadBuildReport, PayloadTypes};
 use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::RethApiServer;
+use reth_rpc_api::{
+    RethApiServer, RethNodeEventsApiServer, RethPayloadApiServer, RethSyncApiServer,
+};
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_rpc_server_types::result::internal_rpc_err;
-use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_stages_types::{NodeEventRecord, PersistenceStatus, StageId, SyncStatus};
+use reth_storage_api::{
+    BlockNumReader, BlockReaderIdExt, ChangeSetReader, StageCheckpointReader, StateProviderFactory,
+};
 use reth_tasks::TaskSpawner;
-use tokio::sync::oneshot;
+use tokio::sync::{broadcast, oneshot, watch};
 
 /// `reth` API implementation.
 ///
@@ -170,3 +178,232 @@ struct RethApiInner<Provider> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+/// `reth` API implementation for inspecting the payload builder.
+///
+/// This is separate from [`RethApi`] because it requires access to the payload builder service.
+pub struct RethPayloadApi<T: PayloadTypes> {
+    payload_store: PayloadStore<T>,
+}
+
+impl<T: PayloadTypes> RethPayloadApi<T> {
+    /// Create a new instance of the [`RethPayloadApi`]
+    pub const fn new(payload_store: PayloadStore<T>) -> Self {
+        Self { payload_store }
+    }
+}
+
+#[async_trait]
+impl<T: PayloadTypes> RethPayloadApiServer for RethPayloadApi<T> {
+    /// Handler for `reth_getPayloadBuildReport`
+    async fn reth_get_payload_build_report(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<Option<PayloadBuildReport>> {
+        Ok(self.payload_store.build_report(payload_id).await.map(Arc::unwrap_or_clone))
+    }
+}
+
+impl<T: PayloadTypes> std::fmt::Debug for RethPayloadApi<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethPayloadApi").finish_non_exhaustive()
+    }
+}
+
+/// `reth` API implementation for inspecting the sync progress of the node.
+///
+/// Pipeline and backfill progress is reported by the node through a watch channel, while the
+/// persistence of canonical blocks is read from the provider on every update.
+pub struct RethSyncApi<Provider> {
+    inner: Arc<RethSyncApiInner<Provider>>,
+}
+
+impl<Provider> RethSyncApi<Provider> {
+    /// Create a new instance of the [`RethSyncApi`]
+    pub fn new(
+        provider: Provider,
+        sync_status: watch::Receiver<SyncStatus>,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(RethSyncApiInner { provider, sync_status, task_spawner });
+        Self { inner }
+    }
+}
+
+impl<Provider> RethSyncApi<Provider>
+where
+    Provider: BlockNumReader + StageCheckpointReader + 'static,
+{
+    /// Returns the latest sync status, including the persistence progress.
+    pub fn sync_status(&self) -> EthResult<SyncStatus> {
+        let status = self.inner.sync_status.borrow().clone();
+        self.with_persistence(status)
+    }
+
+    /// Fills in the persistence of canonical blocks that are kept in memory.
+    ///
+    /// The `Finish` stage checkpoint is advanced every time blocks are persisted, so it points to
+    /// the last persisted block.
+    fn with_persistence(&self, mut status: SyncStatus) -> EthResult<SyncStatus> {
+        let canonical_head = self.inner.provider.best_block_number()?;
+        let persisted_block = self
+            .inner
+            .provider
+            .get_stage_checkpoint(StageId::Finish)?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default();
+        status.persistence = Some(PersistenceStatus::new(canonical_head, persisted_block));
+
+        Ok(status)
+    }
+}
+
+#[async_trait]
+impl<Provider> RethSyncApiServer for RethSyncApi<Provider>
+where
+    Provider: BlockNumReader + StageCheckpointReader + 'static,
+{
+    /// Handler for `reth_syncStatus`
+    async fn reth_sync_status(&self) -> RpcResult<SyncStatus> {
+        Ok(self.sync_status()?)
+    }
+
+    /// Handler for `reth_subscribeSyncStatus`
+    async fn reth_subscribe_sync_status(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+        let mut updates = self.inner.sync_status.clone();
+        let this = self.clone();
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            // Send the current status right away, then on every update
+            updates.mark_changed();
+            loop {
+                tokio::select! {
+                    _ = sink.closed() => break,
+                    changed = updates.changed() => {
+                        if changed.is_err() {
+                            // the node shut down
+                            break
+                        }
+
+                        let status = updates.borrow_and_update().clone();
+                        let Ok(status) = this.with_persistence(status) else { continue };
+                        let Ok(msg) = SubscriptionMessage::new(
+                            sink.method_name(),
+                            sink.subscription_id(),
+                            &status,
+                        ) else {
+                            break
+                        };
+
+                        if sink.send(msg).await.is_err() {
+                            break
+                        }
+                    }
+                }
+            }
+        }));
+
+        Ok(())
+    }
+}
+
+impl<Provider> std::fmt::Debug for RethSyncApi<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethSyncApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider> Clone for RethSyncApi<Provider> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct RethSyncApiInner<Provider> {
+    /// The provider to read the persisted blocks from.
+    provider: Provider,
+    /// Sync progress reported by the node.
+    sync_status: watch::Receiver<SyncStatus>,
+    /// The type that can spawn the subscription tasks.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+/// `reth` API implementation for following the events of the node.
+pub struct RethNodeEventsApi {
+    inner: Arc<RethNodeEventsApiInner>,
+}
+
+impl RethNodeEventsApi {
+    /// Create a new instance of the [`RethNodeEventsApi`]
+    pub fn new(
+        node_events: broadcast::Sender<NodeEventRecord>,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(RethNodeEventsApiInner { node_events, task_spawner });
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl RethNodeEventsApiServer for RethNodeEventsApi {
+    /// Handler for `reth_subscribeNodeEvents`
+    async fn reth_subscribe_node_events(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+        let mut events = self.inner.node_events.subscribe();
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            loop {
+                tokio::select! {
+                    _ = sink.closed() => break,
+                    event = events.recv() => {
+                        let event = match event {
+                            Ok(event) => event,
+                            // the subscriber is too slow, skip the missed events
+                            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                            // the node shut down
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        };
+
+                        let Ok(msg) = SubscriptionMessage::new(
+                            sink.method_name(),
+                            sink.subscription_id(),
+                            &event,
+                        ) else {
+                            break
+                        };
+
+                        if sink.send(msg).await.is_err() {
+                            break
+                        }
+                    }
+                }
+            }
+        }));
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for RethNodeEventsApi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethNodeEventsApi").finish_non_exhaustive()
+    }
+}
+
+impl Clone for RethNodeEventsApi {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct RethNodeEventsApiInner {
+    /// Events published by the node.
+    node_events: broadcast::Sender<NodeEventRecord>,
+    /// The type that can spawn the subscription tasks.
+    task_spawner: Box<dyn TaskSpawner>,
+}
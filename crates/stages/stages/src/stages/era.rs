@@ -55,18 +55,22 @@ where
     Body: FullBlockBody<OmmerHeader = Header>,
 {
     fn create(self, input: ExecInput) -> Result<ThreadSafeEraStream<Header, Body>, StageError> {
-        match self {
-            Self::Path(path) => Self::convert(
+        match self.location {
+            EraImportLocation::Path(path) => Self::convert(
                 read_dir(path, input.next_block()).map_err(|e| StageError::Fatal(e.into()))?,
+                self.known_roots,
             ),
-            Self::Url(url, folder) => {
+            EraImportLocation::Url(url, folder) => {
                 let _ = reth_fs_util::create_dir_all(&folder);
                 let client = EraClient::new(Client::new(), url, folder);
 
-                Self::convert(EraStream::new(
-                    client,
-                    EraStreamConfig::default().start_from(input.next_block()),
-                ))
+                Self::convert(
+                    EraStream::new(
+                        client,
+                        EraStreamConfig::default().start_from(input.next_block()),
+                    ),
+                    self.known_roots,
+                )
             }
         }
     }
@@ -79,6 +83,7 @@ impl EraImportSource {
             + Sync
             + 'static
             + Unpin,
+        known_roots: Option<&'static [era::KnownRoot]>,
     ) -> Result<ThreadSafeEraStream<Header, Body>, StageError>
     where
         Header: FullBlockHeader + Value,
@@ -86,6 +91,8 @@ impl EraImportSource {
     {
         Ok(Box::new(Box::pin(stream.map(|meta| {
             meta.and_then(|meta| {
+                era::verify(&meta, known_roots)?;
+
                 let file = reth_fs_util::open(meta.path())?;
                 let reader = Era1Reader::new(file);
                 let iter = reader.iter();
@@ -227,9 +234,20 @@ where
     }
 }
 
+/// Describes where to get the era files from and how to verify them.
+#[derive(Debug, Clone)]
+pub struct EraImportSource {
+    /// Where the files are read from.
+    pub location: EraImportLocation,
+    /// The known accumulator roots of all epochs that every file is verified against, if any.
+    ///
+    /// See [`era::known_roots`] for the roots of mainnet and sepolia.
+    pub known_roots: Option<&'static [era::KnownRoot]>,
+}
+
 /// Describes where to get the era files from.
 #[derive(Debug, Clone)]
-pub enum EraImportSource {
+pub enum EraImportLocation {
     /// Remote HTTP accessible host.
     Url(Url, Box<Path>),
     /// Local directory.
@@ -254,7 +272,16 @@ impl EraImportSource {
         default: impl FnOnce() -> Option<Url>,
         folder: impl FnOnce() -> Box<Path>,
     ) -> Option<Self> {
-        path.map(Self::Path).or_else(|| url.or_else(default).map(|url| Self::Url(url, folder())))
+        let location = path
+            .map(EraImportLocation::Path)
+            .or_else(|| url.or_else(default).map(|url| EraImportLocation::Url(url, folder())))?;
+        Some(Self { location, known_roots: None })
+    }
+
+    /// Verifies every file against the `known_roots` of all epochs.
+    pub const fn with_known_roots(mut self, known_roots: Option<&'static [era::KnownRoot]>) -> Self {
+        self.known_roots = known_roots;
+        self
     }
 }
 
@@ -56,6 +56,10 @@ use super::missing_static_data_error;
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
 ///
+/// Unless the account or storage history is pruned, changesets are written to the
+/// [`StaticFileSegment::AccountChangeSets`] and [`StaticFileSegment::StorageChangeSets`] static
+/// files instead.
+///
 /// For unwinds we are accessing:
 /// - [`tables::BlockBodyIndices`] get tx index to know what needs to be unwinded
 /// - [`tables::AccountsHistory`] to remove change set and apply old values to
@@ -173,6 +177,9 @@ where
 
     /// Performs consistency check on static files.
     ///
+    /// Changesets above the checkpoint in static files are pruned, since they're written again
+    /// during execution.
+    ///
     /// This function compares the highest receipt number recorded in the database with that in the
     /// static file to detect any discrepancies due to unexpected shutdowns or database rollbacks.
     /// **If the height in the static file is higher**, it rolls back (unwinds) the static file.
@@ -188,6 +195,23 @@ where
     where
         Provider: StaticFileProviderFactory + DBProvider + BlockReader + HeaderProvider,
     {
+        let static_file_provider = provider.static_file_provider();
+
+        // Changesets of blocks above the checkpoint may have been committed to static files, but
+        // NOT to the database. They're written again when executing these blocks, so we prune
+        // them straight away.
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            if let Some(highest) = static_file_provider
+                .get_highest_static_file_block(segment)
+                .filter(|highest| *highest > checkpoint)
+            {
+                let mut static_file_producer = static_file_provider.latest_writer(segment)?;
+                static_file_producer.prune_changesets(highest - checkpoint)?;
+                static_file_producer.commit()?;
+            }
+        }
+
         // If there's any receipts pruning configured, receipts are written directly to database and
         // inconsistencies are expected.
         if provider.prune_modes_ref().has_receipts_pruning() {
@@ -198,8 +222,6 @@ where
         let next_receipt_num =
             provider.block_body_indices(checkpoint)?.map(|b| b.next_tx_num()).unwrap_or(0);
 
-        let static_file_provider = provider.static_file_provider();
-
         // Get next expected receipt number in static files
         let next_static_file_receipt_num = static_file_provider
             .get_highest_static_file_tx(StaticFileSegment::Receipts)
@@ -0,0 +1,174 @@
+use alloy_consensus::TxReceipt;
+use alloy_primitives::BlockNumber;
+use reth_config::config::IndexLogsConfig;
+use reth_provider::{DBProvider, LogIndexWriter, PruneCheckpointReader, ReceiptProvider};
+use reth_prune_types::PruneSegment;
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{fmt::Debug, ops::RangeInclusive};
+use tracing::info;
+
+/// Stage that indexes the addresses and topics of the logs in the receipts generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage]. For more information on the index take a
+/// look at [`tables::LogAddressIndex`][reth_db_api::tables::LogAddressIndex] and
+/// [`tables::LogTopicIndex`][reth_db_api::tables::LogTopicIndex].
+///
+/// The stage is optional and only part of the pipeline if the log index is enabled.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(config: IndexLogsConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold }
+    }
+
+    /// Returns the part of the range for which receipts are still available.
+    fn unpruned_range<Provider: PruneCheckpointReader>(
+        provider: &Provider,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Option<RangeInclusive<BlockNumber>>, StageError> {
+        let mut start = *range.start();
+        if let Some(pruned) = provider
+            .get_prune_checkpoint(PruneSegment::Receipts)?
+            .and_then(|checkpoint| checkpoint.block_number)
+        {
+            start = start.max(pruned + 1);
+        }
+        Ok((start <= *range.end()).then(|| start..=*range.end()))
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 10_000 }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogsStage
+where
+    Provider: DBProvider + ReceiptProvider + LogIndexWriter + PruneCheckpointReader,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        if let Some(range) = Self::unpruned_range(provider, range.clone())? {
+            info!(target: "sync::stages::index_logs::exec", ?range, "Indexing logs");
+            let receipts = provider.receipts_by_block_range(range.clone())?;
+            provider.insert_log_indices(
+                range.zip(receipts.iter().map(|receipts| receipts.iter().flat_map(|r| r.logs()))),
+            )?;
+        }
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        if let Some(range) = Self::unpruned_range(provider, range)? {
+            let first_block = *range.start();
+            let receipts = provider.receipts_by_block_range(range)?;
+            provider.unwind_log_indices(
+                first_block,
+                receipts.iter().flatten().flat_map(|receipt| receipt.logs()),
+            )?;
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::{address, Address, Bytes, Log, B256};
+    use reth_provider::{DatabaseProviderFactory, LogIndexReader, StageCheckpointWriter};
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_receipt, BlockRangeParams,
+    };
+
+    const ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=10,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..2, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        // Only the receipt of block 5 contains a log emitted by `ADDRESS`.
+        let topic = B256::random();
+        let mut tx_num = 0;
+        let receipts = blocks.iter().map(|block| {
+            let receipts = block
+                .body()
+                .transactions
+                .iter()
+                .map(|transaction| {
+                    let mut receipt = random_receipt(&mut rng, transaction, Some(0));
+                    if block.number == 5 {
+                        receipt.logs.push(Log::new_unchecked(ADDRESS, vec![topic], Bytes::new()));
+                    }
+                    tx_num += 1;
+                    (tx_num - 1, receipt)
+                })
+                .collect::<Vec<_>>();
+            (block.number, receipts)
+        });
+        db.insert_receipts_by_block(receipts.collect::<Vec<_>>(), StorageKind::Static).unwrap();
+
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input = ExecInput { target: Some(10), checkpoint: Some(StageCheckpoint::new(0)) };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(10), done: true });
+        provider.save_stage_checkpoint(StageId::IndexLogs, out.checkpoint).unwrap();
+
+        let matches = provider.log_index_blocks(0..=10, &[ADDRESS], &[]).unwrap().unwrap();
+        assert_eq!(matches.indexed_to, 10);
+        assert_eq!(matches.blocks, vec![5]);
+        let matches = provider.log_index_blocks(0..=10, &[], &[vec![topic]]).unwrap().unwrap();
+        assert_eq!(matches.blocks, vec![5]);
+        let matches =
+            provider.log_index_blocks(0..=10, &[ADDRESS], &[vec![B256::random()]]).unwrap();
+        assert_eq!(matches.unwrap().blocks, Vec::<BlockNumber>::new());
+
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(10), unwind_to: 4, bad_block: None };
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(4) });
+        provider.save_stage_checkpoint(StageId::IndexLogs, out.checkpoint).unwrap();
+
+        let matches = provider.log_index_blocks(0..=10, &[ADDRESS], &[]).unwrap().unwrap();
+        assert_eq!(matches.indexed_to, 4);
+        assert!(matches.blocks.is_empty());
+    }
+}
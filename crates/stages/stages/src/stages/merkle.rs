@@ -8,16 +8,19 @@ use reth_db_api::{
 };
 use reth_primitives_traits::{GotExpected, SealedHeader};
 use reth_provider::{
-    DBProvider, HeaderProvider, ProviderError, StageCheckpointReader, StageCheckpointWriter,
-    StatsReader, TrieWriter,
+    AccountExtReader, DBProvider, HeaderProvider, ProviderError, ProviderResult,
+    StageCheckpointReader, StageCheckpointWriter, StatsReader, StorageReader, TrieWriter,
 };
 use reth_stages_api::{
     BlockErrorKind, EntitiesCheckpoint, ExecInput, ExecOutput, MerkleCheckpoint, Stage,
     StageCheckpoint, StageError, StageId, StorageRootMerkleCheckpoint, UnwindInput, UnwindOutput,
 };
-use reth_trie::{IntermediateStateRootState, StateRoot, StateRootProgress, StoredSubNode};
-use reth_trie_db::DatabaseStateRoot;
-use std::fmt::Debug;
+use reth_trie::{
+    prefix_set::TriePrefixSets, IntermediateStateRootState, KeccakKeyHasher, StateRoot,
+    StateRootProgress, StoredSubNode,
+};
+use reth_trie_db::{DatabaseStateRoot, PrefixSetLoader};
+use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::*;
 
 // TODO: automate the process outlined below so the user can just send in a debugging package
@@ -158,6 +161,8 @@ where
         + TrieWriter
         + StatsReader
         + HeaderProvider
+        + AccountExtReader
+        + StorageReader
         + StageCheckpointReader
         + StageCheckpointWriter,
 {
@@ -196,6 +201,12 @@ where
             .ok_or_else(|| ProviderError::HeaderNotFound(to_block.into()))?;
         let target_block_root = target_block.state_root();
 
+        // The trie is updated for the whole range at once, so the trie changesets of the blocks
+        // in the range can't be written.
+        if !range.is_empty() {
+            provider.invalidate_trie_changesets(to_block)?;
+        }
+
         let mut checkpoint = self.get_execution_checkpoint(provider)?;
         let (trie_root, entities_checkpoint) = if range.is_empty() {
             (target_block_root, input.checkpoint().entities_stage_checkpoint().unwrap_or_default())
@@ -310,8 +321,10 @@ where
                     chunk_range = ?chunk_range,
                     "Processing chunk"
                 );
-                let (root, updates) =
-                StateRoot::incremental_root_with_updates(provider.tx_ref(), chunk_range)
+                let prefix_sets = load_prefix_sets(provider, chunk_range)?;
+                let (root, updates) = StateRoot::from_tx(provider.tx_ref())
+                    .with_prefix_sets(prefix_sets)
+                    .root_with_updates()
                     .map_err(|e| {
                         error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
                         StageError::Fatal(Box::new(e))
@@ -372,6 +385,8 @@ where
                     tx.entries::<tables::HashedStorages>()?) as u64,
             });
 
+        provider.unwind_trie_changesets(input.unwind_to)?;
+
         if input.unwind_to == 0 {
             tx.clear::<tables::AccountsTrie>()?;
             tx.clear::<tables::StoragesTrie>()?;
@@ -388,7 +403,9 @@ where
         if range.is_empty() {
             info!(target: "sync::stages::merkle::unwind", "Nothing to unwind");
         } else {
-            let (block_root, updates) = StateRoot::incremental_root_with_updates(tx, range)
+            let (block_root, updates) = StateRoot::from_tx(tx)
+                .with_prefix_sets(load_prefix_sets(provider, range)?)
+                .root_with_updates()
                 .map_err(|e| StageError::Fatal(Box::new(e)))?;
 
             // Validate the calculated state root
@@ -408,6 +425,23 @@ where
     }
 }
 
+/// Loads the trie prefix sets of the accounts and storage slots changed in the given block range.
+///
+/// Unlike [`StateRoot::incremental_root_with_updates`], this reads the changesets through the
+/// provider, which includes the changesets that were moved to static files.
+fn load_prefix_sets<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<TriePrefixSets>
+where
+    Provider: DBProvider + AccountExtReader + StorageReader,
+{
+    let changed_accounts = provider.changed_accounts_with_range(range.clone())?;
+    let changed_storages = provider.changed_storages_with_range(range)?;
+    Ok(PrefixSetLoader::<_, KeccakKeyHasher>::new(provider.tx_ref())
+        .load_changed(changed_accounts, changed_storages)?)
+}
+
 /// Check that the computed state root matches the root in the expected header.
 #[inline]
 fn validate_state_root<H: BlockHeader + Sealable + Debug>(
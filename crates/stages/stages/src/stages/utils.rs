@@ -3,27 +3,84 @@ use alloy_primitives::{BlockNumber, TxNumber};
 use reth_config::config::EtlConfig;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
-    models::sharded_key::NUM_OF_INDICES_IN_SHARD,
+    models::{sharded_key::NUM_OF_INDICES_IN_SHARD, BlockNumberAddress},
     table::{Decompress, Table},
+    tables,
     transaction::{DbTx, DbTxMut},
     BlockNumberList, DatabaseError,
 };
 use reth_etl::Collector;
+use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError,
+    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError, ProviderResult,
     StaticFileProviderFactory,
 };
 use reth_stages_api::StageError;
 use reth_static_file_types::StaticFileSegment;
-use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Range, RangeBounds, RangeInclusive},
+};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
 const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 
+/// Number of blocks of which changesets are read from static files at once.
+const STATIC_FILE_CHANGESETS_CHUNK: u64 = 1_000;
+
+/// A changeset table whose changesets may have been moved to static files.
+pub(crate) trait ChangeSetTable: Table {
+    /// Static file segment holding the changesets of the table.
+    const SEGMENT: StaticFileSegment;
+
+    /// Returns the range of table keys that covers the given block range.
+    fn key_range(range: RangeInclusive<BlockNumber>) -> impl RangeBounds<Self::Key>;
+
+    /// Returns the changesets of the given block range from static files.
+    fn static_file_changesets<N: NodePrimitives>(
+        static_file_provider: &StaticFileProvider<N>,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(Self::Key, Self::Value)>>;
+}
+
+impl ChangeSetTable for tables::AccountChangeSets {
+    const SEGMENT: StaticFileSegment = StaticFileSegment::AccountChangeSets;
+
+    fn key_range(range: RangeInclusive<BlockNumber>) -> impl RangeBounds<Self::Key> {
+        range
+    }
+
+    fn static_file_changesets<N: NodePrimitives>(
+        static_file_provider: &StaticFileProvider<N>,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(Self::Key, Self::Value)>> {
+        static_file_provider.account_changesets_range(range)
+    }
+}
+
+impl ChangeSetTable for tables::StorageChangeSets {
+    const SEGMENT: StaticFileSegment = StaticFileSegment::StorageChangeSets;
+
+    fn key_range(range: RangeInclusive<BlockNumber>) -> impl RangeBounds<Self::Key> {
+        BlockNumberAddress::range(range)
+    }
+
+    fn static_file_changesets<N: NodePrimitives>(
+        static_file_provider: &StaticFileProvider<N>,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(Self::Key, Self::Value)>> {
+        static_file_provider.storage_changesets_range(range)
+    }
+}
+
 /// Collects all history (`H`) indices for a range of changesets (`CS`) and stores them in a
 /// [`Collector`].
 ///
+/// Changesets that were moved to static files are read from there first, followed by the ones in
+/// the database.
+///
 /// ## Process
 /// The function utilizes a `HashMap` cache with a structure of `PartialKey` (`P`) (Address or
 /// Address.StorageKey) to `BlockNumberList`. When the cache exceeds its capacity, its contents are
@@ -40,14 +97,14 @@ const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 /// `(Address1.300, [100,300])`. The entries may be stored across one or more files.
 pub(crate) fn collect_history_indices<Provider, CS, H, P>(
     provider: &Provider,
-    range: impl RangeBounds<CS::Key>,
+    range: RangeInclusive<BlockNumber>,
     sharded_key_factory: impl Fn(P, BlockNumber) -> H::Key,
     partial_key_factory: impl Fn((CS::Key, CS::Value)) -> (u64, P),
     etl_config: &EtlConfig,
 ) -> Result<Collector<H::Key, H::Value>, StageError>
 where
-    Provider: DBProvider,
-    CS: Table,
+    Provider: DBProvider + StaticFileProviderFactory,
+    CS: ChangeSetTable,
     H: Table<Value = BlockNumberList>,
     P: Copy + Eq + Hash,
 {
@@ -67,20 +124,12 @@ where
         Ok::<(), StageError>(())
     };
 
-    // observability
-    let total_changesets = provider.tx_ref().entries::<CS>()?;
-    let interval = (total_changesets / 1000).max(1);
-
     let mut flush_counter = 0;
     let mut current_block_number = u64::MAX;
-    for (idx, entry) in changeset_cursor.walk_range(range)?.enumerate() {
-        let (block_number, key) = partial_key_factory(entry?);
+    let mut insert = |entry: (CS::Key, CS::Value)| {
+        let (block_number, key) = partial_key_factory(entry);
         cache.entry(key).or_default().push(block_number);
 
-        if idx > 0 && idx % interval == 0 && total_changesets > 1000 {
-            info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", (idx as f64 / total_changesets as f64) * 100.0), "Collecting indices");
-        }
-
         // Make sure we only flush the cache every DEFAULT_CACHE_THRESHOLD blocks.
         if current_block_number != block_number {
             current_block_number = block_number;
@@ -91,6 +140,38 @@ where
                 flush_counter = 0;
             }
         }
+        Ok::<(), StageError>(())
+    };
+
+    // Changesets in static files belong to lower blocks than the ones in the database.
+    let (mut start, end) = range.into_inner();
+    let static_file_provider = provider.static_file_provider();
+    if let Some(highest) = static_file_provider
+        .get_highest_static_file_block(CS::SEGMENT)
+        .filter(|highest| *highest >= start)
+    {
+        let static_file_end = highest.min(end) + 1;
+        for chunk_start in (start..static_file_end).step_by(STATIC_FILE_CHANGESETS_CHUNK as usize) {
+            let chunk_end = (chunk_start + STATIC_FILE_CHANGESETS_CHUNK).min(static_file_end);
+            info!(target: "sync::stages::index_history", from = chunk_start, to = chunk_end - 1, "Collecting indices from static files");
+            for entry in CS::static_file_changesets(&static_file_provider, chunk_start..chunk_end)?
+            {
+                insert(entry)?;
+            }
+        }
+        start = static_file_end;
+    }
+
+    // observability
+    let total_changesets = provider.tx_ref().entries::<CS>()?;
+    let interval = (total_changesets / 1000).max(1);
+
+    for (idx, entry) in changeset_cursor.walk_range(CS::key_range(start..=end))?.enumerate() {
+        insert(entry?)?;
+
+        if idx > 0 && idx % interval == 0 && total_changesets > 1000 {
+            info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", (idx as f64 / total_changesets as f64) * 100.0), "Collecting indices");
+        }
     }
     collect(&cache)?;
 
@@ -25,6 +25,12 @@ pub use checkpoints::{
 mod execution;
 pub use execution::*;
 
+mod node_events;
+pub use node_events::{NodeEventKind, NodeEventRecord, PrunedSegmentRecord, StaticFileTargetRecord};
+
+mod sync_status;
+pub use sync_status::{BackfillSyncStatus, PersistenceStatus, StageSyncStatus, SyncStatus};
+
 /// Direction and target block for pipeline operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PipelineTarget {
@@ -0,0 +1,158 @@
+use crate::EntitiesCheckpoint;
+use alloc::{string::String, vec::Vec};
+use alloy_primitives::{BlockHash, BlockNumber};
+
+/// An event emitted by a component of the node, in a form that can be exported to other
+/// processes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeEventRecord {
+    /// Unix timestamp in milliseconds at which the event was emitted.
+    pub timestamp: u64,
+    /// The event.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub event: NodeEventKind,
+}
+
+/// The kinds of [`NodeEventRecord`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")
+)]
+pub enum NodeEventKind {
+    /// The pipeline started executing a stage.
+    StageStarted {
+        /// Name of the stage, see [`StageId`](crate::StageId).
+        stage: String,
+        /// 1-indexed position of the stage in the pipeline.
+        pipeline_stage: usize,
+        /// Total number of stages in the pipeline.
+        pipeline_stages: usize,
+        /// Block number the stage starts from.
+        checkpoint: BlockNumber,
+        /// Block number the stage is syncing to, if known.
+        target: Option<BlockNumber>,
+    },
+    /// A stage committed progress without reaching its target.
+    StageProgress {
+        /// Name of the stage.
+        stage: String,
+        /// Block number the stage has reached.
+        checkpoint: BlockNumber,
+        /// Entities processed by the stage out of the total, if the stage reports them.
+        entities: Option<EntitiesCheckpoint>,
+    },
+    /// A stage reached its target.
+    StageFinished {
+        /// Name of the stage.
+        stage: String,
+        /// Block number the stage has reached.
+        checkpoint: BlockNumber,
+        /// Entities processed by the stage out of the total, if the stage reports them.
+        entities: Option<EntitiesCheckpoint>,
+    },
+    /// The pipeline started unwinding a stage.
+    StageUnwindStarted {
+        /// Name of the stage.
+        stage: String,
+        /// Block number the stage unwinds from.
+        checkpoint: BlockNumber,
+        /// Block number the stage unwinds to.
+        unwind_to: BlockNumber,
+        /// The bad block that caused the unwind, if any.
+        bad_block: Option<BlockNumber>,
+    },
+    /// A stage finished unwinding.
+    StageUnwound {
+        /// Name of the stage.
+        stage: String,
+        /// Block number the stage was unwound to.
+        checkpoint: BlockNumber,
+    },
+    /// A stage failed.
+    StageError {
+        /// Name of the stage.
+        stage: String,
+    },
+    /// The consensus engine processed a forkchoice update.
+    ForkchoiceUpdated {
+        /// Hash of the head block.
+        head: BlockHash,
+        /// Hash of the safe block.
+        safe: BlockHash,
+        /// Hash of the finalized block.
+        finalized: BlockHash,
+        /// Status of the update, one of `valid`, `invalid` or `syncing`.
+        status: String,
+    },
+    /// The consensus engine committed a new canonical head.
+    CanonicalChainCommitted {
+        /// Number of the new head block.
+        number: BlockNumber,
+        /// Hash of the new head block.
+        hash: BlockHash,
+        /// Time it took to make the chain canonical, in milliseconds.
+        elapsed_ms: u64,
+    },
+    /// The consensus engine encountered an invalid block.
+    InvalidBlock {
+        /// Number of the invalid block.
+        number: BlockNumber,
+        /// Hash of the invalid block.
+        hash: BlockHash,
+    },
+    /// The pruner started running.
+    PrunerStarted {
+        /// The tip block number before pruning.
+        tip_block_number: BlockNumber,
+    },
+    /// The pruner finished running.
+    PrunerFinished {
+        /// The tip block number before pruning.
+        tip_block_number: BlockNumber,
+        /// Time the run took, in milliseconds.
+        elapsed_ms: u64,
+        /// The segments that were pruned.
+        segments: Vec<PrunedSegmentRecord>,
+    },
+    /// The static file producer started moving data to static files.
+    StaticFileProducerStarted {
+        /// The block ranges that are moved, per segment.
+        targets: Vec<StaticFileTargetRecord>,
+    },
+    /// The static file producer finished moving data to static files.
+    StaticFileProducerFinished {
+        /// The block ranges that were moved, per segment.
+        targets: Vec<StaticFileTargetRecord>,
+        /// Time the run took, in milliseconds.
+        elapsed_ms: u64,
+    },
+}
+
+/// A segment pruned during a pruner run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PrunedSegmentRecord {
+    /// Name of the segment.
+    pub segment: String,
+    /// Number of pruned entries.
+    pub pruned: usize,
+    /// Whether the segment has no more data to prune.
+    pub finished: bool,
+}
+
+/// A block range moved to static files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StaticFileTargetRecord {
+    /// Name of the static file segment.
+    pub segment: String,
+    /// First block of the range.
+    pub from: BlockNumber,
+    /// Last block of the range, inclusive.
+    pub to: BlockNumber,
+}
@@ -0,0 +1,78 @@
+use crate::EntitiesCheckpoint;
+use alloc::{string::String, vec::Vec};
+use alloy_primitives::BlockNumber;
+
+/// Detailed sync progress of the node.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SyncStatus {
+    /// Progress of the pipeline stages in pipeline order, starting from their checkpoints in the
+    /// database when the node launched.
+    pub stages: Vec<StageSyncStatus>,
+    /// Name of the stage the pipeline is currently executing, if any.
+    pub current_stage: Option<String>,
+    /// Backfill sync of the engine, if one is in progress.
+    pub backfill: Option<BackfillSyncStatus>,
+    /// Persistence of canonical blocks to disk, if known.
+    pub persistence: Option<PersistenceStatus>,
+    /// Unix timestamp in seconds at which the status was last updated.
+    pub updated_at: u64,
+}
+
+/// Sync progress of a single pipeline stage.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StageSyncStatus {
+    /// Name of the stage, see [`StageId`](crate::StageId).
+    pub stage: String,
+    /// Block number the stage has reached.
+    pub checkpoint: BlockNumber,
+    /// Block number the stage is syncing to, if known.
+    pub target: Option<BlockNumber>,
+    /// Entities processed by the stage out of the total, if the stage reports them.
+    pub entities: Option<EntitiesCheckpoint>,
+    /// Entities processed per second between the last two checkpoints.
+    pub throughput: Option<f64>,
+    /// Estimated number of seconds until the stage finishes, as of
+    /// [`SyncStatus::updated_at`].
+    pub eta: Option<u64>,
+    /// Whether the stage has reached its target.
+    pub done: bool,
+}
+
+/// Progress of the engine backfill sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BackfillSyncStatus {
+    /// Unix timestamp in seconds at which the backfill sync started.
+    pub started_at: u64,
+    /// Block number the backfill sync is syncing to, once the pipeline reported it.
+    pub target: Option<BlockNumber>,
+}
+
+/// Persistence of canonical blocks that are kept in memory by the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PersistenceStatus {
+    /// Number of the canonical head block.
+    pub canonical_head: BlockNumber,
+    /// Number of the last block persisted to disk.
+    pub persisted_block: BlockNumber,
+    /// Number of canonical blocks that are not persisted yet.
+    pub lag: u64,
+}
+
+impl PersistenceStatus {
+    /// Creates a new persistence status from the canonical head and the last persisted block.
+    pub const fn new(canonical_head: BlockNumber, persisted_block: BlockNumber) -> Self {
+        Self {
+            canonical_head,
+            persisted_block,
+            lag: canonical_head.saturating_sub(persisted_block),
+        }
+    }
+}
@@ -188,6 +188,8 @@ where
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
             block_meta: stages_checkpoints[2],
+            account_changesets: stages_checkpoints[1],
+            storage_changesets: stages_checkpoints[1],
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
         self.run(targets)?;
@@ -327,6 +329,8 @@ mod tests {
                 receipts: Some(1),
                 transactions: Some(1),
                 block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -345,7 +349,9 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
-                block_meta: None
+                block_meta: None,
+                account_changesets: None,
+                storage_changesets: None
             }
         );
 
@@ -355,6 +361,8 @@ mod tests {
                 receipts: Some(3),
                 transactions: Some(3),
                 block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -373,7 +381,9 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
-                block_meta: None
+                block_meta: None,
+                account_changesets: None,
+                storage_changesets: None
             }
         );
 
@@ -383,6 +393,8 @@ mod tests {
                 receipts: Some(4),
                 transactions: Some(4),
                 block_meta: None,
+                account_changesets: None,
+                storage_changesets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -404,7 +416,9 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
-                block_meta: None
+                block_meta: None,
+                account_changesets: None,
+                storage_changesets: None
             }
         );
     }
@@ -434,6 +448,8 @@ mod tests {
                         receipts: Some(1),
                         transactions: Some(1),
                         block_meta: None,
+                        account_changesets: None,
+                        storage_changesets: None,
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
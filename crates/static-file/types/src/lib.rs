@@ -39,6 +39,12 @@ pub struct HighestStaticFiles {
     /// Highest static file block of transactions, inclusive.
     /// If [`None`], no static file is available.
     pub block_meta: Option<BlockNumber>,
+    /// Highest static file block of account changesets, inclusive.
+    /// If [`None`], no static file is available.
+    pub account_changesets: Option<BlockNumber>,
+    /// Highest static file block of storage changesets, inclusive.
+    /// If [`None`], no static file is available.
+    pub storage_changesets: Option<BlockNumber>,
 }
 
 impl HighestStaticFiles {
@@ -49,6 +55,8 @@ impl HighestStaticFiles {
             StaticFileSegment::Transactions => self.transactions,
             StaticFileSegment::Receipts => self.receipts,
             StaticFileSegment::BlockMeta => self.block_meta,
+            StaticFileSegment::AccountChangeSets => self.account_changesets,
+            StaticFileSegment::StorageChangeSets => self.storage_changesets,
         }
     }
 
@@ -59,12 +67,22 @@ impl HighestStaticFiles {
             StaticFileSegment::Transactions => &mut self.transactions,
             StaticFileSegment::Receipts => &mut self.receipts,
             StaticFileSegment::BlockMeta => &mut self.block_meta,
+            StaticFileSegment::AccountChangeSets => &mut self.account_changesets,
+            StaticFileSegment::StorageChangeSets => &mut self.storage_changesets,
         }
     }
 
     /// Returns an iterator over all static file segments
     fn iter(&self) -> impl Iterator<Item = Option<BlockNumber>> {
-        [self.headers, self.transactions, self.receipts, self.block_meta].into_iter()
+        [
+            self.headers,
+            self.transactions,
+            self.receipts,
+            self.block_meta,
+            self.account_changesets,
+            self.storage_changesets,
+        ]
+        .into_iter()
     }
 
     /// Returns the minimum block of all segments.
@@ -141,6 +159,8 @@ mod tests {
             receipts: Some(200),
             transactions: None,
             block_meta: None,
+            account_changesets: None,
+            storage_changesets: None,
         };
 
         // Test for headers segment
@@ -181,6 +201,8 @@ mod tests {
             receipts: Some(100),
             transactions: None,
             block_meta: None,
+            account_changesets: None,
+            storage_changesets: None,
         };
 
         // Minimum value among the available segments
@@ -198,6 +220,8 @@ mod tests {
             receipts: Some(100),
             transactions: Some(500),
             block_meta: Some(500),
+            account_changesets: None,
+            storage_changesets: None,
         };
 
         // Maximum value among the available segments
@@ -41,6 +41,16 @@ pub enum StaticFileSegment {
     /// Static File segment responsible for the `BlockBodyIndices`, `BlockOmmers`,
     /// `BlockWithdrawals` tables.
     BlockMeta,
+    #[strum(serialize = "accountchangesets")]
+    /// Static File segment responsible for the `AccountChangeSets` table.
+    ///
+    /// Each row holds all account changes of a block.
+    AccountChangeSets,
+    #[strum(serialize = "storagechangesets")]
+    /// Static File segment responsible for the `StorageChangeSets` table.
+    ///
+    /// Each row holds all storage changes of a block.
+    StorageChangeSets,
 }
 
 impl StaticFileSegment {
@@ -51,6 +61,8 @@ impl StaticFileSegment {
             Self::Transactions => "transactions",
             Self::Receipts => "receipts",
             Self::BlockMeta => "blockmeta",
+            Self::AccountChangeSets => "accountchangesets",
+            Self::StorageChangeSets => "storagechangesets",
         }
     }
 
@@ -58,7 +70,15 @@ impl StaticFileSegment {
     pub fn iter() -> impl Iterator<Item = Self> {
         // The order of segments is significant and must be maintained to ensure correctness. For
         // example, Transactions require BlockBodyIndices from Blockmeta to be sound.
-        [Self::Headers, Self::BlockMeta, Self::Transactions, Self::Receipts].into_iter()
+        [
+            Self::Headers,
+            Self::BlockMeta,
+            Self::Transactions,
+            Self::Receipts,
+            Self::AccountChangeSets,
+            Self::StorageChangeSets,
+        ]
+        .into_iter()
     }
 
     /// Returns the default configuration of the segment.
@@ -70,7 +90,25 @@ impl StaticFileSegment {
     pub const fn columns(&self) -> usize {
         match self {
             Self::Headers | Self::BlockMeta => 3,
-            Self::Transactions | Self::Receipts => 1,
+            Self::Transactions |
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets => 1,
+        }
+    }
+
+    /// Returns the number of rows covered by each integrity checksum of the segment.
+    ///
+    /// Segments with one row per block are checksummed in smaller chunks than those with one row
+    /// per transaction or changeset entry, so that a corrupted chunk spans a similar number of
+    /// blocks.
+    pub const fn rows_per_checksum(&self) -> u64 {
+        match self {
+            Self::Headers | Self::BlockMeta => 1_000,
+            Self::Transactions |
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets => 10_000,
         }
     }
 
@@ -143,6 +181,12 @@ impl StaticFileSegment {
         matches!(self, Self::Receipts)
     }
 
+    /// Returns `true` if the segment is `StaticFileSegment::AccountChangeSets` or
+    /// `StaticFileSegment::StorageChangeSets`.
+    pub const fn is_changesets(&self) -> bool {
+        matches!(self, Self::AccountChangeSets | Self::StorageChangeSets)
+    }
+
     /// Returns `true` if a segment row is linked to a transaction.
     pub const fn is_tx_based(&self) -> bool {
         matches!(self, Self::Receipts | Self::Transactions)
@@ -150,7 +194,10 @@ impl StaticFileSegment {
 
     /// Returns `true` if a segment row is linked to a block.
     pub const fn is_block_based(&self) -> bool {
-        matches!(self, Self::Headers | Self::BlockMeta)
+        matches!(
+            self,
+            Self::Headers | Self::BlockMeta | Self::AccountChangeSets | Self::StorageChangeSets
+        )
     }
 }
 
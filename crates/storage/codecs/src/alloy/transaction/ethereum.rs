@@ -140,7 +140,7 @@ impl<T: Envelope + ToTxCompact + Transaction + Send + Sync> CompactEnvelope for
         buf.put_u8(0);
 
         let sig_bit = self.signature().to_compact(buf) as u8;
-        let zstd_bit = self.input().len() >= 32;
+        let zstd_bit = self.input().len() >= 32 && reth_zstd_compressors::is_compression_enabled();
 
         let tx_bits = if zstd_bit {
             // compress the tx prefixed with txtype
@@ -18,7 +18,7 @@ pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 
 use crate::{
     models::{
-        accounts::BlockNumberAddress,
+        accounts::{BlockNumberAddress, BlockNumberHashedAddress},
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, ClientVersion, CompactU256, IntegerList, ShardedKey,
@@ -32,7 +32,9 @@ use reth_ethereum_primitives::{Receipt, TransactionSigned};
 use reth_primitives_traits::{Account, Bytecode, StorageEntry};
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::StageCheckpoint;
-use reth_trie_common::{BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey};
+use reth_trie_common::{
+    BranchNodeCompact, StorageTrieEntry, StoredNibbles, StoredNibblesSubKey, TrieChangeSetsEntry,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -523,6 +525,50 @@ tables! {
         type Key = ChainStateKey;
         type Value = BlockNumber;
     }
+
+    /// Stores the last finished height reported by each `ExEx`, saved in batches by the `ExEx`
+    /// manager.
+    table ExExBackfillCheckpoints {
+        type Key = ExExId;
+        type Value = BlockNumber;
+    }
+
+    /// Stores pointers to the blocks with logs emitted by each address.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], the last shard of an address is keyed with
+    /// `u64::MAX`. Only populated if the log index is enabled.
+    table LogAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks with logs containing each topic, regardless of the position
+    /// of the topic in the log.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], the last shard of a topic is keyed with
+    /// `u64::MAX`. Only populated if the log index is enabled.
+    table LogTopicIndex {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores the account trie nodes before a certain block changed them.
+    ///
+    /// If [`TrieChangeSetsEntry::node`] is `None`, the node didn't exist before the block.
+    table AccountsTrieChangeSets {
+        type Key = BlockNumber;
+        type Value = TrieChangeSetsEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
+
+    /// Stores the storage trie nodes of an account before a certain block changed them.
+    ///
+    /// If [`TrieChangeSetsEntry::node`] is `None`, the node didn't exist before the block.
+    table StoragesTrieChangeSets {
+        type Key = BlockNumberHashedAddress;
+        type Value = TrieChangeSetsEntry;
+        type SubKey = StoredNibblesSubKey;
+    }
 }
 
 /// Keys for the `ChainState` table.
@@ -563,6 +609,9 @@ pub type BlockNumberList = IntegerList;
 /// Encoded stage id.
 pub type StageId = String;
 
+/// Encoded `ExEx` id.
+pub type ExExId = String;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,11 +253,10 @@ where
         Vec::new(),
     );
 
-    provider.write_state(
-        &execution_outcome,
-        OriginalValuesKnown::Yes,
-        StorageLocation::Database,
-    )?;
+    // There are no receipts to write, but this allows the changesets of the genesis block to be
+    // written to static files, so that the following blocks can continue them.
+    let write_to = if block == 0 { StorageLocation::StaticFiles } else { StorageLocation::Database };
+    provider.write_state(&execution_outcome, OriginalValuesKnown::Yes, write_to)?;
 
     trace!(target: "reth::cli", "Inserted state");
 
@@ -0,0 +1,97 @@
+use crate::AccountBeforeTx;
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, U256};
+use reth_primitives_traits::StorageEntry;
+
+/// The account changes of a block as stored in the `AccountChangeSets` static file segment.
+///
+/// Changes are sorted by [`Address`].
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StaticFileAccountChangeSet {
+    /// Account states before the block.
+    pub changes: Vec<AccountBeforeTx>,
+}
+
+impl StaticFileAccountChangeSet {
+    /// Returns the state of `address` before the block, if it was changed by it.
+    pub fn get(&self, address: &Address) -> Option<&AccountBeforeTx> {
+        self.changes
+            .binary_search_by(|change| change.address.cmp(address))
+            .ok()
+            .map(|index| &self.changes[index])
+    }
+}
+
+/// Storage slot as it was before a block changed it.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageBeforeTx {
+    /// Address of the account owning the slot.
+    pub address: Address,
+    /// Storage key.
+    pub key: B256,
+    /// Value of the slot before the block.
+    pub value: U256,
+}
+
+impl StorageBeforeTx {
+    /// Returns the slot as a [`StorageEntry`].
+    pub const fn entry(&self) -> StorageEntry {
+        StorageEntry { key: self.key, value: self.value }
+    }
+}
+
+/// The storage changes of a block as stored in the `StorageChangeSets` static file segment.
+///
+/// Changes are sorted by [`Address`] and storage key.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StaticFileStorageChangeSet {
+    /// Storage slots before the block.
+    pub changes: Vec<StorageBeforeTx>,
+}
+
+impl StaticFileStorageChangeSet {
+    /// Returns the storage slot `key` of `address` before the block, if it was changed by it.
+    pub fn get(&self, address: &Address, key: &B256) -> Option<&StorageBeforeTx> {
+        self.changes
+            .binary_search_by(|change| (&change.address, &change.key).cmp(&(address, key)))
+            .ok()
+            .map(|index| &self.changes[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_changeset_get() {
+        let address = Address::with_last_byte(1);
+        let changes = (0..4u8)
+            .map(|i| StorageBeforeTx {
+                address: Address::with_last_byte(i % 2),
+                key: B256::with_last_byte(i),
+                value: U256::from(i),
+            })
+            .collect::<Vec<_>>();
+        let mut changeset = StaticFileStorageChangeSet { changes };
+        changeset.changes.sort_by_key(|change| (change.address, change.key));
+
+        assert_eq!(
+            changeset.get(&address, &B256::with_last_byte(3)).map(|c| c.value),
+            Some(U256::from(3))
+        );
+        assert_eq!(changeset.get(&address, &B256::with_last_byte(2)), None);
+    }
+}
@@ -0,0 +1,657 @@
+//! Conformance tests that every database implementation must pass.
+//!
+//! Each test is written against the [`Database`] trait and run on both MDBX and the in-memory
+//! database, so the in-memory backend behaves exactly like MDBX wherever the node relies on it.
+
+use crate::{
+    tables::{AccountChangeSets, CanonicalHeaders, PlainAccountState, PlainStorageState},
+    DatabaseError,
+};
+use alloy_primitives::{Address, B256, U256};
+use assert_matches::assert_matches;
+use proptest::prelude::*;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, ReverseWalker, Walker},
+    database::Database,
+    models::AccountBeforeTx,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives_traits::{Account, StorageEntry};
+use reth_storage_errors::db::DatabaseWriteOperation;
+use std::collections::BTreeMap;
+
+macro_rules! conformance_tests {
+    ($($name:ident),* $(,)?) => {
+        mod mdbx {
+            $(
+                #[test]
+                fn $name() {
+                    super::$name(&*crate::test_utils::create_test_rw_db())
+                }
+            )*
+        }
+
+        mod memory {
+            $(
+                #[test]
+                fn $name() {
+                    super::$name(&crate::memory::MemoryDatabase::new())
+                }
+            )*
+        }
+    };
+}
+
+conformance_tests!(
+    put_get_delete,
+    read_transaction_isolation,
+    single_write_transaction,
+    cursor_writes_visible_in_transaction,
+    cursor_walk_range,
+    cursor_walk_range_on_dup_table,
+    cursor_walkers,
+    cursor_walk_back,
+    cursor_seek_exact_missing_key,
+    cursor_insert,
+    cursor_delete_current,
+    cursor_append,
+    cursor_upsert,
+    cursor_dupsort_append,
+    dup_sort_order,
+    dup_cursor_navigation,
+    dup_cursor_delete_current_duplicates,
+    random_operations,
+);
+
+/// Puts `keys` into [`CanonicalHeaders`] and commits.
+fn put_canonical_headers<DB: Database>(db: &DB, keys: impl IntoIterator<Item = u64>) {
+    let tx = db.tx_mut().unwrap();
+    for key in keys {
+        tx.put::<CanonicalHeaders>(key, B256::with_last_byte(key as u8)).unwrap();
+    }
+    tx.commit().unwrap();
+}
+
+/// Returns all keys of [`CanonicalHeaders`].
+fn canonical_header_keys<TX: DbTx>(tx: &TX) -> Vec<u64> {
+    tx.cursor_read::<CanonicalHeaders>()
+        .unwrap()
+        .walk(None)
+        .unwrap()
+        .map(|row| row.unwrap().0)
+        .collect()
+}
+
+fn storage_entry(key: u8, value: u64) -> StorageEntry {
+    StorageEntry { key: B256::with_last_byte(key), value: U256::from(value) }
+}
+
+fn put_get_delete<DB: Database>(db: &DB) {
+    let tx = db.tx_mut().unwrap();
+    tx.put::<CanonicalHeaders>(1, B256::with_last_byte(1)).unwrap();
+    tx.put::<CanonicalHeaders>(1, B256::with_last_byte(2)).unwrap();
+    tx.put::<CanonicalHeaders>(2, B256::with_last_byte(3)).unwrap();
+    assert_eq!(tx.get::<CanonicalHeaders>(1), Ok(Some(B256::with_last_byte(2))));
+    assert_eq!(tx.get::<CanonicalHeaders>(3), Ok(None));
+    assert_eq!(tx.entries::<CanonicalHeaders>(), Ok(2));
+
+    assert_eq!(tx.delete::<CanonicalHeaders>(1, None), Ok(true));
+    assert_eq!(tx.delete::<CanonicalHeaders>(1, None), Ok(false));
+    assert_eq!(tx.get::<CanonicalHeaders>(1), Ok(None));
+
+    let address = Address::with_last_byte(1);
+    tx.put::<PlainStorageState>(address, storage_entry(1, 1)).unwrap();
+    tx.put::<PlainStorageState>(address, storage_entry(2, 2)).unwrap();
+    assert_eq!(tx.delete::<PlainStorageState>(address, Some(storage_entry(1, 1))), Ok(true));
+    assert_eq!(tx.get::<PlainStorageState>(address), Ok(Some(storage_entry(2, 2))));
+    assert_eq!(tx.delete::<PlainStorageState>(address, None), Ok(true));
+    assert_eq!(tx.get::<PlainStorageState>(address), Ok(None));
+    tx.commit().unwrap();
+
+    let tx = db.tx_mut().unwrap();
+    tx.clear::<CanonicalHeaders>().unwrap();
+    assert_eq!(tx.entries::<CanonicalHeaders>(), Ok(0));
+    tx.commit().unwrap();
+
+    let tx = db.tx().unwrap();
+    assert_eq!(tx.get::<CanonicalHeaders>(2), Ok(None));
+}
+
+fn read_transaction_isolation<DB: Database>(db: &DB) {
+    put_canonical_headers(db, [0]);
+
+    let before = db.tx().unwrap();
+    let mut cursor = before.cursor_read::<CanonicalHeaders>().unwrap();
+
+    let tx = db.tx_mut().unwrap();
+    tx.put::<CanonicalHeaders>(1, B256::ZERO).unwrap();
+    tx.commit().unwrap();
+
+    // Changes committed after the read transaction was opened are not visible to it.
+    assert_eq!(canonical_header_keys(&before), vec![0]);
+    assert_eq!(cursor.last(), Ok(Some((0, B256::with_last_byte(0)))));
+    assert_eq!(canonical_header_keys(&db.tx().unwrap()), vec![0, 1]);
+
+    // Aborted changes are discarded.
+    let tx = db.tx_mut().unwrap();
+    tx.put::<CanonicalHeaders>(2, B256::ZERO).unwrap();
+    tx.abort();
+    assert_eq!(canonical_header_keys(&db.tx().unwrap()), vec![0, 1]);
+
+    // Dropped write transactions are discarded too.
+    {
+        let tx = db.tx_mut().unwrap();
+        tx.put::<CanonicalHeaders>(3, B256::ZERO).unwrap();
+    }
+    assert_eq!(canonical_header_keys(&db.tx().unwrap()), vec![0, 1]);
+}
+
+fn single_write_transaction<DB: Database>(db: &DB) {
+    let tx = db.tx_mut().unwrap();
+    tx.put::<CanonicalHeaders>(0, B256::ZERO).unwrap();
+
+    std::thread::scope(|scope| {
+        // The second write transaction can only start once the first one is committed.
+        let handle = scope.spawn(|| {
+            let tx = db.tx_mut().unwrap();
+            tx.put::<CanonicalHeaders>(1, B256::ZERO).unwrap();
+            let keys = canonical_header_keys(&tx);
+            tx.commit().unwrap();
+            keys
+        });
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        tx.commit().unwrap();
+
+        assert_eq!(handle.join().unwrap(), vec![0, 1]);
+    });
+}
+
+fn cursor_writes_visible_in_transaction<DB: Database>(db: &DB) {
+    let tx = db.tx_mut().unwrap();
+    let mut dup_cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+
+    let address = Address::with_last_byte(1);
+    let entry_0 = storage_entry(1, 0);
+    let entry_1 = storage_entry(1, 1);
+
+    dup_cursor.upsert(address, &entry_0).unwrap();
+    dup_cursor.upsert(address, &entry_1).unwrap();
+    assert_eq!(
+        dup_cursor.walk(None).unwrap().collect::<Result<Vec<_>, _>>(),
+        Ok(vec![(address, entry_0), (address, entry_1)])
+    );
+
+    let mut walker = dup_cursor.walk(None).unwrap();
+    walker.delete_current().unwrap();
+    assert_eq!(walker.next(), Some(Ok((address, entry_1))));
+
+    // Other cursors of the transaction see the deletion.
+    assert_eq!(
+        tx.cursor_dup_read::<PlainStorageState>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>(),
+        Ok(vec![(address, entry_1)])
+    );
+    assert_eq!(walker.next(), None);
+}
+
+fn cursor_walk_range<DB: Database>(db: &DB) {
+    put_canonical_headers(db, [0, 1, 2, 3]);
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    let mut keys = |range: (std::ops::Bound<u64>, std::ops::Bound<u64>)| {
+        cursor.walk_range(range).unwrap().map(|row| row.unwrap().0).collect::<Vec<_>>()
+    };
+
+    use std::ops::Bound::*;
+    assert_eq!(keys((Included(1), Excluded(3))), vec![1, 2]);
+    assert_eq!(keys((Included(1), Included(2))), vec![1, 2]);
+    assert_eq!(keys((Included(1), Unbounded)), vec![1, 2, 3]);
+    assert_eq!(keys((Included(2), Excluded(4))), vec![2, 3]);
+    assert_eq!(keys((Unbounded, Excluded(3))), vec![0, 1, 2]);
+    assert_eq!(keys((Unbounded, Unbounded)), vec![0, 1, 2, 3]);
+    assert_eq!(keys((Included(3), Excluded(1))), Vec::<u64>::new());
+    assert_eq!(keys((Included(15), Included(2))), Vec::<u64>::new());
+    assert_eq!(keys((Included(1), Excluded(1))), Vec::<u64>::new());
+    assert_eq!(keys((Included(4), Unbounded)), Vec::<u64>::new());
+}
+
+fn cursor_walk_range_on_dup_table<DB: Database>(db: &DB) {
+    let changes = (0..3)
+        .flat_map(|block| {
+            (0..3).map(move |address| {
+                (block, AccountBeforeTx { address: Address::with_last_byte(address), info: None })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let tx = db.tx_mut().unwrap();
+    for (block, change) in changes.iter().rev() {
+        tx.put::<AccountChangeSets>(*block, change.clone()).unwrap();
+    }
+    tx.commit().unwrap();
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_read::<AccountChangeSets>().unwrap();
+    assert_eq!(cursor.walk_range(..).unwrap().collect::<Result<Vec<_>, _>>(), Ok(changes.clone()));
+    assert_eq!(
+        cursor.walk_range(0..=1).unwrap().collect::<Result<Vec<_>, _>>(),
+        Ok(changes[..6].to_vec())
+    );
+}
+
+fn cursor_walkers<DB: Database>(db: &DB) {
+    put_canonical_headers(db, [0, 1, 3]);
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    let row = |key: u64| Some(Ok((key, B256::with_last_byte(key as u8))));
+
+    let mut walker = Walker::new(&mut cursor, None);
+    assert_eq!(walker.next(), row(0));
+    assert_eq!(walker.next(), row(1));
+    assert_eq!(walker.next(), row(3));
+    assert_eq!(walker.next(), None);
+
+    // The cursor stays at the last entry after moving past it.
+    let mut reverse_walker = walker.rev();
+    assert_eq!(reverse_walker.next(), row(3));
+    assert_eq!(reverse_walker.next(), row(1));
+    assert_eq!(reverse_walker.next(), row(0));
+    assert_eq!(reverse_walker.next(), None);
+
+    // The cursor stays at the first entry after moving before it.
+    let mut walker = reverse_walker.forward();
+    assert_eq!(walker.next(), row(0));
+    assert_eq!(walker.next(), row(1));
+    assert_eq!(walker.next(), row(3));
+    assert_eq!(walker.next(), None);
+
+    // Moving backwards from an unpositioned cursor starts at the last entry.
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    let mut reverse_walker = ReverseWalker::new(&mut cursor, None);
+    assert_eq!(reverse_walker.next(), row(3));
+    assert_eq!(reverse_walker.next(), row(1));
+}
+
+fn cursor_walk_back<DB: Database>(db: &DB) {
+    put_canonical_headers(db, [0, 1, 3]);
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    let mut keys = |start: Option<u64>| {
+        cursor.walk_back(start).unwrap().map(|row| row.unwrap().0).collect::<Vec<_>>()
+    };
+
+    assert_eq!(keys(Some(1)), vec![1, 0]);
+    assert_eq!(keys(Some(2)), vec![3, 1, 0]);
+    assert_eq!(keys(Some(4)), vec![3, 1, 0]);
+    assert_eq!(keys(None), vec![3, 1, 0]);
+}
+
+fn cursor_seek_exact_missing_key<DB: Database>(db: &DB) {
+    put_canonical_headers(db, [0, 1, 3]);
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    assert_eq!(cursor.current(), Ok(None));
+    assert_eq!(cursor.seek_exact(2), Ok(None));
+    assert_eq!(cursor.current(), Ok(None));
+    assert_eq!(cursor.seek(2), Ok(Some((3, B256::with_last_byte(3)))));
+    assert_eq!(cursor.seek_exact(1), Ok(Some((1, B256::with_last_byte(1)))));
+    assert_eq!(cursor.next(), Ok(Some((3, B256::with_last_byte(3)))));
+    assert_eq!(cursor.prev(), Ok(Some((1, B256::with_last_byte(1)))));
+}
+
+fn cursor_insert<DB: Database>(db: &DB) {
+    put_canonical_headers(db, [0, 1, 3, 5, 7, 9]);
+
+    let tx = db.tx_mut().unwrap();
+    let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+
+    // Inserts wherever the cursor is.
+    cursor.last().unwrap();
+    for key in (2..=8).step_by(2) {
+        assert_eq!(cursor.insert(key, &B256::ZERO), Ok(()));
+        assert_eq!(cursor.current(), Ok(Some((key, B256::ZERO))));
+    }
+
+    assert_matches!(
+        cursor.insert(2, &B256::ZERO),
+        Err(DatabaseError::Write(err)) if err.operation == DatabaseWriteOperation::CursorInsert
+    );
+    assert_eq!(cursor.current(), Ok(Some((2, B256::ZERO))));
+    tx.commit().unwrap();
+
+    assert_eq!(canonical_header_keys(&db.tx().unwrap()), (0..=9).collect::<Vec<_>>());
+
+    // Can't insert another value for an existing key into a dup table.
+    let tx = db.tx_mut().unwrap();
+    let mut dup_cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+    let address = Address::with_last_byte(1);
+    assert!(dup_cursor.insert(address, &storage_entry(1, 1)).is_ok());
+    assert!(dup_cursor.insert(address, &storage_entry(2, 2)).is_err());
+}
+
+fn cursor_delete_current<DB: Database>(db: &DB) {
+    let tx = db.tx_mut().unwrap();
+    let keys = [1, 2, 3].map(Address::with_last_byte);
+    let mut cursor = tx.cursor_write::<PlainAccountState>().unwrap();
+    for key in keys {
+        cursor.insert(key, &Account::default()).unwrap();
+    }
+
+    cursor.seek_exact(keys[1]).unwrap();
+    assert_eq!(cursor.delete_current(), Ok(()));
+    assert_eq!(cursor.next(), Ok(Some((keys[2], Account::default()))));
+    assert_eq!(cursor.seek_exact(keys[1]), Ok(None));
+
+    // Deleting without a current entry fails.
+    assert_matches!(cursor.delete_current(), Err(DatabaseError::Delete(_)));
+    assert_eq!(cursor.seek_exact(keys[0]), Ok(Some((keys[0], Account::default()))));
+    assert_eq!(cursor.seek_exact(keys[2]), Ok(Some((keys[2], Account::default()))));
+}
+
+fn cursor_append<DB: Database>(db: &DB) {
+    put_canonical_headers(db, [0, 1, 3, 4]);
+
+    let tx = db.tx_mut().unwrap();
+    let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+    assert_eq!(cursor.append(5, &B256::ZERO), Ok(()));
+
+    for key in [2, 5] {
+        assert_matches!(
+            cursor.append(key, &B256::ZERO),
+            Err(DatabaseError::Write(err)) if err.operation == DatabaseWriteOperation::CursorAppend
+        );
+        // The cursor is at the end of the table.
+        assert_eq!(cursor.current(), Ok(Some((5, B256::ZERO))));
+    }
+    tx.commit().unwrap();
+
+    assert_eq!(canonical_header_keys(&db.tx().unwrap()), vec![0, 1, 3, 4, 5]);
+}
+
+fn cursor_upsert<DB: Database>(db: &DB) {
+    let tx = db.tx_mut().unwrap();
+    let mut cursor = tx.cursor_write::<PlainAccountState>().unwrap();
+    let key = Address::with_last_byte(1);
+
+    for nonce in 0..3 {
+        let account = Account { nonce, ..Default::default() };
+        cursor.upsert(key, &account).unwrap();
+        assert_eq!(cursor.seek_exact(key), Ok(Some((key, account))));
+    }
+
+    // Upserting into a dup table adds the value, even for the same subkey.
+    let mut dup_cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+    let entry_1 = storage_entry(1, 1);
+    let entry_2 = storage_entry(1, 2);
+    dup_cursor.upsert(key, &entry_1).unwrap();
+    assert_eq!(dup_cursor.seek_by_key_subkey(key, entry_1.key), Ok(Some(entry_1)));
+    dup_cursor.upsert(key, &entry_2).unwrap();
+    assert_eq!(dup_cursor.seek_by_key_subkey(key, entry_1.key), Ok(Some(entry_1)));
+    assert_eq!(dup_cursor.next_dup_val(), Ok(Some(entry_2)));
+}
+
+fn cursor_dupsort_append<DB: Database>(db: &DB) {
+    let block = 2;
+    let change =
+        |address: u8| AccountBeforeTx { address: Address::with_last_byte(address), info: None };
+
+    let tx = db.tx_mut().unwrap();
+    let mut cursor = tx.cursor_write::<AccountChangeSets>().unwrap();
+    for address in [0, 1, 3, 4, 5] {
+        cursor.append(block, &change(address)).unwrap();
+    }
+    tx.commit().unwrap();
+
+    let tx = db.tx_mut().unwrap();
+    let mut cursor = tx.cursor_dup_write::<AccountChangeSets>().unwrap();
+    assert_matches!(
+        cursor.append_dup(block, change(2)),
+        Err(DatabaseError::Write(err)) if err.operation == DatabaseWriteOperation::CursorAppendDup
+    );
+    assert_matches!(
+        cursor.append(block - 1, &change(2)),
+        Err(DatabaseError::Write(err)) if err.operation == DatabaseWriteOperation::CursorAppend
+    );
+    // Appending to the last key of a dup table inserts the value in order.
+    assert_eq!(cursor.append(block, &change(2)), Ok(()));
+    assert_eq!(cursor.append_dup(block, change(6)), Ok(()));
+    assert_eq!(
+        cursor
+            .walk_dup(Some(block), None)
+            .unwrap()
+            .map(|row| row.unwrap().1.address)
+            .collect::<Vec<_>>(),
+        [0, 1, 2, 3, 4, 5, 6].map(Address::with_last_byte)
+    );
+}
+
+fn dup_sort_order<DB: Database>(db: &DB) {
+    let key = Address::with_last_byte(1);
+    let other_key = Address::with_last_byte(2);
+    let value00 = storage_entry(0, 0);
+    let value01 = storage_entry(0, 1);
+    let value11 = storage_entry(1, 1);
+    let value22 = storage_entry(2, 2);
+
+    for (key, value) in
+        [(key, value22), (key, value01), (key, value11), (key, value00), (other_key, value22)]
+    {
+        db.update(|tx| tx.put::<PlainStorageState>(key, value).unwrap()).unwrap();
+    }
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+
+    // Values are ordered by subkey, and then by the rest of the value.
+    assert_eq!(cursor.next_dup_val(), Ok(Some(value00)));
+    assert_eq!(cursor.next_dup_val(), Ok(Some(value01)));
+    assert_eq!(cursor.next_dup_val(), Ok(Some(value11)));
+    assert_eq!(cursor.next_dup_val(), Ok(Some(value22)));
+    assert_eq!(cursor.next_dup_val(), Ok(None));
+
+    let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+    assert_eq!(
+        cursor.walk(None).unwrap().collect::<Result<Vec<_>, _>>(),
+        Ok(vec![
+            (key, value00),
+            (key, value01),
+            (key, value11),
+            (key, value22),
+            (other_key, value22)
+        ])
+    );
+}
+
+fn dup_cursor_navigation<DB: Database>(db: &DB) {
+    let key1 = Address::with_last_byte(1);
+    let key2 = Address::with_last_byte(2);
+    let value00 = storage_entry(0, 0);
+    let value11 = storage_entry(1, 1);
+    let value22 = storage_entry(2, 2);
+
+    let tx = db.tx_mut().unwrap();
+    tx.put::<PlainStorageState>(key1, value00).unwrap();
+    tx.put::<PlainStorageState>(key1, value11).unwrap();
+    tx.put::<PlainStorageState>(key2, value22).unwrap();
+    tx.commit().unwrap();
+
+    let tx = db.tx().unwrap();
+    let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+
+    // Dup walkers only iterate over the values of a single key.
+    let mut walker = cursor.walk_dup(None, None).unwrap();
+    assert_eq!(walker.next(), Some(Ok((key1, value00))));
+    assert_eq!(walker.next(), Some(Ok((key1, value11))));
+    assert_eq!(walker.next(), None);
+
+    let mut walker = cursor.walk_dup(Some(key1), Some(value11.key)).unwrap();
+    assert_eq!(walker.next(), Some(Ok((key1, value11))));
+    assert_eq!(walker.next(), None);
+
+    let mut walker = cursor.walk_dup(Some(Address::ZERO), None).unwrap();
+    assert_eq!(walker.next(), None);
+
+    // Seeking a subkey returns the first value with a greater or equal subkey of the same key.
+    assert_eq!(cursor.seek_by_key_subkey(key1, B256::with_last_byte(1)), Ok(Some(value11)));
+    assert_eq!(cursor.seek_by_key_subkey(key1, value22.key), Ok(None));
+    assert_eq!(cursor.seek_by_key_subkey(key2, value00.key), Ok(Some(value22)));
+
+    assert_eq!(cursor.first(), Ok(Some((key1, value00))));
+    assert_eq!(cursor.next_no_dup(), Ok(Some((key2, value22))));
+    assert_eq!(cursor.next_dup(), Ok(None));
+    assert_eq!(cursor.current(), Ok(Some((key2, value22))));
+    assert_eq!(cursor.next_no_dup(), Ok(None));
+}
+
+fn dup_cursor_delete_current_duplicates<DB: Database>(db: &DB) {
+    let key1 = Address::with_last_byte(1);
+    let key2 = Address::with_last_byte(2);
+
+    let tx = db.tx_mut().unwrap();
+    tx.put::<PlainStorageState>(key1, storage_entry(0, 0)).unwrap();
+    tx.put::<PlainStorageState>(key1, storage_entry(1, 1)).unwrap();
+    tx.put::<PlainStorageState>(key2, storage_entry(2, 2)).unwrap();
+
+    let mut cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+    cursor.seek_exact(key1).unwrap();
+    cursor.delete_current_duplicates().unwrap();
+    tx.commit().unwrap();
+
+    let tx = db.tx().unwrap();
+    assert_eq!(
+        tx.cursor_dup_read::<PlainStorageState>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>(),
+        Ok(vec![(key2, storage_entry(2, 2))])
+    );
+}
+
+/// Operation of [`random_operations`].
+#[derive(Debug, Clone)]
+enum Operation {
+    Put(u8, u8, u8),
+    Delete(u8, Option<(u8, u8)>),
+    Seek(u8),
+    SeekExact(u8),
+    SeekBySubkey(u8, u8),
+    First,
+    Last,
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        3 => (0..8u8, 0..4u8, 0..4u8).prop_map(|(key, subkey, value)| Operation::Put(key, subkey, value)),
+        1 => (0..8u8, proptest::option::of((0..4u8, 0..4u8)))
+            .prop_map(|(key, value)| Operation::Delete(key, value)),
+        1 => (0..9u8).prop_map(Operation::Seek),
+        1 => (0..9u8).prop_map(Operation::SeekExact),
+        1 => (0..9u8, 0..5u8).prop_map(|(key, subkey)| Operation::SeekBySubkey(key, subkey)),
+        1 => Just(Operation::First),
+        1 => Just(Operation::Last),
+    ]
+}
+
+/// Applies `operations` to the plain and dup tables of `tx`, returning the results of all reads.
+///
+/// Cursors are only moved relative to entries that were found, since that's where both backends
+/// are required to agree.
+fn apply_operations<TX: DbTx + DbTxMut>(tx: &TX, operations: &[Operation]) -> Vec<String> {
+    let address = |key: u8| Address::with_last_byte(key);
+    let mut results = Vec::new();
+
+    for operation in operations {
+        let mut plain = tx.cursor_write::<CanonicalHeaders>().unwrap();
+        let mut dup = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+
+        match *operation {
+            Operation::Put(key, subkey, value) => {
+                tx.put::<CanonicalHeaders>(key as u64, B256::with_last_byte(value)).unwrap();
+                dup.upsert(address(key), &storage_entry(subkey, value as u64)).unwrap();
+            }
+            Operation::Delete(key, value) => {
+                results.push(format!("{:?}", tx.delete::<CanonicalHeaders>(key as u64, None)));
+                results.push(format!(
+                    "{:?}",
+                    tx.delete::<PlainStorageState>(
+                        address(key),
+                        value.map(|(subkey, value)| storage_entry(subkey, value as u64))
+                    )
+                ));
+            }
+            Operation::Seek(key) => {
+                let value = plain.seek(key as u64);
+                let next = if matches!(value, Ok(Some(_))) { Some(plain.next()) } else { None };
+                results.push(format!("{value:?} {next:?}"));
+
+                let value = dup.seek(address(key));
+                let next = if matches!(value, Ok(Some(_))) { Some(dup.next_dup()) } else { None };
+                results.push(format!("{value:?} {next:?}"));
+            }
+            Operation::SeekExact(key) => {
+                let value = plain.seek_exact(key as u64);
+                let prev = if matches!(value, Ok(Some(_))) { Some(plain.prev()) } else { None };
+                results.push(format!("{value:?} {prev:?}"));
+
+                let value = dup.seek_exact(address(key));
+                let next =
+                    if matches!(value, Ok(Some(_))) { Some(dup.next_no_dup()) } else { None };
+                results.push(format!("{value:?} {next:?}"));
+            }
+            Operation::SeekBySubkey(key, subkey) => {
+                let value = dup.seek_by_key_subkey(address(key), B256::with_last_byte(subkey));
+                let next = if matches!(value, Ok(Some(_))) { Some(dup.next_dup()) } else { None };
+                results.push(format!("{value:?} {next:?}"));
+            }
+            Operation::First => {
+                results.push(format!("{:?} {:?}", plain.first(), plain.next()));
+                results.push(format!("{:?} {:?}", dup.first(), dup.next()));
+            }
+            Operation::Last => {
+                results.push(format!("{:?} {:?}", plain.last(), plain.prev()));
+                results.push(format!("{:?} {:?}", dup.last(), dup.prev()));
+            }
+        }
+    }
+
+    let plain = tx
+        .cursor_read::<CanonicalHeaders>()
+        .unwrap()
+        .walk(None)
+        .unwrap()
+        .collect::<Result<BTreeMap<_, _>, _>>();
+    let dup = tx
+        .cursor_dup_read::<PlainStorageState>()
+        .unwrap()
+        .walk(None)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>();
+    results.push(format!("{plain:?} {dup:?}"));
+    results
+}
+
+/// Checks that random sequences of operations give the same results as on a freshly created
+/// in-memory database.
+fn random_operations<DB: Database>(db: &DB) {
+    proptest!(ProptestConfig::with_cases(64), |(operations in proptest::collection::vec(operation(), 1..64))| {
+        let reference = crate::memory::MemoryDatabase::new();
+
+        let tx = db.tx_mut().unwrap();
+        tx.clear::<CanonicalHeaders>().unwrap();
+        tx.clear::<PlainStorageState>().unwrap();
+        let results = apply_operations(&tx, &operations);
+        tx.abort();
+
+        let reference_tx = reference.tx_mut().unwrap();
+        prop_assert_eq!(results, apply_operations(&reference_tx, &operations));
+    });
+}
@@ -0,0 +1,513 @@
+//! Cursors of the in-memory database.
+
+use super::{Error, TableEntries, Tables, TransactionKind, RW};
+use crate::DatabaseError;
+use parking_lot::Mutex;
+use reth_db_api::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{Compress, Decode, Decompress, DupSort, Encode, Table, TableRow},
+};
+use reth_storage_errors::db::{DatabaseWriteError, DatabaseWriteOperation};
+use std::{
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    sync::{Arc, LazyLock},
+};
+
+/// Encoded `(key, value)` pair.
+type Entry = (Vec<u8>, Vec<u8>);
+
+/// Entries of tables that were never written to.
+static EMPTY: LazyLock<TableEntries> = LazyLock::new(TableEntries::new);
+
+/// Position of a [`Cursor`].
+#[derive(Debug, Clone)]
+enum Position {
+    /// The cursor was not positioned yet, or the last seek didn't find an entry.
+    Unset,
+    /// The last exact seek didn't find the key. Same as [`Position::Unset`], except that there
+    /// are no duplicates to move to.
+    Missing,
+    /// The cursor points to the entry.
+    At(Entry),
+    /// The entry the cursor pointed to was deleted, the cursor now points to the entry following
+    /// it.
+    Deleted(Entry),
+    /// The cursor moved past the last entry, either from the entry it pointed to or by seeking
+    /// beyond the last key.
+    End(Option<Entry>),
+}
+
+/// Cursor over table `T` of an in-memory database transaction.
+///
+/// Follows MDBX cursor semantics: moving to the next entry from an unpositioned cursor returns the
+/// first entry, the cursor stays at the last entry after moving past it and at the first entry
+/// after moving before it, and after deleting the current entry the cursor points to the entry
+/// following it.
+#[derive(Debug)]
+pub struct Cursor<K: TransactionKind, T: Table> {
+    /// Tables of the transaction.
+    tables: Arc<Mutex<Tables>>,
+    /// Current position.
+    position: Position,
+    _dbi: PhantomData<(K, T)>,
+}
+
+impl<K: TransactionKind, T: Table> Cursor<K, T> {
+    pub(crate) const fn new(tables: Arc<Mutex<Tables>>) -> Self {
+        Self { tables, position: Position::Unset, _dbi: PhantomData }
+    }
+
+    /// Finds an entry in the table with `f`, moves the cursor to it and decodes it.
+    ///
+    /// If no entry is found, the cursor is moved to `not_found` if set, or stays in place
+    /// otherwise.
+    fn move_to(
+        &mut self,
+        f: impl FnOnce(&TableEntries, &Position) -> Option<Entry>,
+        not_found: Option<Position>,
+    ) -> PairResult<T> {
+        let entry = {
+            let tables = self.tables.lock();
+            f(table_entries::<T>(&tables), &self.position)
+        };
+
+        match entry {
+            Some(entry) => {
+                let row = decode::<T>(&entry)?;
+                self.position = Position::At(entry);
+                Ok(Some(row))
+            }
+            None => {
+                if let Some(position) = not_found {
+                    self.position = position;
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Seeks to the first entry with the encoded `key`.
+    pub(crate) fn seek_exact_encoded(&mut self, key: &[u8]) -> PairResult<T> {
+        self.move_to(
+            |entries, _| seek(entries, key).filter(|(k, _)| k == key).cloned(),
+            Some(Position::Missing),
+        )
+    }
+
+    /// Returns the entry the cursor currently points to, if it still exists.
+    fn current_entry(&self, entries: &TableEntries) -> Option<Entry> {
+        match &self.position {
+            Position::At(entry) | Position::End(Some(entry)) => {
+                entries.contains(entry).then(|| entry.clone())
+            }
+            Position::Deleted(entry) => after(entries, entry).cloned(),
+            Position::Unset | Position::Missing | Position::End(None) => None,
+        }
+    }
+
+    /// Seeks to the first entry with `key` whose value is greater than or equal to `subkey`.
+    fn seek_both_range(&mut self, key: Vec<u8>, subkey: &[u8]) -> PairResult<T> {
+        self.move_to(
+            |entries, _| {
+                entries
+                    .range::<_, Entry>((key.clone(), subkey.to_vec())..)
+                    .next()
+                    .filter(|(k, _)| *k == key)
+                    .cloned()
+            },
+            Some(Position::Missing),
+        )
+    }
+}
+
+impl<T: Table> Cursor<RW, T> {
+    /// Applies `f` to the entries of the table.
+    ///
+    /// Entries shared with snapshots of other transactions are copied on write, node by node.
+    fn modify<R>(&self, f: impl FnOnce(&mut TableEntries) -> R) -> R {
+        let mut tables = self.tables.lock();
+        f(tables.entry(T::NAME).or_default())
+    }
+
+    /// Deletes the `(key, value)` pair, or all values of `key` if `value` is `None`.
+    ///
+    /// Returns `true` if anything was deleted.
+    pub(crate) fn delete_entry(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let key: Vec<u8> = key.encode().into();
+        match value {
+            Some(value) => {
+                let entry = (key, compress::<T>(&value));
+                Ok(self.modify(|entries| entries.remove(&entry).is_some()))
+            }
+            None => Ok(self.modify(|entries| {
+                let values = key_entries(entries, &key);
+                for entry in &values {
+                    entries.remove(entry);
+                }
+                !values.is_empty()
+            })),
+        }
+    }
+
+    /// Inserts the entry and moves the cursor to it.
+    fn put_entry(&mut self, entry: Entry) {
+        self.modify(|entries| {
+            if !T::DUPSORT {
+                for existing in key_entries(entries, &entry.0) {
+                    entries.remove(&existing);
+                }
+            }
+            entries.insert(entry.clone());
+        });
+        self.position = Position::At(entry);
+    }
+}
+
+impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
+    fn first(&mut self) -> PairResult<T> {
+        self.move_to(|entries, _| entries.get_min().cloned(), Some(Position::Unset))
+    }
+
+    fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        self.seek_exact_encoded(key.encode().as_ref())
+    }
+
+    fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        let key = key.encode();
+        self.move_to(|entries, _| seek(entries, key.as_ref()).cloned(), Some(Position::End(None)))
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        let end = Position::End(match &self.position {
+            Position::At(entry) | Position::Deleted(entry) | Position::End(Some(entry)) => {
+                Some(entry.clone())
+            }
+            Position::Unset | Position::Missing | Position::End(None) => None,
+        });
+
+        self.move_to(
+            |entries, position| match position {
+                Position::Unset | Position::Missing => entries.get_min().cloned(),
+                Position::At(entry) | Position::Deleted(entry) => after(entries, entry).cloned(),
+                Position::End(_) => None,
+            },
+            Some(end),
+        )
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        self.move_to(
+            |entries, position| match position {
+                Position::Unset | Position::Missing | Position::End(None) => {
+                    entries.get_max().cloned()
+                }
+                Position::At(entry) | Position::Deleted(entry) | Position::End(Some(entry)) => {
+                    before(entries, entry).cloned()
+                }
+            },
+            None,
+        )
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        self.move_to(|entries, _| entries.get_max().cloned(), Some(Position::Unset))
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        let entry = {
+            let tables = self.tables.lock();
+            self.current_entry(table_entries::<T>(&tables))
+        };
+
+        match entry {
+            Some(entry) => {
+                let row = decode::<T>(&entry)?;
+                self.position = Position::At(entry);
+                Ok(Some(row))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start = if let Some(start_key) = start_key {
+            self.seek(start_key).transpose()
+        } else {
+            self.first().transpose()
+        };
+
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.last() }
+                .transpose();
+
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
+    fn next_dup(&mut self) -> PairResult<T> {
+        self.move_to(
+            |entries, position| match position {
+                Position::Unset => entries.get_min().cloned(),
+                Position::At(entry) | Position::Deleted(entry) => {
+                    after(entries, entry).filter(|(key, _)| *key == entry.0).cloned()
+                }
+                Position::Missing | Position::End(_) => None,
+            },
+            None,
+        )
+    }
+
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        self.move_to(
+            |entries, position| match position {
+                Position::Unset | Position::Missing => entries.get_min().cloned(),
+                Position::At(entry) | Position::Deleted(entry) => entries
+                    .range::<_, Entry>((Bound::Excluded(entry), Bound::Unbounded))
+                    .find(|(key, _)| *key != entry.0)
+                    .cloned(),
+                Position::End(_) => None,
+            },
+            None,
+        )
+    }
+
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        Ok(self.next_dup()?.map(|(_, value)| value))
+    }
+
+    fn seek_by_key_subkey(
+        &mut self,
+        key: <T as Table>::Key,
+        subkey: <T as DupSort>::SubKey,
+    ) -> ValueOnlyResult<T> {
+        Ok(self
+            .seek_both_range(key.encode().into(), subkey.encode().as_ref())?
+            .map(|(_, value)| value))
+    }
+
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => {
+                self.seek_both_range(key.encode().into(), subkey.encode().as_ref()).transpose()
+            }
+            (Some(key), None) => self.seek_exact(key).transpose(),
+            (None, Some(subkey)) => {
+                if let Some((key, _)) = self.first()? {
+                    self.seek_both_range(key.encode().into(), subkey.encode().as_ref()).transpose()
+                } else {
+                    Some(Err(DatabaseError::Read(Error::NotFound.into())))
+                }
+            }
+            (None, None) => self.first().transpose(),
+        };
+
+        Ok(DupWalker::<'_, T, Self> { cursor: self, start })
+    }
+}
+
+impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
+    /// Database operation that will update an existing row if a specified value already
+    /// exists in a table, and insert a new row if the specified value doesn't already exist
+    ///
+    /// For a DUPSORT table, `upsert` will not actually update-or-insert. If the key already exists,
+    /// it will append the value to the subkey, even if the subkeys are the same. So if you want
+    /// to properly upsert, you'll need to `seek_exact` & `delete_current` if the key+subkey was
+    /// found, before calling `upsert`.
+    fn upsert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        self.put_entry((key.encode().into(), compress::<T>(value)));
+        Ok(())
+    }
+
+    fn insert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        let key: Vec<u8> = key.encode().into();
+
+        if self.seek_exact_encoded(&key)?.is_some() {
+            return Err(write_error::<T>(
+                Error::KeyExist,
+                DatabaseWriteOperation::CursorInsert,
+                key,
+            ))
+        }
+
+        self.put_entry((key, compress::<T>(value)));
+        Ok(())
+    }
+
+    /// Appends the data to the end of the table. Consequently, the append operation
+    /// will fail if the inserted key is less than the last table key
+    fn append(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        let key: Vec<u8> = key.encode().into();
+
+        if let Some((last_key, _)) = self.last_entry() {
+            let out_of_order = if T::DUPSORT { key < last_key } else { key <= last_key };
+            if out_of_order {
+                return Err(write_error::<T>(
+                    Error::KeyMismatch,
+                    DatabaseWriteOperation::CursorAppend,
+                    key,
+                ))
+            }
+        }
+
+        self.put_entry((key, compress::<T>(value)));
+        Ok(())
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        let position = self.modify(|entries| {
+            let entry = self.current_entry(entries)?;
+            entries.remove(&entry);
+            Some(Position::Deleted(entry))
+        });
+
+        self.position = position.ok_or_else(|| DatabaseError::Delete(Error::NoData.into()))?;
+        Ok(())
+    }
+}
+
+impl<T: DupSort> DbDupCursorRW<T> for Cursor<RW, T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        let position = self.modify(|entries| {
+            let (key, _) = self.current_entry(entries)?;
+            let values = key_entries(entries, &key);
+            for entry in &values {
+                entries.remove(entry);
+            }
+            values.into_iter().next_back().map(Position::Deleted)
+        });
+
+        self.position = position.ok_or_else(|| DatabaseError::Delete(Error::NoData.into()))?;
+        Ok(())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let entry = (key.encode().into(), compress::<T>(&value));
+
+        let last_dup = {
+            let tables = self.tables.lock();
+            tables.get(T::NAME).and_then(|entries| key_entries(entries, &entry.0).pop())
+        };
+        if let Some(last_dup) = last_dup {
+            if entry <= last_dup {
+                self.position = Position::At(last_dup);
+                return Err(write_error::<T>(
+                    Error::KeyMismatch,
+                    DatabaseWriteOperation::CursorAppendDup,
+                    entry.0,
+                ))
+            }
+        }
+
+        self.put_entry(entry);
+        Ok(())
+    }
+}
+
+impl<T: Table> Cursor<RW, T> {
+    /// Moves the cursor to the last entry of the table and returns it.
+    fn last_entry(&mut self) -> Option<Entry> {
+        let last = {
+            let tables = self.tables.lock();
+            tables.get(T::NAME).and_then(|entries| entries.get_max().cloned())
+        };
+        if let Some(last) = &last {
+            self.position = Position::At(last.clone());
+        }
+        last
+    }
+}
+
+/// Returns the entries of table `T`.
+fn table_entries<T: Table>(tables: &Tables) -> &TableEntries {
+    match tables.get(T::NAME) {
+        Some(entries) => entries,
+        None => &EMPTY,
+    }
+}
+
+/// Returns the first entry with a key greater than or equal to `key`.
+fn seek<'a>(entries: &'a TableEntries, key: &[u8]) -> Option<&'a Entry> {
+    entries.range::<_, Entry>((key.to_vec(), Vec::new())..).next()
+}
+
+/// Returns the entry following `entry`.
+fn after<'a>(entries: &'a TableEntries, entry: &Entry) -> Option<&'a Entry> {
+    entries.range::<_, Entry>((Bound::Excluded(entry), Bound::Unbounded)).next()
+}
+
+/// Returns the entry preceding `entry`.
+fn before<'a>(entries: &'a TableEntries, entry: &Entry) -> Option<&'a Entry> {
+    entries.range::<_, Entry>((Bound::Unbounded, Bound::Excluded(entry))).next_back()
+}
+
+/// Returns all entries with `key`, ordered by value.
+fn key_entries(entries: &TableEntries, key: &[u8]) -> Vec<Entry> {
+    entries
+        .range::<_, Entry>((key.to_vec(), Vec::new())..)
+        .take_while(|(k, _)| k == key)
+        .cloned()
+        .collect()
+}
+
+/// Decodes a `(key, value)` pair of table `T`.
+fn decode<T: Table>((key, value): &Entry) -> Result<TableRow<T>, DatabaseError> {
+    Ok((T::Key::decode(key)?, T::Value::decompress(value)?))
+}
+
+/// Compresses a value of table `T`.
+fn compress<T: Table>(value: &T::Value) -> Vec<u8> {
+    value.uncompressable_ref().map_or_else(
+        || {
+            let mut buf = Vec::new();
+            value.compress_to_buf(&mut buf);
+            buf
+        },
+        <[u8]>::to_vec,
+    )
+}
+
+/// Creates a write error for table `T`.
+fn write_error<T: Table>(
+    error: Error,
+    operation: DatabaseWriteOperation,
+    key: Vec<u8>,
+) -> DatabaseError {
+    DatabaseWriteError { info: error.into(), operation, table_name: T::NAME, key }.into()
+}
@@ -0,0 +1,148 @@
+//! In-memory implementation of reth's database abstraction layer.
+//!
+//! Tables are kept as ordered sets of encoded `(key, value)` pairs, so keys and duplicate values
+//! are sorted by their encoded bytes exactly like in MDBX. The sets are persistent data
+//! structures: cloning them is cheap, and a write only copies the nodes on the path to the changed
+//! entry. Read-only transactions take a snapshot of the committed tables, and the single
+//! read-write transaction changes its own copy of them without affecting any snapshot.
+
+use crate::DatabaseError;
+use parking_lot::{Condvar, Mutex, RwLock};
+use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
+use std::{collections::BTreeMap, fmt, sync::Arc};
+use tx::Tx;
+
+pub mod cursor;
+pub mod tx;
+
+/// Encoded `(key, value)` pairs of a table, ordered by key and then by value.
+pub(crate) type TableEntries = im::OrdSet<(Vec<u8>, Vec<u8>)>;
+
+/// Tables of the database by their names.
+///
+/// Tables that were never written to are absent and treated as empty.
+pub(crate) type Tables = BTreeMap<&'static str, TableEntries>;
+
+/// Marker for read-only transactions.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct RO;
+
+/// Marker for read-write transactions.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct RW;
+
+/// Kind of a transaction, either [`RO`] or [`RW`].
+pub trait TransactionKind: fmt::Debug + Send + Sync + 'static {}
+
+impl TransactionKind for RO {}
+
+impl TransactionKind for RW {}
+
+/// Database that keeps all tables in memory.
+///
+/// Like MDBX, it allows any number of concurrent read-only transactions and a single read-write
+/// transaction at a time. Read-only transactions see the state committed at the moment they were
+/// opened, and [`Database::tx_mut`] blocks until the previous read-write transaction is committed
+/// or dropped.
+///
+/// Cloning the database is cheap, and all clones share the same state.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryDatabase {
+    inner: Arc<MemoryDatabaseInner>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryDatabaseInner {
+    /// Latest committed state of the tables.
+    committed: RwLock<Arc<Tables>>,
+    /// Whether a read-write transaction is currently open.
+    writer: Mutex<bool>,
+    /// Notified when the read-write transaction is closed.
+    writer_closed: Condvar,
+}
+
+impl MemoryDatabase {
+    /// Creates a new empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a snapshot of the committed tables.
+    fn snapshot(&self) -> Tables {
+        Tables::clone(&self.inner.committed.read())
+    }
+}
+
+impl Database for MemoryDatabase {
+    type TX = Tx<RO>;
+    type TXMut = Tx<RW>;
+
+    fn tx(&self) -> Result<Self::TX, DatabaseError> {
+        Ok(Tx::new(self.snapshot(), None))
+    }
+
+    fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
+        let mut writer = self.inner.writer.lock();
+        while *writer {
+            self.inner.writer_closed.wait(&mut writer);
+        }
+        *writer = true;
+        drop(writer);
+
+        Ok(Tx::new(self.snapshot(), Some(WriterHandle { db: self.clone() })))
+    }
+}
+
+impl DatabaseMetrics for MemoryDatabase {}
+
+/// Handle of the open read-write transaction, releases the writer slot of the database when
+/// dropped.
+#[derive(Debug)]
+pub(crate) struct WriterHandle {
+    db: MemoryDatabase,
+}
+
+impl WriterHandle {
+    /// Makes `tables` the committed state of the database.
+    pub(crate) fn commit(&self, tables: Tables) {
+        *self.db.inner.committed.write() = Arc::new(tables);
+    }
+}
+
+impl Drop for WriterHandle {
+    fn drop(&mut self) {
+        *self.db.inner.writer.lock() = false;
+        self.db.inner.writer_closed.notify_one();
+    }
+}
+
+/// Errors of the in-memory database, mirroring the MDBX errors for the same conditions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, derive_more::Display)]
+pub enum Error {
+    /// Key/data pair already exists.
+    #[display("key/data pair already exists")]
+    KeyExist,
+    /// No matching key/data pair found.
+    #[display("no matching key/data pair found")]
+    NotFound,
+    /// The given key value is mismatched to the current cursor position.
+    #[display("the given key value is mismatched to the current cursor position")]
+    KeyMismatch,
+    /// No data available, the cursor is not positioned.
+    #[display("no data available")]
+    NoData,
+}
+
+impl From<Error> for i32 {
+    fn from(error: Error) -> Self {
+        // Same codes as the corresponding MDBX errors.
+        match error {
+            Error::KeyExist => -30799,
+            Error::NotFound => -30798,
+            Error::KeyMismatch => -30418,
+            Error::NoData => 61,
+        }
+    }
+}
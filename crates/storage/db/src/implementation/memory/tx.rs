@@ -0,0 +1,109 @@
+//! Transactions of the in-memory database.
+
+use super::{cursor::Cursor, Tables, TransactionKind, WriterHandle, RW};
+use crate::DatabaseError;
+use parking_lot::Mutex;
+use reth_db_api::{
+    cursor::DbCursorRW,
+    table::{DupSort, Encode, Table, TableImporter},
+    transaction::{DbTx, DbTxMut},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// In-memory database transaction.
+///
+/// Tables are shared with the cursors of the transaction, so changes made through a cursor are
+/// immediately visible to the transaction and its other cursors.
+#[derive(Debug)]
+pub struct Tx<K: TransactionKind> {
+    /// Tables as seen by this transaction.
+    tables: Arc<Mutex<Tables>>,
+    /// Writer slot of the database, only set for read-write transactions.
+    writer: Option<WriterHandle>,
+    _kind: PhantomData<K>,
+}
+
+impl<K: TransactionKind> Tx<K> {
+    pub(crate) fn new(tables: Tables, writer: Option<WriterHandle>) -> Self {
+        Self { tables: Arc::new(Mutex::new(tables)), writer, _kind: PhantomData }
+    }
+
+    /// Creates a new cursor over table `T`.
+    pub fn new_cursor<T: Table>(&self) -> Cursor<K, T> {
+        Cursor::new(self.tables.clone())
+    }
+}
+
+impl TableImporter for Tx<RW> {}
+
+impl<K: TransactionKind> DbTx for Tx<K> {
+    type Cursor<T: Table> = Cursor<K, T>;
+    type DupCursor<T: DupSort> = Cursor<K, T>;
+
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<T::Value>, DatabaseError> {
+        self.get_by_encoded_key::<T>(&key.encode())
+    }
+
+    fn get_by_encoded_key<T: Table>(
+        &self,
+        key: &<T::Key as Encode>::Encoded,
+    ) -> Result<Option<T::Value>, DatabaseError> {
+        let mut cursor = self.new_cursor::<T>();
+        Ok(cursor.seek_exact_encoded(key.as_ref())?.map(|(_, value)| value))
+    }
+
+    fn commit(self) -> Result<bool, DatabaseError> {
+        if let Some(writer) = &self.writer {
+            writer.commit(self.tables.lock().clone());
+        }
+
+        Ok(true)
+    }
+
+    fn abort(self) {}
+
+    fn cursor_read<T: Table>(&self) -> Result<Self::Cursor<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+
+    fn cursor_dup_read<T: DupSort>(&self) -> Result<Self::DupCursor<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        Ok(self.tables.lock().get(T::NAME).map_or(0, |table| table.len()))
+    }
+
+    fn disable_long_read_transaction_safety(&mut self) {}
+}
+
+impl DbTxMut for Tx<RW> {
+    type CursorMut<T: Table> = Cursor<RW, T>;
+    type DupCursorMut<T: DupSort> = Cursor<RW, T>;
+
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        self.new_cursor::<T>().upsert(key, &value)
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        self.new_cursor::<T>().delete_entry(key, value)
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        self.tables.lock().remove(T::NAME);
+
+        Ok(())
+    }
+
+    fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+
+    fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+}
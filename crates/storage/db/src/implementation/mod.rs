@@ -1,2 +1,6 @@
 #[cfg(feature = "mdbx")]
 pub(crate) mod mdbx;
+pub(crate) mod memory;
+
+#[cfg(all(test, feature = "mdbx"))]
+mod conformance;
@@ -17,6 +17,7 @@
 
 mod implementation;
 pub mod lockfile;
+pub mod memory;
 #[cfg(feature = "mdbx")]
 mod metrics;
 pub mod static_file;
@@ -33,6 +34,7 @@ pub use utils::is_database_empty;
 
 #[cfg(feature = "mdbx")]
 pub use mdbx::{create_db, init_db, open_db, open_db_read_only, DatabaseEnv, DatabaseEnvKind};
+pub use memory::MemoryDatabase;
 
 pub use models::ClientVersion;
 pub use reth_db_api::*;
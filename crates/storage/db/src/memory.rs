@@ -0,0 +1,3 @@
+//! In-memory database, mainly used for tests and ephemeral nodes.
+
+pub use crate::implementation::memory::*;
@@ -2,7 +2,7 @@ use super::mask::{ColumnSelectorOne, ColumnSelectorThree, ColumnSelectorTwo};
 use alloy_primitives::B256;
 use derive_more::{Deref, DerefMut};
 use reth_db_api::table::Decompress;
-use reth_nippy_jar::{DataReader, NippyJar, NippyJarCursor};
+use reth_nippy_jar::{ChecksumVerifier, DataReader, NippyJar, NippyJarCursor};
 use reth_static_file_types::SegmentHeader;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::sync::Arc;
@@ -20,6 +20,11 @@ impl<'a> StaticFileCursor<'a> {
         Ok(Self(NippyJarCursor::with_reader(jar, reader).map_err(ProviderError::other)?))
     }
 
+    /// Verifies the checksums of the rows read by the cursor.
+    pub fn with_checksum_verification(self, verifier: Arc<ChecksumVerifier>) -> Self {
+        Self(self.0.with_checksum_verification(verifier))
+    }
+
     /// Returns the current `BlockNumber` or `TxNumber` of the cursor depending on the kind of
     /// static file segment.
     pub fn number(&self) -> Option<u64> {
@@ -0,0 +1,174 @@
+use crate::{DataReader, NippyJar, NippyJarError, NippyJarHeader};
+use serde::{Deserialize, Serialize};
+use std::{
+    ops::Range,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Checksums over the data of a [`NippyJar`], one per chunk of consecutive rows.
+///
+/// The rows of a jar are stored back to back in the data file, so the data of a chunk is the
+/// contiguous byte range between the offsets of its first row and of the row after its last one.
+/// Every chunk holds `rows_per_checksum` rows, except for the last one which may be partial.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checksums {
+    /// Number of rows covered by each checksum.
+    rows_per_checksum: u64,
+    /// Checksums of the full chunks of rows.
+    chunks: Vec<u64>,
+    /// Checksum of the rows following the last full chunk, if there are any.
+    tail: u64,
+}
+
+impl Checksums {
+    /// Creates empty checksums for chunks of `rows_per_checksum` rows.
+    pub(crate) fn new(rows_per_checksum: u64) -> Self {
+        assert!(rows_per_checksum > 0, "rows per checksum must be greater than zero");
+        Self { rows_per_checksum, chunks: Vec::new(), tail: 0 }
+    }
+
+    /// Returns the number of rows covered by each checksum.
+    pub const fn rows_per_checksum(&self) -> u64 {
+        self.rows_per_checksum
+    }
+
+    /// Returns the number of chunks, including a partial one, for a jar with `rows` rows.
+    pub const fn chunks(&self, rows: usize) -> usize {
+        rows.div_ceil(self.rows_per_checksum as usize)
+    }
+
+    /// Returns the rows of a chunk for a jar with `rows` rows.
+    pub fn chunk_rows(&self, chunk: usize, rows: usize) -> Range<usize> {
+        let start = chunk * self.rows_per_checksum as usize;
+        start..(start + self.rows_per_checksum as usize).min(rows)
+    }
+
+    /// Returns the expected checksum of a chunk.
+    fn expected(&self, chunk: usize) -> u64 {
+        self.chunks.get(chunk).copied().unwrap_or(self.tail)
+    }
+
+    /// Records the checksum of a completed chunk.
+    pub(crate) fn push(&mut self, checksum: u64) {
+        self.chunks.push(checksum);
+        self.tail = 0;
+    }
+
+    /// Records the checksum of the rows after the last full chunk.
+    pub(crate) const fn set_tail(&mut self, checksum: u64) {
+        self.tail = checksum;
+    }
+
+    /// Drops the checksums of all chunks that aren't fully contained in the first `rows` rows.
+    ///
+    /// Returns the first row that isn't covered by a full chunk anymore.
+    pub(crate) fn truncate(&mut self, rows: usize) -> usize {
+        let full_chunks = rows / self.rows_per_checksum as usize;
+        self.chunks.truncate(full_chunks);
+        self.tail = 0;
+        full_chunks * self.rows_per_checksum as usize
+    }
+}
+
+/// Returns the checksum of a finished [`blake3::Hasher`].
+pub(crate) fn finalize(hasher: &blake3::Hasher) -> u64 {
+    let hash = hasher.finalize();
+    u64::from_le_bytes(hash.as_bytes()[..8].try_into().expect("hash has 32 bytes"))
+}
+
+impl<H: NippyJarHeader> NippyJar<H> {
+    /// Returns the byte range of the data file that holds `rows`.
+    pub(crate) fn rows_data_range(
+        &self,
+        reader: &DataReader,
+        rows: Range<usize>,
+    ) -> Result<Range<usize>, NippyJarError> {
+        // There is one offset per column value, followed by the size of the data file.
+        let start = reader.offset(rows.start * self.columns)? as usize;
+        let end = reader.offset(rows.end * self.columns)? as usize;
+        Ok(start..end)
+    }
+
+    /// Returns whether the data of a chunk of rows matches its checksum.
+    ///
+    /// Jars without checksums always match.
+    pub fn verify_chunk(&self, reader: &DataReader, chunk: usize) -> Result<bool, NippyJarError> {
+        let Some(checksums) = &self.checksums else { return Ok(true) };
+
+        let range = self.rows_data_range(reader, checksums.chunk_rows(chunk, self.rows))?;
+        // Corrupted offsets can point anywhere, including outside of the data file.
+        if range.start > range.end || range.end > reader.size() {
+            return Ok(false)
+        }
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(reader.data(range));
+        Ok(finalize(&hasher) == checksums.expected(chunk))
+    }
+
+    /// Verifies the data of all rows against their checksums.
+    ///
+    /// Returns the ranges of rows whose data doesn't match, merging adjacent ranges.
+    pub fn verify_checksums(&self) -> Result<Vec<Range<usize>>, NippyJarError> {
+        let Some(checksums) = &self.checksums else { return Err(NippyJarError::MissingChecksums) };
+
+        let reader = self.open_data_reader()?;
+        let mut corrupted: Vec<Range<usize>> = Vec::new();
+        for chunk in 0..checksums.chunks(self.rows) {
+            if self.verify_chunk(&reader, chunk)? {
+                continue
+            }
+
+            let rows = checksums.chunk_rows(chunk, self.rows);
+            match corrupted.last_mut() {
+                Some(last) if last.end == rows.start => last.end = rows.end,
+                _ => corrupted.push(rows),
+            }
+        }
+
+        Ok(corrupted)
+    }
+}
+
+/// Verifies the checksums of the rows read from a [`NippyJar`], so that corrupted data is reported
+/// instead of being returned.
+///
+/// Each chunk of rows is verified on its first read only. The verifier is meant to be shared by
+/// all cursors over the same jar and [`DataReader`].
+#[derive(Debug)]
+pub struct ChecksumVerifier {
+    /// Whether a chunk has been verified already.
+    verified: Box<[AtomicBool]>,
+}
+
+impl ChecksumVerifier {
+    /// Creates a new verifier for the chunks of the given jar.
+    pub fn new<H: NippyJarHeader>(jar: &NippyJar<H>) -> Self {
+        let chunks =
+            jar.checksums().map(|checksums| checksums.chunks(jar.rows)).unwrap_or_default();
+        Self { verified: (0..chunks).map(|_| AtomicBool::new(false)).collect() }
+    }
+
+    /// Verifies the chunk that contains `row`, unless it has been verified before.
+    pub fn verify_row<H: NippyJarHeader>(
+        &self,
+        jar: &NippyJar<H>,
+        reader: &DataReader,
+        row: usize,
+    ) -> Result<(), NippyJarError> {
+        let Some(checksums) = jar.checksums() else { return Ok(()) };
+
+        let chunk = row / checksums.rows_per_checksum() as usize;
+        let Some(verified) = self.verified.get(chunk) else { return Ok(()) };
+        if verified.load(Ordering::Relaxed) {
+            return Ok(())
+        }
+
+        if !jar.verify_chunk(reader, chunk)? {
+            return Err(NippyJarError::ChecksumMismatch(checksums.chunk_rows(chunk, jar.rows)))
+        }
+        verified.store(true, Ordering::Relaxed);
+
+        Ok(())
+    }
+}
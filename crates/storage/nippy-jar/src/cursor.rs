@@ -1,6 +1,6 @@
 use crate::{
     compression::{Compression, Compressors, Zstd},
-    DataReader, NippyJar, NippyJarError, NippyJarHeader, RefRow,
+    ChecksumVerifier, DataReader, NippyJar, NippyJarError, NippyJarHeader, RefRow,
 };
 use std::{ops::Range, sync::Arc};
 use zstd::bulk::Decompressor;
@@ -16,6 +16,8 @@ pub struct NippyJarCursor<'a, H = ()> {
     internal_buffer: Vec<u8>,
     /// Cursor row position.
     row: u64,
+    /// Verifies the checksums of the rows before they are read, if enabled.
+    checksum_verifier: Option<Arc<ChecksumVerifier>>,
 }
 
 impl<H: NippyJarHeader> std::fmt::Debug for NippyJarCursor<'_, H> {
@@ -34,6 +36,7 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
             // Makes sure that we have enough buffer capacity to decompress any row of data.
             internal_buffer: Vec::with_capacity(max_row_size),
             row: 0,
+            checksum_verifier: None,
         })
     }
 
@@ -50,9 +53,16 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
             // Makes sure that we have enough buffer capacity to decompress any row of data.
             internal_buffer: Vec::with_capacity(max_row_size),
             row: 0,
+            checksum_verifier: None,
         })
     }
 
+    /// Verifies the checksums of the rows with the given verifier before returning them.
+    pub fn with_checksum_verification(mut self, verifier: Arc<ChecksumVerifier>) -> Self {
+        self.checksum_verifier = Some(verifier);
+        self
+    }
+
     /// Returns a reference to the related [`NippyJar`]
     pub const fn jar(&self) -> &NippyJar<H> {
         self.jar
@@ -83,6 +93,10 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
             return Ok(None)
         }
 
+        if let Some(verifier) = &self.checksum_verifier {
+            verifier.verify_row(self.jar, &self.reader, self.row as usize)?;
+        }
+
         let mut row = Vec::with_capacity(self.jar.columns);
 
         // Retrieve all column values from the row
@@ -123,6 +137,10 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
             return Ok(None)
         }
 
+        if let Some(verifier) = &self.checksum_verifier {
+            verifier.verify_row(self.jar, &self.reader, self.row as usize)?;
+        }
+
         let columns = self.jar.columns;
         let mut row = Vec::with_capacity(columns);
 
@@ -168,10 +186,8 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
                     // If we are here, then for sure we have the necessary dictionaries and they're
                     // loaded (happens during deserialization). Otherwise, there's an issue
                     // somewhere else and we can't recover here anyway.
-                    let dictionaries = z.dictionaries.as_ref().expect("dictionaries to exist")
-                        [column]
-                        .loaded()
-                        .expect("dictionary to be loaded");
+                    let dictionaries =
+                        z.dictionaries.as_ref().expect("dictionaries to exist")[column].loaded();
                     let mut decompressor = Decompressor::with_prepared_dictionary(dictionaries)?;
                     Zstd::decompress_with_dictionary(
                         self.reader.data(column_offset_range),
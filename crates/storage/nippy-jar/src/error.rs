@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{ops::Range, path::PathBuf};
 use thiserror::Error;
 
 /// Errors associated with [`crate::NippyJar`].
@@ -92,4 +92,12 @@ pub enum NippyJarError {
     /// A specified file is missing.
     #[error("Missing file: {}", .0.display())]
     MissingFile(PathBuf),
+
+    /// The data of a range of rows does not match its checksum.
+    #[error("checksum mismatch for rows {}..{}", .0.start, .0.end)]
+    ChecksumMismatch(Range<usize>),
+
+    /// The jar has no checksums to verify its data against.
+    #[error("jar has no checksums")]
+    MissingChecksums,
 }
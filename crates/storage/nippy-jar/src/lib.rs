@@ -16,18 +16,18 @@ use memmap2::Mmap;
 use serde::{Deserialize, Serialize};
 use std::{
     error::Error as StdError,
+    ffi::OsString,
     fs::File,
     io::Read,
     ops::Range,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use tracing::*;
 
 /// Compression algorithms supported by `NippyJar`.
 pub mod compression;
-#[cfg(test)]
-use compression::Compression;
-use compression::Compressors;
+use compression::{Compression, Compressors};
 
 /// empty enum for backwards compatibility
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,14 +51,30 @@ pub use writer::NippyJarWriter;
 mod consistency;
 pub use consistency::NippyJarChecker;
 
+mod checksum;
+pub use checksum::{ChecksumVerifier, Checksums};
+
 /// The version number of the Nippy Jar format.
-const NIPPY_JAR_VERSION: usize = 1;
+const NIPPY_JAR_VERSION: usize = 3;
+/// The first version of the Nippy Jar format whose configuration includes checksums.
+const NIPPY_JAR_CHECKSUMS_VERSION: usize = 2;
+/// The first version of the Nippy Jar format whose configuration includes the version of the
+/// compression dictionaries.
+const NIPPY_JAR_DICTIONARY_VERSIONING_VERSION: usize = 3;
 /// The file extension used for index files.
 const INDEX_FILE_EXTENSION: &str = "idx";
 /// The file extension used for offsets files.
 const OFFSETS_FILE_EXTENSION: &str = "off";
 /// The file extension used for configuration files.
 pub const CONFIG_FILE_EXTENSION: &str = "conf";
+/// Number of rows after which a rewrite commits the written rows.
+const REWRITE_COMMIT_INTERVAL: usize = 100_000;
+/// Prefix of the file name of a jar that is being rewritten.
+///
+/// A prefix keeps the rewritten files from being mistaken for the static files they replace.
+const REWRITE_PREFIX: &str = "rewrite_";
+/// The file extension of the file marking a rewritten jar as complete.
+const REWRITE_COMPLETE_EXTENSION: &str = "complete";
 
 /// A [`RefRow`] is a list of column value slices pointing to either an internal buffer or a
 /// memory-mapped file.
@@ -106,11 +122,78 @@ pub struct NippyJar<H = ()> {
     /// Maximum uncompressed row size of the set. This will enable decompression without any
     /// resizing of the output buffer.
     max_row_size: usize,
+    /// Optional checksums of the row data.
+    checksums: Option<Checksums>,
+    /// Version of the compression dictionaries the data was compressed with, if it was recompressed
+    /// with trained dictionaries.
+    dictionary_version: Option<u64>,
     /// Data path for file. Supporting files will have a format `{path}.{extension}`.
     #[serde(skip)]
     path: PathBuf,
 }
 
+/// Configuration of [`NippyJar`] files written before checksums were introduced.
+#[derive(Deserialize)]
+struct NippyJarV1<H> {
+    version: usize,
+    user_header: H,
+    columns: usize,
+    rows: usize,
+    compressor: Option<Compressors>,
+    max_row_size: usize,
+}
+
+impl<H> From<NippyJarV1<H>> for NippyJar<H> {
+    fn from(jar: NippyJarV1<H>) -> Self {
+        let NippyJarV1 { version, user_header, columns, rows, compressor, max_row_size } = jar;
+        Self {
+            version,
+            user_header,
+            columns,
+            rows,
+            compressor,
+            filter: None,
+            phf: None,
+            max_row_size,
+            checksums: None,
+            dictionary_version: None,
+            path: PathBuf::new(),
+        }
+    }
+}
+
+/// Configuration of [`NippyJar`] files written before dictionary versions were introduced.
+#[derive(Deserialize)]
+struct NippyJarV2<H> {
+    version: usize,
+    user_header: H,
+    columns: usize,
+    rows: usize,
+    compressor: Option<Compressors>,
+    max_row_size: usize,
+    checksums: Option<Checksums>,
+}
+
+impl<H> From<NippyJarV2<H>> for NippyJar<H> {
+    fn from(jar: NippyJarV2<H>) -> Self {
+        let NippyJarV2 { version, user_header, columns, rows, compressor, max_row_size, checksums } =
+            jar;
+        Self {
+            version,
+            user_header,
+            columns,
+            rows,
+            compressor,
+            filter: None,
+            phf: None,
+            max_row_size,
+            checksums,
+            dictionary_version: None,
+            path: PathBuf::new(),
+        }
+    }
+}
+
 impl<H: NippyJarHeader> std::fmt::Debug for NippyJar<H> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("NippyJar")
@@ -123,6 +206,7 @@ impl<H: NippyJarHeader> std::fmt::Debug for NippyJar<H> {
             .field("phf", &self.phf)
             .field("path", &self.path)
             .field("max_row_size", &self.max_row_size)
+            .field("dictionary_version", &self.dictionary_version)
             .finish_non_exhaustive()
     }
 }
@@ -151,6 +235,8 @@ impl<H: NippyJarHeader> NippyJar<H> {
             compressor: None,
             filter: None,
             phf: None,
+            checksums: None,
+            dictionary_version: None,
             path: path.to_path_buf(),
         }
     }
@@ -168,6 +254,17 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Adds [`Checksums`] over every `rows_per_checksum` rows.
+    ///
+    /// Only takes effect on jars without any rows, since the checksums of existing rows are
+    /// unknown.
+    pub fn with_checksums(mut self, rows_per_checksum: u64) -> Self {
+        if self.rows == 0 {
+            self.checksums = Some(Checksums::new(rows_per_checksum));
+        }
+        self
+    }
+
     /// Gets a reference to the user header.
     pub const fn user_header(&self) -> &H {
         &self.user_header
@@ -193,10 +290,24 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self.compressor.as_mut()
     }
 
+    /// Gets a reference to the checksums of the row data.
+    pub const fn checksums(&self) -> Option<&Checksums> {
+        self.checksums.as_ref()
+    }
+
+    /// Gets the version of the compression dictionaries, if the jar was recompressed with trained
+    /// dictionaries.
+    pub const fn dictionary_version(&self) -> Option<u64> {
+        self.dictionary_version
+    }
+
     /// Loads the file configuration and returns [`Self`].
     ///
     /// **The user must ensure the header type matches the one used during the jar's creation.**
     pub fn load(path: &Path) -> Result<Self, NippyJarError> {
+        // A rewrite that was interrupted while moving its files in place has to be finished first.
+        finish_rewrite(path)?;
+
         // Read [`Self`] located at the data file.
         let config_path = path.with_extension(CONFIG_FILE_EXTENSION);
         let config_file = File::open(&config_path)
@@ -208,8 +319,20 @@ impl<H: NippyJarHeader> NippyJar<H> {
     }
 
     /// Deserializes an instance of [`Self`] from a [`Read`] type.
-    pub fn load_from_reader<R: Read>(reader: R) -> Result<Self, NippyJarError> {
-        Ok(bincode::deserialize_from(reader)?)
+    pub fn load_from_reader<R: Read>(mut reader: R) -> Result<Self, NippyJarError> {
+        let mut config = Vec::new();
+        reader.read_to_end(&mut config)?;
+
+        // The version is the first field, older versions don't have all of the current fields.
+        let version: usize = bincode::deserialize(&config)?;
+        if version < NIPPY_JAR_CHECKSUMS_VERSION {
+            return Ok(bincode::deserialize::<NippyJarV1<H>>(&config)?.into())
+        }
+        if version < NIPPY_JAR_DICTIONARY_VERSIONING_VERSION {
+            return Ok(bincode::deserialize::<NippyJarV2<H>>(&config)?.into())
+        }
+
+        Ok(bincode::deserialize(&config)?)
     }
 
     /// Returns the path for the data file
@@ -253,17 +376,150 @@ impl<H: NippyJarHeader> NippyJar<H> {
         DataReader::new(self.data_path())
     }
 
-    /// Writes all necessary configuration to file.
-    fn freeze_config(&self) -> Result<(), NippyJarError> {
-        Ok(reth_fs_util::atomic_write_file(&self.config_path(), |file| {
-            bincode::serialize_into(file, &self)
-        })?)
+    /// Rewrites the jar, taking the rows in `replaced` from `replace` instead of the data file.
+    ///
+    /// This is meant to repair rows whose data doesn't match their checksums, see
+    /// [`Self::verify_checksums`]. `replace` returns the uncompressed column values of a row. The
+    /// jar is rewritten next to the current one and moved in place once it's complete.
+    pub fn rewrite_rows<F>(
+        self,
+        replaced: &[Range<usize>],
+        mut replace: F,
+    ) -> Result<Self, NippyJarError>
+    where
+        F: FnMut(usize) -> Result<Vec<Vec<u8>>, NippyJarError>,
+    {
+        debug!(target: "nippy-jar", path = ?self.data_path(), ?replaced, "Rewriting jar.");
+
+        self.rewrite(
+            |_| {},
+            |row, cursor| {
+                if replaced.iter().any(|range| range.contains(&row)) {
+                    return replace(row)
+                }
+                let values = cursor
+                    .row_by_number(row)?
+                    .ok_or(NippyJarError::UnexpectedMissingValue(row as u64, 0))?;
+                Ok(values.into_iter().map(<[u8]>::to_vec).collect())
+            },
+        )
     }
-}
 
-#[cfg(test)]
-impl<H: NippyJarHeader> NippyJar<H> {
-    /// If required, prepares any compression algorithm to an early pass of the data.
+    /// Rewrites the jar compressing its data with `compressor`, and records `dictionary_version` as
+    /// the version of its compression dictionaries.
+    ///
+    /// `map_row` receives the uncompressed column values of every row and returns the ones to
+    /// write, e.g. to undo a compression done by the values themselves. The jar is rewritten next
+    /// to the current one and moved in place once it's complete.
+    pub fn recompress<F>(
+        self,
+        compressor: Compressors,
+        dictionary_version: Option<u64>,
+        mut map_row: F,
+    ) -> Result<Self, NippyJarError>
+    where
+        F: FnMut(Vec<&[u8]>) -> Result<Vec<Vec<u8>>, NippyJarError>,
+    {
+        if !compressor.is_ready() {
+            return Err(NippyJarError::CompressorNotReady)
+        }
+
+        debug!(target: "nippy-jar", path = ?self.data_path(), ?dictionary_version, "Recompressing jar.");
+
+        self.rewrite(
+            |jar| {
+                jar.compressor = Some(compressor);
+                jar.dictionary_version = dictionary_version;
+            },
+            |row, cursor| {
+                let values = cursor
+                    .row_by_number(row)?
+                    .ok_or(NippyJarError::UnexpectedMissingValue(row as u64, 0))?;
+                map_row(values)
+            },
+        )
+    }
+
+    /// Rewrites every row of the jar next to the current one, with the configuration changes of
+    /// `configure`, and moves it in place once it's complete.
+    ///
+    /// `row` returns the uncompressed column values of a row, given a cursor over the current jar.
+    fn rewrite<C, F>(self, configure: C, row: F) -> Result<Self, NippyJarError>
+    where
+        C: FnOnce(&mut Self),
+        F: FnMut(usize, &mut NippyJarCursor<'_, H>) -> Result<Vec<Vec<u8>>, NippyJarError>,
+    {
+        let mut jar = self.write_rewrite(configure, row)?;
+        finish_rewrite(&self.path)?;
+        jar.path = self.path;
+
+        Ok(jar)
+    }
+
+    /// Writes the rewritten jar next to the current one and marks it as complete.
+    ///
+    /// From then on, the rewritten files replace the current ones: if moving them in place is
+    /// interrupted, it's finished by the next [`Self::load`]. Until then, the current files are
+    /// left untouched and an interrupted rewrite is discarded.
+    fn write_rewrite<C, F>(&self, configure: C, mut row: F) -> Result<Self, NippyJarError>
+    where
+        C: FnOnce(&mut Self),
+        F: FnMut(usize, &mut NippyJarCursor<'_, H>) -> Result<Vec<Vec<u8>>, NippyJarError>,
+    {
+        let rewrite_path = rewrite_path(&self.path)?;
+
+        // Same configuration, but without any rows.
+        let mut jar: Self = bincode::deserialize(&bincode::serialize(self)?)?;
+        jar.path = rewrite_path.clone();
+        jar.rows = 0;
+        jar.max_row_size = 0;
+        jar.checksums =
+            self.checksums.as_ref().map(|checksums| Checksums::new(checksums.rows_per_checksum()));
+        configure(&mut jar);
+
+        // Leftovers of an interrupted rewrite.
+        for path in [
+            jar.data_path().into(),
+            jar.offsets_path(),
+            jar.config_path(),
+            rewrite_path.with_extension(REWRITE_COMPLETE_EXTENSION),
+        ] {
+            if path.exists() {
+                reth_fs_util::remove_file(path)?;
+            }
+        }
+
+        let mut writer = NippyJarWriter::new(jar)?;
+        let mut cursor = NippyJarCursor::with_reader(self, Arc::new(self.open_data_reader()?))?;
+        for number in 0..self.rows {
+            let values = row(number, &mut cursor)?;
+            if values.len() != self.columns {
+                return Err(NippyJarError::ColumnLenMismatch(self.columns, values.len()))
+            }
+            for value in values {
+                writer.append_column(Some(Ok(value)))?;
+            }
+
+            if (number + 1) % REWRITE_COMMIT_INTERVAL == 0 {
+                writer.commit()?;
+            }
+        }
+        writer.commit()?;
+        // The data file can't be replaced on Windows while it's memory mapped.
+        drop(cursor);
+
+        // All files of the rewritten jar are synced, so this is the single step that switches to
+        // them.
+        reth_fs_util::atomic_write_file(
+            &rewrite_path.with_extension(REWRITE_COMPLETE_EXTENSION),
+            |_| Ok::<_, std::io::Error>(()),
+        )?;
+
+        Ok(writer.into_jar())
+    }
+
+    /// If required, prepares any compression algorithm to an early pass of the data, e.g. training
+    /// zstd dictionaries.
     pub fn prepare_compression(
         &mut self,
         columns: Vec<impl IntoIterator<Item = Vec<u8>>>,
@@ -276,6 +532,56 @@ impl<H: NippyJarHeader> NippyJar<H> {
         Ok(())
     }
 
+    /// Writes all necessary configuration to file.
+    fn freeze_config(&self) -> Result<(), NippyJarError> {
+        Ok(reth_fs_util::atomic_write_file(&self.config_path(), |file| {
+            bincode::serialize_into(file, &self)
+        })?)
+    }
+}
+
+/// Returns the path of the data file of the rewrite of the jar at `path`.
+fn rewrite_path(path: &Path) -> Result<PathBuf, NippyJarError> {
+    let file_name = path.file_name().ok_or_else(|| NippyJarError::MissingFile(path.to_path_buf()))?;
+    let mut rewrite_file_name = OsString::from(REWRITE_PREFIX);
+    rewrite_file_name.push(file_name);
+    Ok(path.with_file_name(rewrite_file_name))
+}
+
+/// Moves the files of a complete rewrite of the jar at `path` in place, if there is one.
+///
+/// The rewrite is marked as complete only after all of its files are written, so the files that
+/// weren't moved yet are still there after an interruption.
+fn finish_rewrite(path: &Path) -> Result<(), NippyJarError> {
+    let rewrite_path = rewrite_path(path)?;
+    let complete_path = rewrite_path.with_extension(REWRITE_COMPLETE_EXTENSION);
+    if !complete_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "nippy-jar", ?path, "Moving rewritten jar in place.");
+    for (from, to) in [
+        (rewrite_path.clone(), path.to_path_buf()),
+        (
+            rewrite_path.with_extension(OFFSETS_FILE_EXTENSION),
+            path.with_extension(OFFSETS_FILE_EXTENSION),
+        ),
+        (
+            rewrite_path.with_extension(CONFIG_FILE_EXTENSION),
+            path.with_extension(CONFIG_FILE_EXTENSION),
+        ),
+    ] {
+        if from.exists() {
+            reth_fs_util::rename(from, to)?;
+        }
+    }
+    reth_fs_util::remove_file(complete_path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+impl<H: NippyJarHeader> NippyJar<H> {
     /// Writes all data and configuration to a file and the offset index to another.
     pub fn freeze(
         self,
@@ -421,9 +727,8 @@ impl DataReader {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use compression::Compression;
     use rand::{rngs::SmallRng, seq::SliceRandom, RngCore, SeedableRng};
-    use std::{fs::OpenOptions, io::Read};
+    use std::{fs, fs::OpenOptions, io::Read};
 
     type ColumnResults<T> = Vec<ColumnResult<T>>;
     type ColumnValues = Vec<Vec<u8>>;
@@ -459,15 +764,15 @@ mod tests {
 
         let mut config_file = OpenOptions::new().read(true).open(jar.config_path()).unwrap();
         let config_file_len = config_file.metadata().unwrap().len();
-        assert_eq!(config_file_len, 37);
+        assert_eq!(config_file_len, 39);
 
         let mut buf = Vec::with_capacity(config_file_len as usize);
         config_file.read_to_end(&mut buf).unwrap();
 
         assert_eq!(
             vec![
-                1, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0,
-                0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+                3, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
             ],
             buf
         );
@@ -478,6 +783,37 @@ mod tests {
         assert_eq!(jar, read_jar);
     }
 
+    #[test]
+    fn test_config_without_checksums() {
+        // Configuration of the first version, which has no checksums.
+        let buf = [
+            1, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let read_jar: NippyJar = NippyJar::load_from_reader(&buf[..]).unwrap();
+        assert_eq!(read_jar.version, 1);
+        assert_eq!(read_jar.columns, 23);
+        assert!(matches!(read_jar.compressor, Some(Compressors::Lz4(_))));
+        assert_eq!(read_jar.checksums, None);
+    }
+
+    #[test]
+    fn test_config_without_dictionary_version() {
+        // Configuration of the second version, which has no dictionary version.
+        let buf = [
+            2, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let read_jar: NippyJar = NippyJar::load_from_reader(&buf[..]).unwrap();
+        assert_eq!(read_jar.version, 2);
+        assert_eq!(read_jar.columns, 23);
+        assert!(matches!(read_jar.compressor, Some(Compressors::Lz4(_))));
+        assert_eq!(read_jar.checksums, None);
+        assert_eq!(read_jar.dictionary_version, None);
+    }
+
     #[test]
     fn test_zstd_with_dictionaries() {
         let (col1, col2) = test_data(None);
@@ -1071,4 +1407,195 @@ mod tests {
         // runs the consistency check.
         let _ = NippyJarWriter::new(nippy).unwrap();
     }
+
+    /// Writes `rows` rows of `col1` and `col2` into a jar with a checksum every 10 rows.
+    fn freeze_with_checksums(file_path: &Path, col1: &[Vec<u8>], col2: &[Vec<u8>], rows: usize) {
+        let nippy = NippyJar::new_without_header(2, file_path).with_lz4().with_checksums(10);
+        let columns = vec![
+            clone_with_result(&col1[..rows].to_vec()),
+            clone_with_result(&col2[..rows].to_vec()),
+        ];
+        nippy.freeze(columns, rows as u64).unwrap();
+    }
+
+    #[test]
+    fn test_checksums() {
+        let (col1, col2) = test_data(None);
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        freeze_with_checksums(file_path.path(), &col1, &col2, 95);
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.checksums().unwrap().chunks(nippy.rows), 10);
+        assert_eq!(nippy.verify_checksums().unwrap(), Vec::<Range<usize>>::new());
+
+        // Corrupt a byte of row 42 and of the partial chunk at the end.
+        let reader = nippy.open_data_reader().unwrap();
+        let corrupted_offsets = [reader.offset(42 * 2).unwrap(), reader.offset(93 * 2).unwrap()];
+        drop(reader);
+        let mut data = fs::read(nippy.data_path()).unwrap();
+        for offset in corrupted_offsets {
+            data[offset as usize] ^= 0xff;
+        }
+        fs::write(nippy.data_path(), data).unwrap();
+
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.verify_checksums().unwrap(), vec![40..50, 90..95]);
+
+        // Reading a corrupted row fails when verifying on read, while other rows can be read.
+        let reader = Arc::new(nippy.open_data_reader().unwrap());
+        let verifier = Arc::new(ChecksumVerifier::new(&nippy));
+        let mut cursor = NippyJarCursor::with_reader(&nippy, reader)
+            .unwrap()
+            .with_checksum_verification(verifier);
+        assert!(
+            matches!(cursor.row_by_number(45), Err(NippyJarError::ChecksumMismatch(rows)) if rows == (40..50))
+        );
+        assert_eq!(cursor.row_by_number(12).unwrap().unwrap(), vec![&col1[12][..], &col2[12][..]]);
+        drop(cursor);
+
+        // Repair the corrupted rows.
+        let corrupted = nippy.verify_checksums().unwrap();
+        let nippy = nippy
+            .rewrite_rows(&corrupted, |row| Ok(vec![col1[row].clone(), col2[row].clone()]))
+            .unwrap();
+        assert_eq!(nippy.verify_checksums().unwrap(), Vec::<Range<usize>>::new());
+
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.rows, 95);
+        assert_eq!(nippy.verify_checksums().unwrap(), Vec::<Range<usize>>::new());
+        let mut cursor = NippyJarCursor::new(&nippy).unwrap();
+        for row in 0..95 {
+            assert_eq!(cursor.next_row().unwrap().unwrap(), vec![&col1[row][..], &col2[row][..]]);
+        }
+    }
+
+    #[test]
+    fn test_recompress() {
+        let (col1, col2) = test_data(None);
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        freeze_with_checksums(file_path.path(), &col1, &col2, 80);
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.dictionary_version(), None);
+
+        // Dictionaries have to be trained before recompressing.
+        let mut zstd = compression::Zstd::new(true, 5000, 2);
+        let untrained = NippyJar::load_without_header(file_path.path()).unwrap().recompress(
+            Compressors::Zstd(zstd.clone()),
+            Some(1),
+            |row| Ok(row.into_iter().map(<[u8]>::to_vec).collect()),
+        );
+        assert!(matches!(untrained, Err(NippyJarError::CompressorNotReady)));
+        zstd.prepare_compression(vec![col1.clone(), col2.clone()]).unwrap();
+
+        let nippy = nippy
+            .recompress(Compressors::Zstd(zstd), Some(1), |row| {
+                Ok(row.into_iter().map(<[u8]>::to_vec).collect())
+            })
+            .unwrap();
+        assert_eq!(nippy.dictionary_version(), Some(1));
+        assert_eq!(nippy.verify_checksums().unwrap(), Vec::<Range<usize>>::new());
+
+        // Rows appended after reopening the jar are compressed with the same dictionaries.
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.dictionary_version(), Some(1));
+        assert!(matches!(nippy.compressor(), Some(Compressors::Zstd(zstd)) if zstd.use_dict));
+        let mut writer = NippyJarWriter::new(nippy).unwrap();
+        writer
+            .append_rows(
+                vec![
+                    clone_with_result(&col1[80..].to_vec()),
+                    clone_with_result(&col2[80..].to_vec()),
+                ],
+                20,
+            )
+            .unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.rows, 100);
+        assert_eq!(nippy.verify_checksums().unwrap(), Vec::<Range<usize>>::new());
+        let mut cursor = NippyJarCursor::new(&nippy).unwrap();
+        for row in 0..100 {
+            assert_eq!(cursor.next_row().unwrap().unwrap(), vec![&col1[row][..], &col2[row][..]]);
+        }
+    }
+
+    #[test]
+    fn test_interrupted_rewrite() {
+        let (col1, col2) = test_data(None);
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+        let mut zstd = compression::Zstd::new(true, 5000, 2);
+        zstd.prepare_compression(vec![col1.clone(), col2.clone()]).unwrap();
+        let configure = |jar: &mut NippyJar| {
+            jar.compressor = Some(Compressors::Zstd(zstd.clone()));
+            jar.dictionary_version = Some(1);
+        };
+        let row = |row: usize, cursor: &mut NippyJarCursor<'_>| {
+            Ok(cursor.row_by_number(row)?.unwrap().into_iter().map(<[u8]>::to_vec).collect())
+        };
+
+        // Interrupted before the rewrite is complete: the current jar is kept.
+        freeze_with_checksums(file_path.path(), &col1, &col2, 80);
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let rewrite = nippy.write_rewrite(configure, row).unwrap();
+        reth_fs_util::remove_file(
+            rewrite_path(file_path.path()).unwrap().with_extension(REWRITE_COMPLETE_EXTENSION),
+        )
+        .unwrap();
+        drop(rewrite);
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.dictionary_version(), None);
+        assert_eq!(nippy.verify_checksums().unwrap(), Vec::<Range<usize>>::new());
+
+        // Interrupted after moving the data file in place: the rest is moved on load.
+        let rewrite = nippy.write_rewrite(configure, row).unwrap();
+        reth_fs_util::rename(rewrite.data_path(), nippy.data_path()).unwrap();
+        drop((nippy, rewrite));
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.dictionary_version(), Some(1));
+        assert_eq!(nippy.verify_checksums().unwrap(), Vec::<Range<usize>>::new());
+        assert!(!rewrite_path(file_path.path()).unwrap().exists());
+        let mut cursor = NippyJarCursor::new(&nippy).unwrap();
+        for row in 0..80 {
+            assert_eq!(cursor.next_row().unwrap().unwrap(), vec![&col1[row][..], &col2[row][..]]);
+        }
+    }
+
+    #[test]
+    fn test_checksums_append_and_prune() {
+        let (col1, col2) = test_data(None);
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        freeze_with_checksums(file_path.path(), &col1, &col2, 25);
+
+        // Append across a chunk boundary after reopening the jar.
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let mut writer = NippyJarWriter::new(nippy).unwrap();
+        writer
+            .append_rows(
+                vec![
+                    clone_with_result(&col1[25..48].to_vec()),
+                    clone_with_result(&col2[25..48].to_vec()),
+                ],
+                23,
+            )
+            .unwrap();
+        writer.commit().unwrap();
+
+        // Prune into the previous chunk.
+        writer.prune_rows(11).unwrap();
+        drop(writer);
+
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(nippy.rows, 37);
+        assert_eq!(nippy.verify_checksums().unwrap(), Vec::<Range<usize>>::new());
+
+        // The checksums match the ones of a jar written in one go.
+        let expected_path = tempfile::NamedTempFile::new().unwrap();
+        freeze_with_checksums(expected_path.path(), &col1, &col2, 37);
+        let expected = NippyJar::load_without_header(expected_path.path()).unwrap();
+        assert_eq!(nippy.checksums(), expected.checksums());
+    }
 }
@@ -1,12 +1,14 @@
 use crate::{
-    compression::Compression, ColumnResult, NippyJar, NippyJarChecker, NippyJarError,
-    NippyJarHeader,
+    checksum,
+    compression::{Compression, Compressors, Zstd},
+    ColumnResult, NippyJar, NippyJarChecker, NippyJarError, NippyJarHeader,
 };
 use std::{
     fs::{File, OpenOptions},
     io::{BufWriter, Read, Seek, SeekFrom, Write},
     path::Path,
 };
+use zstd::bulk::Compressor;
 
 /// Size of one offset in bytes.
 pub(crate) const OFFSET_SIZE_BYTES: u8 = 8;
@@ -35,6 +37,8 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     offsets_file: BufWriter<File>,
     /// Temporary buffer to reuse when compressing data.
     tmp_buf: Vec<u8>,
+    /// Compressors with the column dictionaries, if the jar is compressed with zstd dictionaries.
+    dictionary_compressors: Option<DictionaryCompressors>,
     /// Used to find the maximum uncompressed size of a row in a jar.
     uncompressed_row_size: usize,
     /// Partial offset list which hasn't been flushed to disk.
@@ -43,6 +47,8 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     column: usize,
     /// Whether the writer has changed data that needs to be committed.
     dirty: bool,
+    /// Hasher over the data of the rows after the last full checksum chunk.
+    checksum_hasher: blake3::Hasher,
 }
 
 impl<H: NippyJarHeader> NippyJarWriter<H> {
@@ -50,6 +56,16 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     ///
     /// If will **always** attempt to heal any inconsistent state when called.
     pub fn new(jar: NippyJar<H>) -> Result<Self, NippyJarError> {
+        let dictionary_compressors = match &jar.compressor {
+            Some(Compressors::Zstd(zstd)) => zstd.compressors()?.map(DictionaryCompressors),
+            _ => None,
+        };
+        if let Some(compressors) = &dictionary_compressors {
+            if compressors.0.len() != jar.columns {
+                return Err(NippyJarError::ColumnLenMismatch(jar.columns, compressors.0.len()))
+            }
+        }
+
         let (data_file, offsets_file, is_created) =
             Self::create_or_open_files(jar.data_path(), &jar.offsets_path())?;
 
@@ -75,13 +91,19 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             data_file,
             offsets_file,
             tmp_buf: Vec::with_capacity(1_000_000),
+            dictionary_compressors,
             uncompressed_row_size: 0,
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
             dirty: false,
+            checksum_hasher: blake3::Hasher::new(),
         };
 
         if !is_created {
+            // Rows after the last full checksum chunk need to be hashed again to continue
+            // appending.
+            writer.resume_checksums()?;
+
             // Commit any potential heals done above.
             writer.commit()?;
         }
@@ -229,11 +251,25 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         self.uncompressed_row_size += value.len();
         let len = if let Some(compression) = &self.jar.compressor {
             let before = self.tmp_buf.len();
-            let len = compression.compress_to(value, &mut self.tmp_buf)?;
-            self.data_file.write_all(&self.tmp_buf[before..before + len])?;
+            let len = match &mut self.dictionary_compressors {
+                Some(compressors) => Zstd::compress_to_with_dictionary(
+                    value,
+                    &mut self.tmp_buf,
+                    &mut compressors.0[self.column],
+                )?,
+                None => compression.compress_to(value, &mut self.tmp_buf)?,
+            };
+            let written = &self.tmp_buf[before..before + len];
+            self.data_file.write_all(written)?;
+            if self.jar.checksums.is_some() {
+                self.checksum_hasher.update(written);
+            }
             len
         } else {
             self.data_file.write_all(value)?;
+            if self.jar.checksums.is_some() {
+                self.checksum_hasher.update(value);
+            }
             value.len()
         };
 
@@ -324,17 +360,62 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         if self.jar.rows == 0 {
             self.jar.max_row_size = 0;
         }
+
+        if self.jar.checksums.is_some() {
+            // The checksums are recomputed from the offsets on disk.
+            self.commit_offsets()?;
+            self.resume_checksums()?;
+            self.commit_checksums();
+        }
+
         self.jar.freeze_config()?;
 
         Ok(())
     }
 
+    /// Drops the checksums of rows that no longer exist and hashes the rows after the last full
+    /// checksum chunk again, so that appending can continue.
+    fn resume_checksums(&mut self) -> Result<(), NippyJarError> {
+        let rows = self.jar.rows;
+        let Some(tail_start) =
+            self.jar.checksums.as_mut().map(|checksums| checksums.truncate(rows))
+        else {
+            return Ok(())
+        };
+
+        self.checksum_hasher.reset();
+        if tail_start < rows {
+            let reader = self.jar.open_data_reader()?;
+            let range = self.jar.rows_data_range(&reader, tail_start..rows)?;
+            self.checksum_hasher.update(reader.data(range));
+        }
+
+        Ok(())
+    }
+
+    /// Records the checksum of the rows after the last full checksum chunk.
+    fn commit_checksums(&mut self) {
+        let rows = self.jar.rows as u64;
+        if let Some(checksums) = &mut self.jar.checksums {
+            if rows % checksums.rows_per_checksum() != 0 {
+                checksums.set_tail(checksum::finalize(&self.checksum_hasher));
+            }
+        }
+    }
+
     /// Updates [`NippyJar`] with the new row count and maximum uncompressed row size, while
     /// resetting internal fields.
     fn finalize_row(&mut self) {
         self.jar.max_row_size = self.jar.max_row_size.max(self.uncompressed_row_size);
         self.jar.rows += 1;
 
+        if let Some(checksums) = &mut self.jar.checksums {
+            if self.jar.rows as u64 % checksums.rows_per_checksum() == 0 {
+                checksums.push(checksum::finalize(&self.checksum_hasher));
+                self.checksum_hasher.reset();
+            }
+        }
+
         self.tmp_buf.clear();
         self.uncompressed_row_size = 0;
         self.column = 0;
@@ -346,8 +427,9 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         self.data_file.get_ref().sync_all()?;
 
         self.commit_offsets()?;
+        self.commit_checksums();
 
-        // Flushes `max_row_size` and total `rows` to disk.
+        // Flushes `max_row_size`, total `rows` and checksums to disk.
         self.jar.freeze_config()?;
         self.dirty = false;
 
@@ -363,8 +445,9 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         self.data_file.flush()?;
 
         self.commit_offsets_without_sync_all()?;
+        self.commit_checksums();
 
-        // Flushes `max_row_size` and total `rows` to disk.
+        // Flushes `max_row_size`, total `rows` and checksums to disk.
         self.jar.freeze_config()?;
         self.dirty = false;
 
@@ -464,3 +547,12 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         &self.jar
     }
 }
+
+/// Compressors with the column dictionaries of a jar, in column order.
+struct DictionaryCompressors(Vec<Compressor<'static>>);
+
+impl std::fmt::Debug for DictionaryCompressors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DictionaryCompressors").field("num", &self.0.len()).finish_non_exhaustive()
+    }
+}
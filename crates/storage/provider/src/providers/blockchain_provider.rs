@@ -36,8 +36,8 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, NodePrimitivesProvider, StateCommitmentProvider,
-    StorageChangeSetReader,
+    BlockBodyIndicesProvider, DBProvider, LogIndexMatches, LogIndexReader, NodePrimitivesProvider,
+    StateCommitmentProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
@@ -416,6 +416,17 @@ impl<N: ProviderNodeTypes> TransactionsProvider for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> LogIndexReader for BlockchainProvider<N> {
+    fn log_index_blocks(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<LogIndexMatches>> {
+        self.database.log_index_blocks(range, addresses, topics)
+    }
+}
+
 impl<N: ProviderNodeTypes> ReceiptProvider for BlockchainProvider<N> {
     type Receipt = ReceiptTy<N>;
 
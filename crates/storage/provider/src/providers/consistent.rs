@@ -24,8 +24,8 @@ use reth_primitives_traits::{Account, BlockBody, RecoveredBlock, SealedHeader, S
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    BlockBodyIndicesProvider, DatabaseProviderFactory, LogIndexMatches, LogIndexReader,
+    NodePrimitivesProvider, StateProvider, StorageChangeSetReader, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use revm_database::states::PlainStorageRevert;
@@ -1059,6 +1059,17 @@ impl<N: ProviderNodeTypes> TransactionsProvider for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> LogIndexReader for ConsistentProvider<N> {
+    fn log_index_blocks(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<LogIndexMatches>> {
+        self.storage_provider.log_index_blocks(range, addresses, topics)
+    }
+}
+
 impl<N: ProviderNodeTypes> ReceiptProvider for ConsistentProvider<N> {
     type Receipt = ReceiptTy<N>;
 
@@ -3,7 +3,7 @@ use crate::{
     providers::{
         database::{chain::ChainStorage, metrics},
         static_file::StaticFileWriter,
-        NodeTypesForProvider, StaticFileProvider,
+        NodeTypesForProvider, StaticFileProvider, StaticFileProviderRWRefMut,
     },
     to_range,
     traits::{
@@ -13,10 +13,11 @@ use crate::{
     BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
     DBProvider, HashingWriter, HeaderProvider, HeaderSyncGapProvider, HistoricalStateProvider,
     HistoricalStateProviderRef, HistoryWriter, LatestStateProvider, LatestStateProviderRef,
-    OriginalValuesKnown, ProviderError, PruneCheckpointReader, PruneCheckpointWriter, RevertsInit,
-    StageCheckpointReader, StateCommitmentProvider, StateProviderBox, StateWriter,
-    StaticFileProviderFactory, StatsReader, StorageLocation, StorageReader, StorageTrieWriter,
-    TransactionVariant, TransactionsProvider, TransactionsProviderExt, TrieWriter,
+    LogIndexMatches, LogIndexReader, LogIndexWriter, OriginalValuesKnown, ProviderError,
+    PruneCheckpointReader, PruneCheckpointWriter, RevertsInit, StageCheckpointReader,
+    StateCommitmentProvider, StateProviderBox, StateWriter, StaticFileProviderFactory, StatsReader,
+    StorageLocation, StorageReader, StorageTrieWriter, TransactionVariant, TransactionsProvider,
+    TransactionsProviderExt, TrieWriter,
 };
 use alloy_consensus::{
     transaction::{SignerRecoverable, TransactionMeta},
@@ -26,7 +27,7 @@ use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+    Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256,
 };
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
@@ -36,7 +37,7 @@ use reth_db_api::{
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices,
+        BlockNumberHashedAddress, ShardedKey, StorageBeforeTx, StoredBlockBodyIndices,
     },
     table::Table,
     tables,
@@ -62,7 +63,8 @@ use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
     updates::{StorageTrieUpdates, TrieUpdates},
-    HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
+    HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles, StoredNibblesSubKey,
+    TrieChangeSetsEntry,
 };
 use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor};
 use revm_database::states::{
@@ -72,7 +74,7 @@ use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
-    ops::{Deref, DerefMut, Range, RangeBounds, RangeInclusive},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds, RangeInclusive},
     sync::{mpsc, Arc},
 };
 use tracing::{debug, trace};
@@ -211,6 +213,51 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns the account changesets of all blocks in `range`, reading the blocks that were
+    /// moved to static files from there and the rest from [`tables::AccountChangeSets`].
+    pub fn account_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::AccountChangeSets,
+            to_range(range),
+            |static_file, range, _| static_file.account_changesets_range(range),
+            |range, _| {
+                self.tx
+                    .cursor_read::<tables::AccountChangeSets>()?
+                    .walk_range(range)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(Into::into)
+            },
+            |_| true,
+        )
+    }
+
+    /// Returns the storage changesets of all blocks in `range`, reading the blocks that were
+    /// moved to static files from there and the rest from [`tables::StorageChangeSets`].
+    pub fn storage_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::StorageChangeSets,
+            to_range(range),
+            |static_file, range, _| static_file.storage_changesets_range(range),
+            |range, _| {
+                let Some(last) = range.end.checked_sub(1) else { return Ok(Vec::new()) };
+                self.tx
+                    .cursor_read::<tables::StorageChangeSets>()?
+                    .walk_range(BlockNumberAddress::range(range.start..=last))?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(Into::into)
+            },
+            |_| true,
+        )
+    }
+}
+
 impl<TX, N: NodeTypes> NodePrimitivesProvider for DatabaseProvider<TX, N> {
     type Primitives = N::Primitives;
 }
@@ -260,11 +307,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<()> {
-        let changed_accounts = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range.clone())?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changed_accounts = self.account_changesets_range(range.clone())?;
 
         // Unwind account hashes. Add changed accounts to account prefix set.
         let hashed_addresses = self.unwind_account_hashing(changed_accounts.iter())?;
@@ -279,13 +322,8 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
 
         // Unwind account history indices.
         self.unwind_account_history_indices(changed_accounts.iter())?;
-        let storage_range = BlockNumberAddress::range(range.clone());
 
-        let changed_storages = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(storage_range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changed_storages = self.storage_changesets_range(range.clone())?;
 
         // Unwind storage hashes. Add changed account and storage keys to corresponding prefix
         // sets.
@@ -335,6 +373,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
             })))
         }
         self.write_trie_updates(&trie_updates)?;
+        self.unwind_trie_changesets(parent_number)?;
 
         Ok(())
     }
@@ -366,6 +405,269 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
 
         Ok(())
     }
+
+    /// Takes the account and storage changesets of all blocks in `range`, which has to end at the
+    /// tip.
+    ///
+    /// Changesets are removed from the database and, if requested, from static files.
+    fn take_changesets(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        remove_from: StorageLocation,
+    ) -> ProviderResult<(
+        Vec<(BlockNumber, AccountBeforeTx)>,
+        Vec<(BlockNumberAddress, StorageEntry)>,
+    )> {
+        let account_changeset = self.account_changesets_range(range.clone())?;
+        let storage_changeset = self.storage_changesets_range(range.clone())?;
+
+        self.remove::<tables::StorageChangeSets>(BlockNumberAddress::range(range.clone()))?;
+        self.remove::<tables::AccountChangeSets>(range.clone())?;
+
+        if remove_from.static_files() {
+            for segment in
+                [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+            {
+                let to_delete = self
+                    .static_file_provider
+                    .get_highest_static_file_block(segment)
+                    .map(|highest| (highest + 1).saturating_sub(*range.start()))
+                    .unwrap_or_default();
+
+                if to_delete > 0 {
+                    self.static_file_provider
+                        .latest_writer(segment)?
+                        .prune_changesets(to_delete)?;
+                }
+            }
+        }
+
+        Ok((account_changeset, storage_changeset))
+    }
+
+    /// Writes state reverts, storing the changesets in static files if `write_to` includes them
+    /// and the history is not pruned. See [`Self::changesets_static_writer`].
+    ///
+    /// NOTE: Reverts will delete all wiped storage from plain state.
+    fn write_state_reverts_to(
+        &self,
+        reverts: PlainStateReverts,
+        first_block: BlockNumber,
+        write_to: StorageLocation,
+    ) -> ProviderResult<()> {
+        // Write storage changes
+        tracing::trace!("Writing storage changes");
+        let mut storages_cursor = self.tx_ref().cursor_dup_write::<tables::PlainStorageState>()?;
+        let mut storage_changeset_static_writer = self.changesets_static_writer(
+            StaticFileSegment::StorageChangeSets,
+            first_block,
+            write_to,
+        )?;
+        let mut storage_changeset_cursor = storage_changeset_static_writer
+            .is_none()
+            .then(|| self.tx_ref().cursor_dup_write::<tables::StorageChangeSets>())
+            .transpose()?;
+        for (block_index, mut storage_changes) in reverts.storage.into_iter().enumerate() {
+            let block_number = first_block + block_index as BlockNumber;
+            let mut static_file_changes = Vec::new();
+
+            tracing::trace!(block_number, "Writing block change");
+            // sort changes by address.
+            storage_changes.par_sort_unstable_by_key(|a| a.address);
+            for PlainStorageRevert { address, wiped, storage_revert } in storage_changes {
+                let storage_id = BlockNumberAddress((block_number, address));
+
+                let mut storage = storage_revert
+                    .into_iter()
+                    .map(|(k, v)| (B256::new(k.to_be_bytes()), v))
+                    .collect::<Vec<_>>();
+                // sort storage slots by key.
+                storage.par_sort_unstable_by_key(|a| a.0);
+
+                // If we are writing the primary storage wipe transition, the pre-existing plain
+                // storage state has to be taken from the database and written to storage history.
+                // See [StorageWipe::Primary] for more details.
+                let mut wiped_storage = Vec::new();
+                if wiped {
+                    tracing::trace!(?address, "Wiping storage");
+                    if let Some((_, entry)) = storages_cursor.seek_exact(address)? {
+                        wiped_storage.push((entry.key, entry.value));
+                        while let Some(entry) = storages_cursor.next_dup_val()? {
+                            wiped_storage.push((entry.key, entry.value))
+                        }
+                    }
+                }
+
+                tracing::trace!(?address, ?storage, "Writing storage reverts");
+                for (key, value) in StorageRevertsIter::new(storage, wiped_storage) {
+                    if let Some(cursor) = &mut storage_changeset_cursor {
+                        cursor.append_dup(storage_id, StorageEntry { key, value })?;
+                    } else {
+                        static_file_changes.push(StorageBeforeTx { address, key, value });
+                    }
+                }
+            }
+
+            if let Some(writer) = &mut storage_changeset_static_writer {
+                writer.append_storage_changeset(block_number, static_file_changes)?;
+            }
+        }
+
+        // Write account changes
+        tracing::trace!("Writing account changes");
+        let mut account_changeset_static_writer = self.changesets_static_writer(
+            StaticFileSegment::AccountChangeSets,
+            first_block,
+            write_to,
+        )?;
+        let mut account_changeset_cursor = account_changeset_static_writer
+            .is_none()
+            .then(|| self.tx_ref().cursor_dup_write::<tables::AccountChangeSets>())
+            .transpose()?;
+
+        for (block_index, mut account_block_reverts) in reverts.accounts.into_iter().enumerate() {
+            let block_number = first_block + block_index as BlockNumber;
+            // Sort accounts by address.
+            account_block_reverts.par_sort_by_key(|a| a.0);
+
+            let changes = account_block_reverts
+                .into_iter()
+                .map(|(address, info)| AccountBeforeTx { address, info: info.map(Into::into) });
+
+            if let Some(writer) = &mut account_changeset_static_writer {
+                writer.append_account_changeset(block_number, changes.collect())?;
+            } else if let Some(cursor) = &mut account_changeset_cursor {
+                for account_before in changes {
+                    cursor.append_dup(block_number, account_before)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns a static file writer for the changesets `segment` if the changesets of the blocks
+    /// starting at `first_block` should be written to static files.
+    ///
+    /// This is only the case if the corresponding history is not pruned and the static file
+    /// continues exactly at `first_block`. Otherwise, the changesets are written to the database,
+    /// so that each block is stored in one place only.
+    ///
+    /// Since the segment has to start at genesis, this only applies to fresh syncs, unless the
+    /// changesets of an existing database were moved with
+    /// [`Self::move_changesets_to_static_files`].
+    fn changesets_static_writer(
+        &self,
+        segment: StaticFileSegment,
+        first_block: BlockNumber,
+        write_to: StorageLocation,
+    ) -> ProviderResult<Option<StaticFileProviderRWRefMut<'_, N::Primitives>>> {
+        debug_assert!(segment.is_changesets());
+
+        let has_history_pruning = if segment == StaticFileSegment::AccountChangeSets {
+            self.prune_modes.account_history.is_some()
+        } else {
+            self.prune_modes.storage_history.is_some()
+        };
+        if !write_to.static_files() || has_history_pruning {
+            return Ok(None)
+        }
+
+        // The segment can only be created at genesis.
+        if self.static_file_provider.get_highest_static_file_block(segment).is_none() &&
+            first_block != 0
+        {
+            return Ok(None)
+        }
+
+        // The writer also knows about the blocks appended earlier in this batch, which are not
+        // committed to the static file index yet.
+        let writer = self.static_file_provider.latest_writer(segment)?;
+        Ok((writer.next_block_number() == first_block).then_some(writer))
+    }
+
+    /// Moves the changesets of the changesets `segment` for all blocks up to and including
+    /// `to_block` from the database to static files, continuing after the highest block that is
+    /// already in static files.
+    ///
+    /// Changesets that are left in the database for blocks already in static files, e.g. because a
+    /// previous migration was interrupted before committing the database, are removed.
+    ///
+    /// Returns the highest block in static files afterwards, or `None` if the history is pruned, in
+    /// which case the changesets stay in the database. Both the static file provider and the
+    /// database have to be committed afterwards, in this order.
+    pub fn move_changesets_to_static_files(
+        &self,
+        segment: StaticFileSegment,
+        to_block: BlockNumber,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        debug_assert!(segment.is_changesets());
+
+        let (prune_mode, prune_segment) = if segment == StaticFileSegment::AccountChangeSets {
+            (self.prune_modes.account_history, PruneSegment::AccountHistory)
+        } else {
+            (self.prune_modes.storage_history, PruneSegment::StorageHistory)
+        };
+        if prune_mode.is_some() || self.get_prune_checkpoint(prune_segment)?.is_some() {
+            return Ok(None)
+        }
+
+        let highest = self.static_file_provider.get_highest_static_file_block(segment);
+        if let Some(highest) = highest {
+            self.remove_changesets_from_database(segment, 0..=highest)?;
+        }
+
+        let from_block = highest.map_or(0, |highest| highest + 1);
+        if from_block > to_block {
+            return Ok(highest)
+        }
+
+        let mut writer = self.static_file_provider.latest_writer(segment)?;
+        if segment == StaticFileSegment::AccountChangeSets {
+            let mut cursor = self.tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+            for block_number in from_block..=to_block {
+                let changes = cursor
+                    .walk_dup(Some(block_number), None)?
+                    .map(|entry| entry.map(|(_, account_before)| account_before))
+                    .collect::<Result<Vec<_>, _>>()?;
+                writer.append_account_changeset(block_number, changes)?;
+            }
+        } else {
+            let mut cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
+            for block_number in from_block..=to_block {
+                let changes = cursor
+                    .walk_range(BlockNumberAddress::range(block_number..=block_number))?
+                    .map(|entry| {
+                        entry.map(|(key, StorageEntry { key: slot, value })| StorageBeforeTx {
+                            address: key.address(),
+                            key: slot,
+                            value,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                writer.append_storage_changeset(block_number, changes)?;
+            }
+        }
+
+        self.remove_changesets_from_database(segment, from_block..=to_block)?;
+
+        Ok(Some(to_block))
+    }
+
+    /// Removes the changesets of the changesets `segment` for all blocks in `range` from the
+    /// database.
+    fn remove_changesets_from_database(
+        &self,
+        segment: StaticFileSegment,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        if segment == StaticFileSegment::AccountChangeSets {
+            self.remove::<tables::AccountChangeSets>(range)?;
+        } else {
+            self.remove::<tables::StorageChangeSets>(BlockNumberAddress::range(range))?;
+        }
+        Ok(())
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for DatabaseProvider<TX, N> {
@@ -515,6 +817,59 @@ where
     Ok(Vec::new())
 }
 
+/// Collects the block numbers within the given range from the shards of the given key in a log
+/// index table.
+fn log_index_blocks_for_key<K, T>(
+    cursor: &mut impl DbCursorRO<T>,
+    key: K,
+    range: &RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<BlockNumber>>
+where
+    K: Clone + PartialEq,
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+{
+    let mut blocks = Vec::new();
+    // The first shard with a highest block number at or above the start of the range is the first
+    // shard that can contain blocks of the range.
+    let mut item = cursor.seek(ShardedKey::new(key.clone(), *range.start()))?;
+    while let Some((sharded_key, list)) = item {
+        if sharded_key.key != key {
+            break
+        }
+
+        blocks.extend(
+            list.iter()
+                .skip_while(|block| block < range.start())
+                .take_while(|block| block <= range.end()),
+        );
+
+        if sharded_key.highest_block_number >= *range.end() {
+            break
+        }
+        item = cursor.next()?;
+    }
+
+    Ok(blocks)
+}
+
+/// Returns the range of blocks that contains all keys of the given [`BlockNumberAddress`] range.
+fn storage_changesets_block_range(
+    range: &impl RangeBounds<BlockNumberAddress>,
+) -> (Bound<BlockNumber>, Bound<BlockNumber>) {
+    let start = match range.start_bound() {
+        Bound::Included(key) | Bound::Excluded(key) => Bound::Included(key.block_number()),
+        Bound::Unbounded => Bound::Unbounded,
+    };
+    let end = match range.end_bound() {
+        Bound::Excluded(key) if key.address() == Address::ZERO => {
+            Bound::Excluded(key.block_number())
+        }
+        Bound::Included(key) | Bound::Excluded(key) => Bound::Included(key.block_number()),
+        Bound::Unbounded => Bound::Unbounded,
+    };
+    (start, end)
+}
+
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
     /// Creates a provider with an inner read-only transaction.
     pub const fn new(
@@ -866,6 +1221,38 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         }
         Ok(())
     }
+
+    /// Removes all blocks at or above the given block number from the shards of the given keys in
+    /// a log index table.
+    fn unwind_log_index<K, T>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        first_block: BlockNumber,
+    ) -> ProviderResult<()>
+    where
+        K: Copy + PartialEq,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for key in keys {
+            let partial_shard = unwind_history_shards::<K, T, _>(
+                &mut cursor,
+                ShardedKey::last(key),
+                first_block,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // Reinsert the blocks of the boundary shard that are below the unwind block.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl<TX: DbTx, N: NodeTypes> AccountReader for DatabaseProvider<TX, N> {
@@ -879,13 +1266,11 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        Ok(self
+            .account_changesets_range(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before.address)
+            .collect())
     }
 
     fn basic_accounts(
@@ -903,16 +1288,13 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
+        let account_transitions = self.account_changesets_range(range)?.into_iter().fold(
             BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
+            |mut accounts: BTreeMap<Address, Vec<u64>>, (index, account)| {
                 accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
+                accounts
             },
-        )?;
+        );
 
         Ok(account_transitions)
     }
@@ -923,13 +1305,7 @@ impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N>
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
-        let range = block_number..=block_number;
-        let storage_range = BlockNumberAddress::range(range);
-        self.tx
-            .cursor_dup_read::<tables::StorageChangeSets>()?
-            .walk_range(storage_range)?
-            .map(|result| -> ProviderResult<_> { Ok(result?) })
-            .collect()
+        self.storage_changesets_range(block_number..=block_number)
     }
 }
 
@@ -938,15 +1314,11 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        Ok(self
+            .account_changesets_range(block_number..=block_number)?
+            .into_iter()
+            .map(|(_, account_before)| account_before)
+            .collect())
     }
 }
 
@@ -1745,36 +2117,35 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
+        Ok(self
+            .storage_changesets_range(range)?
+            .into_iter()
             // fold all storages and save its old state so we can remove it from HashedStorage
             // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
-                accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+            .fold(
+                BTreeMap::new(),
+                |mut accounts: BTreeMap<Address, BTreeSet<B256>>,
+                 (BlockNumberAddress((_, address)), storage_entry)| {
+                    accounts.entry(address).or_default().insert(storage_entry.key);
+                    accounts
+                },
+            ))
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
-        let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
-                BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
-                    storages
-                        .entry((index.address(), storage.key))
-                        .or_default()
-                        .push(index.block_number());
-                    Ok(storages)
-                },
-            )?;
+        let storage_changeset_lists = self.storage_changesets_range(range)?.into_iter().fold(
+            BTreeMap::new(),
+            |mut storages: BTreeMap<(Address, B256), Vec<u64>>, (index, storage)| {
+                storages
+                    .entry((index.address(), storage.key))
+                    .or_default()
+                    .push(index.block_number());
+                storages
+            },
+        );
 
         Ok(storage_changeset_lists)
     }
@@ -1801,7 +2172,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         let (plain_state, reverts) =
             execution_outcome.bundle.to_plain_state_and_reverts(is_value_known);
 
-        self.write_state_reverts(reverts, first_block)?;
+        self.write_state_reverts_to(reverts, first_block, write_receipts_to)?;
         self.write_state_changes(plain_state)?;
 
         // Fetch the first transaction number for each block in the range
@@ -1904,67 +2275,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         reverts: PlainStateReverts,
         first_block: BlockNumber,
     ) -> ProviderResult<()> {
-        // Write storage changes
-        tracing::trace!("Writing storage changes");
-        let mut storages_cursor = self.tx_ref().cursor_dup_write::<tables::PlainStorageState>()?;
-        let mut storage_changeset_cursor =
-            self.tx_ref().cursor_dup_write::<tables::StorageChangeSets>()?;
-        for (block_index, mut storage_changes) in reverts.storage.into_iter().enumerate() {
-            let block_number = first_block + block_index as BlockNumber;
-
-            tracing::trace!(block_number, "Writing block change");
-            // sort changes by address.
-            storage_changes.par_sort_unstable_by_key(|a| a.address);
-            for PlainStorageRevert { address, wiped, storage_revert } in storage_changes {
-                let storage_id = BlockNumberAddress((block_number, address));
-
-                let mut storage = storage_revert
-                    .into_iter()
-                    .map(|(k, v)| (B256::new(k.to_be_bytes()), v))
-                    .collect::<Vec<_>>();
-                // sort storage slots by key.
-                storage.par_sort_unstable_by_key(|a| a.0);
-
-                // If we are writing the primary storage wipe transition, the pre-existing plain
-                // storage state has to be taken from the database and written to storage history.
-                // See [StorageWipe::Primary] for more details.
-                let mut wiped_storage = Vec::new();
-                if wiped {
-                    tracing::trace!(?address, "Wiping storage");
-                    if let Some((_, entry)) = storages_cursor.seek_exact(address)? {
-                        wiped_storage.push((entry.key, entry.value));
-                        while let Some(entry) = storages_cursor.next_dup_val()? {
-                            wiped_storage.push((entry.key, entry.value))
-                        }
-                    }
-                }
-
-                tracing::trace!(?address, ?storage, "Writing storage reverts");
-                for (key, value) in StorageRevertsIter::new(storage, wiped_storage) {
-                    storage_changeset_cursor.append_dup(storage_id, StorageEntry { key, value })?;
-                }
-            }
-        }
-
-        // Write account changes
-        tracing::trace!("Writing account changes");
-        let mut account_changeset_cursor =
-            self.tx_ref().cursor_dup_write::<tables::AccountChangeSets>()?;
-
-        for (block_index, mut account_block_reverts) in reverts.accounts.into_iter().enumerate() {
-            let block_number = first_block + block_index as BlockNumber;
-            // Sort accounts by address.
-            account_block_reverts.par_sort_by_key(|a| a.0);
-
-            for (address, info) in account_block_reverts {
-                account_changeset_cursor.append_dup(
-                    block_number,
-                    AccountBeforeTx { address, info: info.map(Into::into) },
-                )?;
-            }
-        }
-
-        Ok(())
+        self.write_state_reverts_to(reverts, first_block, StorageLocation::Database)
     }
 
     fn write_state_changes(&self, mut changes: StateChangeset) -> ProviderResult<()> {
@@ -2106,10 +2417,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         let from_transaction_num =
             block_bodies.first().expect("already checked if there are blocks").first_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let (account_changeset, storage_changeset) =
+            self.take_changesets(range, remove_receipts_from)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2204,10 +2513,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").last_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let (account_changeset, storage_changeset) =
+            self.take_changesets(range, remove_receipts_from)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2353,6 +2660,134 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> TrieWriter for DatabaseProvider
 
         Ok(num_entries)
     }
+
+    fn write_trie_changesets(
+        &self,
+        block_number: BlockNumber,
+        trie_updates: &TrieUpdates,
+    ) -> ProviderResult<usize> {
+        // Trie changesets are only available for the blocks above the first block they were
+        // written for.
+        if self.get_prune_checkpoint(PruneSegment::TrieChangeSets)?.is_none() {
+            self.save_prune_checkpoint(
+                PruneSegment::TrieChangeSets,
+                PruneCheckpoint {
+                    block_number: block_number.checked_sub(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(block_number),
+                },
+            )?;
+        }
+
+        if trie_updates.is_empty() {
+            return Ok(0)
+        }
+
+        let tx = self.tx_ref();
+        let mut num_entries = 0;
+
+        // Current values of the updated and removed account trie nodes, sorted by nibbles.
+        let mut account_trie_cursor = tx.cursor_read::<tables::AccountsTrie>()?;
+        let mut account_changesets = BTreeMap::new();
+        for nibbles in trie_updates
+            .account_nodes_ref()
+            .keys()
+            .chain(trie_updates.removed_nodes_ref())
+            .filter(|nibbles| !nibbles.is_empty())
+        {
+            let node =
+                account_trie_cursor.seek_exact(StoredNibbles(*nibbles))?.map(|(_, node)| node);
+            account_changesets.insert(*nibbles, node);
+        }
+
+        let mut account_changesets_cursor =
+            tx.cursor_dup_write::<tables::AccountsTrieChangeSets>()?;
+        for (nibbles, node) in account_changesets {
+            num_entries += 1;
+            account_changesets_cursor.upsert(
+                block_number,
+                &TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(nibbles), node },
+            )?;
+        }
+
+        let mut storage_tries = Vec::from_iter(trie_updates.storage_tries_ref());
+        storage_tries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+        let mut storage_trie_cursor = tx.cursor_dup_read::<tables::StoragesTrie>()?;
+        let mut storage_changesets_cursor =
+            tx.cursor_dup_write::<tables::StoragesTrieChangeSets>()?;
+        for (hashed_address, updates) in storage_tries {
+            // Current values of the updated and removed storage trie nodes, sorted by nibbles.
+            let mut storage_changesets = BTreeMap::new();
+            for nibbles in updates
+                .storage_nodes_ref()
+                .keys()
+                .chain(updates.removed_nodes_ref())
+                .filter(|nibbles| !nibbles.is_empty())
+            {
+                let nibbles = StoredNibblesSubKey(*nibbles);
+                let node = storage_trie_cursor
+                    .seek_by_key_subkey(*hashed_address, nibbles.clone())?
+                    .filter(|entry| entry.nibbles == nibbles)
+                    .map(|entry| entry.node);
+                storage_changesets.insert(nibbles.0, node);
+            }
+
+            // If the storage trie is wiped, all of its nodes are removed.
+            if updates.is_deleted() {
+                for entry in storage_trie_cursor.walk_dup(Some(*hashed_address), None)? {
+                    let (_, entry) = entry?;
+                    storage_changesets.insert(entry.nibbles.0, Some(entry.node));
+                }
+            }
+
+            let key = BlockNumberHashedAddress((block_number, *hashed_address));
+            for (nibbles, node) in storage_changesets {
+                num_entries += 1;
+                storage_changesets_cursor.upsert(
+                    key,
+                    &TrieChangeSetsEntry { nibbles: StoredNibblesSubKey(nibbles), node },
+                )?;
+            }
+        }
+
+        Ok(num_entries)
+    }
+
+    fn unwind_trie_changesets(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.remove::<tables::AccountsTrieChangeSets>(block_number + 1..)?;
+        self.remove::<tables::StoragesTrieChangeSets>(
+            BlockNumberHashedAddress((block_number + 1, B256::ZERO))..,
+        )?;
+        Ok(())
+    }
+
+    fn invalidate_trie_changesets(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.remove::<tables::AccountsTrieChangeSets>(..=block_number)?;
+        self.remove::<tables::StoragesTrieChangeSets>(
+            ..BlockNumberHashedAddress((block_number + 1, B256::ZERO)),
+        )?;
+
+        let checkpoint = self.get_prune_checkpoint(PruneSegment::TrieChangeSets)?;
+        if checkpoint
+            .and_then(|checkpoint| checkpoint.block_number)
+            .is_none_or(|pruned_to| pruned_to < block_number)
+        {
+            self.save_prune_checkpoint(
+                PruneSegment::TrieChangeSets,
+                PruneCheckpoint {
+                    block_number: Some(block_number),
+                    tx_number: None,
+                    prune_mode: checkpoint
+                        .map_or(PruneMode::Before(block_number + 1), |checkpoint| {
+                            checkpoint.prune_mode
+                        }),
+                },
+            )?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> StorageTrieWriter for DatabaseProvider<TX, N> {
@@ -2425,11 +2860,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeMap<B256, Option<Account>>> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.account_changesets_range(range)?;
         self.unwind_account_hashing(changesets.iter())
     }
 
@@ -2490,11 +2921,10 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         range: impl RangeBounds<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
         let changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
-        self.unwind_storage_hashing(changesets.into_iter())
+            .storage_changesets_range(storage_changesets_block_range(&range))?
+            .into_iter()
+            .filter(|(key, _)| range.contains(key));
+        self.unwind_storage_hashing(changesets)
     }
 
     fn insert_storage_for_hashing(
@@ -2608,6 +3038,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
                 })))
             }
             self.write_trie_updates(&trie_updates)?;
+            self.invalidate_trie_changesets(*range.end())?;
         }
         durations_recorder.record_relative(metrics::Action::InsertMerkleTree);
 
@@ -2656,11 +3087,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<usize> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.account_changesets_range(range)?;
         self.unwind_account_history_indices(changesets.iter())
     }
 
@@ -2715,11 +3142,10 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         range: impl RangeBounds<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
         let changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
-        self.unwind_storage_history_indices(changesets.into_iter())
+            .storage_changesets_range(storage_changesets_block_range(&range))?
+            .into_iter()
+            .filter(|(key, _)| range.contains(key));
+        self.unwind_storage_history_indices(changesets)
     }
 
     fn insert_storage_history_index(
@@ -2751,6 +3177,108 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypes> LogIndexReader for DatabaseProvider<TX, N> {
+    fn log_index_blocks(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<LogIndexMatches>> {
+        let topics = topics.iter().filter(|alternatives| !alternatives.is_empty()).collect_vec();
+        if addresses.is_empty() && topics.is_empty() {
+            return Ok(None)
+        }
+
+        let Some(indexed_to) = self
+            .tx
+            .get::<tables::StageCheckpoints>(StageId::IndexLogs.to_string())?
+            .map(|checkpoint| checkpoint.block_number)
+        else {
+            return Ok(None)
+        };
+        if *range.start() > indexed_to {
+            return Ok(None)
+        }
+        let range = *range.start()..=indexed_to.min(*range.end());
+
+        let mut blocks = None;
+        if !addresses.is_empty() {
+            let mut cursor = self.tx.cursor_read::<tables::LogAddressIndex>()?;
+            let mut matches = Vec::new();
+            for address in addresses {
+                matches.extend(log_index_blocks_for_key(&mut cursor, *address, &range)?);
+            }
+            matches.sort_unstable();
+            matches.dedup();
+            blocks = Some(matches);
+        }
+
+        let mut cursor = self.tx.cursor_read::<tables::LogTopicIndex>()?;
+        for alternatives in topics {
+            let mut matches = Vec::new();
+            for topic in alternatives {
+                matches.extend(log_index_blocks_for_key(&mut cursor, *topic, &range)?);
+            }
+            matches.sort_unstable();
+            matches.dedup();
+
+            // Logs need to match the alternatives of every topic position.
+            blocks = Some(match blocks {
+                Some(mut blocks) => {
+                    blocks.retain(|block| matches.binary_search(block).is_ok());
+                    blocks
+                }
+                None => matches,
+            });
+        }
+
+        Ok(Some(LogIndexMatches { indexed_to: *range.end(), blocks: blocks.unwrap_or_default() }))
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> LogIndexWriter for DatabaseProvider<TX, N> {
+    fn insert_log_indices<'a>(
+        &self,
+        blocks: impl IntoIterator<Item = (BlockNumber, impl IntoIterator<Item = &'a Log>)>,
+    ) -> ProviderResult<()> {
+        let mut addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut topics = BTreeMap::<B256, Vec<BlockNumber>>::new();
+        for (block_number, logs) in blocks {
+            for log in logs {
+                let indices = addresses.entry(log.address).or_default();
+                if indices.last() != Some(&block_number) {
+                    indices.push(block_number);
+                }
+                for topic in log.topics() {
+                    let indices = topics.entry(*topic).or_default();
+                    if indices.last() != Some(&block_number) {
+                        indices.push(block_number);
+                    }
+                }
+            }
+        }
+
+        self.append_history_index::<_, tables::LogAddressIndex>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topics, ShardedKey::new)
+    }
+
+    fn unwind_log_indices<'a>(
+        &self,
+        first_block: BlockNumber,
+        logs: impl IntoIterator<Item = &'a Log>,
+    ) -> ProviderResult<()> {
+        let mut addresses = BTreeSet::new();
+        let mut topics = BTreeSet::new();
+        for log in logs {
+            addresses.insert(log.address);
+            topics.extend(log.topics().iter().copied());
+        }
+
+        self.unwind_log_index::<_, tables::LogAddressIndex>(addresses, first_block)?;
+        self.unwind_log_index::<_, tables::LogTopicIndex>(topics, first_block)
+    }
+}
+
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecutionWriter
     for DatabaseProvider<TX, N>
 {
@@ -3210,6 +3738,24 @@ mod tests {
         BlockWriter,
     };
     use reth_testing_utils::generators::{self, random_block, BlockParams};
+    use revm_database::states::RevertToSlot;
+    use revm_state::AccountInfo;
+
+    /// Returns the reverts of a single block that restore the nonce of `address` and its storage
+    /// slot `1` to `value`.
+    fn single_block_reverts(address: Address, value: u64) -> PlainStateReverts {
+        PlainStateReverts {
+            accounts: vec![vec![(
+                address,
+                Some(AccountInfo { nonce: value, ..Default::default() }),
+            )]],
+            storage: vec![vec![PlainStorageRevert {
+                address,
+                wiped: false,
+                storage_revert: vec![(U256::from(1), RevertToSlot::Some(U256::from(value)))],
+            }]],
+        }
+    }
 
     #[test]
     fn test_receipts_by_block_range_empty_range() {
@@ -3454,4 +4000,230 @@ mod tests {
 
         assert_eq!(range_result, individual_results);
     }
+
+    #[test]
+    fn changesets_static_files_round_trip() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+
+        // Block 0 creates the static files, like the genesis does. Blocks 1 and 2 are written in
+        // one batch and continue them. Block 4 doesn't, so it goes to the database.
+        for batch in [vec![0], vec![1, 2, 4]] {
+            let provider_rw = factory.provider_rw().unwrap();
+            for block in batch {
+                provider_rw
+                    .write_state_reverts_to(
+                        single_block_reverts(address, block),
+                        block,
+                        StorageLocation::StaticFiles,
+                    )
+                    .unwrap();
+            }
+            provider_rw.static_file_provider().commit().unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        let static_file_provider = factory.static_file_provider();
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            assert_eq!(static_file_provider.get_highest_static_file_block(segment), Some(2));
+        }
+
+        let provider = factory.provider().unwrap();
+        let tx = provider.tx_ref();
+        assert_eq!(
+            tx.cursor_read::<tables::AccountChangeSets>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .map(|entry| entry.unwrap().0)
+                .collect::<Vec<_>>(),
+            vec![4]
+        );
+        assert_eq!(
+            tx.cursor_read::<tables::StorageChangeSets>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .map(|entry| entry.unwrap().0.block_number())
+                .collect::<Vec<_>>(),
+            vec![4]
+        );
+
+        for block in [0, 1, 2, 4] {
+            assert_eq!(
+                provider.account_block_changeset(block).unwrap(),
+                vec![AccountBeforeTx {
+                    address,
+                    info: Some(Account { nonce: block, ..Default::default() })
+                }]
+            );
+            assert_eq!(
+                provider.storage_changeset(block).unwrap(),
+                vec![(
+                    BlockNumberAddress((block, address)),
+                    StorageEntry { key: B256::with_last_byte(1), value: U256::from(block) }
+                )]
+            );
+        }
+        assert!(provider.account_block_changeset(3).unwrap().is_empty());
+        assert_eq!(
+            provider
+                .account_changesets_range(0..=4)
+                .unwrap()
+                .into_iter()
+                .map(|(block, _)| block)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 4]
+        );
+    }
+
+    #[test]
+    fn take_changesets_from_static_files_and_database() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+
+        for batch in [
+            vec![(0, StorageLocation::StaticFiles)],
+            vec![
+                (1, StorageLocation::StaticFiles),
+                (2, StorageLocation::StaticFiles),
+                (3, StorageLocation::Database),
+            ],
+        ] {
+            let provider_rw = factory.provider_rw().unwrap();
+            for (block, write_to) in batch {
+                provider_rw
+                    .write_state_reverts_to(single_block_reverts(address, block), block, write_to)
+                    .unwrap();
+            }
+            provider_rw.static_file_provider().commit().unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        // Unwind blocks 2 and 3, which are split between static files and the database.
+        let provider_rw = factory.provider_rw().unwrap();
+        let (accounts, storages) =
+            provider_rw.take_changesets(2..=3, StorageLocation::Both).unwrap();
+        assert_eq!(accounts.iter().map(|(block, _)| *block).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(
+            storages
+                .iter()
+                .map(|(key, entry)| (key.block_number(), entry.value))
+                .collect::<Vec<_>>(),
+            vec![(2, U256::from(2)), (3, U256::from(3))]
+        );
+        provider_rw.static_file_provider().commit().unwrap();
+        provider_rw.commit().unwrap();
+
+        let static_file_provider = factory.static_file_provider();
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            assert_eq!(static_file_provider.get_highest_static_file_block(segment), Some(1));
+        }
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::AccountChangeSets>().unwrap(), 0);
+        assert_eq!(provider.tx_ref().entries::<tables::StorageChangeSets>().unwrap(), 0);
+        assert_eq!(
+            provider
+                .storage_changesets_range(0..=3)
+                .unwrap()
+                .into_iter()
+                .map(|(key, _)| key.block_number())
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+
+        // The static files continue at block 2 again.
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .write_state_reverts_to(
+                single_block_reverts(address, 2),
+                2,
+                StorageLocation::StaticFiles,
+            )
+            .unwrap();
+        provider_rw.static_file_provider().commit().unwrap();
+        provider_rw.commit().unwrap();
+        assert_eq!(
+            factory
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn move_changesets_to_static_files() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+
+        // An existing database keeps all changesets in the database.
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in 0..=2 {
+            provider_rw
+                .write_state_reverts_to(
+                    single_block_reverts(address, block),
+                    block,
+                    StorageLocation::Database,
+                )
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        for to_block in [1, 2] {
+            let provider_rw = factory.provider_rw().unwrap();
+            for segment in
+                [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+            {
+                assert_eq!(
+                    provider_rw.move_changesets_to_static_files(segment, to_block).unwrap(),
+                    Some(to_block)
+                );
+            }
+            provider_rw.static_file_provider().commit().unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::AccountChangeSets>().unwrap(), 0);
+        assert_eq!(provider.tx_ref().entries::<tables::StorageChangeSets>().unwrap(), 0);
+        assert_eq!(
+            provider
+                .account_changesets_range(0..=2)
+                .unwrap()
+                .into_iter()
+                .map(|(block, _)| block)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            provider
+                .storage_changesets_range(0..=2)
+                .unwrap()
+                .into_iter()
+                .map(|(key, entry)| (key.block_number(), entry.value))
+                .collect::<Vec<_>>(),
+            vec![(0, U256::ZERO), (1, U256::from(1)), (2, U256::from(2))]
+        );
+
+        // New blocks continue in static files.
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .write_state_reverts_to(
+                single_block_reverts(address, 3),
+                3,
+                StorageLocation::StaticFiles,
+            )
+            .unwrap();
+        provider_rw.static_file_provider().commit().unwrap();
+        provider_rw.commit().unwrap();
+        assert_eq!(
+            factory
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+            Some(3)
+        );
+    }
 }
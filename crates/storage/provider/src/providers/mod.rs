@@ -15,7 +15,10 @@ pub use static_file::{
 
 mod state;
 pub use state::{
-    historical::{HistoricalStateProvider, HistoricalStateProviderRef, LowestAvailableBlocks},
+    historical::{
+        HistoricalStateProvider, HistoricalStateProviderRef, LowestAvailableBlocks,
+        MAX_TRIE_CHANGESETS_REVERT_DISTANCE,
+    },
     latest::{LatestStateProvider, LatestStateProviderRef},
 };
 
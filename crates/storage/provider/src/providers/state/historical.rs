@@ -1,18 +1,27 @@
 use crate::{
     providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
     HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
+    StaticFileProviderFactory,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{
+    map::{AddressMap, B256Map},
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
+};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberHashedAddress,
+        ShardedKey,
+    },
     table::Table,
     tables,
     transaction::DbTx,
     BlockNumberList,
 };
-use reth_primitives_traits::{Account, Bytecode};
+use reth_primitives_traits::{Account, Bytecode, StorageEntry};
+use reth_prune_types::PruneSegment;
+use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
     BlockNumReader, BytecodeReader, DBProvider, StateCommitmentProvider, StateProofProvider,
     StorageRootProvider,
@@ -22,14 +31,21 @@ use reth_trie::{
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
-    StorageMultiProof, StorageRoot, TrieInput,
+    AccountProof, HashedPostState, HashedStorage, KeyHasher, MultiProof, MultiProofTargets,
+    StateRoot, StorageMultiProof, StorageRoot, TrieChangeSetsEntry, TrieInput,
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
     DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::Range};
+
+/// Maximum distance to the tip of a historical state whose trie is restored from the trie
+/// changesets, see [`HistoricalStateProviderRef::has_trie_changesets`].
+///
+/// Restoring the trie walks the trie changesets of all blocks up to the tip, so states further
+/// away from the tip are treated as if their trie changesets were not available.
+pub const MAX_TRIE_CHANGESETS_REVERT_DISTANCE: u64 = 100_000;
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -42,6 +58,11 @@ use std::fmt::Debug;
 /// - [`tables::StoragesHistory`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+/// - [`tables::AccountsTrieChangeSets`]
+/// - [`tables::StoragesTrieChangeSets`]
+///
+/// Changesets of blocks that were moved to the [`StaticFileSegment::AccountChangeSets`] and
+/// [`StaticFileSegment::StorageChangeSets`] static files are read from there instead.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, Provider> {
     /// Database provider
@@ -60,8 +81,10 @@ pub enum HistoryInfo {
     MaybeInPlainState,
 }
 
-impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
-    HistoricalStateProviderRef<'b, Provider>
+impl<
+        'b,
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > HistoricalStateProviderRef<'b, Provider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
@@ -135,9 +158,152 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedPostState::from_reverts::<
+        let mut revert_state = HashedPostState::from_reverts::<
             <Provider::StateCommitment as StateCommitment>::KeyHasher,
-        >(self.tx(), self.block_number)?)
+        >(self.tx(), self.block_number)?;
+        // Changesets in static files belong to lower blocks than the ones in the database, so
+        // their values take precedence.
+        revert_state.extend(self.static_file_revert_state()?);
+
+        Ok(revert_state)
+    }
+
+    /// Retrieve revert hashed state from the changesets of the blocks at or after this block that
+    /// were moved to static files.
+    fn static_file_revert_state(&self) -> ProviderResult<HashedPostState> {
+        type KH<P> =
+            <<P as StateCommitmentProvider>::StateCommitment as StateCommitment>::KeyHasher;
+
+        let static_file_provider = self.provider.static_file_provider();
+
+        // Record value before first occurring account change.
+        let mut accounts = AddressMap::default();
+        if let Some(range) = self.static_file_changesets_range(StaticFileSegment::AccountChangeSets)
+        {
+            for (_, AccountBeforeTx { address, info }) in
+                static_file_provider.account_changesets_range(range)?
+            {
+                accounts.entry(address).or_insert(info);
+            }
+        }
+
+        // Record value before first occurring storage change.
+        let mut storages = AddressMap::<B256Map<U256>>::default();
+        if let Some(range) = self.static_file_changesets_range(StaticFileSegment::StorageChangeSets)
+        {
+            for (key, StorageEntry { key: slot, value }) in
+                static_file_provider.storage_changesets_range(range)?
+            {
+                storages.entry(key.address()).or_default().entry(slot).or_insert(value);
+            }
+        }
+
+        Ok(HashedPostState {
+            accounts: accounts
+                .into_iter()
+                .map(|(address, info)| (KH::<Provider>::hash_key(address), info))
+                .collect(),
+            storages: storages
+                .into_iter()
+                .map(|(address, storage)| {
+                    (
+                        KH::<Provider>::hash_key(address),
+                        HashedStorage::from_iter(
+                            false,
+                            storage
+                                .into_iter()
+                                .map(|(slot, value)| (KH::<Provider>::hash_key(slot), value)),
+                        ),
+                    )
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns the range of blocks at or after this block that are stored in the static files of
+    /// the given changesets segment, if any.
+    fn static_file_changesets_range(
+        &self,
+        segment: StaticFileSegment,
+    ) -> Option<Range<BlockNumber>> {
+        self.provider
+            .static_file_provider()
+            .get_highest_static_file_block(segment)
+            .filter(|highest| *highest >= self.block_number)
+            .map(|highest| self.block_number..highest + 1)
+    }
+
+    /// Retrieve the trie nodes that were updated or removed at or after this block, with their
+    /// values at the start of the block, from the trie changesets.
+    ///
+    /// This walks the trie changesets of all blocks from this block to the tip, so like
+    /// [`Self::revert_state`] the cost grows with the distance to the tip. The distance is bounded
+    /// by [`MAX_TRIE_CHANGESETS_REVERT_DISTANCE`].
+    ///
+    /// Returns `None` if the trie changesets of the blocks are not available.
+    fn revert_trie(&self) -> ProviderResult<Option<TrieUpdates>> {
+        if !self.has_trie_changesets()? {
+            return Ok(None)
+        }
+
+        let mut trie_updates = TrieUpdates::default();
+
+        // The first changeset of a node at or after this block holds its value at the start of
+        // the block.
+        for entry in self
+            .tx()
+            .cursor_dup_read::<tables::AccountsTrieChangeSets>()?
+            .walk_range(self.block_number..)?
+        {
+            let (_, TrieChangeSetsEntry { nibbles, node }) = entry?;
+            if trie_updates.account_nodes.contains_key(&nibbles.0) ||
+                trie_updates.removed_nodes.contains(&nibbles.0)
+            {
+                continue
+            }
+            match node {
+                Some(node) => {
+                    trie_updates.account_nodes.insert(nibbles.0, node);
+                }
+                None => {
+                    trie_updates.removed_nodes.insert(nibbles.0);
+                }
+            }
+        }
+
+        for entry in self
+            .tx()
+            .cursor_dup_read::<tables::StoragesTrieChangeSets>()?
+            .walk_range(BlockNumberHashedAddress((self.block_number, B256::ZERO))..)?
+        {
+            let (key, TrieChangeSetsEntry { nibbles, node }) = entry?;
+            let storage_trie = trie_updates.storage_tries.entry(key.hashed_address()).or_default();
+            if storage_trie.storage_nodes.contains_key(&nibbles.0) ||
+                storage_trie.removed_nodes.contains(&nibbles.0)
+            {
+                continue
+            }
+            match node {
+                Some(node) => {
+                    storage_trie.storage_nodes.insert(nibbles.0, node);
+                }
+                None => {
+                    storage_trie.removed_nodes.insert(nibbles.0);
+                }
+            }
+        }
+
+        Ok(Some(trie_updates))
+    }
+
+    /// Prepends the revert state and, if available, the reverted trie nodes to the trie input.
+    fn prepend_revert(&self, input: &mut TrieInput) -> ProviderResult<()> {
+        let revert_state = self.revert_state()?;
+        match self.revert_trie()? {
+            Some(revert_trie) => input.prepend_cached(revert_trie, revert_state),
+            None => input.prepend(revert_state),
+        }
+        Ok(())
     }
 
     /// Retrieve revert hashed storage for this history provider and target address.
@@ -154,7 +320,96 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedStorage::from_reverts(self.tx(), address, self.block_number)?)
+        let mut revert_storage =
+            HashedStorage::from_reverts(self.tx(), address, self.block_number)?;
+
+        // Changesets in static files belong to lower blocks than the ones in the database, so
+        // their values take precedence.
+        //
+        // Instead of decoding the changesets of all blocks up to the tip, the history index is
+        // used to find the first change of each slot at or after this block, and only that entry
+        // is read.
+        if let Some(range) = self.static_file_changesets_range(StaticFileSegment::StorageChangeSets)
+        {
+            let static_file_provider = self.provider.static_file_provider();
+            let mut cursor = self.tx().cursor_read::<tables::StoragesHistory>()?;
+            let mut last_slot = None;
+            for entry in cursor.walk(Some(StorageShardedKey::new(address, B256::ZERO, 0)))? {
+                let (key, chunk) = entry?;
+                if key.address != address {
+                    break
+                }
+
+                // Shards are sorted by their highest block, so the first shard of a slot that
+                // ends at or after this block contains its first change.
+                let slot = key.sharded_key.key;
+                if key.sharded_key.highest_block_number < self.block_number ||
+                    last_slot == Some(slot)
+                {
+                    continue
+                }
+                last_slot = Some(slot);
+
+                let rank = self.block_number.checked_sub(1).map_or(0, |block| chunk.0.rank(block));
+                let Some(block_number) = chunk.0.select(rank).filter(|block| range.contains(block))
+                else {
+                    continue
+                };
+                if let Some(entry) =
+                    static_file_provider.storage_changeset_entry(block_number, &address, &slot)?
+                {
+                    revert_storage.storage.insert(
+                        <Provider::StateCommitment as StateCommitment>::KeyHasher::hash_key(slot),
+                        entry.value,
+                    );
+                }
+            }
+        }
+
+        Ok(revert_storage)
+    }
+
+    /// Returns the changeset entry of the account at the given block, from static files if the
+    /// block was moved there and from the database otherwise.
+    fn account_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        self.provider.static_file_provider().get_with_static_file_or_database(
+            StaticFileSegment::AccountChangeSets,
+            block_number,
+            |static_file| static_file.account_changeset_entry(block_number, &address),
+            || {
+                Ok(self
+                    .tx()
+                    .cursor_dup_read::<tables::AccountChangeSets>()?
+                    .seek_by_key_subkey(block_number, address)?
+                    .filter(|acc| acc.address == address))
+            },
+        )
+    }
+
+    /// Returns the changeset entry of the storage slot at the given block, from static files if
+    /// the block was moved there and from the database otherwise.
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        self.provider.static_file_provider().get_with_static_file_or_database(
+            StaticFileSegment::StorageChangeSets,
+            block_number,
+            |static_file| static_file.storage_changeset_entry(block_number, &address, &storage_key),
+            || {
+                Ok(self
+                    .tx()
+                    .cursor_dup_read::<tables::StorageChangeSets>()?
+                    .seek_by_key_subkey((block_number, address).into(), storage_key)?
+                    .filter(|entry| entry.key == storage_key))
+            },
+        )
     }
 
     fn history_info<T, K>(
@@ -245,18 +500,16 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provi
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> AccountReader
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > AccountReader for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         match self.account_history_lookup(*address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, *address)?
-                .filter(|acc| &acc.address == address)
+                .account_changeset(changeset_block_number, *address)?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address: *address,
@@ -286,8 +539,9 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > StateRootProvider for HistoricalStateProviderRef<'_, Provider>
 {
     fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
         let mut revert_state = self.revert_state()?;
@@ -322,8 +576,9 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRootProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > StorageRootProvider for HistoricalStateProviderRef<'_, Provider>
 {
     fn storage_root(
         &self,
@@ -361,8 +616,9 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProofProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > StateProofProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get account and storage proofs.
     fn proof(
@@ -371,7 +627,7 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         address: Address,
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
-        input.prepend(self.revert_state()?);
+        self.prepend_revert(&mut input)?;
         Proof::overlay_account_proof(self.tx(), input, address, slots).map_err(ProviderError::from)
     }
 
@@ -380,16 +636,30 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProof
         mut input: TrieInput,
         targets: MultiProofTargets,
     ) -> ProviderResult<MultiProof> {
-        input.prepend(self.revert_state()?);
+        self.prepend_revert(&mut input)?;
         Proof::overlay_multiproof(self.tx(), input, targets).map_err(ProviderError::from)
     }
 
     fn witness(&self, mut input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
-        input.prepend(self.revert_state()?);
+        self.prepend_revert(&mut input)?;
         TrieWitness::overlay_witness(self.tx(), input, target)
             .map_err(ProviderError::from)
             .map(|hm| hm.into_values().collect())
     }
+
+    fn has_trie_changesets(&self) -> ProviderResult<bool> {
+        let tip = self.provider.last_block_number()?;
+        if tip.saturating_sub(self.block_number) > MAX_TRIE_CHANGESETS_REVERT_DISTANCE {
+            return Ok(false)
+        }
+
+        // Trie changesets are available for all blocks above the prune checkpoint.
+        Ok(self.tx().get::<tables::PruneCheckpoints>(PruneSegment::TrieChangeSets)?.is_some_and(
+            |checkpoint| {
+                checkpoint.block_number.is_none_or(|pruned_to| pruned_to < self.block_number)
+            },
+        ))
+    }
 }
 
 impl<Provider: StateCommitmentProvider> HashedPostStateProvider
@@ -402,8 +672,13 @@ impl<Provider: StateCommitmentProvider> HashedPostStateProvider
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider>
-    StateProvider for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + BlockHashReader
+            + StaticFileProviderFactory
+            + StateCommitmentProvider,
+    > StateProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get storage.
     fn storage(
@@ -414,10 +689,7 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentPr
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx()
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.storage_changeset(changeset_block_number, address, storage_key)?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -463,8 +735,9 @@ pub struct HistoricalStateProvider<Provider> {
     lowest_available_blocks: LowestAvailableBlocks,
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
-    HistoricalStateProvider<Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > HistoricalStateProvider<Provider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
@@ -507,7 +780,7 @@ impl<Provider: StateCommitmentProvider> StateCommitmentProvider
 }
 
 // Delegates all provider impls to [HistoricalStateProviderRef]
-delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider]);
+delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + StaticFileProviderFactory + StateCommitmentProvider]);
 
 /// Lowest blocks at which different parts of the state are available.
 /// They may be [Some] if pruning is enabled.
@@ -543,15 +816,19 @@ mod tests {
         providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        StaticFileProviderFactory, StaticFileWriter,
     };
-    use alloy_primitives::{address, b256, Address, B256, U256};
+    use alloy_primitives::{address, b256, keccak256, Address, B256, U256};
     use reth_db_api::{
-        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey, StorageBeforeTx,
+        },
         tables,
         transaction::{DbTx, DbTxMut},
         BlockNumberList,
     };
     use reth_primitives_traits::{Account, StorageEntry};
+    use reth_static_file_types::StaticFileSegment;
     use reth_storage_api::{
         BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory,
         StateCommitmentProvider,
@@ -566,7 +843,11 @@ mod tests {
     const fn assert_state_provider<T: StateProvider>() {}
     #[expect(dead_code)]
     const fn assert_historical_state_provider<
-        T: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider,
+        T: DBProvider
+            + BlockNumReader
+            + BlockHashReader
+            + StaticFileProviderFactory
+            + StateCommitmentProvider,
     >() {
         assert_state_provider::<HistoricalStateProvider<T>>();
     }
@@ -779,6 +1060,104 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn history_provider_static_file_changesets() {
+        let factory = create_test_provider_factory();
+
+        let acc_plain = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at7 = Account { nonce: 7, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+
+        // The changesets of blocks 0 to 3 are in static files, the ones of block 7 in the database.
+        let static_file_provider = factory.static_file_provider();
+        let mut account_writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        let mut storage_writer =
+            static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+        for block in 0..=3 {
+            let (accounts, storages) = match block {
+                1 => (
+                    vec![AccountBeforeTx { address: ADDRESS, info: None }],
+                    vec![StorageBeforeTx { address: ADDRESS, key: STORAGE, value: U256::ZERO }],
+                ),
+                3 => (
+                    vec![AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) }],
+                    vec![StorageBeforeTx { address: ADDRESS, key: STORAGE, value: U256::from(3) }],
+                ),
+                _ => (Vec::new(), Vec::new()),
+            };
+            account_writer.append_account_changeset(block, accounts).unwrap();
+            storage_writer.append_storage_changeset(block, storages).unwrap();
+        }
+        account_writer.commit().unwrap();
+        storage_writer.commit().unwrap();
+        drop((account_writer, storage_writer));
+
+        let tx = factory.provider_rw().unwrap().into_tx();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([1, 3, 7]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([1, 3, 7]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            7,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at7) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>(
+            (7, ADDRESS).into(),
+            StorageEntry { key: STORAGE, value: U256::from(7) },
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(ADDRESS, acc_plain).unwrap();
+        tx.put::<tables::PlainStorageState>(
+            ADDRESS,
+            StorageEntry { key: STORAGE, value: U256::from(100) },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        for (block, account, storage) in [
+            (1, None, Some(U256::ZERO)),
+            (2, Some(acc_at3), Some(U256::from(3))),
+            (3, Some(acc_at3), Some(U256::from(3))),
+            (4, Some(acc_at7), Some(U256::from(7))),
+            (8, Some(acc_plain), Some(U256::from(100))),
+        ] {
+            let provider = HistoricalStateProviderRef::new(&db, block);
+            assert_eq!(provider.basic_account(&ADDRESS).unwrap(), account, "block {block}");
+            assert_eq!(provider.storage(ADDRESS, STORAGE).unwrap(), storage, "block {block}");
+        }
+
+        // The values of the first change at or after the block win, wherever they are stored.
+        let hashed_address = keccak256(ADDRESS);
+        let hashed_slot = keccak256(STORAGE);
+        for (block, account, storage) in [(2, acc_at3, U256::from(3)), (5, acc_at7, U256::from(7))]
+        {
+            let provider = HistoricalStateProviderRef::new(&db, block);
+
+            let revert_state = provider.revert_state().unwrap();
+            assert_eq!(revert_state.accounts.get(&hashed_address), Some(&Some(account)));
+            assert_eq!(
+                revert_state.storages.get(&hashed_address).unwrap().storage.get(&hashed_slot),
+                Some(&storage)
+            );
+
+            let revert_storage = provider.revert_storage(ADDRESS).unwrap();
+            assert_eq!(revert_storage.storage.get(&hashed_slot), Some(&storage));
+        }
+    }
+
     #[test]
     fn history_provider_unavailable() {
         let factory = create_test_provider_factory();
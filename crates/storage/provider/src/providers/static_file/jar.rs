@@ -11,16 +11,17 @@ use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
 use reth_chainspec::ChainInfo;
 use reth_db::static_file::{
-    BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask, ReceiptMask, StaticFileCursor,
-    TDWithHashMask, TotalDifficultyMask, TransactionMask,
+    AccountChangeSetMask, BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask,
+    ReceiptMask, StaticFileCursor, StorageChangeSetMask, TDWithHashMask, TotalDifficultyMask,
+    TransactionMask,
 };
 use reth_db_api::{
-    models::StoredBlockBodyIndices,
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
     table::{Decompress, Value},
 };
 use reth_node_types::NodePrimitives;
-use reth_primitives_traits::{SealedHeader, SignedTransaction};
-use reth_storage_api::BlockBodyIndicesProvider;
+use reth_primitives_traits::{SealedHeader, SignedTransaction, StorageEntry};
+use reth_storage_api::{BlockBodyIndicesProvider, ChangeSetReader, StorageChangeSetReader};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
     fmt::Debug,
@@ -64,7 +65,10 @@ impl<'a, N: NodePrimitives> StaticFileJarProvider<'a, N> {
     where
         'b: 'a,
     {
-        let result = StaticFileCursor::new(self.value(), self.mmap_handle())?;
+        let mut result = StaticFileCursor::new(self.value(), self.mmap_handle())?;
+        if let Some(verifier) = self.checksum_verifier() {
+            result = result.with_checksum_verification(verifier);
+        }
 
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
@@ -381,3 +385,37 @@ impl<N: NodePrimitives> BlockBodyIndicesProvider for StaticFileJarProvider<'_, N
         Ok(indices)
     }
 }
+
+impl<N: NodePrimitives> ChangeSetReader for StaticFileJarProvider<'_, N> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        Ok(self
+            .cursor()?
+            .get_one::<AccountChangeSetMask>(block_number.into())?
+            .map(|changeset| changeset.changes)
+            .unwrap_or_default())
+    }
+}
+
+impl<N: NodePrimitives> StorageChangeSetReader for StaticFileJarProvider<'_, N> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(self
+            .cursor()?
+            .get_one::<StorageChangeSetMask>(block_number.into())?
+            .map(|changeset| {
+                changeset
+                    .changes
+                    .into_iter()
+                    .map(|change| {
+                        (BlockNumberAddress((block_number, change.address)), change.entry())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
@@ -22,13 +22,14 @@ use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec};
 use reth_db::{
     lockfile::StorageLock,
     static_file::{
-        iter_static_files, BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask,
-        ReceiptMask, StaticFileCursor, TDWithHashMask, TransactionMask,
+        iter_static_files, AccountChangeSetMask, BlockHashMask, BodyIndicesMask, HeaderMask,
+        HeaderWithHashMask, ReceiptMask, StaticFileCursor, StorageChangeSetMask, TDWithHashMask,
+        TransactionMask,
     },
 };
 use reth_db_api::{
     cursor::DbCursorRO,
-    models::StoredBlockBodyIndices,
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
     table::{Decompress, Table, Value},
     tables,
     transaction::DbTx,
@@ -36,13 +37,15 @@ use reth_db_api::{
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
 use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
 use reth_node_types::{FullNodePrimitives, NodePrimitives};
-use reth_primitives_traits::{RecoveredBlock, SealedHeader, SignedTransaction};
+use reth_primitives_traits::{RecoveredBlock, SealedHeader, SignedTransaction, StorageEntry};
 use reth_stages_types::{PipelineTarget, StageId};
 use reth_static_file_types::{
     find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive, StaticFileSegment,
     DEFAULT_BLOCKS_PER_STATIC_FILE,
 };
-use reth_storage_api::{BlockBodyIndicesProvider, DBProvider};
+use reth_storage_api::{
+    BlockBodyIndicesProvider, ChangeSetReader, DBProvider, LogIndexReader, StorageChangeSetReader,
+};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
@@ -260,6 +263,8 @@ pub struct StaticFileProviderInner<N> {
     writers: StaticFileWriters<N>,
     /// Metrics for the static files.
     metrics: Option<Arc<StaticFileProviderMetrics>>,
+    /// Whether to verify the checksums of static file rows when reading them.
+    verify_checksums: bool,
     /// Access rights of the provider.
     access: StaticFileAccess,
     /// Number of blocks per file.
@@ -288,6 +293,7 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
             metrics: None,
+            verify_checksums: false,
             access,
             blocks_per_file: DEFAULT_BLOCKS_PER_STATIC_FILE,
             _lock_file,
@@ -326,6 +332,17 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Self(Arc::new(provider))
     }
 
+    /// Enables verification of the static file checksums on read.
+    ///
+    /// Every chunk of rows is verified against its checksum the first time it's read, and reads of
+    /// corrupted rows fail instead of returning their data.
+    pub fn with_checksum_verification(self) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.verify_checksums = true;
+        Self(Arc::new(provider))
+    }
+
     /// Reports metrics for the static files.
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
@@ -453,17 +470,33 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     /// This will not delete the file that contains the block itself, because files can only be
     /// removed entirely.
     pub fn delete_transactions_below(&self, block: BlockNumber) -> ProviderResult<()> {
+        self.delete_segment_below_block(StaticFileSegment::Transactions, block)
+    }
+
+    /// Deletes all static files of `segment` below the given block. Used to prune changesets and
+    /// expire transactions.
+    ///
+    /// This will not delete the file that contains the block itself, because files can only be
+    /// removed entirely.
+    pub fn delete_segment_below_block(
+        &self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+    ) -> ProviderResult<()> {
         // Nothing to delete if block is 0.
         if block == 0 {
             return Ok(())
         }
 
         loop {
-            let Some(block_height) =
-                self.get_lowest_static_file_block(StaticFileSegment::Transactions)
-            else {
+            let Some(mut block_height) = self.get_lowest_static_file_block(segment) else {
                 return Ok(())
             };
+            // The latest file usually holds fewer blocks than its range, and can be deleted as
+            // soon as its highest block is below the given one.
+            if let Some(highest_block) = self.get_highest_static_file_block(segment) {
+                block_height = block_height.min(highest_block);
+            }
 
             if block_height >= block {
                 return Ok(())
@@ -471,15 +504,16 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
             debug!(
                 target: "provider::static_file",
+                ?segment,
                 ?block_height,
-                "Deleting transaction static file below block"
+                "Deleting static file below block"
             );
 
             // now we need to wipe the static file, this will take care of updating the index and
-            // advance the lowest tracked block height for the transactions segment.
-            self.delete_jar(StaticFileSegment::Transactions, block_height)
+            // advance the lowest tracked block height for the segment.
+            self.delete_jar(segment, block_height)
                 .inspect_err(|err| {
-                    warn!( target: "provider::static_file", %block_height, ?err, "Failed to delete transaction static file below block")
+                    warn!( target: "provider::static_file", ?segment, %block_height, ?err, "Failed to delete static file below block")
                 })
                 ?;
         }
@@ -533,7 +567,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Creating jar from scratch");
             let path = self.path.join(segment.filename(fixed_block_range));
             let jar = NippyJar::load(&path).map_err(ProviderError::other)?;
-            self.map.entry(key).insert(LoadedJar::new(jar)?).downgrade().into()
+            let jar = LoadedJar::new(jar, self.verify_checksums)?;
+            self.map.entry(key).insert(jar).downgrade().into()
         };
 
         if let Some(metrics) = &self.metrics {
@@ -649,7 +684,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 }
 
                 // Update the cached provider.
-                self.map.insert((fixed_range.end(), segment), LoadedJar::new(jar)?);
+                self.map.insert(
+                    (fixed_range.end(), segment),
+                    LoadedJar::new(jar, self.verify_checksums)?,
+                );
 
                 // Delete any cached provider that no longer has an associated jar.
                 self.map.retain(|(end, seg), _| !(*seg == segment && *end > fixed_range.end()));
@@ -786,6 +824,14 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 continue
             }
 
+            // Changesets are only stored as static files by nodes that started writing them from
+            // genesis, otherwise they stay in the database. Once stored, the segment has to be
+            // checked even if the history is pruned now, since new changesets go to the database
+            // from then on.
+            if segment.is_changesets() && self.get_highest_static_file_block(segment).is_none() {
+                continue
+            }
+
             let initial_highest_block = self.get_highest_static_file_block(segment);
 
             //  File consistency is broken if:
@@ -884,6 +930,28 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         highest_block,
                         highest_block,
                     )?,
+                StaticFileSegment::AccountChangeSets => self
+                    .ensure_invariants::<_, tables::AccountChangeSets>(
+                        provider,
+                        segment,
+                        highest_block,
+                        highest_block,
+                    )?,
+                StaticFileSegment::StorageChangeSets => {
+                    let mut db_cursor =
+                        provider.tx_ref().cursor_read::<tables::StorageChangeSets>()?;
+                    let db_entries = db_cursor
+                        .first()?
+                        .zip(db_cursor.last()?)
+                        .map(|((first, _), (last, _))| (first.block_number(), last.block_number()));
+                    self.ensure_invariants_with_database_entries(
+                        provider,
+                        segment,
+                        db_entries,
+                        highest_block,
+                        highest_block,
+                    )?
+                }
             } {
                 update_unwind_target(unwind);
             }
@@ -931,8 +999,32 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Provider: DBProvider + BlockReader + StageCheckpointReader,
     {
         let mut db_cursor = provider.tx_ref().cursor_read::<T>()?;
+        let db_entries =
+            db_cursor.first()?.zip(db_cursor.last()?).map(|((first, _), (last, _))| (first, last));
+
+        self.ensure_invariants_with_database_entries(
+            provider,
+            segment,
+            db_entries,
+            highest_static_file_entry,
+            highest_static_file_block,
+        )
+    }
 
-        if let Some((db_first_entry, _)) = db_cursor.first()? {
+    /// Same as [`Self::ensure_invariants`], given the first and last keys of the corresponding
+    /// database table, for tables whose keys are not plain numbers.
+    fn ensure_invariants_with_database_entries<Provider>(
+        &self,
+        provider: &Provider,
+        segment: StaticFileSegment,
+        db_entries: Option<(u64, u64)>,
+        highest_static_file_entry: Option<u64>,
+        highest_static_file_block: Option<BlockNumber>,
+    ) -> ProviderResult<Option<BlockNumber>>
+    where
+        Provider: DBProvider + BlockReader + StageCheckpointReader,
+    {
+        if let Some((db_first_entry, db_last_entry)) = db_entries {
             if let (Some(highest_entry), Some(highest_block)) =
                 (highest_static_file_entry, highest_static_file_block)
             {
@@ -952,12 +1044,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 }
             }
 
-            if let Some((db_last_entry, _)) = db_cursor.last()? {
-                if highest_static_file_entry
-                    .is_none_or(|highest_entry| db_last_entry > highest_entry)
-                {
-                    return Ok(None)
-                }
+            if highest_static_file_entry.is_none_or(|highest_entry| db_last_entry > highest_entry) {
+                return Ok(None)
             }
         }
 
@@ -970,7 +1058,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             .get_stage_checkpoint(match segment {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions | StaticFileSegment::BlockMeta => StageId::Bodies,
-                StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::Receipts |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => StageId::Execution,
             })?
             .unwrap_or_default()
             .block_number;
@@ -1002,6 +1092,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             if segment.is_headers() {
                 // TODO(joshie): is_block_meta
                 writer.prune_headers(highest_static_file_block - checkpoint_block_number)?;
+            } else if segment.is_changesets() {
+                writer.prune_changesets(highest_static_file_block - checkpoint_block_number)?;
             } else if let Some(block) = provider.block_body_indices(checkpoint_block_number)? {
                 // todo joshie: is querying block_body_indices a potential issue once bbi is moved
                 // to sf as well
@@ -1071,6 +1163,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
             block_meta: self.get_highest_static_file_block(StaticFileSegment::BlockMeta),
+            account_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            storage_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
         }
     }
 
@@ -1303,6 +1399,90 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(data)
     }
 
+    /// Returns the account changes of every block in `range`, alongside their block number.
+    pub fn account_changesets_range(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let changesets = self.fetch_range_with_predicate(
+            StaticFileSegment::AccountChangeSets,
+            range,
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<AccountChangeSetMask>(number.into())?
+                    .map(|changeset| (number, changeset)))
+            },
+            |_| true,
+        )?;
+
+        Ok(changesets
+            .into_iter()
+            .flat_map(|(number, changeset)| {
+                changeset.changes.into_iter().map(move |change| (number, change))
+            })
+            .collect())
+    }
+
+    /// Returns the storage changes of every block in `range`, keyed by block number and address.
+    pub fn storage_changesets_range(
+        &self,
+        range: Range<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let changesets = self.fetch_range_with_predicate(
+            StaticFileSegment::StorageChangeSets,
+            range,
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<StorageChangeSetMask>(number.into())?
+                    .map(|changeset| (number, changeset)))
+            },
+            |_| true,
+        )?;
+
+        Ok(changesets
+            .into_iter()
+            .flat_map(|(number, changeset)| {
+                changeset.changes.into_iter().map(move |change| {
+                    (BlockNumberAddress((number, change.address)), change.entry())
+                })
+            })
+            .collect())
+    }
+
+    /// Returns the state of `address` before block `number`, if the block changed it.
+    pub fn account_changeset_entry(
+        &self,
+        number: BlockNumber,
+        address: &Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        let provider = self.get_segment_provider_from_block(
+            StaticFileSegment::AccountChangeSets,
+            number,
+            None,
+        )?;
+        let changeset = provider.cursor()?.get_one::<AccountChangeSetMask>(number.into())?;
+
+        Ok(changeset.and_then(|changeset| changeset.get(address).cloned()))
+    }
+
+    /// Returns the storage slot `key` of `address` before block `number`, if the block changed
+    /// it.
+    pub fn storage_changeset_entry(
+        &self,
+        number: BlockNumber,
+        address: &Address,
+        key: &B256,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        let provider = self.get_segment_provider_from_block(
+            StaticFileSegment::StorageChangeSets,
+            number,
+            None,
+        )?;
+        let changeset = provider.cursor()?.get_one::<StorageChangeSetMask>(number.into())?;
+
+        Ok(changeset.and_then(|changeset| changeset.get(address, key).map(|change| change.entry())))
+    }
+
     /// Returns `static_files` directory
     #[cfg(any(test, feature = "test-utils"))]
     pub fn path(&self) -> &Path {
@@ -1483,6 +1663,8 @@ impl<N: NodePrimitives> BlockHashReader for StaticFileProvider<N> {
     }
 }
 
+impl<N: NodePrimitives> LogIndexReader for StaticFileProvider<N> {}
+
 impl<N: NodePrimitives<SignedTx: Value + SignedTransaction, Receipt: Value>> ReceiptProvider
     for StaticFileProvider<N>
 {
@@ -1829,6 +2011,48 @@ impl<N: NodePrimitives> BlockBodyIndicesProvider for StaticFileProvider<N> {
     }
 }
 
+impl<N: NodePrimitives> ChangeSetReader for StaticFileProvider<N> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::AccountChangeSets,
+            block_number,
+            None,
+        )
+        .and_then(|provider| provider.account_block_changeset(block_number))
+        .or_else(|err| {
+            if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                Ok(Vec::new())
+            } else {
+                Err(err)
+            }
+        })
+    }
+}
+
+impl<N: NodePrimitives> StorageChangeSetReader for StaticFileProvider<N> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::StorageChangeSets,
+            block_number,
+            None,
+        )
+        .and_then(|provider| provider.storage_changeset(block_number))
+        .or_else(|err| {
+            if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                Ok(Vec::new())
+            } else {
+                Err(err)
+            }
+        })
+    }
+}
+
 impl<N: NodePrimitives> StatsReader for StaticFileProvider<N> {
     fn count_entries<T: Table>(&self) -> ProviderResult<usize> {
         match T::NAME {
@@ -8,7 +8,7 @@ mod writer;
 pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
-use reth_nippy_jar::NippyJar;
+use reth_nippy_jar::{ChecksumVerifier, NippyJar};
 use reth_static_file_types::{SegmentHeader, StaticFileSegment};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{ops::Deref, sync::Arc};
@@ -21,14 +21,18 @@ type LoadedJarRef<'a> = dashmap::mapref::one::Ref<'a, (u64, StaticFileSegment),
 pub struct LoadedJar {
     jar: NippyJar<SegmentHeader>,
     mmap_handle: Arc<reth_nippy_jar::DataReader>,
+    /// Verifies the checksums of the rows read by cursors, if enabled.
+    checksum_verifier: Option<Arc<ChecksumVerifier>>,
 }
 
 impl LoadedJar {
-    fn new(jar: NippyJar<SegmentHeader>) -> ProviderResult<Self> {
+    fn new(jar: NippyJar<SegmentHeader>, verify_checksums: bool) -> ProviderResult<Self> {
         match jar.open_data_reader() {
             Ok(data_reader) => {
                 let mmap_handle = Arc::new(data_reader);
-                Ok(Self { jar, mmap_handle })
+                let checksum_verifier =
+                    verify_checksums.then(|| Arc::new(ChecksumVerifier::new(&jar)));
+                Ok(Self { jar, mmap_handle, checksum_verifier })
             }
             Err(e) => Err(ProviderError::other(e)),
         }
@@ -39,6 +43,11 @@ impl LoadedJar {
         self.mmap_handle.clone()
     }
 
+    /// Returns a clone of the checksum verifier, if checksums are verified on read.
+    fn checksum_verifier(&self) -> Option<Arc<ChecksumVerifier>> {
+        self.checksum_verifier.clone()
+    }
+
     const fn segment(&self) -> StaticFileSegment {
         self.jar.user_header().segment()
     }
@@ -58,13 +67,16 @@ mod tests {
         test_utils::create_test_provider_factory, HeaderProvider, StaticFileProviderFactory,
     };
     use alloy_consensus::{Header, SignableTransaction, Transaction, TxLegacy};
-    use alloy_primitives::{BlockHash, Signature, TxNumber, B256, U256};
+    use alloy_primitives::{Address, BlockHash, Signature, TxNumber, B256, U256};
     use rand::seq::SliceRandom;
     use reth_db::test_utils::create_test_static_files_dir;
     use reth_db_api::{
-        transaction::DbTxMut, CanonicalHeaders, HeaderNumbers, HeaderTerminalDifficulties, Headers,
+        models::{AccountBeforeTx, BlockNumberAddress, StorageBeforeTx},
+        transaction::DbTxMut,
+        CanonicalHeaders, HeaderNumbers, HeaderTerminalDifficulties, Headers,
     };
     use reth_ethereum_primitives::{EthPrimitives, Receipt, TransactionSigned};
+    use reth_primitives_traits::StorageEntry;
     use reth_static_file_types::{
         find_fixed_range, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE,
     };
@@ -562,4 +574,53 @@ mod tests {
 
         Ok(count)
     }
+
+    #[test]
+    fn test_changesets_heal_interrupted_commit() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+        let address = Address::with_last_byte(1);
+
+        // The changesets of blocks 1 and 2 were committed to static files, but the node crashed
+        // before the database commit that moves the execution checkpoint past block 0.
+        {
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+            for block in 0..=2 {
+                writer
+                    .append_account_changeset(block, vec![AccountBeforeTx { address, info: None }])
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+        }
+        {
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+            for block in 0..=2 {
+                writer
+                    .append_storage_changeset(
+                        block,
+                        vec![StorageBeforeTx { address, key: B256::ZERO, value: U256::from(block) }],
+                    )
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(static_file_provider.check_consistency(&provider, false).unwrap(), None);
+
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            assert_eq!(static_file_provider.get_highest_static_file_block(segment), Some(0));
+        }
+        assert_eq!(
+            static_file_provider.account_changesets_range(0..3).unwrap(),
+            vec![(0, AccountBeforeTx { address, info: None })]
+        );
+        assert_eq!(
+            static_file_provider.storage_changesets_range(0..3).unwrap(),
+            vec![(BlockNumberAddress((0, address)), StorageEntry { key: B256::ZERO, value: U256::ZERO })]
+        );
+    }
 }
@@ -29,7 +29,7 @@ use reth_prune_types::PruneModes;
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
     BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, NodePrimitivesProvider, StageCheckpointReader,
+    HashedPostStateProvider, LogIndexReader, NodePrimitivesProvider, StageCheckpointReader,
     StateCommitmentProvider, StateProofProvider, StorageRootProvider,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
@@ -587,6 +587,11 @@ where
 {
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync + 'static> LogIndexReader
+    for MockEthProvider<T, ChainSpec>
+{
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync + 'static> BlockHashReader
     for MockEthProvider<T, ChainSpec>
 {
@@ -151,6 +151,11 @@ where
 
         debug!(target: "provider::storage_writer", block_count = %blocks.len(), "Writing blocks and execution data to storage");
 
+        // Trie changesets are only written if they are retained for a configured range of blocks,
+        // so nodes don't accumulate them forever by default.
+        let write_trie_changesets =
+            self.database().prune_modes_ref().trie_changesets.is_some_and(|mode| !mode.is_full());
+
         // TODO: Do performant / batched writes for each type of object
         // instead of a loop over all blocks,
         // meaning:
@@ -165,6 +170,7 @@ where
             trie,
         } in blocks
         {
+            let block_number = recovered_block.number();
             let block_hash = recovered_block.hash();
             self.database()
                 .insert_block(Arc::unwrap_or_clone(recovered_block), StorageLocation::Both)?;
@@ -180,9 +186,16 @@ where
             // insert hashes and intermediate merkle nodes
             self.database()
                 .write_hashed_state(&Arc::unwrap_or_clone(hashed_state).into_sorted())?;
-            self.database().write_trie_updates(
-                trie.as_ref().ok_or(ProviderError::MissingTrieUpdates(block_hash))?,
-            )?;
+            let trie = trie.as_ref().ok_or(ProviderError::MissingTrieUpdates(block_hash))?;
+            if write_trie_changesets {
+                self.database().write_trie_changesets(block_number, trie)?;
+            }
+            self.database().write_trie_updates(trie)?;
+        }
+
+        if !write_trie_changesets {
+            // Trie changesets written before the prune mode changed are incomplete now.
+            self.database().invalidate_trie_changesets(last_block_number)?;
         }
 
         // update history indices
@@ -228,7 +241,8 @@ where
 mod tests {
     use super::*;
     use crate::{
-        test_utils::create_test_provider_factory, AccountReader, StorageTrieWriter, TrieWriter,
+        test_utils::create_test_provider_factory, AccountReader, PruneCheckpointReader,
+        StorageTrieWriter, TrieWriter,
     };
     use alloy_primitives::{keccak256, map::HashMap, Address, B256, U256};
     use reth_db_api::{
@@ -240,10 +254,12 @@ mod tests {
     use reth_ethereum_primitives::Receipt;
     use reth_execution_types::ExecutionOutcome;
     use reth_primitives_traits::{Account, StorageEntry};
+    use reth_prune_types::PruneSegment;
     use reth_storage_api::{DatabaseProviderFactory, HashedPostStateProvider};
     use reth_trie::{
         test_utils::{state_root, storage_root_prehashed},
         HashedPostState, HashedStorage, StateRoot, StorageRoot, StorageRootProgress,
+        StorageTrieEntry, StoredNibbles,
     };
     use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot};
     use revm_database::{
@@ -1375,4 +1391,87 @@ mod tests {
         let storage_root = StorageRoot::overlay_root(tx, address, updated_storage.clone()).unwrap();
         assert_eq!(storage_root, storage_root_prehashed(updated_storage.storage));
     }
+
+    #[test]
+    fn trie_changesets_revert_trie() {
+        let provider_factory = create_test_provider_factory();
+        let provider_rw = provider_factory.database_provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+
+        // Enough accounts and storage slots for the tries to have nodes below the root.
+        let hashed_storage_address = keccak256(0u64.to_be_bytes());
+        for i in 0..2000u64 {
+            let account = Account { nonce: 1, balance: U256::from(i), bytecode_hash: None };
+            tx.put::<tables::HashedAccounts>(keccak256(i.to_be_bytes()), account).unwrap();
+        }
+        for i in 0..1000u64 {
+            tx.put::<tables::HashedStorages>(
+                hashed_storage_address,
+                StorageEntry { key: keccak256(i.to_be_bytes()), value: U256::from(i + 1) },
+            )
+            .unwrap();
+        }
+
+        let (_, updates) = StateRoot::from_tx(tx).root_with_updates().unwrap();
+        provider_rw.write_trie_updates(&updates).unwrap();
+        let account_trie = provider_rw.table::<tables::AccountsTrie>().unwrap();
+        let storage_trie = provider_rw.table::<tables::StoragesTrie>().unwrap();
+        assert!(!storage_trie.is_empty());
+
+        // Update and destroy some of the accounts, including the one with storage.
+        let mut state = HashedPostState::default();
+        for i in 0..1000u64 {
+            let account = (i % 3 != 0).then_some(Account {
+                nonce: 2,
+                balance: U256::ZERO,
+                bytecode_hash: None,
+            });
+            state.accounts.insert(keccak256(i.to_be_bytes()), account);
+        }
+        state.storages.insert(hashed_storage_address, HashedStorage::new(true));
+
+        let (_, updates) = StateRoot::overlay_root_with_updates(tx, state.clone()).unwrap();
+        assert!(provider_rw.write_trie_changesets(1, &updates).unwrap() > 0);
+        provider_rw.write_hashed_state(&state.into_sorted()).unwrap();
+        provider_rw.write_trie_updates(&updates).unwrap();
+        assert_ne!(provider_rw.table::<tables::AccountsTrie>().unwrap(), account_trie);
+        assert!(provider_rw.table::<tables::StoragesTrie>().unwrap().is_empty());
+
+        // Applying the changesets restores the trie.
+        let mut account_trie_cursor = tx.cursor_write::<tables::AccountsTrie>().unwrap();
+        for (_, entry) in provider_rw.table::<tables::AccountsTrieChangeSets>().unwrap() {
+            let nibbles = StoredNibbles(entry.nibbles.0);
+            match entry.node {
+                Some(node) => account_trie_cursor.upsert(nibbles, &node).unwrap(),
+                None => {
+                    if account_trie_cursor.seek_exact(nibbles).unwrap().is_some() {
+                        account_trie_cursor.delete_current().unwrap();
+                    }
+                }
+            }
+        }
+        let mut storage_trie_cursor = tx.cursor_dup_write::<tables::StoragesTrie>().unwrap();
+        for (key, entry) in provider_rw.table::<tables::StoragesTrieChangeSets>().unwrap() {
+            assert_eq!(key.block_number(), 1);
+            if let Some(node) = entry.node {
+                storage_trie_cursor
+                    .upsert(
+                        key.hashed_address(),
+                        &StorageTrieEntry { nibbles: entry.nibbles, node },
+                    )
+                    .unwrap();
+            }
+        }
+        assert_eq!(provider_rw.table::<tables::AccountsTrie>().unwrap(), account_trie);
+        assert_eq!(provider_rw.table::<tables::StoragesTrie>().unwrap(), storage_trie);
+
+        // Changesets are only available for the blocks above the first written one.
+        assert_eq!(
+            provider_rw
+                .get_prune_checkpoint(PruneSegment::TrieChangeSets)
+                .unwrap()
+                .and_then(|checkpoint| checkpoint.block_number),
+            Some(0)
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    BlockBodyIndicesProvider, BlockNumReader, HeaderProvider, ReceiptProvider,
+    BlockBodyIndicesProvider, BlockNumReader, HeaderProvider, LogIndexReader, ReceiptProvider,
     ReceiptProviderIdExt, TransactionVariant, TransactionsProvider,
 };
 use alloc::{sync::Arc, vec::Vec};
@@ -53,6 +53,7 @@ pub trait BlockReader:
     + BlockBodyIndicesProvider
     + TransactionsProvider
     + ReceiptProvider
+    + LogIndexReader
     + Send
     + Sync
 {
@@ -39,6 +39,9 @@ pub use prune_checkpoint::*;
 mod receipts;
 pub use receipts::*;
 
+mod log_index;
+pub use log_index::*;
+
 mod stage_checkpoint;
 pub use stage_checkpoint::*;
 
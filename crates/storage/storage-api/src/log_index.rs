@@ -0,0 +1,60 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber, Log, B256};
+use auto_impl::auto_impl;
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// The blocks found in the log index for a log filter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogIndexMatches {
+    /// The highest block number of the queried range that is covered by the log index.
+    ///
+    /// Blocks above it are not indexed yet and need to be checked without the index.
+    pub indexed_to: BlockNumber,
+    /// The numbers of the blocks that may contain matching logs, in ascending order.
+    pub blocks: Vec<BlockNumber>,
+}
+
+/// Api trait for looking up blocks in the log index.
+#[auto_impl(&, Arc)]
+pub trait LogIndexReader: Send + Sync {
+    /// Returns the blocks in the given range that may contain logs emitted by any of the given
+    /// addresses and containing any of the given topics for each topic position.
+    ///
+    /// Empty address or topic sets match all logs. Topics are indexed regardless of their position
+    /// in the log, so the returned blocks are a superset of the blocks with matching logs.
+    ///
+    /// Returns `None` if the log index is not available for the start of the range, or if neither
+    /// addresses nor topics are given.
+    fn log_index_blocks(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<LogIndexMatches>> {
+        let _ = (range, addresses, topics);
+        Ok(None)
+    }
+}
+
+/// Api trait for updating the log index.
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexWriter: Send + Sync {
+    /// Appends the addresses and topics of the logs of the given blocks to the log index.
+    ///
+    /// Blocks must be given in ascending order and be higher than all blocks already in the
+    /// index.
+    fn insert_log_indices<'a>(
+        &self,
+        blocks: impl IntoIterator<Item = (BlockNumber, impl IntoIterator<Item = &'a Log>)>,
+    ) -> ProviderResult<()>;
+
+    /// Removes all blocks at or above the given block number from the log index.
+    ///
+    /// The logs of the removed blocks are required to find the affected index entries.
+    fn unwind_log_indices<'a>(
+        &self,
+        first_block: BlockNumber,
+        logs: impl IntoIterator<Item = &'a Log>,
+    ) -> ProviderResult<()>;
+}
@@ -16,6 +16,9 @@ pub trait StateWriter {
 
     /// Write the state and receipts to the database or static files if `static_file_producer` is
     /// `Some`. It should be `None` if there is any kind of pruning/filtering over the receipts.
+    ///
+    /// If `write_receipts_to` includes static files, the account and storage changesets are
+    /// written to static files as well, as long as the corresponding history is not pruned.
     fn write_state(
         &self,
         execution_outcome: &ExecutionOutcome<Self::Receipt>,
@@ -1,5 +1,5 @@
 use alloc::vec::Vec;
-use alloy_primitives::{map::B256Map, Address, Bytes, B256};
+use alloy_primitives::{map::B256Map, Address, BlockNumber, Bytes, B256};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie_common::{
     updates::{StorageTrieUpdates, TrieUpdates},
@@ -87,6 +87,12 @@ pub trait StateProofProvider: Send + Sync {
 
     /// Get trie witness for provided state.
     fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>>;
+
+    /// Returns `true` if the trie of a historical state can be restored from the stored trie
+    /// changesets, so that proofs don't require reverting the trie from the state changesets.
+    fn has_trie_changesets(&self) -> ProviderResult<bool> {
+        Ok(false)
+    }
 }
 
 /// Trie Writer
@@ -96,6 +102,25 @@ pub trait TrieWriter: Send + Sync {
     ///
     /// Returns the number of entries modified.
     fn write_trie_updates(&self, trie_updates: &TrieUpdates) -> ProviderResult<usize>;
+
+    /// Writes the trie changesets of the given block, i.e. the current values of all account and
+    /// storage trie nodes that are going to be updated or removed by the trie updates of the block.
+    ///
+    /// Must be called before the trie updates of the block are written.
+    ///
+    /// Returns the number of entries written.
+    fn write_trie_changesets(
+        &self,
+        block_number: BlockNumber,
+        trie_updates: &TrieUpdates,
+    ) -> ProviderResult<usize>;
+
+    /// Removes the trie changesets of all blocks above the given block number.
+    fn unwind_trie_changesets(&self, block_number: BlockNumber) -> ProviderResult<()>;
+
+    /// Marks the trie changesets of all blocks up to and including the given block number as
+    /// unavailable, because the trie was updated for these blocks without writing changesets.
+    fn invalidate_trie_changesets(&self, block_number: BlockNumber) -> ProviderResult<()>;
 }
 
 /// Storage Trie Writer
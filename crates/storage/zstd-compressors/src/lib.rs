@@ -25,7 +25,7 @@ pub use locals::*;
 #[cfg(feature = "std")]
 mod locals {
     use super::*;
-    use core::cell::RefCell;
+    use core::cell::{Cell, RefCell};
 
     // We use `thread_local` compressors and decompressors because dictionaries can be quite big,
     // and zstd-rs recommends to use one context/compressor per thread
@@ -55,7 +55,37 @@ mod locals {
                 Decompressor::with_dictionary(RECEIPT_DICTIONARY)
                     .expect("failed to initialize receipt decompressor"),
             ));
+
+        /// Whether types compress their encoding with the dictionaries on this thread.
+        static COMPRESSION_ENABLED: Cell<bool> = const { Cell::new(true) };
+    }
+
+    /// Returns whether types should compress their encoding with the dictionaries on this thread.
+    ///
+    /// It's only disabled while [`without_compression`] runs.
+    pub fn is_compression_enabled() -> bool {
+        COMPRESSION_ENABLED.with(Cell::get)
     }
+
+    /// Runs `f` with the dictionary compression of types disabled on this thread.
+    ///
+    /// Values encoded in `f` can still be decoded, since types flag whether their encoding is
+    /// compressed. This is useful when the encoded values are compressed as a whole afterwards,
+    /// e.g. by static files with their own dictionaries.
+    pub fn without_compression<R>(f: impl FnOnce() -> R) -> R {
+        let enabled = COMPRESSION_ENABLED.replace(false);
+        let result = f();
+        COMPRESSION_ENABLED.set(enabled);
+        result
+    }
+}
+
+/// Returns whether types should compress their encoding with the dictionaries.
+///
+/// Compression can only be disabled with `std`.
+#[cfg(not(feature = "std"))]
+pub const fn is_compression_enabled() -> bool {
+    true
 }
 
 /// Fn creates tx [`Compressor`]
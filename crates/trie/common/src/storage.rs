@@ -31,3 +31,61 @@ impl reth_codecs::Compact for StorageTrieEntry {
         (this, buf)
     }
 }
+
+/// The value of an intermediate trie node before it was changed by a block.
+///
+/// Stored in the trie changesets, which allow to revert the trie to historical blocks.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+pub struct TrieChangeSetsEntry {
+    /// The nibbles of the intermediate node
+    pub nibbles: StoredNibblesSubKey,
+    /// Encoded node before the change, or `None` if the node didn't exist.
+    pub node: Option<BranchNodeCompact>,
+}
+
+// NOTE: The subkey is encoded manually, same as for `StorageTrieEntry`. A missing node is encoded
+// by omitting the node bytes.
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for TrieChangeSetsEntry {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let nibbles_len = self.nibbles.to_compact(buf);
+        let node_len = self.node.as_ref().map_or(0, |node| node.to_compact(buf));
+        nibbles_len + node_len
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (nibbles, buf) = StoredNibblesSubKey::from_compact(buf, 33);
+        if len <= 33 {
+            return (Self { nibbles, node: None }, buf)
+        }
+        let (node, buf) = BranchNodeCompact::from_compact(buf, len - 33);
+        (Self { nibbles, node: Some(node) }, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Nibbles;
+    use reth_codecs::Compact;
+
+    #[test]
+    fn trie_changesets_entry_roundtrip() {
+        let nibbles = StoredNibblesSubKey(Nibbles::from_nibbles_unchecked([0x01, 0x02, 0x03]));
+        let node = BranchNodeCompact::new(0b11, 0b01, 0, vec![], None);
+
+        for entry in [
+            TrieChangeSetsEntry { nibbles: nibbles.clone(), node: None },
+            TrieChangeSetsEntry { nibbles, node: Some(node) },
+        ] {
+            let mut buf = Vec::new();
+            let len = entry.to_compact(&mut buf);
+            let (decoded, _) = TrieChangeSetsEntry::from_compact(&buf, len);
+            assert_eq!(decoded, entry);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use alloy_primitives::{
     map::{HashMap, HashSet},
-    BlockNumber, B256,
+    Address, BlockNumber, B256,
 };
 use core::{
     marker::PhantomData,
@@ -41,16 +41,44 @@ impl<TX, KH> Deref for PrefixSetLoader<'_, TX, KH> {
 impl<TX: DbTx, KH: KeyHasher> PrefixSetLoader<'_, TX, KH> {
     /// Load all account and storage changes for the given block range.
     pub fn load(self, range: RangeInclusive<BlockNumber>) -> Result<TriePrefixSets, DatabaseError> {
+        // Walk account changeset and collect changed accounts.
+        let mut account_changeset_cursor = self.cursor_read::<tables::AccountChangeSets>()?;
+        let changed_accounts = account_changeset_cursor
+            .walk_range(range.clone())?
+            .map(|entry| entry.map(|(_, AccountBeforeTx { address, .. })| address))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Walk storage changeset and collect changed storage slots.
+        let mut changed_storages = HashMap::<Address, Vec<B256>>::default();
+        let mut storage_cursor = self.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let storage_range = BlockNumberAddress::range(range);
+        for storage_entry in storage_cursor.walk_range(storage_range)? {
+            let (BlockNumberAddress((_, address)), StorageEntry { key, .. }) = storage_entry?;
+            changed_storages.entry(address).or_default().push(key);
+        }
+
+        self.load_changed(changed_accounts, changed_storages)
+    }
+
+    /// Load the prefix sets of the given changed accounts and storage slots.
+    ///
+    /// This allows loading prefix sets from changesets that are not stored in the database.
+    pub fn load_changed<S>(
+        self,
+        changed_accounts: impl IntoIterator<Item = Address>,
+        changed_storages: impl IntoIterator<Item = (Address, S)>,
+    ) -> Result<TriePrefixSets, DatabaseError>
+    where
+        S: IntoIterator<Item = B256>,
+    {
         // Initialize prefix sets.
         let mut account_prefix_set = PrefixSetMut::default();
         let mut storage_prefix_sets = HashMap::<B256, PrefixSetMut>::default();
         let mut destroyed_accounts = HashSet::default();
 
-        // Walk account changeset and insert account prefixes.
-        let mut account_changeset_cursor = self.cursor_read::<tables::AccountChangeSets>()?;
+        // Insert account prefixes.
         let mut account_hashed_state_cursor = self.cursor_read::<tables::HashedAccounts>()?;
-        for account_entry in account_changeset_cursor.walk_range(range.clone())? {
-            let (_, AccountBeforeTx { address, .. }) = account_entry?;
+        for address in changed_accounts {
             let hashed_address = KH::hash_key(address);
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
 
@@ -59,18 +87,15 @@ impl<TX: DbTx, KH: KeyHasher> PrefixSetLoader<'_, TX, KH> {
             }
         }
 
-        // Walk storage changeset and insert storage prefixes as well as account prefixes if missing
-        // from the account prefix set.
-        let mut storage_cursor = self.cursor_dup_read::<tables::StorageChangeSets>()?;
-        let storage_range = BlockNumberAddress::range(range);
-        for storage_entry in storage_cursor.walk_range(storage_range)? {
-            let (BlockNumberAddress((_, address)), StorageEntry { key, .. }) = storage_entry?;
+        // Insert storage prefixes as well as account prefixes if missing from the account prefix
+        // set.
+        for (address, keys) in changed_storages {
             let hashed_address = KH::hash_key(address);
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
-            storage_prefix_sets
-                .entry(hashed_address)
-                .or_default()
-                .insert(Nibbles::unpack(KH::hash_key(key)));
+            let storage_prefix_set = storage_prefix_sets.entry(hashed_address).or_default();
+            for key in keys {
+                storage_prefix_set.insert(Nibbles::unpack(KH::hash_key(key)));
+            }
         }
 
         Ok(TriePrefixSets {
@@ -376,6 +376,25 @@ export const sidebar: SidebarItem[] = [
                                     }
                                 ]
                             },
+                            {
+                                text: "reth db static-file",
+                                link: "/cli/reth/db/static-file",
+                                collapsed: true,
+                                items: [
+                                    {
+                                        text: "reth db static-file verify",
+                                        link: "/cli/reth/db/static-file/verify"
+                                    },
+                                    {
+                                        text: "reth db static-file recompress",
+                                        link: "/cli/reth/db/static-file/recompress"
+                                    },
+                                    {
+                                        text: "reth db static-file migrate-changesets",
+                                        link: "/cli/reth/db/static-file/migrate-changesets"
+                                    }
+                                ]
+                            },
                             {
                                 text: "reth db version",
                                 link: "/cli/reth/db/version"
@@ -440,6 +459,10 @@ export const sidebar: SidebarItem[] = [
                                         link: "/cli/reth/stage/unwind/num-blocks"
                                     }
                                 ]
+                            },
+                            {
+                                text: "reth stage bench",
+                                link: "/cli/reth/stage/bench"
                             }
                         ]
                     },
@@ -510,6 +533,43 @@ export const sidebar: SidebarItem[] = [
                     {
                         text: "reth prune",
                         link: "/cli/reth/prune"
+                    },
+                    {
+                        text: "reth exex",
+                        link: "/cli/reth/exex",
+                        collapsed: true,
+                        items: [
+                            {
+                                text: "reth exex wal",
+                                link: "/cli/reth/exex/wal",
+                                collapsed: true,
+                                items: [
+                                    {
+                                        text: "reth exex wal inspect",
+                                        link: "/cli/reth/exex/wal/inspect"
+                                    },
+                                    {
+                                        text: "reth exex wal truncate",
+                                        link: "/cli/reth/exex/wal/truncate"
+                                    },
+                                    {
+                                        text: "reth exex wal export",
+                                        link: "/cli/reth/exex/wal/export"
+                                    }
+                                ]
+                            }
+                        ]
+                    },
+                    {
+                        text: "reth engine",
+                        link: "/cli/reth/engine",
+                        collapsed: true,
+                        items: [
+                            {
+                                text: "reth engine replay",
+                                link: "/cli/reth/engine/replay"
+                            }
+                        ]
                     }
                 ]
             }